@@ -0,0 +1,47 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ludtwig_parser::parse;
+
+/// Template with deeply nested html, twig blocks and expressions, which produces a lot of
+/// parser events and syntax nodes (doesn't need any fixture files).
+fn generate_template(block_count: usize) -> String {
+    let mut template = String::from("{% sw_extends '@Storefront/storefront/base.html.twig' %}\n\n");
+
+    for i in 0..block_count {
+        template.push_str(&format!(
+            r#"{{% block page_block_{i} %}}
+    <div class="container container-{i}" data-index="{i}" {{% if active %}}aria-current="page"{{% endif %}}>
+        {{# some comment #}}
+        <ul>
+            {{% for item in items|filter(i => i.active) %}}
+                <li class="item {{{{ loop.index is even ? 'even' : 'odd' }}}}">
+                    <a href="{{{{ path('frontend.detail.page', {{ productId: item.id }}) }}}}" title="{{{{ item.name|e('html_attr') }}}}">
+                        {{{{ item.translated.name|default('fallback')|upper }}}} - {{{{ (item.price * 1.19)|round(2) }}}}
+                    </a>
+                </li>
+            {{% endfor %}}
+        </ul>
+        {{% set counter = counter + {i} %}}
+        <input type="text" name="field-{i}" value="{{{{ counter }}}}" disabled>
+    </div>
+{{% endblock %}}
+
+"#
+        ));
+    }
+
+    template
+}
+
+fn parsing_generated_benchmark(c: &mut Criterion) {
+    let input = generate_template(200);
+
+    c.bench_function("parsing generated template", |b| {
+        b.iter(|| {
+            let result = parse(black_box(&input));
+            black_box(result)
+        })
+    });
+}
+
+criterion_group!(benches, parsing_generated_benchmark);
+criterion_main!(benches);
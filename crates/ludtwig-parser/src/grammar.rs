@@ -1,5 +1,5 @@
 use crate::grammar::html::parse_any_html;
-use crate::grammar::twig::parse_any_twig;
+use crate::grammar::twig::{parse_any_twig, parse_twig_expression};
 use crate::parser::event::{CompletedMarker, Marker};
 use crate::parser::{ParseErrorBuilder, Parser};
 use crate::syntax::untyped::SyntaxKind;
@@ -33,6 +33,52 @@ pub(super) fn root(parser: &mut Parser) -> CompletedMarker {
     parser.complete(m, SyntaxKind::ROOT)
 }
 
+/// Root for [`crate::parse_expression`], a single twig expression without delimiters
+pub(super) fn expression_root(parser: &mut Parser) -> CompletedMarker {
+    let m = parser.start();
+
+    if parse_twig_expression(parser).is_some() {
+        parse_remaining_as_error(parser, Some("end of twig expression"));
+    } else {
+        parser.add_error(ParseErrorBuilder::new("twig expression"));
+        parse_remaining_as_error(parser, None);
+    }
+
+    parser.complete(m, SyntaxKind::ROOT)
+}
+
+/// Root for [`crate::parse_twig_block_content`], the tokens are already wrapped
+/// in (empty) `{%` and `%}` delimiters
+pub(super) fn twig_block_content_root(parser: &mut Parser) -> CompletedMarker {
+    let m = parser.start();
+
+    if parse_any_twig(parser, parse_any_element).is_some() {
+        parse_remaining_as_error(parser, Some("end of twig tag"));
+    } else {
+        // the tag parser already reported the error
+        parse_remaining_as_error(parser, None);
+    }
+
+    parser.complete(m, SyntaxKind::ROOT)
+}
+
+/// Wraps all remaining tokens inside an error node and reports the expected description (if any)
+fn parse_remaining_as_error(parser: &mut Parser, expected: Option<&str>) {
+    if parser.at_end() {
+        return;
+    }
+
+    if let Some(expected) = expected {
+        parser.add_error(ParseErrorBuilder::new(expected));
+    }
+
+    let error_m = parser.start();
+    while !parser.at_end() {
+        parser.bump();
+    }
+    parser.complete(error_m, SyntaxKind::ERROR);
+}
+
 fn parse_many<E, P>(parser: &mut Parser, mut early_exit_closure: E, mut child_parser: P)
 where
     E: FnMut(&mut Parser) -> bool,
@@ -97,8 +143,9 @@ mod tests {
     use crate::lex;
     use expect_test::expect;
 
-    use crate::parser::{check_parse, Parser};
+    use crate::parser::Parser;
     use crate::syntax::untyped::SyntaxKind;
+    use crate::testing::check_parse;
 
     #[test]
     fn parse_synthetic_minimal() {
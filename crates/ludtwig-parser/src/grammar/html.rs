@@ -1,5 +1,6 @@
 use crate::grammar::twig::{at_twig_termination_tag, parse_any_twig, parse_twig_var_statement};
 use crate::grammar::{parse_any_element, parse_ludtwig_directive, parse_many, ParseFunction};
+use crate::lexer::Token;
 use crate::parser::event::{CompletedMarker, Marker};
 use crate::parser::{ParseErrorBuilder, Parser, GENERAL_RECOVERY_SET};
 use crate::syntax::untyped::SyntaxKind;
@@ -9,7 +10,7 @@ use regex::Regex;
 
 // Every token value that matches this regex is allowed for html attribute names
 static HTML_ATTRIBUTE_NAME_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"^([a-zA-Z]|([:@\#_\$][a-zA-Z]))[a-zA-Z0-9_\-]*$").unwrap());
+    Lazy::new(|| Regex::new(r"^([a-zA-Z]|([_\$][a-zA-Z]))[a-zA-Z0-9_\-]*$").unwrap());
 
 static HTML_TAG_NAME_REGEX: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"^[a-zA-Z][a-zA-Z0-9\-]*$").unwrap());
@@ -19,11 +20,169 @@ static HTML_VOID_ELEMENTS: &[&str] = &[
     "meta", "param", "source", "track", "wbr",
 ];
 
+/// Void elements never have children or an end tag (tag names are case-insensitive in HTML)
+fn is_html_void_element(tag_name: &str) -> bool {
+    HTML_VOID_ELEMENTS
+        .iter()
+        .any(|void_element| void_element.eq_ignore_ascii_case(tag_name))
+}
+
+/// Elements whose ending tag may be omitted, together with the starting tags that implicitly close them.
+/// Any ending tag of another element (most likely the parent) also closes them
+/// (see "generate implied end tags" in the HTML spec).
+static HTML_IMPLIED_END_TAG_ELEMENTS: &[(&str, &[&str])] = &[
+    ("li", &["li"]),
+    (
+        "p",
+        &[
+            "address",
+            "article",
+            "aside",
+            "blockquote",
+            "details",
+            "div",
+            "dl",
+            "fieldset",
+            "figcaption",
+            "figure",
+            "footer",
+            "form",
+            "h1",
+            "h2",
+            "h3",
+            "h4",
+            "h5",
+            "h6",
+            "header",
+            "hgroup",
+            "hr",
+            "main",
+            "menu",
+            "nav",
+            "ol",
+            "p",
+            "pre",
+            "section",
+            "table",
+            "ul",
+        ],
+    ),
+    ("tr", &["tr", "tbody", "thead", "tfoot"]),
+    ("td", &["td", "th", "tr"]),
+    ("th", &["td", "th", "tr"]),
+    ("dt", &["dt", "dd"]),
+    ("dd", &["dt", "dd"]),
+    ("option", &["option", "optgroup"]),
+];
+
+fn get_implied_end_tag_closers(tag_name: &str) -> Option<&'static [&'static str]> {
+    HTML_IMPLIED_END_TAG_ELEMENTS
+        .iter()
+        .find(|(element, _)| element.eq_ignore_ascii_case(tag_name))
+        .map(|(_, closers)| *closers)
+}
+
+/// Twig tags which need a matching `{% end... %}` tag (`set` is left out, because it may be used without one)
+static TWIG_BLOCK_TAGS: &[&str] = &[
+    "apply",
+    "autoescape",
+    "block",
+    "cache",
+    "embed",
+    "filter",
+    "for",
+    "guard",
+    "if",
+    "macro",
+    "sandbox",
+    "spaceless",
+    "sw_silent_feature_call",
+    "trans",
+    "verbatim",
+    "with",
+];
+
+/// Is the parser at a starting tag or foreign ending tag which implicitly closes the current element
+fn at_implied_end_tag(parser: &mut Parser, tag_name: &str, closers: &[&str]) -> bool {
+    if parser.at(T!["</"]) {
+        return true; // the matching ending tag is checked before, so this must be another one
+    }
+
+    if !parser.at(T!["<"]) {
+        return false;
+    }
+
+    let at_closer = parser
+        .peek_nth_token(1)
+        .is_some_and(|t| closers.iter().any(|c| c.eq_ignore_ascii_case(t.text)));
+
+    // browsers would still close the element here and turn the stray ending tag into an
+    // empty element, but that ending tag is accepted instead of letting it close the parent
+    at_closer && !at_following_end_tag(parser, tag_name)
+}
+
+/// Does the ending tag of `tag_name` follow on the current nesting level (before the parent
+/// element or twig block ends)? Only the next `MAX_LOOKAHEAD` tokens are searched.
+fn at_following_end_tag(parser: &mut Parser, tag_name: &str) -> bool {
+    let mut html_depth: usize = 0;
+    let mut twig_depth: usize = 0;
+    let mut in_starting_tag = false;
+    let mut tokens = parser.following_tokens().peekable();
+
+    while let Some(token) = tokens.next() {
+        match token.kind {
+            T!["<"] => {
+                in_starting_tag = tokens.peek().is_some_and(|t| !is_html_void_element(t.text));
+                if in_starting_tag {
+                    html_depth += 1;
+                }
+            }
+            T![">"] => in_starting_tag = false,
+            T!["/>"] if in_starting_tag => {
+                html_depth -= 1;
+                in_starting_tag = false;
+            }
+            T!["</"] => {
+                let Some(name) = tokens.next() else {
+                    return false;
+                };
+                if html_depth == 0 {
+                    return name.text.eq_ignore_ascii_case(tag_name);
+                }
+                html_depth -= 1;
+            }
+            T!["{%"] => match tokens.peek().map(|t| t.text) {
+                Some(name) if name.starts_with("end") || name == "else" || name == "elseif" => {
+                    if twig_depth == 0 {
+                        return false;
+                    }
+                    if name.starts_with("end") {
+                        twig_depth -= 1;
+                    }
+                }
+                Some(name) if TWIG_BLOCK_TAGS.contains(&name) => twig_depth += 1,
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    false
+}
+
 pub(super) fn parse_any_html(parser: &mut Parser) -> Option<CompletedMarker> {
     if parser.at(T!["<"]) {
         Some(parse_html_element(parser))
+    } else if parser.at_following(&[T!["<!--"], T!["["], T!["if"]])
+        || parser.at_following(&[T!["<!"], T!["["], T!["if"]])
+    {
+        Some(parse_html_conditional_comment(parser))
     } else if parser.at(T!["<!--"]) {
         Some(parse_html_comment(parser))
+    } else if at_cdata_start(parser) {
+        Some(parse_html_cdata(parser))
+    } else if parser.at(T!["<?"]) {
+        Some(parse_html_xml_prolog(parser))
     } else if parser.at(T!["<!"]) {
         Some(parse_html_doctype(parser))
     } else {
@@ -43,6 +202,80 @@ fn parse_html_doctype(parser: &mut Parser) -> CompletedMarker {
     parser.complete(m, SyntaxKind::HTML_DOCTYPE)
 }
 
+fn parse_html_xml_prolog(parser: &mut Parser) -> CompletedMarker {
+    debug_assert!(parser.at(T!["<?"]));
+    let m = parser.start();
+    parser.bump();
+
+    parser.expect(T![word], &[T!["?>"]]);
+
+    let attributes_m = parser.start();
+    parse_many(
+        parser,
+        |p| p.at(T!["?>"]),
+        |p| {
+            parse_html_attribute_or_twig(p);
+        },
+    );
+    parser.complete(attributes_m, SyntaxKind::HTML_ATTRIBUTE_LIST);
+
+    parser.expect(T!["?>"], &[]);
+    parser.complete(m, SyntaxKind::HTML_XML_PROLOG)
+}
+
+fn parse_html_cdata(parser: &mut Parser) -> CompletedMarker {
+    debug_assert!(at_cdata_start(parser));
+    let m = parser.start();
+    parser.bump_next_n_as(4, T!["<![CDATA["]);
+
+    parse_many(parser, at_cdata_end, |p| {
+        parse_cdata_content(p);
+    });
+
+    if at_cdata_end(parser) {
+        parser.bump_next_n_as(3, T!["]]>"]);
+    } else {
+        parser.add_error(ParseErrorBuilder::new("]]>"));
+    }
+    parser.complete(m, SyntaxKind::HTML_CDATA)
+}
+
+/// Is the parser at `<![CDATA[` (as single tokens without any trivia in between)
+fn at_cdata_start(parser: &mut Parser) -> bool {
+    parser.at(T!["<!"])
+        && parser.peek_nth_token(1).is_some_and(|t| t.kind == T!["["])
+        && parser.peek_nth_token(2).is_some_and(|t| t.text == "CDATA")
+        && parser.peek_nth_token(3).is_some_and(|t| t.kind == T!["["])
+}
+
+/// Is the parser at `]]>` (as single tokens without any trivia in between)
+fn at_cdata_end(parser: &mut Parser) -> bool {
+    parser.at(T!["]"])
+        && parser.peek_nth_token(1).is_some_and(|t| t.kind == T!["]"])
+        && parser.peek_nth_token(2).is_some_and(|t| t.kind == T![">"])
+}
+
+/// The content of CDATA sections is plain text, only twig syntax is parsed
+fn parse_cdata_content(parser: &mut Parser) -> Option<CompletedMarker> {
+    if let Some(twig) = parse_any_twig(parser, parse_cdata_content) {
+        return Some(twig);
+    }
+
+    if parser.at_end() || parser.at_set(&[T!["{%"], T!["{{"], T!["{#"]]) || at_cdata_end(parser) {
+        return None;
+    }
+
+    let m = parser.start();
+    parse_many(
+        parser,
+        |p| p.at_set(&[T!["{%"], T!["{{"], T!["{#"]]) || at_cdata_end(p),
+        |p| {
+            p.bump();
+        },
+    );
+    Some(parser.complete(m, SyntaxKind::HTML_TEXT))
+}
+
 fn parse_html_text(parser: &mut Parser) -> Option<CompletedMarker> {
     if parser.at_end() || parser.at_set(GENERAL_RECOVERY_SET) || parser.at_set(&[T!["</"]]) {
         return None;
@@ -73,6 +306,62 @@ fn parse_html_comment(parser: &mut Parser) -> CompletedMarker {
     }
 }
 
+/// Parses the (downlevel-hidden and downlevel-revealed) conditional comments
+/// `<!--[if IE]> ... <![endif]-->`, `<![if !IE]> ... <![endif]>` and `<!--[if !IE]><!--> ... <!--<![endif]-->`
+fn parse_html_conditional_comment(parser: &mut Parser) -> CompletedMarker {
+    debug_assert!(parser.at_set(&[T!["<!--"], T!["<!"]]));
+    let m = parser.start();
+    let is_hidden = parser.at(T!["<!--"]);
+    parser.bump();
+    parser.bump(); // [
+    parser.bump(); // if
+
+    // the condition like `lt IE 9` or `!IE`
+    parse_many(
+        parser,
+        |p| p.at_set(&[T!["]"], T![">"]]),
+        |p| {
+            p.bump();
+        },
+    );
+    parser.expect(T!["]"], &[T![">"]]);
+    if is_hidden && parser.at_following(&[T![">"], T!["<!--"], T![">"]]) {
+        // the content is revealed for all other browsers in `<!--[if !IE]><!-->`
+        parser.bump();
+        parser.bump();
+    }
+    parser.expect(T![">"], &[]);
+
+    let body_m = parser.start();
+    parse_many(
+        parser,
+        |p| {
+            p.at_following(&[T!["<!"], T!["["], T!["endif"]])
+                || p.at_following(&[T!["<!--"], T!["<!"], T!["["], T!["endif"]])
+                || at_twig_termination_tag(p)
+        },
+        |p| {
+            parse_any_element(p);
+        },
+    );
+    parser.complete(body_m, SyntaxKind::BODY);
+
+    if parser.at_following(&[T!["<!--"], T!["<!"]]) {
+        parser.bump();
+    }
+    parser.expect(T!["<!"], &[T!["endif"], T!["]"], T!["-->"], T![">"]]);
+    parser.expect(T!["["], &[T!["endif"], T!["]"], T!["-->"], T![">"]]);
+    parser.expect(T!["endif"], &[T!["]"], T!["-->"], T![">"]]);
+    parser.expect(T!["]"], &[T!["-->"], T![">"]]);
+    if parser.at(T![">"]) {
+        parser.bump();
+    } else {
+        parser.expect(T!["-->"], &[]);
+    }
+
+    parser.complete(m, SyntaxKind::HTML_CONDITIONAL_COMMENT)
+}
+
 fn parse_plain_html_comment(parser: &mut Parser, outer: Marker) -> CompletedMarker {
     parse_many(
         parser,
@@ -99,14 +388,14 @@ fn parse_html_element(parser: &mut Parser) -> CompletedMarker {
         parser.bump_as(T![word]);
     } else {
         parser.add_error(ParseErrorBuilder::new("HTML Tag Name"));
-        parser.recover(&[T![">"], T!["/>"], T!["</"], T![word], T![">"]]);
+        parser.recover(&[T![">"], T!["/"], T!["</"], T![word], T![">"]]);
     }
 
     // parse attributes (can include twig)
     let attributes_m = parser.start();
     parse_many(
         parser,
-        |p| p.at(T![">"]) || p.at(T!["/>"]),
+        |p| p.at(T![">"]) || at_self_closing_end(p),
         |p| {
             parse_html_attribute_or_twig(p);
         },
@@ -114,20 +403,37 @@ fn parse_html_element(parser: &mut Parser) -> CompletedMarker {
     parser.complete(attributes_m, SyntaxKind::HTML_ATTRIBUTE_LIST);
 
     // parse end of starting tag
-    let mut is_self_closing = if parser.at(T!["/>"]) {
-        parser.bump();
+    let mut is_self_closing = if at_self_closing_end(parser) {
+        parser.bump_next_n_as(2, T!["/>"]);
         true
     } else {
         parser.expect(T![">"], &[T!["</"], T![word], T![">"]]);
         false
     };
 
-    if HTML_VOID_ELEMENTS.contains(&&*tag_name) {
+    let is_void_element = is_html_void_element(&tag_name);
+    if is_void_element {
         is_self_closing = true; // void elements never have children or an end tag
     }
 
     parser.complete(starting_tag_m, SyntaxKind::HTML_STARTING_TAG);
 
+    // tolerate a (not needed) ending tag directly behind a void element like `<br></br>`
+    // instead of letting it break the closing tag of the parent
+    if is_void_element
+        && parser.at(T!["</"])
+        && parser
+            .peek_nth_token(1)
+            .is_some_and(|t| t.text.eq_ignore_ascii_case(&tag_name))
+    {
+        let end_tag_m = parser.start();
+        parser.bump();
+        parser.bump_as(T![word]);
+        parser.expect(T![">"], &[]);
+        parser.complete(end_tag_m, SyntaxKind::HTML_ENDING_TAG);
+        return parser.complete(m, SyntaxKind::HTML_TAG);
+    }
+
     // early return in case of self closing
     if is_self_closing {
         return parser.complete(m, SyntaxKind::HTML_TAG);
@@ -136,11 +442,13 @@ fn parse_html_element(parser: &mut Parser) -> CompletedMarker {
     // parse all the children
     let body_m = parser.start();
     let mut matching_end_tag_encountered = false;
+    let mut implied_end_tag_encountered = false;
+    let implied_end_tag_closers = get_implied_end_tag_closers(&tag_name);
 
     parse_many(
         parser,
         |p| {
-            if p.at_following_content(&[(T!["</"], None), (T![word], Some(&tag_name))]) {
+            if at_matching_end_tag(p, &tag_name) {
                 matching_end_tag_encountered = true;
                 return true; // found matching closing tag
             }
@@ -149,6 +457,13 @@ fn parse_html_element(parser: &mut Parser) -> CompletedMarker {
                 return true; // endblock in the wild may mean this tag has a missing closing tag
             }
 
+            if implied_end_tag_closers
+                .is_some_and(|closers| at_implied_end_tag(p, &tag_name, closers))
+            {
+                implied_end_tag_encountered = true;
+                return true;
+            }
+
             false
         },
         |p| {
@@ -157,12 +472,18 @@ fn parse_html_element(parser: &mut Parser) -> CompletedMarker {
     );
     parser.complete(body_m, SyntaxKind::BODY);
 
+    // the ending tag of these elements is optional and implied by the following starting
+    // tag or ending tag of the parent, so there is no ending tag to parse
+    if implied_end_tag_encountered {
+        return parser.complete(m, SyntaxKind::HTML_TAG);
+    }
+
     // parse matching end tag or report missing (the tag itself is not self closing!)
     let end_tag_m = parser.start();
     if matching_end_tag_encountered {
         // found matching closing tag
         parser.expect(T!["</"], &[T![word], T![">"]]);
-        parser.expect(T![word], &[T![">"]]);
+        parser.bump_as(T![word]);
         parser.expect(T![">"], &[]);
     } else {
         // no matching end tag found!
@@ -174,22 +495,22 @@ fn parse_html_element(parser: &mut Parser) -> CompletedMarker {
     parser.complete(m, SyntaxKind::HTML_TAG)
 }
 
+/// Is the parser at `</tag_name>`, the name may also be lexed as a twig keyword (like `style`)
+fn at_matching_end_tag(parser: &mut Parser, tag_name: &str) -> bool {
+    parser.at(T!["</"]) && parser.peek_nth_token(1).is_some_and(|t| t.text == tag_name)
+}
+
 fn parse_html_attribute_or_twig(parser: &mut Parser) -> Option<CompletedMarker> {
-    let token_text = if parser.at(T![":"]) {
-        format!(":{}", parser.peek_nth_token(1)?.text)
-    } else {
-        parser.peek_token()?.text.to_owned()
-    };
+    let token_text = parser.peek_token()?.text.to_owned();
 
-    let attribute_m = if HTML_ATTRIBUTE_NAME_REGEX.is_match(&token_text) {
+    let attribute_m = if at_vue_directive(parser) {
+        let attribute_m = parser.start();
+        parse_vue_directive(parser);
+        attribute_m
+    } else if HTML_ATTRIBUTE_NAME_REGEX.is_match(&token_text) {
         // normal html attribute name
         let attribute_m = parser.start();
-        if parser.at(T![":"]) {
-            parser.bump_next_n_as(2, T![word]);
-        } else {
-            parser.bump_as(T![word]);
-        }
-
+        parser.bump_as(T![word]);
         attribute_m
     } else {
         // is the attribute name a twig var expression?
@@ -213,7 +534,110 @@ fn parse_html_attribute_or_twig(parser: &mut Parser) -> Option<CompletedMarker>
     Some(parser.complete(attribute_m, SyntaxKind::HTML_ATTRIBUTE))
 }
 
-/// html attribute value can be either a single word or twig var expression or
+/// Does the token start with an alpha character (keywords like `if` are also allowed as names in html)
+fn is_word_like(token: Option<&Token>) -> bool {
+    token.is_some_and(|t| t.text.starts_with(|c: char| c.is_ascii_alphabetic()))
+}
+
+/// Is the parser at a vue directive like `v-if`, `v-on:click` or one of the
+/// shorthands `:prop` (v-bind), `@click` (v-on) and `#slot` (v-slot)
+fn at_vue_directive(parser: &mut Parser) -> bool {
+    if parser.at_set(&[T![":"], T!["@"], T!["#"]]) {
+        // the argument must follow directly after the shorthand
+        let next = parser.peek_nth_token(1);
+        return next.is_some_and(|t| t.kind == T!["["]) || is_word_like(next);
+    }
+
+    parser
+        .peek_token()
+        .is_some_and(|t| t.text.len() > 2 && t.text.starts_with("v-"))
+}
+
+fn parse_vue_directive(parser: &mut Parser) -> CompletedMarker {
+    debug_assert!(at_vue_directive(parser));
+    let m = parser.start();
+
+    if parser.at_set(&[T![":"], T!["@"], T!["#"]]) {
+        parser.bump();
+        parse_vue_directive_argument(parser);
+    } else {
+        // full directive name like `v-on`
+        parser.bump_as(T![word]);
+        if parser.at(T![":"]) && !parser.at_preceding_trivia() {
+            parser.bump();
+            parse_vue_directive_argument(parser);
+        }
+    }
+
+    while parser.at(T!["."]) && !parser.at_preceding_trivia() {
+        let modifier_m = parser.start();
+        parser.bump();
+        if !parser.at_preceding_trivia() && is_word_like(parser.peek_token()) {
+            parser.bump_as(T![word]);
+        } else {
+            parser.add_error(ParseErrorBuilder::new("vue directive modifier name"));
+        }
+        parser.complete(modifier_m, SyntaxKind::VUE_DIRECTIVE_MODIFIER);
+    }
+
+    parser.complete(m, SyntaxKind::VUE_DIRECTIVE)
+}
+
+/// The argument directly follows the directive prefix and ends at the first modifier or the value.
+/// It may contain colons like in `@update:model-value`.
+fn parse_vue_directive_argument(parser: &mut Parser) -> CompletedMarker {
+    let m = parser.start();
+
+    if parser.at(T!["["]) {
+        // dynamic argument like `:[key]`
+        parser.bump();
+        parse_many(
+            parser,
+            |p| p.at(T!["]"]) || p.at_preceding_trivia(),
+            |p| {
+                p.bump();
+            },
+        );
+        parser.expect(T!["]"], &[T!["="], T![">"], T!["/"]]);
+    } else {
+        parse_many(
+            parser,
+            |p| {
+                p.at_preceding_trivia()
+                    || p.at_set(&[T!["."], T!["="], T![">"]])
+                    || at_self_closing_end(p)
+                    || p.at_set(GENERAL_RECOVERY_SET)
+            },
+            |p| {
+                if is_word_like(p.peek_token()) {
+                    p.bump_as(T![word]);
+                } else {
+                    p.bump();
+                }
+            },
+        );
+    }
+
+    parser.complete(m, SyntaxKind::VUE_DIRECTIVE_ARGUMENT)
+}
+
+/// Is the parser at the `/>` end of a self-closing starting tag (lexed as two tokens)
+fn at_self_closing_end(parser: &mut Parser) -> bool {
+    parser.at(T!["/"]) && parser.peek_nth_token(1).is_some_and(|t| t.kind == T![">"])
+}
+
+/// Is the parser at a token which ends an unquoted html attribute value
+fn at_unquoted_value_end(parser: &mut Parser) -> bool {
+    if parser.at(T!["{{"]) {
+        return false;
+    }
+
+    parser.at_end()
+        || parser.at_set(&[T![">"], T!["?>"], T!["\""], T!["'"], T!["</"]])
+        || parser.at_set(GENERAL_RECOVERY_SET)
+}
+
+/// html attribute value can be either an unquoted value (until the next whitespace, may contain twig var expressions) or
 /// a single / double quoted string (which can contain arbitrary twig syntax)
 /// In either case it will be wrapped into an `HTML_STRING` node which may or may
 /// not contain quotes
@@ -249,14 +673,24 @@ fn parse_html_attribute_value_string(parser: &mut Parser) -> CompletedMarker {
     }
 
     fn inner_no_quote_parser(parser: &mut Parser) -> Option<CompletedMarker> {
-        if parser.at(T![word]) {
-            parser.bump();
-        } else if parser.at(T!["{{"]) {
-            // a single twig var expression with missing quotes should also count as an html attribute value
-            parse_twig_var_statement(parser);
-        } else {
+        if at_unquoted_value_end(parser) && !parser.at(T!["{{"]) {
             parser.add_error(ParseErrorBuilder::new("html attribute value"));
-            parser.recover(&[T![word], T![">"], T!["/>"]]);
+            parser.recover(&[T![word], T![">"], T!["/"]]);
+            return None;
+        }
+
+        // an unquoted value goes on until the next whitespace or the end of the tag
+        loop {
+            if parser.at(T!["{{"]) {
+                // twig var expressions with missing quotes should also count as an html attribute value
+                parse_twig_var_statement(parser);
+            } else {
+                parser.bump();
+            }
+
+            if parser.at_preceding_trivia() || at_unquoted_value_end(parser) {
+                break;
+            }
         }
 
         None
@@ -272,7 +706,11 @@ fn parse_html_attribute_value_string(parser: &mut Parser) -> CompletedMarker {
 
     fn child_parser(p: &mut Parser, inner_twig_child_parser: ParseFunction) {
         if parse_any_twig(p, inner_twig_child_parser).is_none() {
-            if p.at_set(&[T![">"], T!["/>"]]) || p.at_set(GENERAL_RECOVERY_SET) || p.at_end() {
+            if p.at(T![">"])
+                || at_self_closing_end(p)
+                || p.at_set(GENERAL_RECOVERY_SET)
+                || p.at_end()
+            {
                 return;
             }
 
@@ -286,7 +724,7 @@ fn parse_html_attribute_value_string(parser: &mut Parser) -> CompletedMarker {
         Some(starting_quote_token.kind)
     } else {
         // the HTML specification also allows no quotes but then
-        // the value must only be a single word
+        // the value ends at the next whitespace
         None
     };
 
@@ -315,7 +753,7 @@ fn parse_html_attribute_value_string(parser: &mut Parser) -> CompletedMarker {
 
     // expect the same closing quote if a starting quote existed
     if let Some(quote_kind) = quote_kind {
-        parser.expect(quote_kind, &[T![">"], T!["/>"]]);
+        parser.expect(quote_kind, &[T![">"], T!["/"]]);
     } else {
         // check for unexpected quote which this parser still consumes to make missing leading quote errors simpler
         if parser.at_set(&[T!["\""], T!["'"]]) {
@@ -336,7 +774,7 @@ fn parse_html_attribute_value_string(parser: &mut Parser) -> CompletedMarker {
 mod tests {
     use expect_test::expect;
 
-    use crate::parser::check_parse;
+    use crate::testing::check_parse;
 
     #[test]
     fn parse_simple_html_element() {
@@ -358,6 +796,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_html_element_with_twig_keyword_name() {
+        check_parse(
+            "<style>.a { color: red; }</style>",
+            expect![[r#"
+                ROOT@0..33
+                  HTML_TAG@0..33
+                    HTML_STARTING_TAG@0..7
+                      TK_LESS_THAN@0..1 "<"
+                      TK_WORD@1..6 "style"
+                      HTML_ATTRIBUTE_LIST@6..6
+                      TK_GREATER_THAN@6..7 ">"
+                    BODY@7..25
+                      HTML_TEXT@7..25
+                        TK_DOT@7..8 "."
+                        TK_WORD@8..9 "a"
+                        TK_WHITESPACE@9..10 " "
+                        TK_OPEN_CURLY@10..11 "{"
+                        TK_WHITESPACE@11..12 " "
+                        TK_WORD@12..17 "color"
+                        TK_COLON@17..18 ":"
+                        TK_WHITESPACE@18..19 " "
+                        TK_WORD@19..22 "red"
+                        TK_SEMICOLON@22..23 ";"
+                        TK_WHITESPACE@23..24 " "
+                        TK_CLOSE_CURLY@24..25 "}"
+                    HTML_ENDING_TAG@25..33
+                      TK_LESS_THAN_SLASH@25..27 "</"
+                      TK_WORD@27..32 "style"
+                      TK_GREATER_THAN@32..33 ">""#]],
+        );
+    }
+
     #[test]
     fn parse_html_element_with_attributes() {
         check_parse(
@@ -690,9 +1161,7 @@ mod tests {
                       TK_LESS_THAN_SLASH@29..31 "</"
                       TK_WORD@31..34 "div"
                       TK_GREATER_THAN@34..35 ">"
-                error at 29..31: expected {% but found </
-                error at 29..31: expected endblock but found </
-                error at 29..31: expected %} but found </"#]],
+                error at 29..31: expected {% or endblock or %} but found </"#]],
         );
     }
 
@@ -736,10 +1205,7 @@ mod tests {
                         TWIG_ENDING_BLOCK@39..39
                     HTML_ENDING_TAG@39..39
                 error at 33..35: expected </span> ending tag but found </
-                error at 38..39: expected {% but reached end of file
-                error at 38..39: expected endblock but reached end of file
-                error at 38..39: expected %} but reached end of file
-                error at 38..39: expected </div> ending tag but reached end of file"#]],
+                error at 38..39: expected {% or endblock or %} or </div> ending tag but reached end of file"#]],
         );
     }
 
@@ -1258,6 +1724,138 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_html_attribute_with_no_quotes_and_special_characters() {
+        check_parse(
+            "<input type=text disabled value=1 data-x=a.b/c:{{ d }}?e=5px><a href=/foo/bar/>",
+            expect![[r#"
+                ROOT@0..79
+                  HTML_TAG@0..61
+                    HTML_STARTING_TAG@0..61
+                      TK_LESS_THAN@0..1 "<"
+                      TK_WORD@1..6 "input"
+                      HTML_ATTRIBUTE_LIST@6..60
+                        HTML_ATTRIBUTE@6..16
+                          TK_WHITESPACE@6..7 " "
+                          TK_WORD@7..11 "type"
+                          TK_EQUAL@11..12 "="
+                          HTML_STRING@12..16
+                            HTML_STRING_INNER@12..16
+                              TK_WORD@12..16 "text"
+                        HTML_ATTRIBUTE@16..25
+                          TK_WHITESPACE@16..17 " "
+                          TK_WORD@17..25 "disabled"
+                        HTML_ATTRIBUTE@25..33
+                          TK_WHITESPACE@25..26 " "
+                          TK_WORD@26..31 "value"
+                          TK_EQUAL@31..32 "="
+                          HTML_STRING@32..33
+                            HTML_STRING_INNER@32..33
+                              TK_NUMBER@32..33 "1"
+                        HTML_ATTRIBUTE@33..60
+                          TK_WHITESPACE@33..34 " "
+                          TK_WORD@34..40 "data-x"
+                          TK_EQUAL@40..41 "="
+                          HTML_STRING@41..60
+                            HTML_STRING_INNER@41..60
+                              TK_WORD@41..42 "a"
+                              TK_DOT@42..43 "."
+                              TK_WORD@43..44 "b"
+                              TK_FORWARD_SLASH@44..45 "/"
+                              TK_WORD@45..46 "c"
+                              TK_COLON@46..47 ":"
+                              TWIG_VAR@47..54
+                                TK_OPEN_CURLY_CURLY@47..49 "{{"
+                                TWIG_EXPRESSION@49..51
+                                  TWIG_LITERAL_NAME@49..51
+                                    TK_WHITESPACE@49..50 " "
+                                    TK_WORD@50..51 "d"
+                                TK_WHITESPACE@51..52 " "
+                                TK_CLOSE_CURLY_CURLY@52..54 "}}"
+                              TK_QUESTION_MARK@54..55 "?"
+                              TK_WORD@55..56 "e"
+                              TK_EQUAL@56..57 "="
+                              TK_NUMBER@57..58 "5"
+                              TK_WORD@58..60 "px"
+                      TK_GREATER_THAN@60..61 ">"
+                  HTML_TAG@61..79
+                    HTML_STARTING_TAG@61..79
+                      TK_LESS_THAN@61..62 "<"
+                      TK_WORD@62..63 "a"
+                      HTML_ATTRIBUTE_LIST@63..78
+                        HTML_ATTRIBUTE@63..78
+                          TK_WHITESPACE@63..64 " "
+                          TK_WORD@64..68 "href"
+                          TK_EQUAL@68..69 "="
+                          HTML_STRING@69..78
+                            HTML_STRING_INNER@69..78
+                              TK_FORWARD_SLASH@69..70 "/"
+                              TK_WORD@70..73 "foo"
+                              TK_FORWARD_SLASH@73..74 "/"
+                              TK_WORD@74..77 "bar"
+                              TK_FORWARD_SLASH@77..78 "/"
+                      TK_GREATER_THAN@78..79 ">"
+                    BODY@79..79
+                    HTML_ENDING_TAG@79..79
+                error at 78..79: expected </a> ending tag but reached end of file"#]],
+        );
+    }
+
+    #[test]
+    fn parse_html_attribute_with_no_quotes_and_self_closing() {
+        // the slash is only the end of a self-closing tag when it doesn't belong to the value
+        check_parse(
+            "<img src=a.png /><input disabled/><a href=/>x</a>",
+            expect![[r#"
+                ROOT@0..49
+                  HTML_TAG@0..17
+                    HTML_STARTING_TAG@0..17
+                      TK_LESS_THAN@0..1 "<"
+                      TK_WORD@1..4 "img"
+                      HTML_ATTRIBUTE_LIST@4..14
+                        HTML_ATTRIBUTE@4..14
+                          TK_WHITESPACE@4..5 " "
+                          TK_WORD@5..8 "src"
+                          TK_EQUAL@8..9 "="
+                          HTML_STRING@9..14
+                            HTML_STRING_INNER@9..14
+                              TK_WORD@9..10 "a"
+                              TK_DOT@10..11 "."
+                              TK_WORD@11..14 "png"
+                      TK_WHITESPACE@14..15 " "
+                      TK_SLASH_GREATER_THAN@15..17 "/>"
+                  HTML_TAG@17..34
+                    HTML_STARTING_TAG@17..34
+                      TK_LESS_THAN@17..18 "<"
+                      TK_WORD@18..23 "input"
+                      HTML_ATTRIBUTE_LIST@23..32
+                        HTML_ATTRIBUTE@23..32
+                          TK_WHITESPACE@23..24 " "
+                          TK_WORD@24..32 "disabled"
+                      TK_SLASH_GREATER_THAN@32..34 "/>"
+                  HTML_TAG@34..49
+                    HTML_STARTING_TAG@34..44
+                      TK_LESS_THAN@34..35 "<"
+                      TK_WORD@35..36 "a"
+                      HTML_ATTRIBUTE_LIST@36..43
+                        HTML_ATTRIBUTE@36..43
+                          TK_WHITESPACE@36..37 " "
+                          TK_WORD@37..41 "href"
+                          TK_EQUAL@41..42 "="
+                          HTML_STRING@42..43
+                            HTML_STRING_INNER@42..43
+                              TK_FORWARD_SLASH@42..43 "/"
+                      TK_GREATER_THAN@43..44 ">"
+                    BODY@44..45
+                      HTML_TEXT@44..45
+                        TK_WORD@44..45 "x"
+                    HTML_ENDING_TAG@45..49
+                      TK_LESS_THAN_SLASH@45..47 "</"
+                      TK_WORD@47..48 "a"
+                      TK_GREATER_THAN@48..49 ">""#]],
+        );
+    }
+
     #[test]
     fn parse_html_attribute_with_no_quotes() {
         check_parse(
@@ -1335,6 +1933,169 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_html_conditional_comment() {
+        check_parse(
+            "<!--[if lt IE 9]><script src=\"html5shiv.js\"></script><![endif]-->",
+            expect![[r#"
+                ROOT@0..65
+                  HTML_CONDITIONAL_COMMENT@0..65
+                    TK_LESS_THAN_EXCLAMATION_MARK_MINUS_MINUS@0..4 "<!--"
+                    TK_OPEN_SQUARE@4..5 "["
+                    TK_IF@5..7 "if"
+                    TK_WHITESPACE@7..8 " "
+                    TK_WORD@8..10 "lt"
+                    TK_WHITESPACE@10..11 " "
+                    TK_WORD@11..13 "IE"
+                    TK_WHITESPACE@13..14 " "
+                    TK_NUMBER@14..15 "9"
+                    TK_CLOSE_SQUARE@15..16 "]"
+                    TK_GREATER_THAN@16..17 ">"
+                    BODY@17..53
+                      HTML_TAG@17..53
+                        HTML_STARTING_TAG@17..44
+                          TK_LESS_THAN@17..18 "<"
+                          TK_WORD@18..24 "script"
+                          HTML_ATTRIBUTE_LIST@24..43
+                            HTML_ATTRIBUTE@24..43
+                              TK_WHITESPACE@24..25 " "
+                              TK_WORD@25..28 "src"
+                              TK_EQUAL@28..29 "="
+                              HTML_STRING@29..43
+                                TK_DOUBLE_QUOTES@29..30 "\""
+                                HTML_STRING_INNER@30..42
+                                  TK_WORD@30..39 "html5shiv"
+                                  TK_DOT@39..40 "."
+                                  TK_WORD@40..42 "js"
+                                TK_DOUBLE_QUOTES@42..43 "\""
+                          TK_GREATER_THAN@43..44 ">"
+                        BODY@44..44
+                        HTML_ENDING_TAG@44..53
+                          TK_LESS_THAN_SLASH@44..46 "</"
+                          TK_WORD@46..52 "script"
+                          TK_GREATER_THAN@52..53 ">"
+                    TK_LESS_THAN_EXCLAMATION_MARK@53..55 "<!"
+                    TK_OPEN_SQUARE@55..56 "["
+                    TK_ENDIF@56..61 "endif"
+                    TK_CLOSE_SQUARE@61..62 "]"
+                    TK_MINUS_MINUS_GREATER_THAN@62..65 "-->""#]],
+        );
+    }
+
+    #[test]
+    fn parse_html_conditional_comment_revealed() {
+        check_parse(
+            "<![if !IE]><p>a</p><![endif]><!--[if !IE]><!--><p>b</p><!--<![endif]-->",
+            expect![[r#"
+                ROOT@0..71
+                  HTML_CONDITIONAL_COMMENT@0..29
+                    TK_LESS_THAN_EXCLAMATION_MARK@0..2 "<!"
+                    TK_OPEN_SQUARE@2..3 "["
+                    TK_IF@3..5 "if"
+                    TK_WHITESPACE@5..6 " "
+                    TK_EXCLAMATION_MARK@6..7 "!"
+                    TK_WORD@7..9 "IE"
+                    TK_CLOSE_SQUARE@9..10 "]"
+                    TK_GREATER_THAN@10..11 ">"
+                    BODY@11..19
+                      HTML_TAG@11..19
+                        HTML_STARTING_TAG@11..14
+                          TK_LESS_THAN@11..12 "<"
+                          TK_WORD@12..13 "p"
+                          HTML_ATTRIBUTE_LIST@13..13
+                          TK_GREATER_THAN@13..14 ">"
+                        BODY@14..15
+                          HTML_TEXT@14..15
+                            TK_WORD@14..15 "a"
+                        HTML_ENDING_TAG@15..19
+                          TK_LESS_THAN_SLASH@15..17 "</"
+                          TK_WORD@17..18 "p"
+                          TK_GREATER_THAN@18..19 ">"
+                    TK_LESS_THAN_EXCLAMATION_MARK@19..21 "<!"
+                    TK_OPEN_SQUARE@21..22 "["
+                    TK_ENDIF@22..27 "endif"
+                    TK_CLOSE_SQUARE@27..28 "]"
+                    TK_GREATER_THAN@28..29 ">"
+                  HTML_CONDITIONAL_COMMENT@29..71
+                    TK_LESS_THAN_EXCLAMATION_MARK_MINUS_MINUS@29..33 "<!--"
+                    TK_OPEN_SQUARE@33..34 "["
+                    TK_IF@34..36 "if"
+                    TK_WHITESPACE@36..37 " "
+                    TK_EXCLAMATION_MARK@37..38 "!"
+                    TK_WORD@38..40 "IE"
+                    TK_CLOSE_SQUARE@40..41 "]"
+                    TK_GREATER_THAN@41..42 ">"
+                    TK_LESS_THAN_EXCLAMATION_MARK_MINUS_MINUS@42..46 "<!--"
+                    TK_GREATER_THAN@46..47 ">"
+                    BODY@47..55
+                      HTML_TAG@47..55
+                        HTML_STARTING_TAG@47..50
+                          TK_LESS_THAN@47..48 "<"
+                          TK_WORD@48..49 "p"
+                          HTML_ATTRIBUTE_LIST@49..49
+                          TK_GREATER_THAN@49..50 ">"
+                        BODY@50..51
+                          HTML_TEXT@50..51
+                            TK_WORD@50..51 "b"
+                        HTML_ENDING_TAG@51..55
+                          TK_LESS_THAN_SLASH@51..53 "</"
+                          TK_WORD@53..54 "p"
+                          TK_GREATER_THAN@54..55 ">"
+                    TK_LESS_THAN_EXCLAMATION_MARK_MINUS_MINUS@55..59 "<!--"
+                    TK_LESS_THAN_EXCLAMATION_MARK@59..61 "<!"
+                    TK_OPEN_SQUARE@61..62 "["
+                    TK_ENDIF@62..67 "endif"
+                    TK_CLOSE_SQUARE@67..68 "]"
+                    TK_MINUS_MINUS_GREATER_THAN@68..71 "-->""#]],
+        );
+    }
+
+    #[test]
+    fn parse_html_conditional_comment_missing_endif() {
+        check_parse(
+            "<div><!--[if IE]><b>a</b></div>",
+            expect![[r#"
+                ROOT@0..31
+                  HTML_TAG@0..31
+                    HTML_STARTING_TAG@0..5
+                      TK_LESS_THAN@0..1 "<"
+                      TK_WORD@1..4 "div"
+                      HTML_ATTRIBUTE_LIST@4..4
+                      TK_GREATER_THAN@4..5 ">"
+                    BODY@5..31
+                      HTML_CONDITIONAL_COMMENT@5..31
+                        TK_LESS_THAN_EXCLAMATION_MARK_MINUS_MINUS@5..9 "<!--"
+                        TK_OPEN_SQUARE@9..10 "["
+                        TK_IF@10..12 "if"
+                        TK_WHITESPACE@12..13 " "
+                        TK_WORD@13..15 "IE"
+                        TK_CLOSE_SQUARE@15..16 "]"
+                        TK_GREATER_THAN@16..17 ">"
+                        BODY@17..25
+                          HTML_TAG@17..25
+                            HTML_STARTING_TAG@17..20
+                              TK_LESS_THAN@17..18 "<"
+                              TK_WORD@18..19 "b"
+                              HTML_ATTRIBUTE_LIST@19..19
+                              TK_GREATER_THAN@19..20 ">"
+                            BODY@20..21
+                              HTML_TEXT@20..21
+                                TK_WORD@20..21 "a"
+                            HTML_ENDING_TAG@21..25
+                              TK_LESS_THAN_SLASH@21..23 "</"
+                              TK_WORD@23..24 "b"
+                              TK_GREATER_THAN@24..25 ">"
+                        ERROR@25..30
+                          TK_LESS_THAN_SLASH@25..27 "</"
+                          TK_WORD@27..30 "div"
+                        TK_GREATER_THAN@30..31 ">"
+                    HTML_ENDING_TAG@31..31
+                error at 25..27: expected <! but found </
+                error at 30..31: expected [ or endif or ] but found >
+                error at 30..31: expected </div> ending tag but reached end of file"#]],
+        );
+    }
+
     #[test]
     fn test_html_self_closing_tag() {
         check_parse(
@@ -1515,23 +2276,20 @@ mod tests {
                           HTML_ATTRIBUTE_LIST@32..32
                           TK_SLASH_GREATER_THAN@32..34 "/>"
                     HTML_ENDING_TAG@34..34
-                  ERROR@34..37
+                  ERROR@34..49
                     TK_WHITESPACE@34..35 " "
                     TK_CURLY_PERCENT@35..37 "{%"
-                  HTML_TEXT@37..50
                     TK_WHITESPACE@37..38 " "
                     TK_ENDBLOCK@38..46 "endblock"
                     TK_WHITESPACE@46..47 " "
                     TK_PERCENT_CURLY@47..49 "%}"
+                  HTML_TEXT@49..50
                     TK_GREATER_THAN@49..50 ">"
                   ERROR@50..56
                     TK_LESS_THAN_SLASH@50..52 "</"
                     TK_WORD@52..55 "div"
                     TK_GREATER_THAN@55..56 ">"
-                error at 29..30: expected {% but found <
-                error at 29..30: expected endblock but found <
-                error at 29..30: expected %} but found <
-                error at 29..30: expected > but found <
+                error at 29..30: expected {% or endblock or %} or > but found <
                 error at 35..37: expected </div> ending tag but found {%
                 error at 38..46: expected twig tag but found endblock
                 error at 50..52: expected html, text or twig element but found </"#]],
@@ -1876,6 +2634,536 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_html_void_element_uppercase() {
+        check_parse(
+            r#"<div><BR><Img src="a.png"></div>"#,
+            expect![[r#"
+            ROOT@0..32
+              HTML_TAG@0..32
+                HTML_STARTING_TAG@0..5
+                  TK_LESS_THAN@0..1 "<"
+                  TK_WORD@1..4 "div"
+                  HTML_ATTRIBUTE_LIST@4..4
+                  TK_GREATER_THAN@4..5 ">"
+                BODY@5..26
+                  HTML_TAG@5..9
+                    HTML_STARTING_TAG@5..9
+                      TK_LESS_THAN@5..6 "<"
+                      TK_WORD@6..8 "BR"
+                      HTML_ATTRIBUTE_LIST@8..8
+                      TK_GREATER_THAN@8..9 ">"
+                  HTML_TAG@9..26
+                    HTML_STARTING_TAG@9..26
+                      TK_LESS_THAN@9..10 "<"
+                      TK_WORD@10..13 "Img"
+                      HTML_ATTRIBUTE_LIST@13..25
+                        HTML_ATTRIBUTE@13..25
+                          TK_WHITESPACE@13..14 " "
+                          TK_WORD@14..17 "src"
+                          TK_EQUAL@17..18 "="
+                          HTML_STRING@18..25
+                            TK_DOUBLE_QUOTES@18..19 "\""
+                            HTML_STRING_INNER@19..24
+                              TK_WORD@19..20 "a"
+                              TK_DOT@20..21 "."
+                              TK_WORD@21..24 "png"
+                            TK_DOUBLE_QUOTES@24..25 "\""
+                      TK_GREATER_THAN@25..26 ">"
+                HTML_ENDING_TAG@26..32
+                  TK_LESS_THAN_SLASH@26..28 "</"
+                  TK_WORD@28..31 "div"
+                  TK_GREATER_THAN@31..32 ">""#]],
+        );
+    }
+
+    #[test]
+    fn parse_html_void_element_with_ending_tag() {
+        check_parse(
+            "<p>a<br></br>b</p>",
+            expect![[r#"
+            ROOT@0..18
+              HTML_TAG@0..18
+                HTML_STARTING_TAG@0..3
+                  TK_LESS_THAN@0..1 "<"
+                  TK_WORD@1..2 "p"
+                  HTML_ATTRIBUTE_LIST@2..2
+                  TK_GREATER_THAN@2..3 ">"
+                BODY@3..14
+                  HTML_TEXT@3..4
+                    TK_WORD@3..4 "a"
+                  HTML_TAG@4..13
+                    HTML_STARTING_TAG@4..8
+                      TK_LESS_THAN@4..5 "<"
+                      TK_WORD@5..7 "br"
+                      HTML_ATTRIBUTE_LIST@7..7
+                      TK_GREATER_THAN@7..8 ">"
+                    HTML_ENDING_TAG@8..13
+                      TK_LESS_THAN_SLASH@8..10 "</"
+                      TK_WORD@10..12 "br"
+                      TK_GREATER_THAN@12..13 ">"
+                  HTML_TEXT@13..14
+                    TK_WORD@13..14 "b"
+                HTML_ENDING_TAG@14..18
+                  TK_LESS_THAN_SLASH@14..16 "</"
+                  TK_WORD@16..17 "p"
+                  TK_GREATER_THAN@17..18 ">""#]],
+        );
+    }
+
+    #[test]
+    fn parse_html_void_element_with_ending_tag_other_case() {
+        check_parse(
+            "<p><BR></br><br></BR></p>",
+            expect![[r#"
+                ROOT@0..25
+                  HTML_TAG@0..25
+                    HTML_STARTING_TAG@0..3
+                      TK_LESS_THAN@0..1 "<"
+                      TK_WORD@1..2 "p"
+                      HTML_ATTRIBUTE_LIST@2..2
+                      TK_GREATER_THAN@2..3 ">"
+                    BODY@3..21
+                      HTML_TAG@3..12
+                        HTML_STARTING_TAG@3..7
+                          TK_LESS_THAN@3..4 "<"
+                          TK_WORD@4..6 "BR"
+                          HTML_ATTRIBUTE_LIST@6..6
+                          TK_GREATER_THAN@6..7 ">"
+                        HTML_ENDING_TAG@7..12
+                          TK_LESS_THAN_SLASH@7..9 "</"
+                          TK_WORD@9..11 "br"
+                          TK_GREATER_THAN@11..12 ">"
+                      HTML_TAG@12..21
+                        HTML_STARTING_TAG@12..16
+                          TK_LESS_THAN@12..13 "<"
+                          TK_WORD@13..15 "br"
+                          HTML_ATTRIBUTE_LIST@15..15
+                          TK_GREATER_THAN@15..16 ">"
+                        HTML_ENDING_TAG@16..21
+                          TK_LESS_THAN_SLASH@16..18 "</"
+                          TK_WORD@18..20 "BR"
+                          TK_GREATER_THAN@20..21 ">"
+                    HTML_ENDING_TAG@21..25
+                      TK_LESS_THAN_SLASH@21..23 "</"
+                      TK_WORD@23..24 "p"
+                      TK_GREATER_THAN@24..25 ">""#]],
+        );
+    }
+
+    #[test]
+    fn parse_html_implied_end_tag_li() {
+        check_parse(
+            "<ul><li>a<li>b</ul>",
+            expect![[r#"
+            ROOT@0..19
+              HTML_TAG@0..19
+                HTML_STARTING_TAG@0..4
+                  TK_LESS_THAN@0..1 "<"
+                  TK_WORD@1..3 "ul"
+                  HTML_ATTRIBUTE_LIST@3..3
+                  TK_GREATER_THAN@3..4 ">"
+                BODY@4..14
+                  HTML_TAG@4..9
+                    HTML_STARTING_TAG@4..8
+                      TK_LESS_THAN@4..5 "<"
+                      TK_WORD@5..7 "li"
+                      HTML_ATTRIBUTE_LIST@7..7
+                      TK_GREATER_THAN@7..8 ">"
+                    BODY@8..9
+                      HTML_TEXT@8..9
+                        TK_WORD@8..9 "a"
+                  HTML_TAG@9..14
+                    HTML_STARTING_TAG@9..13
+                      TK_LESS_THAN@9..10 "<"
+                      TK_WORD@10..12 "li"
+                      HTML_ATTRIBUTE_LIST@12..12
+                      TK_GREATER_THAN@12..13 ">"
+                    BODY@13..14
+                      HTML_TEXT@13..14
+                        TK_WORD@13..14 "b"
+                HTML_ENDING_TAG@14..19
+                  TK_LESS_THAN_SLASH@14..16 "</"
+                  TK_WORD@16..18 "ul"
+                  TK_GREATER_THAN@18..19 ">""#]],
+        );
+    }
+
+    #[test]
+    fn parse_html_implied_end_tag_p() {
+        check_parse(
+            "<p>a<div>b</div><p>c</p>",
+            expect![[r#"
+                ROOT@0..24
+                  HTML_TAG@0..4
+                    HTML_STARTING_TAG@0..3
+                      TK_LESS_THAN@0..1 "<"
+                      TK_WORD@1..2 "p"
+                      HTML_ATTRIBUTE_LIST@2..2
+                      TK_GREATER_THAN@2..3 ">"
+                    BODY@3..4
+                      HTML_TEXT@3..4
+                        TK_WORD@3..4 "a"
+                  HTML_TAG@4..16
+                    HTML_STARTING_TAG@4..9
+                      TK_LESS_THAN@4..5 "<"
+                      TK_WORD@5..8 "div"
+                      HTML_ATTRIBUTE_LIST@8..8
+                      TK_GREATER_THAN@8..9 ">"
+                    BODY@9..10
+                      HTML_TEXT@9..10
+                        TK_WORD@9..10 "b"
+                    HTML_ENDING_TAG@10..16
+                      TK_LESS_THAN_SLASH@10..12 "</"
+                      TK_WORD@12..15 "div"
+                      TK_GREATER_THAN@15..16 ">"
+                  HTML_TAG@16..24
+                    HTML_STARTING_TAG@16..19
+                      TK_LESS_THAN@16..17 "<"
+                      TK_WORD@17..18 "p"
+                      HTML_ATTRIBUTE_LIST@18..18
+                      TK_GREATER_THAN@18..19 ">"
+                    BODY@19..20
+                      HTML_TEXT@19..20
+                        TK_WORD@19..20 "c"
+                    HTML_ENDING_TAG@20..24
+                      TK_LESS_THAN_SLASH@20..22 "</"
+                      TK_WORD@22..23 "p"
+                      TK_GREATER_THAN@23..24 ">""#]],
+        );
+    }
+
+    #[test]
+    fn parse_html_implied_end_tag_missing_at_end_of_file() {
+        check_parse(
+            "<ul><li>a",
+            expect![[r#"
+            ROOT@0..9
+              HTML_TAG@0..9
+                HTML_STARTING_TAG@0..4
+                  TK_LESS_THAN@0..1 "<"
+                  TK_WORD@1..3 "ul"
+                  HTML_ATTRIBUTE_LIST@3..3
+                  TK_GREATER_THAN@3..4 ">"
+                BODY@4..9
+                  HTML_TAG@4..9
+                    HTML_STARTING_TAG@4..8
+                      TK_LESS_THAN@4..5 "<"
+                      TK_WORD@5..7 "li"
+                      HTML_ATTRIBUTE_LIST@7..7
+                      TK_GREATER_THAN@7..8 ">"
+                    BODY@8..9
+                      HTML_TEXT@8..9
+                        TK_WORD@8..9 "a"
+                    HTML_ENDING_TAG@9..9
+                HTML_ENDING_TAG@9..9
+            error at 8..9: expected </li> ending tag or </ul> ending tag but reached end of file"#]],
+        );
+    }
+
+    #[test]
+    fn parse_html_implied_end_tag_missing_before_endblock() {
+        check_parse(
+            "{% block a %}<p>a{% endblock %}",
+            expect![[r#"
+            ROOT@0..31
+              TWIG_BLOCK@0..31
+                TWIG_STARTING_BLOCK@0..13
+                  TK_CURLY_PERCENT@0..2 "{%"
+                  TK_WHITESPACE@2..3 " "
+                  TK_BLOCK@3..8 "block"
+                  TK_WHITESPACE@8..9 " "
+                  TK_WORD@9..10 "a"
+                  TK_WHITESPACE@10..11 " "
+                  TK_PERCENT_CURLY@11..13 "%}"
+                BODY@13..17
+                  HTML_TAG@13..17
+                    HTML_STARTING_TAG@13..16
+                      TK_LESS_THAN@13..14 "<"
+                      TK_WORD@14..15 "p"
+                      HTML_ATTRIBUTE_LIST@15..15
+                      TK_GREATER_THAN@15..16 ">"
+                    BODY@16..17
+                      HTML_TEXT@16..17
+                        TK_WORD@16..17 "a"
+                    HTML_ENDING_TAG@17..17
+                TWIG_ENDING_BLOCK@17..31
+                  TK_CURLY_PERCENT@17..19 "{%"
+                  TK_WHITESPACE@19..20 " "
+                  TK_ENDBLOCK@20..28 "endblock"
+                  TK_WHITESPACE@28..29 " "
+                  TK_PERCENT_CURLY@29..31 "%}"
+            error at 17..19: expected </p> ending tag but found {%"#]],
+        );
+    }
+
+    #[test]
+    fn parse_html_implied_end_tag_td() {
+        check_parse(
+            "<tr><td>a<td>b</tr>",
+            expect![[r#"
+            ROOT@0..19
+              HTML_TAG@0..19
+                HTML_STARTING_TAG@0..4
+                  TK_LESS_THAN@0..1 "<"
+                  TK_WORD@1..3 "tr"
+                  HTML_ATTRIBUTE_LIST@3..3
+                  TK_GREATER_THAN@3..4 ">"
+                BODY@4..14
+                  HTML_TAG@4..9
+                    HTML_STARTING_TAG@4..8
+                      TK_LESS_THAN@4..5 "<"
+                      TK_WORD@5..7 "td"
+                      HTML_ATTRIBUTE_LIST@7..7
+                      TK_GREATER_THAN@7..8 ">"
+                    BODY@8..9
+                      HTML_TEXT@8..9
+                        TK_WORD@8..9 "a"
+                  HTML_TAG@9..14
+                    HTML_STARTING_TAG@9..13
+                      TK_LESS_THAN@9..10 "<"
+                      TK_WORD@10..12 "td"
+                      HTML_ATTRIBUTE_LIST@12..12
+                      TK_GREATER_THAN@12..13 ">"
+                    BODY@13..14
+                      HTML_TEXT@13..14
+                        TK_WORD@13..14 "b"
+                HTML_ENDING_TAG@14..19
+                  TK_LESS_THAN_SLASH@14..16 "</"
+                  TK_WORD@16..18 "tr"
+                  TK_GREATER_THAN@18..19 ">""#]],
+        );
+    }
+
+    #[test]
+    fn parse_html_implied_end_tag_option() {
+        check_parse(
+            "<select><option>a<option>b</option></select>",
+            expect![[r#"
+                ROOT@0..44
+                  HTML_TAG@0..44
+                    HTML_STARTING_TAG@0..8
+                      TK_LESS_THAN@0..1 "<"
+                      TK_WORD@1..7 "select"
+                      HTML_ATTRIBUTE_LIST@7..7
+                      TK_GREATER_THAN@7..8 ">"
+                    BODY@8..35
+                      HTML_TAG@8..17
+                        HTML_STARTING_TAG@8..16
+                          TK_LESS_THAN@8..9 "<"
+                          TK_WORD@9..15 "option"
+                          HTML_ATTRIBUTE_LIST@15..15
+                          TK_GREATER_THAN@15..16 ">"
+                        BODY@16..17
+                          HTML_TEXT@16..17
+                            TK_WORD@16..17 "a"
+                      HTML_TAG@17..35
+                        HTML_STARTING_TAG@17..25
+                          TK_LESS_THAN@17..18 "<"
+                          TK_WORD@18..24 "option"
+                          HTML_ATTRIBUTE_LIST@24..24
+                          TK_GREATER_THAN@24..25 ">"
+                        BODY@25..26
+                          HTML_TEXT@25..26
+                            TK_WORD@25..26 "b"
+                        HTML_ENDING_TAG@26..35
+                          TK_LESS_THAN_SLASH@26..28 "</"
+                          TK_WORD@28..34 "option"
+                          TK_GREATER_THAN@34..35 ">"
+                    HTML_ENDING_TAG@35..44
+                      TK_LESS_THAN_SLASH@35..37 "</"
+                      TK_WORD@37..43 "select"
+                      TK_GREATER_THAN@43..44 ">""#]],
+        );
+    }
+
+    #[test]
+    fn parse_html_implied_end_tag_tr() {
+        check_parse(
+            "<table><tr><td>a</table>",
+            expect![[r#"
+            ROOT@0..24
+              HTML_TAG@0..24
+                HTML_STARTING_TAG@0..7
+                  TK_LESS_THAN@0..1 "<"
+                  TK_WORD@1..6 "table"
+                  HTML_ATTRIBUTE_LIST@6..6
+                  TK_GREATER_THAN@6..7 ">"
+                BODY@7..16
+                  HTML_TAG@7..16
+                    HTML_STARTING_TAG@7..11
+                      TK_LESS_THAN@7..8 "<"
+                      TK_WORD@8..10 "tr"
+                      HTML_ATTRIBUTE_LIST@10..10
+                      TK_GREATER_THAN@10..11 ">"
+                    BODY@11..16
+                      HTML_TAG@11..16
+                        HTML_STARTING_TAG@11..15
+                          TK_LESS_THAN@11..12 "<"
+                          TK_WORD@12..14 "td"
+                          HTML_ATTRIBUTE_LIST@14..14
+                          TK_GREATER_THAN@14..15 ">"
+                        BODY@15..16
+                          HTML_TEXT@15..16
+                            TK_WORD@15..16 "a"
+                HTML_ENDING_TAG@16..24
+                  TK_LESS_THAN_SLASH@16..18 "</"
+                  TK_WORD@18..23 "table"
+                  TK_GREATER_THAN@23..24 ">""#]],
+        );
+    }
+
+    #[test]
+    fn parse_html_implied_end_tag_dt_dd() {
+        check_parse(
+            "<dl><dt>a<dd>b</dl>",
+            expect![[r#"
+            ROOT@0..19
+              HTML_TAG@0..19
+                HTML_STARTING_TAG@0..4
+                  TK_LESS_THAN@0..1 "<"
+                  TK_WORD@1..3 "dl"
+                  HTML_ATTRIBUTE_LIST@3..3
+                  TK_GREATER_THAN@3..4 ">"
+                BODY@4..14
+                  HTML_TAG@4..9
+                    HTML_STARTING_TAG@4..8
+                      TK_LESS_THAN@4..5 "<"
+                      TK_WORD@5..7 "dt"
+                      HTML_ATTRIBUTE_LIST@7..7
+                      TK_GREATER_THAN@7..8 ">"
+                    BODY@8..9
+                      HTML_TEXT@8..9
+                        TK_WORD@8..9 "a"
+                  HTML_TAG@9..14
+                    HTML_STARTING_TAG@9..13
+                      TK_LESS_THAN@9..10 "<"
+                      TK_WORD@10..12 "dd"
+                      HTML_ATTRIBUTE_LIST@12..12
+                      TK_GREATER_THAN@12..13 ">"
+                    BODY@13..14
+                      HTML_TEXT@13..14
+                        TK_WORD@13..14 "b"
+                HTML_ENDING_TAG@14..19
+                  TK_LESS_THAN_SLASH@14..16 "</"
+                  TK_WORD@16..18 "dl"
+                  TK_GREATER_THAN@18..19 ">""#]],
+        );
+    }
+
+    #[test]
+    fn parse_html_implied_end_tag_p_with_stray_ending_tag() {
+        check_parse(
+            "<div>\n    <p>\n        <div>x</div>\n    </p>\n</div>",
+            expect![[r#"
+                ROOT@0..50
+                  HTML_TAG@0..50
+                    HTML_STARTING_TAG@0..5
+                      TK_LESS_THAN@0..1 "<"
+                      TK_WORD@1..4 "div"
+                      HTML_ATTRIBUTE_LIST@4..4
+                      TK_GREATER_THAN@4..5 ">"
+                    BODY@5..43
+                      HTML_TAG@5..43
+                        HTML_STARTING_TAG@5..13
+                          TK_LINE_BREAK@5..6 "\n"
+                          TK_WHITESPACE@6..10 "    "
+                          TK_LESS_THAN@10..11 "<"
+                          TK_WORD@11..12 "p"
+                          HTML_ATTRIBUTE_LIST@12..12
+                          TK_GREATER_THAN@12..13 ">"
+                        BODY@13..34
+                          HTML_TAG@13..34
+                            HTML_STARTING_TAG@13..27
+                              TK_LINE_BREAK@13..14 "\n"
+                              TK_WHITESPACE@14..22 "        "
+                              TK_LESS_THAN@22..23 "<"
+                              TK_WORD@23..26 "div"
+                              HTML_ATTRIBUTE_LIST@26..26
+                              TK_GREATER_THAN@26..27 ">"
+                            BODY@27..28
+                              HTML_TEXT@27..28
+                                TK_WORD@27..28 "x"
+                            HTML_ENDING_TAG@28..34
+                              TK_LESS_THAN_SLASH@28..30 "</"
+                              TK_WORD@30..33 "div"
+                              TK_GREATER_THAN@33..34 ">"
+                        HTML_ENDING_TAG@34..43
+                          TK_LINE_BREAK@34..35 "\n"
+                          TK_WHITESPACE@35..39 "    "
+                          TK_LESS_THAN_SLASH@39..41 "</"
+                          TK_WORD@41..42 "p"
+                          TK_GREATER_THAN@42..43 ">"
+                    HTML_ENDING_TAG@43..50
+                      TK_LINE_BREAK@43..44 "\n"
+                      TK_LESS_THAN_SLASH@44..46 "</"
+                      TK_WORD@46..49 "div"
+                      TK_GREATER_THAN@49..50 ">""#]],
+        );
+    }
+
+    #[test]
+    fn parse_html_implied_end_tag_p_with_stray_ending_tag_after_twig() {
+        check_parse(
+            "<p><div>{% if a %}<div>b</div>{% endif %}</div></p>",
+            expect![[r#"
+                ROOT@0..51
+                  HTML_TAG@0..51
+                    HTML_STARTING_TAG@0..3
+                      TK_LESS_THAN@0..1 "<"
+                      TK_WORD@1..2 "p"
+                      HTML_ATTRIBUTE_LIST@2..2
+                      TK_GREATER_THAN@2..3 ">"
+                    BODY@3..47
+                      HTML_TAG@3..47
+                        HTML_STARTING_TAG@3..8
+                          TK_LESS_THAN@3..4 "<"
+                          TK_WORD@4..7 "div"
+                          HTML_ATTRIBUTE_LIST@7..7
+                          TK_GREATER_THAN@7..8 ">"
+                        BODY@8..41
+                          TWIG_IF@8..41
+                            TWIG_IF_BLOCK@8..18
+                              TK_CURLY_PERCENT@8..10 "{%"
+                              TK_WHITESPACE@10..11 " "
+                              TK_IF@11..13 "if"
+                              TWIG_EXPRESSION@13..15
+                                TWIG_LITERAL_NAME@13..15
+                                  TK_WHITESPACE@13..14 " "
+                                  TK_WORD@14..15 "a"
+                              TK_WHITESPACE@15..16 " "
+                              TK_PERCENT_CURLY@16..18 "%}"
+                            BODY@18..30
+                              HTML_TAG@18..30
+                                HTML_STARTING_TAG@18..23
+                                  TK_LESS_THAN@18..19 "<"
+                                  TK_WORD@19..22 "div"
+                                  HTML_ATTRIBUTE_LIST@22..22
+                                  TK_GREATER_THAN@22..23 ">"
+                                BODY@23..24
+                                  HTML_TEXT@23..24
+                                    TK_WORD@23..24 "b"
+                                HTML_ENDING_TAG@24..30
+                                  TK_LESS_THAN_SLASH@24..26 "</"
+                                  TK_WORD@26..29 "div"
+                                  TK_GREATER_THAN@29..30 ">"
+                            TWIG_ENDIF_BLOCK@30..41
+                              TK_CURLY_PERCENT@30..32 "{%"
+                              TK_WHITESPACE@32..33 " "
+                              TK_ENDIF@33..38 "endif"
+                              TK_WHITESPACE@38..39 " "
+                              TK_PERCENT_CURLY@39..41 "%}"
+                        HTML_ENDING_TAG@41..47
+                          TK_LESS_THAN_SLASH@41..43 "</"
+                          TK_WORD@43..46 "div"
+                          TK_GREATER_THAN@46..47 ">"
+                    HTML_ENDING_TAG@47..51
+                      TK_LESS_THAN_SLASH@47..49 "</"
+                      TK_WORD@49..50 "p"
+                      TK_GREATER_THAN@50..51 ">""#]],
+        );
+    }
+
     #[test]
     fn parse_fuzzing_bump_error() {
         check_parse(
@@ -1886,23 +3174,21 @@ mod tests {
                     HTML_STARTING_TAG@0..8
                       TK_LESS_THAN@0..1 "<"
                       TK_WORD@1..2 "d"
-                      HTML_ATTRIBUTE_LIST@2..7
+                      HTML_ATTRIBUTE_LIST@2..8
                         HTML_ATTRIBUTE@2..5
                           TK_WHITESPACE@2..3 " "
                           TK_WORD@3..4 "a"
                           TK_EQUAL@4..5 "="
                           HTML_STRING@5..5
                             HTML_STRING_INNER@5..5
-                        ERROR@5..7
+                        ERROR@5..8
                           TK_CURLY_PERCENT@5..7 "{%"
-                      ERROR@7..8
-                        TK_PERCENT@7..8 "%"
+                          TK_PERCENT@7..8 "%"
                     BODY@8..8
                     HTML_ENDING_TAG@8..8
                 error at 5..7: expected html attribute value but found {%
                 error at 7..8: expected twig tag but found %
-                error at 7..8: expected > but found %
-                error at 7..8: expected </d> ending tag but reached end of file"#]],
+                error at 7..8: expected > or </d> ending tag but reached end of file"#]],
         );
     }
 
@@ -1955,8 +3241,11 @@ mod tests {
                       TK_WORD@18..26 "template"
                       HTML_ATTRIBUTE_LIST@26..32
                         HTML_ATTRIBUTE@26..32
-                          TK_WHITESPACE@26..27 " "
-                          TK_WORD@27..32 "#slot"
+                          VUE_DIRECTIVE@26..32
+                            TK_WHITESPACE@26..27 " "
+                            TK_HASHTAG@27..28 "#"
+                            VUE_DIRECTIVE_ARGUMENT@28..32
+                              TK_WORD@28..32 "slot"
                       TK_GREATER_THAN@32..33 ">"
                     BODY@33..165
                       HTML_TAG@33..165
@@ -1967,8 +3256,11 @@ mod tests {
                           TK_WORD@55..67 "my-component"
                           HTML_ATTRIBUTE_LIST@67..98
                             HTML_ATTRIBUTE@67..81
-                              TK_WHITESPACE@67..68 " "
-                              TK_WORD@68..73 ":bind"
+                              VUE_DIRECTIVE@67..73
+                                TK_WHITESPACE@67..68 " "
+                                TK_COLON@68..69 ":"
+                                VUE_DIRECTIVE_ARGUMENT@69..73
+                                  TK_WORD@69..73 "bind"
                               TK_EQUAL@73..74 "="
                               HTML_STRING@74..81
                                 TK_DOUBLE_QUOTES@74..75 "\""
@@ -1976,8 +3268,11 @@ mod tests {
                                   TK_WORD@75..80 "hello"
                                 TK_DOUBLE_QUOTES@80..81 "\""
                             HTML_ATTRIBUTE@81..98
-                              TK_WHITESPACE@81..82 " "
-                              TK_WORD@82..88 "@click"
+                              VUE_DIRECTIVE@81..88
+                                TK_WHITESPACE@81..82 " "
+                                TK_AT@82..83 "@"
+                                VUE_DIRECTIVE_ARGUMENT@83..88
+                                  TK_WORD@83..88 "click"
                               TK_EQUAL@88..89 "="
                               HTML_STRING@89..98
                                 TK_DOUBLE_QUOTES@89..90 "\""
@@ -2007,6 +3302,87 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_html_tag_with_vue_directives() {
+        check_parse(
+            r#"<sw-field v-model.trim="value" v-on:click.prevent="submit" @update:model-value="onUpdate" :[key]="x" v-else></sw-field>"#,
+            expect![[r#"
+                ROOT@0..119
+                  HTML_TAG@0..119
+                    HTML_STARTING_TAG@0..108
+                      TK_LESS_THAN@0..1 "<"
+                      TK_WORD@1..9 "sw-field"
+                      HTML_ATTRIBUTE_LIST@9..107
+                        HTML_ATTRIBUTE@9..30
+                          VUE_DIRECTIVE@9..22
+                            TK_WHITESPACE@9..10 " "
+                            TK_WORD@10..17 "v-model"
+                            VUE_DIRECTIVE_MODIFIER@17..22
+                              TK_DOT@17..18 "."
+                              TK_WORD@18..22 "trim"
+                          TK_EQUAL@22..23 "="
+                          HTML_STRING@23..30
+                            TK_DOUBLE_QUOTES@23..24 "\""
+                            HTML_STRING_INNER@24..29
+                              TK_WORD@24..29 "value"
+                            TK_DOUBLE_QUOTES@29..30 "\""
+                        HTML_ATTRIBUTE@30..58
+                          VUE_DIRECTIVE@30..49
+                            TK_WHITESPACE@30..31 " "
+                            TK_WORD@31..35 "v-on"
+                            TK_COLON@35..36 ":"
+                            VUE_DIRECTIVE_ARGUMENT@36..41
+                              TK_WORD@36..41 "click"
+                            VUE_DIRECTIVE_MODIFIER@41..49
+                              TK_DOT@41..42 "."
+                              TK_WORD@42..49 "prevent"
+                          TK_EQUAL@49..50 "="
+                          HTML_STRING@50..58
+                            TK_DOUBLE_QUOTES@50..51 "\""
+                            HTML_STRING_INNER@51..57
+                              TK_WORD@51..57 "submit"
+                            TK_DOUBLE_QUOTES@57..58 "\""
+                        HTML_ATTRIBUTE@58..89
+                          VUE_DIRECTIVE@58..78
+                            TK_WHITESPACE@58..59 " "
+                            TK_AT@59..60 "@"
+                            VUE_DIRECTIVE_ARGUMENT@60..78
+                              TK_WORD@60..66 "update"
+                              TK_COLON@66..67 ":"
+                              TK_WORD@67..78 "model-value"
+                          TK_EQUAL@78..79 "="
+                          HTML_STRING@79..89
+                            TK_DOUBLE_QUOTES@79..80 "\""
+                            HTML_STRING_INNER@80..88
+                              TK_WORD@80..88 "onUpdate"
+                            TK_DOUBLE_QUOTES@88..89 "\""
+                        HTML_ATTRIBUTE@89..100
+                          VUE_DIRECTIVE@89..96
+                            TK_WHITESPACE@89..90 " "
+                            TK_COLON@90..91 ":"
+                            VUE_DIRECTIVE_ARGUMENT@91..96
+                              TK_OPEN_SQUARE@91..92 "["
+                              TK_WORD@92..95 "key"
+                              TK_CLOSE_SQUARE@95..96 "]"
+                          TK_EQUAL@96..97 "="
+                          HTML_STRING@97..100
+                            TK_DOUBLE_QUOTES@97..98 "\""
+                            HTML_STRING_INNER@98..99
+                              TK_WORD@98..99 "x"
+                            TK_DOUBLE_QUOTES@99..100 "\""
+                        HTML_ATTRIBUTE@100..107
+                          VUE_DIRECTIVE@100..107
+                            TK_WHITESPACE@100..101 " "
+                            TK_WORD@101..107 "v-else"
+                      TK_GREATER_THAN@107..108 ">"
+                    BODY@108..108
+                    HTML_ENDING_TAG@108..119
+                      TK_LESS_THAN_SLASH@108..110 "</"
+                      TK_WORD@110..118 "sw-field"
+                      TK_GREATER_THAN@118..119 ">""#]],
+        );
+    }
+
     #[test]
     fn parse_html_tag_with_token_collision_name() {
         check_parse(
@@ -2025,8 +3401,7 @@ mod tests {
                       HTML_STRING@15..20
                         TK_DOUBLE_QUOTES@15..16 "\""
                         HTML_STRING_INNER@16..19
-                          TK_DOUBLE_DOT@16..18 ".."
-                          TK_DOT@18..19 "."
+                          TK_TRIPLE_DOT@16..19 "..."
                         TK_DOUBLE_QUOTES@19..20 "\""
                   TK_GREATER_THAN@20..21 ">""#]],
         );
@@ -2046,4 +3421,169 @@ mod tests {
                 TK_GREATER_THAN@14..15 ">""#]],
         );
     }
+
+    #[test]
+    fn parse_html_xml_prolog() {
+        check_parse(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset></urlset>"#,
+            expect![[r#"
+                ROOT@0..56
+                  HTML_XML_PROLOG@0..38
+                    TK_LESS_THAN_QUESTION_MARK@0..2 "<?"
+                    TK_WORD@2..5 "xml"
+                    HTML_ATTRIBUTE_LIST@5..36
+                      HTML_ATTRIBUTE@5..19
+                        TK_WHITESPACE@5..6 " "
+                        TK_WORD@6..13 "version"
+                        TK_EQUAL@13..14 "="
+                        HTML_STRING@14..19
+                          TK_DOUBLE_QUOTES@14..15 "\""
+                          HTML_STRING_INNER@15..18
+                            TK_NUMBER@15..18 "1.0"
+                          TK_DOUBLE_QUOTES@18..19 "\""
+                      HTML_ATTRIBUTE@19..36
+                        TK_WHITESPACE@19..20 " "
+                        TK_WORD@20..28 "encoding"
+                        TK_EQUAL@28..29 "="
+                        HTML_STRING@29..36
+                          TK_DOUBLE_QUOTES@29..30 "\""
+                          HTML_STRING_INNER@30..35
+                            TK_WORD@30..35 "UTF-8"
+                          TK_DOUBLE_QUOTES@35..36 "\""
+                    TK_QUESTION_MARK_GREATER_THAN@36..38 "?>"
+                  HTML_TAG@38..56
+                    HTML_STARTING_TAG@38..47
+                      TK_LINE_BREAK@38..39 "\n"
+                      TK_LESS_THAN@39..40 "<"
+                      TK_WORD@40..46 "urlset"
+                      HTML_ATTRIBUTE_LIST@46..46
+                      TK_GREATER_THAN@46..47 ">"
+                    BODY@47..47
+                    HTML_ENDING_TAG@47..56
+                      TK_LESS_THAN_SLASH@47..49 "</"
+                      TK_WORD@49..55 "urlset"
+                      TK_GREATER_THAN@55..56 ">""#]],
+        );
+    }
+
+    #[test]
+    fn parse_html_cdata() {
+        check_parse(
+            "<description><![CDATA[<p>{{ product.description|raw }}</p> & a[0]]]></description>",
+            expect![[r#"
+                ROOT@0..82
+                  HTML_TAG@0..82
+                    HTML_STARTING_TAG@0..13
+                      TK_LESS_THAN@0..1 "<"
+                      TK_WORD@1..12 "description"
+                      HTML_ATTRIBUTE_LIST@12..12
+                      TK_GREATER_THAN@12..13 ">"
+                    BODY@13..68
+                      HTML_CDATA@13..68
+                        TK_CDATA_START@13..22 "<![CDATA["
+                        HTML_TEXT@22..25
+                          TK_LESS_THAN@22..23 "<"
+                          TK_WORD@23..24 "p"
+                          TK_GREATER_THAN@24..25 ">"
+                        TWIG_VAR@25..54
+                          TK_OPEN_CURLY_CURLY@25..27 "{{"
+                          TWIG_EXPRESSION@27..51
+                            TWIG_FILTER@27..51
+                              TWIG_OPERAND@27..47
+                                TWIG_ACCESSOR@27..47
+                                  TWIG_OPERAND@27..35
+                                    TWIG_LITERAL_NAME@27..35
+                                      TK_WHITESPACE@27..28 " "
+                                      TK_WORD@28..35 "product"
+                                  TK_DOT@35..36 "."
+                                  TWIG_OPERAND@36..47
+                                    TWIG_LITERAL_NAME@36..47
+                                      TK_WORD@36..47 "description"
+                              TK_SINGLE_PIPE@47..48 "|"
+                              TWIG_OPERAND@48..51
+                                TWIG_LITERAL_NAME@48..51
+                                  TK_WORD@48..51 "raw"
+                          TK_WHITESPACE@51..52 " "
+                          TK_CLOSE_CURLY_CURLY@52..54 "}}"
+                        HTML_TEXT@54..65
+                          TK_LESS_THAN_SLASH@54..56 "</"
+                          TK_WORD@56..57 "p"
+                          TK_GREATER_THAN@57..58 ">"
+                          TK_WHITESPACE@58..59 " "
+                          TK_AMPERSAND@59..60 "&"
+                          TK_WHITESPACE@60..61 " "
+                          TK_WORD@61..62 "a"
+                          TK_OPEN_SQUARE@62..63 "["
+                          TK_NUMBER@63..64 "0"
+                          TK_CLOSE_SQUARE@64..65 "]"
+                        TK_CDATA_END@65..68 "]]>"
+                    HTML_ENDING_TAG@68..82
+                      TK_LESS_THAN_SLASH@68..70 "</"
+                      TK_WORD@70..81 "description"
+                      TK_GREATER_THAN@81..82 ">""#]],
+        );
+    }
+
+    #[test]
+    fn parse_html_cdata_with_twig_block() {
+        check_parse(
+            "<![CDATA[{% if a %}<b>{{ a }}{% endif %}]]>",
+            expect![[r#"
+                ROOT@0..43
+                  HTML_CDATA@0..43
+                    TK_CDATA_START@0..9 "<![CDATA["
+                    TWIG_IF@9..40
+                      TWIG_IF_BLOCK@9..19
+                        TK_CURLY_PERCENT@9..11 "{%"
+                        TK_WHITESPACE@11..12 " "
+                        TK_IF@12..14 "if"
+                        TWIG_EXPRESSION@14..16
+                          TWIG_LITERAL_NAME@14..16
+                            TK_WHITESPACE@14..15 " "
+                            TK_WORD@15..16 "a"
+                        TK_WHITESPACE@16..17 " "
+                        TK_PERCENT_CURLY@17..19 "%}"
+                      BODY@19..29
+                        HTML_TEXT@19..22
+                          TK_LESS_THAN@19..20 "<"
+                          TK_WORD@20..21 "b"
+                          TK_GREATER_THAN@21..22 ">"
+                        TWIG_VAR@22..29
+                          TK_OPEN_CURLY_CURLY@22..24 "{{"
+                          TWIG_EXPRESSION@24..26
+                            TWIG_LITERAL_NAME@24..26
+                              TK_WHITESPACE@24..25 " "
+                              TK_WORD@25..26 "a"
+                          TK_WHITESPACE@26..27 " "
+                          TK_CLOSE_CURLY_CURLY@27..29 "}}"
+                      TWIG_ENDIF_BLOCK@29..40
+                        TK_CURLY_PERCENT@29..31 "{%"
+                        TK_WHITESPACE@31..32 " "
+                        TK_ENDIF@32..37 "endif"
+                        TK_WHITESPACE@37..38 " "
+                        TK_PERCENT_CURLY@38..40 "%}"
+                    TK_CDATA_END@40..43 "]]>""#]],
+        );
+    }
+
+    #[test]
+    fn parse_html_cdata_missing_end() {
+        check_parse(
+            "<![CDATA[ a ] ]>",
+            expect![[r#"
+            ROOT@0..16
+              HTML_CDATA@0..16
+                TK_CDATA_START@0..9 "<![CDATA["
+                HTML_TEXT@9..16
+                  TK_WHITESPACE@9..10 " "
+                  TK_WORD@10..11 "a"
+                  TK_WHITESPACE@11..12 " "
+                  TK_CLOSE_SQUARE@12..13 "]"
+                  TK_WHITESPACE@13..14 " "
+                  TK_CLOSE_SQUARE@14..15 "]"
+                  TK_GREATER_THAN@15..16 ">"
+            error at 15..16: expected ]]> but reached end of file"#]],
+        );
+    }
 }
@@ -3,9 +3,10 @@ pub(crate) mod literal;
 mod shopware;
 mod tags;
 
+pub(crate) use expression::parse_twig_expression;
 pub(crate) use tags::at_twig_termination_tag;
 
-use crate::grammar::twig::expression::{parse_twig_expression, TWIG_EXPRESSION_RECOVERY_SET};
+use crate::grammar::twig::expression::TWIG_EXPRESSION_RECOVERY_SET;
 use crate::grammar::{parse_ludtwig_directive, parse_many, ParseFunction};
 use crate::parser::event::{CompletedMarker, Marker};
 use crate::parser::{ParseErrorBuilder, Parser};
@@ -72,7 +73,7 @@ pub(crate) fn parse_twig_var_statement(parser: &mut Parser) -> CompletedMarker {
 mod tests {
     use expect_test::expect;
 
-    use crate::parser::check_parse;
+    use crate::testing::check_parse;
 
     #[test]
     fn parse_twig_var() {
@@ -1,8 +1,9 @@
-use crate::grammar::twig::literal::{parse_postfix_operators, parse_twig_literal};
+use crate::grammar::parse_many;
+use crate::grammar::twig::literal::{parse_postfix_operators, parse_twig_literal, parse_twig_name};
 use crate::parser::event::CompletedMarker;
 use crate::parser::{ParseErrorBuilder, Parser};
 use crate::syntax::untyped::SyntaxKind;
-use crate::T;
+use crate::{TwigVersion, T};
 
 pub(crate) static TWIG_EXPRESSION_RECOVERY_SET: &[SyntaxKind] = &[
     T!["|"],
@@ -23,6 +24,7 @@ pub(crate) static TWIG_EXPRESSION_RECOVERY_SET: &[SyntaxKind] = &[
     T!["'"],
     T!["["],
     T!["null"],
+    T!["none"],
     T!["true"],
     T!["false"],
     T!["{"],
@@ -46,6 +48,8 @@ pub(crate) static TWIG_EXPRESSION_RECOVERY_SET: &[SyntaxKind] = &[
     T!["matches"],
     T!["starts with"],
     T!["ends with"],
+    T!["has some"],
+    T!["has every"],
     T!["==="],
     T!["!=="],
     T![".."],
@@ -61,7 +65,7 @@ pub(crate) static TWIG_EXPRESSION_RECOVERY_SET: &[SyntaxKind] = &[
     T!["??"],
 ];
 
-pub(super) fn parse_twig_expression(parser: &mut Parser) -> Option<CompletedMarker> {
+pub(crate) fn parse_twig_expression(parser: &mut Parser) -> Option<CompletedMarker> {
     parse_twig_expression_binding_power(parser, 0)
 }
 
@@ -100,6 +104,8 @@ impl Operator for SyntaxKind {
             | T!["matches"]
             | T!["starts with"]
             | T!["ends with"]
+            | T!["has some"]
+            | T!["has every"]
             | T!["==="] // not official twig but still parse `===` and `!==` to later notify the user by rule
             | T!["!=="] => Some((20, 21)),
             T![".."] => Some((25, 26)),
@@ -148,6 +154,19 @@ fn parse_twig_expression_binding_power(
             break;
         }
 
+        // wrap the previous binary expression (left-associative), so every operand is an expression
+        if is_binary {
+            let m = parser.precede(lhs);
+            lhs = parser.complete(m, SyntaxKind::TWIG_EXPRESSION);
+        }
+
+        if parser.at_set(&[T!["has some"], T!["has every"]]) {
+            parser.require_twig_version(
+                TwigVersion::new(3, 11),
+                "'has some' and 'has every' operators",
+            );
+        }
+
         // Eat the operator’s token.
         let eaten_kind = parser.bump().kind;
         if (eaten_kind == T!["not"] && parser.at(T!["in"]))
@@ -215,7 +234,11 @@ fn parse_conditional_expression(
 }
 
 fn parse_twig_expression_lhs(parser: &mut Parser) -> Option<CompletedMarker> {
-    if parser.at(T!["("]) {
+    if at_arrow_function(parser) {
+        Some(parse_arrow_function(parser))
+    } else if parser.at(T!["..."]) {
+        Some(parse_spread(parser))
+    } else if parser.at(T!["("]) {
         let node = parse_paren_expression(parser);
         // including postfix operators
         Some(parse_postfix_operators(parser, node))
@@ -227,6 +250,77 @@ fn parse_twig_expression_lhs(parser: &mut Parser) -> Option<CompletedMarker> {
     }
 }
 
+/// Lookahead for an arrow function like `v => v * 2` or `(v, k) => v * k`
+fn at_arrow_function(parser: &mut Parser) -> bool {
+    let mut kinds = parser.following_kinds();
+    match kinds.next() {
+        Some(T!["("]) => {
+            while let Some(kind) = kinds.next() {
+                match kind {
+                    T![")"] => return kinds.next() == Some(T!["=>"]),
+                    T!["("] | T!["}}"] | T!["%}"] => return false,
+                    _ => {}
+                }
+            }
+            false
+        }
+        Some(_) => kinds.next() == Some(T!["=>"]),
+        None => false,
+    }
+}
+
+fn parse_arrow_function(parser: &mut Parser) -> CompletedMarker {
+    let m = parser.start();
+
+    // parameters
+    if parser.at(T!["("]) {
+        parser.bump();
+        parse_many(
+            parser,
+            |p| p.at_set(&[T![")"], T!["=>"]]),
+            |p| {
+                if parse_twig_name(p).is_none() {
+                    p.add_error(ParseErrorBuilder::new("arrow function parameter"));
+                }
+
+                if p.at(T![","]) {
+                    p.bump();
+                } else if !p.at(T![")"]) {
+                    p.add_error(ParseErrorBuilder::new(","));
+                }
+            },
+        );
+        parser.expect(T![")"], &[T!["=>"]]);
+    } else if parse_twig_name(parser).is_none() {
+        parser.add_error(ParseErrorBuilder::new("arrow function parameter"));
+        parser.recover(&[T!["=>"]]);
+    }
+
+    parser.require_twig_version(TwigVersion::new(2, 10), "arrow functions");
+    parser.expect(T!["=>"], TWIG_EXPRESSION_RECOVERY_SET);
+
+    // body
+    if parse_twig_expression(parser).is_none() {
+        parser.add_error(ParseErrorBuilder::new("twig expression"));
+    }
+
+    parser.complete(m, SyntaxKind::TWIG_ARROW_FUNCTION)
+}
+
+fn parse_spread(parser: &mut Parser) -> CompletedMarker {
+    debug_assert!(parser.at(T!["..."]));
+
+    let m = parser.start();
+    parser.require_twig_version(TwigVersion::new(3, 7), "spread operators");
+    parser.bump();
+
+    if parse_twig_expression(parser).is_none() {
+        parser.add_error(ParseErrorBuilder::new("twig expression"));
+    }
+
+    parser.complete(m, SyntaxKind::TWIG_SPREAD)
+}
+
 fn parse_paren_expression(parser: &mut Parser) -> CompletedMarker {
     debug_assert!(parser.at(T!["("]));
 
@@ -258,7 +352,8 @@ fn parse_unary_expression(parser: &mut Parser) -> CompletedMarker {
 mod tests {
     use expect_test::expect;
 
-    use crate::parser::check_parse;
+    use crate::testing::check_parse;
+    use crate::{parse_for_twig_version, TwigVersion};
 
     #[test]
     fn parse_twig_empty_expression() {
@@ -475,39 +570,41 @@ mod tests {
                     TK_OPEN_CURLY_CURLY@0..2 "{{"
                     TWIG_EXPRESSION@2..48
                       TWIG_BINARY_EXPRESSION@2..48
-                        TWIG_BINARY_EXPRESSION@2..40
-                          TWIG_BINARY_EXPRESSION@2..18
-                            TWIG_EXPRESSION@2..11
-                              TWIG_LITERAL_STRING@2..11
-                                TK_WHITESPACE@2..3 " "
-                                TK_SINGLE_QUOTES@3..4 "'"
-                                TWIG_LITERAL_STRING_INNER@4..10
-                                  TK_WORD@4..9 "hello"
-                                  TK_WHITESPACE@9..10 " "
-                                TK_SINGLE_QUOTES@10..11 "'"
-                            TK_WHITESPACE@11..12 " "
-                            TK_TILDE@12..13 "~"
-                            TWIG_EXPRESSION@13..18
-                              TWIG_LITERAL_NAME@13..18
-                                TK_WHITESPACE@13..14 " "
-                                TK_WORD@14..18 "name"
-                          TK_WHITESPACE@18..19 " "
-                          TK_TILDE@19..20 "~"
-                          TWIG_EXPRESSION@20..40
-                            TWIG_LITERAL_STRING@20..40
-                              TK_WHITESPACE@20..21 " "
-                              TK_SINGLE_QUOTES@21..22 "'"
-                              TWIG_LITERAL_STRING_INNER@22..39
-                                TK_WHITESPACE@22..23 " "
-                                TK_WORD@23..25 "to"
-                                TK_WHITESPACE@25..26 " "
-                                TK_WORD@26..29 "the"
-                                TK_WHITESPACE@29..30 " "
-                                TK_WORD@30..35 "world"
-                                TK_WHITESPACE@35..36 " "
-                                TK_WORD@36..38 "of"
-                                TK_WHITESPACE@38..39 " "
-                              TK_SINGLE_QUOTES@39..40 "'"
+                        TWIG_EXPRESSION@2..40
+                          TWIG_BINARY_EXPRESSION@2..40
+                            TWIG_EXPRESSION@2..18
+                              TWIG_BINARY_EXPRESSION@2..18
+                                TWIG_EXPRESSION@2..11
+                                  TWIG_LITERAL_STRING@2..11
+                                    TK_WHITESPACE@2..3 " "
+                                    TK_SINGLE_QUOTES@3..4 "'"
+                                    TWIG_LITERAL_STRING_INNER@4..10
+                                      TK_WORD@4..9 "hello"
+                                      TK_WHITESPACE@9..10 " "
+                                    TK_SINGLE_QUOTES@10..11 "'"
+                                TK_WHITESPACE@11..12 " "
+                                TK_TILDE@12..13 "~"
+                                TWIG_EXPRESSION@13..18
+                                  TWIG_LITERAL_NAME@13..18
+                                    TK_WHITESPACE@13..14 " "
+                                    TK_WORD@14..18 "name"
+                            TK_WHITESPACE@18..19 " "
+                            TK_TILDE@19..20 "~"
+                            TWIG_EXPRESSION@20..40
+                              TWIG_LITERAL_STRING@20..40
+                                TK_WHITESPACE@20..21 " "
+                                TK_SINGLE_QUOTES@21..22 "'"
+                                TWIG_LITERAL_STRING_INNER@22..39
+                                  TK_WHITESPACE@22..23 " "
+                                  TK_WORD@23..25 "to"
+                                  TK_WHITESPACE@25..26 " "
+                                  TK_WORD@26..29 "the"
+                                  TK_WHITESPACE@29..30 " "
+                                  TK_WORD@30..35 "world"
+                                  TK_WHITESPACE@35..36 " "
+                                  TK_WORD@36..38 "of"
+                                  TK_WHITESPACE@38..39 " "
+                                TK_SINGLE_QUOTES@39..40 "'"
                         TK_WHITESPACE@40..41 " "
                         TK_TILDE@41..42 "~"
                         TWIG_EXPRESSION@42..48
@@ -1311,4 +1408,182 @@ mod tests {
                     TK_CLOSE_CURLY_CURLY@13..15 "}}""#]],
         );
     }
+
+    #[test]
+    fn parse_twig_arrow_function() {
+        check_parse(
+            "{{ f(v => v) }}",
+            expect![[r#"
+                ROOT@0..15
+                  TWIG_VAR@0..15
+                    TK_OPEN_CURLY_CURLY@0..2 "{{"
+                    TWIG_EXPRESSION@2..12
+                      TWIG_FUNCTION_CALL@2..12
+                        TWIG_OPERAND@2..4
+                          TWIG_LITERAL_NAME@2..4
+                            TK_WHITESPACE@2..3 " "
+                            TK_WORD@3..4 "f"
+                        TK_OPEN_PARENTHESIS@4..5 "("
+                        TWIG_ARGUMENTS@5..11
+                          TWIG_EXPRESSION@5..11
+                            TWIG_ARROW_FUNCTION@5..11
+                              TWIG_LITERAL_NAME@5..6
+                                TK_WORD@5..6 "v"
+                              TK_WHITESPACE@6..7 " "
+                              TK_EQUAL_GREATER_THAN@7..9 "=>"
+                              TWIG_EXPRESSION@9..11
+                                TWIG_LITERAL_NAME@9..11
+                                  TK_WHITESPACE@9..10 " "
+                                  TK_WORD@10..11 "v"
+                        TK_CLOSE_PARENTHESIS@11..12 ")"
+                    TK_WHITESPACE@12..13 " "
+                    TK_CLOSE_CURLY_CURLY@13..15 "}}""#]],
+        );
+    }
+
+    #[test]
+    fn parse_twig_arrow_function_with_multiple_parameters() {
+        check_parse(
+            "{{ f((a, b) => a) }}",
+            expect![[r#"
+                ROOT@0..20
+                  TWIG_VAR@0..20
+                    TK_OPEN_CURLY_CURLY@0..2 "{{"
+                    TWIG_EXPRESSION@2..17
+                      TWIG_FUNCTION_CALL@2..17
+                        TWIG_OPERAND@2..4
+                          TWIG_LITERAL_NAME@2..4
+                            TK_WHITESPACE@2..3 " "
+                            TK_WORD@3..4 "f"
+                        TK_OPEN_PARENTHESIS@4..5 "("
+                        TWIG_ARGUMENTS@5..16
+                          TWIG_EXPRESSION@5..16
+                            TWIG_ARROW_FUNCTION@5..16
+                              TK_OPEN_PARENTHESIS@5..6 "("
+                              TWIG_LITERAL_NAME@6..7
+                                TK_WORD@6..7 "a"
+                              TK_COMMA@7..8 ","
+                              TWIG_LITERAL_NAME@8..10
+                                TK_WHITESPACE@8..9 " "
+                                TK_WORD@9..10 "b"
+                              TK_CLOSE_PARENTHESIS@10..11 ")"
+                              TK_WHITESPACE@11..12 " "
+                              TK_EQUAL_GREATER_THAN@12..14 "=>"
+                              TWIG_EXPRESSION@14..16
+                                TWIG_LITERAL_NAME@14..16
+                                  TK_WHITESPACE@14..15 " "
+                                  TK_WORD@15..16 "a"
+                        TK_CLOSE_PARENTHESIS@16..17 ")"
+                    TK_WHITESPACE@17..18 " "
+                    TK_CLOSE_CURLY_CURLY@18..20 "}}""#]],
+        );
+    }
+
+    #[test]
+    fn parse_twig_spread_in_array() {
+        check_parse(
+            "{{ [...a, b] }}",
+            expect![[r#"
+                ROOT@0..15
+                  TWIG_VAR@0..15
+                    TK_OPEN_CURLY_CURLY@0..2 "{{"
+                    TWIG_EXPRESSION@2..12
+                      TWIG_LITERAL_ARRAY@2..12
+                        TK_WHITESPACE@2..3 " "
+                        TK_OPEN_SQUARE@3..4 "["
+                        TWIG_LITERAL_ARRAY_INNER@4..11
+                          TWIG_EXPRESSION@4..8
+                            TWIG_SPREAD@4..8
+                              TK_TRIPLE_DOT@4..7 "..."
+                              TWIG_EXPRESSION@7..8
+                                TWIG_LITERAL_NAME@7..8
+                                  TK_WORD@7..8 "a"
+                          TK_COMMA@8..9 ","
+                          TWIG_EXPRESSION@9..11
+                            TWIG_LITERAL_NAME@9..11
+                              TK_WHITESPACE@9..10 " "
+                              TK_WORD@10..11 "b"
+                        TK_CLOSE_SQUARE@11..12 "]"
+                    TK_WHITESPACE@12..13 " "
+                    TK_CLOSE_CURLY_CURLY@13..15 "}}""#]],
+        );
+    }
+
+    #[test]
+    fn parse_twig_spread_in_hash() {
+        check_parse(
+            "{{ {...a, b: 1} }}",
+            expect![[r#"
+                ROOT@0..18
+                  TWIG_VAR@0..18
+                    TK_OPEN_CURLY_CURLY@0..2 "{{"
+                    TWIG_EXPRESSION@2..15
+                      TWIG_LITERAL_HASH@2..15
+                        TK_WHITESPACE@2..3 " "
+                        TK_OPEN_CURLY@3..4 "{"
+                        TWIG_LITERAL_HASH_ITEMS@4..14
+                          TWIG_EXPRESSION@4..8
+                            TWIG_SPREAD@4..8
+                              TK_TRIPLE_DOT@4..7 "..."
+                              TWIG_EXPRESSION@7..8
+                                TWIG_LITERAL_NAME@7..8
+                                  TK_WORD@7..8 "a"
+                          TK_COMMA@8..9 ","
+                          TWIG_LITERAL_HASH_PAIR@9..14
+                            TWIG_LITERAL_HASH_KEY@9..11
+                              TK_WHITESPACE@9..10 " "
+                              TK_WORD@10..11 "b"
+                            TK_COLON@11..12 ":"
+                            TWIG_EXPRESSION@12..14
+                              TWIG_LITERAL_NUMBER@12..14
+                                TK_WHITESPACE@12..13 " "
+                                TK_NUMBER@13..14 "1"
+                        TK_CLOSE_CURLY@14..15 "}"
+                    TK_WHITESPACE@15..16 " "
+                    TK_CLOSE_CURLY_CURLY@16..18 "}}""#]],
+        );
+    }
+
+    #[test]
+    fn parse_twig_has_some_expression() {
+        check_parse(
+            "{{ a has some b }}",
+            expect![[r#"
+                ROOT@0..18
+                  TWIG_VAR@0..18
+                    TK_OPEN_CURLY_CURLY@0..2 "{{"
+                    TWIG_EXPRESSION@2..15
+                      TWIG_BINARY_EXPRESSION@2..15
+                        TWIG_EXPRESSION@2..4
+                          TWIG_LITERAL_NAME@2..4
+                            TK_WHITESPACE@2..3 " "
+                            TK_WORD@3..4 "a"
+                        TK_WHITESPACE@4..5 " "
+                        TK_HAS_SOME@5..13 "has some"
+                        TWIG_EXPRESSION@13..15
+                          TWIG_LITERAL_NAME@13..15
+                            TK_WHITESPACE@13..14 " "
+                            TK_WORD@14..15 "b"
+                    TK_WHITESPACE@15..16 " "
+                    TK_CLOSE_CURLY_CURLY@16..18 "}}""#]],
+        );
+    }
+
+    #[test]
+    fn parse_twig_syntax_not_supported_by_twig_version() {
+        let parse = parse_for_twig_version("{{ f(v => v) }}", TwigVersion::new(2, 9));
+        expect![[r#"error at 7..9: expected syntax of twig 2.9 (arrow functions require twig 2.10 or newer) but found =>"#]]
+            .assert_eq(&parse.errors[0].to_string());
+
+        let parse = parse_for_twig_version("{{ [...a] }}", TwigVersion::new(3, 6));
+        expect![[r#"error at 4..7: expected syntax of twig 3.6 (spread operators require twig 3.7 or newer) but found ..."#]]
+            .assert_eq(&parse.errors[0].to_string());
+
+        let parse = parse_for_twig_version("{{ a has every b }}", TwigVersion::new(2, u8::MAX));
+        expect![[r#"error at 5..14: expected syntax of twig 2.x ('has some' and 'has every' operators require twig 3.11 or newer) but found has every"#]]
+            .assert_eq(&parse.errors[0].to_string());
+
+        let parse = parse_for_twig_version("{{ a has every v => v > 1 }}", TwigVersion::new(3, 11));
+        assert!(parse.errors.is_empty());
+    }
 }
@@ -18,7 +18,7 @@ pub(crate) fn parse_twig_literal(parser: &mut Parser) -> Option<CompletedMarker>
         Some(parse_twig_string(parser, true))
     } else if parser.at(T!["["]) {
         Some(parse_twig_array(parser))
-    } else if parser.at(T!["null"]) {
+    } else if parser.at_set(&[T!["null"], T!["none"]]) {
         Some(parse_twig_null(parser))
     } else if parser.at_set(&[T!["true"], T!["false"]]) {
         Some(parse_twig_boolean(parser))
@@ -153,7 +153,7 @@ fn parse_twig_array(parser: &mut Parser) -> CompletedMarker {
 }
 
 fn parse_twig_null(parser: &mut Parser) -> CompletedMarker {
-    debug_assert!(parser.at(T!["null"]));
+    debug_assert!(parser.at_set(&[T!["null"], T!["none"]]));
     let m = parser.start();
     parser.bump();
 
@@ -196,6 +196,11 @@ fn parse_twig_hash(parser: &mut Parser) -> CompletedMarker {
 }
 
 fn parse_twig_hash_pair(parser: &mut Parser) -> Option<CompletedMarker> {
+    if parser.at(T!["..."]) {
+        // spread of another hash like `{...defaults, a: 1}`
+        return parse_twig_expression(parser);
+    }
+
     let key = if parser.at(T![number]) {
         let m = parse_twig_number(parser);
         let preceded = parser.precede(m);
@@ -432,7 +437,7 @@ pub(crate) fn parse_twig_name(parser: &mut Parser) -> Option<CompletedMarker> {
 mod tests {
     use expect_test::expect;
 
-    use crate::parser::check_parse;
+    use crate::testing::check_parse;
 
     #[test]
     fn parse_twig_string_single_quotes() {
@@ -753,6 +758,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_twig_none() {
+        check_parse(
+            "{{ none }}",
+            expect![[r#"
+                ROOT@0..10
+                  TWIG_VAR@0..10
+                    TK_OPEN_CURLY_CURLY@0..2 "{{"
+                    TWIG_EXPRESSION@2..7
+                      TWIG_LITERAL_NULL@2..7
+                        TK_WHITESPACE@2..3 " "
+                        TK_NONE@3..7 "none"
+                    TK_WHITESPACE@7..8 " "
+                    TK_CLOSE_CURLY_CURLY@8..10 "}}""#]],
+        );
+    }
+
+    #[test]
+    fn parse_twig_uppercase_literals() {
+        check_parse(
+            "{{ [TRUE, False, NULL, NONE] }}",
+            expect![[r#"
+                ROOT@0..31
+                  TWIG_VAR@0..31
+                    TK_OPEN_CURLY_CURLY@0..2 "{{"
+                    TWIG_EXPRESSION@2..28
+                      TWIG_LITERAL_ARRAY@2..28
+                        TK_WHITESPACE@2..3 " "
+                        TK_OPEN_SQUARE@3..4 "["
+                        TWIG_LITERAL_ARRAY_INNER@4..27
+                          TWIG_EXPRESSION@4..8
+                            TWIG_LITERAL_BOOLEAN@4..8
+                              TK_TRUE@4..8 "TRUE"
+                          TK_COMMA@8..9 ","
+                          TWIG_EXPRESSION@9..15
+                            TWIG_LITERAL_BOOLEAN@9..15
+                              TK_WHITESPACE@9..10 " "
+                              TK_FALSE@10..15 "False"
+                          TK_COMMA@15..16 ","
+                          TWIG_EXPRESSION@16..21
+                            TWIG_LITERAL_NULL@16..21
+                              TK_WHITESPACE@16..17 " "
+                              TK_NULL@17..21 "NULL"
+                          TK_COMMA@21..22 ","
+                          TWIG_EXPRESSION@22..27
+                            TWIG_LITERAL_NULL@22..27
+                              TK_WHITESPACE@22..23 " "
+                              TK_NONE@23..27 "NONE"
+                        TK_CLOSE_SQUARE@27..28 "]"
+                    TK_WHITESPACE@28..29 " "
+                    TK_CLOSE_CURLY_CURLY@29..31 "}}""#]],
+        );
+    }
+
     #[test]
     fn parse_twig_boolean_true() {
         check_parse(
@@ -196,7 +196,7 @@ fn parse_twig_sw_include(parser: &mut Parser, outer: Marker) -> CompletedMarker
 
 #[cfg(test)]
 mod tests {
-    use crate::parser::check_parse;
+    use crate::testing::check_parse;
     use expect_test::expect;
 
     #[test]
@@ -213,7 +213,8 @@ mod tests {
                       TK_WHITESPACE@13..14 " "
                       TK_SINGLE_QUOTES@14..15 "'"
                       TWIG_LITERAL_STRING_INNER@15..52
-                        TK_WORD@15..26 "@Storefront"
+                        TK_AT@15..16 "@"
+                        TK_WORD@16..26 "Storefront"
                         TK_FORWARD_SLASH@26..27 "/"
                         TK_WORD@27..37 "storefront"
                         TK_FORWARD_SLASH@37..38 "/"
@@ -243,7 +244,8 @@ mod tests {
                         TK_WHITESPACE@13..14 " "
                         TK_SINGLE_QUOTES@14..15 "'"
                         TWIG_LITERAL_STRING_INNER@15..59
-                          TK_WORD@15..26 "@Storefront"
+                          TK_AT@15..16 "@"
+                          TK_WORD@16..26 "Storefront"
                           TK_FORWARD_SLASH@26..27 "/"
                           TK_WORD@27..37 "storefront"
                           TK_FORWARD_SLASH@37..38 "/"
@@ -275,7 +277,8 @@ mod tests {
                         TK_WHITESPACE@13..14 " "
                         TK_DOUBLE_QUOTES@14..15 "\""
                         TWIG_LITERAL_STRING_INNER@15..63
-                          TK_WORD@15..26 "@Storefront"
+                          TK_AT@15..16 "@"
+                          TK_WORD@16..26 "Storefront"
                           TK_FORWARD_SLASH@26..27 "/"
                           TK_WORD@27..37 "storefront"
                           TK_FORWARD_SLASH@37..38 "/"
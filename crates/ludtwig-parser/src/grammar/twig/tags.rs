@@ -9,7 +9,7 @@ use crate::grammar::{parse_many, ParseFunction};
 use crate::parser::event::{CompletedMarker, Marker};
 use crate::parser::{ParseErrorBuilder, Parser};
 use crate::syntax::untyped::SyntaxKind;
-use crate::T;
+use crate::{TwigVersion, T};
 
 /// Checks if the parser is at an twig ending / delimiter tag like
 /// `endblock` or `elseif` which should be caught by html body parsers to stop parsing early
@@ -26,12 +26,16 @@ pub(crate) fn at_twig_termination_tag(p: &mut Parser) -> bool {
         || p.at_following(&[T!["{%"], T!["endfor"]])
         || p.at_following(&[T!["{%"], T!["endembed"]])
         || p.at_following(&[T!["{%"], T!["endapply"]])
+        || p.at_following(&[T!["{%"], T!["endfilter"]])
         || p.at_following(&[T!["{%"], T!["endautoescape"]])
         || p.at_following(&[T!["{%"], T!["endsandbox"]])
+        || p.at_following(&[T!["{%"], T!["endguard"]])
+        || p.at_following(&[T!["{%"], T!["endspaceless"]])
         || p.at_following(&[T!["{%"], T!["endverbatim"]])
         || p.at_following(&[T!["{%"], T!["endmacro"]])
         || p.at_following(&[T!["{%"], T!["endwith"]])
         || p.at_following(&[T!["{%"], T!["endcache"]])
+        || p.at_following(&[T!["{%"], T!["endtrans"]])
         || p.at_following(&[T!["{%"], T!["endsw_silent_feature_call"]])
 }
 
@@ -63,7 +67,7 @@ pub(crate) fn parse_twig_block_statement(
         Some(parse_twig_from(parser, m))
     } else if parser.at(T!["import"]) {
         Some(parse_twig_import(parser, m))
-    } else if parser.at(T!["apply"]) {
+    } else if parser.at_set(&[T!["apply"], T!["filter"]]) {
         Some(parse_twig_apply(parser, m, child_parser))
     } else if parser.at(T!["autoescape"]) {
         Some(parse_twig_autoescape(parser, m, child_parser))
@@ -75,6 +79,10 @@ pub(crate) fn parse_twig_block_statement(
         Some(parse_twig_flush(parser, m))
     } else if parser.at(T!["sandbox"]) {
         Some(parse_twig_sandbox(parser, m, child_parser))
+    } else if parser.at(T!["guard"]) {
+        Some(parse_twig_guard(parser, m, child_parser))
+    } else if parser.at(T!["spaceless"]) {
+        Some(parse_twig_spaceless(parser, m, child_parser))
     } else if parser.at(T!["verbatim"]) {
         Some(parse_twig_verbatim(parser, m, child_parser))
     } else if parser.at(T!["macro"]) {
@@ -83,10 +91,15 @@ pub(crate) fn parse_twig_block_statement(
         Some(parse_twig_with(parser, m, child_parser))
     } else if parser.at(T!["cache"]) {
         Some(parse_twig_cache(parser, m, child_parser))
+    } else if parser.at(T!["trans"]) {
+        Some(parse_twig_trans(parser, m, child_parser))
+    } else if parser.at(T!["trans_default_domain"]) {
+        Some(parse_twig_trans_default_domain(parser, m))
     } else {
         match parse_shopware_twig_block_statement(parser, m, child_parser) {
             BlockParseResult::NothingFound(m) => {
                 parser.add_error(ParseErrorBuilder::new("twig tag".to_string()));
+                parser.recover_twig_tag();
                 parser.complete(m, SyntaxKind::ERROR);
                 None
             }
@@ -344,6 +357,192 @@ fn parse_twig_sandbox(
     parser.complete(wrapper_m, SyntaxKind::TWIG_SANDBOX)
 }
 
+fn parse_twig_guard(
+    parser: &mut Parser,
+    outer: Marker,
+    child_parser: ParseFunction,
+) -> CompletedMarker {
+    debug_assert!(parser.at(T!["guard"]));
+    parser.require_twig_version(TwigVersion::new(3, 15), "guard tags");
+    parser.bump();
+
+    // type of the guarded construct
+    let is_at_type = parser.at(T!["filter"])
+        || parser
+            .peek_token()
+            .is_some_and(|t| matches!(t.text, "function" | "test"));
+    if is_at_type {
+        parser.bump_as(SyntaxKind::TK_WORD);
+    } else {
+        parser.add_error(ParseErrorBuilder::new("function, filter or test"));
+    }
+
+    if parse_twig_name(parser).is_none() {
+        parser.add_error(ParseErrorBuilder::new("twig name of the guarded construct"));
+        parser.recover(&[T!["%}"], T!["else"], T!["endguard"], T!["</"]]);
+    }
+    parser.expect(T!["%}"], &[T!["else"], T!["endguard"], T!["%}"], T!["</"]]);
+
+    let wrapper_m = parser.complete(outer, SyntaxKind::TWIG_GUARD_STARTING_BLOCK);
+    let wrapper_m = parser.precede(wrapper_m);
+
+    // parse all the children except else or endguard
+    let body_m = parser.start();
+    parse_many(
+        parser,
+        |p| p.at_following(&[T!["{%"], T!["endguard"]]) || p.at_following(&[T!["{%"], T!["else"]]),
+        |p| {
+            child_parser(p);
+        },
+    );
+    parser.complete(body_m, SyntaxKind::BODY);
+
+    // check for else block, which is rendered if the construct doesn't exist
+    if parser.at_following(&[T!["{%"], T!["else"]]) {
+        let else_m = parser.start();
+        parser.bump();
+        parser.bump();
+        parser.expect(T!["%}"], &[T!["endguard"], T!["%}"], T!["</"]]);
+        parser.complete(else_m, SyntaxKind::TWIG_GUARD_ELSE_BLOCK);
+
+        // parse all the children except endguard
+        let body_m = parser.start();
+        parse_many(
+            parser,
+            |p| p.at_following(&[T!["{%"], T!["endguard"]]),
+            |p| {
+                child_parser(p);
+            },
+        );
+        parser.complete(body_m, SyntaxKind::BODY);
+    }
+
+    let end_block_m = parser.start();
+    parser.expect(T!["{%"], &[T!["endguard"], T!["%}"], T!["</"]]);
+    parser.expect(T!["endguard"], &[T!["%}"], T!["</"]]);
+    parser.expect(T!["%}"], &[T!["</"]]);
+    parser.complete(end_block_m, SyntaxKind::TWIG_GUARD_ENDING_BLOCK);
+
+    // close overall twig guard
+    parser.complete(wrapper_m, SyntaxKind::TWIG_GUARD)
+}
+
+fn parse_twig_spaceless(
+    parser: &mut Parser,
+    outer: Marker,
+    child_parser: ParseFunction,
+) -> CompletedMarker {
+    debug_assert!(parser.at(T!["spaceless"]));
+    parser.bump();
+    parser.expect(T!["%}"], &[T!["endspaceless"], T!["%}"], T!["</"]]);
+
+    let wrapper_m = parser.complete(outer, SyntaxKind::TWIG_SPACELESS_STARTING_BLOCK);
+    let wrapper_m = parser.precede(wrapper_m);
+
+    // parse all the children except endspaceless
+    let body_m = parser.start();
+    parse_many(
+        parser,
+        |p| p.at_following(&[T!["{%"], T!["endspaceless"]]),
+        |p| {
+            child_parser(p);
+        },
+    );
+    parser.complete(body_m, SyntaxKind::BODY);
+
+    let end_block_m = parser.start();
+    parser.expect(T!["{%"], &[T!["endspaceless"], T!["%}"], T!["</"]]);
+    parser.expect(T!["endspaceless"], &[T!["%}"], T!["</"]]);
+    parser.expect(T!["%}"], &[T!["</"]]);
+    parser.complete(end_block_m, SyntaxKind::TWIG_SPACELESS_ENDING_BLOCK);
+
+    // close overall twig spaceless
+    parser.complete(wrapper_m, SyntaxKind::TWIG_SPACELESS)
+}
+
+/// Symfony translation tag like `{% trans with {'%name%': name} from 'app' %}...{% endtrans %}`
+fn parse_twig_trans(
+    parser: &mut Parser,
+    outer: Marker,
+    child_parser: ParseFunction,
+) -> CompletedMarker {
+    debug_assert!(parser.at(T!["trans"]));
+    parser.bump();
+
+    let options = [
+        (
+            T!["with"],
+            "translation variables",
+            SyntaxKind::TWIG_TRANS_WITH,
+        ),
+        (
+            T!["from"],
+            "translation domain",
+            SyntaxKind::TWIG_TRANS_FROM,
+        ),
+        (
+            T!["into"],
+            "translation locale",
+            SyntaxKind::TWIG_TRANS_INTO,
+        ),
+    ];
+    for (keyword, description, kind) in options {
+        if !parser.at(keyword) {
+            continue;
+        }
+
+        let option_m = parser.start();
+        parser.bump();
+        if parse_twig_expression(parser).is_none() {
+            parser.add_error(ParseErrorBuilder::new(format!(
+                "twig expression as {description}"
+            )));
+            parser.recover(&[T!["from"], T!["into"], T!["endtrans"], T!["%}"], T!["</"]]);
+        }
+        parser.complete(option_m, kind);
+    }
+
+    parser.expect(T!["%}"], &[T!["endtrans"], T!["%}"], T!["</"]]);
+
+    let wrapper_m = parser.complete(outer, SyntaxKind::TWIG_TRANS_STARTING_BLOCK);
+    let wrapper_m = parser.precede(wrapper_m);
+
+    // parse all the children except endtrans
+    let body_m = parser.start();
+    parse_many(
+        parser,
+        |p| p.at_following(&[T!["{%"], T!["endtrans"]]),
+        |p| {
+            child_parser(p);
+        },
+    );
+    parser.complete(body_m, SyntaxKind::BODY);
+
+    let end_block_m = parser.start();
+    parser.expect(T!["{%"], &[T!["endtrans"], T!["%}"], T!["</"]]);
+    parser.expect(T!["endtrans"], &[T!["%}"], T!["</"]]);
+    parser.expect(T!["%}"], &[T!["</"]]);
+    parser.complete(end_block_m, SyntaxKind::TWIG_TRANS_ENDING_BLOCK);
+
+    // close overall twig trans
+    parser.complete(wrapper_m, SyntaxKind::TWIG_TRANS)
+}
+
+fn parse_twig_trans_default_domain(parser: &mut Parser, outer: Marker) -> CompletedMarker {
+    debug_assert!(parser.at(T!["trans_default_domain"]));
+    parser.bump();
+
+    if parse_twig_expression(parser).is_none() {
+        parser.add_error(ParseErrorBuilder::new(
+            "twig expression as translation domain",
+        ));
+        parser.recover(&[T!["%}"], T!["</"]]);
+    }
+
+    parser.expect(T!["%}"], &[T!["</"]]);
+    parser.complete(outer, SyntaxKind::TWIG_TRANS_DEFAULT_DOMAIN)
+}
+
 fn parse_twig_flush(parser: &mut Parser, outer: Marker) -> CompletedMarker {
     debug_assert!(parser.at(T!["flush"]));
     parser.bump();
@@ -429,7 +628,13 @@ fn parse_twig_apply(
     outer: Marker,
     child_parser: ParseFunction,
 ) -> CompletedMarker {
-    debug_assert!(parser.at(T!["apply"]));
+    debug_assert!(parser.at_set(&[T!["apply"], T!["filter"]]));
+    // the deprecated filter tag works exactly like the apply tag
+    let end_keyword = if parser.at(T!["filter"]) {
+        T!["endfilter"]
+    } else {
+        T!["endapply"]
+    };
     parser.bump();
 
     // parse any amount of filters
@@ -452,7 +657,7 @@ fn parse_twig_apply(
                 },
             );
             parser.complete(arguments_m, SyntaxKind::TWIG_ARGUMENTS);
-            parser.expect(T![")"], &[T!["endapply"], T!["%}"], T!["</"]]);
+            parser.expect(T![")"], &[end_keyword, T!["%}"], T!["</"]]);
         }
 
         // parse any amount of piped filters
@@ -467,19 +672,19 @@ fn parse_twig_apply(
         );
     } else {
         parser.add_error(ParseErrorBuilder::new("twig filter"));
-        parser.recover(&[T!["%}"], T!["endapply"], T!["</"]]);
+        parser.recover(&[T!["%}"], end_keyword, T!["</"]]);
     }
 
-    parser.expect(T!["%}"], &[T!["endapply"], T!["</"]]);
+    parser.expect(T!["%}"], &[end_keyword, T!["</"]]);
 
     let wrapper_m = parser.complete(outer, SyntaxKind::TWIG_APPLY_STARTING_BLOCK);
     let wrapper_m = parser.precede(wrapper_m);
 
-    // parse all the children except endapply
+    // parse all the children except endapply / endfilter
     let body_m = parser.start();
     parse_many(
         parser,
-        |p| p.at_following(&[T!["{%"], T!["endapply"]]),
+        |p| p.at_following(&[T!["{%"], end_keyword]),
         |p| {
             child_parser(p);
         },
@@ -487,8 +692,8 @@ fn parse_twig_apply(
     parser.complete(body_m, SyntaxKind::BODY);
 
     let end_block_m = parser.start();
-    parser.expect(T!["{%"], &[T!["endapply"], T!["%}"], T!["</"]]);
-    parser.expect(T!["endapply"], &[T!["%}"], T!["</"]]);
+    parser.expect(T!["{%"], &[end_keyword, T!["%}"], T!["</"]]);
+    parser.expect(end_keyword, &[T!["%}"], T!["</"]]);
     parser.expect(T!["%}"], &[T!["</"]]);
     parser.complete(end_block_m, SyntaxKind::TWIG_APPLY_ENDING_BLOCK);
 
@@ -1050,18 +1255,74 @@ fn parse_twig_if(
 
 #[cfg(test)]
 mod tests {
-    use crate::parser::check_parse;
+    use crate::testing::check_parse;
+    use crate::{parse_for_twig_version, TwigVersion};
     use expect_test::expect;
 
+    #[test]
+    fn parse_error_recovers_after_broken_tag() {
+        check_parse(
+            "<div>{% foo a < b %}<p></p>{% bar <hr/></div>",
+            expect![[r#"
+                ROOT@0..45
+                  HTML_TAG@0..45
+                    HTML_STARTING_TAG@0..5
+                      TK_LESS_THAN@0..1 "<"
+                      TK_WORD@1..4 "div"
+                      HTML_ATTRIBUTE_LIST@4..4
+                      TK_GREATER_THAN@4..5 ">"
+                    BODY@5..39
+                      ERROR@5..20
+                        TK_CURLY_PERCENT@5..7 "{%"
+                        TK_WHITESPACE@7..8 " "
+                        TK_WORD@8..11 "foo"
+                        TK_WHITESPACE@11..12 " "
+                        TK_WORD@12..13 "a"
+                        TK_WHITESPACE@13..14 " "
+                        TK_LESS_THAN@14..15 "<"
+                        TK_WHITESPACE@15..16 " "
+                        TK_WORD@16..17 "b"
+                        TK_WHITESPACE@17..18 " "
+                        TK_PERCENT_CURLY@18..20 "%}"
+                      HTML_TAG@20..27
+                        HTML_STARTING_TAG@20..23
+                          TK_LESS_THAN@20..21 "<"
+                          TK_WORD@21..22 "p"
+                          HTML_ATTRIBUTE_LIST@22..22
+                          TK_GREATER_THAN@22..23 ">"
+                        BODY@23..23
+                        HTML_ENDING_TAG@23..27
+                          TK_LESS_THAN_SLASH@23..25 "</"
+                          TK_WORD@25..26 "p"
+                          TK_GREATER_THAN@26..27 ">"
+                      ERROR@27..33
+                        TK_CURLY_PERCENT@27..29 "{%"
+                        TK_WHITESPACE@29..30 " "
+                        TK_WORD@30..33 "bar"
+                      HTML_TAG@33..39
+                        HTML_STARTING_TAG@33..39
+                          TK_WHITESPACE@33..34 " "
+                          TK_LESS_THAN@34..35 "<"
+                          TK_WORD@35..37 "hr"
+                          HTML_ATTRIBUTE_LIST@37..37
+                          TK_SLASH_GREATER_THAN@37..39 "/>"
+                    HTML_ENDING_TAG@39..45
+                      TK_LESS_THAN_SLASH@39..41 "</"
+                      TK_WORD@41..44 "div"
+                      TK_GREATER_THAN@44..45 ">"
+                error at 8..11: expected twig tag but found word
+                error at 30..33: expected twig tag but found word"#]],
+        );
+    }
+
     #[test]
     fn parse_error() {
         check_parse(
             "{% asdf",
             expect![[r#"
                 ROOT@0..7
-                  ERROR@0..2
+                  ERROR@0..7
                     TK_CURLY_PERCENT@0..2 "{%"
-                  HTML_TEXT@2..7
                     TK_WHITESPACE@2..3 " "
                     TK_WORD@3..7 "asdf"
                 error at 3..7: expected twig tag but found word"#]],
@@ -1212,52 +1473,53 @@ mod tests {
         check_parse(
             "{% if temperature > 18 and temperature < 27 %} true {% endif %}",
             expect![[r#"
-            ROOT@0..63
-              TWIG_IF@0..63
-                TWIG_IF_BLOCK@0..46
-                  TK_CURLY_PERCENT@0..2 "{%"
-                  TK_WHITESPACE@2..3 " "
-                  TK_IF@3..5 "if"
-                  TWIG_EXPRESSION@5..43
-                    TWIG_BINARY_EXPRESSION@5..43
-                      TWIG_BINARY_EXPRESSION@5..22
-                        TWIG_EXPRESSION@5..17
-                          TWIG_LITERAL_NAME@5..17
-                            TK_WHITESPACE@5..6 " "
-                            TK_WORD@6..17 "temperature"
-                        TK_WHITESPACE@17..18 " "
-                        TK_GREATER_THAN@18..19 ">"
-                        TWIG_EXPRESSION@19..22
-                          TWIG_LITERAL_NUMBER@19..22
-                            TK_WHITESPACE@19..20 " "
-                            TK_NUMBER@20..22 "18"
-                      TK_WHITESPACE@22..23 " "
-                      TK_AND@23..26 "and"
-                      TWIG_EXPRESSION@26..43
-                        TWIG_BINARY_EXPRESSION@26..43
-                          TWIG_EXPRESSION@26..38
-                            TWIG_LITERAL_NAME@26..38
-                              TK_WHITESPACE@26..27 " "
-                              TK_WORD@27..38 "temperature"
-                          TK_WHITESPACE@38..39 " "
-                          TK_LESS_THAN@39..40 "<"
-                          TWIG_EXPRESSION@40..43
-                            TWIG_LITERAL_NUMBER@40..43
-                              TK_WHITESPACE@40..41 " "
-                              TK_NUMBER@41..43 "27"
-                  TK_WHITESPACE@43..44 " "
-                  TK_PERCENT_CURLY@44..46 "%}"
-                BODY@46..51
-                  HTML_TEXT@46..51
-                    TK_WHITESPACE@46..47 " "
-                    TK_TRUE@47..51 "true"
-                TWIG_ENDIF_BLOCK@51..63
-                  TK_WHITESPACE@51..52 " "
-                  TK_CURLY_PERCENT@52..54 "{%"
-                  TK_WHITESPACE@54..55 " "
-                  TK_ENDIF@55..60 "endif"
-                  TK_WHITESPACE@60..61 " "
-                  TK_PERCENT_CURLY@61..63 "%}""#]],
+                ROOT@0..63
+                  TWIG_IF@0..63
+                    TWIG_IF_BLOCK@0..46
+                      TK_CURLY_PERCENT@0..2 "{%"
+                      TK_WHITESPACE@2..3 " "
+                      TK_IF@3..5 "if"
+                      TWIG_EXPRESSION@5..43
+                        TWIG_BINARY_EXPRESSION@5..43
+                          TWIG_EXPRESSION@5..22
+                            TWIG_BINARY_EXPRESSION@5..22
+                              TWIG_EXPRESSION@5..17
+                                TWIG_LITERAL_NAME@5..17
+                                  TK_WHITESPACE@5..6 " "
+                                  TK_WORD@6..17 "temperature"
+                              TK_WHITESPACE@17..18 " "
+                              TK_GREATER_THAN@18..19 ">"
+                              TWIG_EXPRESSION@19..22
+                                TWIG_LITERAL_NUMBER@19..22
+                                  TK_WHITESPACE@19..20 " "
+                                  TK_NUMBER@20..22 "18"
+                          TK_WHITESPACE@22..23 " "
+                          TK_AND@23..26 "and"
+                          TWIG_EXPRESSION@26..43
+                            TWIG_BINARY_EXPRESSION@26..43
+                              TWIG_EXPRESSION@26..38
+                                TWIG_LITERAL_NAME@26..38
+                                  TK_WHITESPACE@26..27 " "
+                                  TK_WORD@27..38 "temperature"
+                              TK_WHITESPACE@38..39 " "
+                              TK_LESS_THAN@39..40 "<"
+                              TWIG_EXPRESSION@40..43
+                                TWIG_LITERAL_NUMBER@40..43
+                                  TK_WHITESPACE@40..41 " "
+                                  TK_NUMBER@41..43 "27"
+                      TK_WHITESPACE@43..44 " "
+                      TK_PERCENT_CURLY@44..46 "%}"
+                    BODY@46..51
+                      HTML_TEXT@46..51
+                        TK_WHITESPACE@46..47 " "
+                        TK_TRUE@47..51 "true"
+                    TWIG_ENDIF_BLOCK@51..63
+                      TK_WHITESPACE@51..52 " "
+                      TK_CURLY_PERCENT@52..54 "{%"
+                      TK_WHITESPACE@54..55 " "
+                      TK_ENDIF@55..60 "endif"
+                      TK_WHITESPACE@60..61 " "
+                      TK_PERCENT_CURLY@61..63 "%}""#]],
         );
     }
 
@@ -1517,21 +1779,19 @@ mod tests {
         check_parse(
             "{% block my_block %}",
             expect![[r#"
-            ROOT@0..20
-              TWIG_BLOCK@0..20
-                TWIG_STARTING_BLOCK@0..20
-                  TK_CURLY_PERCENT@0..2 "{%"
-                  TK_WHITESPACE@2..3 " "
-                  TK_BLOCK@3..8 "block"
-                  TK_WHITESPACE@8..9 " "
-                  TK_WORD@9..17 "my_block"
-                  TK_WHITESPACE@17..18 " "
-                  TK_PERCENT_CURLY@18..20 "%}"
-                BODY@20..20
-                TWIG_ENDING_BLOCK@20..20
-            error at 18..20: expected {% but reached end of file
-            error at 18..20: expected endblock but reached end of file
-            error at 18..20: expected %} but reached end of file"#]],
+                ROOT@0..20
+                  TWIG_BLOCK@0..20
+                    TWIG_STARTING_BLOCK@0..20
+                      TK_CURLY_PERCENT@0..2 "{%"
+                      TK_WHITESPACE@2..3 " "
+                      TK_BLOCK@3..8 "block"
+                      TK_WHITESPACE@8..9 " "
+                      TK_WORD@9..17 "my_block"
+                      TK_WHITESPACE@17..18 " "
+                      TK_PERCENT_CURLY@18..20 "%}"
+                    BODY@20..20
+                    TWIG_ENDING_BLOCK@20..20
+                error at 18..20: expected {% or endblock or %} but reached end of file"#]],
         );
     }
 
@@ -1570,8 +1830,7 @@ mod tests {
                       HTML_TEXT@49..61
                         TK_LINE_BREAK@49..50 "\n"
                         TK_WHITESPACE@50..58 "        "
-                        TK_DOUBLE_DOT@58..60 ".."
-                        TK_DOT@60..61 "."
+                        TK_TRIPLE_DOT@58..61 "..."
                     TWIG_ENDING_BLOCK@61..94
                       TK_LINE_BREAK@61..62 "\n"
                       TK_WHITESPACE@62..66 "    "
@@ -1629,8 +1888,7 @@ mod tests {
                       HTML_TEXT@49..61
                         TK_LINE_BREAK@49..50 "\n"
                         TK_WHITESPACE@50..58 "        "
-                        TK_DOUBLE_DOT@58..60 ".."
-                        TK_DOT@60..61 "."
+                        TK_TRIPLE_DOT@58..61 "..."
                     TWIG_ENDING_BLOCK@61..88
                       TK_LINE_BREAK@61..62 "\n"
                       TK_WHITESPACE@62..66 "    "
@@ -1970,9 +2228,7 @@ mod tests {
                     BODY@23..23
                     TWIG_ENDSET_BLOCK@23..23
                 error at 21..23: expected = followed by 3 twig expressions but found %}
-                error at 21..23: expected {% but reached end of file
-                error at 21..23: expected endset but reached end of file
-                error at 21..23: expected %} but reached end of file"#]],
+                error at 21..23: expected {% or endset or %} but reached end of file"#]],
         );
     }
 
@@ -3334,6 +3590,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_twig_filter_tag() {
+        check_parse(
+            r#"{% filter upper %}a{% endfilter %}"#,
+            expect![[r#"
+                ROOT@0..34
+                  TWIG_APPLY@0..34
+                    TWIG_APPLY_STARTING_BLOCK@0..18
+                      TK_CURLY_PERCENT@0..2 "{%"
+                      TK_WHITESPACE@2..3 " "
+                      TK_FILTER@3..9 "filter"
+                      TWIG_LITERAL_NAME@9..15
+                        TK_WHITESPACE@9..10 " "
+                        TK_WORD@10..15 "upper"
+                      TK_WHITESPACE@15..16 " "
+                      TK_PERCENT_CURLY@16..18 "%}"
+                    BODY@18..19
+                      HTML_TEXT@18..19
+                        TK_WORD@18..19 "a"
+                    TWIG_APPLY_ENDING_BLOCK@19..34
+                      TK_CURLY_PERCENT@19..21 "{%"
+                      TK_WHITESPACE@21..22 " "
+                      TK_ENDFILTER@22..31 "endfilter"
+                      TK_WHITESPACE@31..32 " "
+                      TK_PERCENT_CURLY@32..34 "%}""#]],
+        );
+    }
+
     #[test]
     fn parse_twig_apply_wrong_type() {
         check_parse(
@@ -3748,8 +4032,7 @@ mod tests {
                       HTML_TEXT@38..46
                         TK_LINE_BREAK@38..39 "\n"
                         TK_WHITESPACE@39..43 "    "
-                        TK_DOUBLE_DOT@43..45 ".."
-                        TK_DOT@45..46 "."
+                        TK_TRIPLE_DOT@43..46 "..."
                     TWIG_EMBED_ENDING_BLOCK@46..61
                       TK_LINE_BREAK@46..47 "\n"
                       TK_CURLY_PERCENT@47..49 "{%"
@@ -3812,8 +4095,7 @@ mod tests {
                       HTML_TEXT@43..51
                         TK_LINE_BREAK@43..44 "\n"
                         TK_WHITESPACE@44..48 "    "
-                        TK_DOUBLE_DOT@48..50 ".."
-                        TK_DOT@50..51 "."
+                        TK_TRIPLE_DOT@48..51 "..."
                     TWIG_EMBED_ENDING_BLOCK@51..66
                       TK_LINE_BREAK@51..52 "\n"
                       TK_CURLY_PERCENT@52..54 "{%"
@@ -3852,8 +4134,7 @@ mod tests {
                       HTML_TEXT@33..41
                         TK_LINE_BREAK@33..34 "\n"
                         TK_WHITESPACE@34..38 "    "
-                        TK_DOUBLE_DOT@38..40 ".."
-                        TK_DOT@40..41 "."
+                        TK_TRIPLE_DOT@38..41 "..."
                     TWIG_EMBED_ENDING_BLOCK@41..56
                       TK_LINE_BREAK@41..42 "\n"
                       TK_CURLY_PERCENT@42..44 "{%"
@@ -3992,24 +4273,23 @@ mod tests {
         check_parse(
             r#"{% from 'forms.html' %}"#,
             expect![[r#"
-            ROOT@0..23
-              TWIG_FROM@0..23
-                TK_CURLY_PERCENT@0..2 "{%"
-                TK_WHITESPACE@2..3 " "
-                TK_FROM@3..7 "from"
-                TWIG_EXPRESSION@7..20
-                  TWIG_LITERAL_STRING@7..20
-                    TK_WHITESPACE@7..8 " "
-                    TK_SINGLE_QUOTES@8..9 "'"
-                    TWIG_LITERAL_STRING_INNER@9..19
-                      TK_WORD@9..14 "forms"
-                      TK_DOT@14..15 "."
-                      TK_WORD@15..19 "html"
-                    TK_SINGLE_QUOTES@19..20 "'"
-                TK_WHITESPACE@20..21 " "
-                TK_PERCENT_CURLY@21..23 "%}"
-            error at 21..23: expected import but found %}
-            error at 21..23: expected at least one macro name as macro name but found %}"#]],
+                ROOT@0..23
+                  TWIG_FROM@0..23
+                    TK_CURLY_PERCENT@0..2 "{%"
+                    TK_WHITESPACE@2..3 " "
+                    TK_FROM@3..7 "from"
+                    TWIG_EXPRESSION@7..20
+                      TWIG_LITERAL_STRING@7..20
+                        TK_WHITESPACE@7..8 " "
+                        TK_SINGLE_QUOTES@8..9 "'"
+                        TWIG_LITERAL_STRING_INNER@9..19
+                          TK_WORD@9..14 "forms"
+                          TK_DOT@14..15 "."
+                          TK_WORD@15..19 "html"
+                        TK_SINGLE_QUOTES@19..20 "'"
+                    TK_WHITESPACE@20..21 " "
+                    TK_PERCENT_CURLY@21..23 "%}"
+                error at 21..23: expected import or at least one macro name as macro name but found %}"#]],
         );
     }
 
@@ -4104,27 +4384,182 @@ mod tests {
         check_parse(
             r#"{% import "forms.html" %}"#,
             expect![[r#"
-            ROOT@0..25
-              TWIG_IMPORT@0..25
-                TK_CURLY_PERCENT@0..2 "{%"
-                TK_WHITESPACE@2..3 " "
-                TK_IMPORT@3..9 "import"
-                TWIG_EXPRESSION@9..22
-                  TWIG_LITERAL_STRING@9..22
-                    TK_WHITESPACE@9..10 " "
-                    TK_DOUBLE_QUOTES@10..11 "\""
-                    TWIG_LITERAL_STRING_INNER@11..21
-                      TK_WORD@11..16 "forms"
-                      TK_DOT@16..17 "."
-                      TK_WORD@17..21 "html"
-                    TK_DOUBLE_QUOTES@21..22 "\""
-                TK_WHITESPACE@22..23 " "
-                TK_PERCENT_CURLY@23..25 "%}"
-            error at 23..25: expected as but found %}
-            error at 23..25: expected name for twig macro but found %}"#]],
+                ROOT@0..25
+                  TWIG_IMPORT@0..25
+                    TK_CURLY_PERCENT@0..2 "{%"
+                    TK_WHITESPACE@2..3 " "
+                    TK_IMPORT@3..9 "import"
+                    TWIG_EXPRESSION@9..22
+                      TWIG_LITERAL_STRING@9..22
+                        TK_WHITESPACE@9..10 " "
+                        TK_DOUBLE_QUOTES@10..11 "\""
+                        TWIG_LITERAL_STRING_INNER@11..21
+                          TK_WORD@11..16 "forms"
+                          TK_DOT@16..17 "."
+                          TK_WORD@17..21 "html"
+                        TK_DOUBLE_QUOTES@21..22 "\""
+                    TK_WHITESPACE@22..23 " "
+                    TK_PERCENT_CURLY@23..25 "%}"
+                error at 23..25: expected as or name for twig macro but found %}"#]],
+        );
+    }
+
+    #[test]
+    fn parse_twig_spaceless() {
+        check_parse(
+            r#"{% spaceless %}a{% endspaceless %}"#,
+            expect![[r#"
+                ROOT@0..34
+                  TWIG_SPACELESS@0..34
+                    TWIG_SPACELESS_STARTING_BLOCK@0..15
+                      TK_CURLY_PERCENT@0..2 "{%"
+                      TK_WHITESPACE@2..3 " "
+                      TK_SPACELESS@3..12 "spaceless"
+                      TK_WHITESPACE@12..13 " "
+                      TK_PERCENT_CURLY@13..15 "%}"
+                    BODY@15..16
+                      HTML_TEXT@15..16
+                        TK_WORD@15..16 "a"
+                    TWIG_SPACELESS_ENDING_BLOCK@16..34
+                      TK_CURLY_PERCENT@16..18 "{%"
+                      TK_WHITESPACE@18..19 " "
+                      TK_ENDSPACELESS@19..31 "endspaceless"
+                      TK_WHITESPACE@31..32 " "
+                      TK_PERCENT_CURLY@32..34 "%}""#]],
+        );
+    }
+
+    #[test]
+    fn parse_twig_trans() {
+        check_parse(
+            r#"{% trans from 'app' %}Hi{% endtrans %}"#,
+            expect![[r#"
+                ROOT@0..38
+                  TWIG_TRANS@0..38
+                    TWIG_TRANS_STARTING_BLOCK@0..22
+                      TK_CURLY_PERCENT@0..2 "{%"
+                      TK_WHITESPACE@2..3 " "
+                      TK_TRANS@3..8 "trans"
+                      TWIG_TRANS_FROM@8..19
+                        TK_WHITESPACE@8..9 " "
+                        TK_FROM@9..13 "from"
+                        TWIG_EXPRESSION@13..19
+                          TWIG_LITERAL_STRING@13..19
+                            TK_WHITESPACE@13..14 " "
+                            TK_SINGLE_QUOTES@14..15 "'"
+                            TWIG_LITERAL_STRING_INNER@15..18
+                              TK_WORD@15..18 "app"
+                            TK_SINGLE_QUOTES@18..19 "'"
+                      TK_WHITESPACE@19..20 " "
+                      TK_PERCENT_CURLY@20..22 "%}"
+                    BODY@22..24
+                      HTML_TEXT@22..24
+                        TK_WORD@22..24 "Hi"
+                    TWIG_TRANS_ENDING_BLOCK@24..38
+                      TK_CURLY_PERCENT@24..26 "{%"
+                      TK_WHITESPACE@26..27 " "
+                      TK_ENDTRANS@27..35 "endtrans"
+                      TK_WHITESPACE@35..36 " "
+                      TK_PERCENT_CURLY@36..38 "%}""#]],
+        );
+    }
+
+    #[test]
+    fn parse_twig_trans_default_domain() {
+        check_parse(
+            r#"{% trans_default_domain 'app' %}"#,
+            expect![[r#"
+                ROOT@0..32
+                  TWIG_TRANS_DEFAULT_DOMAIN@0..32
+                    TK_CURLY_PERCENT@0..2 "{%"
+                    TK_WHITESPACE@2..3 " "
+                    TK_TRANS_DEFAULT_DOMAIN@3..23 "trans_default_domain"
+                    TWIG_EXPRESSION@23..29
+                      TWIG_LITERAL_STRING@23..29
+                        TK_WHITESPACE@23..24 " "
+                        TK_SINGLE_QUOTES@24..25 "'"
+                        TWIG_LITERAL_STRING_INNER@25..28
+                          TK_WORD@25..28 "app"
+                        TK_SINGLE_QUOTES@28..29 "'"
+                    TK_WHITESPACE@29..30 " "
+                    TK_PERCENT_CURLY@30..32 "%}""#]],
+        );
+    }
+
+    #[test]
+    fn parse_twig_guard() {
+        check_parse(
+            "{% guard function importmap %}a{% else %}b{% endguard %}",
+            expect![[r#"
+                ROOT@0..56
+                  TWIG_GUARD@0..56
+                    TWIG_GUARD_STARTING_BLOCK@0..30
+                      TK_CURLY_PERCENT@0..2 "{%"
+                      TK_WHITESPACE@2..3 " "
+                      TK_GUARD@3..8 "guard"
+                      TK_WHITESPACE@8..9 " "
+                      TK_WORD@9..17 "function"
+                      TWIG_LITERAL_NAME@17..27
+                        TK_WHITESPACE@17..18 " "
+                        TK_WORD@18..27 "importmap"
+                      TK_WHITESPACE@27..28 " "
+                      TK_PERCENT_CURLY@28..30 "%}"
+                    BODY@30..31
+                      HTML_TEXT@30..31
+                        TK_WORD@30..31 "a"
+                    TWIG_GUARD_ELSE_BLOCK@31..41
+                      TK_CURLY_PERCENT@31..33 "{%"
+                      TK_WHITESPACE@33..34 " "
+                      TK_ELSE@34..38 "else"
+                      TK_WHITESPACE@38..39 " "
+                      TK_PERCENT_CURLY@39..41 "%}"
+                    BODY@41..42
+                      HTML_TEXT@41..42
+                        TK_WORD@41..42 "b"
+                    TWIG_GUARD_ENDING_BLOCK@42..56
+                      TK_CURLY_PERCENT@42..44 "{%"
+                      TK_WHITESPACE@44..45 " "
+                      TK_ENDGUARD@45..53 "endguard"
+                      TK_WHITESPACE@53..54 " "
+                      TK_PERCENT_CURLY@54..56 "%}""#]],
         );
     }
 
+    #[test]
+    fn parse_twig_guard_filter() {
+        check_parse(
+            "{% guard filter upper %}{% endguard %}",
+            expect![[r#"
+                ROOT@0..38
+                  TWIG_GUARD@0..38
+                    TWIG_GUARD_STARTING_BLOCK@0..24
+                      TK_CURLY_PERCENT@0..2 "{%"
+                      TK_WHITESPACE@2..3 " "
+                      TK_GUARD@3..8 "guard"
+                      TK_WHITESPACE@8..9 " "
+                      TK_WORD@9..15 "filter"
+                      TWIG_LITERAL_NAME@15..21
+                        TK_WHITESPACE@15..16 " "
+                        TK_WORD@16..21 "upper"
+                      TK_WHITESPACE@21..22 " "
+                      TK_PERCENT_CURLY@22..24 "%}"
+                    BODY@24..24
+                    TWIG_GUARD_ENDING_BLOCK@24..38
+                      TK_CURLY_PERCENT@24..26 "{%"
+                      TK_WHITESPACE@26..27 " "
+                      TK_ENDGUARD@27..35 "endguard"
+                      TK_WHITESPACE@35..36 " "
+                      TK_PERCENT_CURLY@36..38 "%}""#]],
+        );
+
+        let parse = parse_for_twig_version(
+            "{% guard filter upper %}{% endguard %}",
+            TwigVersion::new(3, 14),
+        );
+        expect![[r#"error at 3..8: expected syntax of twig 3.14 (guard tags require twig 3.15 or newer) but found guard"#]]
+            .assert_eq(&parse.errors[0].to_string());
+    }
+
     #[test]
     fn parse_twig_sandbox() {
         check_parse(
@@ -4426,8 +4861,7 @@ mod tests {
                       HTML_TEXT@19..27
                         TK_LINE_BREAK@19..20 "\n"
                         TK_WHITESPACE@20..24 "    "
-                        TK_DOUBLE_DOT@24..26 ".."
-                        TK_DOT@26..27 "."
+                        TK_TRIPLE_DOT@24..27 "..."
                     TWIG_MACRO_ENDING_BLOCK@27..48
                       TK_LINE_BREAK@27..28 "\n"
                       TK_CURLY_PERCENT@28..30 "{%"
@@ -4464,8 +4898,7 @@ mod tests {
                       HTML_TEXT@19..27
                         TK_LINE_BREAK@19..20 "\n"
                         TK_WHITESPACE@20..24 "    "
-                        TK_DOUBLE_DOT@24..26 ".."
-                        TK_DOT@26..27 "."
+                        TK_TRIPLE_DOT@24..27 "..."
                     TWIG_MACRO_ENDING_BLOCK@27..46
                       TK_LINE_BREAK@27..28 "\n"
                       TK_CURLY_PERCENT@28..30 "{%"
@@ -4501,8 +4934,7 @@ mod tests {
                       HTML_TEXT@17..25
                         TK_LINE_BREAK@17..18 "\n"
                         TK_WHITESPACE@18..22 "    "
-                        TK_DOUBLE_DOT@22..24 ".."
-                        TK_DOT@24..25 "."
+                        TK_TRIPLE_DOT@22..25 "..."
                     TWIG_MACRO_ENDING_BLOCK@25..46
                       TK_LINE_BREAK@25..26 "\n"
                       TK_CURLY_PERCENT@26..28 "{%"
@@ -4512,8 +4944,7 @@ mod tests {
                       TK_WORD@38..43 "input"
                       TK_WHITESPACE@43..44 " "
                       TK_PERCENT_CURLY@44..46 "%}"
-                error at 15..17: expected ( but found %}
-                error at 15..17: expected ) but found %}"#]],
+                error at 15..17: expected ( or ) but found %}"#]],
         );
     }
 
@@ -4705,8 +5136,7 @@ mod tests {
                       HTML_TEXT@44..52
                         TK_LINE_BREAK@44..45 "\n"
                         TK_WHITESPACE@45..49 "    "
-                        TK_DOUBLE_DOT@49..51 ".."
-                        TK_DOT@51..52 "."
+                        TK_TRIPLE_DOT@49..52 "..."
                     TWIG_WITH_ENDING_BLOCK@52..66
                       TK_LINE_BREAK@52..53 "\n"
                       TK_CURLY_PERCENT@53..55 "{%"
@@ -5071,39 +5501,41 @@ mod tests {
                       TK_CACHE@3..8 "cache"
                       TWIG_EXPRESSION@8..58
                         TWIG_BINARY_EXPRESSION@8..58
-                          TWIG_BINARY_EXPRESSION@8..40
-                            TWIG_BINARY_EXPRESSION@8..34
-                              TWIG_EXPRESSION@8..24
-                                TWIG_LITERAL_STRING@8..24
-                                  TK_WHITESPACE@8..9 " "
-                                  TK_DOUBLE_QUOTES@9..10 "\""
-                                  TWIG_LITERAL_STRING_INNER@10..23
-                                    TK_WORD@10..19 "blog_post"
-                                    TK_SEMICOLON@19..20 ";"
-                                    TK_WORD@20..22 "v1"
-                                    TK_SEMICOLON@22..23 ";"
-                                  TK_DOUBLE_QUOTES@23..24 "\""
-                              TK_WHITESPACE@24..25 " "
-                              TK_TILDE@25..26 "~"
-                              TWIG_EXPRESSION@26..34
-                                TWIG_ACCESSOR@26..34
-                                  TWIG_OPERAND@26..31
-                                    TWIG_LITERAL_NAME@26..31
-                                      TK_WHITESPACE@26..27 " "
-                                      TK_WORD@27..31 "post"
-                                  TK_DOT@31..32 "."
-                                  TWIG_OPERAND@32..34
-                                    TWIG_LITERAL_NAME@32..34
-                                      TK_WORD@32..34 "id"
-                            TK_WHITESPACE@34..35 " "
-                            TK_TILDE@35..36 "~"
-                            TWIG_EXPRESSION@36..40
-                              TWIG_LITERAL_STRING@36..40
-                                TK_WHITESPACE@36..37 " "
-                                TK_DOUBLE_QUOTES@37..38 "\""
-                                TWIG_LITERAL_STRING_INNER@38..39
-                                  TK_SEMICOLON@38..39 ";"
-                                TK_DOUBLE_QUOTES@39..40 "\""
+                          TWIG_EXPRESSION@8..40
+                            TWIG_BINARY_EXPRESSION@8..40
+                              TWIG_EXPRESSION@8..34
+                                TWIG_BINARY_EXPRESSION@8..34
+                                  TWIG_EXPRESSION@8..24
+                                    TWIG_LITERAL_STRING@8..24
+                                      TK_WHITESPACE@8..9 " "
+                                      TK_DOUBLE_QUOTES@9..10 "\""
+                                      TWIG_LITERAL_STRING_INNER@10..23
+                                        TK_WORD@10..19 "blog_post"
+                                        TK_SEMICOLON@19..20 ";"
+                                        TK_WORD@20..22 "v1"
+                                        TK_SEMICOLON@22..23 ";"
+                                      TK_DOUBLE_QUOTES@23..24 "\""
+                                  TK_WHITESPACE@24..25 " "
+                                  TK_TILDE@25..26 "~"
+                                  TWIG_EXPRESSION@26..34
+                                    TWIG_ACCESSOR@26..34
+                                      TWIG_OPERAND@26..31
+                                        TWIG_LITERAL_NAME@26..31
+                                          TK_WHITESPACE@26..27 " "
+                                          TK_WORD@27..31 "post"
+                                      TK_DOT@31..32 "."
+                                      TWIG_OPERAND@32..34
+                                        TWIG_LITERAL_NAME@32..34
+                                          TK_WORD@32..34 "id"
+                              TK_WHITESPACE@34..35 " "
+                              TK_TILDE@35..36 "~"
+                              TWIG_EXPRESSION@36..40
+                                TWIG_LITERAL_STRING@36..40
+                                  TK_WHITESPACE@36..37 " "
+                                  TK_DOUBLE_QUOTES@37..38 "\""
+                                  TWIG_LITERAL_STRING_INNER@38..39
+                                    TK_SEMICOLON@38..39 ";"
+                                  TK_DOUBLE_QUOTES@39..40 "\""
                           TK_WHITESPACE@40..41 " "
                           TK_TILDE@41..42 "~"
                           TWIG_EXPRESSION@42..58
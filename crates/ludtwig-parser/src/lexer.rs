@@ -4,13 +4,52 @@ use crate::syntax::untyped::{SyntaxKind, TextRange, TextSize};
 
 /// Lex the source code into a Vec of tokens with their corresponding span (position in source code).
 /// These tokens are produced by a dumb lexer and don't have any meaning / semantic attached to them.
-pub(crate) fn lex(source: &str) -> Vec<Token> {
-    let mut lexer = SyntaxKind::lexer(source);
-    let mut result = vec![];
+///
+/// The parser may still change the kind of some tokens depending on their context
+/// (for example keywords in html text become words), so the kinds can differ from the final syntax tree.
+///
+/// ```
+/// use ludtwig_parser::lex;
+/// use ludtwig_parser::syntax::untyped::SyntaxKind;
+///
+/// let kinds: Vec<SyntaxKind> = lex("{{ a }}").into_iter().map(|t| t.kind).collect();
+/// assert_eq!(
+///     kinds,
+///     vec![
+///         SyntaxKind::TK_OPEN_CURLY_CURLY,
+///         SyntaxKind::TK_WHITESPACE,
+///         SyntaxKind::TK_WORD,
+///         SyntaxKind::TK_WHITESPACE,
+///         SyntaxKind::TK_CLOSE_CURLY_CURLY
+///     ]
+/// );
+/// ```
+#[must_use]
+pub fn lex(source: &str) -> Vec<Token<'_>> {
+    Lexer::new(source).collect()
+}
+
+/// Streaming version of [`lex`], which produces the tokens one by one.
+pub struct Lexer<'source> {
+    inner: logos::Lexer<'source, SyntaxKind>,
+}
+
+impl<'source> Lexer<'source> {
+    #[must_use]
+    pub fn new(source: &'source str) -> Self {
+        Self {
+            inner: SyntaxKind::lexer(source),
+        }
+    }
+}
+
+impl<'source> Iterator for Lexer<'source> {
+    type Item = Token<'source>;
 
-    while let Some(kind) = lexer.next() {
+    fn next(&mut self) -> Option<Self::Item> {
+        let kind = self.inner.next()?;
         let range = {
-            let span = lexer.span();
+            let span = self.inner.span();
             let start = TextSize::try_from(span.start)
                 .expect("lexer span range should fit into a u32 (file should be smaller than 4GB)");
             let end = TextSize::try_from(span.end)
@@ -18,21 +57,20 @@ pub(crate) fn lex(source: &str) -> Vec<Token> {
             TextRange::new(start, end)
         };
 
-        result.push(Token {
+        Some(Token {
             kind,
-            text: lexer.slice(),
+            text: self.inner.slice(),
             range,
-        });
+        })
     }
-
-    result
 }
 
+/// A single token of the source code
 #[derive(Debug, Clone, Eq, PartialEq)]
-pub(crate) struct Token<'source> {
-    pub(crate) kind: SyntaxKind,
-    pub(crate) text: &'source str,
-    pub(crate) range: TextRange,
+pub struct Token<'source> {
+    pub kind: SyntaxKind,
+    pub text: &'source str,
+    pub range: TextRange,
 }
 
 impl<'source> Token<'source> {
@@ -93,6 +131,25 @@ mod tests {
         assert_eq!(input, format!("{}", lexer_results[0].kind));
     }
 
+    #[test]
+    fn lexer_streams_same_tokens() {
+        let source = "<div class=\"{{ a }}\">{% if b %}c{% endif %}</div>";
+        let mut lexer = Lexer::new(source);
+
+        let first = lexer.next().unwrap();
+        assert_eq!(first.kind, T!["<"]);
+        assert_eq!(first.range, TextRange::new(0.into(), 1.into()));
+
+        let mut tokens = vec![first];
+        tokens.extend(lexer);
+        assert_eq!(tokens, lex(source));
+        assert_eq!(
+            tokens.iter().map(|t| t.text).collect::<String>(),
+            source,
+            "tokens should cover the whole source"
+        );
+    }
+
     #[test]
     fn lex_simple_output() {
         let results = lex("</div>");
@@ -162,6 +219,7 @@ mod tests {
         add("&#10;", T![html escape character]);
         add(".", T!["."]);
         add("..", T![".."]);
+        add("...", T!["..."]);
         add(",", T![","]);
         add(":", T![":"]);
         add(";", T![";"]);
@@ -193,12 +251,14 @@ mod tests {
         add("doctype", T!["DOCTYPE"]);
         add(">", T![">"]);
         add(">=", T![">="]);
-        add("/>", T!["/>"]);
         add("<!--", T!["<!--"]);
         add("-->", T!["-->"]);
+        add("<?", T!["<?"]);
+        add("?>", T!["?>"]);
         add("=", T!["="]);
         add("==", T!["=="]);
         add("===", T!["==="]);
+        add("=>", T!["=>"]);
         add("+", T!["+"]);
         add("-", T!["-"]);
         add("*", T!["*"]);
@@ -212,6 +272,7 @@ mod tests {
         add("}}", T!["}}"]);
         add("{#", T!["{#"]);
         add("#", T!["#"]);
+        add("@", T!["@"]);
         add("#}", T!["#}"]);
         add("true", T!["true"]);
         add("false", T!["false"]);
@@ -223,6 +284,8 @@ mod tests {
         add("endif", T!["endif"]);
         add("apply", T!["apply"]);
         add("endapply", T!["endapply"]);
+        add("filter", T!["filter"]);
+        add("endfilter", T!["endfilter"]);
         add("autoescape", T!["autoescape"]);
         add("endautoescape", T!["endautoescape"]);
         add("cache", T!["cache"]);
@@ -241,6 +304,10 @@ mod tests {
         add("endmacro", T!["endmacro"]);
         add("sandbox", T!["sandbox"]);
         add("endsandbox", T!["endsandbox"]);
+        add("guard", T!["guard"]);
+        add("endguard", T!["endguard"]);
+        add("spaceless", T!["spaceless"]);
+        add("endspaceless", T!["endspaceless"]);
         add("set", T!["set"]);
         add("endset", T!["endset"]);
         add("use", T!["use"]);
@@ -252,6 +319,10 @@ mod tests {
         add("endwith", T!["endwith"]);
         add("ttl", T!["ttl"]);
         add("tags", T!["tags"]);
+        add("trans", T!["trans"]);
+        add("endtrans", T!["endtrans"]);
+        add("trans_default_domain", T!["trans_default_domain"]);
+        add("into", T!["into"]);
         add("not", T!["not"]);
         add("or", T!["or"]);
         add("and", T!["and"]);
@@ -262,6 +333,8 @@ mod tests {
         add("matches", T!["matches"]);
         add("starts with", T!["starts with"]);
         add("ends with", T!["ends with"]);
+        add("has some", T!["has some"]);
+        add("has every", T!["has every"]);
         add("is", T!["is"]);
         add("even", T!["even"]);
         add("odd", T!["odd"]);
@@ -316,6 +389,30 @@ mod tests {
         check_regex("\r\n\n\r\n", T![lb], "line break");
     }
 
+    #[test]
+    fn lex_byte_order_mark() {
+        check_regex("\u{FEFF}", T![bom], "byte order mark");
+
+        let kinds: Vec<SyntaxKind> = lex("\u{FEFF}<div>\r\n</div>\r\n")
+            .into_iter()
+            .map(|t| t.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                T![bom],
+                T!["<"],
+                T![word],
+                T![">"],
+                T![lb],
+                T!["</"],
+                T![word],
+                T![">"],
+                T![lb]
+            ]
+        );
+    }
+
     #[test]
     fn lex_word() {
         check_regex("hello", T![word], "word");
@@ -323,8 +420,6 @@ mod tests {
         check_regex("camelCase", T![word], "word");
         check_regex("kebab-case", T![word], "word");
         check_regex("snake_case", T![word], "word");
-        check_regex("#hello123", T![word], "word");
-        check_regex("@hello123", T![word], "word");
         check_regex("block1", T![word], "word");
         check_regex("block_", T![word], "word");
         check_regex("blocks", T![word], "word");
@@ -376,6 +471,11 @@ mod tests {
         check_token("..", T![".."]);
     }
 
+    #[test]
+    fn lex_triple_dot() {
+        check_token("...", T!["..."]);
+    }
+
     #[test]
     fn lex_comma() {
         check_token(",", T![","]);
@@ -533,7 +633,9 @@ mod tests {
 
     #[test]
     fn lex_slash_greater_than() {
-        check_token("/>", T!["/>"]);
+        // combined by the parser at the end of html starting tags
+        let syntax_kinds: Vec<SyntaxKind> = lex("/>").into_iter().map(|t| t.kind).collect();
+        assert_eq!(syntax_kinds, vec![T!["/"], T![">"]]);
     }
 
     #[test]
@@ -546,6 +648,16 @@ mod tests {
         check_token("-->", T!["-->"]);
     }
 
+    #[test]
+    fn lex_less_than_question_mark() {
+        check_token("<?", T!["<?"]);
+    }
+
+    #[test]
+    fn lex_question_mark_greater_than() {
+        check_token("?>", T!["?>"]);
+    }
+
     #[test]
     fn lex_equal() {
         check_token("=", T!["="]);
@@ -561,6 +673,11 @@ mod tests {
         check_token("===", T!["==="]);
     }
 
+    #[test]
+    fn lex_equal_greater_than() {
+        check_token("=>", T!["=>"]);
+    }
+
     #[test]
     fn lex_plus() {
         check_token("+", T!["+"]);
@@ -631,6 +748,11 @@ mod tests {
         check_token("#", T!["#"]);
     }
 
+    #[test]
+    fn lex_at() {
+        check_token("@", T!["@"]);
+    }
+
     #[test]
     fn lex_true() {
         check_token("true", T!["true"]);
@@ -681,6 +803,16 @@ mod tests {
         check_token("endapply", T!["endapply"]);
     }
 
+    #[test]
+    fn lex_filter() {
+        check_token("filter", T!["filter"]);
+    }
+
+    #[test]
+    fn lex_endfilter() {
+        check_token("endfilter", T!["endfilter"]);
+    }
+
     #[test]
     fn lex_autoescape() {
         check_token("autoescape", T!["autoescape"]);
@@ -771,6 +903,26 @@ mod tests {
         check_token("endsandbox", T!["endsandbox"]);
     }
 
+    #[test]
+    fn lex_guard() {
+        check_token("guard", T!["guard"]);
+    }
+
+    #[test]
+    fn lex_endguard() {
+        check_token("endguard", T!["endguard"]);
+    }
+
+    #[test]
+    fn lex_spaceless() {
+        check_token("spaceless", T!["spaceless"]);
+    }
+
+    #[test]
+    fn lex_endspaceless() {
+        check_token("endspaceless", T!["endspaceless"]);
+    }
+
     #[test]
     fn lex_set() {
         check_token("set", T!["set"]);
@@ -826,6 +978,26 @@ mod tests {
         check_token("tags", T!["tags"]);
     }
 
+    #[test]
+    fn lex_trans() {
+        check_token("trans", T!["trans"]);
+    }
+
+    #[test]
+    fn lex_endtrans() {
+        check_token("endtrans", T!["endtrans"]);
+    }
+
+    #[test]
+    fn lex_trans_default_domain() {
+        check_token("trans_default_domain", T!["trans_default_domain"]);
+    }
+
+    #[test]
+    fn lex_into() {
+        check_token("into", T!["into"]);
+    }
+
     #[test]
     fn lex_not() {
         check_token("not", T!["not"]);
@@ -876,6 +1048,16 @@ mod tests {
         check_token("ends with", T!["ends with"]);
     }
 
+    #[test]
+    fn lex_has_some() {
+        check_token("has some", T!["has some"]);
+    }
+
+    #[test]
+    fn lex_has_every() {
+        check_token("has every", T!["has every"]);
+    }
+
     #[test]
     fn lex_is() {
         check_token("is", T!["is"]);
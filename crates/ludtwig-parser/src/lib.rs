@@ -1,16 +1,27 @@
 #![warn(clippy::pedantic)]
 #![allow(clippy::module_name_repetitions)]
 
+pub use lexer::lex;
+pub use lexer::Lexer;
+pub use lexer::Token;
+pub use line_index::LineCol;
+pub use line_index::LineIndex;
 pub use parser::parse;
+pub use parser::parse_expression;
+pub use parser::parse_for_twig_version;
+pub use parser::parse_twig_block_content;
 pub use parser::Parse;
 pub use parser::ParseError;
-
-use crate::lexer::lex;
+pub use twig_version::TwigVersion;
 
 mod grammar;
 mod lexer;
+mod line_index;
 mod parser;
 pub mod syntax;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+mod twig_version;
 
 pub use grammar::TWIG_NAME_REGEX;
 
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+
+use crate::syntax::untyped::{TextRange, TextSize};
+
+/// Zero-based line and column position in a source text.
+///
+/// Depending on the used conversion the column is counted in UTF-8 bytes
+/// or UTF-16 code units (like the language server protocol expects).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineCol {
+    pub line: u32,
+    pub col: u32,
+}
+
+/// Character inside a line, which has a different length in UTF-8 and UTF-16
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct WideChar {
+    /// UTF-8 offset relative to the line start
+    start: u32,
+    len_utf8: u32,
+    len_utf16: u32,
+}
+
+/// Maps offsets of a source text to lines and columns and back.
+///
+/// Lines are only separated by `\n`, so a `\r` of windows line endings is the last column of the line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    /// Offset of the first character of every line
+    line_starts: Vec<TextSize>,
+    /// Non ASCII characters by their line, lines without them are not stored
+    wide_chars: HashMap<u32, Vec<WideChar>>,
+    len: TextSize,
+}
+
+impl LineIndex {
+    #[must_use]
+    pub fn new(text: &str) -> Self {
+        let mut line_starts = vec![TextSize::from(0)];
+        let mut wide_chars: HashMap<u32, Vec<WideChar>> = HashMap::new();
+        let mut line_start = TextSize::from(0);
+        let mut offset = TextSize::from(0);
+
+        for c in text.chars() {
+            let len = TextSize::of(c);
+
+            if c == '\n' {
+                line_starts.push(offset + len);
+                line_start = offset + len;
+            } else if !c.is_ascii() {
+                #[allow(clippy::cast_possible_truncation)]
+                wide_chars
+                    .entry(line_starts.len() as u32 - 1)
+                    .or_default()
+                    .push(WideChar {
+                        start: (offset - line_start).into(),
+                        len_utf8: len.into(),
+                        len_utf16: c.len_utf16() as u32,
+                    });
+            }
+
+            offset += len;
+        }
+
+        Self {
+            line_starts,
+            wide_chars,
+            len: offset,
+        }
+    }
+
+    /// Amount of lines in the text (an empty text still has one line)
+    #[must_use]
+    pub fn line_count(&self) -> u32 {
+        #[allow(clippy::cast_possible_truncation)]
+        let count = self.line_starts.len() as u32;
+        count
+    }
+
+    /// Range of the line, which includes the trailing line break
+    #[must_use]
+    pub fn line_range(&self, line: u32) -> Option<TextRange> {
+        let start = *self.line_starts.get(line as usize)?;
+        let end = self
+            .line_starts
+            .get(line as usize + 1)
+            .copied()
+            .unwrap_or(self.len);
+
+        Some(TextRange::new(start, end))
+    }
+
+    /// Line and UTF-8 column of the offset (offsets after the end of the text are clamped)
+    #[must_use]
+    pub fn line_col(&self, offset: TextSize) -> LineCol {
+        let offset = offset.min(self.len);
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+
+        #[allow(clippy::cast_possible_truncation)]
+        LineCol {
+            line: line as u32,
+            col: (offset - self.line_starts[line]).into(),
+        }
+    }
+
+    /// Offset of the line and UTF-8 column, columns after the line break or the end of the text are invalid
+    #[must_use]
+    pub fn offset(&self, line_col: LineCol) -> Option<TextSize> {
+        let line_range = self.line_range(line_col.line)?;
+        let offset = line_range.start() + TextSize::from(line_col.col);
+
+        // allow the position of the line break, but not after it
+        let is_last_line = line_col.line + 1 == self.line_count();
+        if offset > line_range.end() || (!is_last_line && offset == line_range.end()) {
+            return None;
+        }
+
+        Some(offset)
+    }
+
+    /// Line and UTF-16 column of the offset (offsets after the end of the text are clamped)
+    #[must_use]
+    pub fn line_col_utf16(&self, offset: TextSize) -> LineCol {
+        let line_col = self.line_col(offset);
+        let mut col = line_col.col;
+
+        for c in self.wide_chars_of(line_col.line) {
+            if c.start >= line_col.col {
+                break;
+            }
+            col -= c.len_utf8 - c.len_utf16;
+        }
+
+        LineCol {
+            line: line_col.line,
+            col,
+        }
+    }
+
+    /// Offset of the line and UTF-16 column, columns after the line break or the end of the text are invalid
+    #[must_use]
+    pub fn offset_utf16(&self, line_col: LineCol) -> Option<TextSize> {
+        let mut col = line_col.col;
+        let mut difference = 0;
+
+        for c in self.wide_chars_of(line_col.line) {
+            if c.start - difference >= line_col.col {
+                break;
+            }
+            difference += c.len_utf8 - c.len_utf16;
+            col += c.len_utf8 - c.len_utf16;
+        }
+
+        self.offset(LineCol {
+            line: line_col.line,
+            col,
+        })
+    }
+
+    fn wide_chars_of(&self, line: u32) -> &[WideChar] {
+        self.wide_chars.get(&line).map_or(&[], Vec::as_slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_col(line: u32, col: u32) -> LineCol {
+        LineCol { line, col }
+    }
+
+    #[test]
+    fn line_index_ascii() {
+        let index = LineIndex::new("<div>\n  {{ a }}\r\n</div>");
+
+        assert_eq!(index.line_count(), 3);
+        assert_eq!(index.line_col(0.into()), line_col(0, 0));
+        assert_eq!(index.line_col(5.into()), line_col(0, 5));
+        assert_eq!(index.line_col(6.into()), line_col(1, 0));
+        assert_eq!(index.line_col(15.into()), line_col(1, 9));
+        assert_eq!(index.line_col(17.into()), line_col(2, 0));
+        assert_eq!(index.line_col(100.into()), line_col(2, 6));
+
+        assert_eq!(index.offset(line_col(1, 2)), Some(8.into()));
+        assert_eq!(index.offset(line_col(0, 5)), Some(5.into()));
+        assert_eq!(index.offset(line_col(0, 6)), None);
+        assert_eq!(index.offset(line_col(2, 6)), Some(23.into()));
+        assert_eq!(index.offset(line_col(2, 7)), None);
+        assert_eq!(index.offset(line_col(3, 0)), None);
+
+        assert_eq!(
+            index.line_range(1),
+            Some(TextRange::new(6.into(), 17.into()))
+        );
+        assert_eq!(index.line_range(3), None);
+    }
+
+    #[test]
+    fn line_index_empty_text() {
+        let index = LineIndex::new("");
+
+        assert_eq!(index.line_count(), 1);
+        assert_eq!(index.line_col(0.into()), line_col(0, 0));
+        assert_eq!(index.offset(line_col(0, 0)), Some(0.into()));
+        assert_eq!(index.offset_utf16(line_col(0, 0)), Some(0.into()));
+    }
+
+    #[test]
+    fn line_index_utf16() {
+        // 'ä' is 2 bytes in UTF-8 and one code unit in UTF-16, '😀' is 4 bytes and two code units
+        let text = "a\nä😀b\n😀";
+        let index = LineIndex::new(text);
+
+        let b_offset = TextSize::of(&text[..text.find('b').unwrap()]);
+        assert_eq!(index.line_col(b_offset), line_col(1, 6));
+        assert_eq!(index.line_col_utf16(b_offset), line_col(1, 3));
+        assert_eq!(index.offset_utf16(line_col(1, 3)), Some(b_offset));
+
+        assert_eq!(index.line_col_utf16(3.into()), line_col(1, 0));
+        assert_eq!(index.line_col_utf16(4.into()), line_col(1, 1));
+        assert_eq!(index.offset_utf16(line_col(1, 1)), Some(4.into()));
+        assert_eq!(
+            index.offset_utf16(line_col(1, 4)),
+            Some(b_offset + TextSize::from(1))
+        );
+        assert_eq!(index.offset_utf16(line_col(1, 5)), None);
+
+        let end = TextSize::of(text);
+        assert_eq!(index.line_col_utf16(end), line_col(2, 2));
+        assert_eq!(index.offset_utf16(line_col(2, 2)), Some(end));
+    }
+}
@@ -5,13 +5,13 @@ use rowan::GreenNode;
 pub use parse_error::ParseError;
 pub use parse_error::ParseErrorBuilder;
 
-use crate::grammar::root;
+use crate::grammar::{expression_root, root, twig_block_content_root};
 use crate::lexer::Token;
 use crate::parser::event::{CompletedMarker, EventCollection, Marker};
 use crate::parser::sink::Sink;
 use crate::parser::source::Source;
-use crate::syntax::untyped::{debug_tree, SyntaxKind, SyntaxNode};
-use crate::{lex, T};
+use crate::syntax::untyped::{debug_tree, SyntaxKind, SyntaxNode, TextRange, TextSize};
+use crate::{lex, TwigVersion, T};
 
 pub(crate) mod event;
 mod parse_error;
@@ -20,14 +20,69 @@ mod source;
 
 /// Tokens which can lead to parsing of another element
 /// (top level parsers under [`crate::grammar::parse_any_element`])
-pub(crate) static GENERAL_RECOVERY_SET: &[SyntaxKind] =
-    &[T!["{%"], T!["{{"], T!["{#"], T!["<"], T!["<!--"], T!["<!"]];
+pub(crate) static GENERAL_RECOVERY_SET: &[SyntaxKind] = &[
+    T!["{%"],
+    T!["{{"],
+    T!["{#"],
+    T!["<"],
+    T!["<!--"],
+    T!["<!"],
+    T!["<?"],
+];
 
 #[must_use]
 pub fn parse(input_text: &str) -> Parse {
+    parse_for_twig_version(input_text, TwigVersion::LATEST)
+}
+
+/// Parse the template like [`parse`], but report syntax which is not supported
+/// by the `twig_version` (like arrow functions before twig 2.10) as errors.
+#[must_use]
+pub fn parse_for_twig_version(input_text: &str, twig_version: TwigVersion) -> Parse {
+    let lex_result = lex(input_text);
+    let mut parser = Parser::new(&lex_result);
+    parser.twig_version = twig_version;
+    let (parse_events, parse_errors) = parser.parse(root);
+    let sink = Sink::new(&lex_result, parse_events, parse_errors);
+    sink.finish()
+}
+
+/// Parse a single twig expression like `product.name|upper`, without any surrounding `{{ }}`.
+/// This is useful for expressions found outside of twig syntax, like in a vue `:prop` binding.
+///
+/// The returned ROOT node contains the expression and all ranges are relative to the
+/// start of the input. Anything after the expression ends up in an error node.
+#[must_use]
+pub fn parse_expression(input_text: &str) -> Parse {
     let lex_result = lex(input_text);
     let parser = Parser::new(&lex_result);
-    let (parse_events, parse_errors) = parser.parse();
+    let (parse_events, parse_errors) = parser.parse(expression_root);
+    let sink = Sink::new(&lex_result, parse_events, parse_errors);
+    sink.finish()
+}
+
+/// Parse the content of a single twig tag like `set foo = 'bar'`, without the surrounding `{% %}`.
+///
+/// The delimiters are inserted as empty tokens (`TK_CURLY_PERCENT@0..0 ""`), which keeps
+/// the typed AST working and all ranges relative to the start of the input.
+/// Tags which have a body (like `if` or `block`) report their missing ending tag as an error.
+#[must_use]
+pub fn parse_twig_block_content(input_text: &str) -> Parse {
+    let end = TextSize::of(input_text);
+    let mut lex_result = vec![Token {
+        kind: T!["{%"],
+        text: "",
+        range: TextRange::empty(TextSize::from(0)),
+    }];
+    lex_result.extend(lex(input_text));
+    lex_result.push(Token {
+        kind: T!["%}"],
+        text: "",
+        range: TextRange::empty(end),
+    });
+
+    let parser = Parser::new(&lex_result);
+    let (parse_events, parse_errors) = parser.parse(twig_block_content_root);
     let sink = Sink::new(&lex_result, parse_events, parse_errors);
     sink.finish()
 }
@@ -57,19 +112,24 @@ pub(crate) struct Parser<'source> {
     source: Source<'source>,
     event_collection: EventCollection,
     parse_errors: Vec<ParseError>,
+    twig_version: TwigVersion,
 }
 
 impl<'source> Parser<'source> {
     pub(crate) fn new(tokens: &'source [Token<'source>]) -> Self {
         Self {
             source: Source::new(tokens),
-            event_collection: EventCollection::new(),
+            event_collection: EventCollection::with_capacity(tokens.len()),
             parse_errors: vec![],
+            twig_version: TwigVersion::LATEST,
         }
     }
 
-    fn parse(mut self) -> (EventCollection, Vec<ParseError>) {
-        root(&mut self);
+    fn parse(
+        mut self,
+        entry: fn(&mut Parser) -> CompletedMarker,
+    ) -> (EventCollection, Vec<ParseError>) {
+        entry(&mut self);
         (self.event_collection, self.parse_errors)
     }
 
@@ -106,8 +166,18 @@ impl<'source> Parser<'source> {
     }
 
     /// Only use this if absolutely necessary, because it is expensive to lookahead!
-    pub(crate) fn at_following_content(&mut self, set: &[(SyntaxKind, Option<&str>)]) -> bool {
-        self.source.at_following_content(set)
+    pub(crate) fn following_kinds(&mut self) -> impl Iterator<Item = SyntaxKind> + '_ {
+        self.source.following_kinds()
+    }
+
+    /// Only use this if absolutely necessary, because it is expensive to lookahead!
+    pub(crate) fn following_tokens(&mut self) -> impl Iterator<Item = &'source Token<'source>> {
+        self.source.following_tokens()
+    }
+
+    /// Is there any trivia (whitespace / line break) in front of the next token
+    pub(crate) fn at_preceding_trivia(&self) -> bool {
+        self.source.at_preceding_trivia()
     }
 
     pub(crate) fn at_end(&mut self) -> bool {
@@ -152,11 +222,11 @@ impl<'source> Parser<'source> {
     }
 
     #[track_caller]
-    pub(crate) fn bump_next_n_as(&mut self, n: usize, kind: SyntaxKind) -> Vec<&Token> {
-        let consumed = self.source.next_n_tokens(n);
+    pub(crate) fn bump_next_n_as(&mut self, n: u32, kind: SyntaxKind) -> &[Token] {
+        let consumed = self.source.next_n_tokens(n as usize);
         assert_eq!(
             consumed.len(),
-            n,
+            n as usize,
             "bump_next_n_as called, but there are not enough tokens!"
         );
 
@@ -215,7 +285,32 @@ impl<'source> Parser<'source> {
         }
     }
 
+    /// Panic-mode recovery for a broken twig tag like `{% unknown a < b %}`, which bumps the rest
+    /// of the tag without creating a node (the caller should wrap it in an error node).
+    /// Everything up to and including the closing `%}` belongs to the broken tag, so its content doesn't
+    /// end up as html with cascading errors. If the tag is never closed it stops in front of
+    /// the next element (like `{%`, `{{` or `<`) instead.
+    pub(crate) fn recover_twig_tag(&mut self) {
+        let closed = self
+            .source
+            .at_before(T!["%}"], &[T!["{%"], T!["{{"], T!["{#"]]);
+
+        while !self.at_end() {
+            if closed && self.at(T!["%}"]) {
+                self.bump();
+                return;
+            }
+            if !closed && self.at_set(GENERAL_RECOVERY_SET) {
+                return;
+            }
+
+            self.bump();
+        }
+    }
+
     /// Adds a parser error but does not bump any tokens into the tree.
+    /// Errors at the same location as the previous one are consolidated into it,
+    /// so a single problem doesn't produce a cascade of errors.
     pub(crate) fn add_error(&mut self, mut error_builder: ParseErrorBuilder) {
         // add missing information to builder
         if error_builder.range.is_none() || error_builder.found.is_none() {
@@ -224,13 +319,8 @@ impl<'source> Parser<'source> {
                 (Some(*kind), *range)
             } else {
                 // If we're at the end of the input we use the range of the very last token
-                // unwrap is fine, because error should not be called on empty file
-                (
-                    None,
-                    self.source
-                        .last_token_range()
-                        .expect("parser error called on empty file which has no last token"),
-                )
+                // (or an empty range for empty input, like an empty expression)
+                (None, self.source.last_token_range().unwrap_or_default())
             };
 
             if error_builder.range.is_none() {
@@ -242,7 +332,31 @@ impl<'source> Parser<'source> {
             }
         }
 
-        self.parse_errors.push(error_builder.build());
+        let error = error_builder.build();
+        if let Some(previous) = self
+            .parse_errors
+            .last_mut()
+            .filter(|e| e.range == error.range && e.found == error.found)
+        {
+            if !previous.expected.split(" or ").any(|e| e == error.expected) {
+                previous.expected = format!("{} or {}", previous.expected, error.expected);
+            }
+            return;
+        }
+
+        self.parse_errors.push(error);
+    }
+
+    /// Adds a parser error at the next token if the syntax (like `arrow functions`) is only supported
+    /// since a newer twig version than the one the template is parsed for.
+    /// This does not stop the parsing of the syntax.
+    pub(crate) fn require_twig_version(&mut self, since: TwigVersion, syntax: &str) {
+        if self.twig_version < since {
+            self.add_error(ParseErrorBuilder::new(format!(
+                "syntax of twig {} ({syntax} require twig {since} or newer)",
+                self.twig_version
+            )));
+        }
     }
 
     pub(crate) fn start(&mut self) -> Marker {
@@ -260,21 +374,168 @@ impl<'source> Parser<'source> {
     }
 }
 
-#[cfg(test)]
-#[allow(clippy::needless_pass_by_value)]
-pub(crate) fn check_parse(input: &str, expected_tree: expect_test::Expect) {
-    let parse = parse(input);
-    expected_tree.assert_eq(&parse.debug_parse());
-}
-
 #[cfg(test)]
 mod tests {
     use expect_test::expect;
 
     use super::*;
+    use crate::testing::check_parse;
 
     #[test]
     fn parse_nothing() {
         check_parse("", expect!["ROOT@0..0"]);
     }
+
+    #[test]
+    fn parse_standalone_expression() {
+        let parse = parse_expression("product.name|upper");
+        expect![[r#"
+            ROOT@0..18
+              TWIG_EXPRESSION@0..18
+                TWIG_FILTER@0..18
+                  TWIG_OPERAND@0..12
+                    TWIG_ACCESSOR@0..12
+                      TWIG_OPERAND@0..7
+                        TWIG_LITERAL_NAME@0..7
+                          TK_WORD@0..7 "product"
+                      TK_DOT@7..8 "."
+                      TWIG_OPERAND@8..12
+                        TWIG_LITERAL_NAME@8..12
+                          TK_WORD@8..12 "name"
+                  TK_SINGLE_PIPE@12..13 "|"
+                  TWIG_OPERAND@13..18
+                    TWIG_LITERAL_NAME@13..18
+                      TK_WORD@13..18 "upper""#]]
+        .assert_eq(&parse.debug_parse());
+    }
+
+    #[test]
+    fn parse_standalone_expression_with_trailing_input() {
+        let parse = parse_expression("a + b }} c");
+        expect![[r#"
+            ROOT@0..10
+              TWIG_EXPRESSION@0..5
+                TWIG_BINARY_EXPRESSION@0..5
+                  TWIG_EXPRESSION@0..1
+                    TWIG_LITERAL_NAME@0..1
+                      TK_WORD@0..1 "a"
+                  TK_WHITESPACE@1..2 " "
+                  TK_PLUS@2..3 "+"
+                  TWIG_EXPRESSION@3..5
+                    TWIG_LITERAL_NAME@3..5
+                      TK_WHITESPACE@3..4 " "
+                      TK_WORD@4..5 "b"
+              ERROR@5..10
+                TK_WHITESPACE@5..6 " "
+                TK_CLOSE_CURLY_CURLY@6..8 "}}"
+                TK_WHITESPACE@8..9 " "
+                TK_WORD@9..10 "c"
+            error at 6..8: expected end of twig expression but found }}"#]]
+        .assert_eq(&parse.debug_parse());
+    }
+
+    #[test]
+    fn parse_standalone_expression_empty() {
+        let parse = parse_expression("");
+        expect![[r#"
+            ROOT@0..0
+            error at 0..0: expected twig expression but reached end of file"#]]
+        .assert_eq(&parse.debug_parse());
+    }
+
+    #[test]
+    fn parse_standalone_twig_block_content() {
+        let parse = parse_twig_block_content("set foo = 'bar'");
+        expect![[r#"
+            ROOT@0..15
+              TWIG_SET@0..15
+                TWIG_SET_BLOCK@0..15
+                  TK_CURLY_PERCENT@0..0 ""
+                  TK_SET@0..3 "set"
+                  TWIG_ASSIGNMENT@3..15
+                    TWIG_LITERAL_NAME@3..7
+                      TK_WHITESPACE@3..4 " "
+                      TK_WORD@4..7 "foo"
+                    TK_WHITESPACE@7..8 " "
+                    TK_EQUAL@8..9 "="
+                    TWIG_EXPRESSION@9..15
+                      TWIG_LITERAL_STRING@9..15
+                        TK_WHITESPACE@9..10 " "
+                        TK_SINGLE_QUOTES@10..11 "'"
+                        TWIG_LITERAL_STRING_INNER@11..14
+                          TK_WORD@11..14 "bar"
+                        TK_SINGLE_QUOTES@14..15 "'"
+                  TK_PERCENT_CURLY@15..15 """#]]
+        .assert_eq(&parse.debug_parse());
+    }
+
+    #[test]
+    fn parse_standalone_twig_block_content_with_body() {
+        let parse = parse_twig_block_content("if a > b");
+        expect![[r#"
+            ROOT@0..8
+              TWIG_IF@0..8
+                TWIG_IF_BLOCK@0..8
+                  TK_CURLY_PERCENT@0..0 ""
+                  TK_IF@0..2 "if"
+                  TWIG_EXPRESSION@2..8
+                    TWIG_BINARY_EXPRESSION@2..8
+                      TWIG_EXPRESSION@2..4
+                        TWIG_LITERAL_NAME@2..4
+                          TK_WHITESPACE@2..3 " "
+                          TK_WORD@3..4 "a"
+                      TK_WHITESPACE@4..5 " "
+                      TK_GREATER_THAN@5..6 ">"
+                      TWIG_EXPRESSION@6..8
+                        TWIG_LITERAL_NAME@6..8
+                          TK_WHITESPACE@6..7 " "
+                          TK_WORD@7..8 "b"
+                  TK_PERCENT_CURLY@8..8 ""
+                BODY@8..8
+                TWIG_ENDIF_BLOCK@8..8
+            error at 8..8: expected {% or endif or %} but reached end of file"#]]
+        .assert_eq(&parse.debug_parse());
+    }
+
+    #[test]
+    fn parse_standalone_twig_block_content_unknown_tag() {
+        let parse = parse_twig_block_content("unknown tag");
+        expect![[r#"
+            ROOT@0..11
+              ERROR@0..11
+                TK_CURLY_PERCENT@0..0 ""
+                TK_WORD@0..7 "unknown"
+                TK_WHITESPACE@7..8 " "
+                TK_WORD@8..11 "tag"
+                TK_PERCENT_CURLY@11..11 ""
+            error at 0..7: expected twig tag but found word"#]]
+        .assert_eq(&parse.debug_parse());
+    }
+
+    #[test]
+    fn parse_byte_order_mark_and_windows_line_endings() {
+        check_parse(
+            "\u{FEFF}<div>\r\n    hello\r\n</div>\r\n",
+            expect![[r#"
+                ROOT@0..29
+                  HTML_TAG@0..27
+                    HTML_STARTING_TAG@0..8
+                      TK_BYTE_ORDER_MARK@0..3 "\u{feff}"
+                      TK_LESS_THAN@3..4 "<"
+                      TK_WORD@4..7 "div"
+                      HTML_ATTRIBUTE_LIST@7..7
+                      TK_GREATER_THAN@7..8 ">"
+                    BODY@8..19
+                      HTML_TEXT@8..19
+                        TK_LINE_BREAK@8..10 "\r\n"
+                        TK_WHITESPACE@10..14 "    "
+                        TK_WORD@14..19 "hello"
+                    HTML_ENDING_TAG@19..27
+                      TK_LINE_BREAK@19..21 "\r\n"
+                      TK_LESS_THAN_SLASH@21..23 "</"
+                      TK_WORD@23..26 "div"
+                      TK_GREATER_THAN@26..27 ">"
+                  TK_LINE_BREAK@27..29 "\r\n""#]],
+        );
+    }
 }
@@ -1,18 +1,24 @@
+use std::num::NonZeroU32;
+
 use crate::syntax::untyped::SyntaxKind;
 
 /// Parsing event which describes an action for creating the syntax tree.
+///
+/// There is at least one event per token, so the offsets are stored as `u32` to keep the event small
+/// (8 bytes instead of 24 with `usize`). The source text is limited to `u32` by `TextSize` anyway.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub(super) enum Event {
     StartNode {
         kind: SyntaxKind,
-        forward_parent: Option<usize>,
+        /// Offset to the event index of the parent node (which is started after this one)
+        forward_parent: Option<NonZeroU32>,
     },
     AddToken {
         kind: SyntaxKind,
     },
     /// Combines the next n lexer tokens into one (in the tree) with a specified SyntaxKind
     AddNextNTokensAs {
-        n: usize,
+        n: u32,
         kind: SyntaxKind,
     },
     FinishNode,
@@ -30,9 +36,16 @@ pub(crate) struct EventCollection {
 }
 
 impl EventCollection {
+    #[cfg(test)]
     pub(super) fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Preallocate the events for the expected amount of tokens, which avoids reallocations while parsing
+    pub(super) fn with_capacity(token_count: usize) -> Self {
         Self {
-            events: vec![],
+            // every token creates an event and most nodes contain only a few tokens
+            events: Vec::with_capacity(token_count * 2),
             #[cfg(debug_assertions)]
             open_markers: vec![],
         }
@@ -42,7 +55,7 @@ impl EventCollection {
         self.events.push(Event::AddToken { kind });
     }
 
-    pub(super) fn add_next_n_tokens_as(&mut self, n: usize, kind: SyntaxKind) {
+    pub(super) fn add_next_n_tokens_as(&mut self, n: u32, kind: SyntaxKind) {
         self.events.push(Event::AddNextNTokensAs { n, kind });
     }
 
@@ -101,7 +114,9 @@ impl EventCollection {
             ..
         } = self.events[completed_marker.pos]
         {
-            *forward_parent = Some(new_m.pos - completed_marker.pos);
+            let offset = u32::try_from(new_m.pos - completed_marker.pos)
+                .expect("event offsets should fit into u32 like the source text");
+            *forward_parent = NonZeroU32::new(offset);
         } else {
             unreachable!();
         }
@@ -147,17 +162,11 @@ mod tests {
     use super::*;
     use crate::T;
 
-    /*
     #[test]
     fn event_size() {
         // should be small for performance reasons
-        println!("event size {}", std::mem::size_of::<Event>());
-        println!(
-            "syntaxkind + usize size {}",
-            std::mem::size_of::<(SyntaxKind, Option<usize>)>()
-        );
+        assert_eq!(std::mem::size_of::<Event>(), 8);
     }
-    */
 
     #[test]
     fn event_collection_markers() {
@@ -216,7 +225,7 @@ mod tests {
             vec![
                 Event::StartNode {
                     kind: SyntaxKind::HTML_STARTING_TAG,
-                    forward_parent: Some(4)
+                    forward_parent: NonZeroU32::new(4)
                 },
                 Event::AddToken {
                     kind: SyntaxKind::TK_LESS_THAN
@@ -227,7 +236,7 @@ mod tests {
                 Event::FinishNode,
                 Event::StartNode {
                     kind: SyntaxKind::HTML_TAG,
-                    forward_parent: Some(2)
+                    forward_parent: NonZeroU32::new(2)
                 },
                 Event::FinishNode,
                 Event::StartNode {
@@ -267,7 +276,7 @@ mod tests {
                 },
                 Event::StartNode {
                     kind: SyntaxKind::HTML_STRING,
-                    forward_parent: Some(3),
+                    forward_parent: NonZeroU32::new(3),
                 },
                 Event::AddToken {
                     kind: SyntaxKind::TK_AND
@@ -275,7 +284,7 @@ mod tests {
                 Event::FinishNode,
                 Event::StartNode {
                     kind: SyntaxKind::BODY,
-                    forward_parent: Some(2),
+                    forward_parent: NonZeroU32::new(2),
                 },
                 Event::FinishNode,
                 Event::StartNode {
@@ -14,6 +14,8 @@ pub(super) struct Sink<'source> {
     events: Vec<Event>,
     parser_errors: Vec<ParseError>,
     builder: GreenNodeBuilder<'source>,
+    /// reused for combining multiple tokens into one
+    text_buffer: String,
 }
 
 impl<'source> Sink<'source> {
@@ -28,11 +30,13 @@ impl<'source> Sink<'source> {
             events: event_collection.into_event_list(),
             parser_errors,
             builder: GreenNodeBuilder::new(),
+            text_buffer: String::new(),
         }
     }
 
     pub(super) fn finish(mut self) -> Parse {
-        let mut forward_kinds = Vec::new();
+        // reused for every node, forward parents are rarely nested deeply
+        let mut forward_kinds = Vec::with_capacity(8);
 
         for idx in 0..self.events.len() {
             if matches!(
@@ -59,7 +63,7 @@ impl<'source> Sink<'source> {
                     let mut idx = idx;
                     let mut forward_parent = forward_parent;
                     while let Some(fp) = forward_parent {
-                        idx += fp;
+                        idx += fp.get() as usize;
 
                         forward_parent = if let Event::StartNode {
                             kind,
@@ -129,14 +133,15 @@ impl<'source> Sink<'source> {
         self.cursor += 1;
     }
 
-    fn next_n_tokens_as(&mut self, n: usize, kind: SyntaxKind) {
-        let combined_string: String = self.tokens[self.cursor..(self.cursor + n)]
-            .iter()
-            .map(|t| t.text)
-            .collect();
+    fn next_n_tokens_as(&mut self, n: u32, kind: SyntaxKind) {
+        let n = n as usize;
+        self.text_buffer.clear();
+        for token in &self.tokens[self.cursor..(self.cursor + n)] {
+            self.text_buffer.push_str(token.text);
+        }
 
         self.builder
-            .token(TemplateLanguage::kind_to_raw(kind), &combined_string);
+            .token(TemplateLanguage::kind_to_raw(kind), &self.text_buffer);
         self.cursor += n;
     }
 }
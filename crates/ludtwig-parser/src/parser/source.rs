@@ -3,6 +3,10 @@ use rowan::TextRange;
 use crate::lexer::Token;
 use crate::syntax::untyped::SyntaxKind;
 
+/// Maximum amount of tokens which the unbounded lookahead methods look at, so broken tags in
+/// large (generated) templates don't make the parser quadratic by scanning to the end every time
+const MAX_LOOKAHEAD: usize = 1024;
+
 /// Wrapper around lexing tokens to only get the non-whitespace tokens back
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub(super) struct Source<'source> {
@@ -24,13 +28,14 @@ impl<'source> Source<'source> {
         Some(token)
     }
 
-    pub(super) fn next_n_tokens(&mut self, n: usize) -> Vec<&'source Token<'source>> {
+    pub(super) fn next_n_tokens(&mut self, n: usize) -> &'source [Token<'source>] {
         self.eat_trivia();
 
-        let token = self.tokens[self.cursor..].iter().take(n).collect();
+        let end = (self.cursor + n).min(self.tokens.len());
+        let tokens = &self.tokens[self.cursor..end];
         self.cursor += n;
 
-        token
+        tokens
     }
 
     pub(super) fn peek_kind(&mut self) -> Option<SyntaxKind> {
@@ -72,39 +77,58 @@ impl<'source> Source<'source> {
         }
     }
 
-    pub(super) fn at_following_content(&mut self, set: &[(SyntaxKind, Option<&str>)]) -> bool {
+    /// The kinds of the following non trivia tokens, up to `MAX_LOOKAHEAD` tokens (lookahead is expensive!)
+    pub(super) fn following_kinds(&mut self) -> impl Iterator<Item = SyntaxKind> + '_ {
         self.eat_trivia();
-        if self.cursor == self.tokens.len() {
-            return false; // end already reached
-        }
+        self.lookahead_tokens()
+            .iter()
+            .map(|t| t.kind)
+            .filter(|k| !k.is_trivia())
+    }
 
-        let mut tokens_iter = self.tokens[self.cursor..]
+    /// The following non trivia tokens, up to `MAX_LOOKAHEAD` tokens (lookahead is expensive!)
+    pub(super) fn following_tokens(&mut self) -> impl Iterator<Item = &'source Token<'source>> {
+        self.eat_trivia();
+        self.lookahead_tokens()
             .iter()
-            .filter(|t| !t.kind.is_trivia());
-        let mut set_iter = set.iter();
+            .filter(|t| !t.kind.is_trivia())
+    }
 
-        loop {
-            match (tokens_iter.next(), set_iter.next()) {
-                (Some(token), Some((set_kind, set_content)))
-                    if token.kind == *set_kind
-                        && set_content.map_or(true, |content| content == token.text) =>
-                {
-                    continue
-                }
-                (None | Some(_), None) => return true,
-                _ => return false,
-            }
-        }
+    /// Does the `target` token come before any token of the `stop_set` (or the end)?
+    /// Only the next `MAX_LOOKAHEAD` tokens are searched.
+    pub(super) fn at_before(&mut self, target: SyntaxKind, stop_set: &[SyntaxKind]) -> bool {
+        self.eat_trivia();
+        self.lookahead_tokens()
+            .iter()
+            .map(|t| t.kind)
+            .find(|k| *k == target || stop_set.contains(k))
+            == Some(target)
     }
 
     pub(super) fn last_token_range(&self) -> Option<TextRange> {
         self.tokens.last().map(|Token { range, .. }| *range)
     }
 
+    /// Is there any trivia between the last consumed token and the next one
+    pub(super) fn at_preceding_trivia(&self) -> bool {
+        self.at_trivia()
+            || self
+                .cursor
+                .checked_sub(1)
+                .and_then(|i| self.tokens.get(i))
+                .is_some_and(|t| t.kind.is_trivia())
+    }
+
     pub(super) fn get_pos(&self) -> usize {
         self.cursor
     }
 
+    /// The following tokens which may be looked at (including trivia)
+    fn lookahead_tokens(&self) -> &'source [Token<'source>] {
+        let end = (self.cursor + MAX_LOOKAHEAD).min(self.tokens.len());
+        &self.tokens[self.cursor..end]
+    }
+
     fn eat_trivia(&mut self) {
         while self.at_trivia() {
             self.cursor += 1;
@@ -182,46 +206,17 @@ mod tests {
     }
 
     #[test]
-    fn source_at_following_content() {
-        let tokens = vec![
-            Token::new_wrong_range(T![ws], "  "),
-            Token::new_wrong_range(T![lb], "\n"),
-            Token::new_wrong_range(T![word], "hello"),
-            Token::new_wrong_range(T![lb], "\n"),
-            Token::new_wrong_range(T![ws], "  "),
-            Token::new_wrong_range(T!["<"], "<"),
-            Token::new_wrong_range(T![lb], "\n"),
-            Token::new_wrong_range(T![">"], ">"),
-            Token::new_wrong_range(T![ws], "  "),
-        ];
+    fn source_lookahead_is_capped() {
+        let mut tokens = vec![Token::new_wrong_range(T!["{%"], "{%")];
+        tokens.extend((1..MAX_LOOKAHEAD).map(|_| Token::new_wrong_range(T![word], "a")));
+        tokens.push(Token::new_wrong_range(T!["%}"], "%}"));
 
         let mut source = Source::new(&tokens);
-        assert!(source.at_following_content(&[
-            (T![word], Some("hello")),
-            (T!["<"], None),
-            (T![">"], None)
-        ]));
-        assert!(source.at_following_content(&[(T![word], None), (T!["<"], None), (T![">"], None)]));
-        assert!(source.at_following_content(&[(T![word], Some("hello")), (T!["<"], None)]));
-        assert!(source.at_following_content(&[(T![word], Some("hello"))]));
-        assert!(source.at_following_content(&[
-            (T![word], None),
-            (T!["<"], None),
-            (T![">"], Some(">"))
-        ]));
-
-        assert!(!source.at_following_content(&[(T![word], Some("nonExistent"))]));
-        assert!(!source.at_following_content(&[
-            (T![word], Some("nonExistent")),
-            (T!["<"], None),
-            (T![">"], None)
-        ]));
+        assert!(!source.at_before(T!["%}"], &[T!["{{"]]));
+        assert_eq!(source.following_kinds().count(), MAX_LOOKAHEAD);
 
         source.next_token();
-        source.next_token();
-        source.next_token();
-
-        // nothing more to compare
-        assert!(!source.at_following_content(&[(T![word], None)]));
+        assert!(source.at_before(T!["%}"], &[T!["{{"]]));
+        assert_eq!(source.following_kinds().count(), MAX_LOOKAHEAD);
     }
 }
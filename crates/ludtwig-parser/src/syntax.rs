@@ -1,2 +1,6 @@
+pub mod editor;
+pub mod scope;
+pub mod semantic;
+pub mod trivia;
 pub mod typed;
 pub mod untyped;
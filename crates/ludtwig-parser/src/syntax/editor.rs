@@ -0,0 +1,408 @@
+//! Structural editing of syntax trees, which produces a new tree instead of raw string replacements.
+//!
+//! The [`SyntaxEditor`] collects changes to nodes and tokens of an existing tree and
+//! rebuilds a new green tree out of them. The same changes are also described as a list
+//! of non overlapping [`TextEdit`]s on the original source code.
+//!
+//! ```
+//! use ludtwig_parser::syntax::editor::{make_token, SyntaxEditor};
+//! use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode};
+//!
+//! let parse = ludtwig_parser::parse("{{ name|upper }}");
+//! let root = SyntaxNode::new_root(parse.green_node);
+//! let filter_name = root
+//!     .descendants_with_tokens()
+//!     .filter_map(|e| e.into_token())
+//!     .find(|t| t.text() == "upper")
+//!     .unwrap();
+//!
+//! let mut editor = SyntaxEditor::new(root);
+//! editor.replace(filter_name, make_token(SyntaxKind::TK_WORD, "lower"));
+//! let edit = editor.finish();
+//!
+//! assert_eq!(SyntaxNode::new_root(edit.green_node).to_string(), "{{ name|lower }}");
+//! assert_eq!(edit.text_edits[0].replace_with, "lower");
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use rowan::{GreenNode, GreenToken, Language, NodeOrToken};
+
+use crate::syntax::untyped::{
+    SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, TemplateLanguage, TextRange, TextSize,
+};
+
+type GreenElement = NodeOrToken<GreenNode, GreenToken>;
+
+/// Replacement of a range in the original source code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub range: TextRange,
+    pub replace_with: String,
+}
+
+/// Result of [`SyntaxEditor::finish`]
+#[derive(Debug, Clone)]
+pub struct SyntaxEdit {
+    /// The new tree with all changes applied
+    pub green_node: GreenNode,
+    /// Sorted and non overlapping edits, which turn the original source code
+    /// into the text of the new tree
+    pub text_edits: Vec<TextEdit>,
+}
+
+#[derive(Debug, Default)]
+struct ElementChanges {
+    insert_before: Vec<SyntaxElement>,
+    insert_after: Vec<SyntaxElement>,
+    /// Replacement of the element itself, an empty list deletes it
+    replace_with: Option<Vec<SyntaxElement>>,
+}
+
+/// Collects replacements, insertions and deletions of elements in a tree.
+///
+/// Changes always refer to elements of the original tree, which was passed to [`SyntaxEditor::new`].
+/// Changes to the root itself, to elements of other trees or to elements inside of
+/// a replaced / deleted element are ignored.
+#[derive(Debug)]
+pub struct SyntaxEditor {
+    root: SyntaxNode,
+    changes: HashMap<SyntaxElement, ElementChanges>,
+}
+
+impl SyntaxEditor {
+    #[must_use]
+    pub fn new(root: SyntaxNode) -> Self {
+        Self {
+            root,
+            changes: HashMap::new(),
+        }
+    }
+
+    /// Replace the element with a new one (the last replacement of an element wins)
+    pub fn replace(&mut self, old: impl Into<SyntaxElement>, new: impl Into<SyntaxElement>) {
+        self.replace_with_many(old, vec![new.into()]);
+    }
+
+    /// Replace the element with any amount of new elements (the last replacement of an element wins)
+    pub fn replace_with_many(&mut self, old: impl Into<SyntaxElement>, new: Vec<SyntaxElement>) {
+        self.changes_of(old.into()).replace_with = Some(new);
+    }
+
+    pub fn delete(&mut self, element: impl Into<SyntaxElement>) {
+        self.replace_with_many(element, vec![]);
+    }
+
+    pub fn insert_before(
+        &mut self,
+        anchor: impl Into<SyntaxElement>,
+        new: impl Into<SyntaxElement>,
+    ) {
+        self.changes_of(anchor.into())
+            .insert_before
+            .push(new.into());
+    }
+
+    pub fn insert_after(
+        &mut self,
+        anchor: impl Into<SyntaxElement>,
+        new: impl Into<SyntaxElement>,
+    ) {
+        self.changes_of(anchor.into()).insert_after.push(new.into());
+    }
+
+    /// Build the new tree and the text edits out of all collected changes
+    #[must_use]
+    pub fn finish(self) -> SyntaxEdit {
+        // only nodes which contain changes need to be rebuilt
+        let changed_nodes: HashSet<SyntaxNode> = self
+            .changes
+            .keys()
+            .filter_map(SyntaxElement::parent)
+            .flat_map(|parent| parent.ancestors())
+            .collect();
+
+        let mut text_edits = vec![];
+        let green_node = self.rebuild_node(&self.root, &changed_nodes, &mut text_edits);
+
+        SyntaxEdit {
+            green_node,
+            text_edits: merge_touching(text_edits),
+        }
+    }
+
+    fn changes_of(&mut self, element: SyntaxElement) -> &mut ElementChanges {
+        self.changes.entry(element).or_default()
+    }
+
+    fn rebuild_node(
+        &self,
+        node: &SyntaxNode,
+        changed_nodes: &HashSet<SyntaxNode>,
+        text_edits: &mut Vec<TextEdit>,
+    ) -> GreenNode {
+        let mut children: Vec<GreenElement> = vec![];
+
+        for child in node.children_with_tokens() {
+            let changes = self.changes.get(&child);
+            let range = child.text_range();
+
+            if let Some(changes) = changes {
+                insert_elements(
+                    &changes.insert_before,
+                    range.start(),
+                    &mut children,
+                    text_edits,
+                );
+            }
+
+            match changes.and_then(|c| c.replace_with.as_ref()) {
+                Some(replacement) => {
+                    children.extend(replacement.iter().map(green_element));
+                    text_edits.push(TextEdit {
+                        range,
+                        replace_with: replacement.iter().map(ToString::to_string).collect(),
+                    });
+                }
+                None => match &child {
+                    NodeOrToken::Node(n) if changed_nodes.contains(n) => {
+                        children.push(NodeOrToken::Node(self.rebuild_node(
+                            n,
+                            changed_nodes,
+                            text_edits,
+                        )));
+                    }
+                    _ => children.push(green_element(&child)),
+                },
+            }
+
+            if let Some(changes) = changes {
+                insert_elements(
+                    &changes.insert_after,
+                    range.end(),
+                    &mut children,
+                    text_edits,
+                );
+            }
+        }
+
+        GreenNode::new(TemplateLanguage::kind_to_raw(node.kind()), children)
+    }
+}
+
+/// Create a detached token, which can be inserted with a [`SyntaxEditor`]
+///
+/// # Panics
+/// never, the token is always the first one of its (temporary) parent node.
+#[must_use]
+pub fn make_token(kind: SyntaxKind, text: &str) -> SyntaxToken {
+    let green = GreenNode::new(
+        TemplateLanguage::kind_to_raw(SyntaxKind::ERROR),
+        [NodeOrToken::Token(GreenToken::new(
+            TemplateLanguage::kind_to_raw(kind),
+            text,
+        ))],
+    );
+
+    SyntaxNode::new_root(green)
+        .first_token()
+        .expect("node should contain the created token")
+}
+
+fn insert_elements(
+    elements: &[SyntaxElement],
+    offset: TextSize,
+    children: &mut Vec<GreenElement>,
+    text_edits: &mut Vec<TextEdit>,
+) {
+    if elements.is_empty() {
+        return;
+    }
+
+    children.extend(elements.iter().map(green_element));
+    text_edits.push(TextEdit {
+        range: TextRange::empty(offset),
+        replace_with: elements.iter().map(ToString::to_string).collect(),
+    });
+}
+
+fn green_element(element: &SyntaxElement) -> GreenElement {
+    match element {
+        NodeOrToken::Node(n) => NodeOrToken::Node(n.green().into_owned()),
+        NodeOrToken::Token(t) => NodeOrToken::Token(t.green().to_owned()),
+    }
+}
+
+/// Combine edits which directly follow each other (for example an insertion after an element
+/// and a replacement of the next element), so no edit touches another one
+fn merge_touching(text_edits: Vec<TextEdit>) -> Vec<TextEdit> {
+    let mut merged: Vec<TextEdit> = Vec::with_capacity(text_edits.len());
+
+    for edit in text_edits {
+        match merged.last_mut() {
+            Some(previous) if previous.range.end() == edit.range.start() => {
+                previous.range = previous.range.cover(edit.range);
+                previous.replace_with.push_str(&edit.replace_with);
+            }
+            _ => merged.push(edit),
+        }
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::syntax::typed::{AstNode, HtmlAttribute, TwigFilter};
+    use crate::syntax::untyped::{debug_tree, SyntaxNode};
+    use crate::T;
+
+    use super::*;
+
+    fn parse_root(source: &str) -> SyntaxNode {
+        SyntaxNode::new_root(crate::parse(source).green_node)
+    }
+
+    /// Checks that the text edits result in the same source code as the new tree
+    fn apply_edit(source: &str, edit: &SyntaxEdit) -> String {
+        let new_root = SyntaxNode::new_root(edit.green_node.clone());
+
+        let mut edited_source = source.to_string();
+        for text_edit in edit.text_edits.iter().rev() {
+            edited_source.replace_range(
+                std::ops::Range::<usize>::from(text_edit.range),
+                &text_edit.replace_with,
+            );
+        }
+        assert_eq!(edited_source, new_root.to_string());
+
+        edited_source
+    }
+
+    #[test]
+    fn editor_replaces_nodes_and_tokens() {
+        let source = r#"<div class="a" id="b">{{ name|upper }}</div>"#;
+        let root = parse_root(source);
+        let mut editor = SyntaxEditor::new(root.clone());
+
+        let class_attribute = root.descendants().find_map(HtmlAttribute::cast).unwrap();
+        let new_attribute = parse_root(r#"<p title="c">"#)
+            .descendants()
+            .find_map(HtmlAttribute::cast)
+            .unwrap();
+        editor.replace(
+            class_attribute.syntax().clone(),
+            new_attribute.syntax().clone(),
+        );
+
+        let filter_name = root
+            .descendants()
+            .find_map(TwigFilter::cast)
+            .and_then(|f| f.name())
+            .unwrap();
+        editor.replace(filter_name, make_token(T![word], "lower"));
+
+        let edit = editor.finish();
+        expect![[r#"<div title="c" id="b">{{ name|lower }}</div>"#]]
+            .assert_eq(&apply_edit(source, &edit));
+        assert_eq!(
+            edit.text_edits,
+            vec![
+                TextEdit {
+                    range: TextRange::new(4.into(), 14.into()),
+                    replace_with: r#" title="c""#.to_string(),
+                },
+                TextEdit {
+                    range: TextRange::new(30.into(), 35.into()),
+                    replace_with: "lower".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn editor_inserts_and_deletes() {
+        let source = r#"<div class="a" id="b"></div>"#;
+        let root = parse_root(source);
+        let mut editor = SyntaxEditor::new(root.clone());
+
+        let mut attributes = root.descendants().filter_map(HtmlAttribute::cast);
+        let class_attribute = attributes.next().unwrap();
+        let id_attribute = attributes.next().unwrap();
+        editor.delete(class_attribute.syntax().clone());
+        editor.insert_after(id_attribute.syntax().clone(), make_token(T![ws], " "));
+        editor.insert_after(
+            id_attribute.syntax().clone(),
+            make_token(T![word], "hidden"),
+        );
+        editor.insert_before(id_attribute.syntax().clone(), make_token(T![ws], " "));
+        editor.insert_before(id_attribute.syntax().clone(), make_token(T![word], "open"));
+
+        let edit = editor.finish();
+        expect![[r#"<div open id="b" hidden></div>"#]].assert_eq(&apply_edit(source, &edit));
+        // deletion and insertion before the id attribute are merged
+        assert_eq!(edit.text_edits.len(), 2);
+    }
+
+    #[test]
+    fn editor_ignores_changes_inside_of_replaced_elements() {
+        let source = "{{ a|upper }}";
+        let root = parse_root(source);
+        let mut editor = SyntaxEditor::new(root.clone());
+
+        let filter = root.descendants().find_map(TwigFilter::cast).unwrap();
+        editor.replace(filter.name().unwrap(), make_token(T![word], "lower"));
+        editor.replace(
+            filter.syntax().clone(),
+            parse_root("{{ b }}")
+                .descendants()
+                .find(|n| n.text() == " b")
+                .unwrap(),
+        );
+
+        let edit = editor.finish();
+        expect!["{{ b }}"].assert_eq(&apply_edit(source, &edit));
+        assert_eq!(edit.text_edits.len(), 1);
+    }
+
+    #[test]
+    fn editor_keeps_the_tree_structure() {
+        let root = parse_root("{% block a %}<hr>{% endblock %}");
+        let mut editor = SyntaxEditor::new(root.clone());
+        let name = root
+            .descendants_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .find(|t| t.text() == "a")
+            .unwrap();
+        editor.replace(name, make_token(T![word], "content"));
+
+        let edit = editor.finish();
+        expect![[r#"
+            ROOT@0..37
+              TWIG_BLOCK@0..37
+                TWIG_STARTING_BLOCK@0..19
+                  TK_CURLY_PERCENT@0..2 "{%"
+                  TK_WHITESPACE@2..3 " "
+                  TK_BLOCK@3..8 "block"
+                  TK_WHITESPACE@8..9 " "
+                  TK_WORD@9..16 "content"
+                  TK_WHITESPACE@16..17 " "
+                  TK_PERCENT_CURLY@17..19 "%}"
+                BODY@19..23
+                  HTML_TAG@19..23
+                    HTML_STARTING_TAG@19..23
+                      TK_LESS_THAN@19..20 "<"
+                      TK_WORD@20..22 "hr"
+                      HTML_ATTRIBUTE_LIST@22..22
+                      TK_GREATER_THAN@22..23 ">"
+                TWIG_ENDING_BLOCK@23..37
+                  TK_CURLY_PERCENT@23..25 "{%"
+                  TK_WHITESPACE@25..26 " "
+                  TK_ENDBLOCK@26..34 "endblock"
+                  TK_WHITESPACE@34..35 " "
+                  TK_PERCENT_CURLY@35..37 "%}""#]]
+        .assert_eq(&debug_tree(&SyntaxNode::new_root(edit.green_node)));
+    }
+}
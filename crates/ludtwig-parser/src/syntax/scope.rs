@@ -0,0 +1,788 @@
+//! Static scope analysis of twig variables.
+//!
+//! The [`ScopeAnalyzer`] walks a syntax tree once and collects the scopes of the template
+//! (`block`, `for`, `macro`, `with` and arrow functions), the variables defined in them (`set`,
+//! loop variables, macro parameters, `with` keys, imports and arrow function parameters)
+//! and resolves every variable usage to its definition.
+//!
+//! Twig templates are rendered with a context, which is unknown to this analysis. So references
+//! which can't be resolved are not necessarily undefined, they can still come from the context or globals.
+//!
+//! ```
+//! use ludtwig_parser::syntax::scope::{DefinitionKind, ScopeAnalyzer};
+//! use ludtwig_parser::syntax::untyped::SyntaxNode;
+//!
+//! let parse = ludtwig_parser::parse("{% set title = 'a' %}{% for item in items %}{{ title }}{% endfor %}");
+//! let analyzer = ScopeAnalyzer::new(&SyntaxNode::new_root(parse.green_node));
+//!
+//! let title = analyzer.definitions().iter().position(|d| d.name == "title").unwrap();
+//! assert_eq!(analyzer.definitions()[title].kind, DefinitionKind::Set);
+//! assert_eq!(analyzer.references_to(title.into()).count(), 1);
+//!
+//! let unresolved: Vec<_> = analyzer.unresolved_references().map(|r| r.name.as_str()).collect();
+//! assert_eq!(unresolved, vec!["items"]);
+//! ```
+
+use rowan::ast::AstNode;
+
+use crate::syntax::typed::{TwigBinaryExpression, TwigLiteralName};
+use crate::syntax::untyped::{SyntaxKind, SyntaxNode, SyntaxToken, TextRange, TextSize};
+use crate::T;
+
+/// Index of a [`Scope`] in [`ScopeAnalyzer::scopes`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ScopeId(pub usize);
+
+/// Index of a [`Definition`] in [`ScopeAnalyzer::definitions`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DefinitionId(pub usize);
+
+impl From<usize> for ScopeId {
+    fn from(index: usize) -> Self {
+        Self(index)
+    }
+}
+
+impl From<usize> for DefinitionId {
+    fn from(index: usize) -> Self {
+        Self(index)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScopeKind {
+    /// The whole template, which is always the first scope
+    Template,
+    Block,
+    /// Only the loop body, the `else` branch belongs to the outer scope
+    For,
+    Macro,
+    With,
+    ArrowFunction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scope {
+    pub kind: ScopeKind,
+    pub range: TextRange,
+    pub parent: Option<ScopeId>,
+    /// Variables of outer scopes are not visible (except for imports), like in macros or `{% with ... only %}`
+    pub isolated: bool,
+    /// Variables of the scope are not statically known, like in `{% with variables %}`
+    pub dynamic: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DefinitionKind {
+    Set,
+    ForVariable,
+    /// The implicit `loop` variable of a for loop
+    Loop,
+    MacroParameter,
+    /// Key of the hash in `{% with { key: value } %}`
+    With,
+    /// `{% import ... as name %}` or `{% from ... import name %}`
+    Import,
+    ArrowFunctionParameter,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Definition {
+    pub name: String,
+    pub kind: DefinitionKind,
+    /// Range of the name (or the `for` keyword for the implicit `loop` variable)
+    pub range: TextRange,
+    pub scope: ScopeId,
+    /// Offset from which the variable can be used (after the defining tag)
+    pub visible_from: TextSize,
+}
+
+/// Usage of a variable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    pub name: String,
+    pub range: TextRange,
+    pub scope: ScopeId,
+    /// `None` if the variable is not defined in the template itself
+    pub definition: Option<DefinitionId>,
+}
+
+/// Scopes, definitions and references of variables in a template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeAnalyzer {
+    scopes: Vec<Scope>,
+    definitions: Vec<Definition>,
+    references: Vec<Reference>,
+    /// Names of called functions, which are only references if they are imported macros
+    function_calls: Vec<Reference>,
+}
+
+impl ScopeAnalyzer {
+    #[must_use]
+    pub fn new(root: &SyntaxNode) -> Self {
+        let mut analyzer = Self {
+            scopes: vec![Scope {
+                kind: ScopeKind::Template,
+                range: root.text_range(),
+                parent: None,
+                isolated: false,
+                dynamic: false,
+            }],
+            definitions: vec![],
+            references: vec![],
+            function_calls: vec![],
+        };
+        analyzer.visit_children(root, ScopeId(0));
+
+        // resolve at the end, because definitions are only collected when their tag is finished
+        for index in 0..analyzer.references.len() {
+            let reference = &analyzer.references[index];
+            let definition =
+                analyzer.resolve(&reference.name, reference.scope, reference.range.start());
+            analyzer.references[index].definition = definition;
+        }
+        for mut call in std::mem::take(&mut analyzer.function_calls) {
+            call.definition = analyzer
+                .resolve(&call.name, call.scope, call.range.start())
+                .filter(|d| analyzer.definition(*d).kind == DefinitionKind::Import);
+            if call.definition.is_some() {
+                analyzer.references.push(call);
+            }
+        }
+        analyzer.references.sort_by_key(|r| r.range.start());
+
+        analyzer
+    }
+
+    /// All scopes in the order of their appearance, the first one is the template scope
+    #[must_use]
+    pub fn scopes(&self) -> &[Scope] {
+        &self.scopes
+    }
+
+    #[must_use]
+    pub fn scope(&self, id: ScopeId) -> &Scope {
+        &self.scopes[id.0]
+    }
+
+    /// All definitions in the order of their appearance
+    #[must_use]
+    pub fn definitions(&self) -> &[Definition] {
+        &self.definitions
+    }
+
+    #[must_use]
+    pub fn definition(&self, id: DefinitionId) -> &Definition {
+        &self.definitions[id.0]
+    }
+
+    /// All variable usages in the order of their appearance
+    #[must_use]
+    pub fn references(&self) -> &[Reference] {
+        &self.references
+    }
+
+    pub fn references_to(&self, id: DefinitionId) -> impl Iterator<Item = &Reference> {
+        self.references
+            .iter()
+            .filter(move |r| r.definition == Some(id))
+    }
+
+    /// References, which are not defined in the template (they may come from the context or globals)
+    pub fn unresolved_references(&self) -> impl Iterator<Item = &Reference> {
+        self.references.iter().filter(|r| r.definition.is_none())
+    }
+
+    /// Innermost scope, which contains the offset
+    #[must_use]
+    pub fn scope_at(&self, offset: TextSize) -> ScopeId {
+        // nested scopes always come after their parents
+        let index = self
+            .scopes
+            .iter()
+            .rposition(|s| s.range.contains_inclusive(offset))
+            .unwrap_or(0);
+
+        ScopeId(index)
+    }
+
+    /// Definition which is visible for a variable with the name at the offset inside of the scope
+    #[must_use]
+    pub fn resolve(&self, name: &str, scope: ScopeId, offset: TextSize) -> Option<DefinitionId> {
+        let mut current = Some(scope);
+        let mut only_imports = false;
+
+        while let Some(id) = current {
+            let found = self
+                .definitions
+                .iter()
+                .enumerate()
+                .filter(|(_, d)| {
+                    d.scope == id
+                        && d.name == name
+                        && d.visible_from <= offset
+                        && (!only_imports || d.kind == DefinitionKind::Import)
+                })
+                .max_by_key(|(_, d)| d.visible_from);
+            if let Some((index, _)) = found {
+                return Some(DefinitionId(index));
+            }
+
+            let scope = self.scope(id);
+            only_imports |= scope.isolated;
+            current = scope.parent;
+        }
+
+        None
+    }
+
+    fn push_scope(&mut self, kind: ScopeKind, range: TextRange, parent: ScopeId) -> ScopeId {
+        self.scopes.push(Scope {
+            kind,
+            range,
+            parent: Some(parent),
+            isolated: false,
+            dynamic: false,
+        });
+
+        ScopeId(self.scopes.len() - 1)
+    }
+
+    fn define(
+        &mut self,
+        name: &SyntaxToken,
+        kind: DefinitionKind,
+        scope: ScopeId,
+        visible_from: TextSize,
+    ) {
+        self.definitions.push(Definition {
+            name: name.text().to_string(),
+            kind,
+            range: name.text_range(),
+            scope,
+            visible_from,
+        });
+    }
+
+    fn visit_children(&mut self, node: &SyntaxNode, scope: ScopeId) {
+        for child in node.children() {
+            self.visit(&child, scope);
+        }
+    }
+
+    fn visit(&mut self, node: &SyntaxNode, scope: ScopeId) {
+        match node.kind() {
+            SyntaxKind::TWIG_LITERAL_NAME => {
+                if let Some(name) = TwigLiteralName::cast(node.clone()).and_then(|n| n.name()) {
+                    let reference = Reference {
+                        name: name.text().to_string(),
+                        range: name.text_range(),
+                        scope,
+                        definition: None,
+                    };
+                    if is_function_name(node) {
+                        self.function_calls.push(reference);
+                    } else if is_variable_reference(node) {
+                        self.references.push(reference);
+                    }
+                }
+            }
+            SyntaxKind::TWIG_BLOCK => {
+                let block_scope = self.push_scope(ScopeKind::Block, node.text_range(), scope);
+                self.visit_children(node, block_scope);
+            }
+            SyntaxKind::TWIG_SET => self.visit_set(node, scope),
+            SyntaxKind::TWIG_FOR => self.visit_for(node, scope),
+            SyntaxKind::TWIG_MACRO => self.visit_macro(node, scope),
+            SyntaxKind::TWIG_WITH => self.visit_with(node, scope),
+            SyntaxKind::TWIG_IMPORT | SyntaxKind::TWIG_FROM => self.visit_import(node, scope),
+            SyntaxKind::TWIG_ARROW_FUNCTION => self.visit_arrow_function(node, scope),
+            // only contains block names
+            SyntaxKind::TWIG_USE => {}
+            _ => self.visit_children(node, scope),
+        }
+    }
+
+    /// `{% set a, b = 1, 2 %}` or `{% set a %}...{% endset %}`
+    fn visit_set(&mut self, node: &SyntaxNode, scope: ScopeId) {
+        let mut targets = vec![];
+        for child in node.children() {
+            if child.kind() == SyntaxKind::TWIG_SET_BLOCK {
+                for assignment in child
+                    .children()
+                    .filter(|c| c.kind() == SyntaxKind::TWIG_ASSIGNMENT)
+                {
+                    for part in assignment.children() {
+                        match TwigLiteralName::cast(part.clone()) {
+                            Some(name) => targets.extend(name.name()),
+                            None => self.visit(&part, scope),
+                        }
+                    }
+                }
+            } else {
+                self.visit(&child, scope);
+            }
+        }
+
+        for target in targets {
+            self.define(&target, DefinitionKind::Set, scope, node.text_range().end());
+        }
+    }
+
+    /// `{% for key, value in items %}...{% else %}...{% endfor %}`
+    fn visit_for(&mut self, node: &SyntaxNode, scope: ScopeId) {
+        let mut for_scope = None;
+
+        for child in node.children() {
+            match child.kind() {
+                SyntaxKind::TWIG_FOR_BLOCK => {
+                    let body_end = child
+                        .next_sibling()
+                        .filter(|s| s.kind() == SyntaxKind::BODY)
+                        .map_or(child.text_range().end(), |b| b.text_range().end());
+                    let id = self.push_scope(
+                        ScopeKind::For,
+                        TextRange::new(child.text_range().start(), body_end),
+                        scope,
+                    );
+                    for_scope = Some(id);
+
+                    let visible_from = child.text_range().end();
+                    for part in child.children() {
+                        match TwigLiteralName::cast(part.clone()).and_then(|n| n.name()) {
+                            Some(name) => {
+                                self.define(&name, DefinitionKind::ForVariable, id, visible_from);
+                            }
+                            // the sequence is evaluated in the outer scope
+                            None => self.visit(&part, scope),
+                        }
+                    }
+                    if let Some(keyword) = child
+                        .children_with_tokens()
+                        .filter_map(rowan::NodeOrToken::into_token)
+                        .find(|t| t.kind() == T!["for"])
+                    {
+                        self.definitions.push(Definition {
+                            name: "loop".to_string(),
+                            kind: DefinitionKind::Loop,
+                            range: keyword.text_range(),
+                            scope: id,
+                            visible_from,
+                        });
+                    }
+                }
+                SyntaxKind::BODY => {
+                    // the body of the else branch has no loop variables
+                    let in_loop = child
+                        .prev_sibling()
+                        .is_some_and(|s| s.kind() == SyntaxKind::TWIG_FOR_BLOCK);
+                    let body_scope = for_scope.filter(|_| in_loop).unwrap_or(scope);
+                    self.visit(&child, body_scope);
+                }
+                _ => self.visit(&child, scope),
+            }
+        }
+    }
+
+    /// `{% macro name(a, b = 1) %}...{% endmacro %}`
+    fn visit_macro(&mut self, node: &SyntaxNode, scope: ScopeId) {
+        let macro_scope = self.push_scope(ScopeKind::Macro, node.text_range(), scope);
+        self.scopes[macro_scope.0].isolated = true;
+
+        for child in node.children() {
+            if child.kind() != SyntaxKind::TWIG_MACRO_STARTING_BLOCK {
+                self.visit(&child, macro_scope);
+                continue;
+            }
+
+            let visible_from = child.text_range().end();
+            let arguments = child
+                .children()
+                .filter(|c| c.kind() == SyntaxKind::TWIG_ARGUMENTS)
+                .flat_map(|a| a.children());
+            for argument in arguments {
+                let name = match argument.kind() {
+                    SyntaxKind::TWIG_EXPRESSION => argument
+                        .first_child()
+                        .and_then(TwigLiteralName::cast)
+                        .and_then(|n| n.name()),
+                    SyntaxKind::TWIG_NAMED_ARGUMENT => {
+                        // default values are evaluated inside of the macro
+                        for value in argument.children() {
+                            self.visit(&value, macro_scope);
+                        }
+                        argument
+                            .children_with_tokens()
+                            .filter_map(rowan::NodeOrToken::into_token)
+                            .find(|t| t.kind() == T![word])
+                    }
+                    _ => None,
+                };
+
+                if let Some(name) = name {
+                    self.define(
+                        &name,
+                        DefinitionKind::MacroParameter,
+                        macro_scope,
+                        visible_from,
+                    );
+                }
+            }
+        }
+    }
+
+    /// `{% with { key: value } only %}...{% endwith %}`
+    fn visit_with(&mut self, node: &SyntaxNode, scope: ScopeId) {
+        let with_scope = self.push_scope(ScopeKind::With, node.text_range(), scope);
+
+        for child in node.children() {
+            if child.kind() != SyntaxKind::TWIG_WITH_STARTING_BLOCK {
+                self.visit(&child, with_scope);
+                continue;
+            }
+
+            let visible_from = child.text_range().end();
+            self.scopes[with_scope.0].isolated =
+                child.children_with_tokens().any(|c| c.kind() == T!["only"]);
+
+            for expression in child.children() {
+                // the variables are evaluated in the outer scope
+                self.visit(&expression, scope);
+
+                let hash = expression
+                    .first_child()
+                    .filter(|h| h.kind() == SyntaxKind::TWIG_LITERAL_HASH);
+                let Some(hash) = hash else {
+                    self.scopes[with_scope.0].dynamic = true;
+                    continue;
+                };
+
+                let keys = hash
+                    .descendants()
+                    .filter(|n| {
+                        n.kind() == SyntaxKind::TWIG_LITERAL_HASH_KEY
+                            && n.ancestors()
+                                .find(|a| a.kind() == SyntaxKind::TWIG_LITERAL_HASH)
+                                .as_ref()
+                                == Some(&hash)
+                    })
+                    .collect::<Vec<_>>();
+                for key in keys {
+                    match key
+                        .children_with_tokens()
+                        .filter_map(rowan::NodeOrToken::into_token)
+                        .find(|t| !t.kind().is_trivia())
+                    {
+                        Some(name) if name.kind() == T![word] => {
+                            self.define(&name, DefinitionKind::With, with_scope, visible_from);
+                        }
+                        // quoted or computed keys
+                        _ => self.scopes[with_scope.0].dynamic = true,
+                    }
+                }
+            }
+        }
+    }
+
+    /// `{% import 'macros.html.twig' as macros %}` or `{% from 'macros.html.twig' import a as b, c %}`
+    fn visit_import(&mut self, node: &SyntaxNode, scope: ScopeId) {
+        let visible_from = node.text_range().end();
+
+        for child in node.children() {
+            match child.kind() {
+                SyntaxKind::TWIG_LITERAL_NAME => {
+                    if let Some(name) = TwigLiteralName::cast(child).and_then(|n| n.name()) {
+                        self.define(&name, DefinitionKind::Import, scope, visible_from);
+                    }
+                }
+                SyntaxKind::TWIG_OVERRIDE => {
+                    // the alias (or the macro name without one) is the defined variable
+                    if let Some(name) = child
+                        .children()
+                        .filter_map(TwigLiteralName::cast)
+                        .last()
+                        .and_then(|n| n.name())
+                    {
+                        self.define(&name, DefinitionKind::Import, scope, visible_from);
+                    }
+                }
+                _ => self.visit(&child, scope),
+            }
+        }
+    }
+
+    /// `(value, key) => value ~ key`
+    fn visit_arrow_function(&mut self, node: &SyntaxNode, scope: ScopeId) {
+        let arrow_scope = self.push_scope(ScopeKind::ArrowFunction, node.text_range(), scope);
+
+        for child in node.children() {
+            match TwigLiteralName::cast(child.clone()).and_then(|n| n.name()) {
+                Some(name) => self.define(
+                    &name,
+                    DefinitionKind::ArrowFunctionParameter,
+                    arrow_scope,
+                    node.text_range().start(),
+                ),
+                None => self.visit(&child, arrow_scope),
+            }
+        }
+    }
+}
+
+/// Whether a name is called like `name()`, which may be a macro imported with `{% from ... import name %}`
+fn is_function_name(name: &SyntaxNode) -> bool {
+    name.parent()
+        .filter(|p| p.kind() == SyntaxKind::TWIG_OPERAND)
+        .and_then(|operand| {
+            let call = operand.parent()?;
+            Some(
+                call.kind() == SyntaxKind::TWIG_FUNCTION_CALL
+                    && call.first_child().as_ref() == Some(&operand),
+            )
+        })
+        .unwrap_or(false)
+}
+
+/// Whether a name is a variable and not the name of a function, filter, test or property
+fn is_variable_reference(name: &SyntaxNode) -> bool {
+    let Some(parent) = name.parent() else {
+        return true;
+    };
+
+    match parent.kind() {
+        SyntaxKind::TWIG_OPERAND => {
+            let Some(outer) = parent.parent() else {
+                return true;
+            };
+            let is_first_operand = outer.first_child().as_ref() == Some(&parent);
+
+            match outer.kind() {
+                // function names and properties
+                SyntaxKind::TWIG_FUNCTION_CALL => !is_first_operand,
+                SyntaxKind::TWIG_ACCESSOR => is_first_operand,
+                // filter names, every operand of the filters in `{% apply upper|escape %}` is a filter name
+                SyntaxKind::TWIG_FILTER => {
+                    is_first_operand
+                        && !outer
+                            .ancestors()
+                            .take_while(|a| {
+                                matches!(
+                                    a.kind(),
+                                    SyntaxKind::TWIG_FILTER
+                                        | SyntaxKind::TWIG_OPERAND
+                                        | SyntaxKind::TWIG_APPLY_STARTING_BLOCK
+                                )
+                            })
+                            .any(|a| a.kind() == SyntaxKind::TWIG_APPLY_STARTING_BLOCK)
+                }
+                _ => true,
+            }
+        }
+        // test names like `is defined`
+        SyntaxKind::TWIG_EXPRESSION => !parent
+            .parent()
+            .and_then(TwigBinaryExpression::cast)
+            .is_some_and(|binary| {
+                binary.operator().is_some_and(|o| o.kind() == T!["is"])
+                    && binary.rhs_expression().map(|e| e.syntax().clone()) == Some(parent.clone())
+            }),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Write;
+
+    use expect_test::expect;
+
+    use super::*;
+
+    /// Debug representation of all scopes, definitions and references
+    fn analysis(source: &str) -> String {
+        let root = SyntaxNode::new_root(crate::parse(source).green_node);
+        let analyzer = ScopeAnalyzer::new(&root);
+        let mut out = String::new();
+
+        for (index, scope) in analyzer.scopes().iter().enumerate() {
+            writeln!(
+                out,
+                "scope {index} {:?}@{:?} parent={:?}{}{}",
+                scope.kind,
+                scope.range,
+                scope.parent.map(|p| p.0),
+                if scope.isolated { " isolated" } else { "" },
+                if scope.dynamic { " dynamic" } else { "" },
+            )
+            .unwrap();
+        }
+        for (index, definition) in analyzer.definitions().iter().enumerate() {
+            writeln!(
+                out,
+                "def {index} {:?} '{}'@{:?} in scope {}",
+                definition.kind, definition.name, definition.range, definition.scope.0
+            )
+            .unwrap();
+        }
+        for reference in analyzer.references() {
+            writeln!(
+                out,
+                "ref '{}'@{:?} in scope {} -> {}",
+                reference.name,
+                reference.range,
+                reference.scope.0,
+                reference
+                    .definition
+                    .map_or("unresolved".to_string(), |d| format!("def {}", d.0))
+            )
+            .unwrap();
+        }
+
+        out
+    }
+
+    #[test]
+    fn scope_set_and_references() {
+        let analysis = analysis(
+            "{{ a }}{% set a, b = 1, a %}{% set a = a|upper ~ b.c ~ f(d) %}{{ a is defined }}",
+        );
+
+        expect![[r#"
+                scope 0 Template@0..80 parent=None
+                def 0 Set 'a'@14..15 in scope 0
+                def 1 Set 'b'@17..18 in scope 0
+                def 2 Set 'a'@35..36 in scope 0
+                ref 'a'@3..4 in scope 0 -> unresolved
+                ref 'a'@24..25 in scope 0 -> unresolved
+                ref 'a'@39..40 in scope 0 -> def 0
+                ref 'b'@49..50 in scope 0 -> def 1
+                ref 'd'@57..58 in scope 0 -> unresolved
+                ref 'a'@65..66 in scope 0 -> def 2
+            "#]]
+        .assert_eq(&analysis);
+    }
+
+    #[test]
+    fn scope_for_loop() {
+        let analysis = analysis(
+            "{% for key, item in item.children %}{{ loop.index }}{{ key }}{% set inner = item %}{% else %}{{ item }}{% endfor %}{{ inner }}",
+        );
+
+        expect![[r#"
+                scope 0 Template@0..126 parent=None
+                scope 1 For@0..83 parent=Some(0)
+                def 0 ForVariable 'key'@7..10 in scope 1
+                def 1 ForVariable 'item'@12..16 in scope 1
+                def 2 Loop 'loop'@3..6 in scope 1
+                def 3 Set 'inner'@68..73 in scope 1
+                ref 'item'@20..24 in scope 0 -> unresolved
+                ref 'loop'@39..43 in scope 1 -> def 2
+                ref 'key'@55..58 in scope 1 -> def 0
+                ref 'item'@76..80 in scope 1 -> def 1
+                ref 'item'@96..100 in scope 0 -> unresolved
+                ref 'inner'@118..123 in scope 0 -> unresolved
+            "#]]
+        .assert_eq(&analysis);
+    }
+
+    #[test]
+    fn scope_block_and_capture() {
+        let analysis = analysis(
+            "{% set a %}{{ b }}{% endset %}{% block content %}{% set b = a %}{{ b }}{% endblock %}{{ b }}",
+        );
+
+        expect![[r#"
+                scope 0 Template@0..92 parent=None
+                scope 1 Block@30..85 parent=Some(0)
+                def 0 Set 'a'@7..8 in scope 0
+                def 1 Set 'b'@56..57 in scope 1
+                ref 'b'@14..15 in scope 0 -> unresolved
+                ref 'a'@60..61 in scope 1 -> def 0
+                ref 'b'@67..68 in scope 1 -> def 1
+                ref 'b'@88..89 in scope 0 -> unresolved
+            "#]]
+        .assert_eq(&analysis);
+    }
+
+    #[test]
+    fn scope_macro_and_imports() {
+        let analysis = analysis(
+            "{% import 'a.twig' as forms %}{% from 'b.twig' import input as field, label %}{% set outer = 1 %}{% macro m(x, y = z) %}{{ x ~ y ~ outer ~ forms.input() ~ field() }}{% endmacro %}",
+        );
+
+        expect![[r#"
+                scope 0 Template@0..179 parent=None
+                scope 1 Macro@97..179 parent=Some(0) isolated
+                def 0 Import 'forms'@22..27 in scope 0
+                def 1 Import 'field'@63..68 in scope 0
+                def 2 Import 'label'@70..75 in scope 0
+                def 3 Set 'outer'@85..90 in scope 0
+                def 4 MacroParameter 'x'@108..109 in scope 1
+                def 5 MacroParameter 'y'@111..112 in scope 1
+                ref 'z'@115..116 in scope 1 -> unresolved
+                ref 'x'@123..124 in scope 1 -> def 4
+                ref 'y'@127..128 in scope 1 -> def 5
+                ref 'outer'@131..136 in scope 1 -> unresolved
+                ref 'forms'@139..144 in scope 1 -> def 0
+                ref 'field'@155..160 in scope 1 -> def 1
+            "#]]
+        .assert_eq(&analysis);
+    }
+
+    #[test]
+    fn scope_with() {
+        let analysis = analysis(
+            "{% set outer = 1 %}{% with { a: outer } only %}{{ a ~ outer }}{% endwith %}{% with vars %}{{ outer }}{% endwith %}{% apply upper|escape('html') %}{{ a }}{% endapply %}",
+        );
+
+        expect![[r#"
+                scope 0 Template@0..167 parent=None
+                scope 1 With@19..75 parent=Some(0) isolated
+                scope 2 With@75..114 parent=Some(0) dynamic
+                def 0 Set 'outer'@7..12 in scope 0
+                def 1 With 'a'@29..30 in scope 1
+                ref 'outer'@32..37 in scope 0 -> def 0
+                ref 'a'@50..51 in scope 1 -> def 1
+                ref 'outer'@54..59 in scope 1 -> unresolved
+                ref 'vars'@83..87 in scope 0 -> unresolved
+                ref 'outer'@93..98 in scope 2 -> def 0
+                ref 'a'@149..150 in scope 0 -> unresolved
+            "#]]
+        .assert_eq(&analysis);
+    }
+
+    #[test]
+    fn scope_arrow_function() {
+        let analysis = analysis("{{ items|filter((v, k) => v > limit and k)|map(v => v) }}{{ v }}");
+
+        expect![[r#"
+                scope 0 Template@0..64 parent=None
+                scope 1 ArrowFunction@16..41 parent=Some(0)
+                scope 2 ArrowFunction@47..53 parent=Some(0)
+                def 0 ArrowFunctionParameter 'v'@17..18 in scope 1
+                def 1 ArrowFunctionParameter 'k'@20..21 in scope 1
+                def 2 ArrowFunctionParameter 'v'@47..48 in scope 2
+                ref 'items'@3..8 in scope 0 -> unresolved
+                ref 'v'@26..27 in scope 1 -> def 0
+                ref 'limit'@30..35 in scope 1 -> unresolved
+                ref 'k'@40..41 in scope 1 -> def 1
+                ref 'v'@52..53 in scope 2 -> def 2
+                ref 'v'@60..61 in scope 0 -> unresolved
+            "#]]
+        .assert_eq(&analysis);
+    }
+
+    #[test]
+    fn scope_at_offset() {
+        let source = "{% block a %}{% for i in x %}{{ i }}{% endfor %}{% endblock %}";
+        let root = SyntaxNode::new_root(crate::parse(source).green_node);
+        let analyzer = ScopeAnalyzer::new(&root);
+
+        assert_eq!(analyzer.scope_at(TextSize::from(0)), ScopeId(1));
+        assert_eq!(analyzer.scope_at(TextSize::from(32)), ScopeId(2));
+        assert_eq!(
+            analyzer.scope_at(TextSize::of(source) + TextSize::from(1)),
+            ScopeId(0)
+        );
+    }
+}
@@ -0,0 +1,388 @@
+//! Simplified, owned tree of a template without any trivia.
+//!
+//! The lossless syntax tree keeps every character of the source code, which is required for
+//! formatting and fixing, but is quite low level to walk for analysis tools. The semantic tree
+//! only keeps the meaning of the template: html elements with their attributes, text, comments and
+//! twig constructs, where literal strings are already evaluated (quotes removed and escape
+//! sequences resolved).
+//!
+//! It is built once from the syntax tree and doesn't reference it anymore.
+//!
+//! ```
+//! use ludtwig_parser::syntax::semantic::{self, Expression, Node};
+//! use ludtwig_parser::syntax::untyped::SyntaxNode;
+//!
+//! let parse = ludtwig_parser::parse("<p class=\"intro\">\n    Hello {{ 'world\\'s' }}\n</p>");
+//! let nodes = semantic::build(&SyntaxNode::new_root(parse.green_node));
+//!
+//! let Node::Element(paragraph) = &nodes[0] else { panic!() };
+//! assert_eq!(paragraph.name, "p");
+//! assert_eq!(paragraph.attribute("class").unwrap().static_value(), Some("intro"));
+//! assert_eq!(paragraph.children[0], Node::Text("Hello".to_string()));
+//! assert_eq!(paragraph.children[1], Node::Output(Expression::String("world's".to_string())));
+//! ```
+
+use rowan::ast::AstNode;
+
+use crate::syntax::trivia::first_non_trivia_token;
+use crate::syntax::typed::{
+    HtmlAttribute, HtmlAttributeValue, HtmlAttributeValueSegment, HtmlTag, TwigBlock,
+    TwigExpression, TwigLiteralString, TwigVar,
+};
+use crate::syntax::untyped::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken};
+use crate::T;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    /// Html element like `<div class="a">...</div>`
+    Element(Element),
+    /// Html text, where every whitespace and line break between words is a single space
+    Text(String),
+    /// Content of an html or twig comment
+    Comment(String),
+    /// Twig output like `{{ name }}`
+    Output(Expression),
+    /// Twig block like `{% block name %}...{% endblock %}`
+    Block(Block),
+    /// Any other twig (or shopware) tag like `{% if %}`, `{% for %}` or `{% sw_extends %}`
+    Tag(Tag),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Element {
+    pub name: String,
+    /// Attributes inside of twig syntax in between the attributes (like `{% if %}`) are included
+    pub attributes: Vec<Attribute>,
+    pub children: Vec<Node>,
+}
+
+impl Element {
+    /// First attribute with the name (case-insensitive, like html attributes)
+    #[must_use]
+    pub fn attribute(&self, name: &str) -> Option<&Attribute> {
+        self.attributes
+            .iter()
+            .find(|a| a.name.eq_ignore_ascii_case(name))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attribute {
+    /// Name of the attribute, for vue directives the full directive like `v-on:click` or `@click`
+    pub name: String,
+    /// Text and twig syntax inside of the quotes, `None` for attributes without a value like `disabled`
+    pub value: Option<Vec<Node>>,
+}
+
+impl Attribute {
+    /// Value of the attribute, if it doesn't contain any twig syntax
+    #[must_use]
+    pub fn static_value(&self) -> Option<&str> {
+        match self.value.as_deref()? {
+            [] => Some(""),
+            [Node::Text(text)] => Some(text),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub name: String,
+    pub children: Vec<Node>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tag {
+    /// Keyword of the opening tag, like `if` or `sw_include`
+    pub name: String,
+    /// Expressions of all parts of the tag, like the conditions of `{% if %}` and `{% elseif %}`
+    pub arguments: Vec<Expression>,
+    /// Every body of the tag in order, like the `if`, `elseif` and `else` branches
+    pub bodies: Vec<Vec<Node>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expression {
+    /// Literal string without interpolation, like `'hello'`
+    String(String),
+    Number(String),
+    Boolean(bool),
+    Null,
+    /// Plain variable like `name`
+    Variable(String),
+    /// Any other expression by its source code, like `product.name|upper`
+    Other(String),
+}
+
+/// Build the semantic tree of the root node of a template
+#[must_use]
+pub fn build(root: &SyntaxNode) -> Vec<Node> {
+    nodes(root)
+}
+
+/// Semantic nodes of all children of a node (usually a `BODY` or `ROOT`)
+fn nodes(parent: &SyntaxNode) -> Vec<Node> {
+    parent.children().flat_map(|child| node(&child)).collect()
+}
+
+fn node(node: &SyntaxNode) -> Vec<Node> {
+    match node.kind() {
+        SyntaxKind::HTML_TAG => HtmlTag::cast(node.clone())
+            .map(|tag| element(&tag))
+            .into_iter()
+            .collect(),
+        SyntaxKind::HTML_TEXT => vec![Node::Text(normalized_text(node))],
+        SyntaxKind::HTML_COMMENT => vec![Node::Comment(comment(node, "<!--", "-->"))],
+        SyntaxKind::TWIG_COMMENT => vec![Node::Comment(comment(node, "{#", "#}"))],
+        SyntaxKind::TWIG_VAR => TwigVar::cast(node.clone())
+            .and_then(|var| var.expression())
+            .map(|e| Node::Output(expression(&e)))
+            .into_iter()
+            .collect(),
+        SyntaxKind::TWIG_BLOCK => TwigBlock::cast(node.clone())
+            .and_then(|b| block(&b))
+            .into_iter()
+            .collect(),
+        _ if is_tag(node) => tag(node).into_iter().collect(),
+        // like conditional comments, only their content is kept
+        _ => node
+            .children()
+            .filter(|c| c.kind() == SyntaxKind::BODY)
+            .flat_map(|body| nodes(&body))
+            .collect(),
+    }
+}
+
+/// Twig tags (and their children like `{% else %}`) start with `{%`
+fn is_tag(node: &SyntaxNode) -> bool {
+    first_non_trivia_token(node).is_some_and(|t| t.kind() == T!["{%"])
+}
+
+fn element(tag: &HtmlTag) -> Node {
+    let attributes = tag
+        .starting_tag()
+        .map(|starting_tag| {
+            starting_tag
+                .syntax()
+                .descendants()
+                .filter_map(HtmlAttribute::cast)
+                .filter_map(|a| attribute(&a))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Node::Element(Element {
+        name: tag.name().map(|n| n.text().to_string()).unwrap_or_default(),
+        attributes,
+        children: tag.body().map(|b| nodes(b.syntax())).unwrap_or_default(),
+    })
+}
+
+fn attribute(attribute: &HtmlAttribute) -> Option<Attribute> {
+    let name = match attribute.vue_directive() {
+        Some(directive) => directive.syntax().text().to_string().trim().to_string(),
+        None => attribute.name()?.text().to_string(),
+    };
+
+    Some(Attribute {
+        name,
+        value: attribute_value(attribute.value()),
+    })
+}
+
+fn attribute_value(value: HtmlAttributeValue) -> Option<Vec<Node>> {
+    match value {
+        HtmlAttributeValue::Missing => None,
+        HtmlAttributeValue::Static(text) if text.is_empty() => Some(vec![]),
+        HtmlAttributeValue::Static(text) => Some(vec![Node::Text(text)]),
+        HtmlAttributeValue::Mixed(segments) => Some(
+            segments
+                .iter()
+                .flat_map(|segment| match segment {
+                    HtmlAttributeValueSegment::Text(text) => vec![Node::Text(text.clone())],
+                    HtmlAttributeValueSegment::Twig(twig) => node(twig),
+                })
+                .collect(),
+        ),
+    }
+}
+
+fn block(block: &TwigBlock) -> Option<Node> {
+    let name = block.name()?.text().to_string();
+    let children = match block.body() {
+        Some(body) => nodes(body.syntax()),
+        // shorthand blocks like `{% block title page.title %}`
+        None => block
+            .starting_block()
+            .and_then(|s| s.syntax().children().find_map(TwigExpression::cast))
+            .map(|e| vec![Node::Output(expression(&e))])
+            .unwrap_or_default(),
+    };
+
+    Some(Node::Block(Block { name, children }))
+}
+
+fn tag(node: &SyntaxNode) -> Option<Node> {
+    let mut tokens = non_trivia_tokens(node).skip_while(|t| t.kind() != T!["{%"]);
+    tokens.next()?;
+    let name = tokens.next()?.text().to_string();
+
+    let mut arguments = vec![];
+    let mut bodies = vec![];
+    collect_tag_parts(node, &mut arguments, &mut bodies);
+
+    Some(Node::Tag(Tag {
+        name,
+        arguments,
+        bodies,
+    }))
+}
+
+/// Expressions and bodies of the tag, without the ones inside of nested expressions and bodies
+fn collect_tag_parts(
+    node: &SyntaxNode,
+    arguments: &mut Vec<Expression>,
+    bodies: &mut Vec<Vec<Node>>,
+) {
+    for child in node.children() {
+        match child.kind() {
+            SyntaxKind::BODY => bodies.push(nodes(&child)),
+            SyntaxKind::TWIG_EXPRESSION => {
+                if let Some(e) = TwigExpression::cast(child) {
+                    arguments.push(expression(&e));
+                }
+            }
+            _ => collect_tag_parts(&child, arguments, bodies),
+        }
+    }
+}
+
+fn expression(expression: &TwigExpression) -> Expression {
+    let source = || expression.syntax().text().to_string().trim().to_string();
+    let Some(inner) = expression.syntax().first_child() else {
+        return Expression::Other(source());
+    };
+
+    match inner.kind() {
+        SyntaxKind::TWIG_LITERAL_STRING => TwigLiteralString::cast(inner)
+            .map(|s| s.value())
+            .filter(|value| !value.has_interpolation)
+            .map_or_else(
+                || Expression::Other(source()),
+                |value| Expression::String(value.text),
+            ),
+        SyntaxKind::TWIG_LITERAL_NUMBER => Expression::Number(source()),
+        SyntaxKind::TWIG_LITERAL_BOOLEAN => {
+            Expression::Boolean(source().eq_ignore_ascii_case("true"))
+        }
+        SyntaxKind::TWIG_LITERAL_NULL => Expression::Null,
+        SyntaxKind::TWIG_LITERAL_NAME => Expression::Variable(source()),
+        _ => Expression::Other(source()),
+    }
+}
+
+fn non_trivia_tokens(node: &SyntaxNode) -> impl Iterator<Item = SyntaxToken> {
+    node.descendants_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .filter(|t| !t.kind().is_trivia())
+}
+
+fn normalized_text(node: &SyntaxNode) -> String {
+    non_trivia_tokens(node)
+        .map(|t| t.text().to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn comment(node: &SyntaxNode, start: &str, end: &str) -> String {
+    let text = node.text().to_string();
+    let text = text.trim();
+    let text = text.strip_prefix(start).unwrap_or(text);
+    let text = text.strip_suffix(end).unwrap_or(text);
+    text.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_source(source: &str) -> Vec<Node> {
+        build(&SyntaxNode::new_root(crate::parse(source).green_node))
+    }
+
+    #[test]
+    fn builds_html() {
+        let nodes = build_source(
+            "<!-- note -->\n<div class=\"a {{ b }}\" {% if c %}hidden{% endif %}>\n    hello   world\n</div>",
+        );
+
+        assert_eq!(
+            nodes,
+            vec![
+                Node::Comment("note".to_string()),
+                Node::Element(Element {
+                    name: "div".to_string(),
+                    attributes: vec![
+                        Attribute {
+                            name: "class".to_string(),
+                            value: Some(vec![
+                                Node::Text("a ".to_string()),
+                                Node::Output(Expression::Variable("b".to_string())),
+                            ]),
+                        },
+                        Attribute {
+                            name: "hidden".to_string(),
+                            value: None,
+                        },
+                    ],
+                    children: vec![Node::Text("hello world".to_string())],
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn builds_twig() {
+        let nodes = build_source(
+            "{# a #}{% block content %}{% if a == 'x\\'y' %}{{ 1 }}{% else %}{{ null }}{% endif %}{% endblock %}",
+        );
+
+        assert_eq!(
+            nodes,
+            vec![
+                Node::Comment("a".to_string()),
+                Node::Block(Block {
+                    name: "content".to_string(),
+                    children: vec![Node::Tag(Tag {
+                        name: "if".to_string(),
+                        arguments: vec![Expression::Other("a == 'x\\'y'".to_string())],
+                        bodies: vec![
+                            vec![Node::Output(Expression::Number("1".to_string()))],
+                            vec![Node::Output(Expression::Null)],
+                        ],
+                    })],
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn evaluates_strings() {
+        let nodes =
+            build_source(r##"{{ 'it\'s' }}{{ "a\tb" }}{{ "#{a}" }}{% block title 'Home' %}"##);
+
+        assert_eq!(
+            nodes,
+            vec![
+                Node::Output(Expression::String("it's".to_string())),
+                Node::Output(Expression::String("a\tb".to_string())),
+                Node::Output(Expression::Other(r##""#{a}""##.to_string())),
+                Node::Block(Block {
+                    name: "title".to_string(),
+                    children: vec![Node::Output(Expression::String("Home".to_string()))],
+                }),
+            ]
+        );
+    }
+}
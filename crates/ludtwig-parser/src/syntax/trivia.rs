@@ -0,0 +1,201 @@
+//! Helpers to inspect the trivia (whitespace and line breaks) of the syntax tree.
+//!
+//! Trivia is attached to the tree as leading tokens of the following node, so for example the
+//! indentation of a html tag is the first token inside of the `HTML_TAG` node.
+
+use std::fmt::{Display, Formatter};
+
+use crate::syntax::untyped::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken};
+use crate::T;
+
+/// Style of the line endings inside of a line break token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LineBreakKind {
+    /// Only `\n`
+    Unix,
+    /// Only `\r\n`
+    Windows,
+    /// Both of them in the same token
+    Mixed,
+}
+
+impl Display for LineBreakKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LineBreakKind::Unix => write!(f, "unix_LF"),
+            LineBreakKind::Windows => write!(f, "windows_CRLF"),
+            LineBreakKind::Mixed => write!(f, "mixed"),
+        }
+    }
+}
+
+/// Trivia tokens in front of the first non trivia token of the node
+/// (this includes trivia of nested child nodes)
+#[must_use]
+pub fn leading_trivia(node: &SyntaxNode) -> Vec<SyntaxToken> {
+    node.descendants_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .take_while(|t| t.kind().is_trivia())
+        .collect()
+}
+
+/// First direct child (node or token), which is not a trivia token
+#[must_use]
+pub fn first_non_trivia_child(node: &SyntaxNode) -> Option<SyntaxElement> {
+    node.children_with_tokens().find(|element| match element {
+        SyntaxElement::Node(_) => true,
+        SyntaxElement::Token(t) => !t.kind().is_trivia(),
+    })
+}
+
+/// First token inside of the node (at any depth), which is not trivia
+#[must_use]
+pub fn first_non_trivia_token(node: &SyntaxNode) -> Option<SyntaxToken> {
+    node.descendants_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .find(|t| !t.kind().is_trivia())
+}
+
+/// Whitespace token at the start of the line the token is on,
+/// `None` if the line isn't indented (or the token is the indentation itself)
+#[must_use]
+pub fn indentation_of(token: &SyntaxToken) -> Option<SyntaxToken> {
+    // `prev_token` stops at empty nodes (like an empty attribute list), so the root is searched instead
+    let root = token.parent_ancestors().last()?;
+    let mut line_start = token.clone();
+    while line_start.text_range().start() > 0.into() {
+        let previous = root
+            .token_at_offset(line_start.text_range().start())
+            .left_biased()?;
+        if previous.kind() == T![lb] {
+            break;
+        }
+        line_start = previous;
+    }
+
+    if line_start.kind() == T![ws] && &line_start != token {
+        Some(line_start)
+    } else {
+        None
+    }
+}
+
+/// Style of the line endings inside of a line break token, `None` for any other token
+#[must_use]
+pub fn line_break_kind(token: &SyntaxToken) -> Option<LineBreakKind> {
+    if token.kind() != SyntaxKind::TK_LINE_BREAK {
+        return None;
+    }
+
+    let mut endings = line_endings(token.text());
+    let first = endings.next()?.1;
+    let kind = |ending: &str| {
+        if ending == "\r\n" {
+            LineBreakKind::Windows
+        } else {
+            LineBreakKind::Unix
+        }
+    };
+
+    if endings.all(|(_, ending)| ending == first) {
+        Some(kind(first))
+    } else {
+        Some(LineBreakKind::Mixed)
+    }
+}
+
+/// Number of lines a line break token ends (a token can contain multiple line breaks)
+#[must_use]
+pub fn line_break_count(token: &SyntaxToken) -> usize {
+    if token.kind() == SyntaxKind::TK_LINE_BREAK {
+        token.text().matches('\n').count()
+    } else {
+        0
+    }
+}
+
+/// Split the text of a line break token into its individual line endings (`\n` or `\r\n`)
+/// together with their offset inside of the text.
+pub fn line_endings(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.match_indices('\n').map(|(offset, _)| {
+        if offset > 0 && text.as_bytes()[offset - 1] == b'\r' {
+            (offset - 1, &text[offset - 1..=offset])
+        } else {
+            (offset, &text[offset..=offset])
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root(source: &str) -> SyntaxNode {
+        SyntaxNode::new_root(crate::parse(source).green_node)
+    }
+
+    fn token(root: &SyntaxNode, text: &str) -> SyntaxToken {
+        root.descendants_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .find(|t| t.text() == text)
+            .unwrap()
+    }
+
+    #[test]
+    fn leading_trivia_of_nested_nodes() {
+        let root = root("<div>\n    <span>a</span>\n</div>");
+        let span = root
+            .descendants()
+            .filter(|n| n.kind() == SyntaxKind::HTML_TAG)
+            .nth(1)
+            .unwrap();
+
+        let trivia: Vec<String> = leading_trivia(&span)
+            .iter()
+            .map(|t| t.text().to_string())
+            .collect();
+        assert_eq!(trivia, vec!["\n", "    "]);
+        assert_eq!(first_non_trivia_token(&span).unwrap().text(), "<");
+        assert_eq!(
+            first_non_trivia_child(&span).unwrap().kind(),
+            SyntaxKind::HTML_STARTING_TAG
+        );
+    }
+
+    #[test]
+    fn indentation() {
+        let root = root("<div>\n    <span>a</span>\n</div>");
+
+        assert_eq!(
+            indentation_of(&token(&root, "span")).unwrap().text(),
+            "    "
+        );
+        assert_eq!(indentation_of(&token(&root, "a")).unwrap().text(), "    ");
+        assert_eq!(indentation_of(&token(&root, "    ")), None);
+        assert_eq!(indentation_of(&token(&root, "div")), None);
+    }
+
+    #[test]
+    fn line_breaks() {
+        let root = root("a\n\nb\r\nc\r\n\nd");
+
+        assert_eq!(
+            line_break_kind(&token(&root, "\n\n")),
+            Some(LineBreakKind::Unix)
+        );
+        assert_eq!(
+            line_break_kind(&token(&root, "\r\n")),
+            Some(LineBreakKind::Windows)
+        );
+        assert_eq!(
+            line_break_kind(&token(&root, "\r\n\n")),
+            Some(LineBreakKind::Mixed)
+        );
+        assert_eq!(line_break_kind(&token(&root, "a")), None);
+        assert_eq!(line_break_count(&token(&root, "\r\n\n")), 2);
+        assert_eq!(
+            line_endings("\n\r\n").collect::<Vec<_>>(),
+            vec![(0, "\n"), (1, "\r\n")]
+        );
+    }
+}
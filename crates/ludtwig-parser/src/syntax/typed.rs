@@ -5,8 +5,13 @@ use rowan::NodeOrToken;
 
 use crate::T;
 
+use super::trivia::{leading_trivia, line_break_count};
 use super::untyped::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, TemplateLanguage};
 
+pub use visitor::{walk, VisitControl, Visitor};
+
+mod visitor;
+
 /// So far, we've been working with a homogeneous untyped tree.
 /// It's nice to provide generic tree operations, like traversals,
 /// but it's a bad fit for semantic analysis.
@@ -100,6 +105,12 @@ impl TwigStartingBlock {
 
 ast_node!(TwigEndingBlock, SyntaxKind::TWIG_ENDING_BLOCK);
 impl TwigEndingBlock {
+    /// Optional name of the twig block behind endblock, like `a` in `{% endblock a %}`
+    #[must_use]
+    pub fn name(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![word])
+    }
+
     /// Parent complete twig block
     #[must_use]
     pub fn twig_block(&self) -> Option<TwigBlock> {
@@ -175,20 +186,83 @@ impl HtmlStartingTag {
     }
 }
 
+ast_node!(HtmlXmlProlog, SyntaxKind::HTML_XML_PROLOG);
+impl HtmlXmlProlog {
+    /// Name of the processing instruction, like `xml` or `xml-stylesheet`
+    #[must_use]
+    pub fn name(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![word])
+    }
+
+    /// Attributes of the declaration, like `version` or `encoding`
+    #[must_use]
+    pub fn attributes(&self) -> AstChildren<HtmlAttribute> {
+        match support::child::<HtmlAttributeList>(&self.syntax) {
+            Some(list) => support::children(&list.syntax),
+            None => support::children(&self.syntax),
+        }
+    }
+}
+
 ast_node!(HtmlAttribute, SyntaxKind::HTML_ATTRIBUTE);
 impl HtmlAttribute {
     /// Name of the attribute (left side of the equal sign)
+    /// this is `None` for vue directives, see `vue_directive`
     #[must_use]
     pub fn name(&self) -> Option<SyntaxToken> {
         support::token(&self.syntax, T![word])
     }
 
-    /// Value of the attribute
+    /// Vue directive in place of the plain name, like `v-on:click.prevent` or `@click`
     #[must_use]
-    pub fn value(&self) -> Option<HtmlString> {
+    pub fn vue_directive(&self) -> Option<VueDirective> {
+        support::child(&self.syntax)
+    }
+
+    /// Value of the attribute as written (with or without quotes)
+    #[must_use]
+    pub fn html_string(&self) -> Option<HtmlString> {
         support::child(&self.syntax)
     }
 
+    /// Content of the value (without quotes), split into text and twig syntax like `{{ name }}`.
+    /// Html entities like `&amp;` are not decoded.
+    #[must_use]
+    pub fn value(&self) -> HtmlAttributeValue {
+        let Some(inner) = self.html_string().and_then(|s| s.get_inner()) else {
+            return match self.html_string() {
+                Some(_) => HtmlAttributeValue::Static(String::new()),
+                None => HtmlAttributeValue::Missing,
+            };
+        };
+
+        let mut segments = vec![];
+        let mut text = String::new();
+        for element in inner.syntax().children_with_tokens() {
+            match element {
+                NodeOrToken::Token(t) => text.push_str(t.text()),
+                NodeOrToken::Node(n) => {
+                    // leading whitespace of twig syntax is part of the text
+                    for trivia in leading_trivia(&n) {
+                        text.push_str(trivia.text());
+                    }
+                    if !text.is_empty() {
+                        segments.push(HtmlAttributeValueSegment::Text(std::mem::take(&mut text)));
+                    }
+                    segments.push(HtmlAttributeValueSegment::Twig(n));
+                }
+            }
+        }
+
+        if segments.is_empty() {
+            return HtmlAttributeValue::Static(text);
+        }
+        if !text.is_empty() {
+            segments.push(HtmlAttributeValueSegment::Text(text));
+        }
+        HtmlAttributeValue::Mixed(segments)
+    }
+
     /// Parent starting html tag
     #[must_use]
     pub fn html_tag(&self) -> Option<HtmlStartingTag> {
@@ -199,6 +273,108 @@ impl HtmlAttribute {
     }
 }
 
+/// Content of an html attribute value, see `HtmlAttribute::value`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HtmlAttributeValue {
+    /// Attribute without value like `disabled`
+    Missing,
+    /// Value without twig syntax like `class="a b"` or `href=/home`
+    Static(String),
+    /// Value with twig syntax like `class="a {{ b }}"`
+    Mixed(Vec<HtmlAttributeValueSegment>),
+}
+
+impl HtmlAttributeValue {
+    /// Text of the value, if it is known before rendering (`Some("")` for missing values)
+    #[must_use]
+    pub fn static_text(&self) -> Option<&str> {
+        match self {
+            HtmlAttributeValue::Missing => Some(""),
+            HtmlAttributeValue::Static(text) => Some(text),
+            HtmlAttributeValue::Mixed(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HtmlAttributeValueSegment {
+    /// Plain text including whitespace
+    Text(String),
+    /// Twig syntax like `{{ name }}`, `{% if a %}...{% endif %}` or `{# comment #}`
+    Twig(SyntaxNode),
+}
+
+ast_node!(VueDirective, SyntaxKind::VUE_DIRECTIVE);
+impl VueDirective {
+    /// Shorthand prefix (`:`, `@` or `#`) or the full directive name like `v-on`
+    #[must_use]
+    pub fn prefix(&self) -> Option<SyntaxToken> {
+        self.syntax
+            .children_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .find(|t| !t.kind().is_trivia())
+    }
+
+    /// Full name of the directive with resolved shorthands, like `v-bind` for `:`
+    #[must_use]
+    pub fn get_directive_name(&self) -> Option<String> {
+        let prefix = self.prefix()?;
+        let name = match prefix.kind() {
+            T![":"] => "v-bind",
+            T!["@"] => "v-on",
+            T!["#"] => "v-slot",
+            _ => prefix.text(),
+        };
+
+        Some(name.to_string())
+    }
+
+    /// Argument of the directive, like `click` in `v-on:click`
+    #[must_use]
+    pub fn argument(&self) -> Option<VueDirectiveArgument> {
+        support::child(&self.syntax)
+    }
+
+    /// Modifiers of the directive, like `.prevent` in `@click.prevent`
+    #[must_use]
+    pub fn modifiers(&self) -> AstChildren<VueDirectiveModifier> {
+        support::children(&self.syntax)
+    }
+
+    /// Value of the directive (right side of the equal sign)
+    #[must_use]
+    pub fn value(&self) -> Option<HtmlString> {
+        self.html_attribute()?.html_string()
+    }
+
+    /// Parent html attribute
+    #[must_use]
+    pub fn html_attribute(&self) -> Option<HtmlAttribute> {
+        match self.syntax.parent() {
+            Some(p) => HtmlAttribute::cast(p),
+            None => None,
+        }
+    }
+}
+
+ast_node!(VueDirectiveArgument, SyntaxKind::VUE_DIRECTIVE_ARGUMENT);
+impl VueDirectiveArgument {
+    /// Is this a dynamic argument like `[key]`
+    #[must_use]
+    pub fn is_dynamic(&self) -> bool {
+        support::token(&self.syntax, T!["["]).is_some()
+    }
+}
+
+ast_node!(VueDirectiveModifier, SyntaxKind::VUE_DIRECTIVE_MODIFIER);
+impl VueDirectiveModifier {
+    /// Name of the modifier without the leading dot
+    #[must_use]
+    pub fn name(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![word])
+    }
+}
+
 ast_node!(HtmlEndingTag, SyntaxKind::HTML_ENDING_TAG);
 impl HtmlEndingTag {
     /// Parent complete html tag
@@ -323,6 +499,94 @@ impl TwigLiteralString {
                 _ => None,
             })
     }
+
+    /// Content of the string like twig evaluates it (escape sequences like `\n` or `\'` are resolved)
+    #[must_use]
+    pub fn value(&self) -> TwigStringValue {
+        let mut value = TwigStringValue {
+            text: String::new(),
+            has_interpolation: false,
+        };
+        let Some(inner) = self.get_inner() else {
+            return value;
+        };
+
+        // escape sequences can span multiple tokens (like `\` followed by a word)
+        let mut raw = String::new();
+        for element in inner.syntax().children_with_tokens() {
+            match element {
+                NodeOrToken::Token(t) => raw.push_str(t.text()),
+                NodeOrToken::Node(n) => {
+                    value.text.push_str(&unescape(&std::mem::take(&mut raw)));
+                    value.text.push_str(&n.text().to_string());
+                    value.has_interpolation = true;
+                }
+            }
+        }
+        value.text.push_str(&unescape(&raw));
+
+        value
+    }
+}
+
+/// Resolve escape sequences like PHP's `stripcslashes`, which twig uses for string literals
+fn unescape(raw: &str) -> String {
+    let mut text = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+
+        let Some(escaped) = chars.next() else {
+            break;
+        };
+        match escaped {
+            'n' => text.push('\n'),
+            't' => text.push('\t'),
+            'r' => text.push('\r'),
+            'v' => text.push('\u{0B}'),
+            'f' => text.push('\u{0C}'),
+            'a' => text.push('\u{07}'),
+            'b' => text.push('\u{08}'),
+            'x' if chars.peek().is_some_and(char::is_ascii_hexdigit) => {
+                let mut code = 0;
+                for _ in 0..2 {
+                    match chars.peek().and_then(|c| c.to_digit(16)) {
+                        Some(digit) => code = code * 16 + digit,
+                        None => break,
+                    }
+                    chars.next();
+                }
+                text.extend(char::from_u32(code));
+            }
+            '0'..='7' => {
+                let mut code = escaped.to_digit(8).unwrap_or_default();
+                for _ in 0..2 {
+                    match chars.peek().and_then(|c| c.to_digit(8)) {
+                        Some(digit) => code = code * 8 + digit,
+                        None => break,
+                    }
+                    chars.next();
+                }
+                text.extend(char::from_u32(code & 0xFF));
+            }
+            _ => text.push(escaped),
+        }
+    }
+
+    text
+}
+
+/// Content of a string literal, see `TwigLiteralString::value`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TwigStringValue {
+    /// Text without quotes and with resolved escape sequences,
+    /// interpolations like `#{name}` are kept as they are written
+    pub text: String,
+    /// Does the string contain interpolations, which means the text is only known at runtime
+    pub has_interpolation: bool,
 }
 
 ast_node!(
@@ -390,42 +654,459 @@ impl TwigExtends {
 
 ast_node!(Body, SyntaxKind::BODY);
 ast_node!(TwigVar, SyntaxKind::TWIG_VAR);
+impl TwigVar {
+    #[must_use]
+    pub fn expression(&self) -> Option<TwigExpression> {
+        support::child(&self.syntax)
+    }
+}
+
 ast_node!(TwigExpression, SyntaxKind::TWIG_EXPRESSION);
 ast_node!(TwigUnaryExpression, SyntaxKind::TWIG_UNARY_EXPRESSION);
+impl TwigUnaryExpression {
+    /// Operator in front of the expression, like `not` or `-`
+    #[must_use]
+    pub fn operator(&self) -> Option<SyntaxToken> {
+        self.syntax
+            .children_with_tokens()
+            .find_map(|element| match element {
+                SyntaxElement::Token(t) if !t.kind().is_trivia() => Some(t),
+                _ => None,
+            })
+    }
+
+    #[must_use]
+    pub fn expression(&self) -> Option<TwigExpression> {
+        support::child(&self.syntax)
+    }
+}
+
 ast_node!(
     TwigParenthesesExpression,
     SyntaxKind::TWIG_PARENTHESES_EXPRESSION
 );
+impl TwigParenthesesExpression {
+    #[must_use]
+    pub fn expression(&self) -> Option<TwigExpression> {
+        support::child(&self.syntax)
+    }
+}
+
 ast_node!(
     TwigConditionalExpression,
     SyntaxKind::TWIG_CONDITIONAL_EXPRESSION
 );
+impl TwigConditionalExpression {
+    /// Condition in front of the `?`
+    #[must_use]
+    pub fn condition(&self) -> Option<TwigExpression> {
+        self.expression_after(None)
+    }
+
+    /// Expression between `?` and `:`, which is missing for the shorthand `a ?: b`
+    #[must_use]
+    pub fn truthy_expression(&self) -> Option<TwigExpression> {
+        self.expression_after(Some(T!["?"]))
+    }
+
+    /// Expression after the `:`, which is missing for the shorthand `a ? b`
+    #[must_use]
+    pub fn falsy_expression(&self) -> Option<TwigExpression> {
+        self.expression_after(Some(T![":"]))
+    }
+
+    /// Expression which directly follows the operator token (or is the first child without an operator)
+    fn expression_after(&self, operator: Option<SyntaxKind>) -> Option<TwigExpression> {
+        let mut last_operator = None;
+        for element in self.syntax.children_with_tokens() {
+            match element {
+                SyntaxElement::Token(t) if matches!(t.kind(), T!["?"] | T![":"]) => {
+                    last_operator = Some(t.kind());
+                }
+                SyntaxElement::Node(n) if last_operator == operator => {
+                    return TwigExpression::cast(n);
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
+}
+
 ast_node!(TwigOperand, SyntaxKind::TWIG_OPERAND);
 ast_node!(TwigAccessor, SyntaxKind::TWIG_ACCESSOR);
+impl TwigAccessor {
+    /// Operand on the left side of the dot
+    #[must_use]
+    pub fn operand(&self) -> Option<TwigOperand> {
+        support::child(&self.syntax)
+    }
+
+    /// Operand on the right side of the dot
+    #[must_use]
+    pub fn property(&self) -> Option<TwigOperand> {
+        support::children(&self.syntax).nth(1)
+    }
+
+    /// Name of the accessed property, like `name` in `product.name`
+    #[must_use]
+    pub fn property_name(&self) -> Option<SyntaxToken> {
+        let literal_name = support::child::<TwigLiteralName>(&self.property()?.syntax)?;
+        literal_name.name()
+    }
+}
+
 ast_node!(TwigFilter, SyntaxKind::TWIG_FILTER);
+impl TwigFilter {
+    /// Operand on the left side of the pipe, which is passed into the filter
+    #[must_use]
+    pub fn operand(&self) -> Option<TwigOperand> {
+        support::child(&self.syntax)
+    }
+
+    /// Name of the applied filter, like `upper` in `name|upper`
+    #[must_use]
+    pub fn name(&self) -> Option<SyntaxToken> {
+        let filter_operand = self.filter_operand()?;
+        let literal_name = support::child::<TwigLiteralName>(&filter_operand.syntax)?;
+        support::token(&literal_name.syntax, T![word])
+    }
+
+    /// Arguments of the filter, like `', '` in `list|join(', ')`
+    #[must_use]
+    pub fn arguments(&self) -> Option<TwigArguments> {
+        support::child(&self.filter_operand()?.syntax)
+    }
+
+    /// Operand on the right side of the pipe, which contains the filter name and arguments
+    fn filter_operand(&self) -> Option<TwigOperand> {
+        support::children::<TwigOperand>(&self.syntax).nth(1)
+    }
+}
+
 ast_node!(TwigIndexLookup, SyntaxKind::TWIG_INDEX_LOOKUP);
+impl TwigIndexLookup {
+    /// Operand in front of the square brackets
+    #[must_use]
+    pub fn operand(&self) -> Option<TwigOperand> {
+        support::child(&self.syntax)
+    }
+
+    /// Single index like `[1]`, which is missing for ranges like `[1:2]`
+    #[must_use]
+    pub fn index(&self) -> Option<TwigIndex> {
+        support::child(&self.syntax)
+    }
+
+    /// Range like `[1:2]`, which is missing for single indices
+    #[must_use]
+    pub fn index_range(&self) -> Option<TwigIndexRange> {
+        support::child(&self.syntax)
+    }
+}
+
 ast_node!(TwigIndex, SyntaxKind::TWIG_INDEX);
+impl TwigIndex {
+    #[must_use]
+    pub fn expression(&self) -> Option<TwigExpression> {
+        support::child(&self.syntax)
+    }
+}
+
 ast_node!(TwigIndexRange, SyntaxKind::TWIG_INDEX_RANGE);
+impl TwigIndexRange {
+    /// Start of the range, which is missing for `[:2]`
+    #[must_use]
+    pub fn lower_bound(&self) -> Option<TwigExpression> {
+        self.bound(false)
+    }
+
+    /// End of the range, which is missing for `[1:]`
+    #[must_use]
+    pub fn upper_bound(&self) -> Option<TwigExpression> {
+        self.bound(true)
+    }
+
+    fn bound(&self, after_colon: bool) -> Option<TwigExpression> {
+        let mut is_after_colon = false;
+        for element in self.syntax.children_with_tokens() {
+            match element {
+                SyntaxElement::Token(t) if t.kind() == T![":"] => is_after_colon = true,
+                SyntaxElement::Node(n) if is_after_colon == after_colon => {
+                    return TwigExpression::cast(n);
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
+}
+
 ast_node!(TwigFunctionCall, SyntaxKind::TWIG_FUNCTION_CALL);
+impl TwigFunctionCall {
+    /// Operand in front of the parentheses, like `path` or `product.getName`
+    #[must_use]
+    pub fn callee(&self) -> Option<TwigOperand> {
+        support::child(&self.syntax)
+    }
+
+    /// Name of the called function, if it is a plain name like `path(...)`
+    #[must_use]
+    pub fn name(&self) -> Option<SyntaxToken> {
+        let operand = support::child::<TwigOperand>(&self.syntax)?;
+        let literal_name = support::child::<TwigLiteralName>(&operand.syntax)?;
+        support::token(&literal_name.syntax, T![word])
+    }
+
+    #[must_use]
+    pub fn arguments(&self) -> Option<TwigArguments> {
+        support::child(&self.syntax)
+    }
+}
+
 ast_node!(TwigArguments, SyntaxKind::TWIG_ARGUMENTS);
+impl TwigArguments {
+    /// Positional arguments (without named arguments)
+    #[must_use]
+    pub fn positional(&self) -> AstChildren<TwigExpression> {
+        support::children(&self.syntax)
+    }
+
+    #[must_use]
+    pub fn named(&self) -> AstChildren<TwigNamedArgument> {
+        support::children(&self.syntax)
+    }
+}
+
 ast_node!(TwigNamedArgument, SyntaxKind::TWIG_NAMED_ARGUMENT);
+impl TwigNamedArgument {
+    /// Name of the argument, like `name` in `name = 'value'`
+    #[must_use]
+    pub fn name(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![word])
+    }
+
+    #[must_use]
+    pub fn value(&self) -> Option<TwigExpression> {
+        support::child(&self.syntax)
+    }
+}
+
+ast_node!(TwigArrowFunction, SyntaxKind::TWIG_ARROW_FUNCTION);
+impl TwigArrowFunction {
+    /// Parameters of the arrow function, like `v` and `k` in `(v, k) => v * k`
+    #[must_use]
+    pub fn parameters(&self) -> AstChildren<TwigLiteralName> {
+        support::children(&self.syntax)
+    }
+
+    /// Expression which is returned by the arrow function
+    #[must_use]
+    pub fn body(&self) -> Option<TwigExpression> {
+        support::child(&self.syntax)
+    }
+}
+
+ast_node!(TwigSpread, SyntaxKind::TWIG_SPREAD);
+impl TwigSpread {
+    #[must_use]
+    pub fn expression(&self) -> Option<TwigExpression> {
+        support::child(&self.syntax)
+    }
+}
 
 ast_node!(
     TwigLiteralStringInterpolation,
     SyntaxKind::TWIG_LITERAL_STRING_INTERPOLATION
 );
+impl TwigLiteralStringInterpolation {
+    #[must_use]
+    pub fn expression(&self) -> Option<TwigExpression> {
+        support::child(&self.syntax)
+    }
+}
+
 ast_node!(TwigLiteralNumber, SyntaxKind::TWIG_LITERAL_NUMBER);
+impl TwigLiteralNumber {
+    #[must_use]
+    pub fn number(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![number])
+    }
+}
+
 ast_node!(TwigLiteralArray, SyntaxKind::TWIG_LITERAL_ARRAY);
+impl TwigLiteralArray {
+    #[must_use]
+    pub fn items(&self) -> AstChildren<TwigExpression> {
+        match support::child::<TwigLiteralArrayInner>(&self.syntax) {
+            Some(inner) => support::children(&inner.syntax),
+            // create an iterator for TwigExpression over the array itself, which should yield no results
+            None => support::children(&self.syntax),
+        }
+    }
+}
+
 ast_node!(TwigLiteralArrayInner, SyntaxKind::TWIG_LITERAL_ARRAY_INNER);
 ast_node!(TwigLiteralNull, SyntaxKind::TWIG_LITERAL_NULL);
 ast_node!(TwigLiteralBoolean, SyntaxKind::TWIG_LITERAL_BOOLEAN);
+impl TwigLiteralBoolean {
+    #[must_use]
+    pub fn value(&self) -> Option<bool> {
+        self.syntax
+            .children_with_tokens()
+            .find_map(|element| match element {
+                SyntaxElement::Token(t) if t.kind() == T!["true"] => Some(true),
+                SyntaxElement::Token(t) if t.kind() == T!["false"] => Some(false),
+                _ => None,
+            })
+    }
+}
+
 ast_node!(TwigLiteralHash, SyntaxKind::TWIG_LITERAL_HASH);
+impl TwigLiteralHash {
+    #[must_use]
+    pub fn pairs(&self) -> AstChildren<TwigLiteralHashPair> {
+        match support::child::<TwigLiteralHashItems>(&self.syntax) {
+            Some(items) => support::children(&items.syntax),
+            // create an iterator for TwigLiteralHashPair over the hash itself, which should yield no results
+            None => support::children(&self.syntax),
+        }
+    }
+}
+
 ast_node!(TwigLiteralHashItems, SyntaxKind::TWIG_LITERAL_HASH_ITEMS);
 ast_node!(TwigLiteralHashPair, SyntaxKind::TWIG_LITERAL_HASH_PAIR);
+impl TwigLiteralHashPair {
+    #[must_use]
+    pub fn key(&self) -> Option<TwigLiteralHashKey> {
+        support::child(&self.syntax)
+    }
+
+    #[must_use]
+    pub fn value(&self) -> Option<TwigExpression> {
+        support::child(&self.syntax)
+    }
+}
+
 ast_node!(TwigLiteralHashKey, SyntaxKind::TWIG_LITERAL_HASH_KEY);
+impl TwigLiteralHashKey {
+    /// Name of the key without quotes, if it is a plain word, number or string without interpolation
+    #[must_use]
+    pub fn get_key_name(&self) -> Option<String> {
+        if let Some(string) = support::child::<TwigLiteralString>(&self.syntax) {
+            let inner = string.get_inner()?;
+            if inner.get_interpolations().next().is_some() {
+                return None;
+            }
+
+            return Some(inner.syntax.text().to_string());
+        }
+        if let Some(number) = support::child::<TwigLiteralNumber>(&self.syntax) {
+            return Some(number.number()?.text().to_string());
+        }
+
+        self.syntax
+            .children_with_tokens()
+            .find_map(|element| match element {
+                NodeOrToken::Token(t) if matches!(t.kind(), T![word] | T![number]) => {
+                    Some(t.text().to_string())
+                }
+                _ => None,
+            })
+    }
+}
+
 ast_node!(TwigLiteralHashValue, SyntaxKind::TWIG_LITERAL_HASH_VALUE);
 ast_node!(TwigLiteralName, SyntaxKind::TWIG_LITERAL_NAME);
+impl TwigLiteralName {
+    #[must_use]
+    pub fn name(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![word])
+    }
+}
+
 ast_node!(TwigComment, SyntaxKind::TWIG_COMMENT);
+impl TwigComment {
+    /// Text between the delimiters without surrounding whitespace, like `some text` in `{# some text #}`
+    #[must_use]
+    pub fn content(&self) -> String {
+        let content: String = self
+            .syntax
+            .children_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .skip_while(|t| t.kind() != T!["{#"])
+            .skip(1)
+            .take_while(|t| t.kind() != T!["#}"])
+            .map(|t| t.text().to_string())
+            .collect();
+
+        content.trim().to_string()
+    }
+
+    /// Annotations in the comment (one per line), like `{# @var product \Shopware\...\SalesChannelProductEntity #}`
+    #[must_use]
+    pub fn annotations(&self) -> Vec<TwigAnnotationComment> {
+        self.content()
+            .lines()
+            .filter_map(TwigAnnotationComment::parse)
+            .collect()
+    }
+}
+
+/// Annotation in a twig comment, see `TwigComment::annotations`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TwigAnnotationComment {
+    /// Name of the annotation without the `@`, like `var`
+    pub tag: String,
+    /// Text behind the tag, like `product \Shopware\...\SalesChannelProductEntity`
+    pub arguments: String,
+}
+
+impl TwigAnnotationComment {
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim().strip_prefix('@')?;
+        let (tag, arguments) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        if tag.is_empty()
+            || !tag
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        {
+            return None;
+        }
+
+        Some(Self {
+            tag: tag.to_string(),
+            arguments: arguments.trim().to_string(),
+        })
+    }
+
+    /// Variable name and declared type of a `@var` annotation.
+    /// Both `@var product \Foo\Product` and `@var \Foo\Product product` are supported.
+    #[must_use]
+    pub fn variable_type(&self) -> Option<(&str, &str)> {
+        if self.tag != "var" {
+            return None;
+        }
+
+        let (first, rest) = self.arguments.split_once(char::is_whitespace)?;
+        let first_is_type = first.contains('\\') || first.ends_with("[]");
+        let (variable, type_name) = if first_is_type {
+            let (type_name, variable) = self.arguments.rsplit_once(char::is_whitespace)?;
+            (variable, type_name.trim())
+        } else {
+            (first, rest.trim())
+        };
+
+        let variable = variable.strip_prefix('$').unwrap_or(variable);
+        if variable.is_empty() || type_name.is_empty() {
+            return None;
+        }
+
+        Some((variable, type_name))
+    }
+}
 ast_node!(TwigIf, SyntaxKind::TWIG_IF);
 ast_node!(TwigIfBlock, SyntaxKind::TWIG_IF_BLOCK);
 ast_node!(TwigElseIfBlock, SyntaxKind::TWIG_ELSE_IF_BLOCK);
@@ -442,7 +1123,35 @@ ast_node!(TwigEndForBlock, SyntaxKind::TWIG_ENDFOR_BLOCK);
 ast_node!(TwigInclude, SyntaxKind::TWIG_INCLUDE);
 ast_node!(TwigIncludeWith, SyntaxKind::TWIG_INCLUDE_WITH);
 ast_node!(TwigUse, SyntaxKind::TWIG_USE);
+impl TwigUse {
+    /// Name of the used template, like `"blocks.html.twig"` in `{% use "blocks.html.twig" %}`
+    #[must_use]
+    pub fn template(&self) -> Option<TwigLiteralString> {
+        support::child(&self.syntax)
+    }
+
+    /// Block aliases behind `with`, like `a as b` in `{% use "blocks.html.twig" with a as b %}`
+    #[must_use]
+    pub fn overrides(&self) -> AstChildren<TwigOverride> {
+        support::children(&self.syntax)
+    }
+}
+
 ast_node!(TwigOverride, SyntaxKind::TWIG_OVERRIDE);
+impl TwigOverride {
+    /// Original name on the left side of `as`
+    #[must_use]
+    pub fn name(&self) -> Option<TwigLiteralName> {
+        support::child(&self.syntax)
+    }
+
+    /// New name on the right side of `as`
+    #[must_use]
+    pub fn alias(&self) -> Option<TwigLiteralName> {
+        support::children(&self.syntax).nth(1)
+    }
+}
+
 ast_node!(TwigApply, SyntaxKind::TWIG_APPLY);
 ast_node!(
     TwigApplyStartingBlock,
@@ -450,10 +1159,41 @@ ast_node!(
 );
 ast_node!(TwigApplyEndingBlock, SyntaxKind::TWIG_APPLY_ENDING_BLOCK);
 ast_node!(TwigAutoescape, SyntaxKind::TWIG_AUTOESCAPE);
+impl TwigAutoescape {
+    #[must_use]
+    pub fn starting_block(&self) -> Option<TwigAutoescapeStartingBlock> {
+        support::child(&self.syntax)
+    }
+
+    #[must_use]
+    pub fn body(&self) -> Option<Body> {
+        support::child(&self.syntax)
+    }
+
+    #[must_use]
+    pub fn ending_block(&self) -> Option<TwigAutoescapeEndingBlock> {
+        support::child(&self.syntax)
+    }
+}
+
 ast_node!(
     TwigAutoescapeStartingBlock,
     SyntaxKind::TWIG_AUTOESCAPE_STARTING_BLOCK
 );
+impl TwigAutoescapeStartingBlock {
+    /// The `false` keyword, if this block disables autoescaping
+    #[must_use]
+    pub fn disabled_keyword(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T!["false"])
+    }
+
+    /// The escape strategy string, like `'html'` or `'js'`
+    #[must_use]
+    pub fn escape_strategy(&self) -> Option<TwigLiteralString> {
+        support::child(&self.syntax)
+    }
+}
+
 ast_node!(
     TwigAutoescapeEndingBlock,
     SyntaxKind::TWIG_AUTOESCAPE_ENDING_BLOCK
@@ -478,6 +1218,68 @@ ast_node!(
     TwigSandboxEndingBlock,
     SyntaxKind::TWIG_SANDBOX_ENDING_BLOCK
 );
+ast_node!(TwigGuard, SyntaxKind::TWIG_GUARD);
+impl TwigGuard {
+    #[must_use]
+    pub fn starting_block(&self) -> Option<TwigGuardStartingBlock> {
+        support::child(&self.syntax)
+    }
+
+    /// Rendered if the guarded construct exists
+    #[must_use]
+    pub fn body(&self) -> Option<Body> {
+        support::child(&self.syntax)
+    }
+
+    #[must_use]
+    pub fn else_block(&self) -> Option<TwigGuardElseBlock> {
+        support::child(&self.syntax)
+    }
+
+    /// Rendered if the guarded construct doesn't exist
+    #[must_use]
+    pub fn else_body(&self) -> Option<Body> {
+        self.else_block()?
+            .syntax()
+            .next_sibling()
+            .and_then(Body::cast)
+    }
+
+    #[must_use]
+    pub fn ending_block(&self) -> Option<TwigGuardEndingBlock> {
+        support::child(&self.syntax)
+    }
+}
+
+ast_node!(
+    TwigGuardStartingBlock,
+    SyntaxKind::TWIG_GUARD_STARTING_BLOCK
+);
+impl TwigGuardStartingBlock {
+    /// Type of the guarded construct, which is `function`, `filter` or `test`
+    #[must_use]
+    pub fn construct_type(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![word])
+    }
+
+    /// Name of the guarded construct, like `importmap` in `{% guard function importmap %}`
+    #[must_use]
+    pub fn name(&self) -> Option<TwigLiteralName> {
+        support::child(&self.syntax)
+    }
+}
+
+ast_node!(TwigGuardElseBlock, SyntaxKind::TWIG_GUARD_ELSE_BLOCK);
+ast_node!(TwigGuardEndingBlock, SyntaxKind::TWIG_GUARD_ENDING_BLOCK);
+ast_node!(TwigSpaceless, SyntaxKind::TWIG_SPACELESS);
+ast_node!(
+    TwigSpacelessStartingBlock,
+    SyntaxKind::TWIG_SPACELESS_STARTING_BLOCK
+);
+ast_node!(
+    TwigSpacelessEndingBlock,
+    SyntaxKind::TWIG_SPACELESS_ENDING_BLOCK
+);
 ast_node!(TwigVerbatim, SyntaxKind::TWIG_VERBATIM);
 ast_node!(
     TwigVerbatimStartingBlock,
@@ -497,13 +1299,143 @@ ast_node!(TwigWith, SyntaxKind::TWIG_WITH);
 ast_node!(TwigWithStartingBlock, SyntaxKind::TWIG_WITH_STARTING_BLOCK);
 ast_node!(TwigWithEndingBlock, SyntaxKind::TWIG_WITH_ENDING_BLOCK);
 ast_node!(TwigCache, SyntaxKind::TWIG_CACHE);
+impl TwigCache {
+    #[must_use]
+    pub fn starting_block(&self) -> Option<TwigCacheStartingBlock> {
+        support::child(&self.syntax)
+    }
+
+    #[must_use]
+    pub fn body(&self) -> Option<Body> {
+        support::child(&self.syntax)
+    }
+
+    #[must_use]
+    pub fn ending_block(&self) -> Option<TwigCacheEndingBlock> {
+        support::child(&self.syntax)
+    }
+}
+
 ast_node!(TwigCacheTTL, SyntaxKind::TWIG_CACHE_TTL);
+impl TwigCacheTTL {
+    /// Time to live in seconds, like `300` in `ttl(300)`
+    #[must_use]
+    pub fn expression(&self) -> Option<TwigExpression> {
+        support::child(&self.syntax)
+    }
+}
+
 ast_node!(TwigCacheTags, SyntaxKind::TWIG_CACHE_TAGS);
+impl TwigCacheTags {
+    /// Tags of the cache entry, like `['cms', 'blog']` in `tags(['cms', 'blog'])`
+    #[must_use]
+    pub fn expression(&self) -> Option<TwigExpression> {
+        support::child(&self.syntax)
+    }
+}
+
 ast_node!(
     TwigCacheStartingBlock,
     SyntaxKind::TWIG_CACHE_STARTING_BLOCK
 );
+impl TwigCacheStartingBlock {
+    /// Key of the cache entry, like `'products_' ~ category.id`
+    #[must_use]
+    pub fn key(&self) -> Option<TwigExpression> {
+        support::child(&self.syntax)
+    }
+
+    #[must_use]
+    pub fn ttl(&self) -> Option<TwigCacheTTL> {
+        support::child(&self.syntax)
+    }
+
+    #[must_use]
+    pub fn tags(&self) -> Option<TwigCacheTags> {
+        support::child(&self.syntax)
+    }
+}
+
 ast_node!(TwigCacheEndingBlock, SyntaxKind::TWIG_CACHE_ENDING_BLOCK);
+ast_node!(TwigTrans, SyntaxKind::TWIG_TRANS);
+impl TwigTrans {
+    #[must_use]
+    pub fn starting_block(&self) -> Option<TwigTransStartingBlock> {
+        support::child(&self.syntax)
+    }
+
+    /// The translated message, like `Hello %name%` (which can contain twig syntax)
+    #[must_use]
+    pub fn body(&self) -> Option<Body> {
+        support::child(&self.syntax)
+    }
+
+    #[must_use]
+    pub fn ending_block(&self) -> Option<TwigTransEndingBlock> {
+        support::child(&self.syntax)
+    }
+}
+
+ast_node!(
+    TwigTransStartingBlock,
+    SyntaxKind::TWIG_TRANS_STARTING_BLOCK
+);
+impl TwigTransStartingBlock {
+    /// The placeholder values, like `{'%name%': name}` in `with {'%name%': name}`
+    #[must_use]
+    pub fn variables(&self) -> Option<TwigExpression> {
+        support::child::<TwigTransWith>(&self.syntax)?.expression()
+    }
+
+    /// The translation domain, like `'app'` in `from 'app'`
+    #[must_use]
+    pub fn domain(&self) -> Option<TwigExpression> {
+        support::child::<TwigTransFrom>(&self.syntax)?.expression()
+    }
+
+    /// The locale, like `'de'` in `into 'de'`
+    #[must_use]
+    pub fn locale(&self) -> Option<TwigExpression> {
+        support::child::<TwigTransInto>(&self.syntax)?.expression()
+    }
+}
+
+ast_node!(TwigTransEndingBlock, SyntaxKind::TWIG_TRANS_ENDING_BLOCK);
+ast_node!(TwigTransWith, SyntaxKind::TWIG_TRANS_WITH);
+impl TwigTransWith {
+    #[must_use]
+    pub fn expression(&self) -> Option<TwigExpression> {
+        support::child(&self.syntax)
+    }
+}
+
+ast_node!(TwigTransFrom, SyntaxKind::TWIG_TRANS_FROM);
+impl TwigTransFrom {
+    #[must_use]
+    pub fn expression(&self) -> Option<TwigExpression> {
+        support::child(&self.syntax)
+    }
+}
+
+ast_node!(TwigTransInto, SyntaxKind::TWIG_TRANS_INTO);
+impl TwigTransInto {
+    #[must_use]
+    pub fn expression(&self) -> Option<TwigExpression> {
+        support::child(&self.syntax)
+    }
+}
+
+ast_node!(
+    TwigTransDefaultDomain,
+    SyntaxKind::TWIG_TRANS_DEFAULT_DOMAIN
+);
+impl TwigTransDefaultDomain {
+    #[must_use]
+    pub fn domain(&self) -> Option<TwigExpression> {
+        support::child(&self.syntax)
+    }
+}
+
 ast_node!(ShopwareTwigExtends, SyntaxKind::SHOPWARE_TWIG_SW_EXTENDS);
 ast_node!(ShopwareTwigInclude, SyntaxKind::SHOPWARE_TWIG_SW_INCLUDE);
 ast_node!(
@@ -528,5 +1460,410 @@ ast_node!(HtmlAttributeList, SyntaxKind::HTML_ATTRIBUTE_LIST);
 ast_node!(HtmlStringInner, SyntaxKind::HTML_STRING_INNER);
 ast_node!(HtmlText, SyntaxKind::HTML_TEXT);
 ast_node!(HtmlComment, SyntaxKind::HTML_COMMENT);
+ast_node!(HtmlConditionalComment, SyntaxKind::HTML_CONDITIONAL_COMMENT);
+impl HtmlConditionalComment {
+    /// Content which is only rendered by the browsers matching the condition
+    #[must_use]
+    pub fn body(&self) -> Option<Body> {
+        support::child(&self.syntax)
+    }
+}
+ast_node!(HtmlCdata, SyntaxKind::HTML_CDATA);
 ast_node!(Error, SyntaxKind::ERROR);
 ast_node!(Root, SyntaxKind::ROOT);
+
+/// Comments which belong to a node, like `{# documentation #}` directly in front of a block.
+///
+/// Twig comments, html comments and ludtwig directives are considered. Comments in front of a node
+/// only belong to it if there is no empty line between them, comments after a node only belong to it
+/// if they are on the same line.
+pub trait AstNodeExt: AstNode<Language = TemplateLanguage> {
+    /// Comments directly in front of this node in the order of their appearance
+    fn leading_comments(&self) -> Vec<SyntaxNode> {
+        let mut comments = vec![];
+        let mut current = self.syntax().clone();
+
+        while !has_leading_empty_line(&current) {
+            match current.prev_sibling_or_token() {
+                Some(NodeOrToken::Node(prev)) if is_comment(&prev) => {
+                    comments.push(prev.clone());
+                    current = prev;
+                }
+                _ => break,
+            }
+        }
+
+        comments.reverse();
+        comments
+    }
+
+    /// Comments after this node on the same line in the order of their appearance
+    fn trailing_comments(&self) -> Vec<SyntaxNode> {
+        let mut comments = vec![];
+        let mut current = self.syntax().clone();
+
+        while let Some(NodeOrToken::Node(next)) = current.next_sibling_or_token() {
+            if !is_comment(&next) || leading_trivia(&next).iter().any(|t| t.kind() == T![lb]) {
+                break;
+            }
+            comments.push(next.clone());
+            current = next;
+        }
+
+        comments
+    }
+}
+
+impl<N: AstNode<Language = TemplateLanguage>> AstNodeExt for N {}
+
+fn is_comment(node: &SyntaxNode) -> bool {
+    matches!(
+        node.kind(),
+        SyntaxKind::TWIG_COMMENT
+            | SyntaxKind::HTML_COMMENT
+            | SyntaxKind::LUDTWIG_DIRECTIVE_FILE_IGNORE
+            | SyntaxKind::LUDTWIG_DIRECTIVE_IGNORE
+    )
+}
+
+fn has_leading_empty_line(node: &SyntaxNode) -> bool {
+    leading_trivia(node).iter().any(|t| line_break_count(t) > 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// First node of the given type in the parsed source
+    fn first<N: AstNode<Language = TemplateLanguage>>(source: &str) -> N {
+        SyntaxNode::new_root(crate::parse(source).green_node)
+            .descendants()
+            .find_map(N::cast)
+            .unwrap()
+    }
+
+    fn text<N: AstNode>(node: Option<N>) -> String {
+        node.map(|n| n.syntax().to_string().trim().to_string())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn typed_binary_and_unary_expressions() {
+        let binary: TwigBinaryExpression = first("{{ a and b or c }}");
+        assert_eq!(binary.operator().unwrap().text(), "or");
+        assert_eq!(text(binary.lhs_expression()), "a and b");
+        assert_eq!(text(binary.rhs_expression()), "c");
+
+        let unary: TwigUnaryExpression = first("{{ not (a) }}");
+        assert_eq!(unary.operator().unwrap().text(), "not");
+        let parentheses: TwigParenthesesExpression =
+            first::<TwigParenthesesExpression>("{{ not (a) }}");
+        assert_eq!(text(parentheses.expression()), "a");
+        assert_eq!(text(unary.expression()), "(a)");
+
+        let var: TwigVar = first("{{ -1 }}");
+        assert_eq!(text(var.expression()), "-1");
+    }
+
+    #[test]
+    fn typed_conditional_expressions() {
+        let full: TwigConditionalExpression = first("{{ a ? b : c }}");
+        assert_eq!(text(full.condition()), "a");
+        assert_eq!(text(full.truthy_expression()), "b");
+        assert_eq!(text(full.falsy_expression()), "c");
+
+        let without_truthy: TwigConditionalExpression = first("{{ a ?: c }}");
+        assert_eq!(text(without_truthy.condition()), "a");
+        assert!(without_truthy.truthy_expression().is_none());
+        assert_eq!(text(without_truthy.falsy_expression()), "c");
+
+        let without_falsy: TwigConditionalExpression = first("{{ a ? b }}");
+        assert_eq!(text(without_falsy.truthy_expression()), "b");
+        assert!(without_falsy.falsy_expression().is_none());
+    }
+
+    #[test]
+    fn typed_accessors_and_index_lookups() {
+        let accessor: TwigAccessor = first("{{ product.name }}");
+        assert_eq!(text(accessor.operand()), "product");
+        assert_eq!(text(accessor.property()), "name");
+        assert_eq!(accessor.property_name().unwrap().text(), "name");
+
+        let index: TwigIndexLookup = first("{{ items[key] }}");
+        assert_eq!(text(index.operand()), "items");
+        assert_eq!(text(index.index().and_then(|i| i.expression())), "key");
+        assert!(index.index_range().is_none());
+
+        let range: TwigIndexRange = first("{{ items[1:n] }}");
+        assert_eq!(text(range.lower_bound()), "1");
+        assert_eq!(text(range.upper_bound()), "n");
+        let range: TwigIndexRange = first("{{ items[:n] }}");
+        assert!(range.lower_bound().is_none());
+        assert_eq!(text(range.upper_bound()), "n");
+        let range: TwigIndexRange = first("{{ items[1:] }}");
+        assert_eq!(text(range.lower_bound()), "1");
+        assert!(range.upper_bound().is_none());
+    }
+
+    #[test]
+    fn typed_function_calls_and_filters() {
+        let call: TwigFunctionCall = first("{{ product.getName(1, short = true) }}");
+        assert_eq!(text(call.callee()), "product.getName");
+        assert!(call.name().is_none());
+        let arguments = call.arguments().unwrap();
+        assert_eq!(text(arguments.positional().next()), "1");
+        let named = arguments.named().next().unwrap();
+        assert_eq!(named.name().unwrap().text(), "short");
+        assert_eq!(text(named.value()), "true");
+
+        let filter: TwigFilter = first("{{ list|join(', ') }}");
+        assert_eq!(text(filter.operand()), "list");
+        assert_eq!(filter.name().unwrap().text(), "join");
+        assert_eq!(text(filter.arguments()), "', '");
+    }
+
+    #[test]
+    fn typed_trans_tags() {
+        let trans: TwigTrans =
+            first("{% trans with {'%name%': name} from 'app' into 'de' %}Hi %name%{% endtrans %}");
+        let starting_block = trans.starting_block().unwrap();
+        assert_eq!(text(starting_block.variables()), "{'%name%': name}");
+        assert_eq!(text(starting_block.domain()), "'app'");
+        assert_eq!(text(starting_block.locale()), "'de'");
+        assert_eq!(text(trans.body()), "Hi %name%");
+        assert!(trans.ending_block().is_some());
+
+        let default_domain: TwigTransDefaultDomain = first("{% trans_default_domain 'app' %}");
+        assert_eq!(text(default_domain.domain()), "'app'");
+    }
+
+    #[test]
+    fn typed_ending_block_name() {
+        let block: TwigBlock = first("{% block a %}{% endblock a %}");
+        assert_eq!(block.ending_block().unwrap().name().unwrap().text(), "a");
+
+        let block: TwigBlock = first("{% block a %}{% endblock %}");
+        assert!(block.ending_block().unwrap().name().is_none());
+    }
+
+    #[test]
+    fn typed_use_tag() {
+        let twig_use: TwigUse = first(
+            r#"{% use "blocks.html.twig" with sidebar as base_sidebar, title as base_title %}"#,
+        );
+        assert_eq!(text(twig_use.template()), r#""blocks.html.twig""#);
+        let overrides: Vec<(String, String)> = twig_use
+            .overrides()
+            .map(|o| (text(o.name()), text(o.alias())))
+            .collect();
+        assert_eq!(
+            overrides,
+            vec![
+                ("sidebar".to_string(), "base_sidebar".to_string()),
+                ("title".to_string(), "base_title".to_string())
+            ]
+        );
+
+        let twig_use: TwigUse = first("{% use 'blocks.html.twig' %}");
+        assert_eq!(twig_use.overrides().count(), 0);
+    }
+
+    #[test]
+    fn typed_html_attribute_value() {
+        let value = |source: &str| first::<HtmlAttribute>(source).value();
+
+        assert_eq!(value("<input disabled>"), HtmlAttributeValue::Missing);
+        assert_eq!(
+            value(r#"<input value="">"#),
+            HtmlAttributeValue::Static(String::new())
+        );
+        assert_eq!(
+            value("<a href=/foo?a=1>"),
+            HtmlAttributeValue::Static("/foo?a=1".to_string())
+        );
+        assert_eq!(value("<a href='/foo'>").static_text(), Some("/foo"));
+
+        let HtmlAttributeValue::Mixed(segments) =
+            value(r#"<div class="a {{ b }}{% if c %} c{% endif %}">"#)
+        else {
+            panic!("expected mixed value");
+        };
+        let segments: Vec<String> = segments
+            .iter()
+            .map(|segment| match segment {
+                HtmlAttributeValueSegment::Text(text) => format!("text:{text}"),
+                HtmlAttributeValueSegment::Twig(node) => format!("{:?}", node.kind()),
+            })
+            .collect();
+        assert_eq!(segments, vec!["text:a ", "TWIG_VAR", "TWIG_IF"]);
+    }
+
+    #[test]
+    fn typed_literal_string_value() {
+        let value = |source: &str| first::<TwigLiteralString>(source).value();
+
+        assert_eq!(value(r"{{ 'it\'s' }}").text, "it's");
+        assert_eq!(value(r#"{{ "a\tb\\c\x41\101\"" }}"#).text, "a\tb\\cAA\"");
+        assert_eq!(
+            value("{{ '' }}"),
+            TwigStringValue {
+                text: String::new(),
+                has_interpolation: false
+            }
+        );
+        assert_eq!(
+            value(r#"{{ "a\n#{ b }" }}"#),
+            TwigStringValue {
+                text: "a\n#{ b }".to_string(),
+                has_interpolation: true
+            }
+        );
+    }
+
+    #[test]
+    fn typed_guard_tag() {
+        let guard: TwigGuard =
+            first("{% guard function importmap %}{{ importmap('app') }}{% else %}-{% endguard %}");
+        let starting_block = guard.starting_block().unwrap();
+        assert_eq!(starting_block.construct_type().unwrap().text(), "function");
+        assert_eq!(text(starting_block.name()), "importmap");
+        assert_eq!(text(guard.body()), "{{ importmap('app') }}");
+        assert_eq!(text(guard.else_body()), "-");
+        assert!(guard.ending_block().is_some());
+
+        let guard: TwigGuard = first("{% guard test same as %}{% endguard %}");
+        let starting_block = guard.starting_block().unwrap();
+        assert_eq!(starting_block.construct_type().unwrap().text(), "test");
+        assert_eq!(text(starting_block.name()), "same as");
+        assert!(guard.else_block().is_none());
+        assert!(guard.else_body().is_none());
+    }
+
+    #[test]
+    fn typed_cache_tag() {
+        let cache: TwigCache =
+            first("{% cache 'list_' ~ page ttl(300) tags(['cms']) %}{{ list }}{% endcache %}");
+        let starting_block = cache.starting_block().unwrap();
+        assert_eq!(text(starting_block.key()), "'list_' ~ page");
+        assert_eq!(
+            text(starting_block.ttl().and_then(|t| t.expression())),
+            "300"
+        );
+        assert_eq!(
+            text(starting_block.tags().and_then(|t| t.expression())),
+            "['cms']"
+        );
+        assert_eq!(text(cache.body()), "{{ list }}");
+        assert!(cache.ending_block().is_some());
+    }
+
+    #[test]
+    fn typed_arrow_functions_and_spreads() {
+        let arrow: TwigArrowFunction = first("{{ list|map((v, k) => v * k) }}");
+        let parameters: Vec<_> = arrow
+            .parameters()
+            .filter_map(|p| p.name())
+            .map(|t| t.text().to_string())
+            .collect();
+        assert_eq!(parameters, vec!["v", "k"]);
+        assert_eq!(text(arrow.body()), "v * k");
+
+        let spread: TwigSpread = first("{{ [...a, b] }}");
+        assert_eq!(text(spread.expression()), "a");
+    }
+
+    #[test]
+    fn typed_leading_and_trailing_comments() {
+        let root = SyntaxNode::new_root(
+            crate::parse(
+                "{# detached #}\n\n<!-- first -->\n{# second #}\n{% block a %}{% endblock %} {# trailing #}<!-- too -->\n{# next line #}",
+            )
+            .green_node,
+        );
+        let block = root.descendants().find_map(TwigBlock::cast).unwrap();
+
+        let leading: Vec<String> = block
+            .leading_comments()
+            .iter()
+            .map(|c| c.text().to_string().trim().to_string())
+            .collect();
+        assert_eq!(leading, vec!["<!-- first -->", "{# second #}"]);
+
+        let trailing: Vec<String> = block
+            .trailing_comments()
+            .iter()
+            .map(|c| c.text().to_string().trim().to_string())
+            .collect();
+        assert_eq!(trailing, vec!["{# trailing #}", "<!-- too -->"]);
+
+        let first = root.children().find_map(TwigComment::cast).unwrap();
+        assert!(first.leading_comments().is_empty());
+        assert!(first.trailing_comments().is_empty());
+    }
+
+    #[test]
+    fn typed_comment_annotations() {
+        let comment: TwigComment = first(
+            "{# @var product \\Shopware\\Core\\Content\\Product\\SalesChannel\\SalesChannelProductEntity #}",
+        );
+        assert_eq!(
+            comment.content(),
+            "@var product \\Shopware\\Core\\Content\\Product\\SalesChannel\\SalesChannelProductEntity"
+        );
+        let annotations = comment.annotations();
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(annotations[0].tag, "var");
+        assert_eq!(
+            annotations[0].variable_type(),
+            Some((
+                "product",
+                "\\Shopware\\Core\\Content\\Product\\SalesChannel\\SalesChannelProductEntity"
+            ))
+        );
+
+        let comment: TwigComment =
+            first("{#\n    @var \\Foo\\Item[] $items\n    @deprecated use other.html.twig\n#}");
+        let annotations = comment.annotations();
+        assert_eq!(
+            annotations[0].variable_type(),
+            Some(("items", "\\Foo\\Item[]"))
+        );
+        assert_eq!(annotations[1].tag, "deprecated");
+        assert_eq!(annotations[1].arguments, "use other.html.twig");
+        assert_eq!(annotations[1].variable_type(), None);
+
+        let comment: TwigComment = first("{# just a comment with an @ sign #}");
+        assert!(comment.annotations().is_empty());
+    }
+
+    #[test]
+    fn typed_literals() {
+        let number: TwigLiteralNumber = first("{{ 4.2 }}");
+        assert_eq!(number.number().unwrap().text(), "4.2");
+
+        let boolean: TwigLiteralBoolean = first("{{ false }}");
+        assert_eq!(boolean.value(), Some(false));
+
+        let name: TwigLiteralName = first("{{ product }}");
+        assert_eq!(name.name().unwrap().text(), "product");
+
+        let array: TwigLiteralArray = first("{{ [1, a] }}");
+        let items: Vec<String> = array.items().map(|i| text(Some(i))).collect();
+        assert_eq!(items, vec!["1", "a"]);
+        let empty_array: TwigLiteralArray = first("{{ [] }}");
+        assert_eq!(empty_array.items().count(), 0);
+
+        let interpolation: TwigLiteralStringInterpolation = first(r#"{{ "a #{ b ~ c }" }}"#);
+        assert_eq!(text(interpolation.expression()), "b ~ c");
+
+        let hash: TwigLiteralHash = first("{{ { a: 1, 'b': 2, 3: 4, (d): 5 } }}");
+        let keys: Vec<Option<String>> = hash
+            .pairs()
+            .map(|pair| pair.key().unwrap().get_key_name())
+            .collect();
+        assert_eq!(
+            keys,
+            vec![Some("a".into()), Some("b".into()), Some("3".into()), None]
+        );
+    }
+}
@@ -0,0 +1,367 @@
+//! Visitor over the typed AST, which removes the need of manually walking the untyped tree
+//! and checking every node with `can_cast`.
+//!
+//! ```
+//! use ludtwig_parser::syntax::typed::{walk, TwigBlock, Visitor};
+//! use ludtwig_parser::syntax::untyped::SyntaxNode;
+//!
+//! #[derive(Default)]
+//! struct BlockNames(Vec<String>);
+//!
+//! impl Visitor for BlockNames {
+//!     fn visit_twig_block(&mut self, node: &TwigBlock) {
+//!         if let Some(name) = node.name() {
+//!             self.0.push(name.text().to_string());
+//!         }
+//!     }
+//! }
+//!
+//! let parse = ludtwig_parser::parse("{% block a %}{% block b %}{% endblock %}{% endblock %}");
+//! let mut block_names = BlockNames::default();
+//! walk(&SyntaxNode::new_root(parse.green_node), &mut block_names);
+//! assert_eq!(block_names.0, vec!["a", "b"]);
+//! ```
+
+use rowan::{NodeOrToken, WalkEvent};
+
+use crate::syntax::untyped::{SyntaxKind, SyntaxNode, SyntaxToken};
+
+use super::{
+    Body, Error, HtmlAttribute, HtmlAttributeList, HtmlCdata, HtmlComment, HtmlConditionalComment,
+    HtmlDoctype, HtmlEndingTag, HtmlStartingTag, HtmlString, HtmlStringInner, HtmlTag, HtmlText,
+    HtmlXmlProlog, LudtwigDirectiveFileIgnore, LudtwigDirectiveIgnore, LudtwigDirectiveRuleList,
+    Root, ShopwareIcon, ShopwareIconStyle, ShopwareReturn, ShopwareSilentFeatureCall,
+    ShopwareSilentFeatureCallEndingBlock, ShopwareSilentFeatureCallStartingBlock,
+    ShopwareThumbnails, ShopwareThumbnailsWith, ShopwareTwigExtends, ShopwareTwigInclude,
+    TwigAccessor, TwigApply, TwigApplyEndingBlock, TwigApplyStartingBlock, TwigArguments,
+    TwigArrowFunction, TwigAssignment, TwigAutoescape, TwigAutoescapeEndingBlock,
+    TwigAutoescapeStartingBlock, TwigBinaryExpression, TwigBlock, TwigCache, TwigCacheEndingBlock,
+    TwigCacheStartingBlock, TwigCacheTTL, TwigCacheTags, TwigComment, TwigConditionalExpression,
+    TwigDeprecated, TwigDo, TwigElseBlock, TwigElseIfBlock, TwigEmbed, TwigEmbedEndingBlock,
+    TwigEmbedStartingBlock, TwigEndForBlock, TwigEndIfBlock, TwigEndSetBlock, TwigEndingBlock,
+    TwigExpression, TwigExtends, TwigFilter, TwigFlush, TwigFor, TwigForBlock, TwigForElseBlock,
+    TwigFrom, TwigFunctionCall, TwigGuard, TwigGuardElseBlock, TwigGuardEndingBlock,
+    TwigGuardStartingBlock, TwigIf, TwigIfBlock, TwigImport, TwigInclude, TwigIncludeWith,
+    TwigIndex, TwigIndexLookup, TwigIndexRange, TwigLiteralArray, TwigLiteralArrayInner,
+    TwigLiteralBoolean, TwigLiteralHash, TwigLiteralHashItems, TwigLiteralHashKey,
+    TwigLiteralHashPair, TwigLiteralHashValue, TwigLiteralName, TwigLiteralNull, TwigLiteralNumber,
+    TwigLiteralString, TwigLiteralStringInner, TwigLiteralStringInterpolation, TwigMacro,
+    TwigMacroEndingBlock, TwigMacroStartingBlock, TwigNamedArgument, TwigOperand, TwigOverride,
+    TwigParenthesesExpression, TwigSandbox, TwigSandboxEndingBlock, TwigSandboxStartingBlock,
+    TwigSet, TwigSetBlock, TwigSpaceless, TwigSpacelessEndingBlock, TwigSpacelessStartingBlock,
+    TwigSpread, TwigStartingBlock, TwigTrans, TwigTransDefaultDomain, TwigTransEndingBlock,
+    TwigTransFrom, TwigTransInto, TwigTransStartingBlock, TwigTransWith, TwigUnaryExpression,
+    TwigUse, TwigVar, TwigVerbatim, TwigVerbatimEndingBlock, TwigVerbatimStartingBlock, TwigWith,
+    TwigWithEndingBlock, TwigWithStartingBlock, VueDirective, VueDirectiveArgument,
+    VueDirectiveModifier,
+};
+
+/// Controls the traversal of [`walk`] after entering a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VisitControl {
+    /// Continue with the children of the node
+    Continue,
+    /// Don't visit any children of the node (the node itself is still left)
+    SkipChildren,
+}
+
+macro_rules! visitor {
+    ($($ast:ident, $kind:ident, $visit:ident, $leave:ident;)*) => {
+        /// Hooks which are called by [`walk`] in document order.
+        /// Every method defaults to doing nothing, so only the interesting ones need an implementation.
+        ///
+        /// For every node `enter_node` is called first, followed by the typed `visit_*` method.
+        /// After all children are visited the typed `leave_*` method and `leave_node` are called.
+        pub trait Visitor {
+            /// Called when entering any node, before the typed `visit_*` method.
+            /// Return [`VisitControl::SkipChildren`] to skip the whole subtree (like for error nodes).
+            #[allow(unused_variables)]
+            fn enter_node(&mut self, node: &SyntaxNode) -> VisitControl {
+                VisitControl::Continue
+            }
+
+            /// Called when leaving any node, after the typed `leave_*` method.
+            #[allow(unused_variables)]
+            fn leave_node(&mut self, node: &SyntaxNode) {}
+
+            /// Called for every token (including trivia) in the tree.
+            #[allow(unused_variables)]
+            fn visit_token(&mut self, token: &SyntaxToken) {}
+
+            $(
+                #[doc = concat!("Called when entering a [`", stringify!($ast), "`] node.")]
+                #[allow(unused_variables)]
+                fn $visit(&mut self, node: &$ast) {}
+
+                #[doc = concat!("Called when leaving a [`", stringify!($ast), "`] node.")]
+                #[allow(unused_variables)]
+                fn $leave(&mut self, node: &$ast) {}
+            )*
+        }
+
+        fn dispatch_visit<V: Visitor + ?Sized>(visitor: &mut V, node: &SyntaxNode) {
+            match node.kind() {
+                $(SyntaxKind::$kind => visitor.$visit(&$ast { syntax: node.clone() }),)*
+                _ => {}
+            }
+        }
+
+        fn dispatch_leave<V: Visitor + ?Sized>(visitor: &mut V, node: &SyntaxNode) {
+            match node.kind() {
+                $(SyntaxKind::$kind => visitor.$leave(&$ast { syntax: node.clone() }),)*
+                _ => {}
+            }
+        }
+    };
+}
+
+visitor! {
+    TwigBlock, TWIG_BLOCK, visit_twig_block, leave_twig_block;
+    TwigStartingBlock, TWIG_STARTING_BLOCK, visit_twig_starting_block, leave_twig_starting_block;
+    TwigEndingBlock, TWIG_ENDING_BLOCK, visit_twig_ending_block, leave_twig_ending_block;
+    HtmlTag, HTML_TAG, visit_html_tag, leave_html_tag;
+    HtmlStartingTag, HTML_STARTING_TAG, visit_html_starting_tag, leave_html_starting_tag;
+    HtmlXmlProlog, HTML_XML_PROLOG, visit_html_xml_prolog, leave_html_xml_prolog;
+    HtmlAttribute, HTML_ATTRIBUTE, visit_html_attribute, leave_html_attribute;
+    VueDirective, VUE_DIRECTIVE, visit_vue_directive, leave_vue_directive;
+    VueDirectiveArgument, VUE_DIRECTIVE_ARGUMENT, visit_vue_directive_argument, leave_vue_directive_argument;
+    VueDirectiveModifier, VUE_DIRECTIVE_MODIFIER, visit_vue_directive_modifier, leave_vue_directive_modifier;
+    HtmlEndingTag, HTML_ENDING_TAG, visit_html_ending_tag, leave_html_ending_tag;
+    TwigBinaryExpression, TWIG_BINARY_EXPRESSION, visit_twig_binary_expression, leave_twig_binary_expression;
+    LudtwigDirectiveRuleList, LUDTWIG_DIRECTIVE_RULE_LIST, visit_ludtwig_directive_rule_list, leave_ludtwig_directive_rule_list;
+    LudtwigDirectiveFileIgnore, LUDTWIG_DIRECTIVE_FILE_IGNORE, visit_ludtwig_directive_file_ignore, leave_ludtwig_directive_file_ignore;
+    LudtwigDirectiveIgnore, LUDTWIG_DIRECTIVE_IGNORE, visit_ludtwig_directive_ignore, leave_ludtwig_directive_ignore;
+    TwigLiteralString, TWIG_LITERAL_STRING, visit_twig_literal_string, leave_twig_literal_string;
+    TwigLiteralStringInner, TWIG_LITERAL_STRING_INNER, visit_twig_literal_string_inner, leave_twig_literal_string_inner;
+    HtmlString, HTML_STRING, visit_html_string, leave_html_string;
+    TwigExtends, TWIG_EXTENDS, visit_twig_extends, leave_twig_extends;
+    Body, BODY, visit_body, leave_body;
+    TwigVar, TWIG_VAR, visit_twig_var, leave_twig_var;
+    TwigExpression, TWIG_EXPRESSION, visit_twig_expression, leave_twig_expression;
+    TwigUnaryExpression, TWIG_UNARY_EXPRESSION, visit_twig_unary_expression, leave_twig_unary_expression;
+    TwigParenthesesExpression, TWIG_PARENTHESES_EXPRESSION, visit_twig_parentheses_expression, leave_twig_parentheses_expression;
+    TwigConditionalExpression, TWIG_CONDITIONAL_EXPRESSION, visit_twig_conditional_expression, leave_twig_conditional_expression;
+    TwigOperand, TWIG_OPERAND, visit_twig_operand, leave_twig_operand;
+    TwigAccessor, TWIG_ACCESSOR, visit_twig_accessor, leave_twig_accessor;
+    TwigFilter, TWIG_FILTER, visit_twig_filter, leave_twig_filter;
+    TwigIndexLookup, TWIG_INDEX_LOOKUP, visit_twig_index_lookup, leave_twig_index_lookup;
+    TwigIndex, TWIG_INDEX, visit_twig_index, leave_twig_index;
+    TwigIndexRange, TWIG_INDEX_RANGE, visit_twig_index_range, leave_twig_index_range;
+    TwigFunctionCall, TWIG_FUNCTION_CALL, visit_twig_function_call, leave_twig_function_call;
+    TwigArguments, TWIG_ARGUMENTS, visit_twig_arguments, leave_twig_arguments;
+    TwigNamedArgument, TWIG_NAMED_ARGUMENT, visit_twig_named_argument, leave_twig_named_argument;
+    TwigArrowFunction, TWIG_ARROW_FUNCTION, visit_twig_arrow_function, leave_twig_arrow_function;
+    TwigSpread, TWIG_SPREAD, visit_twig_spread, leave_twig_spread;
+    TwigLiteralStringInterpolation, TWIG_LITERAL_STRING_INTERPOLATION, visit_twig_literal_string_interpolation, leave_twig_literal_string_interpolation;
+    TwigLiteralNumber, TWIG_LITERAL_NUMBER, visit_twig_literal_number, leave_twig_literal_number;
+    TwigLiteralArray, TWIG_LITERAL_ARRAY, visit_twig_literal_array, leave_twig_literal_array;
+    TwigLiteralArrayInner, TWIG_LITERAL_ARRAY_INNER, visit_twig_literal_array_inner, leave_twig_literal_array_inner;
+    TwigLiteralNull, TWIG_LITERAL_NULL, visit_twig_literal_null, leave_twig_literal_null;
+    TwigLiteralBoolean, TWIG_LITERAL_BOOLEAN, visit_twig_literal_boolean, leave_twig_literal_boolean;
+    TwigLiteralHash, TWIG_LITERAL_HASH, visit_twig_literal_hash, leave_twig_literal_hash;
+    TwigLiteralHashItems, TWIG_LITERAL_HASH_ITEMS, visit_twig_literal_hash_items, leave_twig_literal_hash_items;
+    TwigLiteralHashPair, TWIG_LITERAL_HASH_PAIR, visit_twig_literal_hash_pair, leave_twig_literal_hash_pair;
+    TwigLiteralHashKey, TWIG_LITERAL_HASH_KEY, visit_twig_literal_hash_key, leave_twig_literal_hash_key;
+    TwigLiteralHashValue, TWIG_LITERAL_HASH_VALUE, visit_twig_literal_hash_value, leave_twig_literal_hash_value;
+    TwigLiteralName, TWIG_LITERAL_NAME, visit_twig_literal_name, leave_twig_literal_name;
+    TwigComment, TWIG_COMMENT, visit_twig_comment, leave_twig_comment;
+    TwigIf, TWIG_IF, visit_twig_if, leave_twig_if;
+    TwigIfBlock, TWIG_IF_BLOCK, visit_twig_if_block, leave_twig_if_block;
+    TwigElseIfBlock, TWIG_ELSE_IF_BLOCK, visit_twig_else_if_block, leave_twig_else_if_block;
+    TwigElseBlock, TWIG_ELSE_BLOCK, visit_twig_else_block, leave_twig_else_block;
+    TwigEndIfBlock, TWIG_ENDIF_BLOCK, visit_twig_end_if_block, leave_twig_end_if_block;
+    TwigSet, TWIG_SET, visit_twig_set, leave_twig_set;
+    TwigSetBlock, TWIG_SET_BLOCK, visit_twig_set_block, leave_twig_set_block;
+    TwigEndSetBlock, TWIG_ENDSET_BLOCK, visit_twig_end_set_block, leave_twig_end_set_block;
+    TwigAssignment, TWIG_ASSIGNMENT, visit_twig_assignment, leave_twig_assignment;
+    TwigFor, TWIG_FOR, visit_twig_for, leave_twig_for;
+    TwigForBlock, TWIG_FOR_BLOCK, visit_twig_for_block, leave_twig_for_block;
+    TwigForElseBlock, TWIG_FOR_ELSE_BLOCK, visit_twig_for_else_block, leave_twig_for_else_block;
+    TwigEndForBlock, TWIG_ENDFOR_BLOCK, visit_twig_end_for_block, leave_twig_end_for_block;
+    TwigInclude, TWIG_INCLUDE, visit_twig_include, leave_twig_include;
+    TwigIncludeWith, TWIG_INCLUDE_WITH, visit_twig_include_with, leave_twig_include_with;
+    TwigUse, TWIG_USE, visit_twig_use, leave_twig_use;
+    TwigOverride, TWIG_OVERRIDE, visit_twig_override, leave_twig_override;
+    TwigApply, TWIG_APPLY, visit_twig_apply, leave_twig_apply;
+    TwigApplyStartingBlock, TWIG_APPLY_STARTING_BLOCK, visit_twig_apply_starting_block, leave_twig_apply_starting_block;
+    TwigApplyEndingBlock, TWIG_APPLY_ENDING_BLOCK, visit_twig_apply_ending_block, leave_twig_apply_ending_block;
+    TwigAutoescape, TWIG_AUTOESCAPE, visit_twig_autoescape, leave_twig_autoescape;
+    TwigAutoescapeStartingBlock, TWIG_AUTOESCAPE_STARTING_BLOCK, visit_twig_autoescape_starting_block, leave_twig_autoescape_starting_block;
+    TwigAutoescapeEndingBlock, TWIG_AUTOESCAPE_ENDING_BLOCK, visit_twig_autoescape_ending_block, leave_twig_autoescape_ending_block;
+    TwigDeprecated, TWIG_DEPRECATED, visit_twig_deprecated, leave_twig_deprecated;
+    TwigDo, TWIG_DO, visit_twig_do, leave_twig_do;
+    TwigEmbed, TWIG_EMBED, visit_twig_embed, leave_twig_embed;
+    TwigEmbedStartingBlock, TWIG_EMBED_STARTING_BLOCK, visit_twig_embed_starting_block, leave_twig_embed_starting_block;
+    TwigEmbedEndingBlock, TWIG_EMBED_ENDING_BLOCK, visit_twig_embed_ending_block, leave_twig_embed_ending_block;
+    TwigFlush, TWIG_FLUSH, visit_twig_flush, leave_twig_flush;
+    TwigFrom, TWIG_FROM, visit_twig_from, leave_twig_from;
+    TwigImport, TWIG_IMPORT, visit_twig_import, leave_twig_import;
+    TwigSandbox, TWIG_SANDBOX, visit_twig_sandbox, leave_twig_sandbox;
+    TwigSandboxStartingBlock, TWIG_SANDBOX_STARTING_BLOCK, visit_twig_sandbox_starting_block, leave_twig_sandbox_starting_block;
+    TwigSandboxEndingBlock, TWIG_SANDBOX_ENDING_BLOCK, visit_twig_sandbox_ending_block, leave_twig_sandbox_ending_block;
+    TwigGuard, TWIG_GUARD, visit_twig_guard, leave_twig_guard;
+    TwigGuardStartingBlock, TWIG_GUARD_STARTING_BLOCK, visit_twig_guard_starting_block, leave_twig_guard_starting_block;
+    TwigGuardElseBlock, TWIG_GUARD_ELSE_BLOCK, visit_twig_guard_else_block, leave_twig_guard_else_block;
+    TwigGuardEndingBlock, TWIG_GUARD_ENDING_BLOCK, visit_twig_guard_ending_block, leave_twig_guard_ending_block;
+    TwigSpaceless, TWIG_SPACELESS, visit_twig_spaceless, leave_twig_spaceless;
+    TwigSpacelessStartingBlock, TWIG_SPACELESS_STARTING_BLOCK, visit_twig_spaceless_starting_block, leave_twig_spaceless_starting_block;
+    TwigSpacelessEndingBlock, TWIG_SPACELESS_ENDING_BLOCK, visit_twig_spaceless_ending_block, leave_twig_spaceless_ending_block;
+    TwigVerbatim, TWIG_VERBATIM, visit_twig_verbatim, leave_twig_verbatim;
+    TwigVerbatimStartingBlock, TWIG_VERBATIM_STARTING_BLOCK, visit_twig_verbatim_starting_block, leave_twig_verbatim_starting_block;
+    TwigVerbatimEndingBlock, TWIG_VERBATIM_ENDING_BLOCK, visit_twig_verbatim_ending_block, leave_twig_verbatim_ending_block;
+    TwigMacro, TWIG_MACRO, visit_twig_macro, leave_twig_macro;
+    TwigMacroStartingBlock, TWIG_MACRO_STARTING_BLOCK, visit_twig_macro_starting_block, leave_twig_macro_starting_block;
+    TwigMacroEndingBlock, TWIG_MACRO_ENDING_BLOCK, visit_twig_macro_ending_block, leave_twig_macro_ending_block;
+    TwigWith, TWIG_WITH, visit_twig_with, leave_twig_with;
+    TwigWithStartingBlock, TWIG_WITH_STARTING_BLOCK, visit_twig_with_starting_block, leave_twig_with_starting_block;
+    TwigWithEndingBlock, TWIG_WITH_ENDING_BLOCK, visit_twig_with_ending_block, leave_twig_with_ending_block;
+    TwigCache, TWIG_CACHE, visit_twig_cache, leave_twig_cache;
+    TwigCacheTTL, TWIG_CACHE_TTL, visit_twig_cache_ttl, leave_twig_cache_ttl;
+    TwigCacheTags, TWIG_CACHE_TAGS, visit_twig_cache_tags, leave_twig_cache_tags;
+    TwigCacheStartingBlock, TWIG_CACHE_STARTING_BLOCK, visit_twig_cache_starting_block, leave_twig_cache_starting_block;
+    TwigCacheEndingBlock, TWIG_CACHE_ENDING_BLOCK, visit_twig_cache_ending_block, leave_twig_cache_ending_block;
+    TwigTrans, TWIG_TRANS, visit_twig_trans, leave_twig_trans;
+    TwigTransStartingBlock, TWIG_TRANS_STARTING_BLOCK, visit_twig_trans_starting_block, leave_twig_trans_starting_block;
+    TwigTransEndingBlock, TWIG_TRANS_ENDING_BLOCK, visit_twig_trans_ending_block, leave_twig_trans_ending_block;
+    TwigTransWith, TWIG_TRANS_WITH, visit_twig_trans_with, leave_twig_trans_with;
+    TwigTransFrom, TWIG_TRANS_FROM, visit_twig_trans_from, leave_twig_trans_from;
+    TwigTransInto, TWIG_TRANS_INTO, visit_twig_trans_into, leave_twig_trans_into;
+    TwigTransDefaultDomain, TWIG_TRANS_DEFAULT_DOMAIN, visit_twig_trans_default_domain, leave_twig_trans_default_domain;
+    ShopwareTwigExtends, SHOPWARE_TWIG_SW_EXTENDS, visit_shopware_twig_extends, leave_shopware_twig_extends;
+    ShopwareTwigInclude, SHOPWARE_TWIG_SW_INCLUDE, visit_shopware_twig_include, leave_shopware_twig_include;
+    ShopwareSilentFeatureCall, SHOPWARE_SILENT_FEATURE_CALL, visit_shopware_silent_feature_call, leave_shopware_silent_feature_call;
+    ShopwareSilentFeatureCallStartingBlock, SHOPWARE_SILENT_FEATURE_CALL_STARTING_BLOCK, visit_shopware_silent_feature_call_starting_block, leave_shopware_silent_feature_call_starting_block;
+    ShopwareSilentFeatureCallEndingBlock, SHOPWARE_SILENT_FEATURE_CALL_ENDING_BLOCK, visit_shopware_silent_feature_call_ending_block, leave_shopware_silent_feature_call_ending_block;
+    ShopwareReturn, SHOPWARE_RETURN, visit_shopware_return, leave_shopware_return;
+    ShopwareIcon, SHOPWARE_ICON, visit_shopware_icon, leave_shopware_icon;
+    ShopwareIconStyle, SHOPWARE_ICON_STYLE, visit_shopware_icon_style, leave_shopware_icon_style;
+    ShopwareThumbnails, SHOPWARE_THUMBNAILS, visit_shopware_thumbnails, leave_shopware_thumbnails;
+    ShopwareThumbnailsWith, SHOPWARE_THUMBNAILS_WITH, visit_shopware_thumbnails_with, leave_shopware_thumbnails_with;
+    HtmlDoctype, HTML_DOCTYPE, visit_html_doctype, leave_html_doctype;
+    HtmlAttributeList, HTML_ATTRIBUTE_LIST, visit_html_attribute_list, leave_html_attribute_list;
+    HtmlStringInner, HTML_STRING_INNER, visit_html_string_inner, leave_html_string_inner;
+    HtmlText, HTML_TEXT, visit_html_text, leave_html_text;
+    HtmlComment, HTML_COMMENT, visit_html_comment, leave_html_comment;
+    HtmlConditionalComment, HTML_CONDITIONAL_COMMENT, visit_html_conditional_comment, leave_html_conditional_comment;
+    HtmlCdata, HTML_CDATA, visit_html_cdata, leave_html_cdata;
+    Error, ERROR, visit_error, leave_error;
+    Root, ROOT, visit_root, leave_root;
+}
+
+/// Walk the tree below (and including) the given node in document order and
+/// call the hooks of the visitor.
+pub fn walk<V: Visitor + ?Sized>(node: &SyntaxNode, visitor: &mut V) {
+    let mut preorder = node.preorder_with_tokens();
+
+    while let Some(walk_event) = preorder.next() {
+        match walk_event {
+            WalkEvent::Enter(NodeOrToken::Node(n)) => {
+                let control = visitor.enter_node(&n);
+                dispatch_visit(visitor, &n);
+
+                if control == VisitControl::SkipChildren {
+                    preorder.skip_subtree();
+                }
+            }
+            WalkEvent::Enter(NodeOrToken::Token(t)) => visitor.visit_token(&t),
+            WalkEvent::Leave(NodeOrToken::Node(n)) => {
+                dispatch_leave(visitor, &n);
+                visitor.leave_node(&n);
+            }
+            WalkEvent::Leave(NodeOrToken::Token(_)) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::parse;
+
+    /// Records all visited nodes as an indented list
+    #[derive(Default)]
+    struct Recorder {
+        depth: usize,
+        output: String,
+    }
+
+    impl Recorder {
+        fn record(&mut self, text: &str) {
+            self.output.push_str(&"  ".repeat(self.depth));
+            self.output.push_str(text);
+            self.output.push('\n');
+        }
+    }
+
+    impl Visitor for Recorder {
+        fn enter_node(&mut self, node: &SyntaxNode) -> VisitControl {
+            if node.kind() == SyntaxKind::HTML_ATTRIBUTE_LIST {
+                VisitControl::SkipChildren
+            } else {
+                VisitControl::Continue
+            }
+        }
+
+        fn visit_html_tag(&mut self, node: &HtmlTag) {
+            self.record(&format!("enter html tag {}", node.name().unwrap().text()));
+            self.depth += 1;
+        }
+
+        fn leave_html_tag(&mut self, node: &HtmlTag) {
+            self.depth -= 1;
+            self.record(&format!("leave html tag {}", node.name().unwrap().text()));
+        }
+
+        fn visit_twig_block(&mut self, node: &TwigBlock) {
+            self.record(&format!("enter twig block {}", node.name().unwrap().text()));
+            self.depth += 1;
+        }
+
+        fn leave_twig_block(&mut self, node: &TwigBlock) {
+            self.depth -= 1;
+            self.record(&format!("leave twig block {}", node.name().unwrap().text()));
+        }
+
+        fn visit_twig_filter(&mut self, node: &TwigFilter) {
+            self.record(&format!(
+                "twig filter {}",
+                node.syntax.text().to_string().trim()
+            ));
+        }
+
+        fn visit_html_attribute(&mut self, node: &HtmlAttribute) {
+            // skipped by enter_node of the parent attribute list
+            self.record(&format!("attribute {}", node.syntax.text()));
+        }
+    }
+
+    #[test]
+    fn walk_visits_in_document_order() {
+        let parse = parse(
+            r#"{% block outer %}
+    <div class="a">
+        {{ title|upper|trim }}
+        {% block inner %}<span></span>{% endblock %}
+    </div>
+{% endblock %}"#,
+        );
+        let mut recorder = Recorder::default();
+        walk(&SyntaxNode::new_root(parse.green_node), &mut recorder);
+
+        expect![[r#"
+            enter twig block outer
+              enter html tag div
+                twig filter title|upper|trim
+                twig filter title|upper
+                enter twig block inner
+                  enter html tag span
+                  leave html tag span
+                leave twig block inner
+              leave html tag div
+            leave twig block outer
+        "#]]
+        .assert_eq(&recorder.output);
+    }
+}
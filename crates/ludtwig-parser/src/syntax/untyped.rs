@@ -1,3 +1,4 @@
+use crate::syntax::trivia::first_non_trivia_token;
 use logos::Logos;
 pub use rowan::Direction;
 /// `GreenNode` is an immutable tree, which is cheap to change,
@@ -28,10 +29,13 @@ pub enum SyntaxKind {
     TK_WHITESPACE = 0,
     #[regex(r"((\n)|(\r\n))+")]
     TK_LINE_BREAK,
+    /// the unicode byte order mark, which is written at the start of files by some (windows) editors
+    #[token("\u{FEFF}")]
+    TK_BYTE_ORDER_MARK,
     /// a single word containing only characters, numbers or symbols
     /// must start with an alpha or one of the special starting characters followed by a normal alpha
     /// special case: allows a single underscore as a valid word
-    #[regex(r"([a-zA-Z]|([@\#_\$][a-zA-Z])|_)[a-zA-Z0-9_\-]*")]
+    #[regex(r"([a-zA-Z]|([_\$][a-zA-Z])|_)[a-zA-Z0-9_\-]*")]
     TK_WORD,
     /// a valid twig number
     #[regex(r"[0-9]+(\.[0-9]+)?([Ee][\+\-][0-9]+)?")]
@@ -43,6 +47,8 @@ pub enum SyntaxKind {
     TK_DOT,
     #[token("..")]
     TK_DOUBLE_DOT,
+    #[token("...")]
+    TK_TRIPLE_DOT,
     #[token(",")]
     TK_COMMA,
     #[token(":")]
@@ -105,18 +111,31 @@ pub enum SyntaxKind {
     TK_GREATER_THAN,
     #[token(">=")]
     TK_GREATER_THAN_EQUAL,
-    #[token("/>")]
+    /// not produced by the lexer, because the slash can also end an unquoted attribute value like `<a href=/foo/>`
+    /// the parser combines the single tokens into this one
     TK_SLASH_GREATER_THAN,
     #[token("<!--")]
     TK_LESS_THAN_EXCLAMATION_MARK_MINUS_MINUS,
     #[token("-->")]
     TK_MINUS_MINUS_GREATER_THAN,
+    /// not produced by the lexer, because it would collide with other `<![` constructs like `<![endif]>`
+    /// the parser combines the single tokens into this one
+    TK_CDATA_START,
+    /// not produced by the lexer, because `]]>` is also valid inside twig expressions like `a[b[0]]>1`
+    /// the parser combines the single tokens into this one
+    TK_CDATA_END,
+    #[token("<?")]
+    TK_LESS_THAN_QUESTION_MARK,
+    #[token("?>")]
+    TK_QUESTION_MARK_GREATER_THAN,
     #[token("=")]
     TK_EQUAL,
     #[token("==")]
     TK_DOUBLE_EQUAL,
     #[token("===")]
     TK_TRIPLE_EQUAL,
+    #[token("=>")]
+    TK_EQUAL_GREATER_THAN,
     #[token("+")]
     TK_PLUS,
     #[token("-")]
@@ -145,6 +164,8 @@ pub enum SyntaxKind {
     TK_HASHTAG_CLOSE_CURLY,
     #[token("#")]
     TK_HASHTAG,
+    #[token("@")]
+    TK_AT,
 
     #[token("true", ignore(ascii_case))]
     TK_TRUE,
@@ -168,6 +189,10 @@ pub enum SyntaxKind {
     TK_APPLY,
     #[token("endapply")]
     TK_ENDAPPLY,
+    #[token("filter")]
+    TK_FILTER,
+    #[token("endfilter")]
+    TK_ENDFILTER,
     #[token("autoescape")]
     TK_AUTOESCAPE,
     #[token("endautoescape")]
@@ -204,6 +229,14 @@ pub enum SyntaxKind {
     TK_SANDBOX,
     #[token("endsandbox")]
     TK_ENDSANDBOX,
+    #[token("guard")]
+    TK_GUARD,
+    #[token("endguard")]
+    TK_ENDGUARD,
+    #[token("spaceless")]
+    TK_SPACELESS,
+    #[token("endspaceless")]
+    TK_ENDSPACELESS,
     #[token("set")]
     TK_SET,
     #[token("endset")]
@@ -226,6 +259,14 @@ pub enum SyntaxKind {
     TK_TTL,
     #[token("tags")]
     TK_TAGS,
+    #[token("trans")]
+    TK_TRANS,
+    #[token("endtrans")]
+    TK_ENDTRANS,
+    #[token("trans_default_domain")]
+    TK_TRANS_DEFAULT_DOMAIN,
+    #[token("into")]
+    TK_INTO,
     /* twig operators */
     #[token("not")]
     TK_NOT,
@@ -247,6 +288,10 @@ pub enum SyntaxKind {
     TK_STARTS_WITH,
     #[token("ends with")]
     TK_ENDS_WITH,
+    #[token("has some")]
+    TK_HAS_SOME,
+    #[token("has every")]
+    TK_HAS_EVERY,
     #[token("is")]
     TK_IS,
     /* twig tests */
@@ -339,6 +384,8 @@ pub enum SyntaxKind {
     TWIG_FUNCTION_CALL,
     TWIG_ARGUMENTS,
     TWIG_NAMED_ARGUMENT,
+    TWIG_ARROW_FUNCTION, // arrow function like '(v, k) => v * 2' (twig 2.10+)
+    TWIG_SPREAD,         // spread operator like '...items' (twig 3.7+)
 
     // twig literals
     TWIG_LITERAL_STRING,
@@ -386,7 +433,7 @@ pub enum SyntaxKind {
     // twig use
     TWIG_USE,
     TWIG_OVERRIDE,
-    // twig apply
+    // twig apply (and the deprecated twig filter tag)
     TWIG_APPLY,
     TWIG_APPLY_STARTING_BLOCK,
     TWIG_APPLY_ENDING_BLOCK,
@@ -412,6 +459,14 @@ pub enum SyntaxKind {
     TWIG_SANDBOX,
     TWIG_SANDBOX_STARTING_BLOCK,
     TWIG_SANDBOX_ENDING_BLOCK,
+    TWIG_GUARD,
+    TWIG_GUARD_STARTING_BLOCK, // like '{% guard function importmap %}'
+    TWIG_GUARD_ELSE_BLOCK,
+    TWIG_GUARD_ENDING_BLOCK,
+    // twig spaceless (deprecated)
+    TWIG_SPACELESS,
+    TWIG_SPACELESS_STARTING_BLOCK,
+    TWIG_SPACELESS_ENDING_BLOCK,
     // twig verbatim
     TWIG_VERBATIM,
     TWIG_VERBATIM_STARTING_BLOCK,
@@ -430,6 +485,14 @@ pub enum SyntaxKind {
     TWIG_CACHE_TAGS,
     TWIG_CACHE_STARTING_BLOCK,
     TWIG_CACHE_ENDING_BLOCK,
+    // symfony translation tags
+    TWIG_TRANS,
+    TWIG_TRANS_STARTING_BLOCK,
+    TWIG_TRANS_ENDING_BLOCK,
+    TWIG_TRANS_WITH, // variables like 'with {'%name%': name}'
+    TWIG_TRANS_FROM, // domain like 'from 'messages''
+    TWIG_TRANS_INTO, // locale like 'into 'de''
+    TWIG_TRANS_DEFAULT_DOMAIN,
 
     // shopware specific
     SHOPWARE_TWIG_SW_EXTENDS,
@@ -447,10 +510,16 @@ pub enum SyntaxKind {
     HTML_DOCTYPE,
     HTML_ATTRIBUTE_LIST,
     HTML_ATTRIBUTE,
-    HTML_STRING,       // used as attribute values
+    VUE_DIRECTIVE, // name of a vue directive attribute like `v-on:click.prevent` or `@click`
+    VUE_DIRECTIVE_ARGUMENT, // like `click` or the dynamic `[key]`
+    VUE_DIRECTIVE_MODIFIER, // like `.prevent`
+    HTML_STRING,   // used as attribute values
     HTML_STRING_INNER, // content inside the quotes of html attribute values
-    HTML_TEXT,         // used as plain text between html tags / twig blocks
+    HTML_TEXT,     // used as plain text between html tags / twig blocks
     HTML_COMMENT,
+    HTML_CONDITIONAL_COMMENT, // <!--[if IE]> ... <![endif]--> with normally parsed content
+    HTML_CDATA,               // <![CDATA[ ... ]]> section, which can contain twig syntax
+    HTML_XML_PROLOG,          // <?xml version="1.0" ?> declaration or other processing instruction
     HTML_TAG,
     HTML_STARTING_TAG,
     HTML_ENDING_TAG,
@@ -471,12 +540,14 @@ pub enum SyntaxKind {
 macro_rules! T {
     [ws] => { $crate::syntax::untyped::SyntaxKind::TK_WHITESPACE };
     [lb] => { $crate::syntax::untyped::SyntaxKind::TK_LINE_BREAK };
+    [bom] => { $crate::syntax::untyped::SyntaxKind::TK_BYTE_ORDER_MARK };
     [word] => { $crate::syntax::untyped::SyntaxKind::TK_WORD };
     [number] => { $crate::syntax::untyped::SyntaxKind::TK_NUMBER };
     [html escape character] => { $crate::syntax::untyped::SyntaxKind::TK_HTML_ESCAPE_CHARACTER };
     [unknown] => { $crate::syntax::untyped::SyntaxKind::TK_UNKNOWN };
     ["."] => { $crate::syntax::untyped::SyntaxKind::TK_DOT };
     [".."] => { $crate::syntax::untyped::SyntaxKind::TK_DOUBLE_DOT };
+    ["..."] => { $crate::syntax::untyped::SyntaxKind::TK_TRIPLE_DOT };
     [","] => { $crate::syntax::untyped::SyntaxKind::TK_COMMA };
     [":"] => { $crate::syntax::untyped::SyntaxKind::TK_COLON };
     [";"] => { $crate::syntax::untyped::SyntaxKind::TK_SEMICOLON };
@@ -511,9 +582,14 @@ macro_rules! T {
     ["/>"] => { $crate::syntax::untyped::SyntaxKind::TK_SLASH_GREATER_THAN };
     ["<!--"] => { $crate::syntax::untyped::SyntaxKind::TK_LESS_THAN_EXCLAMATION_MARK_MINUS_MINUS };
     ["-->"] => { $crate::syntax::untyped::SyntaxKind::TK_MINUS_MINUS_GREATER_THAN };
+    ["<![CDATA["] => { $crate::syntax::untyped::SyntaxKind::TK_CDATA_START };
+    ["]]>"] => { $crate::syntax::untyped::SyntaxKind::TK_CDATA_END };
+    ["<?"] => { $crate::syntax::untyped::SyntaxKind::TK_LESS_THAN_QUESTION_MARK };
+    ["?>"] => { $crate::syntax::untyped::SyntaxKind::TK_QUESTION_MARK_GREATER_THAN };
     ["="] => { $crate::syntax::untyped::SyntaxKind::TK_EQUAL };
     ["=="] => { $crate::syntax::untyped::SyntaxKind::TK_DOUBLE_EQUAL };
     ["==="] => { $crate::syntax::untyped::SyntaxKind::TK_TRIPLE_EQUAL };
+    ["=>"] => { $crate::syntax::untyped::SyntaxKind::TK_EQUAL_GREATER_THAN };
     ["+"] => { $crate::syntax::untyped::SyntaxKind::TK_PLUS };
     ["-"] => { $crate::syntax::untyped::SyntaxKind::TK_MINUS };
     ["*"] => { $crate::syntax::untyped::SyntaxKind::TK_STAR };
@@ -528,6 +604,7 @@ macro_rules! T {
     ["{#"] => { $crate::syntax::untyped::SyntaxKind::TK_OPEN_CURLY_HASHTAG };
     ["#}"] => { $crate::syntax::untyped::SyntaxKind::TK_HASHTAG_CLOSE_CURLY };
     ["#"] => { $crate::syntax::untyped::SyntaxKind::TK_HASHTAG };
+    ["@"] => { $crate::syntax::untyped::SyntaxKind::TK_AT };
     ["true"] => { $crate::syntax::untyped::SyntaxKind::TK_TRUE };
     ["false"] => { $crate::syntax::untyped::SyntaxKind::TK_FALSE };
     ["block"] => { $crate::syntax::untyped::SyntaxKind::TK_BLOCK };
@@ -538,6 +615,8 @@ macro_rules! T {
     ["endif"] => { $crate::syntax::untyped::SyntaxKind::TK_ENDIF };
     ["apply"] => { $crate::syntax::untyped::SyntaxKind::TK_APPLY };
     ["endapply"] => { $crate::syntax::untyped::SyntaxKind::TK_ENDAPPLY };
+    ["filter"] => { $crate::syntax::untyped::SyntaxKind::TK_FILTER };
+    ["endfilter"] => { $crate::syntax::untyped::SyntaxKind::TK_ENDFILTER };
     ["autoescape"] => { $crate::syntax::untyped::SyntaxKind::TK_AUTOESCAPE };
     ["endautoescape"] => { $crate::syntax::untyped::SyntaxKind::TK_ENDAUTOESCAPE };
     ["cache"] => { $crate::syntax::untyped::SyntaxKind::TK_CACHE };
@@ -556,6 +635,10 @@ macro_rules! T {
     ["endmacro"] => { $crate::syntax::untyped::SyntaxKind::TK_ENDMACRO };
     ["sandbox"] => { $crate::syntax::untyped::SyntaxKind::TK_SANDBOX };
     ["endsandbox"] => { $crate::syntax::untyped::SyntaxKind::TK_ENDSANDBOX };
+    ["guard"] => { $crate::syntax::untyped::SyntaxKind::TK_GUARD };
+    ["endguard"] => { $crate::syntax::untyped::SyntaxKind::TK_ENDGUARD };
+    ["spaceless"] => { $crate::syntax::untyped::SyntaxKind::TK_SPACELESS };
+    ["endspaceless"] => { $crate::syntax::untyped::SyntaxKind::TK_ENDSPACELESS };
     ["set"] => { $crate::syntax::untyped::SyntaxKind::TK_SET };
     ["endset"] => { $crate::syntax::untyped::SyntaxKind::TK_ENDSET };
     ["use"] => { $crate::syntax::untyped::SyntaxKind::TK_USE };
@@ -567,6 +650,10 @@ macro_rules! T {
     ["endwith"] => { $crate::syntax::untyped::SyntaxKind::TK_ENDWITH };
     ["ttl"] => { $crate::syntax::untyped::SyntaxKind::TK_TTL };
     ["tags"] => { $crate::syntax::untyped::SyntaxKind::TK_TAGS };
+    ["trans"] => { $crate::syntax::untyped::SyntaxKind::TK_TRANS };
+    ["endtrans"] => { $crate::syntax::untyped::SyntaxKind::TK_ENDTRANS };
+    ["trans_default_domain"] => { $crate::syntax::untyped::SyntaxKind::TK_TRANS_DEFAULT_DOMAIN };
+    ["into"] => { $crate::syntax::untyped::SyntaxKind::TK_INTO };
     ["not"] => { $crate::syntax::untyped::SyntaxKind::TK_NOT };
     ["or"] => { $crate::syntax::untyped::SyntaxKind::TK_OR };
     ["and"] => { $crate::syntax::untyped::SyntaxKind::TK_AND };
@@ -577,6 +664,8 @@ macro_rules! T {
     ["matches"] => { $crate::syntax::untyped::SyntaxKind::TK_MATCHES };
     ["starts with"] => { $crate::syntax::untyped::SyntaxKind::TK_STARTS_WITH };
     ["ends with"] => { $crate::syntax::untyped::SyntaxKind::TK_ENDS_WITH };
+    ["has some"] => { $crate::syntax::untyped::SyntaxKind::TK_HAS_SOME };
+    ["has every"] => { $crate::syntax::untyped::SyntaxKind::TK_HAS_EVERY };
     ["is"] => { $crate::syntax::untyped::SyntaxKind::TK_IS };
     ["even"] => { $crate::syntax::untyped::SyntaxKind::TK_EVEN };
     ["odd"] => { $crate::syntax::untyped::SyntaxKind::TK_ODD };
@@ -613,7 +702,7 @@ impl SyntaxKind {
     #[must_use]
     pub fn is_trivia(self) -> bool {
         // Add comments and other non interesting things for the parser here in the future
-        matches!(self, T![ws] | T![lb])
+        matches!(self, T![ws] | T![lb] | T![bom])
     }
 }
 
@@ -623,11 +712,13 @@ impl fmt::Display for SyntaxKind {
         f.write_str(match self {
             SyntaxKind::TK_WHITESPACE => "whitespace",
             SyntaxKind::TK_LINE_BREAK => "line break",
+            SyntaxKind::TK_BYTE_ORDER_MARK => "byte order mark",
             SyntaxKind::TK_WORD => "word",
             SyntaxKind::TK_NUMBER => "number",
             SyntaxKind::TK_HTML_ESCAPE_CHARACTER => "html escape character",
             SyntaxKind::TK_DOT => ".",
             SyntaxKind::TK_DOUBLE_DOT => "..",
+            SyntaxKind::TK_TRIPLE_DOT => "...",
             SyntaxKind::TK_COMMA => ",",
             SyntaxKind::TK_COLON => ":",
             SyntaxKind::TK_SEMICOLON => ";",
@@ -662,9 +753,14 @@ impl fmt::Display for SyntaxKind {
             SyntaxKind::TK_SLASH_GREATER_THAN => "/>",
             SyntaxKind::TK_LESS_THAN_EXCLAMATION_MARK_MINUS_MINUS => "<!--",
             SyntaxKind::TK_MINUS_MINUS_GREATER_THAN => "-->",
+            SyntaxKind::TK_CDATA_START => "<![CDATA[",
+            SyntaxKind::TK_CDATA_END => "]]>",
+            SyntaxKind::TK_LESS_THAN_QUESTION_MARK => "<?",
+            SyntaxKind::TK_QUESTION_MARK_GREATER_THAN => "?>",
             SyntaxKind::TK_EQUAL => "=",
             SyntaxKind::TK_DOUBLE_EQUAL => "==",
             SyntaxKind::TK_TRIPLE_EQUAL => "===",
+            SyntaxKind::TK_EQUAL_GREATER_THAN => "=>",
             SyntaxKind::TK_PLUS => "+",
             SyntaxKind::TK_MINUS => "-",
             SyntaxKind::TK_STAR => "*",
@@ -679,6 +775,7 @@ impl fmt::Display for SyntaxKind {
             SyntaxKind::TK_OPEN_CURLY_HASHTAG => "{#",
             SyntaxKind::TK_HASHTAG_CLOSE_CURLY => "#}",
             SyntaxKind::TK_HASHTAG => "#",
+            SyntaxKind::TK_AT => "@",
             SyntaxKind::TK_TRUE => "true",
             SyntaxKind::TK_FALSE => "false",
             SyntaxKind::TK_BLOCK => "block",
@@ -689,6 +786,8 @@ impl fmt::Display for SyntaxKind {
             SyntaxKind::TK_ENDIF => "endif",
             SyntaxKind::TK_APPLY => "apply",
             SyntaxKind::TK_ENDAPPLY => "endapply",
+            SyntaxKind::TK_FILTER => "filter",
+            SyntaxKind::TK_ENDFILTER => "endfilter",
             SyntaxKind::TK_AUTOESCAPE => "autoescape",
             SyntaxKind::TK_ENDAUTOESCAPE => "endautoescape",
             SyntaxKind::TK_CACHE => "cache",
@@ -707,6 +806,10 @@ impl fmt::Display for SyntaxKind {
             SyntaxKind::TK_ENDMACRO => "endmacro",
             SyntaxKind::TK_SANDBOX => "sandbox",
             SyntaxKind::TK_ENDSANDBOX => "endsandbox",
+            SyntaxKind::TK_GUARD => "guard",
+            SyntaxKind::TK_ENDGUARD => "endguard",
+            SyntaxKind::TK_SPACELESS => "spaceless",
+            SyntaxKind::TK_ENDSPACELESS => "endspaceless",
             SyntaxKind::TK_SET => "set",
             SyntaxKind::TK_ENDSET => "endset",
             SyntaxKind::TK_USE => "use",
@@ -718,6 +821,10 @@ impl fmt::Display for SyntaxKind {
             SyntaxKind::TK_ENDWITH => "endwith",
             SyntaxKind::TK_TTL => "ttl",
             SyntaxKind::TK_TAGS => "tags",
+            SyntaxKind::TK_TRANS => "trans",
+            SyntaxKind::TK_ENDTRANS => "endtrans",
+            SyntaxKind::TK_TRANS_DEFAULT_DOMAIN => "trans_default_domain",
+            SyntaxKind::TK_INTO => "into",
             SyntaxKind::TK_NOT => "not",
             SyntaxKind::TK_OR => "or",
             SyntaxKind::TK_AND => "and",
@@ -728,6 +835,8 @@ impl fmt::Display for SyntaxKind {
             SyntaxKind::TK_MATCHES => "matches",
             SyntaxKind::TK_STARTS_WITH => "starts with",
             SyntaxKind::TK_ENDS_WITH => "ends with",
+            SyntaxKind::TK_HAS_SOME => "has some",
+            SyntaxKind::TK_HAS_EVERY => "has every",
             SyntaxKind::TK_IS => "is",
             SyntaxKind::TK_EVEN => "even",
             SyntaxKind::TK_ODD => "odd",
@@ -818,21 +927,13 @@ pub trait SyntaxNodeExt {
 
 impl SyntaxNodeExt for SyntaxNode {
     /// Trims leading trivia from the original `text_range`
+    /// (this includes leading trivia of nested child nodes)
     fn text_range_trimmed_trivia(&self) -> TextRange {
-        let mut range = self.text_range();
+        let range = self.text_range();
 
-        for element in self.children_with_tokens() {
-            match element {
-                SyntaxElement::Token(t) if t.kind().is_trivia() => {
-                    let new_start = range.start() + t.text_range().len();
-                    if new_start < range.end() {
-                        range = TextRange::new(new_start, range.end());
-                    }
-                }
-                _ => return range,
-            }
+        match first_non_trivia_token(self) {
+            Some(t) => TextRange::new(t.text_range().start(), range.end()),
+            None => range,
         }
-
-        range
     }
 }
@@ -0,0 +1,42 @@
+//! Snapshot test helpers for the syntax tree (requires the `testing` feature).
+//!
+//! These are the same helpers the parser uses for its own grammar tests, so projects which add
+//! their own grammar (like custom tags) can test it the same way:
+//!
+//! ```
+//! use ludtwig_parser::testing::{check_parse, expect};
+//!
+//! check_parse(
+//!     "{{ a }}",
+//!     expect![[r#"
+//!         ROOT@0..7
+//!           TWIG_VAR@0..7
+//!             TK_OPEN_CURLY_CURLY@0..2 "{{"
+//!             TWIG_EXPRESSION@2..4
+//!               TWIG_LITERAL_NAME@2..4
+//!                 TK_WHITESPACE@2..3 " "
+//!                 TK_WORD@3..4 "a"
+//!             TK_WHITESPACE@4..5 " "
+//!             TK_CLOSE_CURLY_CURLY@5..7 "}}""#]],
+//! );
+//! ```
+//!
+//! Run the tests with `UPDATE_EXPECT=1` to update the expected trees in place.
+
+pub use expect_test::{expect, expect_file, Expect};
+
+use crate::{parse, parse_for_twig_version, TwigVersion};
+
+/// Parse the input and compare the debug tree (including parser errors) with the expectation
+#[allow(clippy::needless_pass_by_value)]
+pub fn check_parse(input: &str, expected_tree: Expect) {
+    let parse = parse(input);
+    expected_tree.assert_eq(&parse.debug_parse());
+}
+
+/// Same as `check_parse`, but parsing the input for a specific twig version
+#[allow(clippy::needless_pass_by_value)]
+pub fn check_parse_for_twig_version(input: &str, twig_version: TwigVersion, expected_tree: Expect) {
+    let parse = parse_for_twig_version(input, twig_version);
+    expected_tree.assert_eq(&parse.debug_parse());
+}
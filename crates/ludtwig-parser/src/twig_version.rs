@@ -0,0 +1,83 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Version of twig like `3.12`, without a minor version (`3` or `3.x`) the newest minor version is meant.
+///
+/// The parser reports syntax which is not supported by the version (like arrow functions before `2.10`)
+/// as errors, but still parses it.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct TwigVersion {
+    pub major: u8,
+    pub minor: u8,
+}
+
+impl TwigVersion {
+    /// The newest minor version of the newest major version, which supports all syntax.
+    pub const LATEST: Self = Self::new(3, u8::MAX);
+
+    #[must_use]
+    pub const fn new(major: u8, minor: u8) -> Self {
+        Self { major, minor }
+    }
+}
+
+impl Default for TwigVersion {
+    fn default() -> Self {
+        Self::LATEST
+    }
+}
+
+impl FromStr for TwigVersion {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!("invalid twig version '{value}', expected something like \"3.x\" or \"2.7\"")
+        };
+        let (major, minor) = value.split_once('.').unwrap_or((value, "x"));
+        let major = major.parse().map_err(|_| invalid())?;
+        let minor = match minor {
+            "x" => u8::MAX,
+            minor => minor.parse().map_err(|_| invalid())?,
+        };
+
+        Ok(Self { major, minor })
+    }
+}
+
+impl Display for TwigVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.minor == u8::MAX {
+            write!(f, "{}.x", self.major)
+        } else {
+            write!(f, "{}.{}", self.major, self.minor)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn twig_version_from_str() {
+        assert_eq!("3.12".parse(), Ok(TwigVersion::new(3, 12)));
+        assert_eq!("3.x".parse(), Ok(TwigVersion::new(3, u8::MAX)));
+        assert_eq!("2".parse(), Ok(TwigVersion::new(2, u8::MAX)));
+        assert!("three".parse::<TwigVersion>().is_err());
+        assert!("3.1a".parse::<TwigVersion>().is_err());
+    }
+
+    #[test]
+    fn twig_version_display() {
+        assert_eq!(TwigVersion::new(2, 7).to_string(), "2.7");
+        assert_eq!(TwigVersion::LATEST.to_string(), "3.x");
+    }
+
+    #[test]
+    fn twig_version_order() {
+        assert!(TwigVersion::new(2, 10) < TwigVersion::new(2, u8::MAX));
+        assert!(TwigVersion::new(2, u8::MAX) < TwigVersion::new(3, 0));
+        assert!(TwigVersion::new(3, 12) < TwigVersion::LATEST);
+    }
+}
@@ -1,3 +1,4 @@
+use std::cell::RefCell;
 use std::sync::Arc;
 
 use codespan_reporting::diagnostic::{Diagnostic, Label};
@@ -7,33 +8,86 @@ use codespan_reporting::term::termcolor::Buffer;
 
 use ludtwig_parser::syntax::typed;
 use ludtwig_parser::syntax::typed::{
-    AstNode, HtmlStringInner, HtmlTag, LudtwigDirectiveIgnore, TwigLiteralStringInner,
+    AstNode, HtmlStringInner, LudtwigDirectiveIgnore, TwigLiteralStringInner,
 };
-use ludtwig_parser::syntax::untyped::{debug_tree, SyntaxElement, SyntaxToken, WalkEvent};
+use ludtwig_parser::syntax::untyped::{
+    debug_tree, SyntaxElement, SyntaxNode, TextRange, WalkEvent,
+};
+use serde_json::{json, Value};
 
+use crate::check::layout::is_trivia_sensitive;
 use crate::check::rule::{
-    CheckResult, CheckSuggestion, RuleRunContext, Severity, TreeTraversalContext,
+    CheckNote, CheckResult, CheckSuggestion, RuleRunContext, Severity, TreeTraversalContext,
+};
+use crate::check::rules::{
+    apply_preset_severity_floors, check_suppressions, is_large_file, suppress_contained_results,
+    tracks_suppressions, EXPENSIVE_RULES,
 };
 use crate::process::FileContext;
 use crate::ProcessingEvent;
 
+pub mod class_properties;
+pub mod dependencies;
+pub mod layout;
 pub mod rule;
 pub mod rules;
+pub mod signatures;
+pub mod templates;
+
+thread_local! {
+    /// Directives which were respected by `check_root` of the running `run_rules` call of this thread
+    static USED_DIRECTIVES: RefCell<Option<Vec<(TextRange, String)>>> = const { RefCell::new(None) };
+}
+
+/// Findings which were not reported because of `ludtwig-ignore` / `ludtwig-ignore-file` directives.
+/// These are only tracked if the `unused-suppression` rule is active for the file.
+#[derive(Debug, Default)]
+pub struct Suppressions {
+    /// Range of every directive with the name of a rule it suppressed
+    pub(crate) used_directives: Vec<(TextRange, String)>,
+    /// The suppressed findings, rules which check the whole file at once (`check_root`)
+    /// skip their findings by themselves, so only the used directives are known for them
+    pub(crate) results: Vec<CheckResult>,
+}
+
+impl Suppressions {
+    fn add(&mut self, directive: TextRange, results: Vec<CheckResult>) {
+        for result in results {
+            self.used_directives
+                .push((directive, result.rule_name.to_string()));
+            self.results.push(result);
+        }
+    }
+
+    /// Rule names of all suppressed findings
+    pub fn rule_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.results.iter().map(|result| result.rule_name)
+    }
+}
 
-#[allow(clippy::too_many_lines)]
 pub fn run_rules(file_context: &FileContext) -> Vec<CheckResult> {
+    run_rules_with_suppressions(file_context).0
+}
+
+/// Run the rules of the file and also return the findings, which were suppressed by directives
+#[allow(clippy::too_many_lines)]
+pub fn run_rules_with_suppressions(file_context: &FileContext) -> (Vec<CheckResult>, Suppressions) {
     let mut check_results = vec![];
+    let mut suppressions = Suppressions::default();
     let mut run_context = RuleRunContext {
         cli_data: Arc::clone(&file_context.cli_context.data),
         traversal_ctx: TreeTraversalContext {
             inside_trivia_sensitive_node: false,
         },
+        file_path: file_context.file_path.clone(),
     };
 
     if file_context.file_rule_definitions.is_empty() {
         // no rules to run for this file
-        return vec![];
+        return (vec![], suppressions);
     }
+    // rules run inside of ignored code as well, to find out which directives are used
+    let track_suppressions = tracks_suppressions(&file_context.file_rule_definitions);
 
     /*
     Performance notes for future considerations:
@@ -46,38 +100,38 @@ pub fn run_rules(file_context: &FileContext) -> Vec<CheckResult> {
      */
 
     // run root node checks once for each rule
+    if track_suppressions {
+        USED_DIRECTIVES.with(|used| used.replace(Some(vec![])));
+    }
     let rule_results_iter = file_context
         .file_rule_definitions
         .iter()
         .filter_map(|rule| rule.check_root(file_context.tree_root.clone(), &run_context))
         .flatten();
     check_results.extend(rule_results_iter);
+    if let Some(mut used) = USED_DIRECTIVES.with(RefCell::take) {
+        suppressions.used_directives.append(&mut used);
+    }
 
     // iterate through syntax tree
-    let mut ignored_rules: Vec<String> = vec![];
+    // with the range of the active ignore directives and their ignored rules (empty for all rules)
+    let mut directives: Vec<(TextRange, Vec<String>)> = vec![];
     let mut preorder = file_context.tree_root.preorder_with_tokens();
     while let Some(walk_event) = preorder.next() {
         match walk_event {
             WalkEvent::Enter(element) => {
                 // add ignored rules when entering the sibling after the ignore directive / comment
                 // also skip the whole subtree if there is a ignore directive without specific rules
-                let mut found_ignored_rules: Vec<String> = match element.prev_sibling_or_token() {
-                    Some(SyntaxElement::Node(node)) => match LudtwigDirectiveIgnore::cast(node) {
-                        Some(directive) => {
-                            let ignored_rules = directive.get_rules();
-                            if ignored_rules.is_empty() {
-                                // all rules are disabled
-                                preorder.skip_subtree();
-                                continue;
-                            }
+                if let Some(directive) = ignore_directive_before(&element) {
+                    let ignored_rules = directive.get_rules();
+                    if ignored_rules.is_empty() && !track_suppressions {
+                        // all rules are disabled
+                        preorder.skip_subtree();
+                        continue;
+                    }
 
-                            ignored_rules
-                        }
-                        None => vec![],
-                    },
-                    _ => vec![],
-                };
-                ignored_rules.append(&mut found_ignored_rules);
+                    directives.push((directive.syntax().text_range(), ignored_rules));
+                }
 
                 // actually run the rules
                 match element {
@@ -92,78 +146,121 @@ pub fn run_rules(file_context: &FileContext) -> Vec<CheckResult> {
                         // adjust traversal context when entering special nodes
                         if HtmlStringInner::can_cast(n.kind())
                             || TwigLiteralStringInner::can_cast(n.kind())
+                            || is_trivia_sensitive(&n)
                         {
                             run_context.traversal_ctx.inside_trivia_sensitive_node = true;
-                        } else if let Some(t) = HtmlTag::cast(n.clone()) {
-                            if let Some("pre" | "textarea") =
-                                t.name().as_ref().map(SyntaxToken::text)
-                            {
-                                run_context.traversal_ctx.inside_trivia_sensitive_node = true;
-                            }
                         }
 
                         // run node checks for every rule
-                        let results = file_context
-                            .file_rule_definitions
-                            .iter()
-                            .filter_map(|rule| {
-                                if ignored_rules.iter().any(|ignored| ignored == rule.name()) {
-                                    None
-                                } else {
-                                    rule.check_node(n.clone(), &run_context)
-                                }
-                            })
-                            .flatten();
-                        check_results.extend(results);
+                        for rule in &file_context.file_rule_definitions {
+                            let ignored_by = ignoring_directive(&directives, rule.name());
+                            if ignored_by.is_some() && !track_suppressions {
+                                continue;
+                            }
+                            let Some(results) = rule.check_node(n.clone(), &run_context) else {
+                                continue;
+                            };
+                            match ignored_by {
+                                Some(directive) => suppressions.add(directive, results),
+                                None => check_results.extend(results),
+                            }
+                        }
                     }
                     SyntaxElement::Token(t) => {
                         // run token checks for every rule
-                        let results = file_context
-                            .file_rule_definitions
-                            .iter()
-                            .filter_map(|rule| {
-                                if ignored_rules.iter().any(|ignored| ignored == rule.name()) {
-                                    None
-                                } else {
-                                    rule.check_token(t.clone(), &run_context)
-                                }
-                            })
-                            .flatten();
-                        check_results.extend(results);
+                        for rule in &file_context.file_rule_definitions {
+                            let ignored_by = ignoring_directive(&directives, rule.name());
+                            if ignored_by.is_some() && !track_suppressions {
+                                continue;
+                            }
+                            let Some(results) = rule.check_token(t.clone(), &run_context) else {
+                                continue;
+                            };
+                            match ignored_by {
+                                Some(directive) => suppressions.add(directive, results),
+                                None => check_results.extend(results),
+                            }
+                        }
                     }
                 }
             }
             WalkEvent::Leave(element) => {
                 // remove ignored rules when leaving the sibling after the ignore directive / comment
-                let found_ignored_rules: Vec<String> = match element.prev_sibling_or_token() {
-                    Some(SyntaxElement::Node(node)) => match LudtwigDirectiveIgnore::cast(node) {
-                        Some(directive) => directive.get_rules(),
-                        None => vec![],
-                    },
-                    _ => vec![],
-                };
-
-                for rule in found_ignored_rules {
-                    ignored_rules.remove(ignored_rules.iter().position(|r| r == &rule).unwrap());
+                // (skipped subtrees never added them)
+                if let Some(directive) = ignore_directive_before(&element) {
+                    if directives
+                        .last()
+                        .is_some_and(|(range, _)| *range == directive.syntax().text_range())
+                    {
+                        directives.pop();
+                    }
                 }
 
                 // adjust traversal context when exiting special nodes
                 if let SyntaxElement::Node(n) = element {
                     if HtmlStringInner::can_cast(n.kind())
                         || TwigLiteralStringInner::can_cast(n.kind())
+                        || is_trivia_sensitive(&n)
                     {
                         run_context.traversal_ctx.inside_trivia_sensitive_node = false;
-                    } else if let Some(t) = HtmlTag::cast(n) {
-                        if let Some("pre" | "textarea") = t.name().as_ref().map(SyntaxToken::text) {
-                            run_context.traversal_ctx.inside_trivia_sensitive_node = false;
-                        }
                     }
                 }
             }
         }
     }
 
-    check_results
+    apply_preset_severity_floors(&file_context.cli_context.data.config, &mut check_results);
+    suppress_contained_results(&file_context.cli_context.data.config, &mut check_results);
+
+    if track_suppressions {
+        let unused_suppressions = check_suppressions(file_context, &mut suppressions);
+        check_results.extend(unused_suppressions);
+    }
+
+    (check_results, suppressions)
+}
+
+fn ignore_directive_before(element: &SyntaxElement) -> Option<LudtwigDirectiveIgnore> {
+    match element.prev_sibling_or_token() {
+        Some(SyntaxElement::Node(node)) => LudtwigDirectiveIgnore::cast(node),
+        _ => None,
+    }
+}
+
+/// Range of the innermost active directive, which ignores the rule
+fn ignoring_directive(
+    directives: &[(TextRange, Vec<String>)],
+    rule_name: &str,
+) -> Option<TextRange> {
+    directives
+        .iter()
+        .rev()
+        .find(|(_, rules)| rules.is_empty() || rules.iter().any(|r| r == rule_name))
+        .map(|(range, _)| *range)
+}
+
+/// Is the node ignored for the rule with a ludtwig-ignore directive in front of it
+/// (without specific rules or with the rule in its list).
+/// `run_rules` already respects the directives for `check_node` and `check_token`,
+/// rules which implement `check_root` have to call this themselves.
+/// A directive which ignores the rule counts as used for the `unused-suppression` rule,
+/// so this should be called for the nodes of findings (instead of every node up front).
+pub fn is_ignored_by_directive(node: &SyntaxNode, rule_name: &str) -> bool {
+    let Some(directive) = node.prev_sibling().and_then(LudtwigDirectiveIgnore::cast) else {
+        return false;
+    };
+    let ignored_rules = directive.get_rules();
+    if !ignored_rules.is_empty() && !ignored_rules.iter().any(|r| r == rule_name) {
+        return false;
+    }
+
+    // the directive counts as used for the unused-suppression rule
+    USED_DIRECTIVES.with(|used| {
+        if let Some(used) = used.borrow_mut().as_mut() {
+            used.push((directive.syntax().text_range(), rule_name.to_string()));
+        }
+    });
+    true
 }
 
 pub fn get_rule_context_suggestions(
@@ -178,6 +275,78 @@ pub fn get_rule_context_suggestions(
         .collect()
 }
 
+/// The reasoning of the rule and the syntax tree fragment around the location of a result.
+/// This helps to understand why a rule reported something on code which looks fine at a glance.
+pub fn explain_result(file_context: &FileContext, result: &CheckResult) -> Vec<String> {
+    let mut notes = vec![];
+
+    if let Some(explanation) = file_context
+        .cli_context
+        .data
+        .rule_registry
+        .get(result.rule_name)
+        .ok()
+        .and_then(rule::Rule::explanation)
+    {
+        notes.push(format!("why: {explanation}"));
+    }
+
+    if let Some(range) = result.covered_range() {
+        let node = covering_node(&file_context.tree_root, range);
+        let mut path: Vec<String> = node
+            .ancestors()
+            .map(|n| format!("{:?}", n.kind()))
+            .collect();
+        path.reverse();
+        notes.push(format!("found in: {}", path.join(" > ")));
+        notes.push(debug_tree(&node));
+    }
+
+    notes
+}
+
+/// The smallest node which contains the whole range
+fn covering_node(root: &SyntaxNode, range: TextRange) -> SyntaxNode {
+    if !root.text_range().contains_range(range) {
+        return root.clone();
+    }
+
+    match root.covering_element(range) {
+        SyntaxElement::Node(node) => node,
+        SyntaxElement::Token(token) => token.parent().unwrap_or_else(|| root.clone()),
+    }
+}
+
+/// Note about `--fix` stopping after `general.max-fix-passes`, while the rules still suggested fixes
+/// (otherwise every suggestion is applied until none are left)
+fn fix_not_converged_note(
+    file_context: &FileContext,
+    rule_results: &[CheckResult],
+) -> Option<Diagnostic<usize>> {
+    let mut unfixed_rules: Vec<&str> = get_rule_context_suggestions(rule_results)
+        .iter()
+        .map(|(rule_name, _)| *rule_name)
+        .collect();
+    unfixed_rules.sort_unstable();
+    unfixed_rules.dedup();
+    if !file_context.cli_context.data.fix || unfixed_rules.is_empty() {
+        return None;
+    }
+
+    let diagnostic = Diagnostic::note()
+        .with_code("FixNotConverged")
+        .with_message(format!(
+            "fixes were applied {} times (general.max-fix-passes), but the rules still suggest fixes",
+            file_context.cli_context.data.config.general.max_fix_passes
+        ))
+        .with_notes(vec![format!(
+            "unfixed rules: {} (these may be fighting each other, run ludtwig again or write a bug report)",
+            unfixed_rules.join(", ")
+        )]);
+    Some(diagnostic)
+}
+
+#[allow(clippy::too_many_lines)]
 pub fn produce_diagnostics(
     file_context: &FileContext,
     rule_results: Vec<CheckResult>,
@@ -206,6 +375,43 @@ pub fn produce_diagnostics(
         term::emit(buffer, &config, &files, &diagnostic).unwrap();
     }
 
+    let config_rules = &file_context.cli_context.data.rule_definitions;
+    let skipped_rules: Vec<&str> = config_rules
+        .iter()
+        .map(|rule| rule.name())
+        .filter(|name| {
+            EXPENSIVE_RULES.contains(name)
+                && !file_context
+                    .file_rule_definitions
+                    .iter()
+                    .any(|rule| rule.name() == *name)
+        })
+        .collect();
+    if is_large_file(
+        &file_context.cli_context.data.config,
+        &file_context.source_code,
+    ) && !skipped_rules.is_empty()
+    {
+        // notify output about this
+        file_context.send_processing_output(ProcessingEvent::Report(Severity::Info));
+
+        let diagnostic = Diagnostic::note()
+            .with_code("LargeFile")
+            .with_message(format!(
+                "file is larger than {} bytes (general.large-file-threshold), so expensive rules are skipped",
+                file_context.cli_context.data.config.general.large_file_threshold
+            ))
+            .with_notes(vec![format!("skipped rules: {}", skipped_rules.join(", "))]);
+
+        term::emit(buffer, &config, &files, &diagnostic).unwrap();
+    }
+
+    if let Some(diagnostic) = fix_not_converged_note(file_context, &rule_results) {
+        // notify output about this
+        file_context.send_processing_output(ProcessingEvent::Report(Severity::Info));
+        term::emit(buffer, &config, &files, &diagnostic).unwrap();
+    }
+
     // run through the parser errors
     for result in &file_context.parse_errors {
         // notify output about this
@@ -229,7 +435,13 @@ pub fn produce_diagnostics(
         };
 
         // notify output about this
-        file_context.send_processing_output(ProcessingEvent::Report(result.severity));
+        file_context.send_processing_output(ProcessingEvent::Report(result.severity.clone()));
+
+        let mut explanation = if file_context.cli_context.data.explain_context {
+            explain_result(file_context, &result)
+        } else {
+            vec![]
+        };
 
         let mut labels = vec![];
         if let Some(primary) = result.primary {
@@ -237,6 +449,12 @@ pub fn produce_diagnostics(
                 .push(Label::primary(file_id, primary.syntax_range).with_message(primary.message));
         }
 
+        for secondary in result.secondary {
+            labels.push(
+                Label::secondary(file_id, secondary.syntax_range).with_message(secondary.message),
+            );
+        }
+
         for suggestion in result.suggestions {
             labels.push(
                 Label::secondary(file_id, suggestion.syntax_range).with_message(format!(
@@ -246,11 +464,74 @@ pub fn produce_diagnostics(
             );
         }
 
+        // results without any location are about the whole file
+        let mut notes = if labels.is_empty() {
+            vec![format!(
+                "in file {}",
+                file_context.file_path.to_string_lossy()
+            )]
+        } else {
+            vec![]
+        };
+        notes.append(&mut explanation);
+
         let diagnostic = diagnostic
             .with_code(result.rule_name)
             .with_message(result.message)
-            .with_labels(labels);
+            .with_labels(labels)
+            .with_notes(notes);
 
         term::emit(buffer, &config, &files, &diagnostic).unwrap();
     }
 }
+
+/// The parser errors and rule check results in a machine-readable form (all ranges are byte offsets).
+pub fn produce_json(file_context: &FileContext, rule_results: &[CheckResult]) -> Value {
+    let parse_errors: Vec<Value> = file_context
+        .parse_errors
+        .iter()
+        .map(|error| {
+            json!({
+                "range": range_json(error.range),
+                "message": error.expected_message(),
+            })
+        })
+        .collect();
+
+    let diagnostics: Vec<Value> = rule_results.iter().map(result_json).collect();
+
+    json!({
+        "parse_errors": parse_errors,
+        "diagnostics": diagnostics,
+    })
+}
+
+/// A single rule check result in a machine-readable form (all ranges are byte offsets).
+pub fn result_json(result: &CheckResult) -> Value {
+    let severity = match result.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Help => "help",
+        Severity::Info => "info",
+    };
+    let note_json = |note: &CheckNote| json!({ "range": range_json(note.syntax_range), "message": note.message });
+
+    json!({
+        "rule": result.rule_name,
+        "severity": severity,
+        "message": result.message,
+        "primary": result.primary.as_ref().map(note_json),
+        "secondary": result.secondary.iter().map(note_json).collect::<Vec<_>>(),
+        // suggestions are applied automatically by `--fix`
+        "fixable": !result.suggestions.is_empty(),
+        "suggestions": result.suggestions.iter().map(|suggestion| json!({
+            "range": range_json(suggestion.syntax_range),
+            "replace_with": suggestion.replace_with,
+            "message": suggestion.message,
+        })).collect::<Vec<_>>(),
+    })
+}
+
+fn range_json(range: TextRange) -> Value {
+    json!({ "start": u32::from(range.start()), "end": u32::from(range.end()) })
+}
@@ -0,0 +1,194 @@
+//! Properties of PHP classes, which are used in templates through `{# @var product \Class\Name #}` annotations.
+//!
+//! The properties are read from user provided json files (`class-properties-files` in the config),
+//! which map fully qualified class names to the names of their properties and public methods, like
+//! `{"Shopware\\Core\\Content\\Product\\ProductEntity": ["name", "getTranslated", "isActive"]}`.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use once_cell::sync::Lazy;
+
+use crate::check::dependencies;
+use crate::Config;
+
+type ClassPropertiesCache = HashMap<
+    Vec<String>,
+    (
+        Vec<Option<SystemTime>>,
+        Result<Arc<ClassProperties>, String>,
+    ),
+>;
+
+/// Loaded class properties by their files and the modification times of the files
+/// (to notice changes while running as a daemon), so every combination is only read once
+static CLASS_PROPERTIES_CACHE: Lazy<Mutex<ClassPropertiesCache>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ClassProperties {
+    /// Property and method names by the class name without the leading backslash
+    classes: HashMap<String, Vec<String>>,
+}
+
+impl ClassProperties {
+    /// Names which can be accessed in twig with `variable.name` on an instance of the class.
+    /// Getters like `getName`, `isActive` and `hasChildren` are also accessible without the prefix.
+    pub fn accessible_names(&self, class_name: &str) -> Option<Vec<String>> {
+        let names = self.classes.get(normalize_class_name(class_name))?;
+
+        let mut accessible = vec![];
+        for name in names {
+            accessible.push(name.clone());
+            if let Some(property) = getter_property(name) {
+                accessible.push(property);
+            }
+        }
+
+        Some(accessible)
+    }
+}
+
+/// Get the class properties of the configured files
+pub fn get_class_properties(config: &Config) -> Result<Arc<ClassProperties>, String> {
+    let files = &config.rules.class_properties_files;
+
+    let modified: Vec<Option<SystemTime>> = files
+        .iter()
+        .map(|file| dependencies::modified_time(Path::new(file)))
+        .collect();
+
+    let mut cache = CLASS_PROPERTIES_CACHE.lock().unwrap();
+    if let Some((cached_modified, properties)) = cache.get(files) {
+        if cached_modified == &modified {
+            return properties.clone();
+        }
+    }
+
+    let properties = load_class_properties(files).map(Arc::new);
+    cache.insert(files.clone(), (modified, properties.clone()));
+    properties
+}
+
+fn load_class_properties(files: &[String]) -> Result<ClassProperties, String> {
+    let mut properties = ClassProperties::default();
+
+    for file in files {
+        let content =
+            std::fs::read_to_string(file).map_err(|e| format!("can't read {file}: {e}"))?;
+        let classes: HashMap<String, Vec<String>> =
+            serde_json::from_str(&content).map_err(|e| format!("can't parse {file}: {e}"))?;
+
+        for (class_name, names) in classes {
+            properties
+                .classes
+                .entry(normalize_class_name(&class_name).to_string())
+                .or_default()
+                .extend(names);
+        }
+    }
+
+    Ok(properties)
+}
+
+/// `\Shopware\Core\Framework\Struct` and `Shopware\Core\Framework\Struct` are the same class
+fn normalize_class_name(class_name: &str) -> &str {
+    class_name.trim().trim_start_matches('\\')
+}
+
+/// The property name of getters like `getName` (`name`), which twig also calls for `variable.name`
+fn getter_property(method_name: &str) -> Option<String> {
+    let property = ["get", "is", "has"]
+        .iter()
+        .find_map(|prefix| method_name.strip_prefix(prefix))?;
+
+    let mut chars = property.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_uppercase() => {
+            Some(first.to_ascii_lowercase().to_string() + chars.as_str())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_class_properties() {
+        let path = std::env::temp_dir().join("ludtwig-class-properties-load.json");
+        std::fs::write(
+            &path,
+            r#"{"\\App\\Product": ["id", "getName", "isActive", "history", "getter"]}"#,
+        )
+        .unwrap();
+
+        let properties = load_class_properties(&[path.to_string_lossy().to_string()]).unwrap();
+
+        assert_eq!(
+            properties.accessible_names("App\\Product"),
+            Some(
+                ["id", "getName", "name", "isActive", "active", "history", "getter"]
+                    .map(String::from)
+                    .to_vec()
+            )
+        );
+        assert_eq!(
+            properties.accessible_names("\\App\\Product"),
+            properties.accessible_names("App\\Product")
+        );
+        assert_eq!(properties.accessible_names("App\\Category"), None);
+    }
+
+    #[test]
+    fn reloads_changed_class_properties_files() {
+        let path = std::env::temp_dir().join(format!(
+            "ludtwig-class-properties-reload-{}.json",
+            std::process::id()
+        ));
+        std::fs::write(&path, r#"{"App\\Product": ["name"]}"#).unwrap();
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.class_properties_files = vec![path.to_string_lossy().to_string()];
+        assert_eq!(
+            get_class_properties(&config)
+                .unwrap()
+                .accessible_names("App\\Product"),
+            Some(vec!["name".to_string()])
+        );
+
+        // with a different modification time, the file system may be too coarse otherwise
+        std::fs::write(&path, r#"{"App\\Product": ["id"]}"#).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH)
+            .unwrap();
+        assert_eq!(
+            get_class_properties(&config)
+                .unwrap()
+                .accessible_names("App\\Product"),
+            Some(vec!["id".to_string()])
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn reports_invalid_class_properties_files() {
+        let path = std::env::temp_dir().join("ludtwig-class-properties-invalid.json");
+        std::fs::write(&path, r#"{"App\\Product": "name"}"#).unwrap();
+
+        assert!(load_class_properties(&[path.to_string_lossy().to_string()])
+            .unwrap_err()
+            .starts_with("can't parse"));
+        assert!(
+            load_class_properties(&["./does-not-exist.json".to_string()])
+                .unwrap_err()
+                .starts_with("can't read ./does-not-exist.json")
+        );
+    }
+}
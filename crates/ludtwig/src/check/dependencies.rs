@@ -0,0 +1,79 @@
+//! Other files which are read while checking a template (like parent templates),
+//! so the daemon can tell whether a previous result of the template is still valid.
+
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// A file with its modification time when it was read, `None` if it doesn't exist
+pub type FileDependency = (PathBuf, Option<SystemTime>);
+
+thread_local! {
+    /// Files read by the running `record_dependencies` call of this thread (rules run single threaded per file)
+    static RECORDED: RefCell<Option<Vec<FileDependency>>> = const { RefCell::new(None) };
+}
+
+/// Modification time of a file, which is recorded as a dependency of the running check.
+/// Caches of other files should use this to notice their changes.
+pub fn modified_time(path: &Path) -> Option<SystemTime> {
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+
+    RECORDED.with(|recorded| {
+        if let Some(files) = recorded.borrow_mut().as_mut() {
+            if !files.iter().any(|(p, _)| p == path) {
+                files.push((path.to_path_buf(), modified));
+            }
+        }
+    });
+
+    modified
+}
+
+/// Run the closure and collect all files it depends on
+pub fn record_dependencies<T>(f: impl FnOnce() -> T) -> (T, Vec<FileDependency>) {
+    let previous = RECORDED.with(|recorded| recorded.replace(Some(vec![])));
+    let value = f();
+    let files = RECORDED.with(|recorded| recorded.replace(previous));
+
+    (value, files.unwrap_or_default())
+}
+
+/// Whether none of the files changed since they were recorded
+pub fn dependencies_unchanged(files: &[FileDependency]) -> bool {
+    files.iter().all(|(path, modified)| {
+        std::fs::metadata(path).and_then(|m| m.modified()).ok() == *modified
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_read_files() {
+        let path =
+            std::env::temp_dir().join(format!("ludtwig-dependencies-{}.txt", std::process::id()));
+        std::fs::write(&path, "a").unwrap();
+
+        let (value, files) = record_dependencies(|| {
+            modified_time(&path);
+            modified_time(&path);
+            modified_time(Path::new("./missing-dependency.txt"));
+            42
+        });
+        assert_eq!(value, 42);
+        assert_eq!(files.len(), 2);
+        assert!(dependencies_unchanged(&files));
+
+        // a changed modification time invalidates the recorded files
+        let changed = vec![(path.clone(), Some(SystemTime::UNIX_EPOCH))];
+        assert!(!dependencies_unchanged(&changed));
+
+        // nothing is recorded outside of `record_dependencies`
+        let ((), files) = record_dependencies(|| ());
+        modified_time(&path);
+        assert!(files.is_empty());
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,89 @@
+//! Expected layout of the whitespace in a template, shared by the rules which format it
+//! (like `indentation` and `max-line-length`), so their fixes don't contradict each other.
+
+use ludtwig_parser::syntax::typed::{AstNode, Body, HtmlCdata, HtmlTag, TwigTrans, TwigVerbatim};
+use ludtwig_parser::syntax::untyped::SyntaxNode;
+
+use crate::config::Format;
+
+/// Html tags which render their whitespace as written
+static TRIVIA_SENSITIVE_TAGS: &[&str] = &["pre", "textarea"];
+
+/// Is the whitespace inside of the node part of the content, so it must not be changed.
+/// This is the case for `<pre>`, `<textarea>`, `<![CDATA[ ]]>`, `{% verbatim %}` and the message of `{% trans %}`.
+pub(crate) fn is_trivia_sensitive(node: &SyntaxNode) -> bool {
+    HtmlCdata::can_cast(node.kind())
+        || TwigVerbatim::can_cast(node.kind())
+        || is_trans_body(node)
+        || HtmlTag::cast(node.clone()).is_some_and(|tag| {
+            tag.name().is_some_and(|name| {
+                TRIVIA_SENSITIVE_TAGS
+                    .iter()
+                    .any(|t| t.eq_ignore_ascii_case(name.text()))
+            })
+        })
+}
+
+/// The message of a `{% trans %}` tag, where the whitespace is part of the translation key
+pub(crate) fn is_trans_body(node: &SyntaxNode) -> bool {
+    Body::can_cast(node.kind()) && node.parent().is_some_and(|p| TwigTrans::can_cast(p.kind()))
+}
+
+/// Whitespace in front of a line with the indentation level (nesting depth)
+/// and additional spaces for alignment (like attributes after the tag name)
+pub(crate) fn indentation_string(format: &Format, level: usize, alignment: usize) -> String {
+    std::iter::repeat_n(
+        format.indentation_mode.corresponding_char(),
+        level * format.indentation_count as usize,
+    )
+    .chain(std::iter::repeat_n(' ', alignment))
+    .collect()
+}
+
+/// Spaces to align attributes on following lines with the first attribute after the tag name,
+/// like `<a class="link"` followed by `   href="/home"`
+pub(crate) fn attribute_alignment(tag_name: &str) -> usize {
+    1 + tag_name.chars().count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use ludtwig_parser::syntax::untyped::SyntaxNode;
+
+    use super::*;
+    use crate::config::{Config, IndentationMode};
+
+    #[test]
+    fn trivia_sensitive_nodes() {
+        let root = SyntaxNode::new_root(
+            ludtwig_parser::parse(
+                "<PRE> a </PRE><div> b </div>{% verbatim %} c {% endverbatim %}{% trans %} d {% endtrans %}",
+            )
+            .green_node,
+        );
+
+        let sensitive: Vec<String> = root
+            .descendants()
+            .filter(is_trivia_sensitive)
+            .map(|n| n.text().to_string())
+            .collect();
+        assert_eq!(
+            sensitive,
+            vec!["<PRE> a </PRE>", "{% verbatim %} c {% endverbatim %}", " d"]
+        );
+    }
+
+    #[test]
+    fn indentation() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        assert_eq!(indentation_string(&config.format, 2, 0), " ".repeat(8));
+        assert_eq!(
+            indentation_string(&config.format, 1, attribute_alignment("div")),
+            " ".repeat(9)
+        );
+
+        config.format.indentation_mode = IndentationMode::Tab;
+        config.format.indentation_count = 1;
+        assert_eq!(indentation_string(&config.format, 2, 3), "\t\t   ");
+    }
+}
@@ -1,12 +1,37 @@
 use crate::{CliSharedData, Config};
+use ludtwig_parser::syntax::editor::SyntaxEdit;
 use ludtwig_parser::syntax::untyped::{SyntaxNode, SyntaxToken, TextRange};
 use std::fmt::{Debug, Formatter};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// A check which runs on every parsed template.
+///
+/// With `--fix` the suggestions of all results are applied in passes: after applying the
+/// (non overlapping) suggestions the template is parsed again and all rules run again, until no rule
+/// suggests a fix anymore (at most `general.max-fix-passes` times). So a rule only has to suggest
+/// the fixes it can safely make on the current tree and can rely on the next pass for problems, which
+/// only show up after its fix (like the indentation of newly inserted line breaks).
 pub trait Rule: Sync {
     /// A unique, kebab-case name for the rule.
     fn name(&self) -> &'static str;
 
+    /// Why the rule exists, which is shown together with the results when explaining them
+    /// (`--explain-context` cli option or the `explain` method of the daemon).
+    #[inline]
+    fn explanation(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Validate the configuration of the rule (like files it reads), which happens once when the
+    /// config is loaded instead of reporting the problem for every checked file.
+    /// Defaults to accepting every configuration.
+    #[allow(unused_variables)]
+    #[inline]
+    fn validate_config(&self, config: &Config) -> Result<(), String> {
+        Ok(())
+    }
+
     /// Check an individual untyped node in the syntax tree.
     /// The conversion to a typed AST node can be made at any time with a simple call to cast.
     /// Defaults to doing nothing.
@@ -37,6 +62,7 @@ pub trait Rule: Sync {
     /// Be Careful, rules that use this must follow this by themselves:
     /// - when iterating you should most likely skip `SyntaxKind::Error` Nodes!
     /// - you need to check for `prev_siblings` of `LudtwigDirectiveIgnore` and respect the ignored rules!
+    ///   (see `crate::check::is_ignored_by_directive`)
     ///
     /// The conversion to a typed AST node can be made at any time with a simple call to cast.
     /// Defaults to doing nothing.
@@ -63,6 +89,7 @@ impl<R: Rule> RuleExt for R {
             severity,
             message: message.into(),
             primary: None,
+            secondary: vec![],
             suggestions: vec![],
         }
     }
@@ -85,6 +112,7 @@ pub struct RuleRunContext {
     // source_text
     pub(super) cli_data: Arc<CliSharedData>,
     pub(super) traversal_ctx: TreeTraversalContext,
+    pub(super) file_path: PathBuf,
 }
 
 impl RuleRunContext {
@@ -95,6 +123,10 @@ impl RuleRunContext {
     pub fn traversal_ctx(&self) -> &TreeTraversalContext {
         &self.traversal_ctx
     }
+
+    pub fn file_path(&self) -> &Path {
+        &self.file_path
+    }
 }
 
 #[derive(Debug)]
@@ -104,6 +136,7 @@ pub struct CheckResult {
     pub(super) severity: Severity,
     pub(super) message: String,
     pub(super) primary: Option<CheckNote>,
+    pub(super) secondary: Vec<CheckNote>,
     pub(super) suggestions: Vec<CheckSuggestion>,
 }
 
@@ -111,6 +144,8 @@ impl CheckResult {
     // TODO: enforce only one primary_note call via type builder pattern
     /// The primary (red) label and location of the error, there should be only one of these per check result.
     /// Further context can be provided with multiple secondary notes.
+    ///
+    /// Results without a primary note (and without suggestions) are reported for the whole file.
     pub fn primary_note<S: Into<String>>(mut self, syntax_range: TextRange, message: S) -> Self {
         self.primary = Some(CheckNote {
             syntax_range,
@@ -119,14 +154,14 @@ impl CheckResult {
         self
     }
 
-    // The secondary (blue) label which can provide more context and explain the error to a user.
-    // pub fn secondary_note<S: Into<String>>(mut self, syntax_range: TextRange, message: S) -> Self {
-    //     self.secondary.push(CheckNote {
-    //         syntax_range,
-    //         message: message.into(),
-    //     });
-    //     self
-    // }
+    /// The secondary (blue) label which can provide more context and explain the error to a user.
+    pub fn secondary_note<S: Into<String>>(mut self, syntax_range: TextRange, message: S) -> Self {
+        self.secondary.push(CheckNote {
+            syntax_range,
+            message: message.into(),
+        });
+        self
+    }
 
     /// Add a code suggestion which the user can follow or is replaced automatically
     pub fn suggestion<R: Into<String>, S: Into<String>>(
@@ -142,6 +177,29 @@ impl CheckResult {
         });
         self
     }
+
+    /// The range which covers the primary note and all suggestions (if there are any)
+    pub fn covered_range(&self) -> Option<TextRange> {
+        self.primary
+            .iter()
+            .map(|n| n.syntax_range)
+            .chain(self.suggestions.iter().map(|s| s.syntax_range))
+            .reduce(TextRange::cover)
+    }
+
+    /// Only keep the code suggestions for which the predicate returns true
+    pub fn retain_suggestions<F: FnMut(&CheckSuggestion) -> bool>(&mut self, f: F) {
+        self.suggestions.retain(f);
+    }
+
+    /// Add every text edit of a structural change (made with a `SyntaxEditor`) as a code suggestion
+    pub fn edit_suggestions<S: Into<String>>(mut self, edit: &SyntaxEdit, message: S) -> Self {
+        let message = message.into();
+        for text_edit in &edit.text_edits {
+            self = self.suggestion(text_edit.range, &text_edit.replace_with, &message);
+        }
+        self
+    }
 }
 
 #[derive(Debug)]
@@ -157,7 +215,8 @@ pub struct CheckSuggestion {
     pub message: String,
 }
 
-#[derive(Debug, Clone)]
+/// Ordered from the most to the least severe variant.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Severity {
     /// Errors which must be fixed for the template to work correctly
     Error,
@@ -1,44 +1,174 @@
-use crate::check::rule::Rule;
+use crate::check::rule::{CheckResult, Rule, Severity};
+use crate::check::rules::a11y_anchor_content::RuleA11yAnchorContent;
+use crate::check::rules::a11y_button_type::RuleA11yButtonType;
+use crate::check::rules::a11y_form_control_label::RuleA11yFormControlLabel;
+use crate::check::rules::a11y_heading_order::RuleA11yHeadingOrder;
+use crate::check::rules::a11y_img_alt::RuleA11yImgAlt;
+use crate::check::rules::commented_out_code::RuleCommentedOutCode;
+use crate::check::rules::fix_inline_whitespace::RuleFixInlineWhitespace;
 use crate::check::rules::html_attribute_name_kebab_case::RuleHtmlAttributeNameKebabCase;
+use crate::check::rules::html_attribute_order::RuleHtmlAttributeOrder;
+use crate::check::rules::html_content_model::RuleHtmlContentModel;
+use crate::check::rules::html_data_attribute_naming::RuleHtmlDataAttributeNaming;
+use crate::check::rules::html_duplicate_attribute::RuleHtmlDuplicateAttribute;
+use crate::check::rules::html_duplicate_id::RuleHtmlDuplicateId;
+use crate::check::rules::html_empty_element::RuleHtmlEmptyElement;
+use crate::check::rules::html_lowercase_names::RuleHtmlLowercaseNames;
+use crate::check::rules::html_nbsp_style::RuleHtmlNbspStyle;
+use crate::check::rules::html_no_inline_script::RuleHtmlNoInlineScript;
+use crate::check::rules::html_no_inline_script_tag::RuleHtmlNoInlineScriptTag;
+use crate::check::rules::html_no_inline_style::RuleHtmlNoInlineStyle;
+use crate::check::rules::html_obsolete_element::RuleHtmlObsoleteElement;
+use crate::check::rules::html_self_closing::RuleHtmlSelfClosing;
+use crate::check::rules::html_slot_outside_vue::RuleHtmlSlotOutsideVue;
 use crate::check::rules::html_string_quotation::RuleHtmlStringQuotation;
+use crate::check::rules::html_target_blank_noopener::RuleHtmlTargetBlankNoopener;
+use crate::check::rules::html_untranslated_text::RuleHtmlUntranslatedText;
 use crate::check::rules::indentation::RuleIndentation;
 use crate::check::rules::line_ending::RuleLineEnding;
 use crate::check::rules::ludtwig_ignore_file_not_on_top::RuleLudtwigIgnoreFileNotOnTop;
+use crate::check::rules::max_line_length::RuleMaxLineLength;
+use crate::check::rules::max_nesting_depth::RuleMaxNestingDepth;
+use crate::check::rules::no_invisible_characters::RuleNoInvisibleCharacters;
+use crate::check::rules::no_trailing_whitespace::RuleNoTrailingWhitespace;
+use crate::check::rules::require_final_newline::RuleRequireFinalNewline;
+use crate::check::rules::twig_block_end_comment::RuleTwigBlockEndComment;
 use crate::check::rules::twig_block_line_breaks::RuleTwigBlockLineBreaks;
+use crate::check::rules::twig_block_name_convention::RuleTwigBlockNameConvention;
 use crate::check::rules::twig_block_name_snake_case::RuleTwigBlockNameSnakeCase;
+use crate::check::rules::twig_block_not_in_parent::RuleTwigBlockNotInParent;
+use crate::check::rules::twig_cache_key_in_loop::RuleTwigCacheKeyInLoop;
+use crate::check::rules::twig_content_outside_block::RuleTwigContentOutsideBlock;
+use crate::check::rules::twig_delimiter_spacing::RuleTwigDelimiterSpacing;
+use crate::check::rules::twig_deprecated::RuleTwigDeprecated;
+use crate::check::rules::twig_duplicate_block_name::RuleTwigDuplicateBlockName;
+use crate::check::rules::twig_duplicate_hash_key::RuleTwigDuplicateHashKey;
+use crate::check::rules::twig_empty_block::RuleTwigEmptyBlock;
+use crate::check::rules::twig_expression_spacing::RuleTwigExpressionSpacing;
+use crate::check::rules::twig_extends_first::RuleTwigExtendsFirst;
+use crate::check::rules::twig_form_helper_pairs::RuleTwigFormHelperPairs;
 use crate::check::rules::twig_hash_key_no_quotes::RuleTwigHashKeyNoQuotes;
+use crate::check::rules::twig_identical_branches::RuleTwigIdenticalBranches;
+use crate::check::rules::twig_invalid_arguments::RuleTwigInvalidArguments;
+use crate::check::rules::twig_literal_lowercase::RuleTwigLiteralLowercase;
 use crate::check::rules::twig_logic_and::RuleTwigLogicAnd;
 use crate::check::rules::twig_logic_or::RuleTwigLogicOr;
+use crate::check::rules::twig_max_block_size::RuleTwigMaxBlockSize;
+use crate::check::rules::twig_no_autoescape_off::RuleTwigNoAutoescapeOff;
+use crate::check::rules::twig_no_nested_ternary::RuleTwigNoNestedTernary;
+use crate::check::rules::twig_no_unsafe_raw::RuleTwigNoUnsafeRaw;
+use crate::check::rules::twig_possibly_undefined_variable::RuleTwigPossiblyUndefinedVariable;
+use crate::check::rules::twig_prefer_null_coalescing::RuleTwigPreferNullCoalescing;
 use crate::check::rules::twig_prefer_shopware_extends::RuleTwigPreferShopwareExtends;
+use crate::check::rules::twig_require_parentheses::RuleTwigRequireParentheses;
+use crate::check::rules::twig_route_validation::RuleTwigRouteValidation;
+use crate::check::rules::twig_script_output_escape::RuleTwigScriptOutputEscape;
+use crate::check::rules::twig_single_extends::RuleTwigSingleExtends;
 use crate::check::rules::twig_string_quotation::RuleTwigStringQuotation;
+use crate::check::rules::twig_trailing_comma::RuleTwigTrailingComma;
+use crate::check::rules::twig_translation_key_format::RuleTwigTranslationKeyFormat;
+use crate::check::rules::twig_type_check::RuleTwigTypeCheck;
+use crate::check::rules::twig_unknown_filter::RuleTwigUnknownFilter;
+use crate::check::rules::twig_unknown_function::RuleTwigUnknownFunction;
+use crate::check::rules::twig_unknown_property::RuleTwigUnknownProperty;
+use crate::check::rules::twig_unknown_test::RuleTwigUnknownTest;
+use crate::check::rules::twig_unused_variable::RuleTwigUnusedVariable;
 use crate::check::rules::twig_use_is_not_same_as::RuleTwigUseIsNotSameAs;
 use crate::check::rules::twig_use_is_same_as::RuleTwigUseIsSameAs;
 use crate::check::rules::unknown_token::RuleUnknownToken;
+use crate::check::rules::unused_suppression::RuleUnusedSuppression;
+use crate::check::rules::vue_v_for::RuleVueVFor;
 use crate::check::rules::whitespace_between_line_breaks::RuleWhitespaceBetweenLineBreaks;
+use crate::check::{run_rules_with_suppressions, Suppressions};
 use crate::error::ConfigurationError;
+use crate::process::FileContext;
 use crate::Config;
 use ludtwig_parser::syntax::typed::{AstNode, LudtwigDirectiveFileIgnore};
 use ludtwig_parser::syntax::untyped::SyntaxNode;
 
+mod a11y_anchor_content;
+mod a11y_button_type;
+mod a11y_form_control_label;
+mod a11y_heading_order;
+mod a11y_img_alt;
+mod commented_out_code;
+mod fix_inline_whitespace;
 mod html_attribute_name_kebab_case;
+mod html_attribute_order;
+mod html_content_model;
+mod html_data_attribute_naming;
+mod html_duplicate_attribute;
+mod html_duplicate_id;
+mod html_empty_element;
+mod html_lowercase_names;
+mod html_nbsp_style;
+mod html_no_inline_script;
+mod html_no_inline_script_tag;
+mod html_no_inline_style;
+mod html_obsolete_element;
+mod html_self_closing;
+mod html_slot_outside_vue;
 mod html_string_quotation;
+mod html_target_blank_noopener;
+mod html_untranslated_text;
 mod indentation;
 mod line_ending;
 mod ludtwig_ignore_file_not_on_top;
+mod max_line_length;
+mod max_nesting_depth;
+mod no_invisible_characters;
+mod no_trailing_whitespace;
+mod require_final_newline;
+mod twig_block_end_comment;
 mod twig_block_line_breaks;
+mod twig_block_name_convention;
 mod twig_block_name_snake_case;
+mod twig_block_not_in_parent;
+mod twig_cache_key_in_loop;
+mod twig_content_outside_block;
+mod twig_delimiter_spacing;
+mod twig_deprecated;
+mod twig_duplicate_block_name;
+mod twig_duplicate_hash_key;
+mod twig_empty_block;
+mod twig_expression_spacing;
+mod twig_extends_first;
+mod twig_form_helper_pairs;
 mod twig_hash_key_no_quotes;
+mod twig_identical_branches;
+mod twig_invalid_arguments;
+mod twig_literal_lowercase;
 mod twig_logic_and;
 mod twig_logic_or;
+mod twig_max_block_size;
+mod twig_no_autoescape_off;
+mod twig_no_nested_ternary;
+mod twig_no_unsafe_raw;
+mod twig_possibly_undefined_variable;
+mod twig_prefer_null_coalescing;
 mod twig_prefer_shopware_extends;
+mod twig_require_parentheses;
+mod twig_route_validation;
+mod twig_script_output_escape;
+mod twig_single_extends;
 mod twig_string_quotation;
+mod twig_trailing_comma;
+mod twig_translation_key_format;
+mod twig_type_check;
+mod twig_unknown_filter;
+mod twig_unknown_function;
+mod twig_unknown_property;
+mod twig_unknown_test;
+mod twig_unused_variable;
 mod twig_use_is_not_same_as;
 mod twig_use_is_same_as;
 mod unknown_token;
+mod unused_suppression;
+mod vue_v_for;
 mod whitespace_between_line_breaks;
 
-/// List of all rule trait objects, also add them to the `active-rules` in `ludtwig-config.toml`!
-pub static RULE_DEFINITIONS: &[&'static dyn Rule] = &[
+/// List of all built-in rule trait objects, also add them to the `active-rules` in `ludtwig-config.toml`!
+static BUILTIN_RULE_DEFINITIONS: &[&'static dyn Rule] = &[
     &RuleLudtwigIgnoreFileNotOnTop,
     &RuleUnknownToken,
     &RuleWhitespaceBetweenLineBreaks,
@@ -55,21 +185,346 @@ pub static RULE_DEFINITIONS: &[&'static dyn Rule] = &[
     &RuleTwigPreferShopwareExtends,
     &RuleTwigUseIsSameAs,
     &RuleTwigUseIsNotSameAs,
+    &RuleTwigScriptOutputEscape,
+    &RuleTwigNoAutoescapeOff,
+    &RuleTwigRouteValidation,
+    &RuleTwigFormHelperPairs,
+    &RuleNoInvisibleCharacters,
+    &RuleHtmlNbspStyle,
+    &RuleHtmlDataAttributeNaming,
+    &RuleTwigTypeCheck,
+    &RuleTwigLiteralLowercase,
+    &RuleTwigRequireParentheses,
+    &RuleTwigNoNestedTernary,
+    &RuleTwigIdenticalBranches,
+    &RuleTwigContentOutsideBlock,
+    &RuleTwigExtendsFirst,
+    &RuleTwigBlockNotInParent,
+    &RuleTwigSingleExtends,
+    &RuleTwigUnknownFilter,
+    &RuleTwigUnknownFunction,
+    &RuleTwigUnknownTest,
+    &RuleTwigDeprecated,
+    &RuleTwigUnusedVariable,
+    &RuleTwigPossiblyUndefinedVariable,
+    &RuleTwigCacheKeyInLoop,
+    &RuleTwigDuplicateBlockName,
+    &RuleTwigBlockNameConvention,
+    &RuleHtmlAttributeOrder,
+    &RuleHtmlDuplicateAttribute,
+    &RuleA11yImgAlt,
+    &RuleA11yButtonType,
+    &RuleA11yFormControlLabel,
+    &RuleA11yAnchorContent,
+    &RuleTwigNoUnsafeRaw,
+    &RuleHtmlTargetBlankNoopener,
+    &RuleHtmlNoInlineScript,
+    &RuleMaxLineLength,
+    &RuleMaxNestingDepth,
+    &RuleTwigEmptyBlock,
+    &RuleHtmlEmptyElement,
+    &RuleHtmlContentModel,
+    &RuleHtmlLowercaseNames,
+    &RuleHtmlSlotOutsideVue,
+    &RuleFixInlineWhitespace,
+    &RuleHtmlSelfClosing,
+    &RuleNoTrailingWhitespace,
+    &RuleRequireFinalNewline,
+    &RuleUnusedSuppression,
+    &RuleTwigDelimiterSpacing,
+    &RuleTwigExpressionSpacing,
+    &RuleTwigPreferNullCoalescing,
+    &RuleTwigDuplicateHashKey,
+    &RuleTwigMaxBlockSize,
+    &RuleTwigBlockEndComment,
+    &RuleTwigTrailingComma,
+    &RuleTwigInvalidArguments,
+    &RuleHtmlUntranslatedText,
+    &RuleTwigTranslationKeyFormat,
+    &RuleTwigUnknownProperty,
+    &RuleHtmlObsoleteElement,
+    &RuleHtmlDuplicateId,
+    &RuleVueVFor,
+    &RuleCommentedOutCode,
+    &RuleHtmlNoInlineStyle,
+    &RuleHtmlNoInlineScriptTag,
+    &RuleA11yHeadingOrder,
 ];
 
-/// Get active rule definitions based on config
+/// All rules which can be activated in the config.
+/// The default registry contains the built-in rules, forks can compose additional rules with
+/// `RuleRegistry::default().with(&MyRule)` where the registry is created in `main`.
+#[derive(Debug, Clone)]
+pub struct RuleRegistry {
+    definitions: Vec<&'static dyn Rule>,
+}
+
+impl Default for RuleRegistry {
+    fn default() -> Self {
+        BUILTIN_RULE_DEFINITIONS.iter().fold(
+            Self {
+                definitions: vec![],
+            },
+            |registry, rule| registry.with(*rule),
+        )
+    }
+}
+
+impl RuleRegistry {
+    /// Register an additional rule. A rule with the same name as an already registered one replaces it.
+    #[must_use]
+    pub fn with(mut self, rule: &'static dyn Rule) -> Self {
+        match self
+            .definitions
+            .iter_mut()
+            .find(|r| r.name() == rule.name())
+        {
+            Some(existing) => *existing = rule,
+            None => self.definitions.push(rule),
+        }
+        self
+    }
+
+    /// All registered rule definitions in the order they run
+    pub fn definitions(&self) -> &[&'static dyn Rule] {
+        &self.definitions
+    }
+
+    /// Get a single rule definition by its name
+    pub fn get(&self, name: &str) -> Result<&'static dyn Rule, ConfigurationError> {
+        self.definitions
+            .iter()
+            .find(|r| r.name() == name)
+            .copied()
+            .ok_or_else(|| ConfigurationError::RuleNotFound {
+                name: name.to_string(),
+            })
+    }
+}
+
+/// A named group of rules which can be activated with `extends = ["ludtwig:<name>"]` in the config.
+#[derive(Debug)]
+pub struct RulePreset {
+    pub name: &'static str,
+    pub rules: &'static [&'static str],
+    /// Results of the preset rules are reported with at least this severity.
+    pub severity_floor: Option<Severity>,
+}
+
+/// List of all presets, every rule name in here must exist in `BUILTIN_RULE_DEFINITIONS`!
+pub static RULE_PRESETS: &[RulePreset] = &[
+    RulePreset {
+        name: "ludtwig:security",
+        rules: &[
+            "twig-script-output-escape",
+            "twig-no-autoescape-off",
+            "twig-no-unsafe-raw",
+            "html-target-blank-noopener",
+            "html-no-inline-script",
+        ],
+        severity_floor: Some(Severity::Warning),
+    },
+    RulePreset {
+        name: "ludtwig:a11y",
+        rules: &[
+            "a11y-img-alt",
+            "a11y-button-type",
+            "a11y-form-control-label",
+            "a11y-anchor-content",
+            "a11y-heading-order",
+        ],
+        severity_floor: None,
+    },
+];
+
+/// Rules which analyze the whole file (like variable scopes) or read other templates.
+/// These are skipped for files above `general.large-file-threshold`, like generated sitemaps or feeds.
+pub static EXPENSIVE_RULES: &[&str] = &[
+    "twig-type-check",
+    "twig-possibly-undefined-variable",
+    "twig-unused-variable",
+    "twig-cache-key-in-loop",
+    "twig-block-not-in-parent",
+    "twig-block-name-convention",
+    "twig-identical-branches",
+];
+
+/// Validate the applied fixes by comparing the tree before and after applying them
+/// (if the `fix-inline-whitespace` rule is active for the file)
+pub fn check_applied_fixes(
+    file_rules: &[&'static dyn Rule],
+    original: &SyntaxNode,
+    fixed: &SyntaxNode,
+) -> Vec<CheckResult> {
+    if file_rules
+        .iter()
+        .any(|rule| rule.name() == RuleFixInlineWhitespace.name())
+    {
+        RuleFixInlineWhitespace.check_fix(original, fixed)
+    } else {
+        vec![]
+    }
+}
+
+/// Whether the `unused-suppression` rule is active for the file, which needs to know
+/// the findings suppressed by the directives (see `run_rules_with_suppressions`)
+pub fn tracks_suppressions(file_rules: &[&'static dyn Rule]) -> bool {
+    file_rules
+        .iter()
+        .any(|rule| rule.name() == RuleUnusedSuppression.name())
+}
+
+/// Report `ludtwig-ignore` and `ludtwig-ignore-file` directives which don't suppress any finding.
+/// The rules disabled by `ludtwig-ignore-file` directives run here (only them), their findings are
+/// added to the suppressions.
+pub fn check_suppressions(
+    file_context: &FileContext,
+    suppressions: &mut Suppressions,
+) -> Vec<CheckResult> {
+    let data = &file_context.cli_context.data;
+    let file_directives: Vec<LudtwigDirectiveFileIgnore> = file_context
+        .tree_root
+        .children()
+        .filter_map(LudtwigDirectiveFileIgnore::cast)
+        .collect();
+    let file_ignored_rules: Vec<&'static dyn Rule> = data
+        .rule_definitions
+        .iter()
+        .filter(|rule| {
+            !file_context
+                .file_rule_definitions
+                .iter()
+                .any(|r| r.name() == rule.name())
+                && file_directives
+                    .iter()
+                    .any(|d| d.get_rules().iter().any(|r| r == rule.name()))
+        })
+        .copied()
+        .collect();
+
+    if !file_ignored_rules.is_empty() {
+        let ignored_context = FileContext {
+            cli_context: file_context.cli_context.clone(),
+            file_path: file_context.file_path.clone(),
+            tree_root: file_context.tree_root.clone(),
+            source_code: file_context.source_code.clone(),
+            parse_errors: vec![],
+            file_rule_definitions: skip_expensive_rules(
+                &data.config,
+                &file_context.source_code,
+                file_ignored_rules,
+            ),
+        };
+        let (results, _) = run_rules_with_suppressions(&ignored_context);
+        for result in results {
+            // the first directive with the rule suppressed it
+            if let Some(directive) = file_directives
+                .iter()
+                .find(|d| d.get_rules().iter().any(|r| r == result.rule_name))
+            {
+                suppressions.used_directives.push((
+                    directive.syntax().text_range(),
+                    result.rule_name.to_string(),
+                ));
+                suppressions.results.push(result);
+            }
+        }
+    }
+
+    RuleUnusedSuppression.check_suppressions(
+        &file_context.tree_root,
+        &data.rule_registry,
+        &file_context.file_rule_definitions,
+        &data.rule_definitions,
+        suppressions,
+    )
+}
+
+/// Get the presets the config extends from
+pub fn get_config_presets(config: &Config) -> Result<Vec<&'static RulePreset>, ConfigurationError> {
+    config
+        .general
+        .extends
+        .iter()
+        .map(|name| {
+            RULE_PRESETS
+                .iter()
+                .find(|p| p.name == name)
+                .ok_or_else(|| ConfigurationError::PresetNotFound { name: name.clone() })
+        })
+        .collect()
+}
+
+/// Raise the severity of results to the severity floor of the extended presets
+pub fn apply_preset_severity_floors(config: &Config, results: &mut [CheckResult]) {
+    let Ok(presets) = get_config_presets(config) else {
+        return;
+    };
+
+    for result in results {
+        for preset in &presets {
+            if let Some(floor) = &preset.severity_floor {
+                if preset.rules.contains(&result.rule_name) && result.severity > *floor {
+                    result.severity = floor.clone();
+                }
+            }
+        }
+    }
+}
+
+/// Remove results which are fully contained in a result of a related rule (see `suppress-contained` in the config)
+/// with at least the same severity.
+pub fn suppress_contained_results(config: &Config, results: &mut Vec<CheckResult>) {
+    let relations = &config.general.suppress_contained;
+    if relations.is_empty() {
+        return;
+    }
+
+    let suppressed: Vec<bool> = results
+        .iter()
+        .map(|result| {
+            let Some(range) = result.covered_range() else {
+                return false;
+            };
+
+            results.iter().any(|other| {
+                relations
+                    .get(other.rule_name)
+                    .is_some_and(|rules| rules.iter().any(|r| r == result.rule_name))
+                    && other.severity <= result.severity
+                    && other
+                        .covered_range()
+                        .is_some_and(|other_range| other_range.contains_range(range))
+            })
+        })
+        .collect();
+
+    let mut suppressed = suppressed.into_iter();
+    results.retain(|_| !suppressed.next().unwrap_or_default());
+}
+
+/// Get active rule definitions of the registry based on config
 pub fn get_config_active_rule_definitions(
+    registry: &RuleRegistry,
     config: &Config,
 ) -> Result<Vec<&'static dyn Rule>, ConfigurationError> {
-    // gather active rules
-    let config_active_rules: Vec<&str> = config
+    // gather active rules (including the ones of extended presets)
+    let mut config_active_rules: Vec<&str> = config
         .general
         .active_rules
         .iter()
         .map(String::as_ref)
         .collect();
+    for preset in get_config_presets(config)? {
+        for rule in preset.rules {
+            if !config_active_rules.contains(rule) {
+                config_active_rules.push(rule);
+            }
+        }
+    }
 
-    let active_rules: Vec<&'static dyn Rule> = RULE_DEFINITIONS
+    let active_rules: Vec<&'static dyn Rule> = registry
+        .definitions()
         .iter()
         .filter_map(|r| {
             if config_active_rules.contains(&r.name()) {
@@ -96,9 +551,39 @@ pub fn get_config_active_rule_definitions(
         }
     }
 
+    for rule in &active_rules {
+        rule.validate_config(config)
+            .map_err(|message| ConfigurationError::InvalidRuleConfig {
+                rule: rule.name().to_string(),
+                message,
+            })?;
+    }
+
     Ok(active_rules)
 }
 
+/// Is the file larger than the configured threshold, where expensive rules are skipped
+pub fn is_large_file(config: &Config, source_code: &str) -> bool {
+    let threshold = config.general.large_file_threshold;
+    threshold > 0 && source_code.len() > threshold
+}
+
+/// Remove the `EXPENSIVE_RULES` from the file rule definitions for large files
+pub fn skip_expensive_rules(
+    config: &Config,
+    source_code: &str,
+    definitions: Vec<&'static dyn Rule>,
+) -> Vec<&'static dyn Rule> {
+    if !is_large_file(config, source_code) {
+        return definitions;
+    }
+
+    definitions
+        .into_iter()
+        .filter(|d| !EXPENSIVE_RULES.contains(&d.name()))
+        .collect()
+}
+
 /// filter down config active rule definitions for a specific file
 /// after looking inside it for ludtwig-ignore-file directives
 pub fn get_file_active_rule_definitions(
@@ -130,20 +615,32 @@ pub fn get_file_active_rule_definitions(
 
 #[cfg(test)]
 pub mod test {
-    use std::path::PathBuf;
+    use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
     use std::sync::mpsc::Receiver;
     use std::sync::{mpsc, Arc};
 
     use codespan_reporting::term::termcolor::Buffer;
+    use expect_test::expect;
 
-    use ludtwig_parser::parse;
-    use ludtwig_parser::syntax::untyped::SyntaxNode;
+    use ludtwig_parser::parse_for_twig_version;
+    use ludtwig_parser::syntax::untyped::{SyntaxNode, TextRange};
 
     use crate::check::produce_diagnostics;
     use crate::check::rule::CheckResult;
-    use crate::check::rules::RULE_DEFINITIONS;
-    use crate::check::run_rules;
-    use crate::process::{iteratively_apply_suggestions, FileContext};
+    use crate::check::rule::{Rule, RuleExt, Severity};
+    use crate::check::rules::indentation::RuleIndentation;
+    use crate::check::rules::twig_block_line_breaks::RuleTwigBlockLineBreaks;
+    use crate::check::rules::twig_logic_and::RuleTwigLogicAnd;
+    use crate::check::rules::{
+        get_config_active_rule_definitions, get_file_active_rule_definitions, skip_expensive_rules,
+        suppress_contained_results, RuleRegistry, EXPENSIVE_RULES, RULE_PRESETS,
+    };
+    use crate::check::{
+        get_rule_context_suggestions, run_rules, run_rules_with_suppressions, Suppressions,
+    };
+    use crate::error::ConfigurationError;
+    use crate::process::{iteratively_apply_suggestions, preview_fix, FileContext};
     use crate::{CliContext, CliSharedData, Config, ProcessingEvent};
 
     fn debug_rule(
@@ -151,13 +648,54 @@ pub mod test {
         source_code: &str,
     ) -> (FileContext, Vec<CheckResult>, Receiver<ProcessingEvent>) {
         let config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        debug_rule_with_config(rule_name, source_code, config)
+    }
+
+    fn debug_rule_with_config(
+        rule_name: &str,
+        source_code: &str,
+        config: Config,
+    ) -> (FileContext, Vec<CheckResult>, Receiver<ProcessingEvent>) {
+        debug_rules_with_config(&[rule_name], source_code, config)
+    }
+
+    fn debug_rules_with_config(
+        rule_names: &[&str],
+        source_code: &str,
+        config: Config,
+    ) -> (FileContext, Vec<CheckResult>, Receiver<ProcessingEvent>) {
+        let (file_context, rx) = debug_file_context(rule_names, source_code, config);
+        let rule_result_context = run_rules(&file_context);
+
+        (file_context, rule_result_context, rx)
+    }
+
+    /// Run the rules and return the findings, which were suppressed by ludtwig-ignore directives
+    pub fn debug_suppressions(
+        rule_names: &[&str],
+        source_code: &str,
+    ) -> (Vec<CheckResult>, Suppressions) {
+        let config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        let (file_context, _rx) = debug_file_context(rule_names, source_code, config);
 
-        let rule = RULE_DEFINITIONS
+        run_rules_with_suppressions(&file_context)
+    }
+
+    fn debug_file_context(
+        rule_names: &[&str],
+        source_code: &str,
+        config: Config,
+    ) -> (FileContext, Receiver<ProcessingEvent>) {
+        let registry = RuleRegistry::default();
+        let rules: Vec<&'static dyn Rule> = rule_names
             .iter()
-            .find(|r| r.name() == rule_name)
-            .unwrap();
+            .map(|name| registry.get(name).unwrap())
+            .collect();
         let (tx, rx) = mpsc::channel();
-        let parse = parse(source_code);
+        let parse = parse_for_twig_version(source_code, config.general.twig_version);
+
+        let root = SyntaxNode::new_root(parse.green_node);
+        let file_rule_definitions = get_file_active_rule_definitions(&root, &rules);
 
         let file_context = FileContext {
             cli_context: CliContext {
@@ -165,20 +703,21 @@ pub mod test {
                 data: Arc::new(CliSharedData {
                     fix: false,
                     inspect: false,
+                    explain_context: false,
+                    preview_fix: None,
                     config,
-                    rule_definitions: vec![*rule],
+                    rule_registry: registry,
+                    rule_definitions: rules,
                 }),
             },
             file_path: PathBuf::from("./debug-rule.html.twig"),
-            tree_root: SyntaxNode::new_root(parse.green_node),
+            tree_root: root,
             source_code: source_code.to_owned(),
             parse_errors: parse.errors,
-            file_rule_definitions: vec![*rule],
+            file_rule_definitions,
         };
 
-        let rule_result_context = run_rules(&file_context);
-
-        (file_context, rule_result_context, rx)
+        (file_context, rx)
     }
 
     #[allow(clippy::needless_pass_by_value)]
@@ -190,6 +729,52 @@ pub mod test {
         drop(rx);
     }
 
+    /// Run multiple rules together, for rules which depend on the results of other rules
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn test_rules(
+        rule_names: &[&str],
+        source_code: &str,
+        expected_report: expect_test::Expect,
+    ) {
+        let config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        let (file_context, rule_result_context, rx) =
+            debug_rules_with_config(rule_names, source_code, config);
+        let mut buffer = Buffer::no_color();
+        produce_diagnostics(&file_context, rule_result_context, &mut buffer);
+        expected_report.assert_eq(&String::from_utf8_lossy(buffer.as_slice()));
+        drop(rx);
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn test_rules_with_config(
+        rule_names: &[&str],
+        source_code: &str,
+        config: Config,
+        expected_report: expect_test::Expect,
+    ) {
+        let (file_context, rule_result_context, rx) =
+            debug_rules_with_config(rule_names, source_code, config);
+        let mut buffer = Buffer::no_color();
+        produce_diagnostics(&file_context, rule_result_context, &mut buffer);
+        expected_report.assert_eq(&String::from_utf8_lossy(buffer.as_slice()));
+        drop(rx);
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn test_rule_with_config(
+        rule_name: &str,
+        source_code: &str,
+        config: Config,
+        expected_report: expect_test::Expect,
+    ) {
+        let (file_context, rule_result_context, rx) =
+            debug_rule_with_config(rule_name, source_code, config);
+        let mut buffer = Buffer::no_color();
+        produce_diagnostics(&file_context, rule_result_context, &mut buffer);
+        expected_report.assert_eq(&String::from_utf8_lossy(buffer.as_slice()));
+        drop(rx);
+    }
+
     #[allow(clippy::needless_pass_by_value)]
     pub fn test_rule_fix(
         rule_name: &str,
@@ -224,4 +809,243 @@ pub mod test {
         assert_eq!(iteration, 0, "No fixing should have no extra iterations!");
         drop(rx);
     }
+
+    #[test]
+    fn preset_rules_exist() {
+        for preset in RULE_PRESETS {
+            for rule in preset.rules {
+                assert!(
+                    RuleRegistry::default().get(rule).is_ok(),
+                    "rule {rule} of preset {} does not exist",
+                    preset.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn large_file_skips_expensive_rules() {
+        for rule in EXPENSIVE_RULES {
+            assert!(
+                RuleRegistry::default().get(rule).is_ok(),
+                "expensive rule {rule} does not exist"
+            );
+        }
+
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.general.active_rules = vec![
+            "twig-logic-and".to_string(),
+            "twig-unused-variable".to_string(),
+        ];
+        config.general.large_file_threshold = 10;
+        let definitions =
+            get_config_active_rule_definitions(&RuleRegistry::default(), &config).unwrap();
+        let (small, large) = ("{{ a }}", "{% set a = 1 %}");
+
+        let names = |definitions: Vec<&'static dyn Rule>| -> Vec<&str> {
+            definitions.iter().map(|r| r.name()).collect()
+        };
+        assert_eq!(
+            names(skip_expensive_rules(&config, small, definitions.clone())),
+            vec!["twig-logic-and", "twig-unused-variable"]
+        );
+        assert_eq!(
+            names(skip_expensive_rules(&config, large, definitions.clone())),
+            vec!["twig-logic-and"]
+        );
+
+        config.general.large_file_threshold = 0;
+        assert_eq!(
+            names(skip_expensive_rules(&config, large, definitions)),
+            vec!["twig-logic-and", "twig-unused-variable"]
+        );
+    }
+
+    #[test]
+    fn config_extends_preset() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.general.active_rules = vec!["twig-logic-and".to_string()];
+        config.general.extends = vec!["ludtwig:security".to_string()];
+
+        let names: Vec<&str> =
+            get_config_active_rule_definitions(&RuleRegistry::default(), &config)
+                .unwrap()
+                .iter()
+                .map(|r| r.name())
+                .collect();
+        assert_eq!(
+            names,
+            vec![
+                "twig-logic-and",
+                "twig-script-output-escape",
+                "twig-no-autoescape-off",
+                "twig-no-unsafe-raw",
+                "html-target-blank-noopener",
+                "html-no-inline-script"
+            ]
+        );
+
+        config.general.extends = vec!["ludtwig:unknown".to_string()];
+        assert!(matches!(
+            get_config_active_rule_definitions(&RuleRegistry::default(), &config),
+            Err(ConfigurationError::PresetNotFound { .. })
+        ));
+    }
+
+    struct RuleCustom(&'static str);
+
+    impl Rule for RuleCustom {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+    }
+
+    #[test]
+    fn registry_with_custom_rules() {
+        static CUSTOM: RuleCustom = RuleCustom("custom-rule");
+        static REPLACED: RuleCustom = RuleCustom("twig-logic-and");
+        let builtin = RuleRegistry::default();
+        let registry = RuleRegistry::default().with(&CUSTOM).with(&REPLACED);
+
+        // a rule with a new name is added at the end, an existing name is replaced in place
+        assert_eq!(
+            registry.definitions().len(),
+            builtin.definitions().len() + 1
+        );
+        assert_eq!(registry.definitions().last().unwrap().name(), "custom-rule");
+        assert!(std::ptr::addr_eq(
+            registry.get("twig-logic-and").unwrap(),
+            std::ptr::from_ref(&REPLACED)
+        ));
+
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.general.active_rules = vec!["custom-rule".to_string()];
+        let names: Vec<&str> = get_config_active_rule_definitions(&registry, &config)
+            .unwrap()
+            .iter()
+            .map(|r| r.name())
+            .collect();
+        assert_eq!(names, vec!["custom-rule"]);
+        assert!(matches!(
+            get_config_active_rule_definitions(&builtin, &config),
+            Err(ConfigurationError::RuleNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn preview_fix_applies_only_the_given_rule() {
+        let config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        let source_code = "{% if a && b %}\n<div     class=\"a\">hello</div>\n{% endif %}";
+
+        let registry = RuleRegistry::default();
+        let path = Path::new("./debug-rule.html.twig");
+        let fixed = preview_fix(
+            path,
+            source_code,
+            "twig-logic-and",
+            &registry,
+            config.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            fixed,
+            "{% if a and b %}\n<div     class=\"a\">hello</div>\n{% endif %}"
+        );
+
+        assert!(preview_fix(path, source_code, "unknown-rule", &registry, config).is_err());
+    }
+
+    #[test]
+    fn fix_passes_stop_at_the_maximum() {
+        let source_code = "{% block a %}{% block b %}<div>a</div>{% endblock %}{% endblock %}";
+        let rules = ["twig-block-line-breaks", "indentation"];
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+
+        // the indentation of the new line breaks is only fixed in a following pass
+        let (file_context, results, rx) =
+            debug_rules_with_config(&rules, source_code, config.clone());
+        let (file_context, results, dirty, passes) =
+            iteratively_apply_suggestions(file_context, results).unwrap();
+        assert!(dirty);
+        assert_eq!(passes, 2);
+        assert!(get_rule_context_suggestions(&results).is_empty());
+        expect![[r#"
+            {% block a %}
+                {% block b %}<div>a</div>{% endblock %}
+            {% endblock %}"#]]
+        .assert_eq(&file_context.source_code);
+        drop(rx);
+
+        // findings which are still fixable after the last pass are returned
+        config.general.max_fix_passes = 1;
+        let (file_context, results, rx) = debug_rules_with_config(&rules, source_code, config);
+        let (file_context, results, dirty, passes) =
+            iteratively_apply_suggestions(file_context, results).unwrap();
+        assert!(dirty);
+        assert_eq!(passes, 1);
+        assert!(!get_rule_context_suggestions(&results).is_empty());
+        expect![[r#"
+            {% block a %}
+            {% block b %}<div>a</div>{% endblock %}
+            {% endblock %}"#]]
+        .assert_eq(&file_context.source_code);
+        drop(rx);
+    }
+
+    #[test]
+    fn fix_passes_stop_when_nothing_changes() {
+        let config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        let (file_context, rx) = debug_file_context(&[], "{{ a }}", config);
+        let results = vec![RuleCustom("custom-rule")
+            .create_result(Severity::Help, "same text")
+            .suggestion(TextRange::new(0.into(), 2.into()), "{{", "Replace it")];
+
+        // the suggestion would be applied in every pass without changing the source code
+        let (file_context, results, dirty, passes) =
+            iteratively_apply_suggestions(file_context, results).unwrap();
+        assert!(!dirty);
+        assert_eq!(passes, 0);
+        assert!(get_rule_context_suggestions(&results).is_empty());
+        assert_eq!(file_context.source_code, "{{ a }}");
+        drop(rx);
+    }
+
+    #[test]
+    fn suppress_contained_results_of_related_rules() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.general.suppress_contained = HashMap::from([(
+            "twig-block-line-breaks".to_string(),
+            vec!["indentation".to_string()],
+        )]);
+        let range = |start: u32, end: u32| TextRange::new(start.into(), end.into());
+
+        let mut results = vec![
+            RuleTwigBlockLineBreaks
+                .create_result(Severity::Help, "outer")
+                .primary_note(range(0, 10), "outer"),
+            // contained in a result of the related rule
+            RuleIndentation
+                .create_result(Severity::Help, "contained")
+                .suggestion(range(2, 4), "", "contained"),
+            // partially outside
+            RuleIndentation
+                .create_result(Severity::Help, "overlapping")
+                .primary_note(range(8, 12), "overlapping"),
+            // more severe
+            RuleIndentation
+                .create_result(Severity::Warning, "severe")
+                .primary_note(range(2, 4), "severe"),
+            // not related
+            RuleTwigLogicAnd
+                .create_result(Severity::Help, "unrelated")
+                .primary_note(range(2, 4), "unrelated"),
+        ];
+        suppress_contained_results(&config, &mut results);
+
+        let messages: Vec<&str> = results.iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec!["outer", "overlapping", "severe", "unrelated"]
+        );
+    }
 }
@@ -0,0 +1,122 @@
+use ludtwig_parser::syntax::typed::{AstNode, HtmlTag};
+use ludtwig_parser::syntax::untyped::{SyntaxElement, SyntaxKind, SyntaxNode};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::rules::a11y_img_alt::{find_attribute, has_dynamic_attributes, static_value};
+
+pub struct RuleA11yAnchorContent;
+
+impl Rule for RuleA11yAnchorContent {
+    fn name(&self) -> &'static str {
+        "a11y-anchor-content"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Links need a destination to be reachable by keyboard and content (or an aria-label) to be announced by screen readers. Use a <button> for elements which only run scripts.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let tag = HtmlTag::cast(node)?;
+        let name = tag.name()?;
+        if !name.text().eq_ignore_ascii_case("a") || has_dynamic_attributes(&tag) {
+            return None;
+        }
+
+        let mut results = vec![];
+        // named anchors (`<a name="top"></a>`) are only jump targets
+        if find_attribute(&tag, "name").is_none() {
+            let href = find_attribute(&tag, "href");
+            let destination = href.as_ref().and_then(static_value);
+            if href.is_none() || matches!(destination.as_deref().map(str::trim), Some("" | "#")) {
+                results.push(
+                    self.create_result(Severity::Warning, "link without destination")
+                        .primary_note(
+                            name.text_range(),
+                            "add a href attribute or use a <button> instead",
+                        ),
+                );
+            }
+
+            if !has_content(&tag)
+                && !["aria-label", "aria-labelledby", "title"]
+                    .iter()
+                    .any(|a| find_attribute(&tag, a).is_some())
+            {
+                results.push(
+                    self.create_result(Severity::Warning, "link without content")
+                        .primary_note(
+                            name.text_range(),
+                            "add text content or an aria-label attribute",
+                        ),
+                );
+            }
+        }
+
+        if results.is_empty() {
+            None
+        } else {
+            Some(results)
+        }
+    }
+}
+
+/// Does the body contain anything (besides whitespace and comments), twig syntax counts as content
+fn has_content(tag: &HtmlTag) -> bool {
+    let Some(body) = tag.body() else {
+        return false;
+    };
+
+    body.syntax()
+        .children_with_tokens()
+        .any(|child| match child {
+            SyntaxElement::Node(n) => !matches!(
+                n.kind(),
+                SyntaxKind::HTML_COMMENT | SyntaxKind::TWIG_COMMENT
+            ),
+            SyntaxElement::Token(t) => !t.kind().is_trivia(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::test_rule;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "a11y-anchor-content",
+            r##"<a>A</a><a href="#">B</a><a href="/c">  <!-- empty --> </a>"##,
+            expect![[r##"
+                warning[a11y-anchor-content]: link without destination
+                  ┌─ ./debug-rule.html.twig:1:2
+                  │
+                1 │ <a>A</a><a href="#">B</a><a href="/c">  <!-- empty --> </a>
+                  │  ^ add a href attribute or use a <button> instead
+
+                warning[a11y-anchor-content]: link without destination
+                  ┌─ ./debug-rule.html.twig:1:10
+                  │
+                1 │ <a>A</a><a href="#">B</a><a href="/c">  <!-- empty --> </a>
+                  │          ^ add a href attribute or use a <button> instead
+
+                warning[a11y-anchor-content]: link without content
+                  ┌─ ./debug-rule.html.twig:1:27
+                  │
+                1 │ <a>A</a><a href="#">B</a><a href="/c">  <!-- empty --> </a>
+                  │                           ^ add text content or an aria-label attribute
+
+            "##]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "a11y-anchor-content",
+            r#"<a href="{{ url }}">{{ label }}</a><a :href="url" aria-label="A"></a><a href="/b"><img src="b.png" alt="B"></a><a name="top"></a>"#,
+            expect![[r#""#]],
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use ludtwig_parser::syntax::typed::{AstNode, HtmlTag};
+use ludtwig_parser::syntax::untyped::SyntaxNode;
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::rules::a11y_img_alt::{find_attribute, has_dynamic_attributes};
+
+pub struct RuleA11yButtonType;
+
+impl Rule for RuleA11yButtonType {
+    fn name(&self) -> &'static str {
+        "a11y-button-type"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Buttons without type submit the surrounding form, which is rarely intended for buttons that only run scripts.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let tag = HtmlTag::cast(node)?;
+        let name = tag.name()?;
+        if !name.text().eq_ignore_ascii_case("button")
+            || has_dynamic_attributes(&tag)
+            || find_attribute(&tag, "type").is_some()
+        {
+            return None;
+        }
+
+        let result = self
+            .create_result(Severity::Warning, "button without type")
+            .primary_note(
+                name.text_range(),
+                "this defaults to type=\"submit\", add type=\"button\" or type=\"submit\"",
+            );
+
+        Some(vec![result])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::test_rule;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "a11y-button-type",
+            r#"<button>a</button><button type="button">b</button><button :type="c">c</button><button {{ attributes }}>d</button>"#,
+            expect![[r#"
+                warning[a11y-button-type]: button without type
+                  ┌─ ./debug-rule.html.twig:1:2
+                  │
+                1 │ <button>a</button><button type="button">b</button><button :type="c">c</button><button {{ attributes }}>d</button>
+                  │  ^^^^^^ this defaults to type="submit", add type="button" or type="submit"
+
+            "#]],
+        );
+    }
+}
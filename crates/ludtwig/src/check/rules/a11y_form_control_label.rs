@@ -0,0 +1,162 @@
+use ludtwig_parser::syntax::typed::{AstNode, HtmlTag};
+use ludtwig_parser::syntax::untyped::SyntaxNode;
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::rules::a11y_img_alt::{find_attribute, has_dynamic_attributes, static_value};
+
+static FORM_CONTROLS: &[&str] = &["input", "select", "textarea"];
+
+/// Input types, which don't need a label (because they are invisible or labeled by their value)
+static UNLABELED_INPUT_TYPES: &[&str] = &["hidden", "submit", "reset", "button", "image"];
+
+pub struct RuleA11yFormControlLabel;
+
+impl Rule for RuleA11yFormControlLabel {
+    fn name(&self) -> &'static str {
+        "a11y-form-control-label"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Form controls without a label can't be identified by screen reader users. Wrap them in a <label>, reference their id with <label for=\"...\"> or add an aria-label.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let tag = HtmlTag::cast(node)?;
+        let name = tag.name()?;
+        if !FORM_CONTROLS
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(name.text()))
+            || has_dynamic_attributes(&tag)
+            || is_labeled(&tag)?
+        {
+            return None;
+        }
+
+        let result = self
+            .create_result(Severity::Warning, "form control without label")
+            .primary_note(
+                name.text_range(),
+                "add a <label> for this control or an aria-label attribute",
+            );
+
+        Some(vec![result])
+    }
+}
+
+/// Is the form control labeled, `None` if this can't be known before rendering
+fn is_labeled(tag: &HtmlTag) -> Option<bool> {
+    if let Some(input_type) = find_attribute(tag, "type") {
+        let input_type = static_value(&input_type)?;
+        if tag.name()?.text().eq_ignore_ascii_case("input")
+            && UNLABELED_INPUT_TYPES
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(input_type.trim()))
+        {
+            return Some(true);
+        }
+    }
+
+    if ["aria-label", "aria-labelledby", "title"]
+        .iter()
+        .any(|a| find_attribute(tag, a).is_some())
+    {
+        return Some(true);
+    }
+
+    // wrapped in a label
+    if tag
+        .syntax()
+        .ancestors()
+        .skip(1)
+        .filter_map(HtmlTag::cast)
+        .any(|t| {
+            t.name()
+                .is_some_and(|n| n.text().eq_ignore_ascii_case("label"))
+        })
+    {
+        return Some(true);
+    }
+
+    // referenced by a label in the same file
+    let Some(id) = find_attribute(tag, "id") else {
+        return Some(false);
+    };
+    let id = static_value(&id)?;
+    let root = tag.syntax().ancestors().last()?;
+    let label_targets = root
+        .descendants()
+        .filter_map(HtmlTag::cast)
+        .filter(|t| {
+            t.name()
+                .is_some_and(|n| n.text().eq_ignore_ascii_case("label"))
+        })
+        .filter_map(|label| find_attribute(&label, "for"));
+    let mut labeled = false;
+    for target in label_targets {
+        match static_value(&target) {
+            Some(target) if target.trim() == id.trim() => labeled = true,
+            Some(_) => {}
+            // labels with a dynamic target may reference this control
+            None => return None,
+        }
+    }
+
+    Some(labeled)
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::test_rule;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "a11y-form-control-label",
+            r#"<input name="a"><select id="b"></select><label for="c">C</label><textarea id="d"></textarea>"#,
+            expect![[r#"
+                warning[a11y-form-control-label]: form control without label
+                  ┌─ ./debug-rule.html.twig:1:2
+                  │
+                1 │ <input name="a"><select id="b"></select><label for="c">C</label><textarea id="d"></textarea>
+                  │  ^^^^^ add a <label> for this control or an aria-label attribute
+
+                warning[a11y-form-control-label]: form control without label
+                  ┌─ ./debug-rule.html.twig:1:18
+                  │
+                1 │ <input name="a"><select id="b"></select><label for="c">C</label><textarea id="d"></textarea>
+                  │                  ^^^^^^ add a <label> for this control or an aria-label attribute
+
+                warning[a11y-form-control-label]: form control without label
+                  ┌─ ./debug-rule.html.twig:1:66
+                  │
+                1 │ <input name="a"><select id="b"></select><label for="c">C</label><textarea id="d"></textarea>
+                  │                                                                  ^^^^^^^^ add a <label> for this control or an aria-label attribute
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_labeled_controls() {
+        test_rule(
+            "a11y-form-control-label",
+            r#"<label>A <input name="a"></label>
+<label for="b">B</label><select id="b"></select>
+<textarea aria-label="C"></textarea>
+<input type="hidden" name="d"><input type="submit" value="E">
+<input id="{{ id }}"><input type="{{ type }}">"#,
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_dynamic_labels() {
+        test_rule(
+            "a11y-form-control-label",
+            r#"<label for="{{ id }}">A</label><input id="a">"#,
+            expect![[r#""#]],
+        );
+    }
+}
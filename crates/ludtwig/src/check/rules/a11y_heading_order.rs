@@ -0,0 +1,151 @@
+use ludtwig_parser::syntax::typed::{AstNode, HtmlTag};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleA11yHeadingOrder;
+
+impl Rule for RuleA11yHeadingOrder {
+    fn name(&self) -> &'static str {
+        "a11y-heading-order"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Screen reader users navigate by the heading levels, a skipped level (like <h4> after <h2>) looks like missing content. Blocks may be rendered in another context (by overriding or including templates), so only headings in the same block are compared by default.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let tag = HtmlTag::cast(node)?;
+        let level = heading_level(&tag)?;
+
+        // the first heading of a block can't be compared, because the block may be rendered
+        // in another context, but the headings of nested blocks are rendered in place by default
+        let block = match innermost_block(tag.syntax()) {
+            Some(block) if !ctx.config().rules.heading_order_across_blocks => block,
+            _ => tag.syntax().ancestors().last()?,
+        };
+        let previous = block
+            .descendants()
+            .take_while(|n| n != tag.syntax())
+            .filter_map(HtmlTag::cast)
+            .filter(|t| heading_level(t).is_some())
+            .last()?;
+
+        let previous_level = heading_level(&previous)?;
+        if level <= previous_level + 1 {
+            return None;
+        }
+
+        let result = self
+            .create_result(
+                Severity::Info,
+                format!("Heading level skipped from h{previous_level} to h{level}"),
+            )
+            .primary_note(
+                tag.name()?.text_range(),
+                format!(
+                    "help: use <h{}> or add the missing levels",
+                    previous_level + 1
+                ),
+            )
+            .secondary_note(previous.name()?.text_range(), "previous heading");
+
+        Some(vec![result])
+    }
+}
+
+/// Level of `<h1>` to `<h6>` elements
+fn heading_level(tag: &HtmlTag) -> Option<u32> {
+    let name = tag.name()?;
+    let mut chars = name.text().chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some('h' | 'H'), Some(level @ '1'..='6'), None) => level.to_digit(10),
+        _ => None,
+    }
+}
+
+fn innermost_block(node: &SyntaxNode) -> Option<SyntaxNode> {
+    node.ancestors()
+        .find(|ancestor| ancestor.kind() == SyntaxKind::TWIG_BLOCK)
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_with_config};
+    use crate::config::Config;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "a11y-heading-order",
+            r#"<h1>Title</h1>
+<h3>Details</h3>
+{% block content %}
+    <h2>a</h2>
+    <H5>b</H5>
+{% endblock %}"#,
+            expect![[r#"
+                note[a11y-heading-order]: Heading level skipped from h1 to h3
+                  ┌─ ./debug-rule.html.twig:2:2
+                  │
+                1 │ <h1>Title</h1>
+                  │  -- previous heading
+                2 │ <h3>Details</h3>
+                  │  ^^ help: use <h2> or add the missing levels
+
+                note[a11y-heading-order]: Heading level skipped from h2 to h5
+                  ┌─ ./debug-rule.html.twig:5:6
+                  │
+                4 │     <h2>a</h2>
+                  │      -- previous heading
+                5 │     <H5>b</H5>
+                  │      ^^ help: use <h3> or add the missing levels
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "a11y-heading-order",
+            r#"<h1>Title</h1>
+<h2>a</h2>
+<h3>b</h3>
+<h2>c</h2>
+{% block content %}
+    <h4>d</h4>
+    {% block inner %}<h6>e</h6>{% endblock %}
+    <h5>f</h5>
+    {% block inner_title %}<h3>g</h3>{% endblock %}
+    <h4>h</h4>
+{% endblock %}
+<header>i</header>"#,
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_reports_across_blocks() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.heading_order_across_blocks = true;
+
+        test_rule_with_config(
+            "a11y-heading-order",
+            "<h2>a</h2>{% block content %}<h4>b</h4>{% endblock %}",
+            config,
+            expect![[r#"
+                note[a11y-heading-order]: Heading level skipped from h2 to h4
+                  ┌─ ./debug-rule.html.twig:1:31
+                  │
+                1 │ <h2>a</h2>{% block content %}<h4>b</h4>{% endblock %}
+                  │  --                           ^^ help: use <h3> or add the missing levels
+                  │  │                             
+                  │  previous heading
+
+            "#]],
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use ludtwig_parser::syntax::typed::{AstNode, HtmlAttribute, HtmlTag};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleA11yImgAlt;
+
+impl Rule for RuleA11yImgAlt {
+    fn name(&self) -> &'static str {
+        "a11y-img-alt"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Screen readers announce images without alt attribute by their file name. Decorative images should have an empty alt attribute instead.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let tag = HtmlTag::cast(node)?;
+        let name = tag.name()?;
+        if !name.text().eq_ignore_ascii_case("img")
+            || has_dynamic_attributes(&tag)
+            || find_attribute(&tag, "alt").is_some()
+        {
+            return None;
+        }
+
+        let result = self
+            .create_result(Severity::Warning, "image without alternative text")
+            .primary_note(
+                name.text_range(),
+                "add an alt attribute (or alt=\"\" for decorative images)",
+            );
+
+        Some(vec![result])
+    }
+}
+
+/// First attribute of the tag with the name (case-insensitive), including vue bindings like `:alt`
+/// and attributes inside of twig syntax (like `{% if %}`)
+pub(super) fn find_attribute(tag: &HtmlTag, name: &str) -> Option<HtmlAttribute> {
+    tag.starting_tag()?
+        .syntax()
+        .descendants()
+        .filter_map(HtmlAttribute::cast)
+        .find(|attribute| match attribute.vue_directive() {
+            Some(directive) => {
+                directive.get_directive_name().as_deref() == Some("v-bind")
+                    && directive
+                        .argument()
+                        .is_some_and(|argument| argument.syntax().text().to_string().trim() == name)
+            }
+            None => attribute
+                .name()
+                .is_some_and(|n| n.text().eq_ignore_ascii_case(name)),
+        })
+}
+
+/// Value of the attribute, if it doesn't contain any twig syntax and isn't a vue binding
+pub(super) fn static_value(attribute: &HtmlAttribute) -> Option<String> {
+    if attribute.vue_directive().is_some() {
+        return None;
+    }
+
+    attribute.value().static_text().map(ToString::to_string)
+}
+
+/// Can the tag have attributes, which are not known before rendering,
+/// like `{{ attributes }}`, `{{ name }}="value"`, `v-bind="object"` or blocks in between the attributes
+pub(super) fn has_dynamic_attributes(tag: &HtmlTag) -> bool {
+    let Some(starting_tag) = tag.starting_tag() else {
+        return false;
+    };
+
+    starting_tag.syntax().descendants().any(|n| match n.kind() {
+        SyntaxKind::TWIG_BLOCK => true,
+        SyntaxKind::HTML_ATTRIBUTE => {
+            HtmlAttribute::cast(n).is_some_and(|attribute| match attribute.vue_directive() {
+                Some(directive) => {
+                    directive.get_directive_name().as_deref() == Some("v-bind")
+                        && directive.argument().is_none()
+                }
+                None => attribute.name().is_none(),
+            })
+        }
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::test_rule;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "a11y-img-alt",
+            r#"<img src="a.png"><IMG src="b.png" /><img src="c.png" alt=""><img :alt="d"><img {% if e %}alt="e"{% endif %}>"#,
+            expect![[r#"
+                warning[a11y-img-alt]: image without alternative text
+                  ┌─ ./debug-rule.html.twig:1:2
+                  │
+                1 │ <img src="a.png"><IMG src="b.png" /><img src="c.png" alt=""><img :alt="d"><img {% if e %}alt="e"{% endif %}>
+                  │  ^^^ add an alt attribute (or alt="" for decorative images)
+
+                warning[a11y-img-alt]: image without alternative text
+                  ┌─ ./debug-rule.html.twig:1:19
+                  │
+                1 │ <img src="a.png"><IMG src="b.png" /><img src="c.png" alt=""><img :alt="d"><img {% if e %}alt="e"{% endif %}>
+                  │                   ^^^ add an alt attribute (or alt="" for decorative images)
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_dynamic_attributes() {
+        test_rule(
+            "a11y-img-alt",
+            r#"<img src="a.png" {{ attributes }}><img v-bind="image"><img {{ name }}="alt">"#,
+            expect![[r#""#]],
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use ludtwig_parser::syntax::typed::{AstNode, TwigComment};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode, SyntaxNodeExt};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleCommentedOutCode;
+
+impl Rule for RuleCommentedOutCode {
+    fn name(&self) -> &'static str {
+        "commented-out-code"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Commented out code is never rendered and gets outdated quickly. Remove it, the version control system still knows it.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let content = match node.kind() {
+            SyntaxKind::TWIG_COMMENT => TwigComment::cast(node.clone())?.content(),
+            SyntaxKind::HTML_COMMENT => {
+                let text = node.text().to_string();
+                let content = text
+                    .trim()
+                    .strip_prefix("<!--")?
+                    .strip_suffix("-->")
+                    .unwrap_or_default()
+                    .trim();
+                // conditional comments like `<!--[if IE]>` are still rendered by old browsers
+                if content.starts_with("[if") {
+                    return None;
+                }
+                content.to_string()
+            }
+            _ => return None,
+        };
+
+        if content.chars().count() < ctx.config().rules.commented_out_code_min_length
+            || !looks_like_code(&content)
+        {
+            return None;
+        }
+
+        let result = self
+            .create_result(Severity::Info, "Commented out code")
+            .primary_note(
+                node.text_range_trimmed_trivia(),
+                "help: remove this code if it is not needed anymore",
+            );
+
+        Some(vec![result])
+    }
+}
+
+/// Heuristic for html / twig code: twig tags, html ending tags or multiple attributes
+fn looks_like_code(content: &str) -> bool {
+    content.contains("{%") || content.contains("</") || content.matches("=\"").count() > 1
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_with_config};
+    use crate::config::Config;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "commented-out-code",
+            r#"<div>
+    {# {% block product_title %}{{ product.name }}{% endblock %} #}
+    <!-- <a href="/" class="logo">Home</a> -->
+    <!-- <img src="a.png" alt="a"> -->
+</div>"#,
+            expect![[r#"
+                note[commented-out-code]: Commented out code
+                  ┌─ ./debug-rule.html.twig:2:5
+                  │
+                2 │     {# {% block product_title %}{{ product.name }}{% endblock %} #}
+                  │     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ help: remove this code if it is not needed anymore
+
+                note[commented-out-code]: Commented out code
+                  ┌─ ./debug-rule.html.twig:3:5
+                  │
+                3 │     <!-- <a href="/" class="logo">Home</a> -->
+                  │     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ help: remove this code if it is not needed anymore
+
+                note[commented-out-code]: Commented out code
+                  ┌─ ./debug-rule.html.twig:4:5
+                  │
+                4 │     <!-- <img src="a.png" alt="a"> -->
+                  │     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ help: remove this code if it is not needed anymore
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "commented-out-code",
+            r#"<div>
+    {# the title is rendered by the parent template #}
+    <!-- shown on every page, see base.html.twig -->
+    {# </div> #}
+    <!--[if IE]><script src="polyfill.js"></script><![endif]-->
+    <!-- <img src="a.png"> -->
+</div>"#,
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_reports_with_min_length() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.commented_out_code_min_length = 0;
+
+        test_rule_with_config(
+            "commented-out-code",
+            "{# </div> #}",
+            config,
+            expect![[r#"
+                note[commented-out-code]: Commented out code
+                  ┌─ ./debug-rule.html.twig:1:1
+                  │
+                1 │ {# </div> #}
+                  │ ^^^^^^^^^^^^ help: remove this code if it is not needed anymore
+
+            "#]],
+        );
+    }
+}
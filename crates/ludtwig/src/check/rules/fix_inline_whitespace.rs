@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+
+use ludtwig_parser::syntax::typed::{AstNode, HtmlTag};
+use ludtwig_parser::syntax::untyped::{SyntaxNode, SyntaxNodeExt, TextRange};
+
+use crate::check::is_ignored_by_directive;
+use crate::check::rule::{CheckResult, Rule, RuleExt, Severity};
+
+/// Elements which are rendered inline, so any whitespace inside of them is visible as a space
+static INLINE_ELEMENTS: &[&str] = &[
+    "a", "abbr", "b", "bdi", "bdo", "button", "cite", "code", "data", "dfn", "em", "i", "kbd",
+    "label", "mark", "q", "s", "samp", "small", "span", "strong", "sub", "sup", "time", "u", "var",
+];
+
+/// Validates the result of `--fix` instead of checking the template itself:
+/// the suggestions of other rules (like `twig-block-line-breaks` or `indentation`) only change whitespace,
+/// but inside of inline elements like `<span>{% block a %}a{% endblock %}</span>` new line breaks
+/// are rendered as additional spaces.
+pub struct RuleFixInlineWhitespace;
+
+impl Rule for RuleFixInlineWhitespace {
+    fn name(&self) -> &'static str {
+        "fix-inline-whitespace"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Applied fixes should not change how the template is rendered. Whitespace inside of inline elements (like <span> or <a>) is visible, so added line breaks show up as spaces in the text.")
+    }
+}
+
+impl RuleFixInlineWhitespace {
+    /// Compare the rendered text of the inline elements before and after applying the fixes.
+    /// Only the innermost elements with a changed text are reported (in the fixed tree).
+    pub fn check_fix(&self, original: &SyntaxNode, fixed: &SyntaxNode) -> Vec<CheckResult> {
+        let original_elements: HashMap<usize, HtmlTag> =
+            inline_elements_by_content_offset(original).collect();
+        let changed: Vec<HtmlTag> = inline_elements_by_content_offset(fixed)
+            .filter_map(|(offset, after)| {
+                let before = original_elements.get(&offset)?;
+                let changed = before.name()?.text() == after.name()?.text()
+                    && rendered_inner_text(before) != rendered_inner_text(&after)
+                    && !is_ignored_by_directive(after.syntax(), self.name());
+                changed.then_some(after)
+            })
+            .collect();
+
+        changed
+            .iter()
+            .filter(|tag| {
+                let range = tag.syntax().text_range();
+                !changed.iter().any(|other| {
+                    other.syntax() != tag.syntax()
+                        && range.contains_range(other.syntax().text_range())
+                })
+            })
+            .filter_map(|tag| {
+                let result = self
+                    .create_result(
+                        Severity::Warning,
+                        "Fix changed the whitespace inside of an inline element",
+                    )
+                    .primary_note(
+                        tag.starting_tag()?.syntax().text_range_trimmed_trivia(),
+                        "the rendered text of this element now contains different spaces, check the applied formatting",
+                    );
+                Some(result)
+            })
+            .collect()
+    }
+}
+
+/// Inline elements with the amount of non-whitespace characters in front of them. The offset stays the same
+/// when the fixes only change whitespace, so it matches the elements before and after applying the fixes
+/// (elements behind other changes don't match and are not compared).
+fn inline_elements_by_content_offset(
+    root: &SyntaxNode,
+) -> impl Iterator<Item = (usize, HtmlTag)> + '_ {
+    let text = root.text().to_string();
+    let mut position = 0;
+    let mut content_offset = 0;
+
+    // the elements are in document order, so the text is only counted once
+    inline_elements(root).map(move |tag| {
+        let start = usize::from(tag.syntax().text_range().start());
+        content_offset += text[position..start]
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .count();
+        position = start;
+        (content_offset, tag)
+    })
+}
+
+fn inline_elements(root: &SyntaxNode) -> impl Iterator<Item = HtmlTag> {
+    root.descendants().filter_map(HtmlTag::cast).filter(|tag| {
+        tag.name().is_some_and(|name| {
+            INLINE_ELEMENTS
+                .iter()
+                .any(|e| e.eq_ignore_ascii_case(name.text()))
+        })
+    })
+}
+
+/// Rendered text between the starting and ending tag (including the whitespace in front of the ending tag)
+fn rendered_inner_text(tag: &HtmlTag) -> Option<String> {
+    let start = tag.starting_tag()?.syntax().text_range().end();
+    let end = tag
+        .ending_tag()?
+        .syntax()
+        .text_range_trimmed_trivia()
+        .start();
+    let offset = tag.syntax().text_range().start();
+    let text = tag
+        .syntax()
+        .text()
+        .slice(TextRange::new(start - offset, end - offset))
+        .to_string();
+
+    Some(rendered_text(&text))
+}
+
+/// Text like it is rendered: whitespace runs are collapsed into a single space and whitespace
+/// inside of twig syntax or removed by whitespace control (like `{%-` or `~}}`) doesn't matter.
+fn rendered_text(text: &str) -> String {
+    let mut rendered = String::with_capacity(text.len());
+    let mut closing_delimiter: Option<&str> = None;
+    let mut skip_whitespace = false;
+
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if let Some(closing) = closing_delimiter {
+            if rest.starts_with(closing) {
+                skip_whitespace = rendered.ends_with(['-', '~']);
+                rendered.push_str(closing);
+                rest = &rest[closing.len()..];
+                closing_delimiter = None;
+                continue;
+            }
+
+            if !c.is_whitespace() {
+                rendered.push(c);
+            }
+        } else if let Some(closing) = ["{{", "{%", "{#"]
+            .iter()
+            .zip(["}}", "%}", "#}"])
+            .find_map(|(opening, closing)| rest.starts_with(opening).then_some(closing))
+        {
+            if rest[2..].starts_with(['-', '~']) && rendered.ends_with(' ') {
+                rendered.pop();
+            }
+            rendered.push_str(&rest[..2]);
+            rest = &rest[2..];
+            closing_delimiter = Some(closing);
+            continue;
+        } else if c.is_whitespace() {
+            if !skip_whitespace && !rendered.ends_with(' ') {
+                rendered.push(' ');
+            }
+        } else {
+            rendered.push(c);
+            skip_whitespace = false;
+        }
+
+        rest = &rest[c.len_utf8()..];
+    }
+
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use ludtwig_parser::syntax::untyped::SyntaxNode;
+
+    use super::*;
+
+    fn check_fix(original: &str, fixed: &str) -> Vec<String> {
+        let root = |source: &str| SyntaxNode::new_root(ludtwig_parser::parse(source).green_node);
+        let fixed_root = root(fixed);
+
+        RuleFixInlineWhitespace
+            .check_fix(&root(original), &fixed_root)
+            .iter()
+            .map(|result| {
+                let range = result.primary.as_ref().unwrap().syntax_range;
+                fixed[range].to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rendered_text_collapses_whitespace() {
+        assert_eq!(rendered_text(" a \n\n  b "), " a b ");
+        assert_eq!(
+            rendered_text("a{{  b }}\n{% if c %}d{% endif %}"),
+            "a{{b}} {%ifc%}d{%endif%}"
+        );
+        assert_eq!(rendered_text("a\n    {%- if b -%}\n    c"), "a{%-ifb-%}c");
+        assert_eq!(rendered_text("a {{~ b }}  c"), "a{{~b}} c");
+    }
+
+    #[test]
+    fn reports_changed_inline_elements() {
+        assert_eq!(
+            check_fix(
+                "<div><a href=\"/\"><span>{% block a %}a{% endblock %}</span></a>\n<strong>b</strong></div>",
+                "<div>\n    <a href=\"/\"><span>\n        {% block a %}a{% endblock %}\n    </span></a>\n    <strong>b</strong>\n</div>",
+            ),
+            vec!["<span>"]
+        );
+    }
+
+    #[test]
+    fn does_not_report_unchanged_text() {
+        assert_eq!(
+            check_fix(
+                "<div><span> a  {{b}} </span><p>c</p></div>",
+                "<div>\n    <span>\n        a {{ b }}\n    </span>\n    <p>\n        c\n    </p>\n</div>",
+            ),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            check_fix(
+                "{# ludtwig-ignore fix-inline-whitespace #}<span>{% block a %}a{% endblock %}</span>",
+                "{# ludtwig-ignore fix-inline-whitespace #}\n<span>\n    {% block a %}a{% endblock %}\n</span>",
+            ),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn matches_elements_by_their_content_offset() {
+        // other fixes may add or remove elements, so the n-th elements are not the same
+        assert_eq!(
+            check_fix(
+                "<span>a</span>\n<b>{% block b %}b{% endblock %}</b>",
+                "<b>{% block b %}b{% endblock %}</b>",
+            ),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            check_fix(
+                "<b>a</b><b>{% block b %}b{% endblock %}</b>",
+                "<b>a</b>\n<b>\n    {% block b %}b{% endblock %}\n</b>",
+            ),
+            vec!["<b>"]
+        );
+    }
+}
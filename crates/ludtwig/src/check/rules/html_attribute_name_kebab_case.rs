@@ -1,5 +1,6 @@
-use ludtwig_parser::syntax::typed::{AstNode, HtmlAttribute};
-use ludtwig_parser::syntax::untyped::SyntaxNode;
+use ludtwig_parser::syntax::typed::{AstNode, HtmlAttribute, VueDirectiveArgument};
+use ludtwig_parser::syntax::untyped::{SyntaxElement, SyntaxNode, SyntaxToken};
+use ludtwig_parser::T;
 
 use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
 
@@ -10,8 +11,17 @@ impl Rule for RuleHtmlAttributeNameKebabCase {
         "html-attribute-name-kebab-case"
     }
 
+    fn explanation(&self) -> Option<&'static str> {
+        Some("HTML attribute names are case-insensitive, kebab-case names stay consistent and also work as vue props.")
+    }
+
     fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
-        let attribute_name = HtmlAttribute::cast(node)?.name()?;
+        let attribute = HtmlAttribute::cast(node)?;
+        let attribute_name = match attribute.vue_directive() {
+            // for vue directives only the argument like `my-prop` in `:my-prop` is checked
+            Some(directive) => single_word_argument(&directive.argument()?)?,
+            None => attribute.name()?,
+        };
         if !is_valid_alphanumeric_kebab_case(attribute_name.text()) {
             // name is not valid
             let mut result = self
@@ -39,6 +49,20 @@ impl Rule for RuleHtmlAttributeNameKebabCase {
     }
 }
 
+/// Dynamic arguments like `[key]` or event names like `update:model-value` are not checked
+fn single_word_argument(argument: &VueDirectiveArgument) -> Option<SyntaxToken> {
+    let mut tokens = argument
+        .syntax()
+        .children_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .filter(|t| !t.kind().is_trivia());
+
+    match (tokens.next(), tokens.next()) {
+        (Some(word), None) if word.kind() == T![word] => Some(word),
+        _ => None,
+    }
+}
+
 fn is_valid_alphanumeric_kebab_case(s: &str) -> bool {
     let mut iter = s.chars().enumerate().peekable();
     while let Some((idx, c)) = iter.next() {
@@ -209,6 +233,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rule_reports_vue_directive_argument() {
+        test_rule(
+            "html-attribute-name-kebab-case",
+            "<custom :myProp=\"a\" @update:modelValue=\"b\" v-model.lazyTrim=\"c\"/>",
+            expect![[r#"
+                help[html-attribute-name-kebab-case]: Attribute name is not written in kebab-case
+                  ┌─ ./debug-rule.html.twig:1:10
+                  │
+                1 │ <custom :myProp="a" @update:modelValue="b" v-model.lazyTrim="c"/>
+                  │          ^^^^^^
+                  │          │
+                  │          help: rename this attribute in kebab-case
+                  │          Try this name instead: my-prop
+
+            "#]],
+        );
+    }
+
     #[test]
     fn rule_fixes() {
         test_rule_fix(
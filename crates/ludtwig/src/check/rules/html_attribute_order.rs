@@ -0,0 +1,260 @@
+use ludtwig_parser::syntax::editor::SyntaxEditor;
+use ludtwig_parser::syntax::trivia::leading_trivia;
+use ludtwig_parser::syntax::typed::{AstNode, HtmlAttribute};
+use ludtwig_parser::syntax::untyped::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxNodeExt};
+use regex::Regex;
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::rules::html_data_attribute_naming::get_naming_regex;
+
+pub struct RuleHtmlAttributeOrder;
+
+impl Rule for RuleHtmlAttributeOrder {
+    fn name(&self) -> &'static str {
+        "html-attribute-order"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("A consistent order of attributes makes the important ones (like vue conditions) easy to spot and keeps diffs small.")
+    }
+
+    fn check_root(&self, _node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let results: Vec<_> = ctx
+            .config()
+            .rules
+            .attribute_order
+            .iter()
+            .filter_map(|pattern| match get_naming_regex(pattern) {
+                Ok(_) => None,
+                Err(e) => Some(self.create_result(
+                    Severity::Error,
+                    format!("can't use attribute-order pattern '{pattern}': {e}"),
+                )),
+            })
+            .collect();
+
+        if results.is_empty() {
+            None
+        } else {
+            Some(results)
+        }
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        // attributes can be direct children of the attribute list or of twig blocks in between them
+        if !matches!(
+            node.kind(),
+            SyntaxKind::HTML_ATTRIBUTE_LIST | SyntaxKind::BODY
+        ) {
+            return None;
+        }
+        let groups = ctx
+            .config()
+            .rules
+            .attribute_order
+            .iter()
+            .map(|pattern| get_naming_regex(pattern).ok())
+            .collect::<Option<Vec<Regex>>>()?;
+
+        // attributes are only reordered between twig syntax (like `{% if %}`), but never across it
+        let mut runs: Vec<Vec<(HtmlAttribute, usize)>> = vec![vec![]];
+        for child in node.children() {
+            let Some(attribute) = HtmlAttribute::cast(child) else {
+                runs.push(vec![]);
+                continue;
+            };
+            let Some(name) = attribute_name(&attribute) else {
+                runs.push(vec![]);
+                continue;
+            };
+
+            // attributes without a group can be placed anywhere
+            if let Some(group) = groups.iter().position(|g| g.is_match(&name)) {
+                runs.last_mut()?.push((attribute, group));
+            }
+        }
+
+        let results: Vec<_> = runs
+            .iter()
+            .filter_map(|run| self.check_run(run, ctx))
+            .collect();
+        if results.is_empty() {
+            None
+        } else {
+            Some(results)
+        }
+    }
+}
+
+impl RuleHtmlAttributeOrder {
+    fn check_run(
+        &self,
+        run: &[(HtmlAttribute, usize)],
+        ctx: &RuleRunContext,
+    ) -> Option<CheckResult> {
+        let mut sorted: Vec<&(HtmlAttribute, usize)> = run.iter().collect();
+        sorted.sort_by_key(|(_, group)| *group);
+
+        // the first attribute, which must be moved before one of the previous attributes
+        let (misplaced, before) = run.iter().enumerate().find_map(|(idx, current)| {
+            let before = run[..idx].iter().find(|(_, group)| *group > current.1)?;
+            Some((current, before))
+        })?;
+
+        let pattern = &ctx.config().rules.attribute_order[misplaced.1];
+        let mut editor = SyntaxEditor::new(misplaced.0.syntax().ancestors().last()?);
+        for ((current, _), (expected, _)) in run.iter().zip(&sorted) {
+            if current != expected {
+                editor.replace(
+                    current.syntax().clone(),
+                    with_leading_trivia(expected.syntax(), current.syntax()),
+                );
+            }
+        }
+
+        let result = self
+            .create_result(Severity::Help, "Attributes are not in the configured order")
+            .primary_note(
+                misplaced.0.syntax().text_range_trimmed_trivia(),
+                format!("help: move this attribute (matching {pattern}) before the other one"),
+            )
+            .secondary_note(
+                before.0.syntax().text_range_trimmed_trivia(),
+                "this attribute belongs to a later group of the attribute-order",
+            )
+            .edit_suggestions(&editor.finish(), "Try this order instead");
+
+        Some(result)
+    }
+}
+
+/// Name of the attribute, for vue directives the full directive like `v-if` or `@click.prevent`,
+/// `None` for dynamic names like `{{ name }}="value"`
+fn attribute_name(attribute: &HtmlAttribute) -> Option<String> {
+    match attribute.vue_directive() {
+        Some(directive) => Some(directive.syntax().text().to_string().trim().to_string()),
+        None => attribute.name().map(|n| n.text().to_string()),
+    }
+}
+
+/// Copy of the attribute which keeps the leading trivia (line breaks and indentation) of the attribute
+/// at the position it is moved to
+fn with_leading_trivia(attribute: &SyntaxNode, position: &SyntaxNode) -> SyntaxNode {
+    let copy = SyntaxNode::new_root(attribute.green().into_owned());
+    let mut editor = SyntaxEditor::new(copy.clone());
+
+    let mut tokens = copy
+        .descendants_with_tokens()
+        .filter_map(SyntaxElement::into_token);
+    for token in tokens.by_ref() {
+        if !token.kind().is_trivia() {
+            for trivia in leading_trivia(position) {
+                editor.insert_before(token.clone(), SyntaxElement::Token(trivia));
+            }
+            break;
+        }
+        editor.delete(token);
+    }
+
+    SyntaxNode::new_root(editor.finish().green_node)
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_fix, test_rule_with_config};
+    use crate::config::Config;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "html-attribute-order",
+            r#"<div class="a" v-if="visible" @click="toggle" data-id="1"></div>"#,
+            expect![[r#"
+                help[html-attribute-order]: Attributes are not in the configured order
+                  ┌─ ./debug-rule.html.twig:1:16
+                  │
+                1 │ <div class="a" v-if="visible" @click="toggle" data-id="1"></div>
+                  │     -----------^^^^^^^^^^^^^^----------------------------
+                  │     ││         │
+                  │     ││         help: move this attribute (matching ^v-(if|else-if|else|for)$) before the other one
+                  │     │this attribute belongs to a later group of the attribute-order
+                  │     Try this order instead:  v-if="visible" class="a" data-id="1" @click="toggle"
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_reports_multiline() {
+        test_rule(
+            "html-attribute-order",
+            "<div\n    data-id=\"1\"\n    class=\"a\">\n</div>",
+            expect![[r#"
+                help[html-attribute-order]: Attributes are not in the configured order
+                  ┌─ ./debug-rule.html.twig:3:5
+                  │  
+                1 │   <div
+                  │ ╭────'
+                2 │ │     data-id="1"
+                  │ │     ----------- this attribute belongs to a later group of the attribute-order
+                3 │ │     class="a">
+                  │ │     ^^^^^^^^^ help: move this attribute (matching ^(id|class)$) before the other one
+                  │ ╰─────────────' Try this order instead: 
+                    class="a"
+                    data-id="1"
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes() {
+        test_rule_fix(
+            "html-attribute-order",
+            r#"<div class="a" v-if="visible" @click="toggle" data-id="1"></div>"#,
+            expect![[r#"<div v-if="visible" class="a" data-id="1" @click="toggle"></div>"#]],
+        );
+        test_rule_fix(
+            "html-attribute-order",
+            "<div\n    data-id=\"1\"\n    class=\"a\">\n</div>",
+            expect![[r#"
+                <div
+                    class="a"
+                    data-id="1">
+                </div>"#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_reorder_across_twig() {
+        test_rule_fix(
+            "html-attribute-order",
+            r#"<div data-b="1" {% if a %}data-a="1" id="a"{% endif %} data-c="1" type="button" id="b"></div>"#,
+            expect![[
+                r#"<div data-b="1" {% if a %}id="a" data-a="1"{% endif %} id="b" type="button" data-c="1"></div>"#
+            ]],
+        );
+    }
+
+    #[test]
+    fn rule_reports_invalid_pattern() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.attribute_order = vec!["^(id".to_string()];
+
+        test_rule_with_config(
+            "html-attribute-order",
+            r#"<div class="a" id="b"></div>"#,
+            config,
+            expect![[r#"
+                error[html-attribute-order]: can't use attribute-order pattern '^(id': regex parse error:
+                    ^(id
+                     ^
+                error: unclosed group
+                 = in file ./debug-rule.html.twig
+
+            "#]],
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use ludtwig_parser::syntax::typed::{AstNode, HtmlTag};
+use ludtwig_parser::syntax::untyped::{Direction, SyntaxKind, SyntaxNode};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+/// Elements which start a new flow of content (the `<p>` is implicitly closed in front of them)
+static BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "div",
+    "dl",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "main",
+    "menu",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+];
+
+/// Interactive elements, which can't contain other interactive elements
+static INTERACTIVE_ELEMENTS: &[&str] = &["a", "button"];
+
+/// Subset of the html content model: elements which are not allowed anywhere inside of the element
+static CONTENT_MODEL: &[(&str, &[&str])] = &[
+    ("form", &["form"]),
+    ("a", INTERACTIVE_ELEMENTS),
+    ("button", INTERACTIVE_ELEMENTS),
+    ("label", &["label"]),
+    ("p", BLOCK_ELEMENTS),
+];
+
+/// Elements with their own separate content (like a vue `<template>`), which ends the ancestor stack
+static CONTENT_BOUNDARIES: &[&str] = &["template", "svg"];
+
+pub struct RuleHtmlContentModel;
+
+impl Rule for RuleHtmlContentModel {
+    fn name(&self) -> &'static str {
+        "html-content-model"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Browsers don't allow some elements inside of others (like a <form> inside of a <form> or a <div> inside of a <p>) and repair the document by closing or dropping elements, so the rendered page looks different than the template.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let tag = HtmlTag::cast(node)?;
+        let name = tag.name()?;
+
+        let ancestor = forbidden_ancestor(&tag).or_else(|| implicitly_closed_paragraph(&tag))?;
+        let ancestor_name = ancestor.name()?;
+
+        let result = self
+            .create_result(
+                Severity::Warning,
+                format!(
+                    "<{}> is not allowed inside of <{}>",
+                    name.text(),
+                    ancestor_name.text()
+                ),
+            )
+            .primary_note(
+                name.text_range(),
+                "browsers close or drop elements to repair this nesting",
+            )
+            .secondary_note(ancestor_name.text_range(), "inside of this element");
+
+        Some(vec![result])
+    }
+}
+
+/// The closest ancestor (inside of the same content) which doesn't allow the element
+fn forbidden_ancestor(tag: &HtmlTag) -> Option<HtmlTag> {
+    tag.syntax()
+        .ancestors()
+        .skip(1)
+        .filter_map(HtmlTag::cast)
+        .take_while(|ancestor| !is_one_of(ancestor, CONTENT_BOUNDARIES))
+        .find(|ancestor| {
+            CONTENT_MODEL.iter().any(|(parent, forbidden)| {
+                is_one_of(ancestor, &[parent]) && is_one_of(tag, forbidden)
+            })
+        })
+}
+
+/// The `<p>` of a block element which was written inside of it like `<p>a<div>b</div></p>`.
+/// The parser keeps such a `<p>` open when it finds its `</p>` ahead, but otherwise (like browsers)
+/// implicitly closes it in front of the block element, so they are siblings and the `</p>` is left over.
+fn implicitly_closed_paragraph(tag: &HtmlTag) -> Option<HtmlTag> {
+    if !is_one_of(tag, BLOCK_ELEMENTS) {
+        return None;
+    }
+
+    let paragraph = tag
+        .syntax()
+        .prev_sibling()
+        .and_then(HtmlTag::cast)
+        .filter(|p| is_one_of(p, &["p"]) && p.ending_tag().is_none())?;
+    let has_left_over_end_tag = tag.syntax().siblings(Direction::Next).any(|sibling| {
+        sibling.kind() == SyntaxKind::ERROR
+            && sibling
+                .text()
+                .to_string()
+                .trim()
+                .eq_ignore_ascii_case("</p>")
+    });
+
+    has_left_over_end_tag.then_some(paragraph)
+}
+
+fn is_one_of(tag: &HtmlTag, names: &[&str]) -> bool {
+    tag.name()
+        .is_some_and(|name| names.iter().any(|n| n.eq_ignore_ascii_case(name.text())))
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::test_rule;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "html-content-model",
+            r#"<form><div><FORM></FORM></div></form>
+<a href="/"><button>a</button></a>
+<p>{% block text %}<div>b</div>{% endblock %}</p>
+<button><span><button>c</button></span></button>"#,
+            expect![[r#"
+                warning[html-content-model]: <FORM> is not allowed inside of <form>
+                  ┌─ ./debug-rule.html.twig:1:13
+                  │
+                1 │ <form><div><FORM></FORM></div></form>
+                  │  ----       ^^^^ browsers close or drop elements to repair this nesting
+                  │  │           
+                  │  inside of this element
+
+                warning[html-content-model]: <button> is not allowed inside of <a>
+                  ┌─ ./debug-rule.html.twig:2:14
+                  │
+                2 │ <a href="/"><button>a</button></a>
+                  │  -           ^^^^^^ browsers close or drop elements to repair this nesting
+                  │  │            
+                  │  inside of this element
+
+                warning[html-content-model]: <div> is not allowed inside of <p>
+                  ┌─ ./debug-rule.html.twig:3:21
+                  │
+                3 │ <p>{% block text %}<div>b</div>{% endblock %}</p>
+                  │  -                  ^^^ browsers close or drop elements to repair this nesting
+                  │  │                   
+                  │  inside of this element
+
+                warning[html-content-model]: <button> is not allowed inside of <button>
+                  ┌─ ./debug-rule.html.twig:4:16
+                  │
+                4 │ <button><span><button>c</button></span></button>
+                  │  ------        ^^^^^^ browsers close or drop elements to repair this nesting
+                  │  │              
+                  │  inside of this element
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_reports_implicitly_closed_paragraph() {
+        test_rule(
+            "html-content-model",
+            r#"<p>a<div>b</div></p>
+<p>c</p><div>d</div>
+<p>e<div>f</div>"#,
+            expect![[r#"
+                warning[html-content-model]: <div> is not allowed inside of <p>
+                  ┌─ ./debug-rule.html.twig:1:6
+                  │
+                1 │ <p>a<div>b</div></p>
+                  │  -   ^^^ browsers close or drop elements to repair this nesting
+                  │  │    
+                  │  inside of this element
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "html-content-model",
+            r#"<form><button>a</button></form><form></form>
+<p><span>b</span><a href="/">c</a></p>
+<a href="/"><template v-if="d"><a href="/">d</a></template></a>
+<label>e<input></label>"#,
+            expect![[r#""#]],
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use ludtwig_parser::syntax::typed::{AstNode, HtmlAttribute};
+use ludtwig_parser::syntax::untyped::SyntaxNode;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+/// Compiled naming regexes by their pattern, so every pattern is only compiled once
+static NAMING_REGEX_CACHE: Lazy<Mutex<HashMap<String, Result<Regex, String>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub struct RuleHtmlDataAttributeNaming;
+
+impl Rule for RuleHtmlDataAttributeNaming {
+    fn name(&self) -> &'static str {
+        "html-data-attribute-naming"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Names of data-* attributes end up in the dataset of the element, a common naming scheme keeps them predictable.")
+    }
+
+    fn check_root(&self, _node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let pattern = &ctx.config().rules.data_attribute_name_regex;
+
+        match get_naming_regex(pattern) {
+            Ok(_) => None,
+            Err(e) => Some(vec![self.create_result(
+                Severity::Error,
+                format!("can't use data-attribute-name-regex '{pattern}': {e}"),
+            )]),
+        }
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let attribute = HtmlAttribute::cast(node)?;
+        let attribute_name = attribute.name()?;
+        if !attribute_name
+            .text()
+            .get(..5)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("data-"))
+        {
+            return None;
+        }
+
+        let pattern = &ctx.config().rules.data_attribute_name_regex;
+        let naming_regex = get_naming_regex(pattern).ok()?;
+        if naming_regex.is_match(attribute_name.text()) {
+            return None;
+        }
+
+        let mut result = self
+            .create_result(
+                Severity::Help,
+                "data attribute name does not match the naming convention",
+            )
+            .primary_note(
+                attribute_name.text_range(),
+                format!("help: rename this attribute to match {pattern}"),
+            );
+
+        let suggested_name = to_kebab_case(attribute_name.text());
+        if naming_regex.is_match(&suggested_name) {
+            result = result.suggestion(
+                attribute_name.text_range(),
+                suggested_name,
+                "Try this name instead",
+            );
+        }
+
+        Some(vec![result])
+    }
+}
+
+pub(super) fn get_naming_regex(pattern: &str) -> Result<Regex, String> {
+    let mut cache = NAMING_REGEX_CACHE.lock().unwrap();
+    cache
+        .entry(pattern.to_string())
+        .or_insert_with(|| Regex::new(pattern).map_err(|e| e.to_string()))
+        .clone()
+}
+
+/// Converts names like `data-productId` or `data-product_id` into `data-product-id`
+fn to_kebab_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len() + 4);
+    let mut previous_lowercase = false;
+
+    for c in name.chars() {
+        if c == '_' || c == '-' {
+            if !result.ends_with('-') {
+                result.push('-');
+            }
+            previous_lowercase = false;
+        } else if c.is_ascii_uppercase() {
+            if previous_lowercase {
+                result.push('-');
+            }
+            result.push(c.to_ascii_lowercase());
+            previous_lowercase = false;
+        } else {
+            result.push(c);
+            previous_lowercase = c.is_ascii_lowercase() || c.is_ascii_digit();
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_fix, test_rule_with_config};
+    use crate::config::Config;
+
+    use super::to_kebab_case;
+
+    #[test]
+    fn converts_to_kebab_case() {
+        assert_eq!(to_kebab_case("data-productId"), "data-product-id");
+        assert_eq!(to_kebab_case("data-product_id"), "data-product-id");
+        assert_eq!(to_kebab_case("DATA-URL"), "data-url");
+        assert_eq!(
+            to_kebab_case("data-offCanvas__cart"),
+            "data-off-canvas-cart"
+        );
+    }
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "html-data-attribute-naming",
+            r#"<div data-offCanvasCart="true" data-product_id="5" data-url="/"></div>"#,
+            expect![[r#"
+                help[html-data-attribute-naming]: data attribute name does not match the naming convention
+                  ┌─ ./debug-rule.html.twig:1:6
+                  │
+                1 │ <div data-offCanvasCart="true" data-product_id="5" data-url="/"></div>
+                  │      ^^^^^^^^^^^^^^^^^^
+                  │      │
+                  │      help: rename this attribute to match ^data-[a-z0-9]+(-[a-z0-9]+)*$
+                  │      Try this name instead: data-off-canvas-cart
+
+                help[html-data-attribute-naming]: data attribute name does not match the naming convention
+                  ┌─ ./debug-rule.html.twig:1:32
+                  │
+                1 │ <div data-offCanvasCart="true" data-product_id="5" data-url="/"></div>
+                  │                                ^^^^^^^^^^^^^^^
+                  │                                │
+                  │                                help: rename this attribute to match ^data-[a-z0-9]+(-[a-z0-9]+)*$
+                  │                                Try this name instead: data-product-id
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes() {
+        test_rule_fix(
+            "html-data-attribute-naming",
+            r#"<div data-offCanvasCart="true" data-product_id="5" data-url="/"></div>"#,
+            expect![[
+                r#"<div data-off-canvas-cart="true" data-product-id="5" data-url="/"></div>"#
+            ]],
+        );
+    }
+
+    #[test]
+    fn rule_reports_with_custom_regex() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.data_attribute_name_regex = "^data-[a-z]+-plugin(-options)?$".to_string();
+
+        test_rule_with_config(
+            "html-data-attribute-naming",
+            r#"<div data-cart-plugin="true" data-cart-plugin-options="{}" data-cart="true"></div>"#,
+            config,
+            expect![[r#"
+                help[html-data-attribute-naming]: data attribute name does not match the naming convention
+                  ┌─ ./debug-rule.html.twig:1:60
+                  │
+                1 │ <div data-cart-plugin="true" data-cart-plugin-options="{}" data-cart="true"></div>
+                  │                                                            ^^^^^^^^^ help: rename this attribute to match ^data-[a-z]+-plugin(-options)?$
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_reports_invalid_regex() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.data_attribute_name_regex = "^data-(".to_string();
+
+        test_rule_with_config(
+            "html-data-attribute-naming",
+            r#"<div data-cart="true"></div>"#,
+            config,
+            expect![[r#"
+                error[html-data-attribute-naming]: can't use data-attribute-name-regex '^data-(': regex parse error:
+                    ^data-(
+                          ^
+                error: unclosed group
+                 = in file ./debug-rule.html.twig
+
+            "#]],
+        );
+    }
+}
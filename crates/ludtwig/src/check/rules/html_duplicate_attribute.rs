@@ -0,0 +1,185 @@
+use ludtwig_parser::syntax::editor::{make_token, SyntaxEdit, SyntaxEditor};
+use ludtwig_parser::syntax::typed::{AstNode, HtmlAttribute};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode, SyntaxNodeExt};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleHtmlDuplicateAttribute;
+
+impl Rule for RuleHtmlDuplicateAttribute {
+    fn name(&self) -> &'static str {
+        "html-duplicate-attribute"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Browsers only use the first of multiple attributes with the same name, all later ones are silently dropped.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        if node.kind() != SyntaxKind::HTML_ATTRIBUTE_LIST {
+            return None;
+        }
+
+        // attributes inside of twig syntax (like `{% if %}`) are included
+        let attributes: Vec<(HtmlAttribute, String)> = node
+            .descendants()
+            .filter_map(HtmlAttribute::cast)
+            .filter_map(|a| {
+                let name = attribute_name(&a)?;
+                Some((a, name))
+            })
+            .collect();
+
+        let results: Vec<_> = attributes
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, (duplicate, name))| {
+                let (first, _) = attributes[..idx].iter().find(|(first, first_name)| {
+                    first_name == name && rendered_together(first, duplicate)
+                })?;
+                Some(self.create_duplicate_result(first, duplicate, name))
+            })
+            .collect();
+
+        if results.is_empty() {
+            None
+        } else {
+            Some(results)
+        }
+    }
+}
+
+impl RuleHtmlDuplicateAttribute {
+    fn create_duplicate_result(
+        &self,
+        first: &HtmlAttribute,
+        duplicate: &HtmlAttribute,
+        name: &str,
+    ) -> CheckResult {
+        let mut result = self
+            .create_result(Severity::Warning, format!("duplicate attribute '{name}'"))
+            .primary_note(
+                duplicate.syntax().text_range_trimmed_trivia(),
+                "this attribute is ignored by browsers",
+            )
+            .secondary_note(
+                first.syntax().text_range_trimmed_trivia(),
+                "because it is already defined here",
+            );
+
+        // class lists in the same scope can be merged into the first attribute
+        if name == "class" && first.syntax().parent() == duplicate.syntax().parent() {
+            if let Some(edit) = merge_class_lists(first, duplicate) {
+                result = result.edit_suggestions(&edit, "Try merging the class lists");
+            }
+        }
+
+        result
+    }
+}
+
+/// Name of the attribute (lowercase, because html attributes are case-insensitive),
+/// for vue directives the full directive like `@click`, `None` for dynamic names like `{{ name }}="value"`
+fn attribute_name(attribute: &HtmlAttribute) -> Option<String> {
+    match attribute.vue_directive() {
+        Some(directive) => Some(directive.syntax().text().to_string().trim().to_string()),
+        None => attribute.name().map(|n| n.text().to_ascii_lowercase()),
+    }
+}
+
+/// Attributes in different twig branches (like `{% if %}` and `{% else %}`) are never rendered together,
+/// but an attribute inside of a branch is always rendered together with the ones around the branch
+fn rendered_together(a: &HtmlAttribute, b: &HtmlAttribute) -> bool {
+    let (Some(scope_a), Some(scope_b)) = (a.syntax().parent(), b.syntax().parent()) else {
+        return false;
+    };
+
+    scope_a.ancestors().any(|n| n == scope_b) || scope_b.ancestors().any(|n| n == scope_a)
+}
+
+/// Move the classes of the duplicate attribute into the value of the first one
+fn merge_class_lists(first: &HtmlAttribute, duplicate: &HtmlAttribute) -> Option<SyntaxEdit> {
+    let first_value = first.html_string()?;
+    let first_inner = first_value.get_inner()?;
+    let closing_quote = first_value.get_closing_quote()?;
+    let duplicate_inner = duplicate.html_string()?.get_inner()?;
+
+    let mut editor = SyntaxEditor::new(first.syntax().ancestors().last()?);
+    if !first_inner.syntax().text().is_empty() && !duplicate_inner.syntax().text().is_empty() {
+        editor.insert_before(
+            closing_quote.clone(),
+            make_token(SyntaxKind::TK_WHITESPACE, " "),
+        );
+    }
+    for element in duplicate_inner.syntax().children_with_tokens() {
+        editor.insert_before(closing_quote.clone(), element);
+    }
+    editor.delete(duplicate.syntax().clone());
+
+    Some(editor.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_does_not_fix, test_rule_fix};
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "html-duplicate-attribute",
+            r#"<div id="a" class="a" ID="b" {% if b %}class="b"{% endif %}></div>"#,
+            expect![[r#"
+                warning[html-duplicate-attribute]: duplicate attribute 'id'
+                  ┌─ ./debug-rule.html.twig:1:23
+                  │
+                1 │ <div id="a" class="a" ID="b" {% if b %}class="b"{% endif %}></div>
+                  │      ------           ^^^^^^ this attribute is ignored by browsers
+                  │      │                 
+                  │      because it is already defined here
+
+                warning[html-duplicate-attribute]: duplicate attribute 'class'
+                  ┌─ ./debug-rule.html.twig:1:40
+                  │
+                1 │ <div id="a" class="a" ID="b" {% if b %}class="b"{% endif %}></div>
+                  │             ---------                  ^^^^^^^^^ this attribute is ignored by browsers
+                  │             │                           
+                  │             because it is already defined here
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_separate_branches() {
+        test_rule(
+            "html-duplicate-attribute",
+            r#"<div {% if a %}class="a"{% elseif b %}class="b"{% else %}class="c"{% endif %} :class="d" @click="e" @click.once="f"></div>"#,
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes() {
+        test_rule_fix(
+            "html-duplicate-attribute",
+            r#"<div class="a {{ b }}" id="c" class="d e"></div>"#,
+            expect![[r#"<div class="a {{ b }} d e" id="c"></div>"#]],
+        );
+        test_rule_fix(
+            "html-duplicate-attribute",
+            r#"<div class="" class="d"></div>"#,
+            expect![[r#"<div class="d"></div>"#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_fix() {
+        test_rule_does_not_fix(
+            "html-duplicate-attribute",
+            r#"<div class="a" {% if b %}class="b"{% endif %} id="a" id="b"></div>"#,
+            expect![[r#"<div class="a" {% if b %}class="b"{% endif %} id="a" id="b"></div>"#]],
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use ludtwig_parser::syntax::typed::{AstNode, HtmlAttribute};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode, SyntaxNodeExt};
+
+use crate::check::is_ignored_by_directive;
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleHtmlDuplicateId;
+
+impl Rule for RuleHtmlDuplicateId {
+    fn name(&self) -> &'static str {
+        "html-duplicate-id"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Ids must be unique in the whole page, otherwise labels, anchors and scripts like `getElementById` only find the first element with the id.")
+    }
+
+    fn check_root(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        // earlier attributes by their id, the first occurrence is reported together with every later one
+        let mut seen: HashMap<String, Vec<HtmlAttribute>> = HashMap::new();
+        let mut results = vec![];
+
+        for attribute in node.descendants().filter_map(HtmlAttribute::cast) {
+            let Some(id) = static_id(&attribute) else {
+                continue;
+            };
+            let earlier = seen.entry(id.clone()).or_default();
+
+            if let Some(first) = earlier
+                .iter()
+                .find(|other| !in_different_branches(other.syntax(), attribute.syntax()))
+            {
+                if !self.is_ignored(attribute.syntax()) {
+                    results.push(
+                        self.create_result(Severity::Warning, format!("duplicate id '{id}'"))
+                            .primary_note(
+                                attribute.syntax().text_range_trimmed_trivia(),
+                                "this id is already used in the template",
+                            )
+                            .secondary_note(
+                                first.syntax().text_range_trimmed_trivia(),
+                                "first used here",
+                            ),
+                    );
+                }
+            }
+
+            earlier.push(attribute);
+        }
+
+        Some(results)
+    }
+}
+
+impl RuleHtmlDuplicateId {
+    /// Whether the attribute is inside of an error node or ignored with a ludtwig-ignore directive
+    fn is_ignored(&self, node: &SyntaxNode) -> bool {
+        node.ancestors()
+            .any(|a| a.kind() == SyntaxKind::ERROR || is_ignored_by_directive(&a, self.name()))
+    }
+}
+
+/// Value of an `id` attribute, if it doesn't contain twig syntax like `id="item-{{ loop.index }}"`
+fn static_id(attribute: &HtmlAttribute) -> Option<String> {
+    if !attribute.name()?.text().eq_ignore_ascii_case("id") {
+        return None;
+    }
+
+    let value = attribute.value();
+    let id = value.static_text()?;
+    if id.trim().is_empty() {
+        None
+    } else {
+        Some(id.to_string())
+    }
+}
+
+/// Nodes in different branches of the same `{% if %}` are never rendered together
+fn in_different_branches(a: &SyntaxNode, b: &SyntaxNode) -> bool {
+    let common_ancestor = a
+        .ancestors()
+        .find(|ancestor| b.ancestors().any(|n| n == *ancestor));
+    common_ancestor.is_some_and(|ancestor| ancestor.kind() == SyntaxKind::TWIG_IF)
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::test_rule;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "html-duplicate-id",
+            r#"<div id="main">
+    <label for="email">a</label><input id="email">
+    {% if a %}<input id="email">{% endif %}
+    <section ID="main"></section>
+</div>"#,
+            expect![[r#"
+                warning[html-duplicate-id]: duplicate id 'email'
+                  ┌─ ./debug-rule.html.twig:3:22
+                  │
+                2 │     <label for="email">a</label><input id="email">
+                  │                                        ---------- first used here
+                3 │     {% if a %}<input id="email">{% endif %}
+                  │                      ^^^^^^^^^^ this id is already used in the template
+
+                warning[html-duplicate-id]: duplicate id 'main'
+                  ┌─ ./debug-rule.html.twig:4:14
+                  │
+                1 │ <div id="main">
+                  │      --------- first used here
+                  ·
+                4 │     <section ID="main"></section>
+                  │              ^^^^^^^^^ this id is already used in the template
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "html-duplicate-id",
+            r#"<div id="a"></div>
+{% if b %}<div id="b"></div>{% elseif c %}<div id="b"></div>{% else %}<div id="b"></div>{% endif %}
+{% for item in items %}<div id="item-{{ item.id }}"></div><div id="item-{{ item.id }}"></div>{% endfor %}
+<div :id="a"></div><div id=""></div><div id=""></div><div class="a"></div>"#,
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_respects_ignore_directives() {
+        test_rule(
+            "html-duplicate-id",
+            r#"<div id="a"></div>
+{# ludtwig-ignore html-duplicate-id #}
+<div id="a"></div>"#,
+            expect![[r#""#]],
+        );
+    }
+}
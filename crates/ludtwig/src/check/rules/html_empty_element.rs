@@ -0,0 +1,95 @@
+use ludtwig_parser::syntax::typed::{AstNode, HtmlAttribute, HtmlTag};
+use ludtwig_parser::syntax::untyped::{SyntaxElement, SyntaxNode, SyntaxNodeExt};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::rules::a11y_img_alt::has_dynamic_attributes;
+
+/// Vue directives which fill the content of the element
+static CONTENT_DIRECTIVES: &[&str] = &["v-html", "v-text"];
+
+pub struct RuleHtmlEmptyElement;
+
+impl Rule for RuleHtmlEmptyElement {
+    fn name(&self) -> &'static str {
+        "html-empty-element"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Empty text elements like <p></p> are often leftovers and are better replaced with css spacing.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let tag = HtmlTag::cast(node)?;
+        let name = tag.name()?;
+        let body = tag.body()?;
+        tag.ending_tag()?; // void and self closing elements can't have content
+
+        if !ctx
+            .config()
+            .rules
+            .empty_element_tags
+            .iter()
+            .any(|t| t.eq_ignore_ascii_case(name.text()))
+            || has_dynamic_attributes(&tag)
+            || tag.attributes().any(|a| is_content_directive(&a))
+        {
+            return None;
+        }
+
+        let is_empty = body
+            .syntax()
+            .children_with_tokens()
+            .all(|child| match child {
+                SyntaxElement::Token(t) => t.kind().is_trivia(),
+                SyntaxElement::Node(_) => false,
+            });
+        if !is_empty {
+            return None;
+        }
+
+        let result = self
+            .create_result(Severity::Help, "Empty html element")
+            .primary_note(
+                tag.starting_tag()?.syntax().text_range_trimmed_trivia(),
+                format!("this <{}> has no content", name.text()),
+            );
+
+        Some(vec![result])
+    }
+}
+
+fn is_content_directive(attribute: &HtmlAttribute) -> bool {
+    attribute
+        .vue_directive()
+        .and_then(|directive| directive.get_directive_name())
+        .is_some_and(|name| CONTENT_DIRECTIVES.contains(&name.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::test_rule;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "html-empty-element",
+            "<p></p>\n<H2 class=\"title\">\n</H2>\n<p>a</p>\n<p>{{ b }}</p>\n<p v-html=\"c\"></p>\n<p {{ attributes }}></p>\n<div></div>\n<li><br></li>",
+            expect![[r#"
+                help[html-empty-element]: Empty html element
+                  ┌─ ./debug-rule.html.twig:1:1
+                  │
+                1 │ <p></p>
+                  │ ^^^ this <p> has no content
+
+                help[html-empty-element]: Empty html element
+                  ┌─ ./debug-rule.html.twig:2:1
+                  │
+                2 │ <H2 class="title">
+                  │ ^^^^^^^^^^^^^^^^^^ this <H2> has no content
+
+            "#]],
+        );
+    }
+}
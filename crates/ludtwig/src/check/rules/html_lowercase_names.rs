@@ -0,0 +1,334 @@
+use ludtwig_parser::syntax::typed::{AstNode, HtmlAttribute, HtmlTag};
+use ludtwig_parser::syntax::untyped::{SyntaxElement, SyntaxNode, SyntaxToken};
+use ludtwig_parser::T;
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::rules::twig_no_autoescape_off::{is_allowed_file, validate_patterns};
+use crate::Config;
+
+/// Names of the html elements, any other tag name (like vue components) keeps its case
+pub(super) static HTML_ELEMENTS: &[&str] = &[
+    "a",
+    "abbr",
+    "address",
+    "area",
+    "article",
+    "aside",
+    "audio",
+    "b",
+    "base",
+    "bdi",
+    "bdo",
+    "blockquote",
+    "body",
+    "br",
+    "button",
+    "canvas",
+    "caption",
+    "cite",
+    "code",
+    "col",
+    "colgroup",
+    "data",
+    "datalist",
+    "dd",
+    "del",
+    "details",
+    "dfn",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "embed",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "i",
+    "iframe",
+    "img",
+    "input",
+    "ins",
+    "kbd",
+    "label",
+    "legend",
+    "li",
+    "link",
+    "main",
+    "map",
+    "mark",
+    "math",
+    "menu",
+    "meta",
+    "meter",
+    "nav",
+    "noscript",
+    "object",
+    "ol",
+    "optgroup",
+    "option",
+    "output",
+    "p",
+    "picture",
+    "pre",
+    "progress",
+    "q",
+    "rp",
+    "rt",
+    "ruby",
+    "s",
+    "samp",
+    "script",
+    "search",
+    "section",
+    "select",
+    "slot",
+    "small",
+    "source",
+    "span",
+    "strong",
+    "style",
+    "sub",
+    "summary",
+    "sup",
+    "svg",
+    "table",
+    "tbody",
+    "td",
+    "template",
+    "textarea",
+    "tfoot",
+    "th",
+    "thead",
+    "time",
+    "title",
+    "tr",
+    "track",
+    "u",
+    "ul",
+    "var",
+    "video",
+    "wbr",
+];
+
+/// Elements with their own case-sensitive names (like `viewBox` or `<foreignObject>`)
+static FOREIGN_ELEMENTS: &[&str] = &["svg", "math"];
+
+pub struct RuleHtmlLowercaseNames;
+
+impl Rule for RuleHtmlLowercaseNames {
+    fn name(&self) -> &'static str {
+        "html-lowercase-names"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("HTML tag and attribute names are case-insensitive, lowercase names are the common convention.")
+    }
+
+    fn validate_config(&self, config: &Config) -> Result<(), String> {
+        validate_patterns("vue-files", &config.rules.vue_files)
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let tag = HtmlTag::cast(node)?;
+        let name = tag.name()?;
+        if !is_one_of(name.text(), HTML_ELEMENTS) || is_inside_foreign_element(&tag) {
+            return None;
+        }
+        // vue resolves PascalCase tags like `<Button>` to components
+        if is_pascal_case(name.text())
+            && is_allowed_file(ctx.file_path(), &ctx.config().rules.vue_files)
+        {
+            return None;
+        }
+
+        let mut results = vec![];
+        if has_uppercase(name.text()) {
+            let lowercase_name = name.text().to_ascii_lowercase();
+            let mut result = self
+                .create_result(Severity::Help, "Tag name is not lowercase")
+                .primary_note(name.text_range(), "help: write this tag name in lowercase")
+                .suggestion(
+                    name.text_range(),
+                    lowercase_name.clone(),
+                    "Try this name instead",
+                );
+
+            if let Some(ending_name) = ending_tag_name(&tag) {
+                result = result.suggestion(
+                    ending_name.text_range(),
+                    lowercase_name,
+                    "Also rename the ending tag",
+                );
+            }
+            results.push(result);
+        }
+
+        // attributes of foreign elements like `<svg viewBox="...">` keep their case
+        if !is_one_of(name.text(), FOREIGN_ELEMENTS) {
+            // attributes inside of twig syntax (like `{% if %}`) are included
+            let attribute_names = tag
+                .starting_tag()?
+                .syntax()
+                .descendants()
+                .filter_map(HtmlAttribute::cast)
+                .filter(|attribute| attribute.vue_directive().is_none())
+                .filter_map(|attribute| attribute.name())
+                .filter(|name| has_uppercase(name.text()) && !is_camel_case(name.text()));
+
+            results.extend(attribute_names.map(|name| {
+                self.create_result(Severity::Help, "Attribute name is not lowercase")
+                    .primary_note(
+                        name.text_range(),
+                        "help: write this attribute name in lowercase",
+                    )
+                    .suggestion(
+                        name.text_range(),
+                        name.text().to_ascii_lowercase(),
+                        "Try this name instead",
+                    )
+            }));
+        }
+
+        if results.is_empty() {
+            None
+        } else {
+            Some(results)
+        }
+    }
+}
+
+pub(super) fn is_one_of(name: &str, names: &[&str]) -> bool {
+    names.iter().any(|n| n.eq_ignore_ascii_case(name))
+}
+
+fn has_uppercase(name: &str) -> bool {
+    name.chars().any(|c| c.is_ascii_uppercase())
+}
+
+fn is_pascal_case(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name.chars().any(|c| c.is_ascii_lowercase())
+}
+
+/// camelCase names like `dataValue` are left to the `html-attribute-name-kebab-case` rule,
+/// which suggests `data-value` instead of `datavalue`
+fn is_camel_case(name: &str) -> bool {
+    name.as_bytes()
+        .windows(2)
+        .any(|pair| pair[0].is_ascii_lowercase() && pair[1].is_ascii_uppercase())
+}
+
+pub(super) fn is_inside_foreign_element(tag: &HtmlTag) -> bool {
+    tag.syntax()
+        .ancestors()
+        .skip(1)
+        .filter_map(HtmlTag::cast)
+        .any(|ancestor| {
+            ancestor
+                .name()
+                .is_some_and(|name| is_one_of(name.text(), FOREIGN_ELEMENTS))
+        })
+}
+
+pub(super) fn ending_tag_name(tag: &HtmlTag) -> Option<SyntaxToken> {
+    tag.ending_tag()?
+        .syntax()
+        .children_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .find(|t| t.kind() == T![word])
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_fix, test_rule_with_config};
+    use crate::config::Config;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "html-lowercase-names",
+            r#"<DIV Class="a">b</DIV>"#,
+            expect![[r#"
+                help[html-lowercase-names]: Tag name is not lowercase
+                  ┌─ ./debug-rule.html.twig:1:2
+                  │
+                1 │ <DIV Class="a">b</DIV>
+                  │  ^^^              --- Also rename the ending tag: div
+                  │  │                 
+                  │  help: write this tag name in lowercase
+                  │  Try this name instead: div
+
+                help[html-lowercase-names]: Attribute name is not lowercase
+                  ┌─ ./debug-rule.html.twig:1:6
+                  │
+                1 │ <DIV Class="a">b</DIV>
+                  │      ^^^^^
+                  │      │
+                  │      help: write this attribute name in lowercase
+                  │      Try this name instead: class
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "html-lowercase-names",
+            r#"<MyComponent someProp="a" :otherProp="b" @Click="c"></MyComponent>
+<svg viewBox="0 0 10 10"><linearGradient gradientUnits="a"></linearGradient></svg>
+<div class="{{ Value }}" data-{{ Name }}="d">e</div>"#,
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes() {
+        test_rule_fix(
+            "html-lowercase-names",
+            r#"<DIV CLASS="x" {% if a %}Data-A="a"{% endif %} dataValue="b"><BR><Span ID="b">c</Span></DIV>"#,
+            expect![[
+                r#"<div class="x" {% if a %}data-a="a"{% endif %} dataValue="b"><br><span id="b">c</span></div>"#
+            ]],
+        );
+    }
+
+    #[test]
+    fn rule_skips_pascal_case_components_in_vue_files() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.vue_files = vec!["*.html.twig".to_string()];
+
+        test_rule_with_config(
+            "html-lowercase-names",
+            r#"<Button @click="save">Save</Button><Form></Form><DIV></DIV>"#,
+            config,
+            expect![[r#"
+                help[html-lowercase-names]: Tag name is not lowercase
+                  ┌─ ./debug-rule.html.twig:1:50
+                  │
+                1 │ <Button @click="save">Save</Button><Form></Form><DIV></DIV>
+                  │                                                  ^^^   --- Also rename the ending tag: div
+                  │                                                  │      
+                  │                                                  help: write this tag name in lowercase
+                  │                                                  Try this name instead: div
+
+            "#]],
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxToken, TextRange, TextSize};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::config::NbspStyle;
+
+const NBSP_ENTITIES: &[&str] = &["&nbsp;", "&#160;", "&#xa0;"];
+
+pub struct RuleHtmlNbspStyle;
+
+impl Rule for RuleHtmlNbspStyle {
+    fn name(&self) -> &'static str {
+        "html-nbsp-style"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Mixing &nbsp; entities and literal non-breaking spaces makes it hard to see where non-breaking spaces are used.")
+    }
+
+    fn check_token(&self, token: SyntaxToken, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        // only plain html text content is relevant
+        let parent = token.parent()?;
+        if !matches!(
+            parent.kind(),
+            SyntaxKind::ROOT | SyntaxKind::BODY | SyntaxKind::HTML_TEXT
+        ) {
+            return None;
+        }
+
+        let style = &ctx.config().format.nbsp_style;
+        let message = format!("use {style} instead");
+
+        match style {
+            NbspStyle::Entity if token.kind() == SyntaxKind::TK_UNKNOWN => {
+                let results: Vec<CheckResult> = token
+                    .text()
+                    .char_indices()
+                    .filter(|(_, c)| *c == '\u{00A0}')
+                    .map(|(offset, c)| {
+                        #[allow(clippy::cast_possible_truncation)]
+                        let range = TextRange::at(
+                            token.text_range().start() + TextSize::from(offset as u32),
+                            TextSize::of(c),
+                        );
+
+                        self.create_result(Severity::Help, "literal non-breaking space")
+                            .primary_note(range, "this looks like a normal space, but isn't one")
+                            .suggestion(range, "&nbsp;", message.clone())
+                    })
+                    .collect();
+
+                Some(results)
+            }
+            NbspStyle::Literal if token.kind() == SyntaxKind::TK_HTML_ESCAPE_CHARACTER => {
+                if !NBSP_ENTITIES
+                    .iter()
+                    .any(|e| e.eq_ignore_ascii_case(token.text()))
+                {
+                    return None;
+                }
+
+                let result = self
+                    .create_result(Severity::Help, "non-breaking space html entity")
+                    .primary_note(token.text_range(), "this is a non-breaking space")
+                    .suggestion(token.text_range(), "\u{00A0}", message);
+
+                Some(vec![result])
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_fix, test_rule_with_config};
+    use crate::config::{Config, NbspStyle};
+
+    fn literal_config() -> Config {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.format.nbsp_style = NbspStyle::Literal;
+        config
+    }
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "html-nbsp-style",
+            "<p>100\u{00A0}€</p>",
+            expect![[r#"
+                help[html-nbsp-style]: literal non-breaking space
+                  ┌─ ./debug-rule.html.twig:1:7
+                  │
+                1 │ <p>100 €</p>
+                  │       ^
+                  │       │
+                  │       this looks like a normal space, but isn't one
+                  │       use the html entity (&nbsp;) instead: &nbsp;
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes() {
+        test_rule_fix(
+            "html-nbsp-style",
+            "<p>100\u{00A0}€ and 5\u{00A0}kg</p>\n<p title=\"a\u{00A0}b\">{{ 'a\u{00A0}b' }}</p>",
+            expect![[r#"
+                <p>100&nbsp;€ and 5&nbsp;kg</p>
+                <p title="a b">{{ 'a b' }}</p>"#]],
+        );
+    }
+
+    #[test]
+    fn rule_reports_literal_style() {
+        test_rule_with_config(
+            "html-nbsp-style",
+            "<p>100&nbsp;€ and 5&#160;kg &amp;</p>",
+            literal_config(),
+            expect![[r#"
+                help[html-nbsp-style]: non-breaking space html entity
+                  ┌─ ./debug-rule.html.twig:1:7
+                  │
+                1 │ <p>100&nbsp;€ and 5&#160;kg &amp;</p>
+                  │       ^^^^^^
+                  │       │
+                  │       this is a non-breaking space
+                  │       use the literal character (U+00A0) instead:  
+
+                help[html-nbsp-style]: non-breaking space html entity
+                  ┌─ ./debug-rule.html.twig:1:20
+                  │
+                1 │ <p>100&nbsp;€ and 5&#160;kg &amp;</p>
+                  │                    ^^^^^^
+                  │                    │
+                  │                    this is a non-breaking space
+                  │                    use the literal character (U+00A0) instead:  
+
+            "#]],
+        );
+    }
+}
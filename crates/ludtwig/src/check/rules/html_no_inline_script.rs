@@ -0,0 +1,171 @@
+use ludtwig_parser::syntax::typed::{
+    AstNode, HtmlAttribute, HtmlAttributeValue, HtmlAttributeValueSegment,
+};
+use ludtwig_parser::syntax::untyped::{SyntaxNode, SyntaxNodeExt};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+/// Attributes which contain urls, that are navigated to or loaded
+static URL_ATTRIBUTES: &[&str] = &["href", "src", "action", "formaction"];
+
+pub struct RuleHtmlNoInlineScript;
+
+impl Rule for RuleHtmlNoInlineScript {
+    fn name(&self) -> &'static str {
+        "html-no-inline-script"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Inline event handlers (like onclick=\"...\") and javascript: urls are blocked by a Content-Security-Policy without 'unsafe-inline'. Register the event listeners in a script file instead.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let attribute = HtmlAttribute::cast(node)?;
+
+        if let Some(directive) = attribute.vue_directive() {
+            if ctx.config().rules.allow_vue_event_handlers
+                || directive.get_directive_name().as_deref() != Some("v-on")
+            {
+                return None;
+            }
+
+            let result = self
+                .create_result(Severity::Warning, "vue event handler")
+                .primary_note(
+                    attribute.syntax().text_range_trimmed_trivia(),
+                    "register the event listener in a script file instead",
+                );
+            return Some(vec![result]);
+        }
+
+        let name = attribute.name()?;
+        let lowercase_name = name.text().to_ascii_lowercase();
+        if is_event_handler(&lowercase_name) {
+            let result = self
+                .create_result(Severity::Warning, "inline event handler")
+                .primary_note(
+                    attribute.syntax().text_range_trimmed_trivia(),
+                    "register the event listener in a script file instead",
+                );
+            return Some(vec![result]);
+        }
+
+        if URL_ATTRIBUTES.contains(&lowercase_name.as_str())
+            && is_javascript_url(&attribute.value())
+        {
+            let result = self
+                .create_result(Severity::Warning, "javascript: url")
+                .primary_note(
+                    attribute.syntax().text_range_trimmed_trivia(),
+                    "use a button with an event listener in a script file instead",
+                );
+            return Some(vec![result]);
+        }
+
+        None
+    }
+}
+
+/// Is the (lowercase) attribute name an event handler like `onclick`
+fn is_event_handler(name: &str) -> bool {
+    name.strip_prefix("on")
+        .is_some_and(|event| !event.is_empty() && event.chars().all(|c| c.is_ascii_lowercase()))
+}
+
+/// Does the value start with the `javascript:` scheme, browsers ignore whitespace in front of it
+/// and tabs or line breaks inside of it
+fn is_javascript_url(value: &HtmlAttributeValue) -> bool {
+    let text = match value {
+        HtmlAttributeValue::Missing => return false,
+        HtmlAttributeValue::Static(text) => text,
+        HtmlAttributeValue::Mixed(segments) => match segments.first() {
+            Some(HtmlAttributeValueSegment::Text(text)) => text,
+            _ => return false,
+        },
+    };
+
+    let scheme: String = text
+        .trim_start()
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+        .take("javascript:".len())
+        .collect();
+    scheme.eq_ignore_ascii_case("javascript:")
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_with_config};
+    use crate::config::Config;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "html-no-inline-script",
+            r#"<button onclick="send()">A</button><a href=" JavaScript:void(0)">B</a><a href="javascript:{{ code }}">C</a><form action="javascript:submit()"></form>"#,
+            expect![[r#"
+                warning[html-no-inline-script]: inline event handler
+                  ┌─ ./debug-rule.html.twig:1:9
+                  │
+                1 │ <button onclick="send()">A</button><a href=" JavaScript:void(0)">B</a><a href="javascript:{{ code }}">C</a><form action="javascript:submit()"></form>
+                  │         ^^^^^^^^^^^^^^^^ register the event listener in a script file instead
+
+                warning[html-no-inline-script]: javascript: url
+                  ┌─ ./debug-rule.html.twig:1:39
+                  │
+                1 │ <button onclick="send()">A</button><a href=" JavaScript:void(0)">B</a><a href="javascript:{{ code }}">C</a><form action="javascript:submit()"></form>
+                  │                                       ^^^^^^^^^^^^^^^^^^^^^^^^^^ use a button with an event listener in a script file instead
+
+                warning[html-no-inline-script]: javascript: url
+                  ┌─ ./debug-rule.html.twig:1:74
+                  │
+                1 │ <button onclick="send()">A</button><a href=" JavaScript:void(0)">B</a><a href="javascript:{{ code }}">C</a><form action="javascript:submit()"></form>
+                  │                                                                          ^^^^^^^^^^^^^^^^^^^^^^^^^^^^ use a button with an event listener in a script file instead
+
+                warning[html-no-inline-script]: javascript: url
+                  ┌─ ./debug-rule.html.twig:1:114
+                  │
+                1 │ <button onclick="send()">A</button><a href=" JavaScript:void(0)">B</a><a href="javascript:{{ code }}">C</a><form action="javascript:submit()"></form>
+                  │                                                                                                                  ^^^^^^^^^^^^^^^^^^^^^^^^^^^^ use a button with an event listener in a script file instead
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "html-no-inline-script",
+            r#"<button @click="send" v-on:keyup.enter="send" :onclick="handler" data-onclick="a">A</button><a href="/javascript:">B</a><a href="{{ url }}">C</a><div on>D</div>"#,
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_reports_vue_event_handlers() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.allow_vue_event_handlers = false;
+
+        test_rule_with_config(
+            "html-no-inline-script",
+            r#"<button @click="send" v-on:keyup.enter="send" :title="a">A</button>"#,
+            config,
+            expect![[r#"
+                warning[html-no-inline-script]: vue event handler
+                  ┌─ ./debug-rule.html.twig:1:9
+                  │
+                1 │ <button @click="send" v-on:keyup.enter="send" :title="a">A</button>
+                  │         ^^^^^^^^^^^^^ register the event listener in a script file instead
+
+                warning[html-no-inline-script]: vue event handler
+                  ┌─ ./debug-rule.html.twig:1:23
+                  │
+                1 │ <button @click="send" v-on:keyup.enter="send" :title="a">A</button>
+                  │                       ^^^^^^^^^^^^^^^^^^^^^^^ register the event listener in a script file instead
+
+            "#]],
+        );
+    }
+}
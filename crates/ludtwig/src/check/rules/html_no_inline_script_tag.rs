@@ -0,0 +1,118 @@
+use ludtwig_parser::syntax::typed::{AstNode, HtmlTag};
+use ludtwig_parser::syntax::untyped::{SyntaxNode, SyntaxNodeExt};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::rules::a11y_img_alt::{find_attribute, static_value};
+
+pub struct RuleHtmlNoInlineScriptTag;
+
+impl Rule for RuleHtmlNoInlineScriptTag {
+    fn name(&self) -> &'static str {
+        "html-no-inline-script-tag"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Inline <script> elements are blocked by a Content-Security-Policy without 'unsafe-inline'. Move the code into a script file (like a storefront plugin) and load it with <script src=\"...\">.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let tag = HtmlTag::cast(node)?;
+        if !tag.name()?.text().eq_ignore_ascii_case("script") {
+            return None;
+        }
+
+        let rules = &ctx.config().rules;
+        if find_attribute(&tag, "src").is_some()
+            || (rules.inline_code_allow_nonce && find_attribute(&tag, "nonce").is_some())
+        {
+            return None;
+        }
+
+        // data blocks like `type="application/ld+json"` are not executed
+        if let Some(script_type) = find_attribute(&tag, "type") {
+            let Some(script_type) = static_value(&script_type) else {
+                return None; // unknown before rendering
+            };
+            if rules
+                .inline_script_allowed_types
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(script_type.trim()))
+            {
+                return None;
+            }
+        }
+
+        let result = self
+            .create_result(Severity::Warning, "inline script")
+            .primary_note(
+                tag.starting_tag()?.syntax().text_range_trimmed_trivia(),
+                "move the code into a script file and load it with the src attribute",
+            );
+
+        Some(vec![result])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_with_config};
+    use crate::config::Config;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "html-no-inline-script-tag",
+            r#"<script>window.a = 1;</script>
+<script type="module">import a from './a.js';</script>"#,
+            expect![[r#"
+                warning[html-no-inline-script-tag]: inline script
+                  ┌─ ./debug-rule.html.twig:1:1
+                  │
+                1 │ <script>window.a = 1;</script>
+                  │ ^^^^^^^^ move the code into a script file and load it with the src attribute
+
+                warning[html-no-inline-script-tag]: inline script
+                  ┌─ ./debug-rule.html.twig:2:1
+                  │
+                2 │ <script type="module">import a from './a.js';</script>
+                  │ ^^^^^^^^^^^^^^^^^^^^^^ move the code into a script file and load it with the src attribute
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "html-no-inline-script-tag",
+            r#"<script src="{{ asset('a.js') }}"></script>
+<script type="application/ld+json">{"@type": "Product"}</script>
+<script type="{{ type }}">a</script>
+<script nonce="{{ cspNonce }}">window.a = 1;</script>
+<div>script</div>"#,
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_reports_nonce_if_not_allowed() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.inline_code_allow_nonce = false;
+
+        test_rule_with_config(
+            "html-no-inline-script-tag",
+            r#"<script nonce="{{ cspNonce }}">window.a = 1;</script>"#,
+            config,
+            expect![[r#"
+                warning[html-no-inline-script-tag]: inline script
+                  ┌─ ./debug-rule.html.twig:1:1
+                  │
+                1 │ <script nonce="{{ cspNonce }}">window.a = 1;</script>
+                  │ ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ move the code into a script file and load it with the src attribute
+
+            "#]],
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use ludtwig_parser::syntax::typed::{AstNode, HtmlAttribute, HtmlTag};
+use ludtwig_parser::syntax::untyped::{SyntaxNode, SyntaxNodeExt};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::rules::a11y_img_alt::find_attribute;
+
+pub struct RuleHtmlNoInlineStyle;
+
+impl Rule for RuleHtmlNoInlineStyle {
+    fn name(&self) -> &'static str {
+        "html-no-inline-style"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Style attributes and <style> elements are blocked by a Content-Security-Policy without 'unsafe-inline' for styles. Use classes and a stylesheet (like the theme scss) instead.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let rules = &ctx.config().rules;
+
+        if let Some(attribute) = HtmlAttribute::cast(node.clone()) {
+            // vue style bindings like `:style` are applied by script and are allowed
+            if !rules.inline_style_attributes
+                || !attribute.name()?.text().eq_ignore_ascii_case("style")
+            {
+                return None;
+            }
+
+            let result = self
+                .create_result(Severity::Warning, "inline style attribute")
+                .primary_note(
+                    attribute.syntax().text_range_trimmed_trivia(),
+                    "use a class with the styles in a stylesheet instead",
+                );
+            return Some(vec![result]);
+        }
+
+        let tag = HtmlTag::cast(node)?;
+        if !tag.name()?.text().eq_ignore_ascii_case("style")
+            || (rules.inline_code_allow_nonce && find_attribute(&tag, "nonce").is_some())
+        {
+            return None;
+        }
+
+        let result = self
+            .create_result(Severity::Warning, "inline style element")
+            .primary_note(
+                tag.starting_tag()?.syntax().text_range_trimmed_trivia(),
+                "move the styles into a stylesheet",
+            );
+
+        Some(vec![result])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_with_config};
+    use crate::config::Config;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "html-no-inline-style",
+            r#"<div style="color: red" class="a">
+    <style>.a { color: red; }</style>
+    <span STYLE="{{ style }}">b</span>
+</div>"#,
+            expect![[r#"
+                warning[html-no-inline-style]: inline style attribute
+                  ┌─ ./debug-rule.html.twig:1:6
+                  │
+                1 │ <div style="color: red" class="a">
+                  │      ^^^^^^^^^^^^^^^^^^ use a class with the styles in a stylesheet instead
+
+                warning[html-no-inline-style]: inline style element
+                  ┌─ ./debug-rule.html.twig:2:5
+                  │
+                2 │     <style>.a { color: red; }</style>
+                  │     ^^^^^^^ move the styles into a stylesheet
+
+                warning[html-no-inline-style]: inline style attribute
+                  ┌─ ./debug-rule.html.twig:3:11
+                  │
+                3 │     <span STYLE="{{ style }}">b</span>
+                  │           ^^^^^^^^^^^^^^^^^^^ use a class with the styles in a stylesheet instead
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "html-no-inline-style",
+            r#"<div class="a" :style="{ color: color }" data-style="a">
+    <style nonce="{{ cspNonce }}">.a { color: red; }</style>
+</div>"#,
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_attributes_if_allowed() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.inline_style_attributes = false;
+
+        test_rule_with_config(
+            "html-no-inline-style",
+            r#"<div style="color: red"></div>"#,
+            config,
+            expect![[r#""#]],
+        );
+    }
+}
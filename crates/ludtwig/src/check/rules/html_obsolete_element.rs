@@ -0,0 +1,188 @@
+use ludtwig_parser::syntax::typed::{AstNode, HtmlTag};
+use ludtwig_parser::syntax::untyped::SyntaxNode;
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::rules::html_lowercase_names::{ending_tag_name, is_inside_foreign_element};
+
+/// Obsolete elements of the html standard with what should be used instead
+/// and the element which can replace them directly (if there is one)
+static OBSOLETE_ELEMENTS: &[(&str, &str, Option<&str>)] = &[
+    ("acronym", "use <abbr> instead", Some("abbr")),
+    ("applet", "use <object> or <embed> instead", None),
+    ("basefont", "use css for the font instead", None),
+    ("bgsound", "use <audio> instead", None),
+    ("big", "use css font-size instead", None),
+    ("blink", "use css animations instead", None),
+    ("center", "use css text-align or margin instead", None),
+    ("dir", "use <ul> instead", Some("ul")),
+    ("font", "use css for the font instead", None),
+    ("frame", "use <iframe> or css layouts instead", None),
+    ("frameset", "use <iframe> or css layouts instead", None),
+    ("isindex", "use a <form> with an <input> instead", None),
+    ("keygen", "use the web crypto api instead", None),
+    ("listing", "use <pre> and <code> instead", None),
+    ("marquee", "use css animations instead", None),
+    ("menuitem", "use <button> inside of a <menu> instead", None),
+    ("multicol", "use css columns instead", None),
+    ("nextid", "use unique ids instead", None),
+    ("nobr", "use css white-space instead", None),
+    (
+        "noembed",
+        "use <object> with fallback content instead",
+        None,
+    ),
+    ("noframes", "remove it together with the frames", None),
+    ("plaintext", "use <pre> and <code> instead", None),
+    ("spacer", "use css margin or padding instead", None),
+    ("strike", "use <del> or <s> instead", Some("s")),
+    ("tt", "use <code>, <kbd>, <samp> or css instead", None),
+    ("xmp", "use <pre> and <code> instead", None),
+];
+
+pub struct RuleHtmlObsoleteElement;
+
+impl Rule for RuleHtmlObsoleteElement {
+    fn name(&self) -> &'static str {
+        "html-obsolete-element"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Obsolete html elements like <font> or <center> are removed from the html standard and may not be supported by browsers and assistive technology. Additional elements (like deprecated components) can be banned with banned-elements in the config.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let tag = HtmlTag::cast(node)?;
+        let name = tag.name()?;
+
+        if let Some((_, help, replacement)) = OBSOLETE_ELEMENTS
+            .iter()
+            .find(|(obsolete, _, _)| obsolete.eq_ignore_ascii_case(name.text()))
+        {
+            // svg and mathml have their own elements
+            if is_inside_foreign_element(&tag) {
+                return None;
+            }
+
+            let mut result = self
+                .create_result(
+                    Severity::Warning,
+                    format!("Obsolete html element <{}>", name.text()),
+                )
+                .primary_note(name.text_range(), format!("help: {help}"));
+
+            if let Some(replacement) = replacement {
+                result = result.suggestion(
+                    name.text_range(),
+                    *replacement,
+                    format!("Use <{replacement}> instead"),
+                );
+                if let Some(ending_name) = ending_tag_name(&tag) {
+                    result = result.suggestion(
+                        ending_name.text_range(),
+                        *replacement,
+                        "Also rename the ending tag",
+                    );
+                }
+            }
+
+            return Some(vec![result]);
+        }
+
+        if ctx
+            .config()
+            .rules
+            .banned_elements
+            .iter()
+            .any(|banned| banned.eq_ignore_ascii_case(name.text()))
+        {
+            let result = self
+                .create_result(
+                    Severity::Warning,
+                    format!("Banned html element <{}>", name.text()),
+                )
+                .primary_note(
+                    name.text_range(),
+                    "this element is banned in this project by the configuration",
+                );
+
+            return Some(vec![result]);
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_fix, test_rule_with_config};
+    use crate::config::Config;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "html-obsolete-element",
+            r#"<CENTER><font color="red">a</font></CENTER>
+<marquee>b</marquee>"#,
+            expect![[r#"
+                warning[html-obsolete-element]: Obsolete html element <CENTER>
+                  ┌─ ./debug-rule.html.twig:1:2
+                  │
+                1 │ <CENTER><font color="red">a</font></CENTER>
+                  │  ^^^^^^ help: use css text-align or margin instead
+
+                warning[html-obsolete-element]: Obsolete html element <font>
+                  ┌─ ./debug-rule.html.twig:1:10
+                  │
+                1 │ <CENTER><font color="red">a</font></CENTER>
+                  │          ^^^^ help: use css for the font instead
+
+                warning[html-obsolete-element]: Obsolete html element <marquee>
+                  ┌─ ./debug-rule.html.twig:2:2
+                  │
+                2 │ <marquee>b</marquee>
+                  │  ^^^^^^^ help: use css animations instead
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_reports_banned_elements() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.banned_elements = vec!["sw-button-deprecated".to_string()];
+
+        test_rule_with_config(
+            "html-obsolete-element",
+            "<sw-button-deprecated>a</sw-button-deprecated><sw-button>b</sw-button>",
+            config,
+            expect![[r#"
+                warning[html-obsolete-element]: Banned html element <sw-button-deprecated>
+                  ┌─ ./debug-rule.html.twig:1:2
+                  │
+                1 │ <sw-button-deprecated>a</sw-button-deprecated><sw-button>b</sw-button>
+                  │  ^^^^^^^^^^^^^^^^^^^^ this element is banned in this project by the configuration
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "html-obsolete-element",
+            "<div><abbr>a</abbr><s>b</s><code>c</code></div><svg><font></font></svg>",
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes() {
+        test_rule_fix(
+            "html-obsolete-element",
+            "<acronym title=\"a\">b</acronym><strike>c</strike><dir><li>d</li></dir>",
+            expect![[r#"<abbr title="a">b</abbr><s>c</s><ul><li>d</li></ul>"#]],
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use ludtwig_parser::syntax::typed::{AstNode, HtmlStartingTag, HtmlTag};
+use ludtwig_parser::syntax::untyped::{SyntaxElement, SyntaxNode, SyntaxToken, TextRange};
+use ludtwig_parser::T;
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::rules::html_lowercase_names::{
+    is_inside_foreign_element, is_one_of, HTML_ELEMENTS,
+};
+use crate::check::rules::twig_no_autoescape_off::{is_allowed_file, validate_patterns};
+use crate::config::VoidElementStyle;
+use crate::Config;
+
+static HTML_VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+pub struct RuleHtmlSelfClosing;
+
+impl Rule for RuleHtmlSelfClosing {
+    fn name(&self) -> &'static str {
+        "html-self-closing"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Void elements like <br> should consistently be written with or without a slash. Vue components without content are self-closing, but browsers don't support self-closing custom elements outside of vue templates.")
+    }
+
+    fn validate_config(&self, config: &Config) -> Result<(), String> {
+        validate_patterns("vue-files", &config.rules.vue_files)
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let tag = HtmlTag::cast(node)?;
+        let name = tag.name()?;
+        if is_inside_foreign_element(&tag) {
+            return None; // svg / math elements can always be self-closing
+        }
+
+        if is_one_of(name.text(), HTML_VOID_ELEMENTS) {
+            return self.check_void_element(&tag, ctx);
+        }
+
+        let is_component = !is_one_of(name.text(), HTML_ELEMENTS)
+            && (name.text().contains('-') || name.text().chars().any(|c| c.is_ascii_uppercase()));
+        if !is_component || !ctx.config().format.self_closing_components {
+            return None;
+        }
+
+        if is_allowed_file(ctx.file_path(), &ctx.config().rules.vue_files) {
+            self.check_vue_component(&tag, &name)
+        } else {
+            self.check_custom_element(&tag, &name)
+        }
+    }
+}
+
+impl RuleHtmlSelfClosing {
+    fn check_void_element(&self, tag: &HtmlTag, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let style = &ctx.config().format.void_element_style;
+        let starting_tag = tag.starting_tag()?;
+        let end = closing_token(&starting_tag)?;
+
+        let (range, replacement) = match (style, end.kind()) {
+            (VoidElementStyle::NoSlash, T!["/>"]) => {
+                (whitespace_and_token_range(&end), ">".to_string())
+            }
+            (VoidElementStyle::Slash, T![">"]) => {
+                let replacement = if previous_is_trivia(&end) {
+                    "/>"
+                } else {
+                    " />"
+                };
+                (end.text_range(), replacement.to_string())
+            }
+            _ => return None,
+        };
+
+        let result = self
+            .create_result(Severity::Help, "Wrong closing of void element")
+            .primary_note(
+                end.text_range(),
+                format!("help: write this element {style}"),
+            )
+            .suggestion(range, replacement, "Try this instead");
+
+        Some(vec![result])
+    }
+
+    /// Vue components without content can be self-closing
+    fn check_vue_component(&self, tag: &HtmlTag, name: &SyntaxToken) -> Option<Vec<CheckResult>> {
+        let ending_tag = tag.ending_tag()?;
+        let body = tag.body()?;
+        if body
+            .syntax()
+            .children_with_tokens()
+            .any(|child| match child {
+                SyntaxElement::Token(t) => !t.kind().is_trivia(),
+                SyntaxElement::Node(_) => true,
+            })
+        {
+            return None;
+        }
+
+        let end = closing_token(&tag.starting_tag()?)?;
+        let replacement = if previous_is_trivia(&end) {
+            "/>"
+        } else {
+            " />"
+        };
+        let range = TextRange::new(
+            end.text_range().start(),
+            ending_tag.syntax().text_range().end(),
+        );
+
+        let result = self
+            .create_result(
+                Severity::Help,
+                "Component without content is not self-closing",
+            )
+            .primary_note(name.text_range(), "help: write this component self-closing")
+            .suggestion(range, replacement, "Try this instead");
+
+        Some(vec![result])
+    }
+
+    /// Self-closing custom elements are not closed by browsers, so they need an ending tag
+    fn check_custom_element(&self, tag: &HtmlTag, name: &SyntaxToken) -> Option<Vec<CheckResult>> {
+        let end = closing_token(&tag.starting_tag()?)?;
+        if end.kind() != T!["/>"] {
+            return None;
+        }
+
+        let result = self
+            .create_result(Severity::Warning, "Self-closing custom element")
+            .primary_note(
+                end.text_range(),
+                "browsers ignore this slash outside of vue templates, so the element is never closed",
+            )
+            .suggestion(
+                whitespace_and_token_range(&end),
+                format!("></{}>", name.text()),
+                "Try adding an ending tag",
+            );
+
+        Some(vec![result])
+    }
+}
+
+/// The `>` or `/>` token of the starting tag
+fn closing_token(starting_tag: &HtmlStartingTag) -> Option<SyntaxToken> {
+    starting_tag
+        .syntax()
+        .children_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .find(|t| matches!(t.kind(), T![">"] | T!["/>"]))
+}
+
+fn previous_is_trivia(token: &SyntaxToken) -> bool {
+    token
+        .prev_token()
+        .is_some_and(|previous| previous.kind().is_trivia())
+}
+
+/// Range of the token together with the whitespace in front of it on the same line
+fn whitespace_and_token_range(token: &SyntaxToken) -> TextRange {
+    match token.prev_token() {
+        Some(previous) if previous.kind() == T![ws] => {
+            TextRange::new(previous.text_range().start(), token.text_range().end())
+        }
+        _ => token.text_range(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_fix};
+    use crate::check::rules::RuleRegistry;
+    use crate::config::{Config, VoidElementStyle};
+    use crate::process::preview_fix;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "html-self-closing",
+            r#"<br/><input type="text" /><my-element /><svg><path d="a" /></svg>"#,
+            expect![[r#"
+                help[html-self-closing]: Wrong closing of void element
+                  ┌─ ./debug-rule.html.twig:1:4
+                  │
+                1 │ <br/><input type="text" /><my-element /><svg><path d="a" /></svg>
+                  │    ^^
+                  │    │
+                  │    help: write this element without a slash (<br>)
+                  │    Try this instead: >
+
+                help[html-self-closing]: Wrong closing of void element
+                  ┌─ ./debug-rule.html.twig:1:25
+                  │
+                1 │ <br/><input type="text" /><my-element /><svg><path d="a" /></svg>
+                  │                        -^^
+                  │                        ││
+                  │                        │help: write this element without a slash (<br>)
+                  │                        Try this instead: >
+
+                warning[html-self-closing]: Self-closing custom element
+                  ┌─ ./debug-rule.html.twig:1:39
+                  │
+                1 │ <br/><input type="text" /><my-element /><svg><path d="a" /></svg>
+                  │                                      -^^
+                  │                                      ││
+                  │                                      │browsers ignore this slash outside of vue templates, so the element is never closed
+                  │                                      Try adding an ending tag: ></my-element>
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes() {
+        test_rule_fix(
+            "html-self-closing",
+            r#"<br/><input type="text" /><hr><my-element :a="b" /><MyComponent></MyComponent>"#,
+            expect![[
+                r#"<br><input type="text"><hr><my-element :a="b"></my-element><MyComponent></MyComponent>"#
+            ]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes_slash_style_and_vue_components() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.format.void_element_style = VoidElementStyle::Slash;
+        config.rules.vue_files = vec!["*.html.twig".to_string()];
+
+        assert_eq!(
+            preview_fix(
+                Path::new("./debug-rule.html.twig"),
+                "<br><input type=\"text\"\n       disabled\n><img/><my-element></my-element><MyComponent :a=\"b\">\n</MyComponent><my-element>a</my-element>",
+                "html-self-closing",
+                &RuleRegistry::default(),
+                config
+            )
+            .unwrap(),
+            "<br /><input type=\"text\"\n       disabled\n/><img/><my-element /><MyComponent :a=\"b\" /><my-element>a</my-element>"
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use ludtwig_parser::syntax::typed::{AstNode, HtmlAttribute, HtmlTag};
+use ludtwig_parser::syntax::untyped::{SyntaxNode, SyntaxNodeExt};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::rules::twig_no_autoescape_off::{is_allowed_file, validate_patterns};
+use crate::Config;
+
+pub struct RuleHtmlSlotOutsideVue;
+
+impl Rule for RuleHtmlSlotOutsideVue {
+    fn name(&self) -> &'static str {
+        "html-slot-outside-vue"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Slots are only rendered by vue. Outside of vue templates (like in the storefront) the browser keeps <slot> as an unknown element and never shows the content of <template>.")
+    }
+
+    fn validate_config(&self, config: &Config) -> Result<(), String> {
+        validate_patterns("vue-files", &config.rules.vue_files)
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let tag = HtmlTag::cast(node)?;
+        let name = tag.name()?;
+
+        let message = if name.text().eq_ignore_ascii_case("slot") {
+            "<slot> outside of a vue template"
+        } else if name.text().eq_ignore_ascii_case("template")
+            && tag.attributes().any(|a| is_slot_directive(&a))
+        {
+            "<template v-slot> outside of a vue template"
+        } else {
+            return None;
+        };
+
+        if is_allowed_file(ctx.file_path(), &ctx.config().rules.vue_files) {
+            return None;
+        }
+
+        let result = self.create_result(Severity::Warning, message).primary_note(
+            tag.starting_tag()?.syntax().text_range_trimmed_trivia(),
+            "this is not rendered by vue, because the file doesn't match rules.vue-files",
+        );
+
+        Some(vec![result])
+    }
+}
+
+/// `v-slot:name` or the shorthand `#name`
+fn is_slot_directive(attribute: &HtmlAttribute) -> bool {
+    attribute
+        .vue_directive()
+        .and_then(|directive| directive.get_directive_name())
+        .as_deref()
+        == Some("v-slot")
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_with_config};
+    use crate::config::Config;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "html-slot-outside-vue",
+            r##"<slot name="a"></slot><template #default="{ item }">b</template><template v-slot:c>c</template><template id="d">d</template>"##,
+            expect![[r#"
+                warning[html-slot-outside-vue]: <slot> outside of a vue template
+                  ┌─ ./debug-rule.html.twig:1:1
+                  │
+                1 │ <slot name="a"></slot><template #default="{ item }">b</template><template v-slot:c>c</template><template id="d">d</template>
+                  │ ^^^^^^^^^^^^^^^ this is not rendered by vue, because the file doesn't match rules.vue-files
+
+                warning[html-slot-outside-vue]: <template v-slot> outside of a vue template
+                  ┌─ ./debug-rule.html.twig:1:23
+                  │
+                1 │ <slot name="a"></slot><template #default="{ item }">b</template><template v-slot:c>c</template><template id="d">d</template>
+                  │                       ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ this is not rendered by vue, because the file doesn't match rules.vue-files
+
+                warning[html-slot-outside-vue]: <template v-slot> outside of a vue template
+                  ┌─ ./debug-rule.html.twig:1:65
+                  │
+                1 │ <slot name="a"></slot><template #default="{ item }">b</template><template v-slot:c>c</template><template id="d">d</template>
+                  │                                                                 ^^^^^^^^^^^^^^^^^^^ this is not rendered by vue, because the file doesn't match rules.vue-files
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_vue_files() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.vue_files = vec!["debug-rule.html.twig".to_string()];
+
+        test_rule_with_config(
+            "html-slot-outside-vue",
+            r#"<slot name="a"></slot><template #default>b</template>"#,
+            config,
+            expect![[r#""#]],
+        );
+    }
+}
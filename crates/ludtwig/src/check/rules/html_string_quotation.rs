@@ -10,6 +10,10 @@ impl Rule for RuleHtmlStringQuotation {
         "html-string-quotation"
     }
 
+    fn explanation(&self) -> Option<&'static str> {
+        Some("HTML attribute values should consistently use the configured quotation.")
+    }
+
     fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
         let html_string = HtmlString::cast(node)?;
 
@@ -51,9 +55,15 @@ pub fn make_changed_quotes_suggestion_if_possible(
         return result;
     };
 
-    let inner_text = inner.syntax().text();
-    if inner_text.contains_char(correct_quote) {
-        return result; // TODO: could still try to transform the string with more effort...
+    // values containing the quote are left untouched, escaping it would change the value for twig / vue
+    if let Some(offset) = inner.syntax().text().find_char(correct_quote) {
+        return result.secondary_note(
+            TextRange::at(
+                inner.syntax().text_range().start() + offset,
+                TextSize::of(correct_quote),
+            ),
+            "the value contains this quote, so it can't be changed automatically",
+        );
     }
 
     // opening quote
@@ -116,7 +126,10 @@ mod tests {
                   ┌─ ./debug-rule.html.twig:1:12
                   │
                 1 │ <div style='value: "a"'></div>
-                  │            ^^^^^^^^^^^^ help: change the quotation to double quotes (")
+                  │            ^^^^^^^^^^^^
+                  │            │       │
+                  │            │       the value contains this quote, so it can't be changed automatically
+                  │            help: change the quotation to double quotes (")
 
             "#]],
         );
@@ -0,0 +1,175 @@
+use ludtwig_parser::syntax::typed::{AstNode, HtmlAttribute, HtmlAttributeValue, HtmlTag};
+use ludtwig_parser::syntax::untyped::{SyntaxNode, SyntaxNodeExt, TextRange, TextSize};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::rules::a11y_img_alt::{find_attribute, has_dynamic_attributes};
+
+/// Tags which open links with their `target` attribute
+static LINK_TAGS: &[&str] = &["a", "area"];
+
+pub struct RuleHtmlTargetBlankNoopener;
+
+impl Rule for RuleHtmlTargetBlankNoopener {
+    fn name(&self) -> &'static str {
+        "html-target-blank-noopener"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Pages opened with target=\"_blank\" can access the opening page through window.opener (tabnabbing) in older browsers, unless the link has rel=\"noopener\" or rel=\"noreferrer\".")
+    }
+
+    fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let tag = HtmlTag::cast(node)?;
+        let name = tag.name()?;
+        if !LINK_TAGS
+            .iter()
+            .any(|t| t.eq_ignore_ascii_case(name.text()))
+            || has_dynamic_attributes(&tag)
+        {
+            return None;
+        }
+
+        let target = find_attribute(&tag, "target")?;
+        if target.vue_directive().is_some()
+            || !target
+                .value()
+                .static_text()?
+                .trim()
+                .eq_ignore_ascii_case("_blank")
+        {
+            return None;
+        }
+
+        let result = if let Some(rel) = find_attribute(&tag, "rel") {
+            self.check_rel(&rel)?
+        } else {
+            self.missing_rel(&target)
+        };
+
+        Some(vec![result])
+    }
+}
+
+impl RuleHtmlTargetBlankNoopener {
+    fn missing_rel(&self, target: &HtmlAttribute) -> CheckResult {
+        let quote = target
+            .html_string()
+            .and_then(|s| s.get_opening_quote())
+            .map_or_else(|| "\"".to_string(), |q| q.text().to_string());
+
+        self.create_result(Severity::Warning, "target=\"_blank\" without rel")
+            .primary_note(
+                target.syntax().text_range_trimmed_trivia(),
+                "the opened page can access this page with window.opener",
+            )
+            .suggestion(
+                TextRange::empty(target.syntax().text_range().end()),
+                format!(" rel={quote}noopener noreferrer{quote}"),
+                "Try adding the rel attribute",
+            )
+    }
+
+    /// Result for an existing rel attribute, which doesn't contain `noopener` or `noreferrer`
+    fn check_rel(&self, rel: &HtmlAttribute) -> Option<CheckResult> {
+        if rel.vue_directive().is_some() {
+            return None;
+        }
+        let HtmlAttributeValue::Static(value) = rel.value() else {
+            return None;
+        };
+        let keywords: Vec<String> = value
+            .split_ascii_whitespace()
+            .map(str::to_ascii_lowercase)
+            .collect();
+        if keywords
+            .iter()
+            .any(|k| k == "noopener" || k == "noreferrer")
+        {
+            return None;
+        }
+
+        let html_string = rel.html_string()?;
+        // empty strings don't have an inner node
+        let start = html_string
+            .get_inner()
+            .map(|inner| inner.syntax().text_range().start())
+            .or_else(|| Some(html_string.get_opening_quote()?.text_range().end()))?;
+        let missing = if value.trim().is_empty() {
+            "noopener noreferrer"
+        } else {
+            " noopener noreferrer"
+        };
+        let end = start + TextSize::of(value.trim_end());
+
+        let result = self
+            .create_result(
+                Severity::Warning,
+                "target=\"_blank\" without rel=\"noopener\"",
+            )
+            .primary_note(
+                rel.syntax().text_range_trimmed_trivia(),
+                "the opened page can access this page with window.opener",
+            )
+            .suggestion(
+                TextRange::empty(end),
+                missing,
+                "Try adding noopener and noreferrer",
+            );
+
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_fix};
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "html-target-blank-noopener",
+            r#"<a href="/a" target="_blank">A</a><a href="/b" target="_blank" rel="nofollow">B</a>"#,
+            expect![[r#"
+                warning[html-target-blank-noopener]: target="_blank" without rel
+                  ┌─ ./debug-rule.html.twig:1:14
+                  │
+                1 │ <a href="/a" target="_blank">A</a><a href="/b" target="_blank" rel="nofollow">B</a>
+                  │              ^^^^^^^^^^^^^^^- Try adding the rel attribute:  rel="noopener noreferrer"
+                  │              │               
+                  │              the opened page can access this page with window.opener
+
+                warning[html-target-blank-noopener]: target="_blank" without rel="noopener"
+                  ┌─ ./debug-rule.html.twig:1:64
+                  │
+                1 │ <a href="/a" target="_blank">A</a><a href="/b" target="_blank" rel="nofollow">B</a>
+                  │                                                                ^^^^^^^^^^^^^^
+                  │                                                                │            │
+                  │                                                                │            Try adding noopener and noreferrer:  noopener noreferrer
+                  │                                                                the opened page can access this page with window.opener
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "html-target-blank-noopener",
+            r#"<a target="_blank" rel="noopener">A</a><a target="_blank" rel="NoReferrer">B</a><a target="_self">C</a><a target="_blank" rel="{{ rel }}">D</a><a target="_blank" {{ attributes }}>E</a>"#,
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes() {
+        test_rule_fix(
+            "html-target-blank-noopener",
+            r#"<a href="/a" target='_blank'>A</a><a target="_blank" rel="nofollow ">B</a><a target="_blank" rel="">C</a>"#,
+            expect![[
+                r#"<a href="/a" target='_blank' rel='noopener noreferrer'>A</a><a target="_blank" rel="nofollow noopener noreferrer ">B</a><a target="_blank" rel="noopener noreferrer">C</a>"#
+            ]],
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use ludtwig_parser::syntax::typed::{AstNode, HtmlTag};
+use ludtwig_parser::syntax::untyped::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxNodeExt};
+use ludtwig_parser::T;
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleHtmlUntranslatedText;
+
+impl Rule for RuleHtmlUntranslatedText {
+    fn name(&self) -> &'static str {
+        "html-untranslated-text"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Hardcoded text is shown in the same language for every user. Use a translation like `{{ 'general.title'|trans }}` or `{% trans %}` instead.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        if node.kind() != SyntaxKind::HTML_TEXT || !is_visible(&node) {
+            return None;
+        }
+
+        // html escapes like `&amp;` are not counted as letters
+        let letters: usize = node
+            .children_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .filter(|t| t.kind() != T![html escape character])
+            .map(|t| t.text().chars().filter(|c| c.is_alphabetic()).count())
+            .sum();
+        if letters < ctx.config().rules.untranslated_text_min_length {
+            return None;
+        }
+
+        let text = node.text().to_string();
+        if ctx
+            .config()
+            .rules
+            .untranslated_text_ignore
+            .iter()
+            .any(|ignored| ignored == text.trim())
+        {
+            return None;
+        }
+
+        let result = self
+            .create_result(Severity::Warning, "Untranslated text")
+            .primary_note(
+                node.text_range_trimmed_trivia(),
+                "this text is not translated, use the trans filter or tag",
+            );
+
+        Some(vec![result])
+    }
+}
+
+/// Is the text shown to the user, which is not the case for scripts, styles and already translated text
+fn is_visible(node: &SyntaxNode) -> bool {
+    !node.ancestors().any(|a| match a.kind() {
+        SyntaxKind::TWIG_TRANS => true,
+        SyntaxKind::HTML_TAG => HtmlTag::cast(a)
+            .and_then(|tag| tag.name())
+            .is_some_and(|name| {
+                name.text().eq_ignore_ascii_case("script")
+                    || name.text().eq_ignore_ascii_case("style")
+            }),
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_with_config};
+    use crate::config::Config;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "html-untranslated-text",
+            r#"<div>
+    <p class="a">Hello world {{ name }} &amp; more</p>
+    <button>{{ 'general.save'|trans }} Now</button>
+</div>"#,
+            expect![[r#"
+                warning[html-untranslated-text]: Untranslated text
+                  ┌─ ./debug-rule.html.twig:2:18
+                  │
+                2 │     <p class="a">Hello world {{ name }} &amp; more</p>
+                  │                  ^^^^^^^^^^^ this text is not translated, use the trans filter or tag
+
+                warning[html-untranslated-text]: Untranslated text
+                  ┌─ ./debug-rule.html.twig:2:41
+                  │
+                2 │     <p class="a">Hello world {{ name }} &amp; more</p>
+                  │                                         ^^^^^^^^^^ this text is not translated, use the trans filter or tag
+
+                warning[html-untranslated-text]: Untranslated text
+                  ┌─ ./debug-rule.html.twig:3:40
+                  │
+                3 │     <button>{{ 'general.save'|trans }} Now</button>
+                  │                                        ^^^ this text is not translated, use the trans filter or tag
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.untranslated_text_ignore = vec!["Shopware".to_string()];
+
+        test_rule_with_config(
+            "html-untranslated-text",
+            r#"<div>
+    {% trans %}Hello world{% endtrans %}
+    <span>{{ 'general.title'|trans }} | 42 &nbsp; x</span>
+    <script>var text = "Hello world";</script>
+    <p>Shopware</p>
+    {# a comment #}<!-- another comment -->
+</div>"#,
+            config,
+            expect![[r#""#]],
+        );
+    }
+}
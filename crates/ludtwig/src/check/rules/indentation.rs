@@ -1,9 +1,10 @@
-use ludtwig_parser::syntax::typed::{AstNode, HtmlStartingTag, HtmlTag, LudtwigDirectiveIgnore};
+use ludtwig_parser::syntax::typed::{AstNode, HtmlStartingTag, LudtwigDirectiveIgnore};
 use ludtwig_parser::syntax::untyped::{
     PreorderWithTokens, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, TextRange, TextSize,
     WalkEvent,
 };
 
+use crate::check::layout::{attribute_alignment, indentation_string, is_trivia_sensitive};
 use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
 
 pub struct RuleIndentation;
@@ -13,6 +14,10 @@ impl Rule for RuleIndentation {
         "indentation"
     }
 
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Children of HTML elements and twig tags are indented one level deeper than their parent for readability.")
+    }
+
     fn check_root(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
         // keep track of some state during tree traversal
         let mut line_break_encountered = true;
@@ -126,12 +131,12 @@ impl RuleIndentation {
         indentation_substeps: usize,
         ctx: &RuleRunContext,
     ) -> Vec<CheckResult> {
-        let indent_char = ctx.config().format.indentation_mode.corresponding_char();
         let indent_char_count = ctx.config().format.indentation_count;
-        let expected_str = std::iter::repeat(indent_char)
-            .take(indentation_level * indent_char_count as usize)
-            .chain(" ".repeat(indentation_substeps).chars())
-            .collect::<String>();
+        let expected_str = indentation_string(
+            &ctx.config().format,
+            indentation_level,
+            indentation_substeps,
+        );
 
         let substeps_expectation_notice = if indentation_substeps > 0 {
             format!(" (+{indentation_substeps} spaces)")
@@ -247,15 +252,13 @@ impl RuleIndentation {
         n: &SyntaxNode,
         walk_mode: WalkMode,
     ) {
-        if let Some(t) = HtmlTag::cast(n.clone()) {
-            if let Some("pre" | "textarea") = t.name().as_ref().map(SyntaxToken::text) {
-                match walk_mode {
-                    WalkMode::Enter => {
-                        *inside_trivia_sensitive_node = true;
-                    }
-                    WalkMode::Leave => {
-                        *inside_trivia_sensitive_node = false;
-                    }
+        if is_trivia_sensitive(n) {
+            match walk_mode {
+                WalkMode::Enter => {
+                    *inside_trivia_sensitive_node = true;
+                }
+                WalkMode::Leave => {
+                    *inside_trivia_sensitive_node = false;
                 }
             }
         }
@@ -297,7 +300,7 @@ impl RuleIndentation {
         if n.kind() == SyntaxKind::HTML_ATTRIBUTE_LIST {
             if let Some(t) = n.parent().and_then(HtmlStartingTag::cast) {
                 if let Some(name) = t.name() {
-                    let adjustment = 1 + name.text().chars().count() + 1;
+                    let adjustment = attribute_alignment(name.text());
 
                     match walk_mode {
                         WalkMode::Enter => {
@@ -329,7 +332,7 @@ fn get_spaces_and_tabs_count(input: &str) -> (i32, i32) {
 mod tests {
     use expect_test::expect;
 
-    use crate::check::rules::test::{test_rule, test_rule_fix};
+    use crate::check::rules::test::{test_rule, test_rule_does_not_fix, test_rule_fix};
 
     #[test]
     fn rule_reports() {
@@ -430,6 +433,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rule_does_not_report_verbatim_and_uppercase_tags() {
+        test_rule(
+            "indentation",
+            r#"<div>
+    {% verbatim %}
+  <p>
+ {{ a }}</p>
+        {% endverbatim %}
+    <PRE>
+  hello
+      </PRE>
+</div>"#,
+            expect![[r#""#]],
+        );
+    }
+
     #[test]
     fn rule_fixes() {
         test_rule_fix(
@@ -468,6 +488,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rule_does_not_fix_block_element_inside_paragraph() {
+        test_rule_does_not_fix(
+            "indentation",
+            "<div>\n    <p>\n        <div>x</div>\n    </p>\n</div>",
+            expect![[r#"
+                <div>
+                    <p>
+                        <div>x</div>
+                    </p>
+                </div>"#]],
+        );
+    }
+
     #[test]
     fn rule_ignores() {
         test_rule(
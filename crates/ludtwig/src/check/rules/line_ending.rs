@@ -1,10 +1,10 @@
-use once_cell::sync::OnceCell;
-use regex::Regex;
-
-use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxToken, TextRange, TextSize};
+use ludtwig_parser::syntax::trivia::line_endings;
+use ludtwig_parser::syntax::untyped::{
+    SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, TextRange, TextSize,
+};
 
+use crate::check::is_ignored_by_directive;
 use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
-use crate::config::LineEnding;
 
 pub struct RuleLineEnding;
 
@@ -13,9 +13,11 @@ impl Rule for RuleLineEnding {
         "line-ending"
     }
 
-    fn check_token(&self, token: SyntaxToken, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
-        static INVALID_REGEX: OnceCell<Regex> = OnceCell::new();
+    fn explanation(&self) -> Option<&'static str> {
+        Some("All line breaks should use the configured line ending to avoid noisy diffs.")
+    }
 
+    fn check_token(&self, token: SyntaxToken, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
         if token.kind() != SyntaxKind::TK_LINE_BREAK {
             return None;
         }
@@ -23,25 +25,16 @@ impl Rule for RuleLineEnding {
         let correct_line_ending = ctx.config().format.line_ending.corresponding_string();
         let message = format!("use {} instead", ctx.config().format.line_ending);
 
-        // compile regex only once and store it in a static
-        // because this function is called in a hot loop this does improve it's performance significantly
-        let invalid_regex = INVALID_REGEX.get_or_init(|| {
-            Regex::new(&format!(
-                r#"({})"#,
-                match ctx.config().format.line_ending {
-                    LineEnding::UnixLF => "\r\n", // inverse: look for windows line endings
-                    LineEnding::WindowsCRLF => "[^\r]?\n", // inverse: look for unix line endings
-                }
-            ))
-            .unwrap()
-        });
-
         let mut results = vec![];
-        for invalid in invalid_regex.find_iter(token.text()) {
+        for (offset, line_ending) in line_endings(token.text()) {
+            if line_ending == correct_line_ending {
+                continue;
+            }
+
             #[allow(clippy::cast_possible_truncation)]
-            let range = TextRange::new(
-                token.text_range().start() + TextSize::from(invalid.start() as u32),
-                token.text_range().start() + TextSize::from(invalid.end() as u32),
+            let range = TextRange::at(
+                token.text_range().start() + TextSize::from(offset as u32),
+                TextSize::of(line_ending),
             );
             let result = self
                 .create_result(Severity::Warning, "invalid line ending")
@@ -60,13 +53,73 @@ impl Rule for RuleLineEnding {
             Some(results)
         }
     }
+
+    /// Report files which mix both line endings once (every wrong line ending is reported and fixed
+    /// by `check_token`)
+    fn check_root(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let ignored_ranges: Vec<TextRange> = node
+            .descendants()
+            .filter(|n| is_ignored_by_directive(n, self.name()))
+            .map(|n| n.text_range())
+            .collect();
+
+        let correct_line_ending = ctx.config().format.line_ending.corresponding_string();
+        let mut correct_count = 0;
+        let mut wrong = vec![];
+        for token in node
+            .descendants_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .filter(|t| t.kind() == SyntaxKind::TK_LINE_BREAK)
+            .filter(|t| {
+                !ignored_ranges
+                    .iter()
+                    .any(|r| r.contains_range(t.text_range()))
+            })
+        {
+            for (offset, line_ending) in line_endings(token.text()) {
+                if line_ending == correct_line_ending {
+                    correct_count += 1;
+                } else {
+                    #[allow(clippy::cast_possible_truncation)]
+                    wrong.push(TextRange::at(
+                        token.text_range().start() + TextSize::from(offset as u32),
+                        TextSize::of(line_ending),
+                    ));
+                }
+            }
+        }
+
+        let first_wrong = *wrong.first()?;
+        if correct_count == 0 {
+            return None; // consistent, but every line ending is reported anyway
+        }
+
+        let result = self
+            .create_result(Severity::Warning, "mixed line endings")
+            .primary_note(
+                first_wrong,
+                format!(
+                    "{} of {} line endings in this file don't use {}, starting here",
+                    wrong.len(),
+                    wrong.len() + correct_count,
+                    ctx.config().format.line_ending
+                ),
+            );
+
+        Some(vec![result])
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
+
     use expect_test::expect;
 
     use crate::check::rules::test::{test_rule, test_rule_fix};
+    use crate::check::rules::RuleRegistry;
+    use crate::config::{Config, LineEnding};
+    use crate::process::preview_fix;
 
     #[test]
     fn rule_line_ending_trivial() {
@@ -188,6 +241,13 @@ mod tests {
             "line-ending",
             "hello\n\r\nworld",
             expect![[r#"
+                warning[line-ending]: mixed line endings
+                  ┌─ ./debug-rule.html.twig:2:1
+                  │  
+                2 │ ╭ 
+                3 │ │ world
+                  │ ╰^ 1 of 2 line endings in this file don't use UnixLF (\n), starting here
+
                 warning[line-ending]: invalid line ending
                   ┌─ ./debug-rule.html.twig:2:1
                   │    
@@ -208,4 +268,22 @@ mod tests {
                 world"#]],
         );
     }
+
+    #[test]
+    fn rule_line_ending_windows() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.format.line_ending = LineEnding::WindowsCRLF;
+
+        assert_eq!(
+            preview_fix(
+                Path::new("./debug-rule.html.twig"),
+                "\u{FEFF}hello\n\n\r\nworld\n",
+                "line-ending",
+                &RuleRegistry::default(),
+                config
+            )
+            .unwrap(),
+            "\u{FEFF}hello\r\n\r\n\r\nworld\r\n"
+        );
+    }
 }
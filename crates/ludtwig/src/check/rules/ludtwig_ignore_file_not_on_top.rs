@@ -10,6 +10,10 @@ impl Rule for RuleLudtwigIgnoreFileNotOnTop {
         "ludtwig-ignore-file-not-on-top"
     }
 
+    fn explanation(&self) -> Option<&'static str> {
+        Some("The ludtwig-ignore-file directive only has an effect on the top level of a file.")
+    }
+
     fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
         let directive = LudtwigDirectiveFileIgnore::cast(node)?;
         let parent = directive.syntax().parent()?;
@@ -0,0 +1,239 @@
+use ludtwig_parser::syntax::trivia::{first_non_trivia_token, indentation_of, leading_trivia};
+use ludtwig_parser::syntax::typed::{AstNode, HtmlStartingTag, LudtwigDirectiveIgnore};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode, TextRange, TextSize};
+
+use crate::check::is_ignored_by_directive;
+use crate::check::layout::{attribute_alignment, is_trivia_sensitive};
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleMaxLineLength;
+
+impl Rule for RuleMaxLineLength {
+    fn name(&self) -> &'static str {
+        "max-line-length"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Long lines are hard to read and review side by side. Html tags with many attributes can be wrapped with one attribute per line.")
+    }
+
+    fn check_root(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let max_line_length = ctx.config().format.max_line_length;
+        let skipped_ranges: Vec<TextRange> = node
+            .descendants()
+            .filter(is_trivia_sensitive)
+            .map(|n| n.text_range())
+            .collect();
+        // only checked for too long lines, so unused directives are noticed
+        let directive_targets: Vec<SyntaxNode> = node
+            .descendants()
+            .filter(|n| {
+                n.prev_sibling()
+                    .is_some_and(|s| LudtwigDirectiveIgnore::can_cast(s.kind()))
+            })
+            .collect();
+
+        let text = node.text().to_string();
+        let mut results = vec![];
+        let mut line_start = 0;
+        for line in text.split_inclusive('\n') {
+            let content = line.trim_end_matches(['\n', '\r']);
+            #[allow(clippy::cast_possible_truncation)]
+            let line_range =
+                TextRange::at(TextSize::from(line_start as u32), TextSize::of(content));
+            line_start += line.len();
+
+            let length = content.chars().count();
+            let overlaps =
+                |range: TextRange| range.intersect(line_range).is_some_and(|r| !r.is_empty());
+            if length <= max_line_length
+                || skipped_ranges.iter().any(|range| overlaps(*range))
+                || directive_targets
+                    .iter()
+                    .any(|n| overlaps(n.text_range()) && is_ignored_by_directive(n, self.name()))
+            {
+                continue;
+            }
+
+            // the part of the line after the maximum length
+            let (exceeding_offset, _) = content.char_indices().nth(max_line_length)?;
+            #[allow(clippy::cast_possible_truncation)]
+            let exceeding_start = line_range.start() + TextSize::from(exceeding_offset as u32);
+            let exceeding_range = TextRange::new(exceeding_start, line_range.end());
+
+            let mut result = self
+                .create_result(Severity::Help, "Line too long")
+                .primary_note(
+                    exceeding_range,
+                    format!("line is {length} characters long, the maximum is {max_line_length}"),
+                );
+
+            if let Some(tag) = wrappable_starting_tag(&node, line_range, exceeding_start) {
+                result = wrap_attributes(result, &tag, ctx);
+            }
+
+            results.push(result);
+        }
+
+        if results.is_empty() {
+            None
+        } else {
+            Some(results)
+        }
+    }
+}
+
+/// Add suggestions to place every attribute on its own line,
+/// aligned after the tag name like the `indentation` rule expects it
+fn wrap_attributes(
+    result: CheckResult,
+    tag: &HtmlStartingTag,
+    ctx: &RuleRunContext,
+) -> CheckResult {
+    let (Some(name), Some(first_token)) = (tag.name(), first_non_trivia_token(tag.syntax())) else {
+        return result;
+    };
+    let Some(attributes) = tag
+        .syntax()
+        .children()
+        .find(|n| n.kind() == SyntaxKind::HTML_ATTRIBUTE_LIST)
+    else {
+        return result;
+    };
+
+    let indentation = indentation_of(&first_token).map_or_else(String::new, |t| t.to_string());
+    let alignment = " ".repeat(attribute_alignment(name.text()));
+    let line_break = ctx.config().format.line_ending.corresponding_string();
+    let replacement = format!("{line_break}{indentation}{alignment}");
+
+    attributes
+        .children()
+        .skip(1)
+        .fold(result, |result, attribute| {
+            let trivia = leading_trivia(&attribute);
+            let range = match (trivia.first(), trivia.last()) {
+                (Some(first), Some(last)) => {
+                    TextRange::new(first.text_range().start(), last.text_range().end())
+                }
+                _ => TextRange::empty(attribute.text_range().start()),
+            };
+
+            result.suggestion(range, replacement.clone(), "Try placing it on a new line")
+        })
+}
+
+/// Html starting tag on the line, which crosses the maximum length and has multiple attributes
+/// (or twig syntax between them), which can be wrapped.
+fn wrappable_starting_tag(
+    root: &SyntaxNode,
+    line_range: TextRange,
+    exceeding_start: TextSize,
+) -> Option<HtmlStartingTag> {
+    root.descendants()
+        .filter_map(HtmlStartingTag::cast)
+        .filter(|tag| {
+            let range = tag.syntax().text_range();
+            range.end() <= line_range.end() && range.end() > exceeding_start
+        })
+        .find(|tag| {
+            let Some(first_token) = first_non_trivia_token(tag.syntax()) else {
+                return false;
+            };
+            let attributes = tag
+                .syntax()
+                .children()
+                .find(|n| n.kind() == SyntaxKind::HTML_ATTRIBUTE_LIST);
+
+            line_range.contains(first_token.text_range().start())
+                && attributes.is_some_and(|list| list.children().count() > 1)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule_fix, test_rule_with_config};
+    use crate::config::Config;
+
+    fn config(max_line_length: usize) -> Config {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.format.max_line_length = max_line_length;
+        config
+    }
+
+    #[test]
+    fn rule_reports() {
+        test_rule_with_config(
+            "max-line-length",
+            r#"<div>
+    <a href="/home">{{ 'home.link.title'|trans }}</a>
+    <p>{{ 'some.translation.key'|trans }}</p>
+</div>"#,
+            config(40),
+            expect![[r#"
+                help[max-line-length]: Line too long
+                  ┌─ ./debug-rule.html.twig:2:41
+                  │
+                2 │     <a href="/home">{{ 'home.link.title'|trans }}</a>
+                  │                                         ^^^^^^^^^^^^^ line is 53 characters long, the maximum is 40
+
+                help[max-line-length]: Line too long
+                  ┌─ ./debug-rule.html.twig:3:41
+                  │
+                3 │     <p>{{ 'some.translation.key'|trans }}</p>
+                  │                                         ^^^^^ line is 45 characters long, the maximum is 40
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_whitespace_sensitive() {
+        test_rule_with_config(
+            "max-line-length",
+            r#"<pre>a very long line inside of a pre tag is fine</pre>
+<TEXTAREA>
+    a very long line inside of a textarea is fine
+</TEXTAREA>
+{% verbatim %}
+    a very long line inside of verbatim is fine
+{% endverbatim %}"#,
+            config(20),
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_ignored() {
+        test_rule_with_config(
+            "max-line-length",
+            r#"<div>
+    {# ludtwig-ignore max-line-length #}
+    <p>a very long line, which is ignored</p>
+</div>"#,
+            config(40),
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes() {
+        test_rule_fix(
+            "max-line-length",
+            &format!(
+                r#"<div>
+    <a class="link" {{% if active %}}aria-current="page"{{% endif %}} href="/home" title="{}">Home</a>
+</div>"#,
+                "home ".repeat(20)
+            ),
+            expect![[r#"
+                <div>
+                    <a class="link"
+                       {% if active %}aria-current="page"{% endif %}
+                       href="/home"
+                       title="home home home home home home home home home home home home home home home home home home home home ">Home</a>
+                </div>"#]],
+        );
+    }
+}
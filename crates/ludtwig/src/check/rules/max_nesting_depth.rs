@@ -0,0 +1,118 @@
+use ludtwig_parser::syntax::typed::{AstNode, HtmlTag};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode, SyntaxNodeExt};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleMaxNestingDepth;
+
+impl Rule for RuleMaxNestingDepth {
+    fn name(&self) -> &'static str {
+        "max-nesting-depth"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Deeply nested html elements and twig tags are hard to follow and override. Move parts into their own blocks or templates instead.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        if !is_nesting(&node) {
+            return None;
+        }
+
+        // only the element which crosses the limit is reported, not every element inside of it
+        let max_depth = ctx.config().rules.max_nesting_depth;
+        let depth = nesting_depth(&node);
+        if depth != max_depth + 1 {
+            return None;
+        }
+
+        // the starting tag like `<div class="a">` or `{% block a %}`
+        let starting_tag = node.first_child()?;
+        let result = self
+            .create_result(Severity::Warning, "Nesting too deep")
+            .primary_note(
+                starting_tag.text_range_trimmed_trivia(),
+                format!("this is nested {depth} levels deep, the maximum is {max_depth}"),
+            );
+
+        Some(vec![result])
+    }
+}
+
+/// Html elements and twig tags with a body (like `{% block %}` or `{% if %}`) count as one level
+fn is_nesting(node: &SyntaxNode) -> bool {
+    HtmlTag::can_cast(node.kind())
+        || (node.kind() != SyntaxKind::BODY
+            && node.children().any(|c| c.kind() == SyntaxKind::BODY))
+}
+
+/// Nesting depth of the element, where elements at the top level of the template have a depth of 1
+fn nesting_depth(node: &SyntaxNode) -> usize {
+    1 + node
+        .ancestors()
+        .filter(|n| n.kind() == SyntaxKind::BODY)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::test_rule_with_config;
+    use crate::config::Config;
+
+    fn config(max_nesting_depth: usize) -> Config {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.max_nesting_depth = max_nesting_depth;
+        config
+    }
+
+    #[test]
+    fn rule_reports() {
+        test_rule_with_config(
+            "max-nesting-depth",
+            r#"{% block a %}
+    <div>
+        {% if b %}
+            <p class="c"><span>d</span></p>
+        {% else %}
+            <br>
+        {% endif %}
+    </div>
+{% endblock %}"#,
+            config(3),
+            expect![[r#"
+                warning[max-nesting-depth]: Nesting too deep
+                  ┌─ ./debug-rule.html.twig:4:13
+                  │
+                4 │             <p class="c"><span>d</span></p>
+                  │             ^^^^^^^^^^^^^ this is nested 4 levels deep, the maximum is 3
+
+                warning[max-nesting-depth]: Nesting too deep
+                  ┌─ ./debug-rule.html.twig:6:13
+                  │
+                6 │             <br>
+                  │             ^^^^ this is nested 4 levels deep, the maximum is 3
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule_with_config(
+            "max-nesting-depth",
+            r#"{% block a %}
+    <div>
+        {% if b %}
+            <p>c</p>
+        {% elseif d %}
+            e
+        {% endif %}
+    </div>
+{% endblock %}"#,
+            config(4),
+            expect![[r#""#]],
+        );
+    }
+}
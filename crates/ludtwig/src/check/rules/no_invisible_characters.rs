@@ -0,0 +1,203 @@
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxToken, TextRange, TextSize};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+const BYTE_ORDER_MARK: char = '\u{FEFF}';
+const NO_BREAK_SPACE: char = '\u{00A0}';
+
+pub struct RuleNoInvisibleCharacters;
+
+impl Rule for RuleNoInvisibleCharacters {
+    fn name(&self) -> &'static str {
+        "no-invisible-characters"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Invisible characters change the output or the meaning of the code without being visible in most editors.")
+    }
+
+    fn check_token(&self, token: SyntaxToken, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        // the lexer only knows ascii characters (and the byte order mark),
+        // so all of these end up as unknown tokens
+        if !matches!(
+            token.kind(),
+            SyntaxKind::TK_UNKNOWN | SyntaxKind::TK_BYTE_ORDER_MARK
+        ) {
+            return None;
+        }
+
+        let context = TokenContext::of(&token);
+        let mut results = vec![];
+        for (offset, c) in token.text().char_indices() {
+            #[allow(clippy::cast_possible_truncation)]
+            let range = TextRange::at(
+                token.text_range().start() + TextSize::from(offset as u32),
+                TextSize::of(c),
+            );
+
+            let result = if c == BYTE_ORDER_MARK && range.start() == TextSize::from(0) {
+                self.create_result(
+                    Severity::Warning,
+                    "byte order mark at the start of the file",
+                )
+                .primary_note(range, "this invisible character is part of the output")
+                .suggestion(range, "", "remove the byte order mark")
+            } else if c == NO_BREAK_SPACE {
+                if context != TokenContext::Code {
+                    continue; // non-breaking spaces are fine in text content
+                }
+
+                self.create_result(Severity::Warning, "non-breaking space in code")
+                    .primary_note(range, "this looks like a normal space, but isn't one")
+                    .suggestion(range, " ", "replace it with a normal space")
+            } else if let Some(name) = invisible_character_name(c) {
+                let result = self
+                    .create_result(Severity::Warning, format!("invisible {name} character"))
+                    .primary_note(range, format!("{name} (U+{:04X}) found here", c as u32));
+
+                if context == TokenContext::Html {
+                    // keep the output the same, but make the character visible in the template
+                    result.suggestion(
+                        range,
+                        format!("&#x{:X};", c as u32),
+                        "replace it with an html entity",
+                    )
+                } else {
+                    result.suggestion(range, "", "remove it")
+                }
+            } else {
+                continue;
+            };
+
+            results.push(result);
+        }
+
+        Some(results)
+    }
+}
+
+/// Name of zero width and bidirectional control characters
+/// (the latter can change the displayed order of source code, see "trojan source").
+fn invisible_character_name(c: char) -> Option<&'static str> {
+    let name = match c {
+        '\u{200B}' => "zero width space",
+        '\u{2060}' => "word joiner",
+        BYTE_ORDER_MARK => "zero width no-break space",
+        '\u{202A}' => "left-to-right embedding",
+        '\u{202B}' => "right-to-left embedding",
+        '\u{202C}' => "pop directional formatting",
+        '\u{202D}' => "left-to-right override",
+        '\u{202E}' => "right-to-left override",
+        '\u{2066}' => "left-to-right isolate",
+        '\u{2067}' => "right-to-left isolate",
+        '\u{2068}' => "first strong isolate",
+        '\u{2069}' => "pop directional isolate",
+        _ => return None,
+    };
+
+    Some(name)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum TokenContext {
+    /// Html text content or attribute values, where html entities are decoded
+    Html,
+    /// Comments, twig strings or CDATA sections
+    Text,
+    /// Everything else, like twig syntax or html tags
+    Code,
+}
+
+impl TokenContext {
+    fn of(token: &SyntaxToken) -> Self {
+        match token.parent().map(|p| p.kind()) {
+            Some(
+                SyntaxKind::ROOT
+                | SyntaxKind::BODY
+                | SyntaxKind::HTML_TEXT
+                | SyntaxKind::HTML_STRING_INNER,
+            ) => TokenContext::Html,
+            Some(
+                SyntaxKind::HTML_COMMENT
+                | SyntaxKind::HTML_CDATA
+                | SyntaxKind::TWIG_LITERAL_STRING_INNER
+                | SyntaxKind::TWIG_COMMENT,
+            ) => TokenContext::Text,
+            _ => TokenContext::Code,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::invisible_characters)] // the snapshots contain the reported characters
+mod tests {
+    use crate::check::rules::test::{test_rule, test_rule_fix};
+    use expect_test::expect;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "no-invisible-characters",
+            "\u{FEFF}<div>a\u{200B}b</div>\n{{ a\u{00A0}~ b }}\n{# \u{2060}comment #}",
+            expect![[r#"
+                warning[no-invisible-characters]: byte order mark at the start of the file
+                  ┌─ ./debug-rule.html.twig:1:1
+                  │
+                1 │ ﻿<div>a​b</div>
+                  │ 
+                  │ │
+                  │ this invisible character is part of the output
+                  │ remove the byte order mark: 
+
+                warning[no-invisible-characters]: invisible zero width space character
+                  ┌─ ./debug-rule.html.twig:1:8
+                  │
+                1 │ ﻿<div>a​b</div>
+                  │       
+                  │       │
+                  │       zero width space (U+200B) found here
+                  │       replace it with an html entity: &#x200B;
+
+                warning[no-invisible-characters]: non-breaking space in code
+                  ┌─ ./debug-rule.html.twig:2:5
+                  │
+                2 │ {{ a ~ b }}
+                  │     ^
+                  │     │
+                  │     this looks like a normal space, but isn't one
+                  │     replace it with a normal space:  
+
+                warning[no-invisible-characters]: invisible word joiner character
+                  ┌─ ./debug-rule.html.twig:3:4
+                  │
+                3 │ {# ⁠comment #}
+                  │    
+                  │    │
+                  │    word joiner (U+2060) found here
+                  │    remove it: 
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes() {
+        test_rule_fix(
+            "no-invisible-characters",
+            "\u{FEFF}<div>a\u{200B}b</div>\n{{ a\u{00A0}~ b }}\n{% if a\u{2066} %}{# \u{202E}c #}{% endif %}",
+            expect![[r#"
+                <div>a&#x200B;b</div>
+                {{ a ~ b }}
+                {% if a %}{# c #}{% endif %}"#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "no-invisible-characters",
+            "<div>100\u{00A0}€</div>\n<p title=\"a\u{00A0}b\">&#x200B;</p>",
+            expect![[r#""#]],
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxToken};
+
+use crate::check::layout::is_trivia_sensitive;
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleNoTrailingWhitespace;
+
+impl Rule for RuleNoTrailingWhitespace {
+    fn name(&self) -> &'static str {
+        "no-trailing-whitespace"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Whitespace at the end of a line is invisible in the editor, but shows up in diffs. Lines which contain only whitespace are reported by whitespace-between-line-breaks.")
+    }
+
+    fn check_token(&self, token: SyntaxToken, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        if token.kind() != SyntaxKind::TK_WHITESPACE {
+            return None;
+        }
+
+        // `next_token` / `prev_token` stop at empty nodes (like an empty body), so the root is searched instead
+        let root = token.parent_ancestors().last()?;
+        let range = token.text_range();
+
+        // only whitespace at the end of a line (or the end of the file)
+        if root
+            .token_at_offset(range.end())
+            .right_biased()
+            .is_some_and(|next| next.kind() != SyntaxKind::TK_LINE_BREAK)
+        {
+            return None;
+        }
+
+        // lines with only whitespace are left to the whitespace-between-line-breaks rule
+        if range.start() == 0.into()
+            || root
+                .token_at_offset(range.start())
+                .left_biased()
+                .is_none_or(|prev| prev.kind() == SyntaxKind::TK_LINE_BREAK)
+        {
+            return None;
+        }
+
+        // the whitespace is part of the content here
+        if token.parent_ancestors().any(|n| {
+            is_trivia_sensitive(&n)
+                || matches!(
+                    n.kind(),
+                    SyntaxKind::HTML_STRING | SyntaxKind::TWIG_LITERAL_STRING
+                )
+        }) {
+            return None;
+        }
+
+        let result = self
+            .create_result(Severity::Help, "Trailing whitespace")
+            .primary_note(range, "Unexpected whitespace at the end of the line")
+            .suggestion(range, "", "Remove whitespace");
+
+        Some(vec![result])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_fix};
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "no-trailing-whitespace",
+            "{% block my_block %}  \n    <hr>\t\n{% endblock %} ",
+            expect![[r#"
+                help[no-trailing-whitespace]: Trailing whitespace
+                  ┌─ ./debug-rule.html.twig:1:21
+                  │
+                1 │ {% block my_block %}  
+                  │                     ^^
+                  │                     │
+                  │                     Unexpected whitespace at the end of the line
+                  │                     Remove whitespace: 
+
+                help[no-trailing-whitespace]: Trailing whitespace
+                  ┌─ ./debug-rule.html.twig:2:9
+                  │
+                2 │     <hr>    
+                  │         ^^^^
+                  │         │   
+                  │         Unexpected whitespace at the end of the line
+                  │         Remove whitespace: 
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_content() {
+        test_rule(
+            "no-trailing-whitespace",
+            "<pre>a  \nb</pre>\n<textarea>a \n</textarea>\n{% verbatim %}a \n{% endverbatim %}\n{% trans %}a \nb{% endtrans %}\n<p title=\"a \nb\">{{ 'a \nb' }}</p>\n    \n",
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes() {
+        test_rule_fix(
+            "no-trailing-whitespace",
+            "{% block my_block %}  \n    <p>a {# b #} \n    c</p>\t\n{% endblock %} \n{% block empty %} \n{% endblock %}",
+            expect![[r#"
+                {% block my_block %}
+                    <p>a {# b #}
+                    c</p>
+                {% endblock %}
+                {% block empty %}
+                {% endblock %}"#]],
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxToken, TextRange};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleRequireFinalNewline;
+
+impl Rule for RuleRequireFinalNewline {
+    fn name(&self) -> &'static str {
+        "require-final-newline"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Files should end with a line break, otherwise tools like git and cat show the last line glued to the following output.")
+    }
+
+    fn check_token(&self, token: SyntaxToken, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        // only the last token of the file (`next_token` stops at empty nodes like an empty body)
+        let root = token.parent_ancestors().last()?;
+        if token.text_range().end() != root.text_range().end()
+            || token.kind() == SyntaxKind::TK_LINE_BREAK
+        {
+            return None;
+        }
+
+        let end = TextRange::empty(token.text_range().end());
+        let result = self
+            .create_result(Severity::Help, "Missing final newline")
+            .primary_note(
+                token.text_range(),
+                "file should end with a line break after this",
+            )
+            .suggestion(
+                end,
+                ctx.config().format.line_ending.corresponding_string(),
+                "Add line break",
+            );
+
+        Some(vec![result])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_fix};
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "require-final-newline",
+            "{% block my_block %}{% endblock %}",
+            expect![[r#"
+                help[require-final-newline]: Missing final newline
+                  ┌─ ./debug-rule.html.twig:1:33
+                  │
+                1 │ {% block my_block %}{% endblock %}
+                  │                                 ^^- Add line break: 
+
+                  │                                 │ 
+                  │                                 file should end with a line break after this
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule("require-final-newline", "<hr>\n", expect![[r#""#]]);
+        test_rule("require-final-newline", "", expect![[r#""#]]);
+    }
+
+    #[test]
+    fn rule_fixes() {
+        test_rule_fix(
+            "require-final-newline",
+            "{% block my_block %}{% endblock %}",
+            expect![[r#"
+                {% block my_block %}{% endblock %}
+            "#]],
+        );
+    }
+}
@@ -0,0 +1,235 @@
+use ludtwig_parser::syntax::typed::{AstNode, AstNodeExt, TwigBlock};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode, SyntaxNodeExt, TextRange};
+use ludtwig_parser::T;
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::config::BlockEndComment;
+
+pub struct RuleTwigBlockEndComment;
+
+impl Rule for RuleTwigBlockEndComment {
+    fn name(&self) -> &'static str {
+        "twig-block-end-comment"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("A comment like `{# end block_name #}` behind the `{% endblock %}` of long blocks shows which block ends there, without scrolling up to the start of it. The name in `{% endblock block_name %}` would also work, but is not supported by every tool.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let block = TwigBlock::cast(node)?;
+        let name = block.name()?;
+        // the parser also creates an (empty) ending block when the `{% endblock %}` is missing
+        let ending_block = block.ending_block().filter(|ending_block| {
+            ending_block
+                .syntax()
+                .children_with_tokens()
+                .any(|element| element.kind() == T!["endblock"])
+        })?;
+        let ending_range = ending_block.syntax().text_range_trimmed_trivia();
+        let expected = format!("{{# end {} #}}", name.text());
+
+        // the marker comment must be on the same line as the `{% endblock %}`
+        let marker = block.trailing_comments().into_iter().find(|comment| {
+            comment.kind() == SyntaxKind::TWIG_COMMENT && marker_name(comment).is_some()
+        });
+
+        let result = match (ctx.config().rules.block_end_comment, marker) {
+            (BlockEndComment::Require, None) => {
+                if block_lines(&block) <= ctx.config().rules.block_end_comment_threshold {
+                    return None;
+                }
+
+                self.create_result(
+                    Severity::Help,
+                    "Missing comment behind the end of the block",
+                )
+                .primary_note(
+                    ending_range,
+                    format!("expected a comment like `{expected}` behind this"),
+                )
+                .suggestion(
+                    TextRange::empty(ending_range.end()),
+                    format!(" {expected}"),
+                    "Add the comment",
+                )
+            }
+            (BlockEndComment::Require, Some(marker)) => {
+                if marker_name(&marker)? == name.text() {
+                    return None;
+                }
+
+                let marker_range = marker.text_range_trimmed_trivia();
+                self.create_result(
+                    Severity::Warning,
+                    "Comment behind the end of the block has the wrong name",
+                )
+                .primary_note(marker_range, "this doesn't match the name of the block")
+                .secondary_note(name.text_range(), "the block is named here")
+                .suggestion(marker_range, expected, "Use the block name")
+            }
+            (BlockEndComment::Forbid, Some(marker)) => {
+                let marker_range = marker.text_range_trimmed_trivia();
+                self.create_result(Severity::Help, "Comment behind the end of the block")
+                    .primary_note(
+                        marker_range,
+                        "comments behind the end of blocks are not allowed",
+                    )
+                    .suggestion(
+                        TextRange::new(ending_range.end(), marker_range.end()),
+                        "",
+                        "Remove the comment",
+                    )
+            }
+            (BlockEndComment::Forbid, None) => return None,
+        };
+
+        Some(vec![result])
+    }
+}
+
+/// The block name of a marker comment like `{# end block_name #}`
+fn marker_name(comment: &SyntaxNode) -> Option<String> {
+    let text = comment.text().to_string();
+    let content = text.trim().strip_prefix("{#")?.strip_suffix("#}")?;
+    let mut words = content.split_whitespace();
+    match (words.next(), words.next(), words.next()) {
+        (Some("end"), Some(name), None) => Some(name.to_string()),
+        _ => None,
+    }
+}
+
+/// Amount of lines of the block from the starting to the ending tag
+fn block_lines(block: &TwigBlock) -> usize {
+    let range = block.syntax().text_range_trimmed_trivia();
+    let line_breaks = block
+        .syntax()
+        .descendants_with_tokens()
+        .filter(|element| element.kind() == T![lb] && range.contains_range(element.text_range()))
+        .count();
+
+    line_breaks + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule_fix, test_rule_with_config};
+    use crate::check::rules::RuleRegistry;
+    use crate::config::{BlockEndComment, Config};
+    use crate::process::preview_fix;
+
+    fn config(block_end_comment: BlockEndComment) -> Config {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.block_end_comment = block_end_comment;
+        config.rules.block_end_comment_threshold = 3;
+        config
+    }
+
+    #[test]
+    fn rule_reports() {
+        test_rule_with_config(
+            "twig-block-end-comment",
+            r#"{% block a %}
+    {% block b %}
+        c
+    {% endblock %} {# end c #}
+{% endblock %}"#,
+            config(BlockEndComment::Require),
+            expect![[r#"
+                help[twig-block-end-comment]: Missing comment behind the end of the block
+                  ┌─ ./debug-rule.html.twig:5:1
+                  │
+                5 │ {% endblock %}
+                  │ ^^^^^^^^^^^^^^- Add the comment:  {# end a #}
+                  │ │             
+                  │ expected a comment like `{# end a #}` behind this
+
+                warning[twig-block-end-comment]: Comment behind the end of the block has the wrong name
+                  ┌─ ./debug-rule.html.twig:4:20
+                  │
+                2 │     {% block b %}
+                  │              - the block is named here
+                3 │         c
+                4 │     {% endblock %} {# end c #}
+                  │                    ^^^^^^^^^^^
+                  │                    │
+                  │                    this doesn't match the name of the block
+                  │                    Use the block name: {# end b #}
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule_with_config(
+            "twig-block-end-comment",
+            r#"{% block a %}
+    {% block b %}c{% endblock %}
+    {% block d %}
+        e
+    {% endblock %} {# end d #}
+{% endblock %} {# end a #}
+{% block f %}{% endblock %}"#,
+            config(BlockEndComment::Require),
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_unterminated_block() {
+        test_rule_with_config(
+            "twig-block-end-comment",
+            "{% block a %}\n    <div>\n        x\n    </div>\n",
+            config(BlockEndComment::Require),
+            expect![[r#"
+                error[SyntaxError]: The parser encountered a syntax error
+                  ┌─ ./debug-rule.html.twig:4:11
+                  │  
+                4 │       </div>
+                  │ ╭──────────^
+                5 │ │ 
+                  │ ╰^ expected {% or endblock or %} but reached end of file
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes() {
+        test_rule_fix(
+            "twig-block-end-comment",
+            "{% block a %}\n    {% block b %}\n        c\n    {% endblock %}\n{% endblock %}{# end b #}\n",
+            expect![[r#"
+                {% block a %}
+                    {% block b %}
+                        c
+                    {% endblock %}
+                {% endblock %}{# end a #}
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes_forbidden() {
+        expect![[r#"
+            {% block a %}
+                b
+            {% endblock %}
+            {% block c %}{% endblock %} {# other #}
+        "#]].assert_eq(
+            &preview_fix(
+                Path::new("./debug-rule.html.twig"),
+                "{% block a %}\n    b\n{% endblock %} {# end a #}\n{% block c %}{% endblock %}{# end c #} {# other #}\n",
+                "twig-block-end-comment",
+                &RuleRegistry::default(),
+                config(BlockEndComment::Forbid),
+            )
+            .unwrap(),
+        );
+    }
+}
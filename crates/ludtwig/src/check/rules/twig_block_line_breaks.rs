@@ -1,4 +1,4 @@
-use ludtwig_parser::syntax::typed::{AstNode, TwigBlock};
+use ludtwig_parser::syntax::typed::{AstNode, AstNodeExt, TwigBlock};
 use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode, TextRange, TextSize};
 
 use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
@@ -10,6 +10,12 @@ impl Rule for RuleTwigBlockLineBreaks {
         "twig-block-line-breaks"
     }
 
+    fn explanation(&self) -> Option<&'static str> {
+        Some(
+            "Twig blocks are separated with line breaks, so they are easy to spot in the template.",
+        )
+    }
+
     #[allow(clippy::too_many_lines)]
     fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
         if ctx.traversal_ctx().inside_trivia_sensitive_node {
@@ -26,23 +32,16 @@ impl Rule for RuleTwigBlockLineBreaks {
         }
 
         // find first token of twig block (ideally a line break)
+        // use the comments before the twig block as starting point if they exist
         let starting_block = block.starting_block()?;
-        let prev_sibling = block.syntax().prev_sibling();
-        let starting_syntax = match prev_sibling {
-            Some(ref n)
-                if matches!(
-                    n.kind(),
-                    SyntaxKind::TWIG_COMMENT
-                        | SyntaxKind::HTML_COMMENT
-                        | SyntaxKind::LUDTWIG_DIRECTIVE_FILE_IGNORE
-                        | SyntaxKind::LUDTWIG_DIRECTIVE_IGNORE
-                ) =>
-            {
-                // use comment before the twig block as starting point if it exists
-                n
-            }
-            _ => starting_block.syntax(),
-        };
+        let leading_comments = block.leading_comments();
+        let starting_syntax = leading_comments
+            .first()
+            .unwrap_or_else(|| starting_block.syntax());
+        let prev_sibling = leading_comments
+            .first()
+            .unwrap_or_else(|| block.syntax())
+            .prev_sibling();
         let first_child_token = starting_syntax.first_token();
 
         // find first token after the twig block (ideally a line break)
@@ -249,6 +248,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rule_fixes_with_leading_comments() {
+        test_rule_fix(
+            "twig-block-line-breaks",
+            "<div>
+    <hr/>
+    {# ludtwig-ignore twig-block-name-snake-case #}
+    {# the inner block #}
+    {% block inner %}
+        hello
+    {% endblock %}
+</div>",
+            expect![[r#"
+                <div>
+                    <hr/>
+
+                    {# ludtwig-ignore twig-block-name-snake-case #}
+                    {# the inner block #}
+                    {% block inner %}
+                        hello
+                    {% endblock %}
+                </div>"#]],
+        );
+    }
+
     #[test]
     fn rule_does_not_report_trivia_sensitive() {
         test_rule(
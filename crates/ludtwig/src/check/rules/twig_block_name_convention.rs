@@ -0,0 +1,193 @@
+use ludtwig_parser::syntax::typed::{AstNode, TwigBlock};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::rules::html_data_attribute_naming::get_naming_regex;
+use crate::check::rules::twig_block_name_snake_case::try_make_snake_case;
+use crate::check::templates::{parent_blocks, used_blocks, TemplateInfo};
+
+pub struct RuleTwigBlockNameConvention;
+
+impl Rule for RuleTwigBlockNameConvention {
+    fn name(&self) -> &'static str {
+        "twig-block-name-convention"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Block names are the extension points of a template, a common naming scheme (and prefix) avoids collisions with blocks of other templates or plugins.")
+    }
+
+    fn check_root(&self, _node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let pattern = &ctx.config().rules.block_name_regex;
+
+        match get_naming_regex(pattern) {
+            Ok(_) => None,
+            Err(e) => Some(vec![self.create_result(
+                Severity::Error,
+                format!("can't use block-name-regex '{pattern}': {e}"),
+            )]),
+        }
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let block = TwigBlock::cast(node)?;
+        let name = block.name()?;
+        let rules = &ctx.config().rules;
+        let naming_regex = get_naming_regex(&rules.block_name_regex).ok()?;
+
+        // blocks of embeds override the blocks of the embedded template
+        if block
+            .syntax()
+            .ancestors()
+            .any(|a| a.kind() == SyntaxKind::TWIG_EMBED)
+        {
+            return None;
+        }
+
+        // the names of blocks which override parent (or used) blocks can't be changed,
+        // `None` if the parent templates are unknown
+        let root = block.syntax().ancestors().last()?;
+        let template = TemplateInfo::from_tree(&root);
+        let overrides_parent = parent_blocks(ctx.file_path(), &template, ctx.config())
+            .zip(used_blocks(&template, ctx.config()))
+            .map(|(parent, used)| parent.contains(name.text()) || used.contains(name.text()));
+        if overrides_parent == Some(true) {
+            return None;
+        }
+
+        let prefix = rules.block_name_prefix.as_str();
+        let requires_prefix = overrides_parent == Some(false) && !prefix.is_empty();
+        let is_valid = |name: &str| {
+            naming_regex.is_match(name) && (!requires_prefix || name.starts_with(prefix))
+        };
+        if is_valid(name.text()) {
+            return None;
+        }
+
+        let (message, help) = if naming_regex.is_match(name.text()) {
+            (
+                format!("Block name does not start with the prefix '{prefix}'"),
+                format!("help: rename this block to start with '{prefix}'"),
+            )
+        } else {
+            (
+                "Block name does not match the naming convention".to_string(),
+                format!(
+                    "help: rename this block to match {}",
+                    rules.block_name_regex
+                ),
+            )
+        };
+        let mut result = self
+            .create_result(Severity::Help, message)
+            .primary_note(name.text_range(), help);
+
+        // try make a suggestion, which also renames the name behind endblock
+        let suggested_name = if naming_regex.is_match(name.text()) {
+            Some(name.text().to_string())
+        } else {
+            try_make_snake_case(name.text())
+        }
+        .map(|n| {
+            if requires_prefix && !n.starts_with(prefix) {
+                format!("{prefix}{n}")
+            } else {
+                n
+            }
+        })
+        .filter(|n| is_valid(n));
+        if let Some(suggested_name) = suggested_name {
+            let end_name = block.ending_block().and_then(|e| e.name());
+            for token in std::iter::once(name).chain(end_name) {
+                result =
+                    result.suggestion(token.text_range(), &suggested_name, "Try this name instead");
+            }
+        }
+
+        Some(vec![result])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_fix, test_rule_with_config};
+    use crate::config::Config;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-block-name-convention",
+            "{% block myBlock %}hello{% endblock %}",
+            expect![[r#"
+                help[twig-block-name-convention]: Block name does not match the naming convention
+                  ┌─ ./debug-rule.html.twig:1:10
+                  │
+                1 │ {% block myBlock %}hello{% endblock %}
+                  │          ^^^^^^^
+                  │          │
+                  │          help: rename this block to match ^[a-z0-9]+(_[a-z0-9]+)*$
+                  │          Try this name instead: my_block
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes() {
+        test_rule_fix(
+            "twig-block-name-convention",
+            "{% block myBlock %}hello{% endblock myBlock %}",
+            expect!["{% block my_block %}hello{% endblock my_block %}"],
+        );
+    }
+
+    #[test]
+    fn rule_reports_missing_prefix() {
+        let dir = std::env::temp_dir().join("ludtwig-block-name-convention-prefix");
+        let base = dir.join("storefront/base.html.twig");
+        std::fs::create_dir_all(base.parent().unwrap()).unwrap();
+        std::fs::write(base, "{% block base_body %}{% endblock %}").unwrap();
+
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.block_name_prefix = "swag_".to_string();
+        config.rules.template_namespaces = HashMap::from([(
+            "Storefront".to_string(),
+            dir.join("storefront").to_string_lossy().to_string(),
+        )]);
+
+        test_rule_with_config(
+            "twig-block-name-convention",
+            r#"{% sw_extends '@Storefront/base.html.twig' %}
+{% block base_body %}{% block swag_teaser %}{% endblock %}{% block content %}{% endblock %}{% endblock %}"#,
+            config,
+            expect![[r#"
+                help[twig-block-name-convention]: Block name does not start with the prefix 'swag_'
+                  ┌─ ./debug-rule.html.twig:2:68
+                  │
+                2 │ {% block base_body %}{% block swag_teaser %}{% endblock %}{% block content %}{% endblock %}{% endblock %}
+                  │                                                                    ^^^^^^^
+                  │                                                                    │
+                  │                                                                    help: rename this block to start with 'swag_'
+                  │                                                                    Try this name instead: swag_content
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_require_prefix_for_unknown_parents() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.block_name_prefix = "swag_".to_string();
+
+        test_rule_with_config(
+            "twig-block-name-convention",
+            "{% sw_extends '@Storefront/base.html.twig' %}{% block base_body %}{% endblock %}",
+            config,
+            expect![[r#""#]],
+        );
+    }
+}
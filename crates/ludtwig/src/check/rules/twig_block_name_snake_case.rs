@@ -10,6 +10,10 @@ impl Rule for RuleTwigBlockNameSnakeCase {
         "twig-block-name-snake-case"
     }
 
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Twig block names should consistently be written in snake_case.")
+    }
+
     fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
         let block_name = TwigStartingBlock::cast(node)?.name()?;
         if !is_valid_ascii_alpha_snake_case(block_name.text()) {
@@ -62,7 +66,7 @@ fn is_valid_ascii_alpha_snake_case(s: &str) -> bool {
     true
 }
 
-fn try_make_snake_case(original: &str) -> Option<String> {
+pub(super) fn try_make_snake_case(original: &str) -> Option<String> {
     let mut iter = original.chars().enumerate().peekable();
     let mut attempt = String::new();
     while let Some((idx, c)) = iter.next() {
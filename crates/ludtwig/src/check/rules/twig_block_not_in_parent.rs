@@ -0,0 +1,226 @@
+use ludtwig_parser::syntax::typed::{AstNode, TwigBlock, TwigFunctionCall};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode, SyntaxNodeExt};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::templates::{extends_tag, parent_blocks, used_blocks, TemplateInfo};
+
+pub struct RuleTwigBlockNotInParent;
+
+impl Rule for RuleTwigBlockNotInParent {
+    fn name(&self) -> &'static str {
+        "twig-block-not-in-parent"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Blocks of an extending template only render if a parent template defines them, otherwise the content is silently dropped.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let block = TwigBlock::cast(node)?;
+        let name = block.name()?;
+
+        let root = block.syntax().ancestors().last()?;
+        let template = TemplateInfo::from_tree(&root);
+        // blocks of other templates can be imported with `use`, which are also the parent of
+        // blocks with the same name
+        if used_blocks(&template, ctx.config())?.contains(name.text()) {
+            return None;
+        }
+        let parent_call = parent_call(&block);
+
+        let Some(extends) = extends_tag(&root) else {
+            let call = parent_call?;
+            let result = self
+                .create_result(
+                    Severity::Error,
+                    "parent() called in a template without a parent",
+                )
+                .primary_note(
+                    call.syntax().text_range_trimmed_trivia(),
+                    "this template doesn't extend another one",
+                );
+            return Some(vec![result]);
+        };
+
+        // nested blocks are new blocks, which are rendered by the outer one
+        if block
+            .syntax()
+            .ancestors()
+            .skip(1)
+            .any(|a| matches!(a.kind(), SyntaxKind::TWIG_BLOCK | SyntaxKind::TWIG_EMBED))
+        {
+            return None;
+        }
+
+        if parent_blocks(ctx.file_path(), &template, ctx.config())?.contains(name.text()) {
+            return None;
+        }
+
+        // without a parent block, the call of parent() fails
+        let severity = if parent_call.is_some() {
+            Severity::Error
+        } else {
+            Severity::Warning
+        };
+        let mut result = self
+            .create_result(
+                severity,
+                format!(
+                    "block '{}' does not exist in the parent templates",
+                    name.text()
+                ),
+            )
+            .primary_note(
+                name.text_range(),
+                "this block is never rendered, check the name for typos",
+            )
+            .secondary_note(
+                extends.text_range_trimmed_trivia(),
+                "none of the templates in this inheritance chain define it",
+            );
+        if let Some(call) = parent_call {
+            result = result.secondary_note(
+                call.syntax().text_range_trimmed_trivia(),
+                "parent() fails, because there is no parent block",
+            );
+        }
+
+        Some(vec![result])
+    }
+}
+
+/// Call of `parent()` directly inside of the block (and not inside of a nested one)
+fn parent_call(block: &TwigBlock) -> Option<TwigFunctionCall> {
+    block
+        .syntax()
+        .descendants()
+        .filter_map(TwigFunctionCall::cast)
+        .find(|call| {
+            call.name().is_some_and(|n| n.text() == "parent")
+                && call.syntax().ancestors().find_map(TwigBlock::cast).as_ref() == Some(block)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_with_config};
+    use crate::config::Config;
+
+    fn config_with_templates(name: &str) -> Config {
+        let dir = std::env::temp_dir().join(name);
+        let base = dir.join("storefront/base.html.twig");
+        std::fs::create_dir_all(base.parent().unwrap()).unwrap();
+        std::fs::write(
+            base,
+            "{% use '@Storefront/blocks.html.twig' with base_sidebar as base_aside %}{% block base_body %}{% block base_content %}{% endblock %}{% endblock %}",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("storefront/blocks.html.twig"),
+            "{% block base_sidebar %}{% endblock %}{% block base_title %}{% endblock %}",
+        )
+        .unwrap();
+
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.template_namespaces = HashMap::from([(
+            "Storefront".to_string(),
+            dir.join("storefront").to_string_lossy().to_string(),
+        )]);
+
+        config
+    }
+
+    #[test]
+    fn rule_reports() {
+        test_rule_with_config(
+            "twig-block-not-in-parent",
+            r#"{% sw_extends '@Storefront/base.html.twig' %}
+{% block base_contnet %}{{ parent() }}{% endblock %}
+{% block base_body %}{% block new_block %}{% endblock %}{% endblock %}
+{% block base_footer %}{% endblock %}
+{% block base_sidebar %}{% endblock %}"#,
+            config_with_templates("ludtwig-block-not-in-parent-reports"),
+            expect![[r#"
+                error[twig-block-not-in-parent]: block 'base_contnet' does not exist in the parent templates
+                  ┌─ ./debug-rule.html.twig:2:10
+                  │
+                1 │ {% sw_extends '@Storefront/base.html.twig' %}
+                  │ --------------------------------------------- none of the templates in this inheritance chain define it
+                2 │ {% block base_contnet %}{{ parent() }}{% endblock %}
+                  │          ^^^^^^^^^^^^      -------- parent() fails, because there is no parent block
+                  │          │                  
+                  │          this block is never rendered, check the name for typos
+
+                warning[twig-block-not-in-parent]: block 'base_footer' does not exist in the parent templates
+                  ┌─ ./debug-rule.html.twig:4:10
+                  │
+                1 │ {% sw_extends '@Storefront/base.html.twig' %}
+                  │ --------------------------------------------- none of the templates in this inheritance chain define it
+                  ·
+                4 │ {% block base_footer %}{% endblock %}
+                  │          ^^^^^^^^^^^ this block is never rendered, check the name for typos
+
+                warning[twig-block-not-in-parent]: block 'base_sidebar' does not exist in the parent templates
+                  ┌─ ./debug-rule.html.twig:5:10
+                  │
+                1 │ {% sw_extends '@Storefront/base.html.twig' %}
+                  │ --------------------------------------------- none of the templates in this inheritance chain define it
+                  ·
+                5 │ {% block base_sidebar %}{% endblock %}
+                  │          ^^^^^^^^^^^^ this block is never rendered, check the name for typos
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_reports_parent_without_extends() {
+        test_rule(
+            "twig-block-not-in-parent",
+            "{% block a %}{{ parent() }}{% block b %}{{ parent() }}{% endblock %}{% endblock %}",
+            expect![[r#"
+                error[twig-block-not-in-parent]: parent() called in a template without a parent
+                  ┌─ ./debug-rule.html.twig:1:17
+                  │
+                1 │ {% block a %}{{ parent() }}{% block b %}{{ parent() }}{% endblock %}{% endblock %}
+                  │                 ^^^^^^^^ this template doesn't extend another one
+
+                error[twig-block-not-in-parent]: parent() called in a template without a parent
+                  ┌─ ./debug-rule.html.twig:1:44
+                  │
+                1 │ {% block a %}{{ parent() }}{% block b %}{{ parent() }}{% endblock %}{% endblock %}
+                  │                                            ^^^^^^^^ this template doesn't extend another one
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        // unknown parent templates can't be checked
+        test_rule(
+            "twig-block-not-in-parent",
+            "{% sw_extends '@Storefront/base.html.twig' %}{% block a %}{{ parent() }}{% endblock %}",
+            expect![[r#""#]],
+        );
+        test_rule_with_config(
+            "twig-block-not-in-parent",
+            r#"{% sw_extends '@Storefront/base.html.twig' %}
+{% block base_content %}{{ parent() }}{% endblock %}
+{% block base_body %}{% block new_block %}{% endblock %}{% endblock %}
+{% block base_aside %}{% endblock %}
+{% block base_title %}{% endblock %}"#,
+            config_with_templates("ludtwig-block-not-in-parent-does-not-report"),
+            expect![[r#""#]],
+        );
+        test_rule(
+            "twig-block-not-in-parent",
+            "{% use 'blocks.html.twig' %}{% block a %}{{ parent() }}{% endblock %}",
+            expect![[r#""#]],
+        );
+    }
+}
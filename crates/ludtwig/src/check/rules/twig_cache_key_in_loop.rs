@@ -0,0 +1,165 @@
+use ludtwig_parser::syntax::scope::{ScopeAnalyzer, ScopeId, ScopeKind};
+use ludtwig_parser::syntax::typed::{AstNode, TwigCacheStartingBlock};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode, SyntaxNodeExt, TextSize};
+
+use crate::check::is_ignored_by_directive;
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleTwigCacheKeyInLoop;
+
+impl Rule for RuleTwigCacheKeyInLoop {
+    fn name(&self) -> &'static str {
+        "twig-cache-key-in-loop"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("A cache key which is the same in every iteration of a loop renders the cached content of the first iteration every time.")
+    }
+
+    fn check_root(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let analyzer = ScopeAnalyzer::new(&node);
+
+        let results = node
+            .descendants()
+            .filter_map(TwigCacheStartingBlock::cast)
+            .filter_map(|starting_block| {
+                let result = self.check_cache(&analyzer, &starting_block)?;
+                (!self.is_ignored(starting_block.syntax())).then_some(result)
+            })
+            .collect();
+
+        Some(results)
+    }
+}
+
+impl RuleTwigCacheKeyInLoop {
+    fn check_cache(
+        &self,
+        analyzer: &ScopeAnalyzer,
+        starting_block: &TwigCacheStartingBlock,
+    ) -> Option<CheckResult> {
+        let key = starting_block.key()?;
+        let key_range = key.syntax().text_range();
+        let loop_scope = innermost_loop_scope(analyzer, key_range.start())?;
+
+        let depends_on_loop = analyzer
+            .references()
+            .iter()
+            .filter(|r| key_range.contains_range(r.range))
+            .filter_map(|r| r.definition)
+            .any(|d| is_inside_scope(analyzer, analyzer.definition(d).scope, loop_scope));
+        if depends_on_loop {
+            return None;
+        }
+
+        let mut result = self
+            .create_result(
+                Severity::Warning,
+                "cache key does not change inside of the loop",
+            )
+            .primary_note(
+                key.syntax().text_range_trimmed_trivia(),
+                "every iteration uses the same cache entry, add a loop variable to the key",
+            );
+
+        if let Some(for_block) = starting_block
+            .syntax()
+            .ancestors()
+            .find(|a| a.kind() == SyntaxKind::TWIG_FOR)
+            .and_then(|f| f.first_child())
+        {
+            result =
+                result.secondary_note(for_block.text_range_trimmed_trivia(), "inside of this loop");
+        }
+
+        Some(result)
+    }
+
+    /// Whether the node is inside of an error node or ignored with a ludtwig-ignore directive
+    fn is_ignored(&self, node: &SyntaxNode) -> bool {
+        node.ancestors()
+            .any(|a| a.kind() == SyntaxKind::ERROR || is_ignored_by_directive(&a, self.name()))
+    }
+}
+
+/// Innermost loop around the offset, but not outside of macros which are called with their own variables
+fn innermost_loop_scope(analyzer: &ScopeAnalyzer, offset: TextSize) -> Option<ScopeId> {
+    let mut current = Some(analyzer.scope_at(offset));
+
+    while let Some(id) = current {
+        let scope = analyzer.scope(id);
+        match scope.kind {
+            ScopeKind::For => return Some(id),
+            ScopeKind::Macro => return None,
+            _ => current = scope.parent,
+        }
+    }
+
+    None
+}
+
+fn is_inside_scope(analyzer: &ScopeAnalyzer, scope: ScopeId, outer: ScopeId) -> bool {
+    let mut current = Some(scope);
+
+    while let Some(id) = current {
+        if id == outer {
+            return true;
+        }
+        current = analyzer.scope(id).parent;
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::test_rule;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-cache-key-in-loop",
+            r#"{% for product in products %}
+    {% cache 'product_' ~ page.id ttl(300) %}{{ product.name }}{% endcache %}
+{% endfor %}"#,
+            expect![[r#"
+                warning[twig-cache-key-in-loop]: cache key does not change inside of the loop
+                  ┌─ ./debug-rule.html.twig:2:14
+                  │
+                1 │ {% for product in products %}
+                  │ ----------------------------- inside of this loop
+                2 │     {% cache 'product_' ~ page.id ttl(300) %}{{ product.name }}{% endcache %}
+                  │              ^^^^^^^^^^^^^^^^^^^^ every iteration uses the same cache entry, add a loop variable to the key
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_keys_with_loop_variables() {
+        test_rule(
+            "twig-cache-key-in-loop",
+            r#"{% cache 'list' %}{% endcache %}
+{% for product in products %}
+    {% cache 'product_' ~ product.id %}{% endcache %}
+    {% cache 'index_' ~ loop.index %}{% endcache %}
+    {% set name = product.name %}{% cache name %}{% endcache %}
+{% endfor %}"#,
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_respects_ignore_directives() {
+        test_rule(
+            "twig-cache-key-in-loop",
+            r#"{% for product in products %}
+    {# ludtwig-ignore twig-cache-key-in-loop #}
+    {% cache 'header' %}{% endcache %}
+{% endfor %}"#,
+            expect![[r#""#]],
+        );
+    }
+}
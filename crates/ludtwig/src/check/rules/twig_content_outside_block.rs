@@ -0,0 +1,164 @@
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode, SyntaxNodeExt, WalkEvent};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleTwigContentOutsideBlock;
+
+impl Rule for RuleTwigContentOutsideBlock {
+    fn name(&self) -> &'static str {
+        "twig-content-outside-block"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Twig never renders content outside of blocks in templates which extend another one.")
+    }
+
+    fn check_root(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let extends = node.children().find(|c| {
+            matches!(
+                c.kind(),
+                SyntaxKind::TWIG_EXTENDS | SyntaxKind::SHOPWARE_TWIG_SW_EXTENDS
+            )
+        })?;
+        let extends_range = extends.text_range_trimmed_trivia();
+
+        let mut results = vec![];
+        let mut preorder = node.preorder();
+        while let Some(event) = preorder.next() {
+            let WalkEvent::Enter(child) = event else {
+                continue;
+            };
+
+            match child.kind() {
+                // content in there is rendered (or captured) somewhere else
+                SyntaxKind::TWIG_BLOCK | SyntaxKind::TWIG_MACRO | SyntaxKind::TWIG_SET => {
+                    preorder.skip_subtree();
+                }
+                // blocks inside of the element still work, only the element itself is never rendered
+                SyntaxKind::HTML_TAG
+                    if child
+                        .descendants()
+                        .any(|d| d.kind() == SyntaxKind::TWIG_BLOCK) => {}
+                kind if is_rendered_content(kind) => {
+                    preorder.skip_subtree();
+                    results.push(
+                        self.create_result(
+                            Severity::Warning,
+                            "content outside of a block in an extending template",
+                        )
+                        .primary_note(
+                            child.text_range_trimmed_trivia(),
+                            "this is never rendered, move it into a block",
+                        )
+                        .secondary_note(extends_range, "because the template extends another one"),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        Some(results)
+    }
+}
+
+/// Nodes which produce output, twig ignores them outside of blocks in a child template
+fn is_rendered_content(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::HTML_TAG
+            | SyntaxKind::HTML_STARTING_TAG
+            | SyntaxKind::HTML_ENDING_TAG
+            | SyntaxKind::HTML_TEXT
+            | SyntaxKind::HTML_COMMENT
+            | SyntaxKind::HTML_CONDITIONAL_COMMENT
+            | SyntaxKind::HTML_CDATA
+            | SyntaxKind::HTML_XML_PROLOG
+            | SyntaxKind::HTML_DOCTYPE
+            | SyntaxKind::TWIG_VAR
+            | SyntaxKind::TWIG_VERBATIM
+            | SyntaxKind::TWIG_TRANS
+            | SyntaxKind::TWIG_INCLUDE
+            | SyntaxKind::SHOPWARE_TWIG_SW_INCLUDE
+            | SyntaxKind::TWIG_EMBED
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::test_rule;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-content-outside-block",
+            r#"{% sw_extends '@Storefront/base.html.twig' %}
+<div class="wrapper">
+    {% block content %}a{% endblock %}
+</div>
+{% if a %}
+    Hello {{ name }}
+{% endif %}"#,
+            expect![[r#"
+                warning[twig-content-outside-block]: content outside of a block in an extending template
+                  ┌─ ./debug-rule.html.twig:2:1
+                  │
+                1 │ {% sw_extends '@Storefront/base.html.twig' %}
+                  │ --------------------------------------------- because the template extends another one
+                2 │ <div class="wrapper">
+                  │ ^^^^^^^^^^^^^^^^^^^^^ this is never rendered, move it into a block
+
+                warning[twig-content-outside-block]: content outside of a block in an extending template
+                  ┌─ ./debug-rule.html.twig:4:1
+                  │
+                1 │ {% sw_extends '@Storefront/base.html.twig' %}
+                  │ --------------------------------------------- because the template extends another one
+                  ·
+                4 │ </div>
+                  │ ^^^^^^ this is never rendered, move it into a block
+
+                warning[twig-content-outside-block]: content outside of a block in an extending template
+                  ┌─ ./debug-rule.html.twig:6:5
+                  │
+                1 │ {% sw_extends '@Storefront/base.html.twig' %}
+                  │ --------------------------------------------- because the template extends another one
+                  ·
+                6 │     Hello {{ name }}
+                  │     ^^^^^ this is never rendered, move it into a block
+
+                warning[twig-content-outside-block]: content outside of a block in an extending template
+                  ┌─ ./debug-rule.html.twig:6:11
+                  │
+                1 │ {% sw_extends '@Storefront/base.html.twig' %}
+                  │ --------------------------------------------- because the template extends another one
+                  ·
+                6 │     Hello {{ name }}
+                  │           ^^^^^^^^^^ this is never rendered, move it into a block
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "twig-content-outside-block",
+            r#"{% extends 'base.html.twig' %}
+{# comment #}
+{% import 'macros.html.twig' as macros %}
+{% set title = 'a' %}
+{% set content %}<p>captured</p>{% endset %}
+{% macro m() %}<p>macro</p>{% endmacro %}
+{% if a %}
+    {% block content %}<div>{{ title }}</div>{% endblock %}
+{% endif %}"#,
+            expect![[r#""#]],
+        );
+        test_rule(
+            "twig-content-outside-block",
+            "<div>{% block content %}{% endblock %}</div>",
+            expect![[r#""#]],
+        );
+    }
+}
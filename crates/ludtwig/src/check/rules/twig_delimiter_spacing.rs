@@ -0,0 +1,236 @@
+use ludtwig_parser::syntax::typed::{AstNode, Body, TwigVerbatim};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode, SyntaxToken, TextRange};
+use ludtwig_parser::T;
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleTwigDelimiterSpacing;
+
+impl Rule for RuleTwigDelimiterSpacing {
+    fn name(&self) -> &'static str {
+        "twig-delimiter-spacing"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Consistent spacing inside of {{ }}, {% %} and {# #} makes the twig syntax easier to spot between the html.")
+    }
+
+    fn check_token(&self, token: SyntaxToken, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let opening = match token.kind() {
+            T!["{{"] | T!["{%"] | T!["{#"] => true,
+            T!["}}"] | T!["%}"] | T!["#}"] => false,
+            _ => return None,
+        };
+
+        // only delimiters of twig syntax (with the opening and closing one in the same node),
+        // not the ones in text or strings and not the content of verbatim
+        let parent = token.parent()?;
+        let delimiters = parent
+            .children_with_tokens()
+            .filter(|element| is_delimiter(element.kind()))
+            .count();
+        if delimiters < 2
+            || token.parent_ancestors().any(|n| {
+                Body::can_cast(n.kind())
+                    && n.parent().is_some_and(|p| TwigVerbatim::can_cast(p.kind()))
+            })
+        {
+            return None;
+        }
+
+        let root = token.parent_ancestors().last()?;
+        let spaces = " ".repeat(ctx.config().format.twig_delimiter_spaces.into());
+
+        // whitespace control modifiers like `{{-` or `~%}` are part of the delimiter
+        let mut delimiter = token.clone();
+        let mut inner = neighbour(&root, &delimiter, opening)?;
+        if matches!(inner.kind(), T!["-"] | T!["~"]) {
+            delimiter = inner;
+            inner = neighbour(&root, &delimiter, opening)?;
+        }
+
+        let (range, message) = match inner.kind() {
+            // empty delimiters like `{{}}` and line breaks (like in multi-line tags) are fine
+            T![lb] => return None,
+            kind if is_delimiter(kind) || matches!(kind, T!["-"] | T!["~"]) => return None,
+            T![ws] => {
+                let after = neighbour(&root, &inner, opening)?;
+                if after.kind() == T![lb] || is_delimiter(after.kind()) || inner.text() == spaces {
+                    return None;
+                }
+
+                (inner.text_range(), "Wrong spacing inside of twig delimiter")
+            }
+            _ if spaces.is_empty() => return None,
+            _ => {
+                let position = if opening {
+                    delimiter.text_range().end()
+                } else {
+                    delimiter.text_range().start()
+                };
+
+                (
+                    TextRange::empty(position),
+                    "Missing space inside of twig delimiter",
+                )
+            }
+        };
+
+        let expected = match spaces.len() {
+            0 => "no spaces".to_string(),
+            1 => "1 space".to_string(),
+            n => format!("{n} spaces"),
+        };
+        let note = if opening {
+            format!("expected {expected} after this")
+        } else {
+            format!("expected {expected} before this")
+        };
+
+        let result = self
+            .create_result(Severity::Help, message)
+            .primary_note(token.text_range().cover(delimiter.text_range()), note)
+            .suggestion(range, spaces, format!("Use {expected}"));
+
+        Some(vec![result])
+    }
+}
+
+fn is_delimiter(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        T!["{{"] | T!["{%"] | T!["{#"] | T!["}}"] | T!["%}"] | T!["#}"]
+    )
+}
+
+/// The next (or previous) token in the tree.
+/// `next_token` / `prev_token` stop at empty nodes (like an empty body), so the root is searched instead.
+fn neighbour(root: &SyntaxNode, token: &SyntaxToken, forward: bool) -> Option<SyntaxToken> {
+    let range = token.text_range();
+    if forward {
+        if range.end() >= root.text_range().end() {
+            return None;
+        }
+        root.token_at_offset(range.end()).right_biased()
+    } else {
+        if range.start() == 0.into() {
+            return None;
+        }
+        root.token_at_offset(range.start()).left_biased()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_fix, test_rule_with_config};
+    use crate::check::rules::RuleRegistry;
+    use crate::config::Config;
+    use crate::process::preview_fix;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-delimiter-spacing",
+            "{{foo}}{%  if a %}{#comment  #}{% endif %}<p title=\"a }}\">{{ '%}' }} #}</p>",
+            expect![[r#"
+                help[twig-delimiter-spacing]: Missing space inside of twig delimiter
+                  ┌─ ./debug-rule.html.twig:1:1
+                  │
+                1 │ {{foo}}{%  if a %}{#comment  #}{% endif %}<p title="a }}">{{ '%}' }} #}</p>
+                  │ ^^- Use 1 space:  
+                  │ │  
+                  │ expected 1 space after this
+
+                help[twig-delimiter-spacing]: Missing space inside of twig delimiter
+                  ┌─ ./debug-rule.html.twig:1:6
+                  │
+                1 │ {{foo}}{%  if a %}{#comment  #}{% endif %}<p title="a }}">{{ '%}' }} #}</p>
+                  │      ^^
+                  │      │
+                  │      expected 1 space before this
+                  │      Use 1 space:  
+
+                help[twig-delimiter-spacing]: Wrong spacing inside of twig delimiter
+                  ┌─ ./debug-rule.html.twig:1:8
+                  │
+                1 │ {{foo}}{%  if a %}{#comment  #}{% endif %}<p title="a }}">{{ '%}' }} #}</p>
+                  │        ^^-- Use 1 space:  
+                  │        │  
+                  │        expected 1 space after this
+
+                help[twig-delimiter-spacing]: Missing space inside of twig delimiter
+                  ┌─ ./debug-rule.html.twig:1:19
+                  │
+                1 │ {{foo}}{%  if a %}{#comment  #}{% endif %}<p title="a }}">{{ '%}' }} #}</p>
+                  │                   ^^- Use 1 space:  
+                  │                   │  
+                  │                   expected 1 space after this
+
+                help[twig-delimiter-spacing]: Wrong spacing inside of twig delimiter
+                  ┌─ ./debug-rule.html.twig:1:30
+                  │
+                1 │ {{foo}}{%  if a %}{#comment  #}{% endif %}<p title="a }}">{{ '%}' }} #}</p>
+                  │                            --^^ expected 1 space before this
+                  │                            │  
+                  │                            Use 1 space:  
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "twig-delimiter-spacing",
+            "{{ foo }}{% if a %}{# comment #}{% endif %}{##}{%\n    set a = 1\n%}{#\n    multi\n#}{% verbatim %}{{foo}}{% endverbatim %}",
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes() {
+        test_rule_fix(
+            "twig-delimiter-spacing",
+            "<a href=\"{{path('home')}}\">{{  'home'|trans}}</a>{%if a%}{#comment  #}{%endif%}",
+            expect![[
+                r#"<a href="{{ path('home') }}">{{ 'home'|trans }}</a>{% if a %}{# comment #}{% endif %}"#
+            ]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes_without_spaces() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.format.twig_delimiter_spaces = 0;
+
+        test_rule_with_config(
+            "twig-delimiter-spacing",
+            "{{ foo}}",
+            config.clone(),
+            expect![[r#"
+                help[twig-delimiter-spacing]: Wrong spacing inside of twig delimiter
+                  ┌─ ./debug-rule.html.twig:1:1
+                  │
+                1 │ {{ foo}}
+                  │ ^^- Use no spaces: 
+                  │ │  
+                  │ expected no spaces after this
+
+            "#]],
+        );
+        expect!["{{foo}}{%if a%}{#comment#}{%endif%}"].assert_eq(
+            &preview_fix(
+                Path::new("./debug-rule.html.twig"),
+                "{{ foo }}{%  if a  %}{# comment #}{%endif%}",
+                "twig-delimiter-spacing",
+                &RuleRegistry::default(),
+                config,
+            )
+            .unwrap(),
+        );
+    }
+}
@@ -0,0 +1,288 @@
+use ludtwig_parser::syntax::typed::{
+    AstNode, TwigApply, TwigApplyEndingBlock, TwigApplyStartingBlock, TwigBinaryExpression,
+    TwigFilter, TwigSpaceless, TwigSpacelessEndingBlock, TwigSpacelessStartingBlock,
+};
+use ludtwig_parser::syntax::untyped::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken};
+use ludtwig_parser::T;
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::rules::twig_unknown_filter::first_apply_filter;
+use crate::check::rules::twig_unknown_test::test_name;
+use crate::config::TwigVersion;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Construct {
+    Tag,
+    Filter,
+    Test,
+}
+
+/// A twig construct which is deprecated since a twig version
+struct Deprecation {
+    construct: Construct,
+    name: &'static str,
+    since: TwigVersion,
+    /// Replacement of the name, which works the same way
+    replacement: Option<&'static str>,
+}
+
+static DEPRECATIONS: &[Deprecation] = &[
+    Deprecation {
+        construct: Construct::Test,
+        name: "sameas",
+        since: TwigVersion::new(1, 21),
+        replacement: Some("same as"),
+    },
+    Deprecation {
+        construct: Construct::Test,
+        name: "divisibleby",
+        since: TwigVersion::new(1, 21),
+        replacement: Some("divisible by"),
+    },
+    Deprecation {
+        construct: Construct::Tag,
+        name: "spaceless",
+        since: TwigVersion::new(2, 7),
+        replacement: Some("apply spaceless"),
+    },
+    Deprecation {
+        construct: Construct::Tag,
+        name: "filter",
+        since: TwigVersion::new(2, 9),
+        replacement: Some("apply"),
+    },
+    Deprecation {
+        construct: Construct::Filter,
+        name: "spaceless",
+        since: TwigVersion::new(3, 12),
+        replacement: None,
+    },
+];
+
+/// The deprecation of the construct, if it is deprecated in the twig version
+fn find_deprecation(
+    construct: &Construct,
+    name: &str,
+    version: TwigVersion,
+) -> Option<&'static Deprecation> {
+    DEPRECATIONS
+        .iter()
+        .find(|d| &d.construct == construct && d.name == name && d.since <= version)
+}
+
+pub struct RuleTwigDeprecated;
+
+impl Rule for RuleTwigDeprecated {
+    fn name(&self) -> &'static str {
+        "twig-deprecated"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Deprecated twig constructs are removed in the next major version of twig, replacing them early makes the upgrade easier.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let version = ctx.config().general.twig_version;
+
+        if let Some(spaceless) = TwigSpaceless::cast(node.clone()) {
+            return self.check_spaceless_tag(&spaceless, version);
+        }
+        if let Some(apply) = TwigApply::cast(node.clone()) {
+            return self.check_filter_tag(&apply, version);
+        }
+
+        let (construct, name) = if let Some(binary) = TwigBinaryExpression::cast(node.clone()) {
+            (Construct::Test, test_name(&binary)?)
+        } else if let Some(filter) = TwigFilter::cast(node.clone()) {
+            (Construct::Filter, filter.name()?)
+        } else {
+            let apply = TwigApplyStartingBlock::cast(node)?;
+            (Construct::Filter, first_apply_filter(&apply)?)
+        };
+
+        let deprecation = find_deprecation(&construct, name.text(), version)?;
+        let construct_name = match construct {
+            Construct::Tag => "tag",
+            Construct::Filter => "filter",
+            Construct::Test => "test",
+        };
+        let mut result = self
+            .create_result(
+                Severity::Warning,
+                format!(
+                    "the '{}' {construct_name} is deprecated since twig {}",
+                    name.text(),
+                    deprecation.since
+                ),
+            )
+            .primary_note(name.text_range(), "deprecated");
+        if let Some(replacement) = deprecation.replacement {
+            result = result.suggestion(
+                name.text_range(),
+                replacement,
+                format!("use '{replacement}' instead"),
+            );
+        }
+
+        Some(vec![result])
+    }
+}
+
+impl RuleTwigDeprecated {
+    fn check_spaceless_tag(
+        &self,
+        spaceless: &TwigSpaceless,
+        version: TwigVersion,
+    ) -> Option<Vec<CheckResult>> {
+        let starting_block = spaceless
+            .syntax()
+            .children()
+            .find_map(TwigSpacelessStartingBlock::cast)?;
+        let keyword = keyword_of(starting_block.syntax(), T!["spaceless"])?;
+        let end_keyword = spaceless
+            .syntax()
+            .children()
+            .find_map(TwigSpacelessEndingBlock::cast)
+            .and_then(|end| keyword_of(end.syntax(), T!["endspaceless"]));
+        let deprecation = find_deprecation(&Construct::Tag, "spaceless", version)?;
+
+        let mut result = self
+            .create_result(
+                Severity::Warning,
+                format!(
+                    "the 'spaceless' tag is deprecated since twig {}",
+                    deprecation.since
+                ),
+            )
+            .primary_note(keyword.text_range(), "deprecated");
+
+        // the replacement is deprecated itself in newer versions
+        if find_deprecation(&Construct::Filter, "spaceless", version).is_none() {
+            result = result.suggestion(
+                keyword.text_range(),
+                "apply spaceless",
+                "use the spaceless filter with the apply tag instead",
+            );
+            if let Some(end_keyword) = end_keyword {
+                result = result.suggestion(
+                    end_keyword.text_range(),
+                    "endapply",
+                    "use the spaceless filter with the apply tag instead",
+                );
+            }
+        }
+
+        Some(vec![result])
+    }
+
+    fn check_filter_tag(
+        &self,
+        apply: &TwigApply,
+        version: TwigVersion,
+    ) -> Option<Vec<CheckResult>> {
+        let starting_block = apply
+            .syntax()
+            .children()
+            .find_map(TwigApplyStartingBlock::cast)?;
+        let keyword = keyword_of(starting_block.syntax(), T!["filter"])?;
+        let end_keyword = apply
+            .syntax()
+            .children()
+            .find_map(TwigApplyEndingBlock::cast)
+            .and_then(|end| keyword_of(end.syntax(), T!["endfilter"]));
+        let deprecation = find_deprecation(&Construct::Tag, "filter", version)?;
+
+        let mut result = self
+            .create_result(
+                Severity::Warning,
+                format!(
+                    "the 'filter' tag is deprecated since twig {}",
+                    deprecation.since
+                ),
+            )
+            .primary_note(keyword.text_range(), "deprecated")
+            .suggestion(keyword.text_range(), "apply", "use the apply tag instead");
+        if let Some(end_keyword) = end_keyword {
+            result = result.suggestion(
+                end_keyword.text_range(),
+                "endapply",
+                "use the apply tag instead",
+            );
+        }
+
+        Some(vec![result])
+    }
+}
+
+/// The keyword token of a twig tag, like `filter` in `{% filter upper %}`
+fn keyword_of(tag: &SyntaxNode, kind: SyntaxKind) -> Option<SyntaxToken> {
+    tag.children_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .find(|t| t.kind() == kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{
+        test_rule, test_rule_does_not_fix, test_rule_fix, test_rule_with_config,
+    };
+    use crate::config::{Config, TwigVersion};
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-deprecated",
+            "{{ a is sameas(b) }}{{ c|spaceless }}",
+            expect![[r#"
+                warning[twig-deprecated]: the 'sameas' test is deprecated since twig 1.21
+                  ┌─ ./debug-rule.html.twig:1:9
+                  │
+                1 │ {{ a is sameas(b) }}{{ c|spaceless }}
+                  │         ^^^^^^
+                  │         │
+                  │         deprecated
+                  │         use 'same as' instead: same as
+
+                warning[twig-deprecated]: the 'spaceless' filter is deprecated since twig 3.12
+                  ┌─ ./debug-rule.html.twig:1:26
+                  │
+                1 │ {{ a is sameas(b) }}{{ c|spaceless }}
+                  │                          ^^^^^^^^^ deprecated
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes() {
+        test_rule_fix(
+            "twig-deprecated",
+            "{% filter upper %}a{% endfilter %}{{ b is divisibleby(2) }}",
+            expect!["{% apply upper %}a{% endapply %}{{ b is divisible by(2) }}"],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_fix_spaceless_tag_with_deprecated_spaceless_filter() {
+        test_rule_does_not_fix(
+            "twig-deprecated",
+            "{% spaceless %}a{% endspaceless %}",
+            expect!["{% spaceless %}a{% endspaceless %}"],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_for_older_twig_version() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.general.twig_version = TwigVersion::new(2, 6);
+
+        test_rule_with_config(
+            "twig-deprecated",
+            "{% spaceless %}a{% endspaceless %}{% filter upper %}b{% endfilter %}",
+            config,
+            expect![[r#""#]],
+        );
+    }
+}
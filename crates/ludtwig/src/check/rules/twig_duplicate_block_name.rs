@@ -0,0 +1,87 @@
+use ludtwig_parser::syntax::typed::{AstNode, TwigBlock};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleTwigDuplicateBlockName;
+
+impl Rule for RuleTwigDuplicateBlockName {
+    fn name(&self) -> &'static str {
+        "twig-duplicate-block-name"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Block names must be unique in a template, twig fails to compile templates which define a block twice.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let block = TwigBlock::cast(node)?;
+        let name = block.name()?;
+        let template = owning_template(block.syntax())?;
+
+        let first_name = template
+            .descendants()
+            .filter_map(TwigBlock::cast)
+            .filter(|b| owning_template(b.syntax()).as_ref() == Some(&template))
+            .find_map(|b| b.name().filter(|n| n.text() == name.text()))?;
+        if first_name == name {
+            return None;
+        }
+
+        let result = self
+            .create_result(
+                Severity::Error,
+                format!("block '{}' is defined more than once", name.text()),
+            )
+            .primary_note(name.text_range(), "rename or remove this block")
+            .secondary_note(first_name.text_range(), "the block is already defined here");
+
+        Some(vec![result])
+    }
+}
+
+/// The root or the `{% embed %}` tag, because blocks of an embed belong to the embedded template
+fn owning_template(block: &SyntaxNode) -> Option<SyntaxNode> {
+    block
+        .ancestors()
+        .skip(1)
+        .find(|a| a.kind() == SyntaxKind::TWIG_EMBED || a.parent().is_none())
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::test_rule;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-duplicate-block-name",
+            r#"{% block content %}{% block title %}{% endblock %}{% endblock %}
+{% if a %}{% block title %}{% endblock %}{% endif %}"#,
+            expect![[r#"
+                error[twig-duplicate-block-name]: block 'title' is defined more than once
+                  ┌─ ./debug-rule.html.twig:2:20
+                  │
+                1 │ {% block content %}{% block title %}{% endblock %}{% endblock %}
+                  │                             ----- the block is already defined here
+                2 │ {% if a %}{% block title %}{% endblock %}{% endif %}
+                  │                    ^^^^^ rename or remove this block
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_blocks_of_embeds() {
+        test_rule(
+            "twig-duplicate-block-name",
+            r#"{% block content %}
+    {% embed 'card.html.twig' %}{% block content %}{% endblock %}{% endembed %}
+    {% embed 'card.html.twig' %}{% block content %}{% endblock %}{% endembed %}
+{% endblock %}"#,
+            expect![[r#""#]],
+        );
+    }
+}
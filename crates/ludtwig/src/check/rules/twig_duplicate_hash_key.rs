@@ -0,0 +1,109 @@
+use ludtwig_parser::syntax::typed::{AstNode, TwigLiteralHash, TwigLiteralHashKey};
+use ludtwig_parser::syntax::untyped::{SyntaxNode, SyntaxNodeExt};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleTwigDuplicateHashKey;
+
+impl Rule for RuleTwigDuplicateHashKey {
+    fn name(&self) -> &'static str {
+        "twig-duplicate-hash-key"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("A hash can't contain the same key twice, the value of a later key silently overwrites the earlier one.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let hash = TwigLiteralHash::cast(node)?;
+
+        // keys are compared by value, so `a`, 'a' and "a" are the same key (and so are 1 and '1').
+        // Expressions like `(name)` and interpolated strings are only known at runtime.
+        let keys: Vec<(TwigLiteralHashKey, String)> = hash
+            .pairs()
+            .filter_map(|pair| {
+                let key = pair.key()?;
+                let name = key.get_key_name()?;
+                Some((key, name))
+            })
+            .collect();
+
+        let results: Vec<_> = keys
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, (duplicate, name))| {
+                let (first, _) = keys[..idx]
+                    .iter()
+                    .find(|(_, first_name)| first_name == name)?;
+
+                let result = self
+                    .create_result(Severity::Warning, format!("duplicate hash key '{name}'"))
+                    .primary_note(
+                        duplicate.syntax().text_range_trimmed_trivia(),
+                        "the value of this key overwrites the earlier one",
+                    )
+                    .secondary_note(
+                        first.syntax().text_range_trimmed_trivia(),
+                        "because it is already defined here",
+                    );
+                Some(result)
+            })
+            .collect();
+
+        if results.is_empty() {
+            None
+        } else {
+            Some(results)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::test_rule;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-duplicate-hash-key",
+            r#"{% set a = { name: 1, 'name': 2, 3: 'c', '3': 'd', "name": 3 } %}"#,
+            expect![[r#"
+                warning[twig-duplicate-hash-key]: duplicate hash key 'name'
+                  ┌─ ./debug-rule.html.twig:1:23
+                  │
+                1 │ {% set a = { name: 1, 'name': 2, 3: 'c', '3': 'd', "name": 3 } %}
+                  │              ----     ^^^^^^ the value of this key overwrites the earlier one
+                  │              │         
+                  │              because it is already defined here
+
+                warning[twig-duplicate-hash-key]: duplicate hash key '3'
+                  ┌─ ./debug-rule.html.twig:1:42
+                  │
+                1 │ {% set a = { name: 1, 'name': 2, 3: 'c', '3': 'd', "name": 3 } %}
+                  │                                  -       ^^^ the value of this key overwrites the earlier one
+                  │                                  │        
+                  │                                  because it is already defined here
+
+                warning[twig-duplicate-hash-key]: duplicate hash key 'name'
+                  ┌─ ./debug-rule.html.twig:1:52
+                  │
+                1 │ {% set a = { name: 1, 'name': 2, 3: 'c', '3': 'd', "name": 3 } %}
+                  │              ----                                  ^^^^^^ the value of this key overwrites the earlier one
+                  │              │                                      
+                  │              because it is already defined here
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "twig-duplicate-hash-key",
+            r#"{% set a = { name: 1, names: 2, (name): 3, nested: { name: 4 } } %}{{ {}|merge({ a, b }) }}"#,
+            expect![[r#""#]],
+        );
+    }
+}
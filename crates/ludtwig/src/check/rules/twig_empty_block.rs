@@ -0,0 +1,97 @@
+use ludtwig_parser::syntax::typed::{AstNode, TwigBlock};
+use ludtwig_parser::syntax::untyped::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxNodeExt};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleTwigEmptyBlock;
+
+impl Rule for RuleTwigEmptyBlock {
+    fn name(&self) -> &'static str {
+        "twig-empty-block"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Empty twig blocks remove the content of the parent block, which is easy to mistake for a leftover. A comment inside of the block documents that it is intentionally empty.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let block = TwigBlock::cast(node)?;
+        let body = block.body()?;
+        let allow_comments = ctx.config().rules.allow_commented_empty_blocks;
+
+        let is_empty = body
+            .syntax()
+            .children_with_tokens()
+            .all(|child| match child {
+                SyntaxElement::Token(t) => t.kind().is_trivia(),
+                SyntaxElement::Node(n) => !allow_comments && n.kind() == SyntaxKind::TWIG_COMMENT,
+            });
+        if !is_empty {
+            return None;
+        }
+
+        let message = if allow_comments {
+            "this block has no content, add a comment like {# intentionally empty #} inside if it should remove the parent content"
+        } else {
+            "this block has no content"
+        };
+        let result = self
+            .create_result(Severity::Help, "Empty twig block")
+            .primary_note(
+                block.starting_block()?.syntax().text_range_trimmed_trivia(),
+                message,
+            );
+
+        Some(vec![result])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_with_config};
+    use crate::config::Config;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-empty-block",
+            "{% block a %}{% endblock %}\n{% block b %}\n    \n{% endblock %}\n{% block c %}{# intentionally empty #}{% endblock %}\n{% block d %}d{% endblock %}",
+            expect![[r#"
+                help[twig-empty-block]: Empty twig block
+                  ┌─ ./debug-rule.html.twig:1:1
+                  │
+                1 │ {% block a %}{% endblock %}
+                  │ ^^^^^^^^^^^^^ this block has no content, add a comment like {# intentionally empty #} inside if it should remove the parent content
+
+                help[twig-empty-block]: Empty twig block
+                  ┌─ ./debug-rule.html.twig:2:1
+                  │
+                2 │ {% block b %}
+                  │ ^^^^^^^^^^^^^ this block has no content, add a comment like {# intentionally empty #} inside if it should remove the parent content
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_reports_commented_blocks() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.allow_commented_empty_blocks = false;
+
+        test_rule_with_config(
+            "twig-empty-block",
+            "{% block a %}{# intentionally empty #}{% endblock %}\n{% block b %}{# b #}b{% endblock %}",
+            config,
+            expect![[r#"
+                help[twig-empty-block]: Empty twig block
+                  ┌─ ./debug-rule.html.twig:1:1
+                  │
+                1 │ {% block a %}{# intentionally empty #}{% endblock %}
+                  │ ^^^^^^^^^^^^^ this block has no content
+
+            "#]],
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode, SyntaxToken, TextRange};
+use ludtwig_parser::T;
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleTwigExpressionSpacing;
+
+impl Rule for RuleTwigExpressionSpacing {
+    fn name(&self) -> &'static str {
+        "twig-expression-spacing"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Consistent spacing around operators, after commas and inside of hashes makes twig expressions easier to read, like `{{ { a: 1, b: 2 }|merge(c) }}`.")
+    }
+
+    fn check_token(&self, token: SyntaxToken, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let parent = token.parent()?;
+        let (message, note, before, after) = match (token.kind(), parent.kind()) {
+            // the range operator is written without spaces like `1..10`
+            (T![".."], SyntaxKind::TWIG_BINARY_EXPRESSION) => return None,
+            (kind, SyntaxKind::TWIG_BINARY_EXPRESSION) if !kind.is_trivia() => (
+                "Wrong spacing around operator",
+                "expected 1 space before and after this",
+                Some(true),
+                Some(true),
+            ),
+            (
+                T![","],
+                SyntaxKind::TWIG_ARGUMENTS
+                | SyntaxKind::TWIG_LITERAL_ARRAY_INNER
+                | SyntaxKind::TWIG_LITERAL_HASH_ITEMS,
+            ) => (
+                "Wrong spacing around comma",
+                "expected no space before and 1 space after this",
+                Some(false),
+                Some(true),
+            ),
+            (T![":"], SyntaxKind::TWIG_LITERAL_HASH_PAIR) => (
+                "Wrong spacing around colon in hash",
+                "expected no space before and 1 space after this",
+                Some(false),
+                Some(true),
+            ),
+            (T!["{"], SyntaxKind::TWIG_LITERAL_HASH) => (
+                "Wrong spacing inside of hash",
+                "expected 1 space after this",
+                None,
+                Some(true),
+            ),
+            (T!["}"], SyntaxKind::TWIG_LITERAL_HASH) => (
+                "Wrong spacing inside of hash",
+                "expected 1 space before this",
+                Some(true),
+                None,
+            ),
+            _ => return None,
+        };
+
+        let root = token.parent_ancestors().last()?;
+        let suggestions: Vec<(TextRange, &str)> = [(before, false), (after, true)]
+            .into_iter()
+            .filter_map(|(space, forward)| wrong_spacing(&root, &token, forward, space?))
+            .collect();
+        if suggestions.is_empty() {
+            return None;
+        }
+
+        let result = suggestions.into_iter().fold(
+            self.create_result(Severity::Help, message)
+                .primary_note(token.text_range(), note),
+            |result, (range, replacement)| {
+                let suggestion_message = if replacement.is_empty() {
+                    "Remove the whitespace"
+                } else {
+                    "Use 1 space"
+                };
+                result.suggestion(range, replacement, suggestion_message)
+            },
+        );
+
+        Some(vec![result])
+    }
+}
+
+/// The range and replacement for the whitespace in front of (or after) the token,
+/// if it doesn't match the expected single space (`space`) or no whitespace at all.
+/// Whitespace next to line breaks is left to the `indentation` and `no-trailing-whitespace` rules.
+fn wrong_spacing(
+    root: &SyntaxNode,
+    token: &SyntaxToken,
+    forward: bool,
+    space: bool,
+) -> Option<(TextRange, &'static str)> {
+    let replacement = if space { " " } else { "" };
+    let next = neighbour(root, token, forward)?;
+    let next_non_whitespace = match next.kind() {
+        T![lb] => return None,
+        T![ws] => neighbour(root, &next, forward),
+        _ => Some(next.clone()),
+    };
+
+    match next_non_whitespace.as_ref().map(SyntaxToken::kind) {
+        None | Some(T![lb]) => return None,
+        // empty hashes like `{}` and trailing commas like `[1, 2,]` don't need a space
+        Some(T!["}"]) if token.kind() == T!["{"] => return None,
+        Some(T!["{"]) if token.kind() == T!["}"] => return None,
+        Some(T!["]"] | T!["}"] | T![")"]) if token.kind() == T![","] && forward => return None,
+        // empty items like `(a,, b)` are broken syntax, where the spacing can't be fixed
+        Some(T![","]) if token.kind() == T![","] => return None,
+        _ => {}
+    }
+    if next_non_whitespace
+        .and_then(|t| t.parent())
+        .is_some_and(|parent| parent.kind() == SyntaxKind::ERROR)
+    {
+        return None;
+    }
+
+    if next.kind() == T![ws] {
+        if next.text() == replacement {
+            return None;
+        }
+        Some((next.text_range(), replacement))
+    } else if space {
+        let position = if forward {
+            token.text_range().end()
+        } else {
+            token.text_range().start()
+        };
+        Some((TextRange::empty(position), replacement))
+    } else {
+        None
+    }
+}
+
+/// The next (or previous) token in the tree.
+/// `next_token` / `prev_token` stop at empty nodes, so the root is searched instead.
+fn neighbour(root: &SyntaxNode, token: &SyntaxToken, forward: bool) -> Option<SyntaxToken> {
+    let range = token.text_range();
+    if forward {
+        if range.end() >= root.text_range().end() {
+            return None;
+        }
+        root.token_at_offset(range.end()).right_biased()
+    } else {
+        if range.start() == 0.into() {
+            return None;
+        }
+        root.token_at_offset(range.start()).left_biased()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_fix};
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-expression-spacing",
+            "{{ {a:1,b :2} }}{{ a+b }}",
+            expect![[r#"
+                help[twig-expression-spacing]: Wrong spacing inside of hash
+                  ┌─ ./debug-rule.html.twig:1:4
+                  │
+                1 │ {{ {a:1,b :2} }}{{ a+b }}
+                  │    ^- Use 1 space:  
+                  │    │ 
+                  │    expected 1 space after this
+
+                help[twig-expression-spacing]: Wrong spacing around colon in hash
+                  ┌─ ./debug-rule.html.twig:1:6
+                  │
+                1 │ {{ {a:1,b :2} }}{{ a+b }}
+                  │      ^- Use 1 space:  
+                  │      │ 
+                  │      expected no space before and 1 space after this
+
+                help[twig-expression-spacing]: Wrong spacing around comma
+                  ┌─ ./debug-rule.html.twig:1:8
+                  │
+                1 │ {{ {a:1,b :2} }}{{ a+b }}
+                  │        ^- Use 1 space:  
+                  │        │ 
+                  │        expected no space before and 1 space after this
+
+                help[twig-expression-spacing]: Wrong spacing around colon in hash
+                  ┌─ ./debug-rule.html.twig:1:11
+                  │
+                1 │ {{ {a:1,b :2} }}{{ a+b }}
+                  │          -^- Use 1 space:  
+                  │          ││ 
+                  │          │expected no space before and 1 space after this
+                  │          Remove the whitespace: 
+
+                help[twig-expression-spacing]: Wrong spacing inside of hash
+                  ┌─ ./debug-rule.html.twig:1:13
+                  │
+                1 │ {{ {a:1,b :2} }}{{ a+b }}
+                  │             ^
+                  │             │
+                  │             expected 1 space before this
+                  │             Use 1 space:  
+
+                help[twig-expression-spacing]: Wrong spacing around operator
+                  ┌─ ./debug-rule.html.twig:1:21
+                  │
+                1 │ {{ {a:1,b :2} }}{{ a+b }}
+                  │                     ^- Use 1 space:  
+                  │                     │ 
+                  │                     expected 1 space before and after this
+                  │                     Use 1 space:  
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "twig-expression-spacing",
+            "{{ { a: 1, b: 2 } }}{{ {} }}{{ a + b ~ 'c' }}{{ f(a, b)|default(1, 2) }}{{ [1, 2,] }}{{ 1..10 }}{{ a is not same as(b) }}{{ a not in b }}{{ x ? y : z }}{{ -a }}{{ a[1:2] }}{% for k,v in x %}{% endfor %}\n{{ {\n    a: 1,\n    b: 2\n} }}\n{{ a\n    + b }}",
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_broken_syntax() {
+        test_rule(
+            "twig-expression-spacing",
+            "{% macro m(a,, b) %}{% endmacro %}{{ f(a,%} b) }}",
+            expect![[r#"
+                error[SyntaxError]: The parser encountered a syntax error
+                  ┌─ ./debug-rule.html.twig:1:42
+                  │
+                1 │ {% macro m(a,, b) %}{% endmacro %}{{ f(a,%} b) }}
+                  │                                          ^^ expected , or ) or }} but found %}
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes() {
+        test_rule_fix(
+            "twig-expression-spacing",
+            "{{ {a:1,b :2} }}{{ a+b*  c }}{{ f(a ,b)|default([1,2]) }}{% set x = {'a':{b:c}|merge(d)} %}",
+            expect!["{{ { a: 1, b: 2 } }}{{ a + b * c }}{{ f(a, b)|default([1, 2]) }}{% set x = { 'a': { b: c }|merge(d) } %}"],
+        );
+    }
+}
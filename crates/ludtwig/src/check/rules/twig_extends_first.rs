@@ -0,0 +1,95 @@
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode, SyntaxNodeExt};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleTwigExtendsFirst;
+
+impl Rule for RuleTwigExtendsFirst {
+    fn name(&self) -> &'static str {
+        "twig-extends-first"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("The extends tag defines how the whole template renders, so it should be the first thing in it.")
+    }
+
+    fn check_root(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let extends = node.children().find(|c| {
+            matches!(
+                c.kind(),
+                SyntaxKind::TWIG_EXTENDS | SyntaxKind::SHOPWARE_TWIG_SW_EXTENDS
+            )
+        })?;
+
+        let preceding = node.children().take_while(|c| c != &extends).find(|c| {
+            !matches!(
+                c.kind(),
+                SyntaxKind::TWIG_COMMENT
+                    | SyntaxKind::LUDTWIG_DIRECTIVE_FILE_IGNORE
+                    | SyntaxKind::LUDTWIG_DIRECTIVE_IGNORE
+            )
+        })?;
+
+        let result = self
+            .create_result(
+                Severity::Error,
+                "extends must be the first statement of the template",
+            )
+            .primary_note(
+                extends.text_range_trimmed_trivia(),
+                "move this to the top of the template",
+            )
+            .secondary_note(
+                preceding.text_range_trimmed_trivia(),
+                "only comments are allowed before it",
+            );
+
+        Some(vec![result])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::test_rule;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-extends-first",
+            r#"{# comment #}
+{% set a = 1 %}
+<!-- b -->
+{% sw_extends '@Storefront/base.html.twig' %}"#,
+            expect![[r#"
+                error[twig-extends-first]: extends must be the first statement of the template
+                  ┌─ ./debug-rule.html.twig:4:1
+                  │
+                2 │ {% set a = 1 %}
+                  │ --------------- only comments are allowed before it
+                3 │ <!-- b -->
+                4 │ {% sw_extends '@Storefront/base.html.twig' %}
+                  │ ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ move this to the top of the template
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "twig-extends-first",
+            r#"{# ludtwig-ignore-file twig-content-outside-block #}
+{# comment #}
+{% extends 'base.html.twig' %}
+{% set a = 1 %}"#,
+            expect![[r#""#]],
+        );
+        test_rule(
+            "twig-extends-first",
+            "<div>{% block a %}{% endblock %}</div>",
+            expect![[r#""#]],
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use ludtwig_parser::syntax::typed::{walk, AstNode, TwigFunctionCall, VisitControl, Visitor};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode, TextRange};
+
+use crate::check::is_ignored_by_directive;
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+/// Form helpers which render (parts of) the form and must come before `form_end`
+static FORM_RENDER_FUNCTIONS: &[&str] = &["form_widget", "form_row", "form_rest"];
+
+pub struct RuleTwigFormHelperPairs;
+
+impl Rule for RuleTwigFormHelperPairs {
+    fn name(&self) -> &'static str {
+        "twig-form-helper-pairs"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Forms rendered with form_start() need a form_end(), which also renders the remaining fields.")
+    }
+
+    fn check_root(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let calls = self.collect_form_calls(&node);
+        let mut results = vec![];
+
+        for (index, call) in calls.iter().enumerate() {
+            let previous_calls = &calls[..index];
+            let following_calls = &calls[index + 1..];
+
+            match call.function.as_str() {
+                "form_start" => {
+                    let has_end = following_calls
+                        .iter()
+                        .any(|c| c.function == "form_end" && c.form == call.form);
+                    if !has_end {
+                        results.push(
+                            self.create_result(
+                                Severity::Warning,
+                                format!("form_start({}) without form_end", call.form),
+                            )
+                            .primary_note(
+                                call.range,
+                                format!("add a matching form_end({}) in this template", call.form),
+                            ),
+                        );
+                    }
+                }
+                function if FORM_RENDER_FUNCTIONS.contains(&function) => {
+                    let after_end = previous_calls.iter().any(|c| {
+                        c.function == "form_end" && (c.form == call.form || is_child(call, c))
+                    });
+                    if after_end {
+                        results.push(
+                            self.create_result(
+                                Severity::Warning,
+                                format!("{function}({}) after form_end", call.form),
+                            )
+                            .primary_note(
+                                call.range,
+                                "the form was already ended, move this before form_end",
+                            ),
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Some(results)
+    }
+}
+
+impl RuleTwigFormHelperPairs {
+    /// Collect all `form_*` function calls in document order while respecting
+    /// error nodes and ludtwig-ignore directives.
+    fn collect_form_calls(&self, root: &SyntaxNode) -> Vec<FormCall> {
+        let mut collector = FormCallCollector {
+            rule_name: self.name(),
+            calls: vec![],
+        };
+        walk(root, &mut collector);
+
+        collector.calls
+    }
+}
+
+struct FormCallCollector {
+    rule_name: &'static str,
+    calls: Vec<FormCall>,
+}
+
+impl Visitor for FormCallCollector {
+    fn enter_node(&mut self, node: &SyntaxNode) -> VisitControl {
+        if node.kind() == SyntaxKind::ERROR {
+            return VisitControl::SkipChildren;
+        }
+
+        if is_ignored_by_directive(node, self.rule_name) {
+            return VisitControl::SkipChildren;
+        }
+
+        VisitControl::Continue
+    }
+
+    fn visit_twig_function_call(&mut self, call: &TwigFunctionCall) {
+        let Some(name) = call.name() else {
+            return;
+        };
+        let function = name.text().to_string();
+        if !function.starts_with("form_") {
+            return;
+        }
+        let Some(form) = call
+            .arguments()
+            .and_then(|a| a.positional().next())
+            .map(|e| e.syntax().text().to_string().trim().to_owned())
+        else {
+            return;
+        };
+
+        self.calls.push(FormCall {
+            function,
+            form,
+            range: TextRange::new(name.text_range().start(), call.syntax().text_range().end()),
+        });
+    }
+}
+
+struct FormCall {
+    function: String,
+    /// The source text of the form argument, like `form` or `form.child`
+    form: String,
+    range: TextRange,
+}
+
+/// Is the call rendering a child of the form which was used by the other call (like `form.email` of `form`)
+fn is_child(call: &FormCall, other: &FormCall) -> bool {
+    call.form
+        .strip_prefix(&other.form)
+        .is_some_and(|rest| rest.starts_with('.') || rest.starts_with('['))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check::rules::test::test_rule;
+    use expect_test::expect;
+
+    #[test]
+    fn rule_reports_missing_form_end() {
+        test_rule(
+            "twig-form-helper-pairs",
+            "{{ form_start(form) }}
+    {{ form_widget(form) }}
+{{ form_end(otherForm) }}",
+            expect![[r#"
+                warning[twig-form-helper-pairs]: form_start(form) without form_end
+                  ┌─ ./debug-rule.html.twig:1:4
+                  │
+                1 │ {{ form_start(form) }}
+                  │    ^^^^^^^^^^^^^^^^ add a matching form_end(form) in this template
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_reports_widget_after_form_end() {
+        test_rule(
+            "twig-form-helper-pairs",
+            "{{ form_start(form) }}
+    {{ form_row(form.email) }}
+{{ form_end(form) }}
+{{ form_widget(form.password) }}
+{{ form_rest(form) }}",
+            expect![[r#"
+                warning[twig-form-helper-pairs]: form_widget(form.password) after form_end
+                  ┌─ ./debug-rule.html.twig:4:4
+                  │
+                4 │ {{ form_widget(form.password) }}
+                  │    ^^^^^^^^^^^^^^^^^^^^^^^^^^ the form was already ended, move this before form_end
+
+                warning[twig-form-helper-pairs]: form_rest(form) after form_end
+                  ┌─ ./debug-rule.html.twig:5:4
+                  │
+                5 │ {{ form_rest(form) }}
+                  │    ^^^^^^^^^^^^^^^ the form was already ended, move this before form_end
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "twig-form-helper-pairs",
+            "{{ form_start(form, { attr: { novalidate: true } }) }}
+    {{ form_errors(form) }}
+    {{ form_row(form.email) }}
+    {{ form_widget(form) }}
+{{ form_end(form) }}
+{{ form_widget(otherForm) }}
+{# ludtwig-ignore twig-form-helper-pairs #}
+{{ form_start(ignoredForm) }}",
+            expect![[r#""#]],
+        );
+    }
+}
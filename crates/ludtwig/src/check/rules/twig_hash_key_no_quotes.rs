@@ -1,5 +1,6 @@
+use ludtwig_parser::syntax::editor::SyntaxEditor;
 use ludtwig_parser::syntax::typed::{support, AstNode, TwigLiteralHashKey, TwigLiteralString};
-use ludtwig_parser::syntax::untyped::{SyntaxNode, SyntaxNodeExt, TextRange, TextSize};
+use ludtwig_parser::syntax::untyped::{SyntaxNode, SyntaxNodeExt};
 use ludtwig_parser::TWIG_NAME_REGEX;
 
 use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
@@ -11,6 +12,10 @@ impl Rule for RuleTwigHashKeyNoQuotes {
         "twig-hash-key-no-quotes"
     }
 
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Hash keys which are valid names don't need quotes.")
+    }
+
     fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
         let hash_key = TwigLiteralHashKey::cast(node)?;
         let key_string_literal: TwigLiteralString = support::child(hash_key.syntax())?;
@@ -23,38 +28,21 @@ impl Rule for RuleTwigHashKeyNoQuotes {
             TWIG_NAME_REGEX.is_match(&key_string_inner.syntax().text().to_string());
 
         if key_only_contains_one_element && key_string_matches_twig_word_regex {
+            let mut editor = SyntaxEditor::new(hash_key.syntax().ancestors().last()?);
+            if let Some(quote) = key_string_literal.get_opening_quote() {
+                editor.delete(quote);
+            }
+            if let Some(quote) = key_string_literal.get_closing_quote() {
+                editor.delete(quote);
+            }
+
             let result = self
                 .create_result(Severity::Help, "unnecessary quotation")
                 .primary_note(
                     key_string_literal.syntax().text_range_trimmed_trivia(),
                     "help: remove quotation",
                 )
-                .suggestion(
-                    key_string_literal.get_opening_quote().map_or_else(
-                        || {
-                            TextRange::at(
-                                key_string_inner.syntax().text_range().start(),
-                                TextSize::from(0),
-                            )
-                        },
-                        |q| q.text_range(),
-                    ),
-                    "",
-                    "remove this quote",
-                )
-                .suggestion(
-                    key_string_literal.get_closing_quote().map_or_else(
-                        || {
-                            TextRange::at(
-                                key_string_inner.syntax().text_range().end(),
-                                TextSize::from(0),
-                            )
-                        },
-                        |q| q.text_range(),
-                    ),
-                    "",
-                    "remove this quote",
-                );
+                .edit_suggestions(&editor.finish(), "remove this quote");
 
             return Some(vec![result]);
         }
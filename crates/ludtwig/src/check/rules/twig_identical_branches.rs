@@ -0,0 +1,170 @@
+use ludtwig_parser::syntax::untyped::{
+    SyntaxElement, SyntaxKind, SyntaxNode, SyntaxNodeExt, SyntaxToken, TextRange,
+};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleTwigIdenticalBranches;
+
+impl Rule for RuleTwigIdenticalBranches {
+    fn name(&self) -> &'static str {
+        "twig-identical-branches"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Conditions with identical branches have no effect and most likely contain a copy-paste error.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let branches = match node.kind() {
+            SyntaxKind::TWIG_IF => if_branches(&node)?,
+            SyntaxKind::TWIG_CONDITIONAL_EXPRESSION => ternary_branches(&node)?,
+            _ => return None,
+        };
+
+        let (first, others) = branches.split_first()?;
+        let first_content = token_content(first.1.as_ref());
+        if !others
+            .iter()
+            .all(|other| token_content(other.1.as_ref()) == first_content)
+        {
+            return None;
+        }
+
+        let result = others.iter().fold(
+            self.create_result(
+                Severity::Warning,
+                "all branches are identical, so the condition has no effect",
+            )
+            .primary_note(first.0, "this branch"),
+            |result, other| result.secondary_note(other.0, "is identical to this branch"),
+        );
+
+        Some(vec![result])
+    }
+}
+
+/// Highlight range and content of a branch
+type Branch = (TextRange, Option<SyntaxNode>);
+
+/// Branches of an if tag, only if it has an else block (otherwise nothing is rendered in one case)
+fn if_branches(node: &SyntaxNode) -> Option<Vec<Branch>> {
+    let mut branches: Vec<Branch> = vec![];
+    let mut has_else = false;
+
+    for child in node.children() {
+        match child.kind() {
+            SyntaxKind::TWIG_IF_BLOCK | SyntaxKind::TWIG_ELSE_IF_BLOCK => {
+                branches.push((child.text_range_trimmed_trivia(), None));
+            }
+            SyntaxKind::TWIG_ELSE_BLOCK => {
+                has_else = true;
+                branches.push((child.text_range_trimmed_trivia(), None));
+            }
+            SyntaxKind::BODY => {
+                let branch = branches.last_mut()?;
+                // point at the body instead of the tag if there is any content
+                if non_trivia_tokens(&child).next().is_some() {
+                    branch.0 = child.text_range_trimmed_trivia();
+                }
+                branch.1 = Some(child);
+            }
+            _ => {}
+        }
+    }
+
+    has_else.then_some(branches)
+}
+
+/// Both branches of a ternary like `a ? b : c` (the shorthand `a ?: b` only has one)
+fn ternary_branches(node: &SyntaxNode) -> Option<Vec<Branch>> {
+    let expressions: Vec<SyntaxNode> = node
+        .children()
+        .filter(|c| c.kind() == SyntaxKind::TWIG_EXPRESSION)
+        .collect();
+    let [_, truthy, falsy] = expressions.as_slice() else {
+        return None;
+    };
+
+    Some(vec![
+        (truthy.text_range_trimmed_trivia(), Some(truthy.clone())),
+        (falsy.text_range_trimmed_trivia(), Some(falsy.clone())),
+    ])
+}
+
+fn non_trivia_tokens(node: &SyntaxNode) -> impl Iterator<Item = SyntaxToken> {
+    node.descendants_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .filter(|t| !t.kind().is_trivia())
+}
+
+/// Content of a branch without any whitespace, so it can be compared
+fn token_content(node: Option<&SyntaxNode>) -> Vec<(SyntaxKind, String)> {
+    node.into_iter()
+        .flat_map(non_trivia_tokens)
+        .map(|t| (t.kind(), t.text().to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::test_rule;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-identical-branches",
+            r#"{% if c %}
+    <p>{{ a }}</p>
+{% else %}
+    <p>{{a}}</p>
+{% endif %}
+{{ c ? 'yes' : 'yes' }}
+{% if c %}{% elseif d %}{% else %}{% endif %}"#,
+            expect![[r#"
+                warning[twig-identical-branches]: all branches are identical, so the condition has no effect
+                  ┌─ ./debug-rule.html.twig:2:5
+                  │
+                2 │     <p>{{ a }}</p>
+                  │     ^^^^^^^^^^^^^^ this branch
+                3 │ {% else %}
+                4 │     <p>{{a}}</p>
+                  │     ------------ is identical to this branch
+
+                warning[twig-identical-branches]: all branches are identical, so the condition has no effect
+                  ┌─ ./debug-rule.html.twig:6:8
+                  │
+                6 │ {{ c ? 'yes' : 'yes' }}
+                  │        ^^^^^   ----- is identical to this branch
+                  │        │        
+                  │        this branch
+
+                warning[twig-identical-branches]: all branches are identical, so the condition has no effect
+                  ┌─ ./debug-rule.html.twig:7:1
+                  │
+                7 │ {% if c %}{% elseif d %}{% else %}{% endif %}
+                  │ ^^^^^^^^^^------------------------ is identical to this branch
+                  │ │         │              
+                  │ │         is identical to this branch
+                  │ this branch
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "twig-identical-branches",
+            r#"{% if c %}<p>a</p>{% else %}<p>b</p>{% endif %}
+{% if c %}a{% elseif d %}a{% endif %}
+{% if c %}a{% elseif d %}a{% else %}b{% endif %}
+{{ c ? 'yes' : 'no' }}
+{{ c ?: c }}
+{{ c ? c }}"#,
+            expect![[r#""#]],
+        );
+    }
+}
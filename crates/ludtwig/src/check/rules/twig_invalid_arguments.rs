@@ -0,0 +1,243 @@
+use ludtwig_parser::syntax::typed::{AstNode, TwigArguments, TwigFilter, TwigFunctionCall};
+use ludtwig_parser::syntax::untyped::{SyntaxNode, SyntaxNodeExt, SyntaxToken};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::signatures::{did_you_mean, get_signatures, Signature};
+
+pub struct RuleTwigInvalidArguments;
+
+impl Rule for RuleTwigInvalidArguments {
+    fn name(&self) -> &'static str {
+        "twig-invalid-arguments"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Twig throws an exception when the template is compiled, if a filter or function is called with too many arguments, without a required argument or with an unknown named argument.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let signatures = get_signatures(ctx.config()).ok()?;
+
+        let (kind, name, arguments, signature) =
+            if let Some(filter) = TwigFilter::cast(node.clone()) {
+                let name = filter.name()?;
+                let signature = signatures.filters.get(name.text())?;
+                ("filter", name, filter.arguments(), signature)
+            } else {
+                let call = TwigFunctionCall::cast(node)?;
+                let name = call.name()?;
+                let signature = signatures.functions.get(name.text())?;
+                ("function", name, call.arguments(), signature)
+            };
+        if signature.variadic {
+            return None;
+        }
+
+        let results = self.check_arguments(kind, &name, arguments.as_ref(), signature);
+        if results.is_empty() {
+            None
+        } else {
+            Some(results)
+        }
+    }
+}
+
+impl RuleTwigInvalidArguments {
+    fn check_arguments(
+        &self,
+        kind: &str,
+        name: &SyntaxToken,
+        arguments: Option<&TwigArguments>,
+        signature: &Signature,
+    ) -> Vec<CheckResult> {
+        let callee = format!("{kind} '{}'", name.text());
+        let mut results = vec![];
+
+        let positional: Vec<_> = arguments
+            .map(|a| a.positional().collect())
+            .unwrap_or_default();
+        if let Some(extra) = positional.get(signature.arguments.len()) {
+            let first_range = extra.syntax().text_range_trimmed_trivia();
+            let last_range = positional
+                .last()
+                .unwrap()
+                .syntax()
+                .text_range_trimmed_trivia();
+            results.push(
+                self.create_result(Severity::Error, format!("too many arguments for {callee}"))
+                    .primary_note(
+                        first_range.cover(last_range),
+                        format!(
+                            "the {kind} only accepts {}",
+                            plural(signature.arguments.len(), "argument")
+                        ),
+                    ),
+            );
+        }
+
+        let mut given: Vec<&str> = signature
+            .arguments
+            .iter()
+            .take(positional.len())
+            .map(|a| a.name.as_str())
+            .collect();
+        for named in arguments.into_iter().flat_map(TwigArguments::named) {
+            let Some(argument_name) = named.name() else {
+                continue;
+            };
+            let known = signature
+                .arguments
+                .iter()
+                .find(|a| normalize_name(&a.name) == normalize_name(argument_name.text()));
+
+            match known {
+                Some(known) if given.contains(&known.name.as_str()) => {
+                    results.push(
+                        self.create_result(
+                            Severity::Error,
+                            format!("argument '{}' of {callee} is given twice", known.name),
+                        )
+                        .primary_note(
+                            named.syntax().text_range_trimmed_trivia(),
+                            "the argument is already given in front of this",
+                        ),
+                    );
+                }
+                Some(known) => given.push(&known.name),
+                None => {
+                    let mut result = self
+                        .create_result(
+                            Severity::Error,
+                            format!("unknown argument '{}' for {callee}", argument_name.text()),
+                        )
+                        .primary_note(
+                            argument_name.text_range(),
+                            format!("the {kind} doesn't have an argument with this name"),
+                        );
+
+                    let known_names = signature.arguments.iter().map(|a| a.name.as_str());
+                    if let Some(similar) = did_you_mean(argument_name.text(), known_names) {
+                        result = result.suggestion(
+                            argument_name.text_range(),
+                            similar,
+                            format!("did you mean '{similar}'?"),
+                        );
+                    }
+                    results.push(result);
+                }
+            }
+        }
+
+        let missing: Vec<&str> = signature
+            .arguments
+            .iter()
+            .filter(|a| !a.optional && !given.contains(&a.name.as_str()))
+            .map(|a| a.name.as_str())
+            .collect();
+        if !missing.is_empty() {
+            results.push(
+                self.create_result(
+                    Severity::Error,
+                    format!("missing argument '{}' for {callee}", missing.join("', '")),
+                )
+                .primary_note(
+                    name.text_range(),
+                    format!(
+                        "the {kind} requires {}",
+                        plural(missing.len(), "more argument")
+                    ),
+                ),
+            );
+        }
+
+        results
+    }
+}
+
+/// Twig converts named arguments like `ignoreMissing` to snake case before matching them
+fn normalize_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    for (idx, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() && idx > 0 {
+            normalized.push('_');
+        }
+        normalized.push(c.to_ascii_lowercase());
+    }
+    normalized
+}
+
+fn plural(count: usize, word: &str) -> String {
+    if count == 1 {
+        format!("1 {word}")
+    } else {
+        format!("{count} {word}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_fix};
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-invalid-arguments",
+            "{{ a|slice() }}{{ include('a.html.twig', ingore_missing = true) }}{{ a|date('d', 'UTC', 1, 2) }}{{ path(parameters = {}) }}{{ a|slice(1, start = 2) }}",
+            expect![[r#"
+                error[twig-invalid-arguments]: missing argument 'start' for filter 'slice'
+                  ┌─ ./debug-rule.html.twig:1:6
+                  │
+                1 │ {{ a|slice() }}{{ include('a.html.twig', ingore_missing = true) }}{{ a|date('d', 'UTC', 1, 2) }}{{ path(parameters = {}) }}{{ a|slice(1, start = 2) }}
+                  │      ^^^^^ the filter requires 1 more argument
+
+                error[twig-invalid-arguments]: unknown argument 'ingore_missing' for function 'include'
+                  ┌─ ./debug-rule.html.twig:1:42
+                  │
+                1 │ {{ a|slice() }}{{ include('a.html.twig', ingore_missing = true) }}{{ a|date('d', 'UTC', 1, 2) }}{{ path(parameters = {}) }}{{ a|slice(1, start = 2) }}
+                  │                                          ^^^^^^^^^^^^^^
+                  │                                          │
+                  │                                          the function doesn't have an argument with this name
+                  │                                          did you mean 'ignore_missing'?: ignore_missing
+
+                error[twig-invalid-arguments]: too many arguments for filter 'date'
+                  ┌─ ./debug-rule.html.twig:1:89
+                  │
+                1 │ {{ a|slice() }}{{ include('a.html.twig', ingore_missing = true) }}{{ a|date('d', 'UTC', 1, 2) }}{{ path(parameters = {}) }}{{ a|slice(1, start = 2) }}
+                  │                                                                                         ^^^^ the filter only accepts 2 arguments
+
+                error[twig-invalid-arguments]: missing argument 'name' for function 'path'
+                  ┌─ ./debug-rule.html.twig:1:100
+                  │
+                1 │ {{ a|slice() }}{{ include('a.html.twig', ingore_missing = true) }}{{ a|date('d', 'UTC', 1, 2) }}{{ path(parameters = {}) }}{{ a|slice(1, start = 2) }}
+                  │                                                                                                    ^^^^ the function requires 1 more argument
+
+                error[twig-invalid-arguments]: argument 'start' of filter 'slice' is given twice
+                  ┌─ ./debug-rule.html.twig:1:138
+                  │
+                1 │ {{ a|slice() }}{{ include('a.html.twig', ingore_missing = true) }}{{ a|date('d', 'UTC', 1, 2) }}{{ path(parameters = {}) }}{{ a|slice(1, start = 2) }}
+                  │                                                                                                                                          ^^^^^^^^^ the argument is already given in front of this
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "twig-invalid-arguments",
+            "{{ a|slice(1) }}{{ a|slice(start = 1, preserve_keys = true) }}{{ include('a.html.twig', ignoreMissing = true) }}{{ a|date }}{{ max(1, 2, 3) }}{{ a|unknown(1, 2) }}{{ foo.bar(1, 2) }}",
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes() {
+        test_rule_fix(
+            "twig-invalid-arguments",
+            "{{ include('a.html.twig', ingore_missing = true) }}",
+            expect!["{{ include('a.html.twig', ignore_missing = true) }}"],
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxToken};
+use ludtwig_parser::T;
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleTwigLiteralLowercase;
+
+impl Rule for RuleTwigLiteralLowercase {
+    fn name(&self) -> &'static str {
+        "twig-literal-lowercase"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Twig literals like true, false and null should consistently be written in lowercase.")
+    }
+
+    fn check_token(&self, token: SyntaxToken, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        if !matches!(
+            token.kind(),
+            T!["true"] | T!["false"] | T!["null"] | T!["none"]
+        ) || !matches!(
+            token.parent()?.kind(),
+            SyntaxKind::TWIG_LITERAL_BOOLEAN | SyntaxKind::TWIG_LITERAL_NULL
+        ) {
+            return None;
+        }
+
+        let lowercase = token.text().to_ascii_lowercase();
+        if token.text() == lowercase {
+            return None;
+        }
+
+        // twig only knows the lowercase and uppercase variants, everything else is a variable name
+        let result = if token.text() == token.text().to_ascii_uppercase() {
+            self.create_result(Severity::Help, format!("uppercase '{lowercase}' literal"))
+                .primary_note(token.text_range(), "help: write this in lowercase")
+        } else {
+            self.create_result(
+                Severity::Warning,
+                format!("mixed case '{lowercase}' literal"),
+            )
+            .primary_note(
+                token.text_range(),
+                format!("twig treats this as a variable name and not as '{lowercase}'"),
+            )
+        };
+
+        Some(vec![result.suggestion(
+            token.text_range(),
+            lowercase,
+            "Try this instead",
+        )])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_fix};
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-literal-lowercase",
+            "{% if a is NULL or b == False %}{{ TRUE }}{% endif %}",
+            expect![[r#"
+                help[twig-literal-lowercase]: uppercase 'null' literal
+                  ┌─ ./debug-rule.html.twig:1:12
+                  │
+                1 │ {% if a is NULL or b == False %}{{ TRUE }}{% endif %}
+                  │            ^^^^
+                  │            │
+                  │            help: write this in lowercase
+                  │            Try this instead: null
+
+                warning[twig-literal-lowercase]: mixed case 'false' literal
+                  ┌─ ./debug-rule.html.twig:1:25
+                  │
+                1 │ {% if a is NULL or b == False %}{{ TRUE }}{% endif %}
+                  │                         ^^^^^
+                  │                         │
+                  │                         twig treats this as a variable name and not as 'false'
+                  │                         Try this instead: false
+
+                help[twig-literal-lowercase]: uppercase 'true' literal
+                  ┌─ ./debug-rule.html.twig:1:36
+                  │
+                1 │ {% if a is NULL or b == False %}{{ TRUE }}{% endif %}
+                  │                                    ^^^^
+                  │                                    │
+                  │                                    help: write this in lowercase
+                  │                                    Try this instead: true
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "twig-literal-lowercase",
+            "{% set a = { TRUE: b.NULL, c: true, d: none } %}{% autoescape false %}{% endautoescape %}\nTRUE",
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes() {
+        test_rule_fix(
+            "twig-literal-lowercase",
+            "{{ [TRUE, False, NULL, NONE, null] }}",
+            expect!["{{ [true, false, null, none, null] }}"],
+        );
+    }
+}
@@ -11,6 +11,10 @@ impl Rule for RuleTwigLogicAnd {
         "twig-logic-and"
     }
 
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Twig uses 'and' as the logical operator, '&&' is not valid twig syntax.")
+    }
+
     fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
         let binary_expr_op = TwigBinaryExpression::cast(node)?.operator()?;
 
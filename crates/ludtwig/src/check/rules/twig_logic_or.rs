@@ -11,6 +11,10 @@ impl Rule for RuleTwigLogicOr {
         "twig-logic-or"
     }
 
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Twig uses 'or' as the logical operator, '||' is not valid twig syntax.")
+    }
+
     fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
         let binary = TwigBinaryExpression::cast(node)?;
         let binary_expr_op = binary.operator()?;
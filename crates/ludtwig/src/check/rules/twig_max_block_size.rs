@@ -0,0 +1,185 @@
+use ludtwig_parser::syntax::typed::{AstNode, Body, TwigBlock};
+use ludtwig_parser::syntax::untyped::{
+    SyntaxElement, SyntaxKind, SyntaxNode, SyntaxNodeExt, SyntaxToken, WalkEvent,
+};
+use ludtwig_parser::T;
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::config::BlockSizeUnit;
+
+pub struct RuleTwigMaxBlockSize;
+
+impl Rule for RuleTwigMaxBlockSize {
+    fn name(&self) -> &'static str {
+        "twig-max-block-size"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Large blocks are hard to override, because extending templates have to copy the whole content to change a small part of it. Move parts of the content into includes or smaller blocks instead.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let block = TwigBlock::cast(node)?;
+        let body = block.body()?;
+
+        let max_size = ctx.config().rules.max_block_size;
+        let unit = ctx.config().rules.block_size_unit;
+        let size = match unit {
+            BlockSizeUnit::Lines => content_lines(&body),
+            BlockSizeUnit::Nodes => content_nodes(&body),
+        };
+        if size <= max_size {
+            return None;
+        }
+
+        let result = self
+            .create_result(
+                Severity::Warning,
+                "Block is too large, move parts of it into includes or smaller blocks",
+            )
+            .primary_note(
+                block.starting_block()?.syntax().text_range_trimmed_trivia(),
+                format!("this block has {size} {unit} of content, the maximum is {max_size}"),
+            );
+
+        Some(vec![result])
+    }
+}
+
+/// Amount of lines with content (without empty lines) in the body, where nested blocks only count as one line
+fn content_lines(body: &Body) -> usize {
+    let mut line = 0;
+    let mut counted_line = None;
+    // is the token the first content on a line?
+    let mut is_new_line = |token: &SyntaxToken| match token.kind() {
+        T![lb] => {
+            line += 1;
+            false
+        }
+        T![ws] => false,
+        _ => counted_line.replace(line) != Some(line),
+    };
+
+    let mut lines = 0;
+    let mut preorder = body.syntax().preorder_with_tokens();
+    while let Some(event) = preorder.next() {
+        match event {
+            // only the line breaks in front of a nested block and its starting line count
+            WalkEvent::Enter(SyntaxElement::Node(n)) if n.kind() == SyntaxKind::TWIG_BLOCK => {
+                preorder.skip_subtree();
+                for token in n
+                    .descendants_with_tokens()
+                    .filter_map(SyntaxElement::into_token)
+                {
+                    lines += usize::from(is_new_line(&token));
+                    if !token.kind().is_trivia() {
+                        break;
+                    }
+                }
+            }
+            WalkEvent::Enter(SyntaxElement::Token(token)) => {
+                lines += usize::from(is_new_line(&token));
+            }
+            _ => {}
+        }
+    }
+
+    lines
+}
+
+/// Amount of html elements, texts, comments, twig tags and outputs in the body,
+/// where nested blocks only count as one node
+fn content_nodes(body: &Body) -> usize {
+    let mut nodes = 0;
+    let mut preorder = body.syntax().preorder();
+    while let Some(event) = preorder.next() {
+        let WalkEvent::Enter(node) = event else {
+            continue;
+        };
+
+        if node.parent().is_some_and(|p| p.kind() == SyntaxKind::BODY) {
+            nodes += 1;
+        }
+        if node.kind() == SyntaxKind::TWIG_BLOCK {
+            preorder.skip_subtree();
+        }
+    }
+
+    nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::test_rule_with_config;
+    use crate::config::{BlockSizeUnit, Config};
+
+    fn config(max_block_size: usize, block_size_unit: BlockSizeUnit) -> Config {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.max_block_size = max_block_size;
+        config.rules.block_size_unit = block_size_unit;
+        config
+    }
+
+    const TEMPLATE: &str = r#"{% block a %}
+    <div class="a">
+        {{ b }}
+
+        {% block c %}
+            <p>d</p>
+            <p>e</p>
+        {% endblock %}
+    </div>
+{% endblock %}"#;
+
+    #[test]
+    fn rule_reports_lines() {
+        test_rule_with_config(
+            "twig-max-block-size",
+            TEMPLATE,
+            config(3, BlockSizeUnit::Lines),
+            expect![[r#"
+                warning[twig-max-block-size]: Block is too large, move parts of it into includes or smaller blocks
+                  ┌─ ./debug-rule.html.twig:1:1
+                  │
+                1 │ {% block a %}
+                  │ ^^^^^^^^^^^^^ this block has 4 lines of content, the maximum is 3
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_reports_nodes() {
+        test_rule_with_config(
+            "twig-max-block-size",
+            TEMPLATE,
+            config(3, BlockSizeUnit::Nodes),
+            expect![[r#"
+                warning[twig-max-block-size]: Block is too large, move parts of it into includes or smaller blocks
+                  ┌─ ./debug-rule.html.twig:5:9
+                  │
+                5 │         {% block c %}
+                  │         ^^^^^^^^^^^^^ this block has 4 nodes of content, the maximum is 3
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule_with_config(
+            "twig-max-block-size",
+            TEMPLATE,
+            config(4, BlockSizeUnit::Lines),
+            expect![[r#""#]],
+        );
+        test_rule_with_config(
+            "twig-max-block-size",
+            TEMPLATE,
+            config(4, BlockSizeUnit::Nodes),
+            expect![[r#""#]],
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ludtwig_parser::syntax::typed::{AstNode, TwigAutoescape};
+use ludtwig_parser::syntax::untyped::{SyntaxNode, SyntaxNodeExt, TextRange};
+use once_cell::sync::Lazy;
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::Config;
+
+pub struct RuleTwigNoAutoescapeOff;
+
+impl Rule for RuleTwigNoAutoescapeOff {
+    fn name(&self) -> &'static str {
+        "twig-no-autoescape-off"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Turning off autoescaping can render user input as HTML, which allows cross-site scripting.")
+    }
+
+    fn validate_config(&self, config: &Config) -> Result<(), String> {
+        validate_patterns(
+            "autoescape-off-allowed-files",
+            &config.rules.autoescape_off_allowed_files,
+        )
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let autoescape = TwigAutoescape::cast(node)?;
+        let starting_block = autoescape.starting_block()?;
+        let disabled_keyword = starting_block.disabled_keyword()?;
+
+        if is_allowed_file(
+            ctx.file_path(),
+            &ctx.config().rules.autoescape_off_allowed_files,
+        ) {
+            return None;
+        }
+
+        let result = self
+            .create_result(
+                Severity::Warning,
+                "autoescaping is disabled for this region",
+            )
+            .primary_note(
+                TextRange::new(
+                    starting_block.syntax().text_range_trimmed_trivia().start(),
+                    autoescape.syntax().text_range().end(),
+                ),
+                format!(
+                    "all output in here is unescaped, because of '{}'",
+                    disabled_keyword.text()
+                ),
+            );
+
+        Some(vec![result])
+    }
+}
+
+type GlobSetCache = HashMap<Vec<String>, Result<Arc<GlobSet>, String>>;
+
+/// Built glob sets by their patterns, so every configured list is only built once
+static GLOB_SET_CACHE: Lazy<Mutex<GlobSetCache>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Does the file path match any of the glob patterns (relative to the working directory).
+/// Invalid patterns match no file, they are reported by `validate_patterns` when the config is loaded.
+pub(super) fn is_allowed_file(file_path: &Path, patterns: &[String]) -> bool {
+    if patterns.is_empty() {
+        return false;
+    }
+
+    glob_set(patterns)
+        .is_ok_and(|set| set.is_match(file_path.strip_prefix("./").unwrap_or(file_path)))
+}
+
+/// Validate the glob patterns of a config option like `vue-files`
+pub(super) fn validate_patterns(option: &str, patterns: &[String]) -> Result<(), String> {
+    glob_set(patterns)
+        .map(|_| ())
+        .map_err(|e| format!("invalid pattern in {option}: {e}"))
+}
+
+fn glob_set(patterns: &[String]) -> Result<Arc<GlobSet>, String> {
+    let mut cache = GLOB_SET_CACHE.lock().unwrap();
+    if let Some(glob_set) = cache.get(patterns) {
+        return glob_set.clone();
+    }
+
+    let glob_set = patterns
+        .iter()
+        .try_fold(GlobSetBuilder::new(), |mut builder, pattern| {
+            builder.add(Glob::new(pattern)?);
+            Ok(builder)
+        })
+        .and_then(|builder| builder.build())
+        .map(Arc::new)
+        .map_err(|e: globset::Error| e.to_string());
+    cache.insert(patterns.to_vec(), glob_set.clone());
+    glob_set
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::check::rules::test::test_rule;
+    use expect_test::expect;
+
+    use super::{is_allowed_file, RuleTwigNoAutoescapeOff};
+    use crate::check::rule::Rule;
+    use crate::config::Config;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-no-autoescape-off",
+            "<div>
+    {% autoescape false %}
+        {{ product.description }}
+    {% endautoescape %}
+</div>",
+            expect![[r#"
+                warning[twig-no-autoescape-off]: autoescaping is disabled for this region
+                  ┌─ ./debug-rule.html.twig:2:5
+                  │  
+                2 │ ╭     {% autoescape false %}
+                3 │ │         {{ product.description }}
+                4 │ │     {% endautoescape %}
+                  │ ╰───────────────────────^ all output in here is unescaped, because of 'false'
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_strategy() {
+        test_rule(
+            "twig-no-autoescape-off",
+            "{% autoescape 'js' %}{{ a }}{% endautoescape %}{% autoescape %}{{ b }}{% endautoescape %}",
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn allowed_files() {
+        let patterns = vec!["templates/email/**".to_string()];
+
+        assert!(is_allowed_file(
+            Path::new("./templates/email/order.html.twig"),
+            &patterns
+        ));
+        assert!(is_allowed_file(
+            Path::new("templates/email/order.html.twig"),
+            &patterns
+        ));
+        assert!(!is_allowed_file(
+            Path::new("./templates/page/index.html.twig"),
+            &patterns
+        ));
+        assert!(!is_allowed_file(
+            Path::new("./templates/email/order.html.twig"),
+            &[]
+        ));
+    }
+
+    #[test]
+    fn invalid_patterns_are_reported_in_the_config() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.autoescape_off_allowed_files = vec!["templates/{email".to_string()];
+
+        assert_eq!(
+            RuleTwigNoAutoescapeOff.validate_config(&config),
+            Err("invalid pattern in autoescape-off-allowed-files: error parsing glob 'templates/{email': unclosed alternate group; missing '}' (maybe escape '{' with '[{]'?)".to_string())
+        );
+        assert!(!is_allowed_file(
+            Path::new("./templates/email/order.html.twig"),
+            &config.rules.autoescape_off_allowed_files
+        ));
+    }
+}
@@ -0,0 +1,85 @@
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode, SyntaxNodeExt};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleTwigNoNestedTernary;
+
+impl Rule for RuleTwigNoNestedTernary {
+    fn name(&self) -> &'static str {
+        "twig-no-nested-ternary"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Nested ternary expressions are hard to read, a separate if tag or variable is easier to follow.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        if node.kind() != SyntaxKind::TWIG_CONDITIONAL_EXPRESSION {
+            return None;
+        }
+
+        // parentheses don't matter here, the ternary is nested anyway
+        let outer = node.ancestors().skip(1).find(|n| {
+            !matches!(
+                n.kind(),
+                SyntaxKind::TWIG_EXPRESSION | SyntaxKind::TWIG_PARENTHESES_EXPRESSION
+            )
+        })?;
+        if outer.kind() != SyntaxKind::TWIG_CONDITIONAL_EXPRESSION {
+            return None;
+        }
+
+        let result = self
+            .create_result(Severity::Help, "nested ternary expression")
+            .primary_note(
+                node.text_range_trimmed_trivia(),
+                "help: use an {% if %} block instead of nesting this inside of another ternary",
+            );
+
+        Some(vec![result])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::test_rule;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-no-nested-ternary",
+            "{{ a ? b : c ? d : e }}\n{{ (a ? b : c) ? d : e }}\n{{ a ?: (b ? c : d) }}",
+            expect![[r#"
+                help[twig-no-nested-ternary]: nested ternary expression
+                  ┌─ ./debug-rule.html.twig:1:12
+                  │
+                1 │ {{ a ? b : c ? d : e }}
+                  │            ^^^^^^^^^ help: use an {% if %} block instead of nesting this inside of another ternary
+
+                help[twig-no-nested-ternary]: nested ternary expression
+                  ┌─ ./debug-rule.html.twig:2:5
+                  │
+                2 │ {{ (a ? b : c) ? d : e }}
+                  │     ^^^^^^^^^ help: use an {% if %} block instead of nesting this inside of another ternary
+
+                help[twig-no-nested-ternary]: nested ternary expression
+                  ┌─ ./debug-rule.html.twig:3:10
+                  │
+                3 │ {{ a ?: (b ? c : d) }}
+                  │          ^^^^^^^^^ help: use an {% if %} block instead of nesting this inside of another ternary
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "twig-no-nested-ternary",
+            "{{ a ? b : c }}\n{{ a ?: b }}\n{{ a ? (b ?? c) : d|default(e ? f : g) }}",
+            expect![[r#""#]],
+        );
+    }
+}
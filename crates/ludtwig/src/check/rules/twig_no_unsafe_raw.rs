@@ -0,0 +1,176 @@
+use ludtwig_parser::syntax::typed::{AstNode, TwigFilter};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode, SyntaxNodeExt};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::rules::html_data_attribute_naming::get_naming_regex;
+
+/// Filters which make the output safe to render as html
+static SANITIZING_FILTERS: &[&str] = &["e", "escape", "sw_sanitize", "striptags"];
+
+pub struct RuleTwigNoUnsafeRaw;
+
+impl Rule for RuleTwigNoUnsafeRaw {
+    fn name(&self) -> &'static str {
+        "twig-no-unsafe-raw"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("The raw filter disables autoescaping, so user-controlled content is rendered as HTML, which allows cross-site scripting. Sanitize the content first (like '|sw_sanitize|raw').")
+    }
+
+    fn check_root(&self, _node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let results: Vec<_> = ctx
+            .config()
+            .rules
+            .raw_allowed_variables
+            .iter()
+            .filter_map(|pattern| match get_naming_regex(pattern) {
+                Ok(_) => None,
+                Err(e) => Some(self.create_result(
+                    Severity::Error,
+                    format!("can't use raw-allowed-variables pattern '{pattern}': {e}"),
+                )),
+            })
+            .collect();
+
+        if results.is_empty() {
+            None
+        } else {
+            Some(results)
+        }
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let filter = TwigFilter::cast(node)?;
+        let name = filter.name()?;
+        if name.text() != "raw" {
+            return None;
+        }
+
+        let operand = filter.operand()?.syntax().first_child()?;
+        if is_sanitized(&operand) {
+            return None;
+        }
+
+        // the allowlist is matched against the variable without whitespace, like `page.header.html`
+        let variable: String = operand
+            .text()
+            .to_string()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        if ctx
+            .config()
+            .rules
+            .raw_allowed_variables
+            .iter()
+            .filter_map(|pattern| get_naming_regex(pattern).ok())
+            .any(|regex| regex.is_match(&variable))
+        {
+            return None;
+        }
+
+        let result = self
+            .create_result(Severity::Warning, "unsafe usage of the raw filter")
+            .primary_note(
+                name.text_range(),
+                "this disables the escaping of the output",
+            )
+            .secondary_note(
+                operand.text_range_trimmed_trivia(),
+                "this may contain user-controlled html",
+            );
+
+        Some(vec![result])
+    }
+}
+
+/// Is the expression a literal or already passed through a sanitizing filter
+fn is_sanitized(expression: &SyntaxNode) -> bool {
+    if is_literal(expression) {
+        return true;
+    }
+
+    match expression.kind() {
+        SyntaxKind::TWIG_PARENTHESES_EXPRESSION | SyntaxKind::TWIG_EXPRESSION => expression
+            .first_child()
+            .is_some_and(|inner| is_sanitized(&inner)),
+        // sanitizing filters can be followed by other filters like `|escape|nl2br|raw`
+        SyntaxKind::TWIG_FILTER => TwigFilter::cast(expression.clone()).is_some_and(|filter| {
+            filter
+                .name()
+                .is_some_and(|name| SANITIZING_FILTERS.contains(&name.text()))
+                || filter
+                    .operand()
+                    .and_then(|operand| operand.syntax().first_child())
+                    .is_some_and(|inner| !is_literal(&inner) && is_sanitized(&inner))
+        }),
+        _ => false,
+    }
+}
+
+fn is_literal(expression: &SyntaxNode) -> bool {
+    matches!(
+        expression.kind(),
+        SyntaxKind::TWIG_LITERAL_STRING
+            | SyntaxKind::TWIG_LITERAL_NUMBER
+            | SyntaxKind::TWIG_LITERAL_BOOLEAN
+            | SyntaxKind::TWIG_LITERAL_NULL
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_with_config};
+    use crate::config::Config;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-no-unsafe-raw",
+            "{{ product.description|raw }}{{ review.content|upper|raw }}",
+            expect![[r#"
+                warning[twig-no-unsafe-raw]: unsafe usage of the raw filter
+                  ┌─ ./debug-rule.html.twig:1:24
+                  │
+                1 │ {{ product.description|raw }}{{ review.content|upper|raw }}
+                  │    ------------------- ^^^ this disables the escaping of the output
+                  │    │                    
+                  │    this may contain user-controlled html
+
+                warning[twig-no-unsafe-raw]: unsafe usage of the raw filter
+                  ┌─ ./debug-rule.html.twig:1:54
+                  │
+                1 │ {{ product.description|raw }}{{ review.content|upper|raw }}
+                  │                                 -------------------- ^^^ this disables the escaping of the output
+                  │                                 │                     
+                  │                                 this may contain user-controlled html
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_sanitized_output() {
+        test_rule(
+            "twig-no-unsafe-raw",
+            "{{ product.description|sw_sanitize|raw }}{{ a|escape('html')|nl2br|raw }}{{ '<br>'|raw }}{{ (c|e)|raw }}{{ b|upper }}",
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_allowed_variables() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.raw_allowed_variables = vec![r"^page\.header\.".to_string()];
+
+        test_rule_with_config(
+            "twig-no-unsafe-raw",
+            "{{ page.header.html|raw }}{{ page . header . script | raw }}",
+            config,
+            expect![[r#""#]],
+        );
+    }
+}
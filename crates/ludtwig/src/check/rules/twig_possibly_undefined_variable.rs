@@ -0,0 +1,185 @@
+use std::collections::HashSet;
+
+use ludtwig_parser::syntax::scope::{Reference, ScopeAnalyzer};
+use ludtwig_parser::syntax::typed::{AstNode, TwigBinaryExpression, TwigFilter};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode};
+use ludtwig_parser::T;
+
+use crate::check::is_ignored_by_directive;
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::rules::twig_unknown_test::test_name;
+
+/// Variables which twig itself provides in every template
+const TWIG_GLOBALS: [&str; 3] = ["_self", "_context", "_charset"];
+
+pub struct RuleTwigPossiblyUndefinedVariable;
+
+impl Rule for RuleTwigPossiblyUndefinedVariable {
+    fn name(&self) -> &'static str {
+        "twig-possibly-undefined-variable"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Variables which are not defined in the template must come from the render context, otherwise they are most likely typos.")
+    }
+
+    fn check_root(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let analyzer = ScopeAnalyzer::new(&node);
+        let known_variables = &ctx.config().rules.known_variables;
+        let mut reported = HashSet::new();
+
+        let results = analyzer
+            .unresolved_references()
+            .filter(|r| {
+                !TWIG_GLOBALS.contains(&r.name.as_str())
+                    && !known_variables.iter().any(|v| v == &r.name)
+                    && !is_in_dynamic_scope(&analyzer, r)
+            })
+            .filter(|r| {
+                let Some(name) = node.covering_element(r.range).parent() else {
+                    return false;
+                };
+                !is_guarded(&name) && !self.is_ignored(&name)
+            })
+            // only the first usage of every name
+            .filter(|r| reported.insert(r.name.clone()))
+            .map(|r| {
+                self.create_result(
+                    Severity::Info,
+                    format!("variable '{}' is possibly undefined", r.name),
+                )
+                .primary_note(
+                    r.range,
+                    "not defined in this template, globally provided variables can be added to the known-variables in the config",
+                )
+            })
+            .collect();
+
+        Some(results)
+    }
+}
+
+impl RuleTwigPossiblyUndefinedVariable {
+    /// Whether the name is inside of an error node or ignored with a ludtwig-ignore directive
+    fn is_ignored(&self, name: &SyntaxNode) -> bool {
+        name.ancestors()
+            .any(|a| a.kind() == SyntaxKind::ERROR || is_ignored_by_directive(&a, self.name()))
+    }
+}
+
+/// Whether the reference may come from variables which are not statically known, like in `{% with variables %}`
+fn is_in_dynamic_scope(analyzer: &ScopeAnalyzer, reference: &Reference) -> bool {
+    let mut current = Some(reference.scope);
+
+    while let Some(id) = current {
+        let scope = analyzer.scope(id);
+        if scope.dynamic {
+            return true;
+        }
+        if scope.isolated {
+            break;
+        }
+        current = scope.parent;
+    }
+
+    false
+}
+
+/// Whether the variable is only used when it is defined,
+/// like in `a is defined`, `a ?? 'fallback'` or `a.b|default('fallback')`
+fn is_guarded(name: &SyntaxNode) -> bool {
+    let mut node = name.clone();
+
+    while let Some(parent) = node.parent() {
+        if parent.first_child().as_ref() != Some(&node) {
+            return false;
+        }
+
+        if let Some(binary) = TwigBinaryExpression::cast(parent.clone()) {
+            return binary.operator().is_some_and(|o| o.kind() == T!["??"])
+                || test_name(&binary).is_some_and(|t| t.text() == "defined");
+        }
+        if TwigFilter::cast(parent.clone())
+            .and_then(|filter| filter.name())
+            .is_some_and(|filter| filter.text() == "default")
+        {
+            return true;
+        }
+
+        match parent.kind() {
+            SyntaxKind::TWIG_EXPRESSION
+            | SyntaxKind::TWIG_OPERAND
+            | SyntaxKind::TWIG_ACCESSOR
+            | SyntaxKind::TWIG_INDEX_LOOKUP
+            | SyntaxKind::TWIG_FILTER => node = parent,
+            _ => return false,
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_with_config};
+    use crate::config::Config;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-possibly-undefined-variable",
+            "{% set title = 'a' %}{{ title ~ titel }}{% for item in items %}{{ item ~ loop.index ~ titel }}{% endfor %}",
+            expect![[r#"
+                note[twig-possibly-undefined-variable]: variable 'titel' is possibly undefined
+                  ┌─ ./debug-rule.html.twig:1:33
+                  │
+                1 │ {% set title = 'a' %}{{ title ~ titel }}{% for item in items %}{{ item ~ loop.index ~ titel }}{% endfor %}
+                  │                                 ^^^^^ not defined in this template, globally provided variables can be added to the known-variables in the config
+
+                note[twig-possibly-undefined-variable]: variable 'items' is possibly undefined
+                  ┌─ ./debug-rule.html.twig:1:56
+                  │
+                1 │ {% set title = 'a' %}{{ title ~ titel }}{% for item in items %}{{ item ~ loop.index ~ titel }}{% endfor %}
+                  │                                                        ^^^^^ not defined in this template, globally provided variables can be added to the known-variables in the config
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_guarded_variables() {
+        test_rule(
+            "twig-possibly-undefined-variable",
+            "{% if a is defined %}{{ b.c ?? 'd' }}{{ e.f|upper|default('g') }}{% endif %}{% with h %}{{ i }}{% endwith %}{{ _self }}{{ list|map(v => v) }}",
+            expect![[r#"
+                note[twig-possibly-undefined-variable]: variable 'h' is possibly undefined
+                  ┌─ ./debug-rule.html.twig:1:85
+                  │
+                1 │ {% if a is defined %}{{ b.c ?? 'd' }}{{ e.f|upper|default('g') }}{% endif %}{% with h %}{{ i }}{% endwith %}{{ _self }}{{ list|map(v => v) }}
+                  │                                                                                     ^ not defined in this template, globally provided variables can be added to the known-variables in the config
+
+                note[twig-possibly-undefined-variable]: variable 'list' is possibly undefined
+                  ┌─ ./debug-rule.html.twig:1:123
+                  │
+                1 │ {% if a is defined %}{{ b.c ?? 'd' }}{{ e.f|upper|default('g') }}{% endif %}{% with h %}{{ i }}{% endwith %}{{ _self }}{{ list|map(v => v) }}
+                  │                                                                                                                           ^^^^ not defined in this template, globally provided variables can be added to the known-variables in the config
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_known_variables() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.known_variables = vec!["product".to_string()];
+
+        test_rule_with_config(
+            "twig-possibly-undefined-variable",
+            "{{ product.name }}{# ludtwig-ignore #}{{ other }}",
+            config,
+            expect![[r#""#]],
+        );
+    }
+}
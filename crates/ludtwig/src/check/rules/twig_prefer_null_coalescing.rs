@@ -0,0 +1,228 @@
+use ludtwig_parser::syntax::typed::{
+    support, AstNode, TwigBinaryExpression, TwigConditionalExpression, TwigExpression, TwigFilter,
+};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode, SyntaxNodeExt};
+use ludtwig_parser::T;
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleTwigPreferNullCoalescing;
+
+impl Rule for RuleTwigPreferNullCoalescing {
+    fn name(&self) -> &'static str {
+        "twig-prefer-null-coalescing"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("The null-coalescing operator `foo ?? 'bar'` is shorter than `foo is defined ? foo : 'bar'` and doesn't repeat the variable. Unlike the ternary it also uses the fallback if the variable is defined as null.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let (value, fallback, reason) =
+            if let Some(conditional) = TwigConditionalExpression::cast(node.clone()) {
+                let (tested, negated) = defined_test(&conditional.condition()?)?;
+                let (value, fallback) = if negated {
+                    (
+                        conditional.falsy_expression()?,
+                        conditional.truthy_expression()?,
+                    )
+                } else {
+                    (
+                        conditional.truthy_expression()?,
+                        conditional.falsy_expression()?,
+                    )
+                };
+                if expression_text(value.syntax()) != expression_text(tested.syntax()) {
+                    return None;
+                }
+
+                (
+                    value.syntax().clone(),
+                    fallback,
+                    "this checks if the value is defined",
+                )
+            } else if let Some(filter) = TwigFilter::cast(node.clone()) {
+                if !ctx.config().rules.prefer_null_coalescing_over_default
+                    || filter.name()?.text() != "default"
+                {
+                    return None;
+                }
+                let arguments = filter.arguments()?;
+                let mut positional = arguments.positional();
+                let (Some(fallback), None, None) = (
+                    positional.next(),
+                    positional.next(),
+                    arguments.named().next(),
+                ) else {
+                    return None;
+                };
+
+                (
+                    filter.operand()?.syntax().clone(),
+                    fallback,
+                    "the default filter also replaces empty values like '' or []",
+                )
+            } else {
+                return None;
+            };
+
+        let replacement = format!(
+            "{} ?? {}",
+            operand_text(&value, false),
+            operand_text(fallback.syntax(), true)
+        );
+        let replacement = if needs_parentheses(&node) {
+            format!("({replacement})")
+        } else {
+            replacement
+        };
+
+        let result = self
+            .create_result(Severity::Help, "Use the null-coalescing operator")
+            .primary_note(node.text_range_trimmed_trivia(), reason)
+            .suggestion(
+                node.text_range_trimmed_trivia(),
+                replacement,
+                "Try this instead",
+            );
+
+        Some(vec![result])
+    }
+}
+
+/// The tested expression of a condition like `foo is defined` (or `foo is not defined`, which is negated)
+fn defined_test(condition: &TwigExpression) -> Option<(TwigExpression, bool)> {
+    let binary = support::child::<TwigBinaryExpression>(condition.syntax())?;
+    if binary.operator()?.kind() != T!["is"]
+        || expression_text(binary.rhs_expression()?.syntax()) != "defined"
+    {
+        return None;
+    }
+
+    let negated = binary
+        .syntax()
+        .children_with_tokens()
+        .any(|element| element.kind() == T!["not"]);
+
+    Some((binary.lhs_expression()?, negated))
+}
+
+fn expression_text(node: &SyntaxNode) -> String {
+    node.text().to_string().trim().to_string()
+}
+
+/// The text of an operand of `??`, in parentheses if it would bind differently without them.
+/// `??` binds stronger than all other binary operators, so only `a ?? b` on the right side stays as it is.
+fn operand_text(node: &SyntaxNode, right_side: bool) -> String {
+    let text = expression_text(node);
+    let inner = node
+        .descendants()
+        .find(|n| n.kind() != SyntaxKind::TWIG_EXPRESSION);
+
+    match inner.as_ref().map(SyntaxNode::kind) {
+        Some(SyntaxKind::TWIG_CONDITIONAL_EXPRESSION) => format!("({text})"),
+        Some(SyntaxKind::TWIG_BINARY_EXPRESSION) => {
+            let is_coalescing = inner
+                .and_then(TwigBinaryExpression::cast)
+                .and_then(|binary| binary.operator())
+                .is_some_and(|op| op.kind() == T!["??"]);
+            if right_side && is_coalescing {
+                text
+            } else {
+                format!("({text})")
+            }
+        }
+        _ => text,
+    }
+}
+
+/// Does the `??` expression need parentheses at the place of the node,
+/// like for an operand of a filter (`a|default(b)|upper`) or a unary minus
+fn needs_parentheses(node: &SyntaxNode) -> bool {
+    let Some(parent) = node.parent() else {
+        return false;
+    };
+
+    match parent.kind() {
+        SyntaxKind::TWIG_OPERAND => true,
+        SyntaxKind::TWIG_EXPRESSION => parent.parent().is_some_and(|p| {
+            p.kind() == SyntaxKind::TWIG_UNARY_EXPRESSION
+                && p.children_with_tokens()
+                    .any(|element| matches!(element.kind(), T!["-"] | T!["+"]))
+        }),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_fix, test_rule_with_config};
+    use crate::check::rules::RuleRegistry;
+    use crate::config::Config;
+    use crate::process::preview_fix;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-prefer-null-coalescing",
+            "{{ foo is defined ? foo : 'bar' }}{{ foo is defined ? bar : 'bar' }}{{ foo|default('bar') }}",
+            expect![[r#"
+                help[twig-prefer-null-coalescing]: Use the null-coalescing operator
+                  ┌─ ./debug-rule.html.twig:1:4
+                  │
+                1 │ {{ foo is defined ? foo : 'bar' }}{{ foo is defined ? bar : 'bar' }}{{ foo|default('bar') }}
+                  │    ^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+                  │    │
+                  │    this checks if the value is defined
+                  │    Try this instead: foo ?? 'bar'
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes() {
+        test_rule_fix(
+            "twig-prefer-null-coalescing",
+            "{{ foo.bar is defined ? foo.bar : 'bar' }}{% set a = b is not defined ? c ~ d : b %}{{ a is defined ? a : (b ? c : d) }}",
+            expect!["{{ foo.bar ?? 'bar' }}{% set a = b ?? (c ~ d) %}{{ a ?? (b ? c : d) }}"],
+        );
+    }
+
+    #[test]
+    fn rule_fixes_default_filter() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.prefer_null_coalescing_over_default = true;
+
+        test_rule_with_config(
+            "twig-prefer-null-coalescing",
+            "{{ foo|default('bar') }}{{ foo|default }}",
+            config.clone(),
+            expect![[r#"
+                help[twig-prefer-null-coalescing]: Use the null-coalescing operator
+                  ┌─ ./debug-rule.html.twig:1:4
+                  │
+                1 │ {{ foo|default('bar') }}{{ foo|default }}
+                  │    ^^^^^^^^^^^^^^^^^^
+                  │    │
+                  │    the default filter also replaces empty values like '' or []
+                  │    Try this instead: foo ?? 'bar'
+
+            "#]],
+        );
+        expect!["{{ (a.b ?? c ?? d)|upper }}{{ -(a ?? 1) }}{{ a ?? 1 ~ b ?? 2 }}"].assert_eq(
+            &preview_fix(
+                Path::new("./debug-rule.html.twig"),
+                "{{ a.b|default(c ?? d)|upper }}{{ -a|default(1) }}{{ a|default(1) ~ b|default(2) }}",
+                "twig-prefer-null-coalescing",
+                &RuleRegistry::default(),
+                config,
+            )
+            .unwrap(),
+        );
+    }
+}
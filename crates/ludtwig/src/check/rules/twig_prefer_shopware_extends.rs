@@ -10,6 +10,10 @@ impl Rule for RuleTwigPreferShopwareExtends {
         "twig-prefer-shopware-extends"
     }
 
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Shopware templates should use sw_extends to support the template inheritance of plugins.")
+    }
+
     fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
         let twig_extends = TwigExtends::cast(node)?;
         let extends_keyword = twig_extends.get_extends_keyword()?;
@@ -0,0 +1,131 @@
+use ludtwig_parser::syntax::typed::{AstNode, TwigBinaryExpression};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode, SyntaxNodeExt, TextRange};
+use ludtwig_parser::T;
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleTwigRequireParentheses;
+
+impl Rule for RuleTwigRequireParentheses {
+    fn name(&self) -> &'static str {
+        "twig-require-parentheses"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Mixed and / or expressions and nested ternaries are easy to misread without parentheses which make the precedence visible.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        // only nodes which are directly nested (without parentheses) inside of another expression
+        let parent = node.parent()?;
+        if parent.kind() != SyntaxKind::TWIG_EXPRESSION {
+            return None;
+        }
+        let outer = parent.parent()?;
+
+        let (message, hint) = match (node.kind(), outer.kind()) {
+            (SyntaxKind::TWIG_BINARY_EXPRESSION, SyntaxKind::TWIG_BINARY_EXPRESSION) => {
+                let inner_operator = logic_operator(&node)?;
+                let outer_operator = logic_operator(&outer)?;
+                if inner_operator == outer_operator {
+                    return None;
+                }
+
+                (
+                    "mixed 'and' / 'or' without parentheses",
+                    format!("help: '{inner_operator}' is evaluated first, add parentheses to make this explicit"),
+                )
+            }
+            (SyntaxKind::TWIG_CONDITIONAL_EXPRESSION, SyntaxKind::TWIG_CONDITIONAL_EXPRESSION) => (
+                "nested ternary without parentheses",
+                "help: this is evaluated as a whole, add parentheses to make this explicit"
+                    .to_string(),
+            ),
+            _ => return None,
+        };
+
+        let range = node.text_range_trimmed_trivia();
+        let result = self
+            .create_result(Severity::Help, message)
+            .primary_note(range, hint)
+            .suggestion(TextRange::empty(range.start()), "(", "add parentheses")
+            .suggestion(TextRange::empty(range.end()), ")", "add parentheses");
+
+        Some(vec![result])
+    }
+}
+
+/// Normalized name of the logical operator ('and' / 'or') of a binary expression
+fn logic_operator(node: &SyntaxNode) -> Option<&'static str> {
+    let operator = TwigBinaryExpression::cast(node.clone())?.operator()?;
+
+    match operator.kind() {
+        T!["and"] | T!["&&"] => Some("and"),
+        T!["or"] | T!["||"] => Some("or"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_fix};
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-require-parentheses",
+            "{% if a and b or c %}{% endif %}\n{{ a ? b : c ? d : e }}\n{% if a and b and c or (d or e) %}{% endif %}",
+            expect![[r#"
+                help[twig-require-parentheses]: mixed 'and' / 'or' without parentheses
+                  ┌─ ./debug-rule.html.twig:1:7
+                  │
+                1 │ {% if a and b or c %}{% endif %}
+                  │       ^^^^^^^- add parentheses: )
+                  │       │       
+                  │       help: 'and' is evaluated first, add parentheses to make this explicit
+                  │       add parentheses: (
+
+                help[twig-require-parentheses]: nested ternary without parentheses
+                  ┌─ ./debug-rule.html.twig:2:12
+                  │
+                2 │ {{ a ? b : c ? d : e }}
+                  │            ^^^^^^^^^- add parentheses: )
+                  │            │         
+                  │            help: this is evaluated as a whole, add parentheses to make this explicit
+                  │            add parentheses: (
+
+                help[twig-require-parentheses]: mixed 'and' / 'or' without parentheses
+                  ┌─ ./debug-rule.html.twig:3:7
+                  │
+                3 │ {% if a and b and c or (d or e) %}{% endif %}
+                  │       ^^^^^^^^^^^^^- add parentheses: )
+                  │       │             
+                  │       help: 'and' is evaluated first, add parentheses to make this explicit
+                  │       add parentheses: (
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "twig-require-parentheses",
+            "{% if (a and b) or c %}{% endif %}\n{% if a and b and c %}{% endif %}\n{% if a or b or c %}{% endif %}\n{{ a ? b : (c ? d : e) }}\n{{ a ?: b == c }}",
+            expect![""],
+        );
+    }
+
+    #[test]
+    fn rule_fixes() {
+        test_rule_fix(
+            "twig-require-parentheses",
+            "{% if a or b and c %}{% endif %}\n{{ a ? b : c ? d : e ? f : g }}",
+            expect![[r#"
+                {% if a or (b and c) %}{% endif %}
+                {{ a ? b : (c ? d : (e ? f : g)) }}"#]],
+        );
+    }
+}
@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use ludtwig_parser::syntax::typed::{
+    AstNode, TwigFunctionCall, TwigLiteralHash, TwigLiteralString,
+};
+use ludtwig_parser::syntax::untyped::{SyntaxNode, SyntaxNodeExt};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value;
+
+use crate::check::dependencies;
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::Config;
+
+/// Matches route placeholders like `{id}`, `{page<\d+>}` or `{page?1}`
+static ROUTE_PLACEHOLDER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{!?(\w+)(?:<[^>]*>)?(\?[^}]*)?\}").unwrap());
+
+/// Route names mapped to their required parameters
+type Routes = HashMap<String, Vec<String>>;
+type RoutesCache = HashMap<PathBuf, (Option<SystemTime>, Result<Arc<Routes>, String>)>;
+
+/// Loaded route exports by their path and modification time
+/// (to notice changes while running as a daemon), so every file is only read once
+static ROUTES_CACHE: Lazy<Mutex<RoutesCache>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub struct RuleTwigRouteValidation;
+
+impl Rule for RuleTwigRouteValidation {
+    fn name(&self) -> &'static str {
+        "twig-route-validation"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Routes which don't exist or miss required parameters throw an exception when the template renders.")
+    }
+
+    fn validate_config(&self, config: &Config) -> Result<(), String> {
+        let routes_file = &config.rules.routes_file;
+        if routes_file.is_empty() {
+            return Ok(());
+        }
+
+        get_routes(Path::new(routes_file))
+            .map(|_| ())
+            .map_err(|e| format!("can't use routes file {routes_file} for route validation: {e}"))
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let function_call = TwigFunctionCall::cast(node)?;
+        let function_name = function_call.name()?;
+        if !matches!(function_name.text(), "path" | "url") {
+            return None;
+        }
+
+        let routes = get_routes(routes_file(ctx)?).ok()?;
+
+        let arguments = function_call.arguments()?;
+        let mut positional = arguments.positional();
+        // only literal route names can be validated
+        let route_name_expression = positional.next()?;
+        let route_name_string = route_name_expression
+            .syntax()
+            .first_child()
+            .and_then(TwigLiteralString::cast)?;
+        let route_name = route_name_string.value();
+        if route_name.has_interpolation {
+            return None;
+        }
+        let route_name = route_name.text;
+
+        let Some(required_parameters) = routes.get(&route_name) else {
+            let result = self
+                .create_result(Severity::Error, format!("unknown route '{route_name}'"))
+                .primary_note(
+                    route_name_string.syntax().text_range_trimmed_trivia(),
+                    "this route name does not exist in the routes file",
+                );
+            return Some(vec![result]);
+        };
+
+        let given_parameters: Vec<String> = match positional.next() {
+            Some(parameters) => {
+                // only literal hashes can be validated
+                let hash = parameters
+                    .syntax()
+                    .first_child()
+                    .and_then(TwigLiteralHash::cast)?;
+                hash.pairs()
+                    .filter_map(|p| p.key()?.get_key_name())
+                    .collect()
+            }
+            None => vec![],
+        };
+
+        let missing_parameters: Vec<&str> = required_parameters
+            .iter()
+            .filter(|p| !given_parameters.contains(p))
+            .map(String::as_str)
+            .collect();
+        if missing_parameters.is_empty() {
+            return None;
+        }
+
+        let result = self
+            .create_result(
+                Severity::Error,
+                format!("missing required parameters for route '{route_name}'"),
+            )
+            .primary_note(
+                function_call.syntax().text_range_trimmed_trivia(),
+                format!("add the parameters: {}", missing_parameters.join(", ")),
+            );
+
+        Some(vec![result])
+    }
+}
+
+fn routes_file(ctx: &RuleRunContext) -> Option<&Path> {
+    let routes_file = &ctx.config().rules.routes_file;
+    if routes_file.is_empty() {
+        return None;
+    }
+
+    Some(Path::new(routes_file))
+}
+
+fn get_routes(path: &Path) -> Result<Arc<Routes>, String> {
+    let modified = dependencies::modified_time(path);
+
+    let mut cache = ROUTES_CACHE.lock().unwrap();
+    if let Some((cached_modified, routes)) = cache.get(path) {
+        if cached_modified == &modified {
+            return routes.clone();
+        }
+    }
+
+    let routes = std::fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| parse_routes(&content))
+        .map(Arc::new);
+    cache.insert(path.to_path_buf(), (modified, routes.clone()));
+    routes
+}
+
+/// Parse the output of Symfony's `debug:router --format=json`
+fn parse_routes(content: &str) -> Result<Routes, String> {
+    let json: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let Value::Object(routes) = json else {
+        return Err("expected an object of routes".to_string());
+    };
+
+    Ok(routes
+        .into_iter()
+        .map(|(name, route)| {
+            let path = route.get("path").and_then(Value::as_str).unwrap_or("");
+            // php serializes empty defaults as an array
+            let defaults = route.get("defaults").and_then(Value::as_object);
+
+            let required_parameters = ROUTE_PLACEHOLDER_REGEX
+                .captures_iter(path)
+                .filter(|c| c.get(2).is_none()) // inline default value
+                .map(|c| c[1].to_string())
+                .filter(|p| !defaults.is_some_and(|d| d.contains_key(p)))
+                // filled from the router context (like `_locale` of the current request)
+                .filter(|p| !p.starts_with('_'))
+                .collect();
+
+            (name, required_parameters)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use expect_test::expect;
+
+    use crate::check::rule::Rule;
+    use crate::check::rules::test::test_rule_with_config;
+    use crate::check::rules::{get_config_active_rule_definitions, RuleRegistry};
+    use crate::config::Config;
+    use crate::error::ConfigurationError;
+
+    use super::{get_routes, parse_routes, RuleTwigRouteValidation};
+
+    static ROUTES: &str = r#"{
+        "frontend.home.page": {
+            "path": "/",
+            "defaults": []
+        },
+        "frontend.detail.page": {
+            "path": "/detail/{productId}",
+            "defaults": {
+                "_controller": "ProductController::index"
+            }
+        },
+        "frontend.listing.page": {
+            "path": "/listing/{navigationId}/{page?1}",
+            "defaults": []
+        },
+        "frontend.account.page": {
+            "path": "/{_locale}/account",
+            "defaults": {
+                "_locale": "en"
+            }
+        },
+        "frontend.account.orders": {
+            "path": "/{_locale}/account/orders",
+            "defaults": []
+        }
+    }"#;
+
+    /// Config with a routes file, which must be removed at the end of the test
+    fn config_with_routes(name: &str) -> (Config, PathBuf) {
+        let path =
+            std::env::temp_dir().join(format!("ludtwig-routes-{name}-{}.json", std::process::id()));
+        std::fs::write(&path, ROUTES).unwrap();
+
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.routes_file = path.to_string_lossy().to_string();
+        (config, path)
+    }
+
+    #[test]
+    fn parses_routes() {
+        let routes = parse_routes(ROUTES).unwrap();
+
+        assert_eq!(routes["frontend.home.page"], Vec::<String>::new());
+        assert_eq!(routes["frontend.detail.page"], vec!["productId"]);
+        assert_eq!(routes["frontend.listing.page"], vec!["navigationId"]);
+        assert_eq!(routes["frontend.account.page"], Vec::<String>::new());
+        assert_eq!(routes["frontend.account.orders"], Vec::<String>::new());
+        assert!(parse_routes("[]").is_err());
+    }
+
+    #[test]
+    fn reloads_changed_routes_file() {
+        let path =
+            std::env::temp_dir().join(format!("ludtwig-routes-reload-{}.json", std::process::id()));
+        std::fs::write(&path, ROUTES).unwrap();
+        assert!(get_routes(&path)
+            .unwrap()
+            .contains_key("frontend.home.page"));
+
+        // with a different modification time, the file system may be too coarse otherwise
+        std::fs::write(&path, r#"{"frontend.other.page": {"path": "/other"}}"#).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(std::time::SystemTime::UNIX_EPOCH)
+            .unwrap();
+        let routes = get_routes(&path).unwrap();
+        assert!(routes.contains_key("frontend.other.page"));
+        assert!(!routes.contains_key("frontend.home.page"));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn rule_reports() {
+        let (config, routes_path) = config_with_routes("reports");
+        test_rule_with_config(
+            "twig-route-validation",
+            "<a href=\"{{ path('frontend.unknown.page') }}\"></a>
+<a href=\"{{ url('frontend.detail.page') }}\"></a>
+<a href=\"{{ path('frontend.listing.page', { page: 2 }) }}\"></a>",
+            config,
+            expect![[r#"
+                error[twig-route-validation]: unknown route 'frontend.unknown.page'
+                  ┌─ ./debug-rule.html.twig:1:18
+                  │
+                1 │ <a href="{{ path('frontend.unknown.page') }}"></a>
+                  │                  ^^^^^^^^^^^^^^^^^^^^^^^ this route name does not exist in the routes file
+
+                error[twig-route-validation]: missing required parameters for route 'frontend.detail.page'
+                  ┌─ ./debug-rule.html.twig:2:13
+                  │
+                2 │ <a href="{{ url('frontend.detail.page') }}"></a>
+                  │             ^^^^^^^^^^^^^^^^^^^^^^^^^^^ add the parameters: productId
+
+                error[twig-route-validation]: missing required parameters for route 'frontend.listing.page'
+                  ┌─ ./debug-rule.html.twig:3:13
+                  │
+                3 │ <a href="{{ path('frontend.listing.page', { page: 2 }) }}"></a>
+                  │             ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ add the parameters: navigationId
+
+            "#]],
+        );
+
+        std::fs::remove_file(routes_path).unwrap();
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        let (config, routes_path) = config_with_routes("does-not-report");
+        test_rule_with_config(
+            "twig-route-validation",
+            "<a href=\"{{ path('frontend.home.page') }}\"></a>
+<a href=\"{{ url('frontend.detail.page', { productId: product.id }) }}\"></a>
+<a href=\"{{ path('frontend.listing.page', { 'navigationId': id }) }}\"></a>
+<a href=\"{{ path('frontend.account.page') }}\"></a>
+<a href=\"{{ path(dynamicRoute) }}\"></a>
+<a href=\"{{ path('frontend.detail.page', parameters) }}\"></a>",
+            config,
+            expect![""],
+        );
+
+        std::fs::remove_file(routes_path).unwrap();
+    }
+
+    #[test]
+    fn rule_reports_unreadable_routes_file_once_in_the_config() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.routes_file = "./does-not-exist.json".to_string();
+
+        assert_eq!(
+            RuleTwigRouteValidation.validate_config(&config),
+            Err("can't use routes file ./does-not-exist.json for route validation: No such file or directory (os error 2)".to_string())
+        );
+        config.general.active_rules = vec!["twig-route-validation".to_string()];
+        assert!(matches!(
+            get_config_active_rule_definitions(&RuleRegistry::default(), &config),
+            Err(ConfigurationError::InvalidRuleConfig { .. })
+        ));
+
+        // the checked files don't repeat the error
+        test_rule_with_config(
+            "twig-route-validation",
+            "{{ path('frontend.home.page') }}",
+            config,
+            expect![""],
+        );
+    }
+}
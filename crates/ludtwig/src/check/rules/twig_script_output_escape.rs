@@ -0,0 +1,165 @@
+use ludtwig_parser::syntax::typed::{AstNode, HtmlTag, TwigVar};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode, SyntaxNodeExt};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleTwigScriptOutputEscape;
+
+impl Rule for RuleTwigScriptOutputEscape {
+    fn name(&self) -> &'static str {
+        "twig-script-output-escape"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Output inside of script tags needs the js escaping strategy, the HTML escaping doesn't prevent cross-site scripting there.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let twig_var = TwigVar::cast(node)?;
+
+        // only output inside of script tags is relevant
+        let inside_script = twig_var.syntax().ancestors().any(|a| {
+            HtmlTag::cast(a)
+                .and_then(|t| t.name())
+                .is_some_and(|n| n.text().eq_ignore_ascii_case("script"))
+        });
+        if !inside_script {
+            return None;
+        }
+
+        let expression = twig_var
+            .syntax()
+            .children()
+            .find(|c| c.kind() == SyntaxKind::TWIG_EXPRESSION)?;
+        let outer = expression.first_child()?;
+
+        // literals can't contain user input
+        if matches!(
+            outer.kind(),
+            SyntaxKind::TWIG_LITERAL_STRING
+                | SyntaxKind::TWIG_LITERAL_NUMBER
+                | SyntaxKind::TWIG_LITERAL_BOOLEAN
+                | SyntaxKind::TWIG_LITERAL_NULL
+        ) {
+            return None;
+        }
+
+        if filter_chain(&outer)
+            .iter()
+            .any(|(name, argument)| is_script_safe_filter(name, argument.as_deref()))
+        {
+            return None;
+        }
+
+        let result = self
+            .create_result(
+                Severity::Warning,
+                "unescaped twig output inside of a script context",
+            )
+            .primary_note(
+                twig_var.syntax().text_range_trimmed_trivia(),
+                "escape this output for javascript with '|e('js')' or '|json_encode'",
+            );
+
+        Some(vec![result])
+    }
+}
+
+/// Collect the names (and first string argument) of all filters that are applied
+/// on the outermost expression, like `value|json_encode|raw`.
+fn filter_chain(node: &SyntaxNode) -> Vec<(String, Option<String>)> {
+    let mut filters = vec![];
+    let mut current = node.clone();
+
+    while current.kind() == SyntaxKind::TWIG_FILTER {
+        let mut operands = current
+            .children()
+            .filter(|c| c.kind() == SyntaxKind::TWIG_OPERAND);
+        let Some(lhs) = operands.next() else {
+            break;
+        };
+
+        if let Some(filter) = operands.next() {
+            let name = filter
+                .children()
+                .find(|c| c.kind() == SyntaxKind::TWIG_LITERAL_NAME)
+                .map(|n| n.text().to_string().trim().to_owned());
+            let argument = filter
+                .descendants()
+                .find(|c| c.kind() == SyntaxKind::TWIG_LITERAL_STRING_INNER)
+                .map(|n| n.text().to_string());
+
+            if let Some(name) = name {
+                filters.push((name, argument));
+            }
+        }
+
+        match lhs.first_child() {
+            Some(inner) => current = inner,
+            None => break,
+        }
+    }
+
+    filters
+}
+
+fn is_script_safe_filter(name: &str, argument: Option<&str>) -> bool {
+    match name {
+        "json_encode" => true,
+        "e" | "escape" => matches!(argument, Some("js")),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check::rules::test::test_rule;
+    use expect_test::expect;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-script-output-escape",
+            "<script>var a = {{ product.name }};</script>",
+            expect![[r#"
+                warning[twig-script-output-escape]: unescaped twig output inside of a script context
+                  ┌─ ./debug-rule.html.twig:1:17
+                  │
+                1 │ <script>var a = {{ product.name }};</script>
+                  │                 ^^^^^^^^^^^^^^^^^^ escape this output for javascript with '|e('js')' or '|json_encode'
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_reports_raw_filter() {
+        test_rule(
+            "twig-script-output-escape",
+            "<SCRIPT>var a = {{ product.name|raw }};</SCRIPT>",
+            expect![[r#"
+                warning[twig-script-output-escape]: unescaped twig output inside of a script context
+                  ┌─ ./debug-rule.html.twig:1:17
+                  │
+                1 │ <SCRIPT>var a = {{ product.name|raw }};</SCRIPT>
+                  │                 ^^^^^^^^^^^^^^^^^^^^^^ escape this output for javascript with '|e('js')' or '|json_encode'
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_escaped_output() {
+        test_rule(
+            "twig-script-output-escape",
+            "<script>
+    var a = {{ a|e('js') }};
+    var b = {{ b|escape('js') }};
+    var c = {{ c|json_encode|raw }};
+    var d = {{ 'literal' }};
+</script>
+<div>{{ product.name }}</div>",
+            expect![[r#""#]],
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use ludtwig_parser::syntax::typed::{AstNode, ShopwareTwigExtends, TwigExtends};
+use ludtwig_parser::syntax::untyped::{SyntaxNode, SyntaxNodeExt};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+pub struct RuleTwigSingleExtends;
+
+impl Rule for RuleTwigSingleExtends {
+    fn name(&self) -> &'static str {
+        "twig-single-extends"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("A template can only extend one other template, twig ignores or rejects every further extends tag.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        if !is_extends(&node) {
+            return None;
+        }
+
+        let root = node.ancestors().last()?;
+        let first = root.descendants().find(is_extends)?;
+        if first == node {
+            return None;
+        }
+
+        let result = self
+            .create_result(Severity::Error, "template extends more than one template")
+            .primary_note(
+                node.text_range_trimmed_trivia(),
+                "remove this, a template can only extend one other template",
+            )
+            .secondary_note(
+                first.text_range_trimmed_trivia(),
+                "the template already extends this one",
+            );
+
+        Some(vec![result])
+    }
+}
+
+fn is_extends(node: &SyntaxNode) -> bool {
+    TwigExtends::can_cast(node.kind()) || ShopwareTwigExtends::can_cast(node.kind())
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::test_rule;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-single-extends",
+            r#"{% sw_extends '@Storefront/base.html.twig' %}
+{% extends 'base.html.twig' %}
+{% if a %}{% sw_extends '@Storefront/other.html.twig' %}{% endif %}"#,
+            expect![[r#"
+                error[twig-single-extends]: template extends more than one template
+                  ┌─ ./debug-rule.html.twig:2:1
+                  │
+                1 │ {% sw_extends '@Storefront/base.html.twig' %}
+                  │ --------------------------------------------- the template already extends this one
+                2 │ {% extends 'base.html.twig' %}
+                  │ ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ remove this, a template can only extend one other template
+
+                error[twig-single-extends]: template extends more than one template
+                  ┌─ ./debug-rule.html.twig:3:11
+                  │
+                1 │ {% sw_extends '@Storefront/base.html.twig' %}
+                  │ --------------------------------------------- the template already extends this one
+                2 │ {% extends 'base.html.twig' %}
+                3 │ {% if a %}{% sw_extends '@Storefront/other.html.twig' %}{% endif %}
+                  │           ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ remove this, a template can only extend one other template
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "twig-single-extends",
+            "{% extends a ? 'a.html.twig' : 'b.html.twig' %}{% block a %}{% endblock %}",
+            expect![[r#""#]],
+        );
+    }
+}
@@ -11,6 +11,10 @@ impl Rule for RuleTwigStringQuotation {
         "twig-string-quotation"
     }
 
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Twig strings should consistently use the configured quotation.")
+    }
+
     fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
         let twig_string = TwigLiteralString::cast(node)?;
 
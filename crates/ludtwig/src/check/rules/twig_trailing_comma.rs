@@ -0,0 +1,141 @@
+use ludtwig_parser::syntax::untyped::{
+    Direction, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxNodeExt, TextRange,
+};
+use ludtwig_parser::T;
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::config::TrailingComma;
+
+pub struct RuleTwigTrailingComma;
+
+impl Rule for RuleTwigTrailingComma {
+    fn name(&self) -> &'static str {
+        "twig-trailing-comma"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("With a comma behind the last item of multi-line arrays, hashes and argument lists, adding another item only changes a single line in the diff. Some projects prefer to never write them instead, so it should at least be consistent.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        if !matches!(
+            node.kind(),
+            SyntaxKind::TWIG_ARGUMENTS
+                | SyntaxKind::TWIG_LITERAL_ARRAY_INNER
+                | SyntaxKind::TWIG_LITERAL_HASH_ITEMS
+        ) {
+            return None;
+        }
+
+        // only lists where the closing bracket is on its own line are multi-line
+        let has_line_break_before_closing = node
+            .siblings_with_tokens(Direction::Next)
+            .skip(1)
+            .take_while(|element| element.kind().is_trivia())
+            .any(|element| element.kind() == T![lb]);
+        if !has_line_break_before_closing {
+            return None;
+        }
+
+        let last = node
+            .children_with_tokens()
+            .filter(|element| !element.kind().is_trivia())
+            .last()?;
+
+        let result = match (ctx.config().format.trailing_comma, last) {
+            (TrailingComma::Always, SyntaxElement::Node(item)) => {
+                let range = item.text_range_trimmed_trivia();
+                self.create_result(Severity::Help, "Missing trailing comma")
+                    .primary_note(range, "expected a comma behind the last item")
+                    .suggestion(TextRange::empty(range.end()), ",", "Add a comma")
+            }
+            (TrailingComma::Never, SyntaxElement::Token(comma)) if comma.kind() == T![","] => self
+                .create_result(Severity::Help, "Unexpected trailing comma")
+                .primary_note(comma.text_range(), "expected no comma behind the last item")
+                .suggestion(comma.text_range(), "", "Remove the comma"),
+            _ => return None,
+        };
+
+        Some(vec![result])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_fix};
+    use crate::check::rules::RuleRegistry;
+    use crate::config::{Config, TrailingComma};
+    use crate::process::preview_fix;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-trailing-comma",
+            "{{ f(\n    a,\n    b\n) }}{{ [1, 2] }}",
+            expect![[r#"
+                help[twig-trailing-comma]: Missing trailing comma
+                  ┌─ ./debug-rule.html.twig:3:5
+                  │
+                3 │     b
+                  │     ^- Add a comma: ,
+                  │     │
+                  │     expected a comma behind the last item
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "twig-trailing-comma",
+            "{{ f(a, b) }}{{ [1, 2,] }}{{ {\n    a: 1,\n} }}{{ f(\n    a,\n    b,\n) }}{{ [\n] }}{{ [1,\n    2] }}",
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes() {
+        test_rule_fix(
+            "twig-trailing-comma",
+            "{{ f(\n    a,\n    [\n        1\n    ]\n)|default({\n    b: 2\n}) }}",
+            expect![[r#"
+                {{ f(
+                    a,
+                    [
+                        1,
+                    ],
+                )|default({
+                    b: 2,
+                }) }}"#]],
+        );
+    }
+
+    #[test]
+    fn rule_fixes_never() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.format.trailing_comma = TrailingComma::Never;
+
+        expect![[r#"
+            {{ f(
+                a,
+                b
+            ) }}{{ [1, 2,] }}{% set c = {
+                d: 1
+            } %}"#]]
+        .assert_eq(
+            &preview_fix(
+                Path::new("./debug-rule.html.twig"),
+                "{{ f(\n    a,\n    b,\n) }}{{ [1, 2,] }}{% set c = {\n    d: 1,\n} %}",
+                "twig-trailing-comma",
+                &RuleRegistry::default(),
+                config,
+            )
+            .unwrap(),
+        );
+    }
+}
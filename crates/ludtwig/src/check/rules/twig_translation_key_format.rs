@@ -0,0 +1,123 @@
+use ludtwig_parser::syntax::typed::{AstNode, TwigFilter, TwigLiteralString};
+use ludtwig_parser::syntax::untyped::{SyntaxNode, SyntaxNodeExt};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::rules::html_data_attribute_naming::get_naming_regex;
+
+pub struct RuleTwigTranslationKeyFormat;
+
+impl Rule for RuleTwigTranslationKeyFormat {
+    fn name(&self) -> &'static str {
+        "twig-translation-key-format"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Translation keys with a common format (like `checkout.cart.title`) are easier to find in the translation files and avoid using the text itself as the key.")
+    }
+
+    fn check_root(&self, _node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let pattern = &ctx.config().rules.translation_key_regex;
+
+        match get_naming_regex(pattern) {
+            Ok(_) => None,
+            Err(e) => Some(vec![self.create_result(
+                Severity::Error,
+                format!("can't use translation-key-regex '{pattern}': {e}"),
+            )]),
+        }
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let filter = TwigFilter::cast(node)?;
+        if filter.name()?.text() != "trans" {
+            return None;
+        }
+
+        // only plain string keys like `'general.title'|trans` are known before rendering
+        let key = filter
+            .operand()?
+            .syntax()
+            .first_child()
+            .and_then(TwigLiteralString::cast)?;
+        let value = key.value();
+        if value.has_interpolation {
+            return None;
+        }
+
+        let pattern = &ctx.config().rules.translation_key_regex;
+        if get_naming_regex(pattern).ok()?.is_match(&value.text) {
+            return None;
+        }
+
+        let result = self
+            .create_result(
+                Severity::Warning,
+                "Translation key does not match the key format",
+            )
+            .primary_note(
+                key.syntax().text_range_trimmed_trivia(),
+                format!("help: use a translation key which matches {pattern}"),
+            );
+
+        Some(vec![result])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_with_config};
+    use crate::config::Config;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-translation-key-format",
+            "{{ 'Add to cart'|trans }}{{ 'title'|trans({ a: 1 })|sw_sanitize }}",
+            expect![[r#"
+                warning[twig-translation-key-format]: Translation key does not match the key format
+                  ┌─ ./debug-rule.html.twig:1:4
+                  │
+                1 │ {{ 'Add to cart'|trans }}{{ 'title'|trans({ a: 1 })|sw_sanitize }}
+                  │    ^^^^^^^^^^^^^ help: use a translation key which matches ^[a-zA-Z0-9_-]+(\.[a-zA-Z0-9_-]+)+$
+
+                warning[twig-translation-key-format]: Translation key does not match the key format
+                  ┌─ ./debug-rule.html.twig:1:29
+                  │
+                1 │ {{ 'Add to cart'|trans }}{{ 'title'|trans({ a: 1 })|sw_sanitize }}
+                  │                             ^^^^^^^ help: use a translation key which matches ^[a-zA-Z0-9_-]+(\.[a-zA-Z0-9_-]+)+$
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "twig-translation-key-format",
+            "{{ 'checkout.addToCart'|trans }}{{ 'general.title'|trans({ a: 1 })|sw_sanitize }}{{ key|trans }}{{ \"checkout.#{name}\"|trans }}{{ 'Add to cart'|upper }}{% trans %}Add to cart{% endtrans %}",
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_reports_with_custom_regex() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.translation_key_regex = r"^[a-z0-9]+(\.[a-z0-9-]+)+$".to_string();
+
+        test_rule_with_config(
+            "twig-translation-key-format",
+            "{{ 'checkout.addToCart'|trans }}{{ 'checkout.add-to-cart'|trans }}",
+            config,
+            expect![[r#"
+                warning[twig-translation-key-format]: Translation key does not match the key format
+                  ┌─ ./debug-rule.html.twig:1:4
+                  │
+                1 │ {{ 'checkout.addToCart'|trans }}{{ 'checkout.add-to-cart'|trans }}
+                  │    ^^^^^^^^^^^^^^^^^^^^ help: use a translation key which matches ^[a-z0-9]+(\.[a-z0-9-]+)+$
+
+            "#]],
+        );
+    }
+}
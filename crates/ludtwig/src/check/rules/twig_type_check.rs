@@ -0,0 +1,277 @@
+use ludtwig_parser::syntax::typed::{
+    AstNode, TwigBinaryExpression, TwigFilter, TwigFunctionCall, TwigLiteralString,
+};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode, SyntaxNodeExt};
+use ludtwig_parser::T;
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::signatures::{get_signatures, Signatures, Type};
+
+pub struct RuleTwigTypeCheck;
+
+impl Rule for RuleTwigTypeCheck {
+    fn name(&self) -> &'static str {
+        "twig-type-check"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Values with an obviously wrong type are either an error at runtime or silently produce unexpected output.")
+    }
+
+    fn check_root(&self, _node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        match get_signatures(ctx.config()) {
+            Ok(_) => None,
+            Err(e) => Some(vec![self.create_result(
+                Severity::Error,
+                format!("can't use signatures for type checking: {e}"),
+            )]),
+        }
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let signatures = get_signatures(ctx.config()).ok()?;
+
+        if let Some(filter) = TwigFilter::cast(node.clone()) {
+            return self.check_filter(&filter, &signatures);
+        }
+
+        let binary = TwigBinaryExpression::cast(node)?;
+        self.check_comparison(&binary, &signatures)
+    }
+}
+
+impl RuleTwigTypeCheck {
+    fn check_filter(
+        &self,
+        filter: &TwigFilter,
+        signatures: &Signatures,
+    ) -> Option<Vec<CheckResult>> {
+        let name = filter.name()?;
+        let operand = filter.operand()?;
+        let expected_type = &signatures.filters.get(name.text())?.input;
+        let operand_type = infer_type(operand.syntax(), signatures);
+
+        let is_compatible = match (expected_type, &operand_type) {
+            (Type::Mixed, _) | (_, Type::Mixed) => true,
+            // php converts numeric strings to numbers
+            (Type::Number, Type::String) => is_numeric_string(operand.syntax()),
+            (expected, found) => expected == found,
+        };
+        if is_compatible {
+            return None;
+        }
+
+        let result = self
+            .create_result(
+                Severity::Warning,
+                format!("filter '{}' used on {operand_type}", name.text()),
+            )
+            .primary_note(
+                operand.syntax().text_range_trimmed_trivia(),
+                format!("this is {operand_type}, but the filter expects {expected_type}"),
+            );
+
+        Some(vec![result])
+    }
+
+    fn check_comparison(
+        &self,
+        binary: &TwigBinaryExpression,
+        signatures: &Signatures,
+    ) -> Option<Vec<CheckResult>> {
+        let operator = binary.operator()?;
+        if !matches!(operator.kind(), T!["=="] | T!["!="]) {
+            return None;
+        }
+
+        let lhs_type = infer_type(binary.lhs_expression()?.syntax(), signatures);
+        let rhs_type = infer_type(binary.rhs_expression()?.syntax(), signatures);
+        let is_mismatch = matches!(
+            (&lhs_type, &rhs_type),
+            (Type::String, Type::Boolean) | (Type::Boolean, Type::String)
+        );
+        if !is_mismatch {
+            return None;
+        }
+
+        let result = self
+            .create_result(
+                Severity::Warning,
+                format!("comparison of {lhs_type} with {rhs_type}"),
+            )
+            .primary_note(
+                binary.syntax().text_range_trimmed_trivia(),
+                "a string is only compared by its truthiness here, which is most likely a mistake",
+            );
+
+        Some(vec![result])
+    }
+}
+
+/// Best-effort type of an expression, which only knows about literals, operators and
+/// the return types of filters / functions
+fn infer_type(node: &SyntaxNode, signatures: &Signatures) -> Type {
+    match node.kind() {
+        SyntaxKind::TWIG_EXPRESSION
+        | SyntaxKind::TWIG_OPERAND
+        | SyntaxKind::TWIG_PARENTHESES_EXPRESSION => node
+            .first_child()
+            .map_or(Type::Mixed, |c| infer_type(&c, signatures)),
+        SyntaxKind::TWIG_LITERAL_STRING => Type::String,
+        SyntaxKind::TWIG_LITERAL_NUMBER => Type::Number,
+        SyntaxKind::TWIG_LITERAL_BOOLEAN => Type::Boolean,
+        SyntaxKind::TWIG_LITERAL_NULL => Type::Null,
+        SyntaxKind::TWIG_LITERAL_ARRAY | SyntaxKind::TWIG_LITERAL_HASH => Type::Array,
+        SyntaxKind::TWIG_FILTER => TwigFilter::cast(node.clone())
+            .and_then(|f| f.name())
+            .and_then(|name| signatures.filters.get(name.text()))
+            .map_or(Type::Mixed, |s| s.returns.clone()),
+        SyntaxKind::TWIG_FUNCTION_CALL => TwigFunctionCall::cast(node.clone())
+            .and_then(|f| f.name())
+            .and_then(|name| signatures.functions.get(name.text()))
+            .map_or(Type::Mixed, |s| s.returns.clone()),
+        SyntaxKind::TWIG_BINARY_EXPRESSION => TwigBinaryExpression::cast(node.clone())
+            .and_then(|b| b.operator())
+            .map_or(Type::Mixed, |operator| match operator.kind() {
+                T!["~"] => Type::String,
+                T!["+"] | T!["-"] | T!["*"] | T!["/"] | T!["//"] | T!["%"] | T!["**"] => {
+                    Type::Number
+                }
+                T![".."] => Type::Array,
+                T!["=="]
+                | T!["!="]
+                | T!["<"]
+                | T![">"]
+                | T!["<="]
+                | T![">="]
+                | T!["and"]
+                | T!["or"]
+                | T!["in"]
+                | T!["matches"]
+                | T!["starts with"]
+                | T!["ends with"]
+                | T!["has some"]
+                | T!["has every"]
+                | T!["is"] => Type::Boolean,
+                _ => Type::Mixed,
+            }),
+        SyntaxKind::TWIG_UNARY_EXPRESSION => {
+            node.first_token()
+                .map_or(Type::Mixed, |operator| match operator.kind() {
+                    T!["not"] => Type::Boolean,
+                    T!["+"] | T!["-"] => Type::Number,
+                    _ => Type::Mixed,
+                })
+        }
+        _ => Type::Mixed,
+    }
+}
+
+/// Is the node a plain string literal which contains a number, like `'5'`
+fn is_numeric_string(node: &SyntaxNode) -> bool {
+    node.descendants()
+        .find_map(TwigLiteralString::cast)
+        .map(|s| s.value())
+        .is_some_and(|value| !value.has_interpolation && value.text.trim().parse::<f64>().is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_with_config};
+    use crate::config::Config;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-type-check",
+            r#"{{ 'text'|abs }}
+{{ 5|upper }}
+{{ (a ~ b)|keys }}
+{% if 'yes' == true %}{% endif %}
+{{ [1, 2]|join(', ')|round }}"#,
+            expect![[r#"
+                warning[twig-type-check]: filter 'abs' used on a string
+                  ┌─ ./debug-rule.html.twig:1:4
+                  │
+                1 │ {{ 'text'|abs }}
+                  │    ^^^^^^ this is a string, but the filter expects a number
+
+                warning[twig-type-check]: filter 'upper' used on a number
+                  ┌─ ./debug-rule.html.twig:2:4
+                  │
+                2 │ {{ 5|upper }}
+                  │    ^ this is a number, but the filter expects a string
+
+                warning[twig-type-check]: filter 'keys' used on a string
+                  ┌─ ./debug-rule.html.twig:3:4
+                  │
+                3 │ {{ (a ~ b)|keys }}
+                  │    ^^^^^^^ this is a string, but the filter expects an array
+
+                warning[twig-type-check]: comparison of a string with a boolean
+                  ┌─ ./debug-rule.html.twig:4:7
+                  │
+                4 │ {% if 'yes' == true %}{% endif %}
+                  │       ^^^^^^^^^^^^^ a string is only compared by its truthiness here, which is most likely a mistake
+
+                warning[twig-type-check]: filter 'round' used on a string
+                  ┌─ ./debug-rule.html.twig:5:4
+                  │
+                5 │ {{ [1, 2]|join(', ')|round }}
+                  │    ^^^^^^^^^^^^^^^^^ this is a string, but the filter expects a number
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule(
+            "twig-type-check",
+            r#"{{ '5'|abs }}
+{{ name|upper }}
+{{ 'text'|upper|lower }}
+{{ [1, 2]|join(', ')|upper }}
+{{ (a + b)|abs }}
+{{ { a: 1 }|keys }}
+{% if name == true %}{% endif %}
+{% if 'yes' == 'no' %}{% endif %}
+{{ 'text'|unknown_filter }}"#,
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_reports_with_custom_signatures() {
+        let path = std::env::temp_dir().join("ludtwig-signatures-type-check.json");
+        std::fs::write(
+            &path,
+            r#"{"filters": {"price": {"input": "number", "returns": "string"}}}"#,
+        )
+        .unwrap();
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.signatures_files = vec![path.to_string_lossy().to_string()];
+
+        test_rule_with_config(
+            "twig-type-check",
+            "{{ 'free'|price }}\n{{ 5|price|abs }}",
+            config,
+            expect![[r#"
+                warning[twig-type-check]: filter 'price' used on a string
+                  ┌─ ./debug-rule.html.twig:1:4
+                  │
+                1 │ {{ 'free'|price }}
+                  │    ^^^^^^ this is a string, but the filter expects a number
+
+                warning[twig-type-check]: filter 'abs' used on a string
+                  ┌─ ./debug-rule.html.twig:2:4
+                  │
+                2 │ {{ 5|price|abs }}
+                  │    ^^^^^^^ this is a string, but the filter expects a number
+
+            "#]],
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use ludtwig_parser::syntax::typed::{AstNode, TwigApplyStartingBlock, TwigFilter, TwigLiteralName};
+use ludtwig_parser::syntax::untyped::{SyntaxNode, SyntaxToken};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::signatures::{did_you_mean, get_signatures};
+
+pub struct RuleTwigUnknownFilter;
+
+impl Rule for RuleTwigUnknownFilter {
+    fn name(&self) -> &'static str {
+        "twig-unknown-filter"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Twig throws an exception for unknown filters when the template is compiled.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let name = if let Some(filter) = TwigFilter::cast(node.clone()) {
+            filter.name()?
+        } else {
+            // the first filter of `{% apply lower|escape %}` has no pipe in front of it
+            let apply = TwigApplyStartingBlock::cast(node)?;
+            first_apply_filter(&apply)?
+        };
+
+        let signatures = get_signatures(ctx.config()).ok()?;
+        let known_filters = &ctx.config().rules.known_filters;
+        if signatures.filters.contains_key(name.text())
+            || known_filters.iter().any(|f| f == name.text())
+        {
+            return None;
+        }
+
+        let mut result = self
+            .create_result(
+                Severity::Error,
+                format!("unknown twig filter '{}'", name.text()),
+            )
+            .primary_note(
+                name.text_range(),
+                "not a known filter, custom filters can be added to the known-filters in the config",
+            );
+
+        let known_names = signatures
+            .filters
+            .keys()
+            .chain(known_filters)
+            .map(String::as_str);
+        if let Some(similar) = did_you_mean(name.text(), known_names) {
+            result = result.secondary_note(name.text_range(), format!("did you mean '{similar}'?"));
+        }
+
+        Some(vec![result])
+    }
+}
+
+/// Name of the filter which is applied first, the innermost operand of the filter chain
+pub(super) fn first_apply_filter(apply: &TwigApplyStartingBlock) -> Option<SyntaxToken> {
+    let mut node = apply
+        .syntax()
+        .children()
+        .find(|c| TwigFilter::can_cast(c.kind()) || TwigLiteralName::can_cast(c.kind()))?;
+
+    while let Some(filter) = TwigFilter::cast(node.clone()) {
+        node = filter.operand()?.syntax().first_child()?;
+    }
+
+    TwigLiteralName::cast(node)?.name()
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_does_not_fix, test_rule_with_config};
+    use crate::config::Config;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-unknown-filter",
+            "{{ data|jsone_encode|upper }}{% apply lowr|escape %}a{% endapply %}{{ a|camelize }}",
+            expect![[r#"
+                error[twig-unknown-filter]: unknown twig filter 'jsone_encode'
+                  ┌─ ./debug-rule.html.twig:1:9
+                  │
+                1 │ {{ data|jsone_encode|upper }}{% apply lowr|escape %}a{% endapply %}{{ a|camelize }}
+                  │         ^^^^^^^^^^^^
+                  │         │
+                  │         not a known filter, custom filters can be added to the known-filters in the config
+                  │         did you mean 'json_encode'?
+
+                error[twig-unknown-filter]: unknown twig filter 'lowr'
+                  ┌─ ./debug-rule.html.twig:1:39
+                  │
+                1 │ {{ data|jsone_encode|upper }}{% apply lowr|escape %}a{% endapply %}{{ a|camelize }}
+                  │                                       ^^^^
+                  │                                       │
+                  │                                       not a known filter, custom filters can be added to the known-filters in the config
+                  │                                       did you mean 'lower'?
+
+                error[twig-unknown-filter]: unknown twig filter 'camelize'
+                  ┌─ ./debug-rule.html.twig:1:73
+                  │
+                1 │ {{ data|jsone_encode|upper }}{% apply lowr|escape %}a{% endapply %}{{ a|camelize }}
+                  │                                                                         ^^^^^^^^ not a known filter, custom filters can be added to the known-filters in the config
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_fix() {
+        // custom filters can be close to a built-in name, renaming them would change the template
+        test_rule_does_not_fix(
+            "twig-unknown-filter",
+            "{{ data|jsone_encode|uper }}{% apply lowr|escape('html') %}a{% endapply %}",
+            expect!["{{ data|jsone_encode|uper }}{% apply lowr|escape('html') %}a{% endapply %}"],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_known_filters() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.known_filters = vec!["camelize".to_string()];
+
+        test_rule_with_config(
+            "twig-unknown-filter",
+            "{{ a|camelize|sw_sanitize|trans|format_currency('EUR') }}{{ b|json_decode|intval|sanitize_html }}{% apply upper %}a{% endapply %}",
+            config,
+            expect![[r#""#]],
+        );
+    }
+}
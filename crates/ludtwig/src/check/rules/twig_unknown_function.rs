@@ -0,0 +1,139 @@
+use ludtwig_parser::syntax::typed::{
+    AstNode, TwigBinaryExpression, TwigFrom, TwigFunctionCall, TwigLiteralName,
+};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::rules::twig_unknown_test::test_name;
+use crate::check::signatures::{did_you_mean, get_signatures};
+
+pub struct RuleTwigUnknownFunction;
+
+impl Rule for RuleTwigUnknownFunction {
+    fn name(&self) -> &'static str {
+        "twig-unknown-function"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Twig throws an exception for unknown functions when the template is compiled.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let call = TwigFunctionCall::cast(node)?;
+        // method calls like `product.getName()` have no plain name
+        let name = call.name()?;
+
+        // tests with arguments like `is same as(false)` are checked by twig-unknown-test
+        let is_test = call
+            .syntax()
+            .parent()
+            .and_then(|expression| expression.parent())
+            .and_then(TwigBinaryExpression::cast)
+            .and_then(|binary| test_name(&binary))
+            .is_some_and(|test| test == name);
+        if is_test {
+            return None;
+        }
+
+        let signatures = get_signatures(ctx.config()).ok()?;
+        let known_functions = &ctx.config().rules.known_functions;
+        if signatures.functions.contains_key(name.text())
+            || known_functions.iter().any(|f| f == name.text())
+            || is_imported_macro(&call, name.text())
+        {
+            return None;
+        }
+
+        let mut result = self
+            .create_result(
+                Severity::Error,
+                format!("unknown twig function '{}'", name.text()),
+            )
+            .primary_note(
+                name.text_range(),
+                "not a known function, custom functions can be added to the known-functions in the config",
+            );
+
+        let known_names = signatures
+            .functions
+            .keys()
+            .chain(known_functions)
+            .map(String::as_str);
+        if let Some(similar) = did_you_mean(name.text(), known_names) {
+            result = result.secondary_note(name.text_range(), format!("did you mean '{similar}'?"));
+        }
+
+        Some(vec![result])
+    }
+}
+
+/// Is the name imported as a macro with `{% from 'macros.html.twig' import name %}`
+fn is_imported_macro(call: &TwigFunctionCall, name: &str) -> bool {
+    let Some(root) = call.syntax().ancestors().last() else {
+        return false;
+    };
+
+    root.descendants()
+        .filter_map(TwigFrom::cast)
+        .flat_map(|from| from.syntax().children())
+        .filter(|c| c.kind() == SyntaxKind::TWIG_OVERRIDE)
+        // the local name comes last, like `input_field` in `input as input_field`
+        .filter_map(|o| o.children().filter_map(TwigLiteralName::cast).last())
+        .any(|imported| imported.name().is_some_and(|n| n.text() == name))
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_does_not_fix, test_rule_with_config};
+    use crate::config::Config;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-unknown-function",
+            "{{ djump(a) }}{{ product.getName() }}{{ render_widget() }}",
+            expect![[r#"
+                error[twig-unknown-function]: unknown twig function 'djump'
+                  ┌─ ./debug-rule.html.twig:1:4
+                  │
+                1 │ {{ djump(a) }}{{ product.getName() }}{{ render_widget() }}
+                  │    ^^^^^
+                  │    │
+                  │    not a known function, custom functions can be added to the known-functions in the config
+                  │    did you mean 'dump'?
+
+                error[twig-unknown-function]: unknown twig function 'render_widget'
+                  ┌─ ./debug-rule.html.twig:1:41
+                  │
+                1 │ {{ djump(a) }}{{ product.getName() }}{{ render_widget() }}
+                  │                                         ^^^^^^^^^^^^^ not a known function, custom functions can be added to the known-functions in the config
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_fix() {
+        // custom functions can be close to a built-in name, renaming them would change the template
+        test_rule_does_not_fix(
+            "twig-unknown-function",
+            "{{ djump(a) }}{{ pathh('frontend.home.page') }}",
+            expect!["{{ djump(a) }}{{ pathh('frontend.home.page') }}"],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_known_functions() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.known_functions = vec!["render_widget".to_string()];
+
+        test_rule_with_config(
+            "twig-unknown-function",
+            "{% from 'forms.html.twig' import input as field, textarea %}{{ field() }}{{ textarea() }}{{ render_widget() }}{{ a is same as(b) }}{{ field_name(form.email) }}{{ enum_cases(a) }}{{ sw_breadcrumb_full(a, b) }}",
+            config,
+            expect![[r#""#]],
+        );
+    }
+}
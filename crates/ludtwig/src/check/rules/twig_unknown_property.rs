@@ -0,0 +1,176 @@
+use ludtwig_parser::syntax::typed::{AstNode, TwigAccessor, TwigComment, TwigLiteralName};
+use ludtwig_parser::syntax::untyped::SyntaxNode;
+
+use crate::check::class_properties::get_class_properties;
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::signatures::did_you_mean;
+
+pub struct RuleTwigUnknownProperty;
+
+impl Rule for RuleTwigUnknownProperty {
+    fn name(&self) -> &'static str {
+        "twig-unknown-property"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Twig renders nothing for properties which don't exist (or throws an exception in strict mode). Variables annotated with `{# @var product \\Class\\Name #}` are checked against the properties of the class in the configured class-properties-files.")
+    }
+
+    fn check_root(&self, _node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        match get_class_properties(ctx.config()) {
+            Ok(_) => None,
+            Err(e) => Some(vec![self.create_result(
+                Severity::Error,
+                format!("can't load class-properties-files: {e}"),
+            )]),
+        }
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        if ctx.config().rules.class_properties_files.is_empty() {
+            return None;
+        }
+
+        let accessor = TwigAccessor::cast(node)?;
+        // only direct access on a variable like `product.name`, the type of `a.b.c` is not known
+        let variable = accessor
+            .operand()?
+            .syntax()
+            .first_child()
+            .and_then(TwigLiteralName::cast)?
+            .name()?;
+        let property = accessor.property_name()?;
+
+        let class_name = annotated_class(accessor.syntax(), variable.text())?;
+        let class_properties = get_class_properties(ctx.config()).ok()?;
+        let known_names = class_properties.accessible_names(&class_name)?;
+
+        // twig looks up methods case-insensitive
+        if known_names
+            .iter()
+            .any(|known| known.eq_ignore_ascii_case(property.text()))
+        {
+            return None;
+        }
+
+        let mut result = self
+            .create_result(
+                Severity::Warning,
+                format!(
+                    "unknown property '{}' of '{}'",
+                    property.text(),
+                    variable.text()
+                ),
+            )
+            .primary_note(
+                property.text_range(),
+                format!("{class_name} doesn't have this property"),
+            );
+
+        if let Some(similar) = did_you_mean(property.text(), known_names.iter().map(String::as_str))
+        {
+            result =
+                result.secondary_note(property.text_range(), format!("did you mean '{similar}'?"));
+        }
+
+        Some(vec![result])
+    }
+}
+
+/// Class name of the `{# @var name \Class\Name #}` annotation for the variable anywhere in the template
+fn annotated_class(node: &SyntaxNode, variable: &str) -> Option<String> {
+    let root = node.ancestors().last()?;
+
+    root.descendants()
+        .filter_map(TwigComment::cast)
+        .flat_map(|comment| comment.annotations())
+        .find_map(|annotation| {
+            let (name, type_name) = annotation.variable_type()?;
+            // collections like `ProductEntity[]` are not accessed like an instance
+            (name == variable && !type_name.ends_with("[]")).then(|| type_name.to_string())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use expect_test::expect;
+
+    use crate::check::rules::test::test_rule_with_config;
+    use crate::check::rules::RuleRegistry;
+    use crate::config::Config;
+    use crate::process::preview_fix;
+
+    fn config() -> Config {
+        let path = std::env::temp_dir().join("ludtwig-class-properties-rule.json");
+        std::fs::write(
+            &path,
+            r#"{"\\App\\Product": ["id", "name", "getTranslated", "isActive", "getPrice"]}"#,
+        )
+        .unwrap();
+
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.class_properties_files = vec![path.to_string_lossy().to_string()];
+        config
+    }
+
+    #[test]
+    fn rule_reports() {
+        test_rule_with_config(
+            "twig-unknown-property",
+            r#"{# @var product \App\Product #}
+{{ product.nmae }}
+{{ product.description|default(product.translated.description) }}"#,
+            config(),
+            expect![[r#"
+                warning[twig-unknown-property]: unknown property 'nmae' of 'product'
+                  ┌─ ./debug-rule.html.twig:2:12
+                  │
+                2 │ {{ product.nmae }}
+                  │            ^^^^
+                  │            │
+                  │            \App\Product doesn't have this property
+                  │            did you mean 'name'?
+
+                warning[twig-unknown-property]: unknown property 'description' of 'product'
+                  ┌─ ./debug-rule.html.twig:3:12
+                  │
+                3 │ {{ product.description|default(product.translated.description) }}
+                  │            ^^^^^^^^^^^ \App\Product doesn't have this property
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule_with_config(
+            "twig-unknown-property",
+            r#"{#
+    @var product \App\Product
+    @var products \App\Product[]
+#}
+{{ product.id }}{{ product.name }}{{ product.translated.name }}{{ product.getTranslated() }}
+{% if product.active and product.isActive() %}{{ product.price }}{% endif %}
+{{ products.first }}{{ category.nmae }}{{ product[0] }}"#,
+            config(),
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_fix() {
+        // the property list of the class may be incomplete (for example magic getters)
+        expect![[r#"{# @var product App\Product #}{{ product.nmae }}"#]].assert_eq(
+            &preview_fix(
+                Path::new("./debug-rule.html.twig"),
+                "{# @var product App\\Product #}{{ product.nmae }}",
+                "twig-unknown-property",
+                &RuleRegistry::default(),
+                config(),
+            )
+            .unwrap(),
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use ludtwig_parser::syntax::typed::{
+    AstNode, TwigBinaryExpression, TwigFunctionCall, TwigLiteralName,
+};
+use ludtwig_parser::syntax::untyped::{SyntaxNode, SyntaxToken};
+use ludtwig_parser::T;
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::signatures::{did_you_mean, get_signatures};
+
+pub struct RuleTwigUnknownTest;
+
+impl Rule for RuleTwigUnknownTest {
+    fn name(&self) -> &'static str {
+        "twig-unknown-test"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Twig throws an exception for unknown tests when the template is compiled.")
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let binary = TwigBinaryExpression::cast(node)?;
+        let name = test_name(&binary)?;
+
+        let signatures = get_signatures(ctx.config()).ok()?;
+        let known_tests = &ctx.config().rules.known_tests;
+        if signatures.tests.contains_key(name.text())
+            || known_tests.iter().any(|t| t == name.text())
+        {
+            return None;
+        }
+
+        let mut result = self
+            .create_result(
+                Severity::Error,
+                format!("unknown twig test '{}'", name.text()),
+            )
+            .primary_note(
+                name.text_range(),
+                "not a known test, custom tests can be added to the known-tests in the config",
+            );
+
+        let known_names = signatures
+            .tests
+            .keys()
+            .chain(known_tests)
+            .map(String::as_str);
+        if let Some(similar) = did_you_mean(name.text(), known_names) {
+            result = result.secondary_note(name.text_range(), format!("did you mean '{similar}'?"));
+        }
+
+        Some(vec![result])
+    }
+}
+
+/// Name of the test on the right side of `is` / `is not`, like `defined` or `divisible by`
+pub(super) fn test_name(binary: &TwigBinaryExpression) -> Option<SyntaxToken> {
+    if binary.operator()?.kind() != T!["is"] {
+        return None;
+    }
+
+    let test = binary.rhs_expression()?.syntax().first_child()?;
+    match TwigFunctionCall::cast(test.clone()) {
+        // tests with arguments, like `same as(false)`
+        Some(call) => call.name(),
+        None => TwigLiteralName::cast(test)?.name(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_does_not_fix, test_rule_with_config};
+    use crate::config::Config;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-unknown-test",
+            "{% if a is definedd %}{% endif %}{{ b is not divisible by(3) }}{{ c is numeric }}",
+            expect![[r#"
+                error[twig-unknown-test]: unknown twig test 'definedd'
+                  ┌─ ./debug-rule.html.twig:1:12
+                  │
+                1 │ {% if a is definedd %}{% endif %}{{ b is not divisible by(3) }}{{ c is numeric }}
+                  │            ^^^^^^^^
+                  │            │
+                  │            not a known test, custom tests can be added to the known-tests in the config
+                  │            did you mean 'defined'?
+
+                error[twig-unknown-test]: unknown twig test 'numeric'
+                  ┌─ ./debug-rule.html.twig:1:72
+                  │
+                1 │ {% if a is definedd %}{% endif %}{{ b is not divisible by(3) }}{{ c is numeric }}
+                  │                                                                        ^^^^^^^ not a known test, custom tests can be added to the known-tests in the config
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_fix() {
+        // custom tests can be close to a built-in name, renaming them would change the template
+        test_rule_does_not_fix(
+            "twig-unknown-test",
+            "{% if a is definedd and b is not empti %}{% endif %}",
+            expect!["{% if a is definedd and b is not empti %}{% endif %}"],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_known_tests() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.known_tests = vec!["numeric".to_string()];
+
+        test_rule_with_config(
+            "twig-unknown-test",
+            "{{ a is numeric }}{{ a is same as(b) }}{{ a is null }}{{ a is not iterable }}{{ form is rootform }}",
+            config,
+            expect![[r#""#]],
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+
+use ludtwig_parser::syntax::scope::{
+    Definition, DefinitionKind, ScopeAnalyzer, ScopeId, ScopeKind,
+};
+use ludtwig_parser::syntax::typed::{AstNode, TwigFunctionCall};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode, TextRange, TextSize};
+
+use crate::check::is_ignored_by_directive;
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+
+/// Functions which render other templates or blocks with the current context
+const CONTEXT_SHARING_FUNCTIONS: [&str; 3] = ["include", "block", "parent"];
+
+pub struct RuleTwigUnusedVariable;
+
+impl Rule for RuleTwigUnusedVariable {
+    fn name(&self) -> &'static str {
+        "twig-unused-variable"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Variables which are never read are most likely leftovers or typos of the name which is used later on.")
+    }
+
+    fn check_root(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let analyzer = ScopeAnalyzer::new(&node);
+        let usages = Usages::new(&analyzer, &node);
+        let ignore_underscore = ctx.config().rules.ignore_underscore_variables;
+
+        let results = analyzer
+            .definitions()
+            .iter()
+            .filter(|d| {
+                matches!(d.kind, DefinitionKind::Set | DefinitionKind::MacroParameter)
+                    && !(ignore_underscore && d.name.starts_with('_'))
+            })
+            .filter(|d| !usages.is_used(d) && !usages.is_context_shared(&analyzer, d))
+            .filter(|d| !self.is_ignored(&node, d.range))
+            .map(|d| {
+                let (message, label) = match d.kind {
+                    DefinitionKind::MacroParameter => (
+                        format!("macro parameter '{}' is never used", d.name),
+                        "remove this parameter or use it in the macro",
+                    ),
+                    _ => (
+                        format!("variable '{}' is set but never used", d.name),
+                        "remove this variable or use it afterwards",
+                    ),
+                };
+
+                self.create_result(Severity::Warning, message)
+                    .primary_note(d.range, label)
+            })
+            .collect();
+
+        Some(results)
+    }
+}
+
+impl RuleTwigUnusedVariable {
+    /// Whether the definition is inside of an error node or ignored with a ludtwig-ignore directive
+    fn is_ignored(&self, root: &SyntaxNode, range: TextRange) -> bool {
+        let Some(parent) = root.covering_element(range).parent() else {
+            return false;
+        };
+
+        parent
+            .ancestors()
+            .any(|a| a.kind() == SyntaxKind::ERROR || is_ignored_by_directive(&a, self.name()))
+    }
+}
+
+/// Everything about the reads of variables which is needed to check all definitions,
+/// collected once for the whole template
+struct Usages<'a> {
+    /// The last definition with a reference to it by scope and name
+    last_read: HashMap<(ScopeId, &'a str), TextSize>,
+    /// The template extends a parent template
+    extends: bool,
+    /// Ranges of `_context` reads and of includes / embeds / function calls which render with the current context
+    context_reads: Vec<TextRange>,
+}
+
+impl<'a> Usages<'a> {
+    fn new(analyzer: &'a ScopeAnalyzer, root: &SyntaxNode) -> Self {
+        let mut last_read: HashMap<(ScopeId, &str), TextSize> = HashMap::new();
+        for definition in analyzer
+            .references()
+            .iter()
+            .filter_map(|r| r.definition.map(|id| analyzer.definition(id)))
+        {
+            let visible_from = last_read
+                .entry((definition.scope, definition.name.as_str()))
+                .or_insert(definition.visible_from);
+            *visible_from = (*visible_from).max(definition.visible_from);
+        }
+
+        let extends = root.children().any(|c| {
+            matches!(
+                c.kind(),
+                SyntaxKind::TWIG_EXTENDS | SyntaxKind::SHOPWARE_TWIG_SW_EXTENDS
+            )
+        });
+
+        let context_reads = analyzer
+            .references()
+            .iter()
+            .filter(|r| r.name == "_context")
+            .map(|r| r.range)
+            .chain(
+                root.descendants()
+                    .filter(|n| match n.kind() {
+                        SyntaxKind::TWIG_INCLUDE
+                        | SyntaxKind::SHOPWARE_TWIG_SW_INCLUDE
+                        | SyntaxKind::TWIG_EMBED => true,
+                        _ => TwigFunctionCall::cast(n.clone())
+                            .and_then(|call| call.name())
+                            .is_some_and(|name| CONTEXT_SHARING_FUNCTIONS.contains(&name.text())),
+                    })
+                    .map(|n| n.text_range()),
+            )
+            .collect();
+
+        Self {
+            last_read,
+            extends,
+            context_reads,
+        }
+    }
+
+    /// Whether the variable is read by any reference to it or to a later assignment of it in the same scope,
+    /// which may only be assigned conditionally like `{% if a %}{% set b = 2 %}{% endif %}`
+    fn is_used(&self, definition: &Definition) -> bool {
+        self.last_read
+            .get(&(definition.scope, definition.name.as_str()))
+            .is_some_and(|visible_from| *visible_from >= definition.visible_from)
+    }
+
+    /// Whether the variable may be read outside of the template or outside of its scope,
+    /// like by included templates, parent templates / blocks or by `_context`
+    fn is_context_shared(&self, analyzer: &ScopeAnalyzer, definition: &Definition) -> bool {
+        let scope = analyzer.scope(definition.scope);
+
+        // assignments inside of loops update variables of the outer scope
+        if scope.kind == ScopeKind::For
+            && scope.parent.is_some_and(|parent| {
+                analyzer
+                    .resolve(&definition.name, parent, definition.range.start())
+                    .is_some()
+            })
+        {
+            return true;
+        }
+
+        // variables outside of blocks are passed to the parent template
+        if scope.kind == ScopeKind::Template && self.extends {
+            return true;
+        }
+
+        let visible_range = TextRange::new(
+            definition.visible_from,
+            scope.range.end().max(definition.visible_from),
+        );
+        self.context_reads
+            .iter()
+            .any(|range| visible_range.contains_range(*range))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{test_rule, test_rule_with_config};
+    use crate::config::Config;
+
+    #[test]
+    fn rule_reports() {
+        test_rule(
+            "twig-unused-variable",
+            "{% set a, b = 1, 2 %}{{ b }}{% macro m(x, y) %}{{ x }}{% endmacro %}",
+            expect![[r#"
+                warning[twig-unused-variable]: variable 'a' is set but never used
+                  ┌─ ./debug-rule.html.twig:1:8
+                  │
+                1 │ {% set a, b = 1, 2 %}{{ b }}{% macro m(x, y) %}{{ x }}{% endmacro %}
+                  │        ^ remove this variable or use it afterwards
+
+                warning[twig-unused-variable]: macro parameter 'y' is never used
+                  ┌─ ./debug-rule.html.twig:1:43
+                  │
+                1 │ {% set a, b = 1, 2 %}{{ b }}{% macro m(x, y) %}{{ x }}{% endmacro %}
+                  │                                           ^ remove this parameter or use it in the macro
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_conditional_and_loop_assignments() {
+        test_rule(
+            "twig-unused-variable",
+            r#"{% set label = 'a' %}{% if b %}{% set label = 'b' %}{% endif %}{{ label }}
+{% set total = 0 %}{% for item in items %}{% set total = total + item %}{% endfor %}{{ total }}"#,
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_shared_context() {
+        test_rule(
+            "twig-unused-variable",
+            r#"{% block a %}{% set title = 'a' %}{{ parent() }}{% endblock %}
+{% block b %}{% set title = 'b' %}{% sw_include '@Storefront/title.html.twig' %}{% endblock %}
+{% block c %}{% set title = 'c' %}{{ dump(_context) }}{% endblock %}"#,
+            expect![[r#""#]],
+        );
+
+        test_rule(
+            "twig-unused-variable",
+            "{% extends 'base.html.twig' %}{% set title = 'a' %}",
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_ignores_underscore_names() {
+        test_rule(
+            "twig-unused-variable",
+            "{% set _unused = 1 %}",
+            expect![[r#""#]],
+        );
+
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.ignore_underscore_variables = false;
+
+        test_rule_with_config(
+            "twig-unused-variable",
+            "{% set _unused = 1 %}",
+            config,
+            expect![[r#"
+                warning[twig-unused-variable]: variable '_unused' is set but never used
+                  ┌─ ./debug-rule.html.twig:1:8
+                  │
+                1 │ {% set _unused = 1 %}
+                  │        ^^^^^^^ remove this variable or use it afterwards
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_respects_ignore_directives() {
+        test_rule(
+            "twig-unused-variable",
+            r#"{# ludtwig-ignore twig-unused-variable #}
+{% set a = 1 %}
+{# ludtwig-ignore #}
+{% macro m(x) %}{% endmacro %}"#,
+            expect![[r#""#]],
+        );
+    }
+}
@@ -11,6 +11,10 @@ impl Rule for RuleTwigUseIsNotSameAs {
         "twig-use-is-not-same-as"
     }
 
+    fn explanation(&self) -> Option<&'static str> {
+        Some("The '!==' operator is not valid in twig, 'is not same as' compares strictly.")
+    }
+
     fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
         let binary = TwigBinaryExpression::cast(node)?;
         let op = binary.operator()?;
@@ -11,6 +11,10 @@ impl Rule for RuleTwigUseIsSameAs {
         "twig-use-is-same-as"
     }
 
+    fn explanation(&self) -> Option<&'static str> {
+        Some("The '===' operator is not valid in twig, 'is same as' compares strictly.")
+    }
+
     fn check_node(&self, node: SyntaxNode, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
         let binary = TwigBinaryExpression::cast(node)?;
         let op = binary.operator()?;
@@ -9,6 +9,10 @@ impl Rule for RuleUnknownToken {
         "unknown-token"
     }
 
+    fn explanation(&self) -> Option<&'static str> {
+        Some("The parser didn't understand this code, so it is most likely a syntax error.")
+    }
+
     fn check_token(&self, token: SyntaxToken, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
         if token.kind() != SyntaxKind::TK_UNKNOWN {
             return None;
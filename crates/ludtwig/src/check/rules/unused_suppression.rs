@@ -0,0 +1,304 @@
+use ludtwig_parser::syntax::typed::{
+    AstNode, LudtwigDirectiveFileIgnore, LudtwigDirectiveIgnore, LudtwigDirectiveRuleList,
+};
+use ludtwig_parser::syntax::untyped::{
+    SyntaxElement, SyntaxKind, SyntaxNode, SyntaxNodeExt, SyntaxToken, TextRange,
+};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, Severity};
+use crate::check::rules::RuleRegistry;
+use crate::check::{is_ignored_by_directive, Suppressions};
+
+/// Reports `ludtwig-ignore` and `ludtwig-ignore-file` directives, which don't suppress any finding (anymore).
+/// Doesn't check the template itself: `run_rules` keeps track of the findings which are suppressed
+/// by the innermost directive around them (see `run_rules_with_suppressions`).
+/// Rules which check the whole file at once (`check_root`) count a directive as used
+/// as soon as they skip a node because of it.
+pub struct RuleUnusedSuppression;
+
+impl Rule for RuleUnusedSuppression {
+    fn name(&self) -> &'static str {
+        "unused-suppression"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Ignore directives which don't suppress any finding are stale after the code or the rules changed. They hide new problems in the ignored code and should be removed.")
+    }
+}
+
+impl RuleUnusedSuppression {
+    /// Report every directive (or rule of a directive), that didn't suppress anything.
+    /// Rules of `ludtwig-ignore-file` directives are checked against the `config_rules`,
+    /// because they are not part of the `file_rules`.
+    pub fn check_suppressions(
+        &self,
+        root: &SyntaxNode,
+        rule_registry: &RuleRegistry,
+        file_rules: &[&'static dyn Rule],
+        config_rules: &[&'static dyn Rule],
+        suppressions: &Suppressions,
+    ) -> Vec<CheckResult> {
+        let directives: Vec<(SyntaxNode, &[&'static dyn Rule])> = root
+            .descendants()
+            .filter_map(|node| {
+                if LudtwigDirectiveIgnore::can_cast(node.kind()) {
+                    Some((node, file_rules))
+                } else if LudtwigDirectiveFileIgnore::can_cast(node.kind()) {
+                    Some((node, config_rules))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        directives
+            .iter()
+            .filter(|(directive, _)| !is_ignored_by_directive(directive, self.name()))
+            .flat_map(|(directive, active_rules)| {
+                let range = directive.text_range();
+                let mut used: Vec<&str> = suppressions
+                    .used_directives
+                    .iter()
+                    .filter(|(directive_range, _)| *directive_range == range)
+                    .map(|(_, rule_name)| rule_name.as_str())
+                    .collect();
+
+                // directives which ignore this rule for the following directive are used as well
+                if directive.next_sibling().is_some_and(|next| {
+                    (LudtwigDirectiveIgnore::can_cast(next.kind())
+                        || LudtwigDirectiveFileIgnore::can_cast(next.kind()))
+                        && is_ignored_by_directive(&next, self.name())
+                }) {
+                    used.push(self.name());
+                }
+
+                self.check_directive(directive, rule_registry, active_rules, &used)
+            })
+            .collect()
+    }
+
+    fn check_directive(
+        &self,
+        directive: &SyntaxNode,
+        rule_registry: &RuleRegistry,
+        active_rules: &[&'static dyn Rule],
+        used: &[&str],
+    ) -> Vec<CheckResult> {
+        let rule_names: Vec<SyntaxToken> = directive
+            .children()
+            .find_map(LudtwigDirectiveRuleList::cast)
+            .map(|list| {
+                list.syntax()
+                    .children_with_tokens()
+                    .filter_map(SyntaxElement::into_token)
+                    .filter(|t| t.kind() == SyntaxKind::TK_WORD)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let unused: Vec<&SyntaxToken> = rule_names
+            .iter()
+            .filter(|name| !used.contains(&name.text()))
+            .collect();
+
+        let all_unused = if rule_names.is_empty() {
+            used.is_empty()
+        } else {
+            unused.len() == rule_names.len()
+        };
+        let kind = if LudtwigDirectiveFileIgnore::can_cast(directive.kind()) {
+            "ludtwig-ignore-file"
+        } else {
+            "ludtwig-ignore"
+        };
+        if all_unused {
+            let result = self
+                .create_result(Severity::Warning, format!("Unused {kind} directive"))
+                .primary_note(
+                    directive.text_range_trimmed_trivia(),
+                    "no finding is suppressed by this directive",
+                )
+                .suggestion(directive.text_range(), "", "Remove the directive");
+            return vec![result];
+        }
+
+        let reason = |name: &SyntaxToken| {
+            if rule_registry.get(name.text()).is_err() {
+                "this rule doesn't exist"
+            } else if !active_rules.iter().any(|r| r.name() == name.text()) {
+                "this rule is not active"
+            } else {
+                "no finding of this rule is suppressed by this directive"
+            }
+        };
+
+        let Some((first, others)) = unused.split_first() else {
+            return vec![];
+        };
+        let (Some(list_start), Some(list_end)) = (rule_names.first(), rule_names.last()) else {
+            return vec![];
+        };
+        let used_names: Vec<&str> = rule_names
+            .iter()
+            .filter(|name| !unused.contains(name))
+            .map(SyntaxToken::text)
+            .collect();
+
+        let mut result = self
+            .create_result(
+                Severity::Warning,
+                format!("Unused rule in {kind} directive"),
+            )
+            .primary_note(first.text_range(), reason(first));
+        for name in others {
+            result = result.secondary_note(name.text_range(), reason(name));
+        }
+        result = result.suggestion(
+            TextRange::new(list_start.text_range().start(), list_end.text_range().end()),
+            used_names.join(", "),
+            "Remove the unused rules",
+        );
+
+        vec![result]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::{debug_suppressions, test_rules, test_rules_with_config};
+    use crate::config::Config;
+
+    #[test]
+    fn rule_reports() {
+        test_rules(
+            &["unused-suppression", "twig-logic-and", "twig-logic-or"],
+            r#"{# ludtwig-ignore twig-logic-and #}
+{{ a and b }}
+{# ludtwig-ignore twig-logic-and, twig-logic-or, html-self-closing, not-a-rule #}
+{{ a && b }}
+{# ludtwig-ignore #}
+<div>{{ a }}</div>"#,
+            expect![[r#"
+                warning[unused-suppression]: Unused ludtwig-ignore directive
+                  ┌─ ./debug-rule.html.twig:1:1
+                  │
+                1 │ {# ludtwig-ignore twig-logic-and #}
+                  │ ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+                  │ │
+                  │ no finding is suppressed by this directive
+                  │ Remove the directive: 
+
+                warning[unused-suppression]: Unused rule in ludtwig-ignore directive
+                  ┌─ ./debug-rule.html.twig:3:35
+                  │
+                3 │ {# ludtwig-ignore twig-logic-and, twig-logic-or, html-self-closing, not-a-rule #}
+                  │                   ----------------^^^^^^^^^^^^^-------------------------------
+                  │                   │               │              │                  │
+                  │                   │               │              │                  this rule doesn't exist
+                  │                   │               │              this rule is not active
+                  │                   │               no finding of this rule is suppressed by this directive
+                  │                   Remove the unused rules: twig-logic-and
+
+                warning[unused-suppression]: Unused ludtwig-ignore directive
+                  ┌─ ./debug-rule.html.twig:5:1
+                  │  
+                4 │   {{ a && b }}
+                  │ ╭────────────'
+                5 │ │ {# ludtwig-ignore #}
+                  │ │ ^^^^^^^^^^^^^^^^^^^^ no finding is suppressed by this directive
+                  │ ╰────────────────────' Remove the directive: 
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rules(
+            &["unused-suppression", "twig-logic-and", "twig-logic-or"],
+            r#"{# ludtwig-ignore twig-logic-and #}
+{{ a && b }}
+{# ludtwig-ignore #}
+<div>
+    {# ludtwig-ignore twig-logic-or #}
+    {{ a || b }}
+    {{ a && b }}
+</div>
+{# ludtwig-ignore unused-suppression #}
+{# ludtwig-ignore twig-logic-or #}
+{{ a }}"#,
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_reports_file_directives() {
+        test_rules(
+            &["unused-suppression", "twig-logic-and", "twig-logic-or"],
+            r#"{# ludtwig-ignore-file twig-logic-and, twig-logic-or #}
+{{ a && b }}"#,
+            expect![[r#"
+                warning[unused-suppression]: Unused rule in ludtwig-ignore-file directive
+                  ┌─ ./debug-rule.html.twig:1:40
+                  │
+                1 │ {# ludtwig-ignore-file twig-logic-and, twig-logic-or #}
+                  │                        ----------------^^^^^^^^^^^^^
+                  │                        │               │
+                  │                        │               no finding of this rule is suppressed by this directive
+                  │                        Remove the unused rules: twig-logic-and
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_reports_directives_of_root_rules() {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.format.max_line_length = 40;
+
+        test_rules_with_config(
+            &["unused-suppression", "max-line-length"],
+            r#"{# ludtwig-ignore max-line-length #}
+<div class="a very long line with more text"></div>
+{# ludtwig-ignore max-line-length #}
+<div></div>"#,
+            config,
+            expect![[r#"
+                warning[unused-suppression]: Unused ludtwig-ignore directive
+                  ┌─ ./debug-rule.html.twig:3:1
+                  │  
+                2 │   <div class="a very long line with more text"></div>
+                  │ ╭───────────────────────────────────────────────────'
+                3 │ │ {# ludtwig-ignore max-line-length #}
+                  │ │ ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ no finding is suppressed by this directive
+                  │ ╰────────────────────────────────────' Remove the directive: 
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn run_rules_tracks_the_suppressed_findings() {
+        let (_, suppressions) = debug_suppressions(
+            &["unused-suppression", "twig-logic-and", "twig-logic-or"],
+            r#"{# ludtwig-ignore-file twig-logic-or #}
+{{ a || b }}
+{# ludtwig-ignore #}
+<div>
+    {# ludtwig-ignore twig-logic-and #}
+    {{ a && b }}
+    {{ a && b }}
+</div>
+{{ a && b }}"#,
+        );
+
+        let mut rule_names: Vec<&str> = suppressions.rule_names().collect();
+        rule_names.sort_unstable();
+        assert_eq!(
+            rule_names,
+            vec!["twig-logic-and", "twig-logic-and", "twig-logic-or"]
+        );
+    }
+}
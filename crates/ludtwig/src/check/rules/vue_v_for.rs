@@ -0,0 +1,156 @@
+use ludtwig_parser::syntax::typed::{AstNode, HtmlAttribute, HtmlTag};
+use ludtwig_parser::syntax::untyped::{SyntaxNode, SyntaxNodeExt};
+
+use crate::check::rule::{CheckResult, Rule, RuleExt, RuleRunContext, Severity};
+use crate::check::rules::html_lowercase_names::{is_one_of, HTML_ELEMENTS};
+use crate::check::rules::twig_no_autoescape_off::{is_allowed_file, validate_patterns};
+use crate::Config;
+
+pub struct RuleVueVFor;
+
+impl Rule for RuleVueVFor {
+    fn name(&self) -> &'static str {
+        "vue-v-for"
+    }
+
+    fn explanation(&self) -> Option<&'static str> {
+        Some("The vue style guide forbids `v-if` on the same element as `v-for`, filter the list in a computed property or move the `v-if` to a wrapping <template> instead. Components rendered with `v-for` need a `:key`, so vue can keep their state when the list changes.")
+    }
+
+    fn validate_config(&self, config: &Config) -> Result<(), String> {
+        validate_patterns("vue-files", &config.rules.vue_files)
+    }
+
+    fn check_node(&self, node: SyntaxNode, ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
+        let tag = HtmlTag::cast(node)?;
+        let name = tag.name()?;
+        let v_for = tag
+            .attributes()
+            .find(|a| directive_name(a).as_deref() == Some("v-for"))?;
+
+        if !is_allowed_file(ctx.file_path(), &ctx.config().rules.vue_files) {
+            return None;
+        }
+
+        let mut results = vec![];
+        if let Some(v_if) = tag
+            .attributes()
+            .find(|a| directive_name(a).as_deref() == Some("v-if"))
+        {
+            results.push(
+                self.create_result(Severity::Warning, "v-if on the same element as v-for")
+                    .primary_note(
+                        v_if.syntax().text_range_trimmed_trivia(),
+                        "help: filter the list in a computed property or move the v-if to a wrapping <template>",
+                    )
+                    .secondary_note(
+                        v_for.syntax().text_range_trimmed_trivia(),
+                        "the element is repeated here",
+                    ),
+            );
+        }
+
+        let is_component = !is_one_of(name.text(), HTML_ELEMENTS)
+            && !name.text().eq_ignore_ascii_case("template")
+            && (name.text().contains('-') || name.text().chars().any(|c| c.is_ascii_uppercase()));
+        if is_component && !tag.attributes().any(|a| is_key(&a)) {
+            results.push(
+                self.create_result(Severity::Warning, "Component in v-for without a key")
+                    .primary_note(
+                        v_for.syntax().text_range_trimmed_trivia(),
+                        format!("help: add a unique :key to this <{}>", name.text()),
+                    ),
+            );
+        }
+
+        if results.is_empty() {
+            None
+        } else {
+            Some(results)
+        }
+    }
+}
+
+/// Full name of the vue directive of the attribute, like `v-for` or `v-bind` for `:key`
+fn directive_name(attribute: &HtmlAttribute) -> Option<String> {
+    attribute.vue_directive()?.get_directive_name()
+}
+
+/// `:key`, `v-bind:key` or a static `key`
+fn is_key(attribute: &HtmlAttribute) -> bool {
+    if let Some(name) = attribute.name() {
+        return name.text() == "key";
+    }
+
+    attribute.vue_directive().is_some_and(|directive| {
+        directive.get_directive_name().as_deref() == Some("v-bind")
+            && directive
+                .argument()
+                .is_some_and(|argument| argument.syntax().text() == "key")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::check::rules::test::test_rule_with_config;
+    use crate::config::Config;
+
+    fn vue_config() -> Config {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.vue_files = vec!["*.html.twig".to_string()];
+        config
+    }
+
+    #[test]
+    fn rule_reports() {
+        test_rule_with_config(
+            "vue-v-for",
+            r#"<li v-for="item in items" v-if="item.active">{{ item.name }}</li>
+<sw-product-card v-for="product in products" :product="product"></sw-product-card>"#,
+            vue_config(),
+            expect![[r#"
+                warning[vue-v-for]: v-if on the same element as v-for
+                  ┌─ ./debug-rule.html.twig:1:27
+                  │
+                1 │ <li v-for="item in items" v-if="item.active">{{ item.name }}</li>
+                  │     --------------------- ^^^^^^^^^^^^^^^^^^ help: filter the list in a computed property or move the v-if to a wrapping <template>
+                  │     │                      
+                  │     the element is repeated here
+
+                warning[vue-v-for]: Component in v-for without a key
+                  ┌─ ./debug-rule.html.twig:2:18
+                  │
+                2 │ <sw-product-card v-for="product in products" :product="product"></sw-product-card>
+                  │                  ^^^^^^^^^^^^^^^^^^^^^^^^^^^ help: add a unique :key to this <sw-product-card>
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report() {
+        test_rule_with_config(
+            "vue-v-for",
+            r#"<template v-for="item in items" :key="item.id">
+    <li v-if="item.active">{{ item.name }}</li>
+</template>
+<sw-product-card v-for="product in products" :key="product.id"></sw-product-card>
+<sw-tag v-for="tag in tags" v-bind:key="tag.id"></sw-tag>
+<li v-for="item in items">{{ item }}</li>"#,
+            vue_config(),
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn rule_does_not_report_outside_of_vue_files() {
+        test_rule_with_config(
+            "vue-v-for",
+            r#"<li v-for="item in items" v-if="item.active">{{ item.name }}</li>"#,
+            Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap(),
+            expect![[r#""#]],
+        );
+    }
+}
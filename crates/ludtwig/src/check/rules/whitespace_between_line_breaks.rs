@@ -9,6 +9,10 @@ impl Rule for RuleWhitespaceBetweenLineBreaks {
         "whitespace-between-line-breaks"
     }
 
+    fn explanation(&self) -> Option<&'static str> {
+        Some("Lines which contain only whitespace add nothing to the template and produce noisy diffs.")
+    }
+
     fn check_token(&self, token: SyntaxToken, _ctx: &RuleRunContext) -> Option<Vec<CheckResult>> {
         // rule only inspects line breaks
         if token.kind() != SyntaxKind::TK_LINE_BREAK {
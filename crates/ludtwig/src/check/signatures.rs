@@ -0,0 +1,277 @@
+//! Database of twig filter, function and test signatures (arguments and types)
+//! which is used by rules that need to know more about the called twig extensions.
+//!
+//! The built-in signatures are stored in `signatures.json` and can be extended or overridden
+//! with additional files in the same format (`signatures-files` in the config).
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+
+use crate::check::dependencies;
+use crate::Config;
+
+pub const DEFAULT_RAW_SIGNATURES: &str = include_str!("../../signatures.json");
+
+type SignaturesCache =
+    HashMap<Vec<String>, (Vec<Option<SystemTime>>, Result<Arc<Signatures>, String>)>;
+
+/// Loaded signatures by their extension files and the modification times of the files
+/// (to notice changes while running as a daemon), so every combination is only read once
+static SIGNATURES_CACHE: Lazy<Mutex<SignaturesCache>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Signatures {
+    #[serde(default)]
+    pub filters: HashMap<String, Signature>,
+    #[serde(default)]
+    pub functions: HashMap<String, Signature>,
+    #[serde(default)]
+    pub tests: HashMap<String, Signature>,
+}
+
+impl Signatures {
+    /// Add all signatures of the other, existing signatures with the same name are replaced
+    fn extend(&mut self, other: Signatures) {
+        self.filters.extend(other.filters);
+        self.functions.extend(other.functions);
+        self.tests.extend(other.tests);
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Signature {
+    /// Type of the value on the left side (only used for filters and tests)
+    #[serde(default)]
+    pub input: Type,
+    /// Arguments in the order of the definition, optional arguments must come last
+    #[serde(default)]
+    pub arguments: Vec<Argument>,
+    /// Accepts any amount of additional arguments
+    #[serde(default)]
+    pub variadic: bool,
+    #[serde(default)]
+    pub returns: Type,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Argument {
+    pub name: String,
+    #[serde(default, rename = "type")]
+    pub value_type: Type,
+    #[serde(default)]
+    pub optional: bool,
+}
+
+/// Types of twig values, which are known without any runtime information
+#[derive(Debug, Default, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Type {
+    String,
+    Number,
+    Boolean,
+    Null,
+    /// Sequence or mapping
+    Array,
+    /// Any type or not known
+    #[default]
+    Mixed,
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::String => write!(f, "a string"),
+            Type::Number => write!(f, "a number"),
+            Type::Boolean => write!(f, "a boolean"),
+            Type::Null => write!(f, "null"),
+            Type::Array => write!(f, "an array"),
+            Type::Mixed => write!(f, "any value"),
+        }
+    }
+}
+
+/// Get the built-in signatures extended with the configured signature files
+pub fn get_signatures(config: &Config) -> Result<Arc<Signatures>, String> {
+    let files = &config.rules.signatures_files;
+
+    let modified: Vec<Option<SystemTime>> = files
+        .iter()
+        .map(|file| dependencies::modified_time(Path::new(file)))
+        .collect();
+
+    let mut cache = SIGNATURES_CACHE.lock().unwrap();
+    if let Some((cached_modified, signatures)) = cache.get(files) {
+        if cached_modified == &modified {
+            return signatures.clone();
+        }
+    }
+
+    let signatures = load_signatures(files).map(Arc::new);
+    cache.insert(files.clone(), (modified, signatures.clone()));
+    signatures
+}
+
+fn load_signatures(files: &[String]) -> Result<Signatures, String> {
+    let mut signatures: Signatures = serde_json::from_str(DEFAULT_RAW_SIGNATURES)
+        .expect("built-in signatures.json should be valid");
+
+    for file in files {
+        let content =
+            std::fs::read_to_string(file).map_err(|e| format!("can't read {file}: {e}"))?;
+        let extension: Signatures =
+            serde_json::from_str(&content).map_err(|e| format!("can't parse {file}: {e}"))?;
+        signatures.extend(extension);
+    }
+
+    Ok(signatures)
+}
+
+/// The most similar known name for a (likely misspelled) name, if there is one which is close enough
+pub fn did_you_mean<'a, I>(name: &str, known_names: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    // allow one typo for short names and more for longer ones
+    let max_distance = (name.chars().count() / 4).clamp(1, 3);
+
+    known_names
+        .into_iter()
+        .map(|known| (edit_distance(name, known), known))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min()
+        .map(|(_, known)| known)
+}
+
+/// Edit distance (amount of inserted, removed, replaced or swapped adjacent characters) between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut before_previous: Vec<usize> = vec![];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let replace = previous[j] + usize::from(a_char != b_char);
+            let mut distance = replace.min(previous[j + 1] + 1).min(current[j] + 1);
+            // typos like `nmae` for `name`
+            if i > 0 && j > 0 && *a_char == b[j - 1] && a[i - 1] == *b_char {
+                distance = distance.min(before_previous[j - 1] + 1);
+            }
+            current.push(distance);
+        }
+        before_previous = std::mem::replace(&mut previous, current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_built_in_signatures() {
+        let signatures = load_signatures(&[]).unwrap();
+
+        let join = &signatures.filters["join"];
+        assert_eq!(join.input, Type::Array);
+        assert_eq!(join.returns, Type::String);
+        assert_eq!(join.arguments.len(), 2);
+        assert!(join.arguments.iter().all(|a| a.optional));
+
+        let path = &signatures.functions["path"];
+        assert!(!path.arguments[0].optional);
+        assert_eq!(path.arguments[0].value_type, Type::String);
+
+        assert!(signatures.functions["max"].variadic);
+        assert_eq!(signatures.tests["divisible by"].input, Type::Number);
+    }
+
+    #[test]
+    fn extends_signatures_with_files() {
+        let path = std::env::temp_dir().join("ludtwig-signatures-extend.json");
+        std::fs::write(
+            &path,
+            r#"{
+                "filters": {
+                    "price": {"input": "number", "arguments": [{"name": "currency", "type": "string"}], "returns": "string"},
+                    "join": {"returns": "mixed"}
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let signatures = load_signatures(&[path.to_string_lossy().to_string()]).unwrap();
+
+        assert_eq!(signatures.filters["price"].input, Type::Number);
+        assert_eq!(signatures.filters["price"].arguments[0].name, "currency");
+        // existing signatures are replaced
+        assert_eq!(signatures.filters["join"].input, Type::Mixed);
+        assert_eq!(signatures.filters["upper"].returns, Type::String);
+    }
+
+    #[test]
+    fn reports_invalid_signature_files() {
+        let path = std::env::temp_dir().join("ludtwig-signatures-invalid.json");
+        std::fs::write(&path, r#"{"filters": {"price": {"input": "money"}}}"#).unwrap();
+
+        assert!(load_signatures(&[path.to_string_lossy().to_string()])
+            .unwrap_err()
+            .starts_with("can't parse"));
+        assert!(load_signatures(&["./does-not-exist.json".to_string()])
+            .unwrap_err()
+            .starts_with("can't read ./does-not-exist.json"));
+    }
+
+    #[test]
+    fn reloads_changed_signature_files() {
+        let path = std::env::temp_dir().join(format!(
+            "ludtwig-signatures-reload-{}.json",
+            std::process::id()
+        ));
+        std::fs::write(&path, r#"{"filters": {"price": {}}}"#).unwrap();
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.signatures_files = vec![path.to_string_lossy().to_string()];
+        assert!(get_signatures(&config)
+            .unwrap()
+            .filters
+            .contains_key("price"));
+
+        // with a different modification time, the file system may be too coarse otherwise
+        std::fs::write(&path, r#"{"filters": {"currency": {}}}"#).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH)
+            .unwrap();
+        let signatures = get_signatures(&config).unwrap();
+        assert!(signatures.filters.contains_key("currency"));
+        assert!(!signatures.filters.contains_key("price"));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn finds_similar_names() {
+        assert_eq!(edit_distance("jsone_encode", "json_encode"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("nmae", "name"), 1);
+
+        let names = ["json_encode", "join", "upper", "lower"];
+        assert_eq!(did_you_mean("jsone_encode", names), Some("json_encode"));
+        assert_eq!(did_you_mean("uper", names), Some("upper"));
+        assert_eq!(did_you_mean("lowr", names), Some("lower"));
+        assert_eq!(did_you_mean("camelize", names), None);
+    }
+}
@@ -0,0 +1,390 @@
+//! Index of other templates in the project, which is used by rules that need to know
+//! about the template inheritance (like the blocks of the parent templates).
+//!
+//! Template names are resolved with the configured `template-roots` (for names like `base.html.twig`)
+//! and `template-namespaces` (for names like `@Storefront/storefront/base.html.twig`).
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use ludtwig_parser::syntax::typed::{AstNode, TwigBlock, TwigLiteralString, TwigUse};
+use ludtwig_parser::syntax::untyped::{SyntaxKind, SyntaxNode};
+use once_cell::sync::Lazy;
+
+use crate::check::dependencies;
+use crate::Config;
+
+/// Parsed templates by their canonical path and modification time (to notice changes while running
+/// as a daemon), `None` if the file can't be read
+#[allow(clippy::type_complexity)]
+static TEMPLATES_CACHE: Lazy<
+    Mutex<HashMap<PathBuf, (Option<SystemTime>, Option<Arc<TemplateInfo>>)>>,
+> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Everything about a single template, which is relevant for other templates
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct TemplateInfo {
+    /// Name of the extended template, if it is a literal string
+    pub extends: Option<String>,
+    /// Whether the template extends another one with a name that is not known statically
+    pub extends_dynamic: bool,
+    /// Names of all blocks defined in the template
+    pub blocks: HashSet<String>,
+    /// Templates whose blocks are imported with `use`
+    pub uses: Vec<UsedTemplate>,
+}
+
+/// A template imported with `{% use "blocks.html.twig" with a as b %}`
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct UsedTemplate {
+    pub name: String,
+    /// New names of aliased blocks, like `a` -> `b`
+    pub aliases: HashMap<String, String>,
+}
+
+impl TemplateInfo {
+    pub fn from_tree(root: &SyntaxNode) -> Self {
+        let (extends, extends_dynamic) = match extends_tag(root) {
+            Some(tag) => match literal_template_name(&tag) {
+                Some(name) => (Some(name), false),
+                None => (None, true),
+            },
+            None => (None, false),
+        };
+
+        let blocks = root
+            .descendants()
+            .filter_map(TwigBlock::cast)
+            .filter_map(|b| b.name())
+            .map(|n| n.text().to_string())
+            .collect();
+
+        let uses = root
+            .children()
+            .filter_map(TwigUse::cast)
+            .filter_map(|u| {
+                let name = u.template()?.value();
+                if name.has_interpolation {
+                    return None;
+                }
+                let aliases = u
+                    .overrides()
+                    .filter_map(|o| {
+                        let name = o.name()?.name()?.text().to_string();
+                        let alias = o.alias()?.name()?.text().to_string();
+                        Some((name, alias))
+                    })
+                    .collect();
+                Some(UsedTemplate {
+                    name: name.text,
+                    aliases,
+                })
+            })
+            .collect();
+
+        Self {
+            extends,
+            extends_dynamic,
+            blocks,
+            uses,
+        }
+    }
+}
+
+/// The `extends` or `sw_extends` tag of the template
+pub fn extends_tag(root: &SyntaxNode) -> Option<SyntaxNode> {
+    root.children().find(|c| {
+        matches!(
+            c.kind(),
+            SyntaxKind::TWIG_EXTENDS | SyntaxKind::SHOPWARE_TWIG_SW_EXTENDS
+        )
+    })
+}
+
+/// Template name of an `extends` tag like `{% extends 'base.html.twig' %}`
+fn literal_template_name(extends: &SyntaxNode) -> Option<String> {
+    let string = extends
+        .children()
+        .find_map(|c| match c.kind() {
+            SyntaxKind::TWIG_EXPRESSION => c.first_child().and_then(TwigLiteralString::cast),
+            _ => TwigLiteralString::cast(c),
+        })?
+        .value();
+    if string.has_interpolation {
+        return None;
+    }
+
+    Some(string.text)
+}
+
+/// Find the file of a template name in the configured template directories
+pub fn resolve_template(name: &str, config: &Config) -> Option<PathBuf> {
+    let candidates: Vec<PathBuf> = match name.strip_prefix('@') {
+        Some(namespaced) => {
+            let (namespace, path) = namespaced.split_once('/')?;
+            let directory = config.rules.template_namespaces.get(namespace)?;
+            vec![Path::new(directory).join(path)]
+        }
+        None => config
+            .rules
+            .template_roots
+            .iter()
+            .map(|root| Path::new(root).join(name))
+            .collect(),
+    };
+
+    candidates.into_iter().find(|p| p.is_file())
+}
+
+/// Get the (cached) information about the template file
+pub fn get_template(path: &Path) -> Option<Arc<TemplateInfo>> {
+    let path = path.canonicalize().ok()?;
+    let modified = dependencies::modified_time(&path);
+
+    let mut cache = TEMPLATES_CACHE.lock().unwrap();
+    if let Some((cached_modified, info)) = cache.get(&path) {
+        if cached_modified == &modified {
+            return info.clone();
+        }
+    }
+
+    let info = std::fs::read_to_string(&path).ok().map(|source| {
+        let root = SyntaxNode::new_root(ludtwig_parser::parse(&source).green_node);
+        Arc::new(TemplateInfo::from_tree(&root))
+    });
+    cache.insert(path, (modified, info.clone()));
+    info
+}
+
+/// All ancestors of a template (starting with the directly extended one), `None` if any of them
+/// can't be resolved. The file of the template itself is used to stop at inheritance cycles.
+pub fn inheritance_chain(
+    file_path: &Path,
+    template: &TemplateInfo,
+    config: &Config,
+) -> Option<Vec<Arc<TemplateInfo>>> {
+    if template.extends_dynamic {
+        return None;
+    }
+
+    let mut visited: HashSet<PathBuf> = file_path.canonicalize().into_iter().collect();
+    let mut chain: Vec<Arc<TemplateInfo>> = vec![];
+    let mut extends = template.extends.clone();
+
+    while let Some(name) = extends.take() {
+        let path = resolve_template(&name, config)?.canonicalize().ok()?;
+        if !visited.insert(path.clone()) {
+            break;
+        }
+
+        let parent = get_template(&path)?;
+        if parent.extends_dynamic {
+            return None;
+        }
+        extends.clone_from(&parent.extends);
+        chain.push(parent);
+    }
+
+    Some(chain)
+}
+
+/// Names of all blocks imported with `use` (aliased blocks are only available under their new name),
+/// `None` if any of the used templates can't be resolved
+pub fn used_blocks(template: &TemplateInfo, config: &Config) -> Option<HashSet<String>> {
+    collect_used_blocks(template, config, &mut HashSet::new())
+}
+
+fn collect_used_blocks(
+    template: &TemplateInfo,
+    config: &Config,
+    visited: &mut HashSet<PathBuf>,
+) -> Option<HashSet<String>> {
+    let mut blocks = HashSet::new();
+    for used in &template.uses {
+        let path = resolve_template(&used.name, config)?.canonicalize().ok()?;
+        if !visited.insert(path.clone()) {
+            continue;
+        }
+
+        let used_template = get_template(&path)?;
+        let mut imported = collect_used_blocks(&used_template, config, visited)?;
+        imported.extend(used_template.blocks.iter().cloned());
+        for (name, alias) in &used.aliases {
+            if imported.remove(name) {
+                imported.insert(alias.clone());
+            }
+        }
+        blocks.extend(imported);
+    }
+
+    Some(blocks)
+}
+
+/// Names of all blocks defined in (or imported with `use` by) the ancestors of a template,
+/// `None` if any of them can't be resolved
+pub fn parent_blocks(
+    file_path: &Path,
+    template: &TemplateInfo,
+    config: &Config,
+) -> Option<HashSet<String>> {
+    let mut blocks = HashSet::new();
+    for parent in inheritance_chain(file_path, template, config)? {
+        blocks.extend(parent.blocks.iter().cloned());
+        blocks.extend(used_blocks(&parent, config)?);
+    }
+
+    Some(blocks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Create template files in a new temporary directory
+    fn template_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(name);
+        for (path, content) in files {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+
+        dir
+    }
+
+    fn config_with_templates(dir: &Path) -> Config {
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.template_roots = vec![dir.join("templates").to_string_lossy().to_string()];
+        config.rules.template_namespaces = HashMap::from([(
+            "Storefront".to_string(),
+            dir.join("storefront").to_string_lossy().to_string(),
+        )]);
+
+        config
+    }
+
+    #[test]
+    fn template_info_from_tree() {
+        let root = SyntaxNode::new_root(
+            ludtwig_parser::parse(
+                "{% sw_extends '@Storefront/base.html.twig' %}{% block a %}{% block b %}{% endblock %}{% endblock %}",
+            )
+            .green_node,
+        );
+        let info = TemplateInfo::from_tree(&root);
+
+        assert_eq!(info.extends.as_deref(), Some("@Storefront/base.html.twig"));
+        assert!(!info.extends_dynamic);
+        assert_eq!(
+            info.blocks,
+            HashSet::from(["a".to_string(), "b".to_string()])
+        );
+
+        let root = SyntaxNode::new_root(
+            ludtwig_parser::parse("{% extends ajax ? 'a.twig' : 'b.twig' %}").green_node,
+        );
+        let info = TemplateInfo::from_tree(&root);
+        assert_eq!(info.extends, None);
+        assert!(info.extends_dynamic);
+
+        let root = SyntaxNode::new_root(
+            ludtwig_parser::parse("{% use 'blocks.html.twig' with a as b %}").green_node,
+        );
+        let info = TemplateInfo::from_tree(&root);
+        assert_eq!(
+            info.uses,
+            vec![UsedTemplate {
+                name: "blocks.html.twig".to_string(),
+                aliases: HashMap::from([("a".to_string(), "b".to_string())]),
+            }]
+        );
+    }
+
+    #[test]
+    fn resolves_used_blocks() {
+        let dir = template_dir(
+            "ludtwig-templates-use",
+            &[
+                (
+                    "templates/blocks.html.twig",
+                    "{% use 'more.html.twig' %}{% block sidebar %}{% endblock %}{% block title %}{% endblock %}",
+                ),
+                ("templates/more.html.twig", "{% block footer %}{% endblock %}"),
+                (
+                    "templates/layout.html.twig",
+                    "{% use 'blocks.html.twig' with sidebar as base_sidebar %}{% block body %}{% endblock %}",
+                ),
+            ],
+        );
+        let config = config_with_templates(&dir);
+
+        let child = TemplateInfo {
+            extends: Some("layout.html.twig".to_string()),
+            ..TemplateInfo::default()
+        };
+        assert_eq!(
+            parent_blocks(Path::new("./child.html.twig"), &child, &config),
+            Some(HashSet::from(
+                ["body", "base_sidebar", "title", "footer"].map(String::from)
+            ))
+        );
+
+        let missing = TemplateInfo {
+            uses: vec![UsedTemplate {
+                name: "missing.html.twig".to_string(),
+                ..UsedTemplate::default()
+            }],
+            ..TemplateInfo::default()
+        };
+        assert!(used_blocks(&missing, &config).is_none());
+    }
+
+    #[test]
+    fn resolves_inheritance_chain() {
+        let dir = template_dir(
+            "ludtwig-templates-chain",
+            &[
+                ("templates/layout.html.twig", "{% block body %}{% endblock %}"),
+                (
+                    "storefront/page.html.twig",
+                    "{% extends 'layout.html.twig' %}{% block body %}{% block content %}{% endblock %}{% endblock %}",
+                ),
+                (
+                    "templates/cycle.html.twig",
+                    "{% extends 'cycle.html.twig' %}{% block a %}{% endblock %}",
+                ),
+            ],
+        );
+        let config = config_with_templates(&dir);
+
+        assert!(resolve_template("layout.html.twig", &config).is_some());
+        assert!(resolve_template("@Storefront/page.html.twig", &config).is_some());
+        assert!(resolve_template("@Unknown/page.html.twig", &config).is_none());
+        assert!(resolve_template("missing.html.twig", &config).is_none());
+
+        let child = TemplateInfo {
+            extends: Some("@Storefront/page.html.twig".to_string()),
+            ..TemplateInfo::default()
+        };
+        let chain = inheritance_chain(Path::new("./child.html.twig"), &child, &config).unwrap();
+        assert_eq!(chain.len(), 2);
+        assert!(chain[0].blocks.contains("content"));
+        assert_eq!(chain[1].extends, None);
+
+        // extends itself
+        let cycle_path = dir.join("templates/cycle.html.twig");
+        let cycle = get_template(&cycle_path).unwrap();
+        assert_eq!(
+            inheritance_chain(&cycle_path, &cycle, &config).map(|c| c.len()),
+            Some(0)
+        );
+
+        let missing = TemplateInfo {
+            extends: Some("missing.html.twig".to_string()),
+            ..TemplateInfo::default()
+        };
+        assert!(inheritance_chain(Path::new("./child.html.twig"), &missing, &config).is_none());
+    }
+}
@@ -1,26 +1,44 @@
-use std::fmt::{Display, Formatter};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Write as _};
 use std::path::{Path, PathBuf};
 
 use figment::providers::{Env, Format as FigFormat, Toml};
 use figment::Figment;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
+pub use ludtwig_parser::TwigVersion;
+
+use ludtwig_parser::syntax::untyped::SyntaxNode;
+
+use crate::check::rule::Rule;
+use crate::check::rules::{
+    get_config_active_rule_definitions, get_file_active_rule_definitions, skip_expensive_rules,
+    RuleRegistry,
+};
+use crate::error::FileProcessingError;
 use crate::Opts;
 
-#[derive(Debug, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
 #[serde(rename_all = "kebab-case")]
 pub struct Config {
     pub general: General,
     pub format: Format,
+    pub rules: Rules,
 }
 
-#[derive(Debug, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
 #[serde(rename_all = "kebab-case")]
 pub struct General {
+    #[serde(with = "twig_version_string")]
+    pub twig_version: TwigVersion,
+    pub extends: Vec<String>,
     pub active_rules: Vec<String>,
+    pub suppress_contained: HashMap<String, Vec<String>>,
+    pub large_file_threshold: usize,
+    pub max_fix_passes: usize,
 }
 
-#[derive(Debug, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
 #[serde(rename_all = "kebab-case")]
 pub struct Format {
     pub line_ending: LineEnding,
@@ -30,9 +48,56 @@ pub struct Format {
     pub linebreaks_around_blocks: bool,
     pub twig_quotation: Quotation,
     pub html_quotation: Quotation,
+    pub nbsp_style: NbspStyle,
+    pub max_line_length: usize,
+    pub void_element_style: VoidElementStyle,
+    pub self_closing_components: bool,
+    pub twig_delimiter_spaces: u8,
+    pub trailing_comma: TrailingComma,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "kebab-case")]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Rules {
+    pub autoescape_off_allowed_files: Vec<String>,
+    pub raw_allowed_variables: Vec<String>,
+    pub allow_vue_event_handlers: bool,
+    pub vue_files: Vec<String>,
+    pub routes_file: String,
+    pub data_attribute_name_regex: String,
+    pub block_name_regex: String,
+    pub block_name_prefix: String,
+    pub attribute_order: Vec<String>,
+    pub max_nesting_depth: usize,
+    pub allow_commented_empty_blocks: bool,
+    pub empty_element_tags: Vec<String>,
+    pub signatures_files: Vec<String>,
+    pub known_filters: Vec<String>,
+    pub known_functions: Vec<String>,
+    pub known_tests: Vec<String>,
+    pub ignore_underscore_variables: bool,
+    pub known_variables: Vec<String>,
+    pub template_roots: Vec<String>,
+    pub template_namespaces: HashMap<String, String>,
+    pub prefer_null_coalescing_over_default: bool,
+    pub max_block_size: usize,
+    pub block_size_unit: BlockSizeUnit,
+    pub block_end_comment: BlockEndComment,
+    pub block_end_comment_threshold: usize,
+    pub untranslated_text_min_length: usize,
+    pub untranslated_text_ignore: Vec<String>,
+    pub translation_key_regex: String,
+    pub class_properties_files: Vec<String>,
+    pub banned_elements: Vec<String>,
+    pub commented_out_code_min_length: usize,
+    pub inline_code_allow_nonce: bool,
+    pub inline_script_allowed_types: Vec<String>,
+    pub inline_style_attributes: bool,
+    pub heading_order_across_blocks: bool,
 }
 
-#[derive(Debug, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
 #[serde(rename_all = "kebab-case")]
 pub enum IndentationMode {
     Space,
@@ -61,7 +126,7 @@ impl IndentationMode {
     }
 }
 
-#[derive(Debug, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
 pub enum LineEnding {
     #[serde(rename = "unix_LF")]
     UnixLF,
@@ -91,7 +156,7 @@ impl LineEnding {
     }
 }
 
-#[derive(Debug, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
 pub enum Quotation {
     #[serde(rename = "single")]
     Single,
@@ -121,12 +186,120 @@ impl Quotation {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
+pub enum NbspStyle {
+    #[serde(rename = "entity")]
+    Entity,
+    #[serde(rename = "literal")]
+    Literal,
+}
+
+impl Display for NbspStyle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NbspStyle::Entity => {
+                write!(f, "the html entity (&nbsp;)")
+            }
+            NbspStyle::Literal => {
+                write!(f, "the literal character (U+00A0)")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
+pub enum VoidElementStyle {
+    #[serde(rename = "no-slash")]
+    NoSlash,
+    #[serde(rename = "slash")]
+    Slash,
+}
+
+impl Display for VoidElementStyle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VoidElementStyle::NoSlash => {
+                write!(f, "without a slash (<br>)")
+            }
+            VoidElementStyle::Slash => {
+                write!(f, "with a slash (<br />)")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
+pub enum TrailingComma {
+    #[serde(rename = "always")]
+    Always,
+    #[serde(rename = "never")]
+    Never,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
+pub enum BlockSizeUnit {
+    #[serde(rename = "lines")]
+    Lines,
+    #[serde(rename = "nodes")]
+    Nodes,
+}
+
+impl Display for BlockSizeUnit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlockSizeUnit::Lines => {
+                write!(f, "lines")
+            }
+            BlockSizeUnit::Nodes => {
+                write!(f, "nodes")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
+pub enum BlockEndComment {
+    #[serde(rename = "require")]
+    Require,
+    #[serde(rename = "forbid")]
+    Forbid,
+}
+
+/// Serde (de)serialization of the twig version as a string like `3.x` or `2.7`
+mod twig_version_string {
+    use ludtwig_parser::TwigVersion;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    #[allow(clippy::trivially_copy_pass_by_ref)] // signature required by serde
+    pub fn serialize<S: Serializer>(
+        version: &TwigVersion,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(version)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<TwigVersion, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 pub const DEFAULT_CONFIG_PATH: &str = "./ludtwig-config.toml";
 pub const DEFAULT_RAW_CONFIG: &str = include_str!("../ludtwig-config.toml");
 
 impl Config {
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, figment::Error> {
-        let config: Config = Figment::new()
+        let config: Config = Self::figment(path).extract()?;
+
+        Ok(config)
+    }
+
+    /// All sources of the config, which are merged in this order
+    fn figment<P: AsRef<Path>>(path: P) -> Figment {
+        Figment::new()
             // first read the raw config from memory (for default values)
             .merge(Toml::string(DEFAULT_RAW_CONFIG))
             // then read the config in the file system (if it exists)
@@ -137,10 +310,149 @@ impl Config {
                     .split("__")
                     .map(|key| key.as_str().replace('_', "-").into()),
             )
-            .extract()?;
+    }
+}
 
-        Ok(config)
+/// Every value of the config with its full key like `format.indentation-count`
+fn flatten_config(config: &Config) -> Vec<(String, String)> {
+    fn flatten(prefix: &str, value: serde_json::Value, out: &mut Vec<(String, String)>) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, value) in map {
+                    let key = if prefix.is_empty() {
+                        key
+                    } else {
+                        format!("{prefix}.{key}")
+                    };
+                    flatten(&key, value, out);
+                }
+            }
+            value => out.push((prefix.to_string(), value.to_string())),
+        }
+    }
+
+    let mut values = vec![];
+    flatten("", serde_json::to_value(config).unwrap(), &mut values);
+    values
+}
+
+/// Human-readable differences between two configs like `format.indentation-count: 4 -> 2`.
+/// The `active-rules` are left out, because they are better compared after resolving the presets.
+pub fn config_diff(old: &Config, new: &Config) -> Vec<String> {
+    let old_values: HashMap<String, String> = flatten_config(old).into_iter().collect();
+
+    let mut diff: Vec<String> = flatten_config(new)
+        .into_iter()
+        .filter(|(key, _)| key != "general.active-rules")
+        .filter_map(|(key, value)| match old_values.get(&key) {
+            Some(old_value) if old_value == &value => None,
+            Some(old_value) => Some(format!("{key}: {old_value} -> {value}")),
+            None => Some(format!("{key}: {value}")),
+        })
+        .collect();
+    diff.sort();
+    diff
+}
+
+/// Human readable effective configuration (after merging the defaults, the config file
+/// and environment variables) with the source of every value, that isn't a default.
+/// With a template file it also lists the rules, that are actually run on that file.
+pub fn show_config(
+    config_path: &Path,
+    config: &Config,
+    rule_registry: &RuleRegistry,
+    file: Option<&Path>,
+) -> Result<String, FileProcessingError> {
+    let figment = Config::figment(config_path);
+    let mut output = String::new();
+    let _ = writeln!(
+        output,
+        "# configuration file: {}",
+        config_path.to_string_lossy()
+    );
+
+    for (key, value) in flatten_config(config) {
+        let source = figment.find_metadata(&key).and_then(|metadata| {
+            if let Some(path) = metadata.source.as_ref().and_then(|s| s.file_path()) {
+                Some(path.to_string_lossy().to_string())
+            } else if metadata.name.contains("environment variable") {
+                let name = key.replace('.', "__").replace('-', "_").to_uppercase();
+                Some(format!("LUDTWIG_{name}"))
+            } else {
+                None
+            }
+        });
+
+        let _ = match source {
+            Some(source) => writeln!(output, "{key} = {value}  # from {source}"),
+            None => writeln!(output, "{key} = {value}"),
+        };
     }
+
+    let mut rules = get_config_active_rule_definitions(rule_registry, config)
+        .map_err(FileProcessingError::Configuration)?;
+    let _ = writeln!(
+        output,
+        "\n# active rules (including presets): {}",
+        rule_names(&rules)
+    );
+
+    if let Some(file) = file {
+        let source_code =
+            std::fs::read_to_string(file).map_err(|io_error| FileProcessingError::FileRead {
+                path: file.to_path_buf(),
+                io_error,
+            })?;
+        let parse =
+            ludtwig_parser::parse_for_twig_version(&source_code, config.general.twig_version);
+        let root = SyntaxNode::new_root(parse.green_node);
+
+        let file_rules = get_file_active_rule_definitions(&root, &rules);
+        let disabled: Vec<_> = rules
+            .iter()
+            .filter(|r| !file_rules.iter().any(|f| f.name() == r.name()))
+            .copied()
+            .collect();
+        if !disabled.is_empty() {
+            let _ = writeln!(
+                output,
+                "# disabled by ludtwig-ignore-file: {}",
+                rule_names(&disabled)
+            );
+        }
+
+        rules = skip_expensive_rules(config, &source_code, file_rules.clone());
+        if rules.len() < file_rules.len() {
+            let skipped: Vec<_> = file_rules
+                .iter()
+                .filter(|r| !rules.iter().any(|f| f.name() == r.name()))
+                .copied()
+                .collect();
+            let _ = writeln!(
+                output,
+                "# skipped for large files (over {} bytes): {}",
+                config.general.large_file_threshold,
+                rule_names(&skipped)
+            );
+        }
+
+        let _ = writeln!(
+            output,
+            "# active rules for {}: {}",
+            file.to_string_lossy(),
+            rule_names(&rules)
+        );
+    }
+
+    Ok(output)
+}
+
+fn rule_names(rules: &[&'static dyn Rule]) -> String {
+    rules
+        .iter()
+        .map(|r| r.name())
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 pub fn handle_config_or_exit(opts: &Opts) -> Config {
@@ -174,3 +486,52 @@ pub fn handle_config_or_exit(opts: &Opts) -> Config {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn show_config_with_sources() {
+        // unique for this test run, because the tests run in parallel
+        let dir = std::env::temp_dir().join(format!("ludtwig-config-show-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("ludtwig-config.toml");
+        std::fs::write(
+            &config_path,
+            "[general]\nactive-rules = [\"twig-logic-and\", \"twig-logic-or\"]\n[format]\nindentation-count = 2\n",
+        )
+        .unwrap();
+        let template_path = dir.join("index.html.twig");
+        std::fs::write(
+            &template_path,
+            "{# ludtwig-ignore-file twig-logic-or #}\n{{ a and b }}",
+        )
+        .unwrap();
+
+        let config = Config::new(&config_path).unwrap();
+        let output = show_config(
+            &config_path,
+            &config,
+            &RuleRegistry::default(),
+            Some(&template_path),
+        )
+        .unwrap();
+        let config_source = config_path.to_string_lossy();
+
+        assert!(output.contains(&format!(
+            "format.indentation-count = 2  # from {config_source}\n"
+        )));
+        assert!(output.contains("format.indentation-mode = \"space\"\n"));
+        assert!(
+            output.contains("# active rules (including presets): twig-logic-and, twig-logic-or\n")
+        );
+        assert!(output.contains("# disabled by ludtwig-ignore-file: twig-logic-or\n"));
+        assert!(output.ends_with(&format!(
+            "# active rules for {}: twig-logic-and\n",
+            template_path.to_string_lossy()
+        )));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
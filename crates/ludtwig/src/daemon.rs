@@ -0,0 +1,750 @@
+//! Long-running mode of ludtwig, which answers JSON-RPC 2.0 requests (one JSON object per line)
+//! on a unix socket. Build tools which invoke ludtwig for many single files can use this to avoid
+//! the process startup and to reuse the results for unchanged files.
+//!
+//! Supported methods (`source` is optional and read from `path` if missing):
+//! - `check` with `{ "path", "source" }` returns the parser errors and rule results
+//! - `fix` with `{ "path", "source", "write" }` returns the source code with all suggestions applied
+//!   and writes it back to `path` if `write` is true
+//! - `format-range` with `{ "path", "source", "start", "end" }` returns the source code with only
+//!   the suggestions inside of the byte range applied
+//! - `explain` with `{ "path", "source", "start", "end" }` returns the rule results which overlap
+//!   the byte range (all results without a range) together with the reasoning of their rule and
+//!   the syntax tree fragment they were found in
+//!
+//! Changes of the config file are picked up with the next request (without restarting the daemon).
+//! With `--metrics <ADDRESS>` the daemon also serves metrics for Prometheus, see `daemon::metrics`.
+
+use std::collections::HashMap;
+use std::fs;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex, RwLock};
+use std::time::{Instant, SystemTime};
+
+use ludtwig_parser::syntax::untyped::{SyntaxNode, TextRange, TextSize};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::check::dependencies::{dependencies_unchanged, record_dependencies, FileDependency};
+use crate::check::rule::Rule;
+use crate::check::rules::{
+    get_config_active_rule_definitions, get_file_active_rule_definitions, skip_expensive_rules,
+    RuleRegistry,
+};
+use crate::check::{explain_result, produce_json, result_json, run_rules};
+use crate::config::{config_diff, Config};
+use crate::daemon::metrics::Metrics;
+use crate::error::FileProcessingError;
+use crate::process::{iteratively_apply_suggestions_in_range, FileContext};
+use crate::{CliContext, CliSharedData};
+
+pub mod metrics;
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const PROCESSING_ERROR: i64 = -32000;
+
+#[derive(Debug, Deserialize)]
+struct Request {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct FileParams {
+    path: PathBuf,
+    source: Option<String>,
+    #[serde(default)]
+    write: bool,
+    start: Option<u32>,
+    end: Option<u32>,
+}
+
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new<S: Into<String>>(code: i64, message: S) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<FileProcessingError> for RpcError {
+    fn from(e: FileProcessingError) -> Self {
+        Self::new(PROCESSING_ERROR, e.to_string())
+    }
+}
+
+/// State which is kept between the requests of all connections
+#[derive(Debug)]
+pub struct Daemon {
+    config_path: PathBuf,
+    /// Modification time of the config file when it was last read
+    config_modified: Mutex<Option<SystemTime>>,
+    /// Replaced as a whole when the config file changes, running requests keep the previous one
+    data: RwLock<Arc<CliSharedData>>,
+    /// Result of the last `check` for every file, which is reused while the source code,
+    /// the config and the other files read by the rules (like parent templates) are unchanged
+    check_cache: Mutex<CheckCache>,
+    /// Recorded for every request, but only served with `--metrics`
+    metrics: Arc<Metrics>,
+}
+
+/// Maximum amount of files in the `check` cache, the least recently checked one is evicted first
+const CHECK_CACHE_CAPACITY: usize = 1000;
+
+#[derive(Debug, Default)]
+struct CheckCache {
+    entries: HashMap<PathBuf, CachedCheck>,
+    /// Incremented with every `check`, to find the least recently used entry
+    uses: u64,
+}
+
+/// Source code, config, read files and result of a `check`
+#[derive(Debug)]
+struct CachedCheck {
+    source: String,
+    data: Arc<CliSharedData>,
+    dependencies: Vec<FileDependency>,
+    result: Value,
+    last_used: u64,
+}
+
+impl CheckCache {
+    /// The cached result, if the file, the config and the dependencies are unchanged
+    fn get(&mut self, path: &Path, source: &str, data: &Arc<CliSharedData>) -> Option<Value> {
+        self.uses += 1;
+        let entry = self.entries.get_mut(path)?;
+        if entry.source != source
+            || !Arc::ptr_eq(&entry.data, data)
+            || !dependencies_unchanged(&entry.dependencies)
+        {
+            return None;
+        }
+
+        entry.last_used = self.uses;
+        Some(entry.result.clone())
+    }
+
+    fn insert(
+        &mut self,
+        path: PathBuf,
+        source: String,
+        data: Arc<CliSharedData>,
+        dependencies: Vec<FileDependency>,
+        result: Value,
+    ) {
+        if self.entries.len() >= CHECK_CACHE_CAPACITY && !self.entries.contains_key(&path) {
+            if let Some(least_recently_used) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(path, _)| path.clone())
+            {
+                self.entries.remove(&least_recently_used);
+            }
+        }
+
+        self.entries.insert(
+            path,
+            CachedCheck {
+                source,
+                data,
+                dependencies,
+                result,
+                last_used: self.uses,
+            },
+        );
+    }
+}
+
+impl Daemon {
+    pub fn new(
+        config_path: PathBuf,
+        config: Config,
+        rule_registry: RuleRegistry,
+        rule_definitions: Vec<&'static dyn Rule>,
+    ) -> Self {
+        Self {
+            config_modified: Mutex::new(modified_time(&config_path)),
+            config_path,
+            data: RwLock::new(shared_data(config, rule_registry, rule_definitions)),
+            check_cache: Mutex::new(CheckCache::default()),
+            metrics: Arc::new(Metrics::default()),
+        }
+    }
+
+    /// Read the config file again if it changed since it was last read.
+    /// Returns `None` if it is unchanged, otherwise the differences in the effective configuration
+    /// or the error of the new config (in which case the previous config stays active).
+    pub fn reload_config_if_changed(&self) -> Option<Result<Vec<String>, String>> {
+        let modified = modified_time(&self.config_path);
+        {
+            let mut config_modified = self.config_modified.lock().unwrap();
+            if *config_modified == modified {
+                return None;
+            }
+            *config_modified = modified;
+        }
+
+        let config = match Config::new(&self.config_path) {
+            Ok(config) => config,
+            Err(e) => return Some(Err(e.to_string())),
+        };
+
+        let mut data = self.data.write().unwrap();
+        let rule_definitions =
+            match get_config_active_rule_definitions(&data.rule_registry, &config) {
+                Ok(rules) => rules,
+                Err(e) => return Some(Err(e.to_string())),
+            };
+
+        let mut diff = config_diff(&data.config, &config);
+        let rule_names = |rules: &[&'static dyn Rule]| -> Vec<&'static str> {
+            rules.iter().map(|r| r.name()).collect()
+        };
+        let (old_rules, new_rules) = (
+            rule_names(&data.rule_definitions),
+            rule_names(&rule_definitions),
+        );
+        let activated: Vec<_> = new_rules
+            .iter()
+            .filter(|r| !old_rules.contains(r))
+            .collect();
+        let deactivated: Vec<_> = old_rules
+            .iter()
+            .filter(|r| !new_rules.contains(r))
+            .collect();
+        if !activated.is_empty() {
+            diff.push(format!("activated rules: {activated:?}"));
+        }
+        if !deactivated.is_empty() {
+            diff.push(format!("deactivated rules: {deactivated:?}"));
+        }
+
+        *data = shared_data(config, data.rule_registry.clone(), rule_definitions);
+        Some(Ok(diff))
+    }
+
+    /// Answer a single line of the protocol with a single line.
+    pub fn handle_line(&self, line: &str) -> String {
+        match self.reload_config_if_changed() {
+            Some(Ok(diff)) => {
+                println!("Configuration reloaded from {}", self.config_path.display());
+                for change in diff {
+                    println!("  {change}");
+                }
+            }
+            Some(Err(e)) => {
+                println!("Error: can't reload the configuration, the previous one is still used:");
+                println!("{e}");
+            }
+            None => {}
+        }
+
+        let (id, result) = match serde_json::from_str::<Value>(line) {
+            Ok(value) => {
+                let id = value.get("id").cloned().unwrap_or(Value::Null);
+                match serde_json::from_value::<Request>(value) {
+                    Ok(request) => {
+                        let start = Instant::now();
+                        let result = self.handle_request(&request);
+                        self.metrics
+                            .record_request(&request.method, start.elapsed());
+                        (request.id.clone(), result)
+                    }
+                    Err(e) => (id, Err(RpcError::new(INVALID_REQUEST, e.to_string()))),
+                }
+            }
+            Err(e) => (Value::Null, Err(RpcError::new(PARSE_ERROR, e.to_string()))),
+        };
+
+        let response = match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(e) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": e.code, "message": e.message },
+            }),
+        };
+
+        response.to_string()
+    }
+
+    fn handle_request(&self, request: &Request) -> Result<Value, RpcError> {
+        let params: FileParams = serde_json::from_value(request.params.clone())
+            .map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))?;
+        let source = match &params.source {
+            Some(source) => source.clone(),
+            None => {
+                fs::read_to_string(&params.path).map_err(|e| FileProcessingError::FileRead {
+                    path: params.path.clone(),
+                    io_error: e,
+                })?
+            }
+        };
+
+        match request.method.as_str() {
+            "check" => Ok(self.check(params.path, source)),
+            "fix" => {
+                let fixed = self.fix(params.path.clone(), source, None)?;
+                if params.write && fixed["changed"] == Value::Bool(true) {
+                    fs::write(&params.path, fixed["source"].as_str().unwrap_or_default()).map_err(
+                        |e| FileProcessingError::FileWrite {
+                            path: params.path,
+                            io_error: e,
+                        },
+                    )?;
+                }
+                Ok(fixed)
+            }
+            "format-range" => {
+                let Some(range) = selected_range(&params, &source)? else {
+                    return Err(RpcError::new(INVALID_PARAMS, "missing start or end"));
+                };
+                Ok(self.fix(params.path, source, Some(range))?)
+            }
+            "explain" => {
+                let range = selected_range(&params, &source)?;
+                Ok(self.explain(params.path, source, range))
+            }
+            method => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("unknown method {method}"),
+            )),
+        }
+    }
+
+    fn check(&self, path: PathBuf, source: String) -> Value {
+        let data = Arc::clone(&self.data.read().unwrap());
+        if let Some(result) = self.check_cache.lock().unwrap().get(&path, &source, &data) {
+            self.metrics.record_check(true, &result);
+            return result;
+        }
+
+        let file_context = file_context(&data, path.clone(), source.clone());
+        let (check_results, dependencies) = record_dependencies(|| run_rules(&file_context));
+        let result = produce_json(&file_context, &check_results);
+        self.metrics.record_check(false, &result);
+
+        self.check_cache
+            .lock()
+            .unwrap()
+            .insert(path, source, data, dependencies, result.clone());
+        result
+    }
+
+    fn explain(&self, path: PathBuf, source: String, range: Option<TextRange>) -> Value {
+        let data = Arc::clone(&self.data.read().unwrap());
+        let file_context = file_context(&data, path, source);
+        let check_results = run_rules(&file_context);
+
+        let diagnostics: Vec<Value> = check_results
+            .iter()
+            .filter(|result| match (range, result.covered_range()) {
+                (Some(range), Some(covered)) => range.intersect(covered).is_some(),
+                (Some(_), None) => false,
+                (None, _) => true,
+            })
+            .map(|result| {
+                let mut value = result_json(result);
+                value["explanation"] = json!(explain_result(&file_context, result));
+                value
+            })
+            .collect();
+
+        json!({ "diagnostics": diagnostics })
+    }
+
+    fn fix(
+        &self,
+        path: PathBuf,
+        source: String,
+        range: Option<TextRange>,
+    ) -> Result<Value, FileProcessingError> {
+        let data = Arc::clone(&self.data.read().unwrap());
+        let file_context = file_context(&data, path, source);
+        let check_results = run_rules(&file_context);
+        let (file_context, _, dirty, iterations) =
+            iteratively_apply_suggestions_in_range(file_context, check_results, range)?;
+
+        Ok(json!({
+            "source": file_context.source_code,
+            "changed": dirty,
+            "iterations": iterations,
+        }))
+    }
+}
+
+/// The byte range of the `start` and `end` params, if there are both
+fn selected_range(params: &FileParams, source: &str) -> Result<Option<TextRange>, RpcError> {
+    let (Some(start), Some(end)) = (params.start, params.end) else {
+        return Ok(None);
+    };
+    if start > end || end as usize > source.len() {
+        return Err(RpcError::new(
+            INVALID_PARAMS,
+            "range is outside of the source",
+        ));
+    }
+
+    Ok(Some(TextRange::new(
+        TextSize::from(start),
+        TextSize::from(end),
+    )))
+}
+
+fn file_context(data: &Arc<CliSharedData>, path: PathBuf, source: String) -> FileContext {
+    // the results are returned in the response, so nothing is sent to the receiver
+    let (tx, _rx) = mpsc::channel();
+    let parse = ludtwig_parser::parse_for_twig_version(&source, data.config.general.twig_version);
+    let root = SyntaxNode::new_root(parse.green_node);
+    let file_rule_definitions = skip_expensive_rules(
+        &data.config,
+        &source,
+        get_file_active_rule_definitions(&root, &data.rule_definitions),
+    );
+
+    FileContext {
+        cli_context: CliContext {
+            output_tx: tx,
+            data: Arc::clone(data),
+        },
+        file_path: path,
+        tree_root: root,
+        source_code: source,
+        parse_errors: parse.errors,
+        file_rule_definitions,
+    }
+}
+
+fn shared_data(
+    config: Config,
+    rule_registry: RuleRegistry,
+    rule_definitions: Vec<&'static dyn Rule>,
+) -> Arc<CliSharedData> {
+    Arc::new(CliSharedData {
+        fix: false,
+        inspect: false,
+        explain_context: false,
+        preview_fix: None,
+        config,
+        rule_registry,
+        rule_definitions,
+    })
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Listen on the unix socket (and serve the metrics on the address) until the process is terminated.
+#[cfg(unix)]
+pub fn run(socket: &Path, metrics_address: Option<SocketAddr>, daemon: Daemon) -> i32 {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::net::UnixListener;
+
+    // remove the socket of a previous daemon, but never other files
+    if fs::metadata(socket).is_ok_and(|m| m.file_type().is_socket()) {
+        let _ = fs::remove_file(socket);
+    }
+
+    let listener = match UnixListener::bind(socket) {
+        Ok(listener) => listener,
+        Err(e) => {
+            println!("Error: can't listen on {}: {e}", socket.to_string_lossy());
+            return 1;
+        }
+    };
+    println!("Listening on {}", socket.to_string_lossy());
+
+    if let Some(address) = metrics_address {
+        if let Err(e) = metrics::serve(address, Arc::clone(&daemon.metrics)) {
+            println!("Error: can't serve metrics on {address}: {e}");
+            return 1;
+        }
+    }
+
+    let daemon = Arc::new(daemon);
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let daemon = Arc::clone(&daemon);
+
+        // every connection is handled by its own thread, the caches are shared between them
+        std::thread::spawn(move || {
+            let Ok(mut writer) = stream.try_clone() else {
+                return;
+            };
+            for line in BufReader::new(stream).lines() {
+                let Ok(line) = line else {
+                    return;
+                };
+                if line.trim().is_empty() {
+                    continue;
+                }
+
+                let response = daemon.handle_line(&line);
+                if writeln!(writer, "{response}").is_err() {
+                    return;
+                }
+            }
+        });
+    }
+
+    0
+}
+
+#[cfg(not(unix))]
+pub fn run(_socket: &Path, _metrics_address: Option<SocketAddr>, _daemon: Daemon) -> i32 {
+    println!("Error: the daemon is only supported on unix systems");
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+
+    fn daemon(rules: &[&str]) -> Daemon {
+        let config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        let rule_registry = RuleRegistry::default();
+        let rule_definitions = rules
+            .iter()
+            .map(|r| rule_registry.get(r).unwrap())
+            .collect();
+
+        Daemon::new(
+            PathBuf::from(crate::config::DEFAULT_CONFIG_PATH),
+            config,
+            rule_registry,
+            rule_definitions,
+        )
+    }
+
+    #[test]
+    fn daemon_check() {
+        let daemon = daemon(&["twig-logic-and"]);
+        let request = r#"{"jsonrpc":"2.0","id":1,"method":"check","params":{"path":"./a.html.twig","source":"{% if a && b %}{% endif %}<div"}}"#;
+
+        let response = daemon.handle_line(request);
+        expect![[r#"{"id":1,"jsonrpc":"2.0","result":{"diagnostics":[{"fixable":true,"message":"'&&' is not a valid twig operator","primary":{"message":"help: change this operator","range":{"end":10,"start":8}},"rule":"twig-logic-and","secondary":[],"severity":"error","suggestions":[{"message":"Try this operator instead","range":{"end":10,"start":8},"replace_with":"and"}]}],"parse_errors":[{"message":"expected > or </div> ending tag but reached end of file","range":{"end":30,"start":27}}]}}"#]]
+            .assert_eq(&response);
+
+        // unchanged files are answered from the cache
+        assert_eq!(daemon.handle_line(request), response);
+        assert_eq!(daemon.check_cache.lock().unwrap().entries.len(), 1);
+
+        let metrics = daemon.metrics.render();
+        assert!(metrics.contains("ludtwig_check_cache_hits_total 1\n"));
+        assert!(metrics
+            .contains("ludtwig_findings_total{rule=\"twig-logic-and\",severity=\"error\"} 2\n"));
+        assert!(metrics.contains("ludtwig_request_duration_seconds_count{method=\"check\"} 2\n"));
+    }
+
+    #[test]
+    fn daemon_check_cache_evicts_least_recently_used() {
+        let daemon = daemon(&[]);
+        let check = |path: &str| {
+            daemon.handle_line(&format!(
+                r#"{{"jsonrpc":"2.0","id":1,"method":"check","params":{{"path":"{path}","source":""}}}}"#
+            ));
+        };
+
+        for i in 0..CHECK_CACHE_CAPACITY {
+            check(&format!("./{i}.html.twig"));
+        }
+        check("./0.html.twig");
+        check("./new.html.twig");
+
+        let cache = daemon.check_cache.lock().unwrap();
+        assert_eq!(cache.entries.len(), CHECK_CACHE_CAPACITY);
+        assert!(cache.entries.contains_key(Path::new("./0.html.twig")));
+        assert!(!cache.entries.contains_key(Path::new("./1.html.twig")));
+        assert!(cache.entries.contains_key(Path::new("./new.html.twig")));
+    }
+
+    #[test]
+    fn daemon_check_cache_notices_changed_parent_templates() {
+        let dir =
+            std::env::temp_dir().join(format!("ludtwig-daemon-parent-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let parent = dir.join("base.html.twig");
+        std::fs::write(&parent, "{% block a %}{% endblock %}").unwrap();
+
+        let mut config = Config::new(crate::config::DEFAULT_CONFIG_PATH).unwrap();
+        config.rules.template_roots = vec![dir.to_string_lossy().to_string()];
+        let rule_registry = RuleRegistry::default();
+        let rule_definitions = vec![rule_registry.get("twig-block-not-in-parent").unwrap()];
+        let daemon = Daemon::new(
+            PathBuf::from(crate::config::DEFAULT_CONFIG_PATH),
+            config,
+            rule_registry,
+            rule_definitions,
+        );
+
+        let request = r#"{"jsonrpc":"2.0","id":1,"method":"check","params":{"path":"./child.html.twig","source":"{% extends 'base.html.twig' %}{% block b %}{% endblock %}"}}"#;
+        let diagnostics = |response: String| -> usize {
+            let response: Value = serde_json::from_str(&response).unwrap();
+            response["result"]["diagnostics"].as_array().unwrap().len()
+        };
+        assert_eq!(diagnostics(daemon.handle_line(request)), 1);
+
+        // the child is unchanged, but the parent now defines the block
+        // (with a different modification time, the file system may be too coarse otherwise)
+        std::fs::write(
+            &parent,
+            "{% block a %}{% endblock %}{% block b %}{% endblock %}",
+        )
+        .unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&parent)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH)
+            .unwrap();
+        assert_eq!(diagnostics(daemon.handle_line(request)), 0);
+        assert!(daemon
+            .metrics
+            .render()
+            .contains("ludtwig_check_cache_misses_total 2\n"));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn daemon_fix_and_format_range() {
+        let daemon = daemon(&["twig-logic-and"]);
+
+        expect![[r#"{"id":2,"jsonrpc":"2.0","result":{"changed":true,"iterations":1,"source":"{{ a and b }}{{ c and d }}"}}"#]]
+            .assert_eq(&daemon.handle_line(
+                r#"{"jsonrpc":"2.0","id":2,"method":"fix","params":{"path":"./a.html.twig","source":"{{ a && b }}{{ c && d }}"}}"#,
+            ));
+        expect![[r#"{"id":3,"jsonrpc":"2.0","result":{"changed":true,"iterations":1,"source":"{{ a && b }}{{ c and d }}"}}"#]]
+            .assert_eq(&daemon.handle_line(
+                r#"{"jsonrpc":"2.0","id":3,"method":"format-range","params":{"path":"./a.html.twig","source":"{{ a && b }}{{ c && d }}","start":12,"end":24}}"#,
+            ));
+    }
+
+    #[test]
+    fn daemon_explain() {
+        let daemon = daemon(&["twig-logic-and"]);
+        let response: Value = serde_json::from_str(&daemon.handle_line(
+            r#"{"jsonrpc":"2.0","id":7,"method":"explain","params":{"path":"./a.html.twig","source":"{{ a && b }}{{ c && d }}","start":0,"end":12}}"#,
+        ))
+        .unwrap();
+
+        // only the result inside of the range is explained
+        let diagnostics = response["result"]["diagnostics"].as_array().unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0]["rule"], "twig-logic-and");
+
+        let explanation: Vec<&str> = diagnostics[0]["explanation"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|note| note.as_str().unwrap())
+            .collect();
+        expect![[r#"
+            why: Twig uses 'and' as the logical operator, '&&' is not valid twig syntax.
+            found in: ROOT > TWIG_VAR > TWIG_EXPRESSION > TWIG_BINARY_EXPRESSION
+            TWIG_BINARY_EXPRESSION@2..9
+              TWIG_EXPRESSION@2..4
+                TWIG_LITERAL_NAME@2..4
+                  TK_WHITESPACE@2..3 " "
+                  TK_WORD@3..4 "a"
+              TK_WHITESPACE@4..5 " "
+              TK_DOUBLE_AMPERSAND@5..7 "&&"
+              TWIG_EXPRESSION@7..9
+                TWIG_LITERAL_NAME@7..9
+                  TK_WHITESPACE@7..8 " "
+                  TK_WORD@8..9 "b""#]]
+        .assert_eq(&explanation.join("\n"));
+    }
+
+    #[test]
+    fn daemon_errors() {
+        let daemon = daemon(&[]);
+
+        expect![[r#"{"error":{"code":-32700,"message":"expected ident at line 1 column 2"},"id":null,"jsonrpc":"2.0"}"#]]
+            .assert_eq(&daemon.handle_line("no json"));
+        expect![[r#"{"error":{"code":-32601,"message":"unknown method lint"},"id":4,"jsonrpc":"2.0"}"#]]
+            .assert_eq(&daemon.handle_line(
+                r#"{"jsonrpc":"2.0","id":4,"method":"lint","params":{"path":"./a.html.twig","source":""}}"#,
+            ));
+        expect![[r#"{"error":{"code":-32602,"message":"range is outside of the source"},"id":5,"jsonrpc":"2.0"}"#]]
+            .assert_eq(&daemon.handle_line(
+                r#"{"jsonrpc":"2.0","id":5,"method":"format-range","params":{"path":"./a.html.twig","source":"","start":0,"end":3}}"#,
+            ));
+        expect![[r#"{"error":{"code":-32000,"message":"file ./missing.html.twig can't be read"},"id":6,"jsonrpc":"2.0"}"#]]
+            .assert_eq(&daemon.handle_line(
+                r#"{"jsonrpc":"2.0","id":6,"method":"check","params":{"path":"./missing.html.twig"}}"#,
+            ));
+    }
+
+    #[test]
+    fn daemon_reloads_changed_config() {
+        let dir =
+            std::env::temp_dir().join(format!("ludtwig-daemon-reload-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("ludtwig-config.toml");
+        std::fs::write(
+            &config_path,
+            "[general]\nactive-rules = [\"twig-logic-and\"]\n",
+        )
+        .unwrap();
+
+        let config = Config::new(&config_path).unwrap();
+        let rules = get_config_active_rule_definitions(&RuleRegistry::default(), &config).unwrap();
+        let daemon = Daemon::new(config_path.clone(), config, RuleRegistry::default(), rules);
+        assert!(daemon.reload_config_if_changed().is_none());
+
+        // force a different modification time, the file system may be too coarse otherwise
+        *daemon.config_modified.lock().unwrap() = None;
+        std::fs::write(
+            &config_path,
+            "[general]\nactive-rules = [\"twig-logic-or\"]\n[format]\nindentation-count = 2\n",
+        )
+        .unwrap();
+        expect![[r#"
+            format.indentation-count: 4 -> 2
+            activated rules: ["twig-logic-or"]
+            deactivated rules: ["twig-logic-and"]"#]]
+        .assert_eq(
+            &daemon
+                .reload_config_if_changed()
+                .unwrap()
+                .unwrap()
+                .join("\n"),
+        );
+        let request = r#"{"jsonrpc":"2.0","id":1,"method":"fix","params":{"path":"./a.html.twig","source":"{{ a && b or c || d }}"}}"#;
+        expect![[r#"{"id":1,"jsonrpc":"2.0","result":{"changed":true,"iterations":1,"source":"{{ a && b or c or d }}"}}"#]].assert_eq(&daemon.handle_line(request));
+
+        // invalid configs are reported and the previous one stays active
+        *daemon.config_modified.lock().unwrap() = None;
+        std::fs::write(
+            &config_path,
+            "[general]\nactive-rules = [\"unknown-rule\"]\n",
+        )
+        .unwrap();
+        assert!(daemon.reload_config_if_changed().unwrap().is_err());
+        expect![[r#"{"id":1,"jsonrpc":"2.0","result":{"changed":true,"iterations":1,"source":"{{ a && b or c or d }}"}}"#]].assert_eq(&daemon.handle_line(request));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
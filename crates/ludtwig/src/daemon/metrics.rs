@@ -0,0 +1,328 @@
+//! Opt-in metrics of the daemon (`--metrics <ADDRESS>`) in the Prometheus text format,
+//! which are served on `GET /metrics` of a small http server.
+//!
+//! - `ludtwig_files_checked_total` amount of answered `check` requests
+//! - `ludtwig_check_cache_hits_total` / `ludtwig_check_cache_misses_total` reuse of previous `check` results
+//! - `ludtwig_findings_total{rule, severity}` rule results of all answered `check` requests
+//! - `ludtwig_parse_errors_total` parser errors of all answered `check` requests
+//! - `ludtwig_request_duration_seconds{method}` histogram of the time to answer the requests
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::net::{SocketAddr, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde_json::Value;
+
+/// Upper bounds of the request duration histogram buckets in seconds
+const DURATION_BUCKETS: [f64; 10] = [0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5];
+
+/// Methods which are recorded with their own label, everything else is recorded as `unknown`
+const KNOWN_METHODS: [&str; 4] = ["check", "fix", "format-range", "explain"];
+
+#[derive(Debug, Default)]
+pub struct Metrics {
+    data: Mutex<MetricsData>,
+}
+
+#[derive(Debug, Default)]
+struct MetricsData {
+    files_checked: u64,
+    cache_hits: u64,
+    cache_misses: u64,
+    /// Amount of rule results by rule name and severity
+    findings: BTreeMap<(String, String), u64>,
+    parse_errors: u64,
+    /// Request durations by method
+    durations: BTreeMap<&'static str, Histogram>,
+}
+
+#[derive(Debug, Default)]
+struct Histogram {
+    /// Amount of observations which are less than or equal to the bucket with the same index
+    buckets: [u64; DURATION_BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, value: f64) {
+        for (bucket, upper_bound) in self.buckets.iter_mut().zip(DURATION_BUCKETS) {
+            if value <= upper_bound {
+                *bucket += 1;
+            }
+        }
+        self.count += 1;
+        self.sum += value;
+    }
+}
+
+impl Metrics {
+    /// Record an answered `check` request with its result (which contains the diagnostics)
+    pub fn record_check(&self, cache_hit: bool, result: &Value) {
+        let mut data = self.data.lock().unwrap();
+        data.files_checked += 1;
+        if cache_hit {
+            data.cache_hits += 1;
+        } else {
+            data.cache_misses += 1;
+        }
+
+        for diagnostic in result["diagnostics"].as_array().into_iter().flatten() {
+            let rule = diagnostic["rule"].as_str().unwrap_or_default().to_string();
+            let severity = diagnostic["severity"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+            *data.findings.entry((rule, severity)).or_default() += 1;
+        }
+        data.parse_errors += result["parse_errors"].as_array().map_or(0, Vec::len) as u64;
+    }
+
+    /// Record the time it took to answer a request
+    pub fn record_request(&self, method: &str, duration: Duration) {
+        let method = KNOWN_METHODS
+            .into_iter()
+            .find(|known| *known == method)
+            .unwrap_or("unknown");
+
+        let mut data = self.data.lock().unwrap();
+        data.durations
+            .entry(method)
+            .or_default()
+            .observe(duration.as_secs_f64());
+    }
+
+    /// All metrics in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let data = self.data.lock().unwrap();
+        let mut out = String::new();
+
+        let mut counter = |name: &str, help: &str, value: u64| {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} counter");
+            let _ = writeln!(out, "{name} {value}");
+        };
+        counter(
+            "ludtwig_files_checked_total",
+            "Amount of answered check requests.",
+            data.files_checked,
+        );
+        counter(
+            "ludtwig_check_cache_hits_total",
+            "Check requests which were answered with the result of an unchanged file.",
+            data.cache_hits,
+        );
+        counter(
+            "ludtwig_check_cache_misses_total",
+            "Check requests for which the rules had to run.",
+            data.cache_misses,
+        );
+        counter(
+            "ludtwig_parse_errors_total",
+            "Parser errors of all answered check requests.",
+            data.parse_errors,
+        );
+
+        out.push_str(
+            "# HELP ludtwig_findings_total Rule results of all answered check requests.\n",
+        );
+        out.push_str("# TYPE ludtwig_findings_total counter\n");
+        for ((rule, severity), count) in &data.findings {
+            let _ = writeln!(
+                out,
+                "ludtwig_findings_total{{rule=\"{rule}\",severity=\"{severity}\"}} {count}"
+            );
+        }
+
+        out.push_str(
+            "# HELP ludtwig_request_duration_seconds Time to answer the requests by method.\n",
+        );
+        out.push_str("# TYPE ludtwig_request_duration_seconds histogram\n");
+        for (method, histogram) in &data.durations {
+            let name = "ludtwig_request_duration_seconds";
+            for (count, upper_bound) in histogram.buckets.iter().zip(DURATION_BUCKETS) {
+                let _ = writeln!(
+                    out,
+                    "{name}_bucket{{method=\"{method}\",le=\"{upper_bound}\"}} {count}"
+                );
+            }
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{method=\"{method}\",le=\"+Inf\"}} {}",
+                histogram.count
+            );
+            let _ = writeln!(out, "{name}_sum{{method=\"{method}\"}} {}", histogram.sum);
+            let _ = writeln!(
+                out,
+                "{name}_count{{method=\"{method}\"}} {}",
+                histogram.count
+            );
+        }
+
+        out
+    }
+}
+
+/// Serve the metrics on `GET /metrics` in a background thread until the process is terminated.
+pub fn serve(address: SocketAddr, metrics: Arc<Metrics>) -> std::io::Result<()> {
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind(address)?;
+    println!("Serving metrics on http://{address}/metrics");
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else {
+                continue;
+            };
+            // a slow scraper must not block the others
+            let metrics = Arc::clone(&metrics);
+            std::thread::spawn(move || handle_connection(stream, &metrics));
+        }
+    });
+
+    Ok(())
+}
+
+/// Answer a single http request of the connection
+fn handle_connection(mut stream: TcpStream, metrics: &Metrics) {
+    use std::io::{BufRead, BufReader};
+
+    // connections which don't send a request are closed eventually
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+    let Ok(reader) = stream.try_clone() else {
+        return;
+    };
+
+    // only the request line is needed, the headers are skipped
+    let mut lines = BufReader::new(reader).lines();
+    let Some(Ok(request_line)) = lines.next() else {
+        return;
+    };
+    for line in lines.by_ref() {
+        if line.map_or(true, |l| l.is_empty()) {
+            break;
+        }
+    }
+
+    let (status, body) = if request_line.starts_with("GET /metrics ") {
+        ("200 OK", metrics.render())
+    } else {
+        (
+            "404 Not Found",
+            "not found, metrics are served on /metrics\n".to_string(),
+        )
+    };
+    let _ = std::io::Write::write_all(
+        &mut stream,
+        format!(
+            "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .as_bytes(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn renders_metrics() {
+        let metrics = Metrics::default();
+        let result = json!({
+            "parse_errors": [{}],
+            "diagnostics": [
+                {"rule": "twig-logic-and", "severity": "error"},
+                {"rule": "twig-logic-and", "severity": "error"},
+                {"rule": "indentation", "severity": "help"},
+            ],
+        });
+        metrics.record_check(false, &result);
+        metrics.record_check(true, &result);
+        metrics.record_request("check", Duration::from_millis(20));
+        metrics.record_request("check", Duration::from_millis(2));
+        metrics.record_request("lint", Duration::from_secs(3));
+
+        expect![[r##"
+            # HELP ludtwig_files_checked_total Amount of answered check requests.
+            # TYPE ludtwig_files_checked_total counter
+            ludtwig_files_checked_total 2
+            # HELP ludtwig_check_cache_hits_total Check requests which were answered with the result of an unchanged file.
+            # TYPE ludtwig_check_cache_hits_total counter
+            ludtwig_check_cache_hits_total 1
+            # HELP ludtwig_check_cache_misses_total Check requests for which the rules had to run.
+            # TYPE ludtwig_check_cache_misses_total counter
+            ludtwig_check_cache_misses_total 1
+            # HELP ludtwig_parse_errors_total Parser errors of all answered check requests.
+            # TYPE ludtwig_parse_errors_total counter
+            ludtwig_parse_errors_total 2
+            # HELP ludtwig_findings_total Rule results of all answered check requests.
+            # TYPE ludtwig_findings_total counter
+            ludtwig_findings_total{rule="indentation",severity="help"} 2
+            ludtwig_findings_total{rule="twig-logic-and",severity="error"} 4
+            # HELP ludtwig_request_duration_seconds Time to answer the requests by method.
+            # TYPE ludtwig_request_duration_seconds histogram
+            ludtwig_request_duration_seconds_bucket{method="check",le="0.001"} 0
+            ludtwig_request_duration_seconds_bucket{method="check",le="0.005"} 1
+            ludtwig_request_duration_seconds_bucket{method="check",le="0.01"} 1
+            ludtwig_request_duration_seconds_bucket{method="check",le="0.025"} 2
+            ludtwig_request_duration_seconds_bucket{method="check",le="0.05"} 2
+            ludtwig_request_duration_seconds_bucket{method="check",le="0.1"} 2
+            ludtwig_request_duration_seconds_bucket{method="check",le="0.25"} 2
+            ludtwig_request_duration_seconds_bucket{method="check",le="0.5"} 2
+            ludtwig_request_duration_seconds_bucket{method="check",le="1"} 2
+            ludtwig_request_duration_seconds_bucket{method="check",le="2.5"} 2
+            ludtwig_request_duration_seconds_bucket{method="check",le="+Inf"} 2
+            ludtwig_request_duration_seconds_sum{method="check"} 0.022
+            ludtwig_request_duration_seconds_count{method="check"} 2
+            ludtwig_request_duration_seconds_bucket{method="unknown",le="0.001"} 0
+            ludtwig_request_duration_seconds_bucket{method="unknown",le="0.005"} 0
+            ludtwig_request_duration_seconds_bucket{method="unknown",le="0.01"} 0
+            ludtwig_request_duration_seconds_bucket{method="unknown",le="0.025"} 0
+            ludtwig_request_duration_seconds_bucket{method="unknown",le="0.05"} 0
+            ludtwig_request_duration_seconds_bucket{method="unknown",le="0.1"} 0
+            ludtwig_request_duration_seconds_bucket{method="unknown",le="0.25"} 0
+            ludtwig_request_duration_seconds_bucket{method="unknown",le="0.5"} 0
+            ludtwig_request_duration_seconds_bucket{method="unknown",le="1"} 0
+            ludtwig_request_duration_seconds_bucket{method="unknown",le="2.5"} 0
+            ludtwig_request_duration_seconds_bucket{method="unknown",le="+Inf"} 1
+            ludtwig_request_duration_seconds_sum{method="unknown"} 3
+            ludtwig_request_duration_seconds_count{method="unknown"} 1
+        "##]]
+        .assert_eq(&metrics.render());
+    }
+
+    #[test]
+    fn idle_connections_do_not_block_other_scrapers() {
+        use std::io::{Read, Write};
+        use std::net::{TcpListener, TcpStream};
+
+        // find a free port for the server
+        let address = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        serve(address, Arc::new(Metrics::default())).unwrap();
+
+        // this connection never sends a request
+        let _idle = TcpStream::connect(address).unwrap();
+
+        let mut scraper = TcpStream::connect(address).unwrap();
+        scraper
+            .set_read_timeout(Some(Duration::from_secs(2)))
+            .unwrap();
+        scraper
+            .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        let mut response = String::new();
+        scraper.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("ludtwig_files_checked_total 0"));
+    }
+}
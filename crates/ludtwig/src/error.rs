@@ -1,6 +1,6 @@
 use std::error::Error;
 use std::fmt::{Display, Formatter};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug)]
 pub enum FileProcessingError {
@@ -12,10 +12,22 @@ pub enum FileProcessingError {
         path: PathBuf,
         io_error: std::io::Error,
     },
-    MaxApplyIteration,
     OverlappingSuggestionInSingleRule {
         rule_name: String,
     },
+    Configuration(ConfigurationError),
+}
+
+impl FileProcessingError {
+    /// The path of the file for errors which include it in their message
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            FileProcessingError::FileRead { path, .. }
+            | FileProcessingError::FileWrite { path, .. } => Some(path),
+            FileProcessingError::OverlappingSuggestionInSingleRule { .. }
+            | FileProcessingError::Configuration(_) => None,
+        }
+    }
 }
 
 impl Display for FileProcessingError {
@@ -27,12 +39,12 @@ impl Display for FileProcessingError {
             FileProcessingError::FileWrite { path, .. } => {
                 write!(f, "file {} can't be written", path.to_string_lossy())
             }
-            FileProcessingError::MaxApplyIteration => {
-                write!(f, "max suggestion apply iteration encountered. This may be caused by fighting rules (programmer error) or too many conflicting suggestions at once")
-            }
             FileProcessingError::OverlappingSuggestionInSingleRule { rule_name } => {
                 write!(f, "Suggestion collision inside the same rule, check rule {rule_name} or write bug report - this is a programmer error")
             }
+            FileProcessingError::Configuration(e) => {
+                write!(f, "{e}")
+            }
         }
     }
 }
@@ -42,8 +54,8 @@ impl Error for FileProcessingError {
         match self {
             FileProcessingError::FileRead { io_error, .. }
             | FileProcessingError::FileWrite { io_error, .. } => Some(io_error),
-            FileProcessingError::MaxApplyIteration
-            | FileProcessingError::OverlappingSuggestionInSingleRule { .. } => None,
+            FileProcessingError::Configuration(e) => Some(e),
+            FileProcessingError::OverlappingSuggestionInSingleRule { .. } => None,
         }
     }
 }
@@ -52,6 +64,8 @@ impl Error for FileProcessingError {
 #[derive(Debug)]
 pub enum ConfigurationError {
     RuleNotFound { name: String },
+    PresetNotFound { name: String },
+    InvalidRuleConfig { rule: String, message: String },
 }
 
 impl Display for ConfigurationError {
@@ -60,6 +74,12 @@ impl Display for ConfigurationError {
             ConfigurationError::RuleNotFound { name } => {
                 write!(f, "Can't find active rule {name}")
             }
+            ConfigurationError::PresetNotFound { name } => {
+                write!(f, "Can't find extended preset {name}")
+            }
+            ConfigurationError::InvalidRuleConfig { rule, message } => {
+                write!(f, "Invalid configuration for rule {rule}: {message}")
+            }
         }
     }
 }
@@ -2,12 +2,13 @@
 #![allow(clippy::module_name_repetitions)]
 
 use crate::check::rule::{Rule, Severity};
-use crate::check::rules::get_config_active_rule_definitions;
+use crate::check::rules::{get_config_active_rule_definitions, RuleRegistry};
 use crate::config::Config;
 use crate::output::ProcessingEvent;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use ignore::types::TypesBuilder;
 use ignore::{WalkBuilder, WalkState};
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc::Sender;
 use std::sync::{mpsc, Arc};
@@ -15,6 +16,7 @@ use std::thread;
 
 mod check;
 mod config;
+mod daemon;
 mod error;
 mod output;
 mod process;
@@ -22,7 +24,12 @@ mod process;
 // uses author, version and description from Cargo.toml
 #[derive(Parser, Debug, Clone)]
 #[command(author, version, about, long_about = None)]
+#[command(subcommand_negates_reqs = true)]
+#[allow(clippy::struct_excessive_bools)] // clap maps every command line flag to its own bool
 pub struct Opts {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Files or directories to scan
     #[arg(
         value_name = "FILE",
@@ -41,6 +48,14 @@ pub struct Opts {
     #[arg(short = 'i', long)]
     inspect: bool,
 
+    /// Explain every reported problem with the reasoning of its rule and the syntax tree fragment it was found in
+    #[arg(short = 'e', long)]
+    explain_context: bool,
+
+    /// Print out each file as it would look like after only applying the suggestions of this rule. This doesn't change any files.
+    #[arg(long, value_name = "RULE", conflicts_with_all = ["fix", "inspect"])]
+    preview_fix: Option<String>,
+
     /// Specify where the ludtwig configuration file is. Ludtwig looks in the current directory for a 'ludtwig-config.toml' by default.
     #[arg(short = 'c', long)]
     config_path: Option<PathBuf>,
@@ -50,6 +65,37 @@ pub struct Opts {
     create_config: bool,
 }
 
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Keep running and answer JSON-RPC requests (`check`, `fix` and `format-range`) on a unix socket.
+    /// This avoids the startup costs for tools which invoke ludtwig very often.
+    Daemon {
+        /// Path of the unix socket to listen on
+        #[arg(long, value_name = "PATH")]
+        socket: PathBuf,
+        /// Serve metrics for Prometheus (files checked, findings, cache hits and request durations)
+        /// on `http://<ADDRESS>/metrics`, like `127.0.0.1:9184`
+        #[arg(long, value_name = "ADDRESS")]
+        metrics: Option<SocketAddr>,
+    },
+    /// Inspect the configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConfigCommand {
+    /// Print the effective configuration (defaults, config file and `LUDTWIG_` environment variables merged)
+    /// together with the active rules
+    Show {
+        /// Also show which rules are active for this template (after `ludtwig-ignore-file` directives)
+        #[arg(long, value_name = "PATH")]
+        file: Option<PathBuf>,
+    },
+}
+
 /// Context to pass to every processing thead (can be cloned)
 #[derive(Debug)]
 pub struct CliContext {
@@ -65,8 +111,14 @@ pub struct CliSharedData {
     pub fix: bool,
     /// Print out the parsed syntax tree for each file
     pub inspect: bool,
+    /// Explain every reported problem with the reasoning of its rule and the syntax tree fragment
+    pub explain_context: bool,
+    /// Print out each file after only applying the suggestions of this rule
+    pub preview_fix: Option<String>,
     /// The config values to use.
     pub config: Config,
+    /// All known rules, which can be activated in the config
+    pub rule_registry: RuleRegistry,
     /// Config active rule definitions
     pub rule_definitions: Vec<&'static dyn Rule>,
 }
@@ -94,20 +146,61 @@ impl CliContext {
 fn main() {
     let opts: Opts = Opts::parse();
     let config = config::handle_config_or_exit(&opts);
+    // additional rules can be registered here with `.with(&MyRule)`
+    let rule_registry = RuleRegistry::default();
+
+    if let Some(Command::Daemon { socket, metrics }) = &opts.command {
+        let process_code = match get_config_active_rule_definitions(&rule_registry, &config) {
+            Ok(rules) => {
+                let config_path = opts
+                    .config_path
+                    .clone()
+                    .unwrap_or_else(|| PathBuf::from(config::DEFAULT_CONFIG_PATH));
+                daemon::run(
+                    socket,
+                    *metrics,
+                    daemon::Daemon::new(config_path, config, rule_registry, rules),
+                )
+            }
+            Err(e) => {
+                println!("Error: {e}");
+                1
+            }
+        };
+        std::process::exit(process_code);
+    }
 
-    let process_code = app(opts, config);
+    if let Some(Command::Config {
+        command: ConfigCommand::Show { file },
+    }) = &opts.command
+    {
+        let config_path = opts
+            .config_path
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(config::DEFAULT_CONFIG_PATH));
+        match config::show_config(&config_path, &config, &rule_registry, file.as_deref()) {
+            Ok(output) => print!("{output}"),
+            Err(e) => {
+                println!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        std::process::exit(0);
+    }
+
+    let process_code = app(opts, config, rule_registry);
     std::process::exit(process_code);
 }
 
 /// The entry point of the async application.
-fn app(opts: Opts, config: Config) -> i32 {
+fn app(opts: Opts, config: Config, rule_registry: RuleRegistry) -> i32 {
     println!("Scanning files...");
 
     // sender and receiver channels for the communication between tasks and the user.
     let (tx, rx) = mpsc::channel();
 
     // construct active rules
-    let active_rules = match get_config_active_rule_definitions(&config) {
+    let active_rules = match get_config_active_rule_definitions(&rule_registry, &config) {
         Ok(rules) => rules,
         Err(e) => {
             println!("Error: {e}");
@@ -115,12 +208,22 @@ fn app(opts: Opts, config: Config) -> i32 {
         }
     };
 
+    if let Some(rule_name) = &opts.preview_fix {
+        if let Err(e) = rule_registry.get(rule_name) {
+            println!("Error: {e}");
+            return 1;
+        }
+    }
+
     let cli_context = CliContext {
         output_tx: tx,
         data: Arc::new(CliSharedData {
             fix: opts.fix,
             inspect: opts.inspect,
+            explain_context: opts.explain_context,
+            preview_fix: opts.preview_fix,
             config,
+            rule_registry,
             rule_definitions: active_rules,
         }),
     };
@@ -189,17 +292,15 @@ fn handle_input_paths(paths: Vec<PathBuf>, cli_context: CliContext) {
 
                 let clone = cli_context.clone();
                 let tx_clone = cli_context.output_tx.clone();
-                s.spawn(
-                    move |_s1| match process::process_file(entry.path().into(), clone) {
-                        Ok(()) => {}
-                        Err(e) => {
-                            tx_clone
-                                .send(ProcessingEvent::Report(Severity::Error))
-                                .expect("output should still receive ProcessingEvents");
-                            println!("Error: {e}");
-                        }
-                    },
-                );
+                s.spawn(move |_s1| {
+                    let path = entry.into_path();
+                    if let Err(error) = process::process_file(path.clone(), clone) {
+                        // reported in the summary, so failures are not lost in the other output
+                        tx_clone
+                            .send(ProcessingEvent::FileFailed { path, error })
+                            .expect("output should still receive ProcessingEvents");
+                    }
+                });
 
                 WalkState::Continue
             })
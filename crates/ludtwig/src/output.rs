@@ -1,14 +1,34 @@
 use codespan_reporting::term::termcolor::{Buffer, BufferWriter, ColorChoice};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::Write as _;
 use std::io;
 use std::io::Write;
+use std::path::PathBuf;
 use std::sync::mpsc::Receiver;
 
 use crate::check::rule::Severity;
+use crate::error::FileProcessingError;
 
 pub enum ProcessingEvent {
     FileProcessed,
     Report(Severity),
     OutputStderrMessage(Buffer),
+    /// The suggestions were applied and the file was written (`--fix`)
+    FileFixed {
+        path: PathBuf,
+        iterations: usize,
+    },
+    /// The file couldn't be read, fixed or written (counts as an error)
+    FileFailed {
+        path: PathBuf,
+        error: FileProcessingError,
+    },
+    /// A finding of the rule was suppressed by a ludtwig-ignore directive
+    /// (only tracked while the `unused-suppression` rule is active)
+    Suppressed {
+        rule_name: &'static str,
+    },
 }
 
 /// This function receives all the [`CliOutputMessage`] instances from the receiver channel and
@@ -19,6 +39,9 @@ pub fn handle_processing_output(rx: &Receiver<ProcessingEvent>) -> i32 {
     let mut warning_count = 0;
     let mut help_count = 0;
     let mut info_count = 0;
+    let mut fixed_files = vec![];
+    let mut failed_files = vec![];
+    let mut suppressed_findings = BTreeMap::new();
 
     let stderr_writer = BufferWriter::stderr(ColorChoice::Always);
 
@@ -45,11 +68,28 @@ pub fn handle_processing_output(rx: &Receiver<ProcessingEvent>) -> i32 {
             ProcessingEvent::OutputStderrMessage(buffer) => {
                 stderr_writer.print(&buffer).unwrap();
             }
+            ProcessingEvent::FileFixed { path, iterations } => {
+                fixed_files.push((path, iterations));
+            }
+            ProcessingEvent::FileFailed { path, error } => {
+                error_count += 1;
+                failed_files.push((path, error));
+            }
+            ProcessingEvent::Suppressed { rule_name } => {
+                *suppressed_findings.entry(rule_name).or_default() += 1;
+            }
         }
     }
 
     drop(stderr_writer); // finish writing to stderr
 
+    // files are processed in parallel, so they are listed sorted by path to get a stable output
+    print!("{}", fixed_files_summary(&mut fixed_files));
+    print!("{}", suppressed_findings_summary(&suppressed_findings));
+    io::stderr()
+        .write_all(failed_files_summary(&mut failed_files).as_bytes())
+        .unwrap();
+
     let conclusion_msg = format!(
         "\nFiles scanned: {}, Errors: {}, Warnings: {}, Helps: {}, Info: {}, Total: {}\n",
         file_count,
@@ -68,3 +108,110 @@ pub fn handle_processing_output(rx: &Receiver<ProcessingEvent>) -> i32 {
         0
     }
 }
+
+/// Lines for every file which was changed by `--fix`, sorted by path
+fn fixed_files_summary(fixed_files: &mut [(PathBuf, usize)]) -> String {
+    fixed_files.sort_by(|(a, _), (b, _)| a.cmp(b));
+    fixed_files
+        .iter()
+        .fold(String::new(), |mut summary, (path, iterations)| {
+            let _ = writeln!(
+                summary,
+                "fixed {} in {iterations} iterations",
+                path.to_string_lossy()
+            );
+            summary
+        })
+}
+
+/// Amount of suppressed findings of every rule, sorted by rule name
+fn suppressed_findings_summary(suppressed_findings: &BTreeMap<&'static str, usize>) -> String {
+    if suppressed_findings.is_empty() {
+        return String::new();
+    }
+
+    let counts: Vec<String> = suppressed_findings
+        .iter()
+        .map(|(rule_name, count)| format!("{rule_name}: {count}"))
+        .collect();
+    format!(
+        "Suppressed findings by ludtwig-ignore directives: {}\n",
+        counts.join(", ")
+    )
+}
+
+/// Lines for every file which failed to process including the cause of the error, sorted by path
+fn failed_files_summary(failed_files: &mut [(PathBuf, FileProcessingError)]) -> String {
+    failed_files.sort_by(|(a, _), (b, _)| a.cmp(b));
+    failed_files
+        .iter()
+        .fold(String::new(), |mut summary, (path, error)| {
+            // the message of read and write errors already contains the path
+            if error.path().is_some() {
+                let _ = write!(summary, "Error: {error}");
+            } else {
+                let _ = write!(summary, "Error: {}: {error}", path.to_string_lossy());
+            }
+            if let Some(source) = error.source() {
+                let _ = write!(summary, " ({source})");
+            }
+            summary.push('\n');
+            summary
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+
+    use super::*;
+
+    #[test]
+    fn suppressed_findings_are_counted_by_rule() {
+        assert_eq!(suppressed_findings_summary(&BTreeMap::new()), "");
+        assert_eq!(
+            suppressed_findings_summary(&BTreeMap::from([
+                ("twig-logic-or", 1),
+                ("indentation", 3)
+            ])),
+            "Suppressed findings by ludtwig-ignore directives: indentation: 3, twig-logic-or: 1\n"
+        );
+    }
+
+    #[test]
+    fn summaries_are_sorted_by_path() {
+        let mut fixed_files = vec![
+            (PathBuf::from("./b/index.html.twig"), 2),
+            (PathBuf::from("./a/index.html.twig"), 1),
+        ];
+        assert_eq!(
+            fixed_files_summary(&mut fixed_files),
+            "fixed ./a/index.html.twig in 1 iterations\nfixed ./b/index.html.twig in 2 iterations\n"
+        );
+
+        let mut failed_files = vec![
+            (
+                PathBuf::from("./b.html.twig"),
+                FileProcessingError::FileWrite {
+                    path: PathBuf::from("./b.html.twig"),
+                    io_error: io::Error::new(ErrorKind::PermissionDenied, "permission denied"),
+                },
+            ),
+            (
+                PathBuf::from("./a.html.twig"),
+                FileProcessingError::OverlappingSuggestionInSingleRule {
+                    rule_name: "indentation".to_string(),
+                },
+            ),
+        ];
+        let summary = failed_files_summary(&mut failed_files);
+        let lines: Vec<&str> = summary.lines().collect();
+        assert!(
+            lines[0].starts_with("Error: ./a.html.twig: Suggestion collision inside the same rule")
+        );
+        assert_eq!(
+            lines[1],
+            "Error: file ./b.html.twig can't be written (permission denied)"
+        );
+    }
+}
@@ -1,18 +1,24 @@
 use std::collections::HashSet;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc};
 
 use codespan_reporting::term::termcolor::{BufferWriter, ColorChoice};
 
-use ludtwig_parser::syntax::untyped::SyntaxNode;
+use ludtwig_parser::syntax::untyped::{SyntaxNode, TextRange, TextSize};
 use ludtwig_parser::ParseError;
 
 use crate::check::rule::{CheckResult, CheckSuggestion, Rule};
-use crate::check::rules::get_file_active_rule_definitions;
-use crate::check::{get_rule_context_suggestions, produce_diagnostics, run_rules};
+use crate::check::rules::{
+    check_applied_fixes, get_file_active_rule_definitions, skip_expensive_rules, RuleRegistry,
+};
+use crate::check::{
+    get_rule_context_suggestions, produce_diagnostics, run_rules, run_rules_with_suppressions,
+};
+use crate::config::Config;
 use crate::error::FileProcessingError;
 use crate::output::ProcessingEvent;
-use crate::CliContext;
+use crate::{CliContext, CliSharedData};
 
 /// The context for a single file.
 #[derive(Debug)]
@@ -52,19 +58,88 @@ pub fn process_file(path: PathBuf, cli_context: CliContext) -> Result<(), FilePr
         }
     };
 
+    if let Some(rule_name) = &cli_context.data.preview_fix {
+        let fixed = preview_fix(
+            &path,
+            &file_content,
+            rule_name,
+            &cli_context.data.rule_registry,
+            cli_context.data.config.clone(),
+        )?;
+        println!("{}:\n{fixed}", path.to_string_lossy());
+        return Ok(());
+    }
+
     run_analysis(path, file_content, cli_context)
 }
 
+/// Apply only the suggestions of a single rule to the source code and return the fixed source code.
+/// This doesn't change any files, so it can be used to preview what a rule would do in isolation.
+pub fn preview_fix(
+    file_path: &Path,
+    source_code: &str,
+    rule_name: &str,
+    rule_registry: &RuleRegistry,
+    config: Config,
+) -> Result<String, FileProcessingError> {
+    let rule = rule_registry
+        .get(rule_name)
+        .map_err(FileProcessingError::Configuration)?;
+
+    // the receiver is kept alive, but this context doesn't produce any output
+    let (tx, _rx) = mpsc::channel();
+    let cli_context = CliContext {
+        output_tx: tx,
+        data: Arc::new(CliSharedData {
+            fix: true,
+            inspect: false,
+            explain_context: false,
+            preview_fix: None,
+            config,
+            rule_registry: rule_registry.clone(),
+            rule_definitions: vec![rule],
+        }),
+    };
+
+    let parse = ludtwig_parser::parse_for_twig_version(
+        source_code,
+        cli_context.data.config.general.twig_version,
+    );
+    let root = SyntaxNode::new_root(parse.green_node);
+    let file_rule_definitions =
+        get_file_active_rule_definitions(&root, &cli_context.data.rule_definitions);
+
+    let file_context = FileContext {
+        cli_context,
+        file_path: file_path.to_path_buf(),
+        source_code: source_code.to_owned(),
+        tree_root: root,
+        parse_errors: parse.errors,
+        file_rule_definitions,
+    };
+
+    let rule_result_context = run_rules(&file_context);
+    let (file_context, _, _, _) = iteratively_apply_suggestions(file_context, rule_result_context)?;
+
+    Ok(file_context.source_code)
+}
+
 fn run_analysis(
     path: PathBuf,
     original_file_content: String,
     cli_context: CliContext,
 ) -> Result<(), FileProcessingError> {
-    let parse = ludtwig_parser::parse(&original_file_content);
+    let parse = ludtwig_parser::parse_for_twig_version(
+        &original_file_content,
+        cli_context.data.config.general.twig_version,
+    );
     let root = SyntaxNode::new_root(parse.green_node);
 
-    let file_rule_definitions =
-        get_file_active_rule_definitions(&root, &cli_context.data.rule_definitions);
+    let file_rule_definitions = skip_expensive_rules(
+        &cli_context.data.config,
+        &original_file_content,
+        get_file_active_rule_definitions(&root, &cli_context.data.rule_definitions),
+    );
 
     let apply_suggestions = cli_context.data.fix;
     let file_context = FileContext {
@@ -77,11 +152,15 @@ fn run_analysis(
     };
 
     // run all the rules
-    let rule_result_context = run_rules(&file_context);
+    let (rule_result_context, suppressions) = run_rules_with_suppressions(&file_context);
+    for rule_name in suppressions.rule_names() {
+        file_context.send_processing_output(ProcessingEvent::Suppressed { rule_name });
+    }
 
     // apply suggestions if needed
     let (file_context, rule_result_context) = if apply_suggestions {
-        let (file_context, rule_result_context, dirty, iterations) =
+        let original_root = file_context.tree_root.clone();
+        let (file_context, mut rule_result_context, dirty, iterations) =
             match iteratively_apply_suggestions(file_context, rule_result_context) {
                 Ok(val) => val,
                 Err(e) => return Err(e),
@@ -96,10 +175,17 @@ fn run_analysis(
                     })
                 }
             };
-            println!(
-                "fixed {:?} in {} iterations",
-                &file_context.file_path, iterations
-            );
+            file_context.send_processing_output(ProcessingEvent::FileFixed {
+                path: file_context.file_path.clone(),
+                iterations,
+            });
+
+            // warn about fixes, which may change how the template is rendered
+            rule_result_context.extend(check_applied_fixes(
+                &file_context.file_rule_definitions,
+                &original_root,
+                &file_context.tree_root,
+            ));
         }
 
         (file_context, rule_result_context)
@@ -120,15 +206,27 @@ pub fn iteratively_apply_suggestions(
     file_context: FileContext,
     check_results: Vec<CheckResult>,
 ) -> Result<(FileContext, Vec<CheckResult>, bool, usize), FileProcessingError> {
-    let mut current_results = (file_context, check_results, false, 0);
+    iteratively_apply_suggestions_in_range(file_context, check_results, None)
+}
 
-    // try at maximum 10 parsing iterations
-    for i in 0..10 {
-        if i >= 9 {
-            return Err(FileProcessingError::MaxApplyIteration);
-        }
+/// Like [`iteratively_apply_suggestions`] but only suggestions which are completely inside
+/// the range are applied (if there is one). The range grows / shrinks with the applied suggestions.
+pub fn iteratively_apply_suggestions_in_range(
+    file_context: FileContext,
+    check_results: Vec<CheckResult>,
+    mut range: Option<TextRange>,
+) -> Result<(FileContext, Vec<CheckResult>, bool, usize), FileProcessingError> {
+    let max_passes = file_context.cli_context.data.config.general.max_fix_passes;
+    let mut original_source_code: Option<String> = None;
+    let mut current_results = (file_context, check_results, false, 0);
 
+    // apply the fixes, parse the result and run all rules again until no rule suggests a fix anymore.
+    // Suggestions that are still left after the last pass are returned with the check results.
+    while current_results.3 < max_passes {
         let mut suggestions = get_rule_context_suggestions(&current_results.1);
+        if let Some(range) = range {
+            suggestions.retain(|(_, sug)| range.contains_range(sug.syntax_range));
+        }
         if suggestions.is_empty() {
             break;
         }
@@ -151,7 +249,7 @@ pub fn iteratively_apply_suggestions(
                 overlapping_rules.insert(*rule_b);
             }
         }
-        let suggestions = suggestions
+        let suggestions: Vec<&CheckSuggestion> = suggestions
             .into_iter()
             .filter_map(|(rule, suggestion)| {
                 if overlapping_rules.contains(&rule) {
@@ -163,11 +261,39 @@ pub fn iteratively_apply_suggestions(
             .collect();
 
         // transform source code according to non overlapping suggestions
-        current_results.2 = true; // set dirty flag
-        let source_code = apply_suggestions_to_text(suggestions, current_results.0.source_code);
+        let original_source_code =
+            original_source_code.get_or_insert_with(|| current_results.0.source_code.clone());
+        let source_code = apply_suggestions_to_text(&suggestions, &current_results.0.source_code);
+
+        // suggestions which don't change anything would be suggested again in every pass
+        if source_code == current_results.0.source_code {
+            for result in &mut current_results.1 {
+                result.retain_suggestions(|sug| source_code[sug.syntax_range] != sug.replace_with);
+            }
+            break;
+        }
 
+        // only a changed file is dirty (the passes may also end with the original source code)
+        current_results.2 = source_code != *original_source_code;
+        if let Some(r) = range {
+            let inserted: TextSize = suggestions
+                .iter()
+                .map(|sug| TextSize::of(sug.replace_with.as_str()))
+                .sum();
+            let removed: TextSize = suggestions.iter().map(|sug| sug.syntax_range.len()).sum();
+            range = Some(TextRange::new(r.start(), r.end() + inserted - removed));
+        }
         // Parse the new source code again
-        let new_parse = ludtwig_parser::parse(&source_code);
+        let new_parse = ludtwig_parser::parse_for_twig_version(
+            &source_code,
+            current_results
+                .0
+                .cli_context
+                .data
+                .config
+                .general
+                .twig_version,
+        );
         let tree_root = SyntaxNode::new_root(new_parse.green_node);
 
         let file_context = FileContext {
@@ -190,11 +316,9 @@ pub fn iteratively_apply_suggestions(
     Ok(current_results)
 }
 
-fn apply_suggestions_to_text(
-    suggestions: Vec<&CheckSuggestion>,
-    mut source_code: String,
-) -> String {
-    suggestions.into_iter().rev().for_each(|suggestion| {
+fn apply_suggestions_to_text(suggestions: &[&CheckSuggestion], source_code: &str) -> String {
+    let mut source_code = source_code.to_owned();
+    suggestions.iter().rev().for_each(|suggestion| {
         let start: usize = suggestion.syntax_range.start().into();
         let end: usize = suggestion.syntax_range.end().into();
 